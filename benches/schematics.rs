@@ -0,0 +1,200 @@
+//! Benchmarks for loading (mixed and leafy fixtures), counting, materials, greedy meshing
+//! and model quads
+//!
+//! Run with `cargo bench`; every input is generated by `schem_tool::fixtures`.
+
+use std::collections::HashMap;
+use std::hint::black_box;
+use std::io::Write;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use schem_tool::export3d::{self, ExportOptions};
+use schem_tool::mc_models::{self, ModelElement, ResolvedModel};
+use schem_tool::schem::SchemWrapper;
+use schem_tool::{fixtures, recipes, Block, BlockState, Litematica, Schematic, UnifiedSchematic};
+
+/// A legacy schematic of stone, planks and glass at the noise fixture's positions
+fn legacy_bytes(schem: &UnifiedSchematic) -> Vec<u8> {
+    let id = |block: &Block| match block.name.as_str() {
+        "minecraft:air" => 0,
+        "minecraft:oak_planks" => 5,
+        "minecraft:glass" => 20,
+        _ => 1,
+    };
+    let legacy = Schematic {
+        width: schem.width as i16,
+        height: schem.height as i16,
+        length: schem.length as i16,
+        materials: Some("Alpha".to_string()),
+        blocks: fastnbt::ByteArray::new(schem.blocks.iter().map(id).collect()),
+        data: fastnbt::ByteArray::new(vec![0; schem.blocks.len()]),
+        add_blocks: None,
+        entities: Vec::new(),
+        tile_entities: Vec::new(),
+        we_origin_x: None,
+        we_origin_y: None,
+        we_origin_z: None,
+        we_offset_x: None,
+        we_offset_y: None,
+        we_offset_z: None,
+        schematica_mapping: None,
+    };
+    let nbt = fastnbt::to_bytes(&legacy).unwrap();
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(&nbt).unwrap();
+    encoder.finish().unwrap()
+}
+
+fn load(c: &mut Criterion) {
+    let schem = fixtures::noise((64, 64, 64), &fixtures::sample_blocks(), 1);
+    let files = [
+        ("litematic", Litematica::from_unified(&schem).to_bytes().unwrap()),
+        ("schem", SchemWrapper::from_unified(&schem).unwrap().to_bytes().unwrap()),
+        ("schematic", legacy_bytes(&schem)),
+    ];
+
+    let mut group = c.benchmark_group("load");
+    for (format, bytes) in &files {
+        group.bench_function(*format, |b| b.iter(|| UnifiedSchematic::from_bytes(black_box(bytes)).unwrap()));
+    }
+    group.finish();
+}
+
+/// Leaves at every distance, persistent or not, and logs on each axis: a tree
+/// farm's worth of blocks that all carry properties
+fn leafy_blocks() -> Vec<Block> {
+    let mut blocks = Vec::new();
+    for wood in ["oak", "birch", "spruce"] {
+        for distance in 1..=7 {
+            for persistent in ["true", "false"] {
+                let properties = [
+                    ("distance".to_string(), distance.to_string()),
+                    ("persistent".to_string(), persistent.to_string()),
+                ]
+                .into_iter()
+                .collect();
+                blocks.push(Block::with_state(format!("minecraft:{}_leaves", wood), BlockState { properties }));
+            }
+        }
+        for axis in ["x", "y", "z"] {
+            let properties = [("axis".to_string(), axis.to_string())].into_iter().collect();
+            blocks.push(Block::with_state(format!("minecraft:{}_log", wood), BlockState { properties }));
+        }
+    }
+    blocks
+}
+
+fn load_leafy(c: &mut Criterion) {
+    let schem = fixtures::noise((64, 64, 64), &leafy_blocks(), 4);
+    let files = [
+        ("litematic", Litematica::from_unified(&schem).to_bytes().unwrap()),
+        ("schem", SchemWrapper::from_unified(&schem).unwrap().to_bytes().unwrap()),
+    ];
+
+    let mut group = c.benchmark_group("load_leafy");
+    for (format, bytes) in &files {
+        group.bench_function(*format, |b| b.iter(|| UnifiedSchematic::from_bytes(black_box(bytes)).unwrap()));
+    }
+    group.finish();
+}
+
+fn counts(c: &mut Criterion) {
+    let schem = fixtures::noise((64, 64, 64), &fixtures::sample_blocks(), 2);
+    c.bench_function("block_counts", |b| b.iter(|| black_box(&schem).block_counts()));
+    c.bench_function("unique_blocks", |b| b.iter(|| black_box(&schem).unique_blocks().len()));
+}
+
+fn materials(c: &mut Criterion) {
+    // Shaped like a survival house: mostly full blocks, with crafted shapes and details
+    let counts: HashMap<String, usize> = [
+        ("stone_bricks", 4200), ("stone_brick_stairs", 380), ("stone_brick_slab", 240),
+        ("stone_brick_wall", 96), ("oak_planks", 2600), ("oak_stairs", 520), ("oak_slab", 310),
+        ("oak_fence", 140), ("oak_door", 12), ("oak_trapdoor", 48), ("spruce_log", 420),
+        ("stripped_spruce_log", 180), ("spruce_planks", 900), ("spruce_stairs", 260),
+        ("glass_pane", 360), ("white_stained_glass", 64), ("cobblestone", 1800),
+        ("cobblestone_wall", 120), ("polished_andesite", 700), ("andesite_slab", 90),
+        ("lantern", 40), ("torch", 80), ("bookshelf", 36), ("chest", 8), ("crafting_table", 2),
+        ("furnace", 4), ("white_wool", 60), ("red_carpet", 48), ("flower_pot", 16),
+        ("ladder", 30), ("iron_bars", 44), ("bricks", 380), ("brick_stairs", 60),
+    ]
+    .into_iter()
+    .map(|(name, count)| (format!("minecraft:{}", name), count))
+    .collect();
+
+    c.bench_function("calculate_materials", |b| {
+        b.iter(|| recipes::calculate_materials_with_options(black_box(&counts), true))
+    });
+}
+
+fn meshing(c: &mut Criterion) {
+    let ball = fixtures::sphere(32, &Block::new("minecraft:stone"));
+    let noise = fixtures::noise((64, 64, 64), &fixtures::sample_blocks(), 3);
+    let path = std::env::temp_dir().join(format!("schem-tool-bench-{}.obj", std::process::id()));
+    let options = ExportOptions::new();
+
+    let mut group = c.benchmark_group("obj");
+    group.sample_size(10);
+    group.bench_function("greedy_sphere_64", |b| {
+        b.iter(|| export3d::export_obj_greedy(black_box(&ball), &path, None, &options).unwrap())
+    });
+    group.bench_function("greedy_noise_64", |b| {
+        b.iter(|| export3d::export_obj_greedy(black_box(&noise), &path, None, &options).unwrap())
+    });
+    group.bench_function("hollow_noise_64", |b| {
+        b.iter(|| export3d::export_obj(black_box(&noise), &path, true, true, &options).unwrap())
+    });
+    group.finish();
+
+    let _ = std::fs::remove_file(&path);
+    let _ = std::fs::remove_file(path.with_extension("mtl"));
+}
+
+/// Models shaped like vanilla ones: a cube, a bottom slab, stairs and a rotated cross
+fn models() -> Vec<(&'static str, ResolvedModel)> {
+    let faces = |faces: &[&str]| {
+        faces.iter()
+            .map(|face| format!(r##""{}": {{"texture": "#all", "cullface": "{}"}}"##, face, face))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let all = faces(&["down", "up", "north", "south", "west", "east"]);
+    let element = |from: &str, to: &str, extra: &str| {
+        format!(r#"{{"from": {}, "to": {}, {} "faces": {{{}}}}}"#, from, to, extra, all)
+    };
+    let model = |elements: Vec<String>| {
+        let elements: Vec<ModelElement> = serde_json::from_str(&format!("[{}]", elements.join(","))).unwrap();
+        ResolvedModel {
+            elements,
+            textures: HashMap::from([("all".to_string(), "block/stone".to_string())]),
+            ambient_occlusion: true,
+        }
+    };
+    let rotated = r#""rotation": {"origin": [8, 8, 8], "axis": "y", "angle": 45, "rescale": true},"#;
+
+    vec![
+        ("cube", model(vec![element("[0, 0, 0]", "[16, 16, 16]", "")])),
+        ("slab", model(vec![element("[0, 0, 0]", "[16, 8, 16]", "")])),
+        ("stairs", model(vec![element("[0, 0, 0]", "[16, 8, 16]", ""), element("[8, 8, 0]", "[16, 16, 16]", "")])),
+        ("cross", model(vec![element("[0.8, 0, 8]", "[15.2, 16, 8]", rotated), element("[8, 0, 0.8]", "[8, 16, 15.2]", rotated)])),
+    ]
+}
+
+fn model_quads(c: &mut Criterion) {
+    // x and y rotations as blockstate variants use them
+    let rotations: Vec<(i32, i32)> = [0, 90, 180, 270].into_iter().flat_map(|y| [(0, y), (90, y)]).collect();
+
+    let mut group = c.benchmark_group("model_quads");
+    for (name, model) in models() {
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                rotations.iter()
+                    .map(|&(x_rot, y_rot)| mc_models::generate_model_quads(black_box(&model), x_rot, y_rot, 1.0, 2.0, 3.0).len())
+                    .sum::<usize>()
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, load, load_leafy, counts, materials, meshing, model_quads);
+criterion_main!(benches);
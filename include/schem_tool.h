@@ -0,0 +1,117 @@
+#ifndef SCHEM_TOOL_H
+#define SCHEM_TOOL_H
+
+/* Generated by cbindgen from src/ffi.rs; do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Result codes returned by the FFI functions
+ */
+typedef enum SchemStatus {
+  SCHEM_STATUS_OK = 0,
+  /**
+   * A required pointer argument was NULL
+   */
+  SCHEM_STATUS_NULL_POINTER = 1,
+  /**
+   * The bytes are not a supported schematic
+   */
+  SCHEM_STATUS_PARSE_ERROR = 2,
+  /**
+   * JSON encoding of the result failed
+   */
+  SCHEM_STATUS_ENCODE_ERROR = 3,
+  /**
+   * Rust code panicked; the message is in `schem_last_error_message`
+   */
+  SCHEM_STATUS_PANIC = 4,
+} SchemStatus;
+
+/**
+ * Opaque handle to a loaded schematic
+ */
+typedef struct SchemHandle SchemHandle;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Parse schematic file contents (gzipped or raw NBT)
+ *
+ * Returns NULL on failure.
+ *
+ * # Safety
+ * `bytes` must point to `len` readable bytes.
+ */
+struct SchemHandle *schem_load(const uint8_t *bytes, size_t len);
+
+/**
+ * Write the schematic's width, height and length
+ *
+ * # Safety
+ * `handle` must come from [`schem_load`]; the out pointers must be writable.
+ */
+enum SchemStatus schem_dimensions(const struct SchemHandle *handle,
+                                  uint32_t *width,
+                                  uint32_t *height,
+                                  uint32_t *length);
+
+/**
+ * Block counts as a JSON object `{ "minecraft:stone": 42, ... }`
+ *
+ * Returns NULL on failure. Free the result with [`schem_string_free`].
+ *
+ * # Safety
+ * `handle` must come from [`schem_load`].
+ */
+char *schem_block_counts_json(const struct SchemHandle *handle);
+
+/**
+ * Raw materials as a JSON object `{ "cobblestone": 12.0, ... }`
+ *
+ * Returns NULL on failure. Free the result with [`schem_string_free`].
+ *
+ * # Safety
+ * `handle` must come from [`schem_load`].
+ */
+char *schem_materials_json(const struct SchemHandle *handle, bool stonecutter);
+
+/**
+ * Status of the last failed call on this thread ([`SchemStatus::Ok`] after a success)
+ */
+enum SchemStatus schem_last_error_code(void);
+
+/**
+ * Message of the last failed call on this thread, or NULL if it succeeded
+ *
+ * Free the result with [`schem_string_free`].
+ */
+char *schem_last_error_message(void);
+
+/**
+ * Release a string returned by this library; NULL is ignored
+ *
+ * # Safety
+ * `s` must come from this library and not have been freed already.
+ */
+void schem_string_free(char *s);
+
+/**
+ * Release a handle returned by [`schem_load`]; NULL is ignored
+ *
+ * # Safety
+ * `handle` must come from [`schem_load`] and not have been freed already.
+ */
+void schem_free(struct SchemHandle *handle);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* SCHEM_TOOL_H */
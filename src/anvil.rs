@@ -0,0 +1,648 @@
+//! Reading blocks from a world's region files (Anvil format)
+//!
+//! A region file `r.<x>.<z>.mca` holds 32x32 chunks behind a 4 KiB table of
+//! sector offsets. Each chunk is compressed NBT with 16-block-tall sections,
+//! each storing a block state palette and packed palette indices. Chunks saved
+//! by 1.18 and later keep their sections at the root and reach down to y=-64;
+//! 1.13 to 1.17 nest them under `Level`. Older numeric block ids aren't
+//! supported. Since 1.17 entities live in the same layout under `entities/`
+//! next to `region/`.
+
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap};
+use std::io::{ErrorKind, Read};
+use std::path::{Path, PathBuf};
+use flate2::read::{GzDecoder, ZlibDecoder};
+use serde::Deserialize;
+use crate::litematica::{calculate_bits_per_block, decode_packed_array, LitematicaBlockState};
+use crate::{
+    checked_volume, Block, BlockClass, BlockEntity, BlockState, Entity, Metadata,
+    SchemError, SchematicFormat, UnifiedSchematic,
+};
+
+const SECTOR: usize = 4096;
+const SECTION_VOLUME: usize = 16 * 16 * 16;
+/// First data version whose packed arrays don't let entries span two longs (20w17a)
+const NO_SPANNING_VERSION: i32 = 2529;
+
+#[derive(Deserialize)]
+struct ChunkNbt {
+    #[serde(rename = "DataVersion", default)]
+    data_version: Option<i32>,
+    #[serde(rename = "Status", default)]
+    status: Option<String>,
+    #[serde(default)]
+    sections: Vec<SectionNbt>,
+    #[serde(default)]
+    block_entities: Vec<HashMap<String, fastnbt::Value>>,
+    #[serde(rename = "Level", default)]
+    level: Option<LevelNbt>,
+}
+
+/// Chunk in an `entities/` region file (1.17+)
+#[derive(Deserialize)]
+struct EntityChunkNbt {
+    #[serde(rename = "Entities", default)]
+    entities: Vec<HashMap<String, fastnbt::Value>>,
+}
+
+/// Pre-1.18 chunk contents
+#[derive(Deserialize)]
+struct LevelNbt {
+    #[serde(rename = "Status", default)]
+    status: Option<String>,
+    #[serde(rename = "Sections", default)]
+    sections: Vec<LegacySectionNbt>,
+    #[serde(rename = "TileEntities", default)]
+    tile_entities: Vec<HashMap<String, fastnbt::Value>>,
+    #[serde(rename = "Entities", default)]
+    entities: Vec<HashMap<String, fastnbt::Value>>,
+}
+
+#[derive(Deserialize)]
+struct SectionNbt {
+    #[serde(rename = "Y")]
+    y: i8,
+    #[serde(default)]
+    block_states: Option<BlockStatesNbt>,
+}
+
+#[derive(Deserialize)]
+struct BlockStatesNbt {
+    #[serde(default)]
+    palette: Vec<LitematicaBlockState>,
+    #[serde(default)]
+    data: Option<fastnbt::LongArray>,
+}
+
+#[derive(Deserialize)]
+struct LegacySectionNbt {
+    #[serde(rename = "Y")]
+    y: i8,
+    #[serde(rename = "Palette", default)]
+    palette: Vec<LitematicaBlockState>,
+    #[serde(rename = "BlockStates", default)]
+    block_states: Option<fastnbt::LongArray>,
+}
+
+/// Whether a chunk's generation status means its blocks are final
+fn is_generated(status: &str) -> bool {
+    // "full"/"minecraft:full" since 1.14, "fullchunk" and "postprocessed" in 1.13
+    status.ends_with("full") || status == "fullchunk" || status == "postprocessed"
+}
+
+/// Block entity with world coordinates; the rest of the compound is kept as is
+fn to_block_entity(mut nbt: HashMap<String, fastnbt::Value>) -> Option<BlockEntity> {
+    let Some(fastnbt::Value::String(id)) = nbt.remove("id") else { return None };
+    let mut coord = |key: &str| match nbt.remove(key) {
+        Some(fastnbt::Value::Int(v)) => Some(v),
+        _ => None,
+    };
+    let pos = (coord("x")?, coord("y")?, coord("z")?);
+    nbt.remove("keepPacked");
+    let data = nbt.iter().map(|(key, value)| (key.clone(), format!("{:?}", value))).collect();
+    Some(BlockEntity { id, pos, data, nbt })
+}
+
+/// Entity with world coordinates; the rest of the compound is kept as is
+fn to_entity(mut nbt: HashMap<String, fastnbt::Value>) -> Option<Entity> {
+    let Some(fastnbt::Value::String(id)) = nbt.remove("id") else { return None };
+    let Some(fastnbt::Value::List(pos)) = nbt.remove("Pos") else { return None };
+    let pos = match pos[..] {
+        [fastnbt::Value::Double(x), fastnbt::Value::Double(y), fastnbt::Value::Double(z)] => (x, y, z),
+        _ => return None,
+    };
+    let data = nbt.iter().map(|(key, value)| (key.clone(), format!("{:?}", value))).collect();
+    Some(Entity { id, pos, data, nbt })
+}
+
+/// Unpack section indices stored without spanning longs (1.16+)
+fn unpack_section(data: &[i64], bits: usize) -> Vec<usize> {
+    let per_long = 64 / bits;
+    let mask = (1u64 << bits) - 1;
+    (0..SECTION_VOLUME)
+        .map(|i| data.get(i / per_long).map_or(0, |&long| ((long as u64 >> ((i % per_long) * bits)) & mask) as usize))
+        .collect()
+}
+
+/// A 16x16x16 section: palette and YZX-ordered indices (empty if the palette has one entry)
+#[derive(Debug, Clone)]
+struct Section {
+    palette: Vec<Block>,
+    indices: Vec<u16>,
+}
+
+impl Section {
+    fn decode(palette: &[LitematicaBlockState], data: Option<&fastnbt::LongArray>, spanning: bool) -> Self {
+        let palette: Vec<Block> = palette.iter().map(|bs| {
+            Block::with_state(&bs.name, BlockState { properties: bs.properties.clone().unwrap_or_default().into() })
+        }).collect();
+        let indices = match data {
+            Some(data) if palette.len() > 1 => {
+                let bits = calculate_bits_per_block(palette.len()).max(4);
+                let indices = if spanning { decode_packed_array(data, bits, SECTION_VOLUME) } else { unpack_section(data, bits) };
+                indices.into_iter().map(|i| i as u16).collect()
+            }
+            _ => Vec::new(),
+        };
+        Section { palette, indices }
+    }
+
+    /// Block at a YZX index, `None` for an out-of-range palette index
+    fn block(&self, index: usize) -> Option<&Block> {
+        let id = self.indices.get(index).map_or(0, |&i| i as usize);
+        self.palette.get(id)
+    }
+}
+
+/// The block sections of one generated chunk
+#[derive(Debug, Clone, Default)]
+pub struct Chunk {
+    pub data_version: Option<i32>,
+    /// Block entities at world coordinates
+    pub block_entities: Vec<BlockEntity>,
+    /// Entities stored in the chunk itself (before 1.17), at world coordinates
+    pub entities: Vec<Entity>,
+    sections: BTreeMap<i32, Section>,
+}
+
+impl Chunk {
+    /// Parse uncompressed chunk NBT; `None` for chunks that aren't fully generated
+    pub fn from_nbt(bytes: &[u8]) -> Result<Option<Self>, SchemError> {
+        let nbt: ChunkNbt = fastnbt::from_bytes(bytes)?;
+        Ok(Self::from_parsed(nbt))
+    }
+
+    fn from_parsed(nbt: ChunkNbt) -> Option<Self> {
+        let status = nbt.status.as_deref().or(nbt.level.as_ref().and_then(|level| level.status.as_deref()));
+        if status.is_some_and(|status| !is_generated(status)) {
+            return None;
+        }
+
+        let mut sections = BTreeMap::new();
+        let mut block_entities: Vec<_> = nbt.block_entities.into_iter().filter_map(to_block_entity).collect();
+        let mut entities = Vec::new();
+        for section in &nbt.sections {
+            if let Some(states) = &section.block_states {
+                sections.insert(section.y as i32, Section::decode(&states.palette, states.data.as_ref(), false));
+            }
+        }
+        if let Some(level) = nbt.level {
+            let spanning = nbt.data_version.is_some_and(|v| v < NO_SPANNING_VERSION);
+            for section in level.sections.iter().filter(|s| !s.palette.is_empty()) {
+                sections.insert(section.y as i32, Section::decode(&section.palette, section.block_states.as_ref(), spanning));
+            }
+            block_entities.extend(level.tile_entities.into_iter().filter_map(to_block_entity));
+            entities.extend(level.entities.into_iter().filter_map(to_entity));
+        }
+        Some(Chunk { data_version: nbt.data_version, block_entities, entities, sections })
+    }
+
+    /// Block at chunk-local x/z and world y, `None` where the chunk has no section
+    /// (which the game treats as air)
+    pub fn block(&self, x: u32, y: i32, z: u32) -> Option<&Block> {
+        let section = self.sections.get(&y.div_euclid(16))?;
+        section.block((y.rem_euclid(16) as usize * 16 + (z & 15) as usize) * 16 + (x & 15) as usize)
+    }
+
+    /// Lowest and highest block y covered by sections
+    pub fn y_range(&self) -> Option<(i32, i32)> {
+        let min = *self.sections.keys().next()?;
+        let max = *self.sections.keys().next_back()?;
+        Some((min * 16, max * 16 + 15))
+    }
+}
+
+/// Decompressed NBT of a chunk in a region file, `None` if it was never saved
+fn read_chunk(region: &[u8], dir: &Path, cx: i32, cz: i32) -> Result<Option<Vec<u8>>, String> {
+    let entry = ((cx & 31) + (cz & 31) * 32) as usize * 4;
+    let Some(location) = region.get(entry..entry + 4) else { return Ok(None) };
+    let offset = u32::from_be_bytes([0, location[0], location[1], location[2]]) as usize * SECTOR;
+    if offset == 0 {
+        return Ok(None);
+    }
+
+    let header = region.get(offset..offset + 5)
+        .ok_or_else(|| format!("chunk {}, {} points past the end of the file", cx, cz))?;
+    let length = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as usize;
+    let compression = header[4];
+
+    // Oversized chunks are stored next to the region file
+    let external;
+    let data = if compression & 128 != 0 {
+        let path = dir.join(format!("c.{}.{}.mcc", cx, cz));
+        external = std::fs::read(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        &external[..]
+    } else {
+        region.get(offset + 5..offset + 4 + length)
+            .ok_or_else(|| format!("chunk {}, {} is truncated", cx, cz))?
+    };
+
+    let mut nbt = Vec::new();
+    let result = match compression & 127 {
+        1 => GzDecoder::new(data).read_to_end(&mut nbt),
+        2 => ZlibDecoder::new(data).read_to_end(&mut nbt),
+        3 => {
+            nbt.extend_from_slice(data);
+            Ok(data.len())
+        }
+        4 => return Err(format!("chunk {}, {} is LZ4 compressed, which isn't supported", cx, cz)),
+        other => return Err(format!("chunk {}, {} has unknown compression type {}", cx, cz, other)),
+    };
+    result.map_err(|e| format!("chunk {}, {}: {}", cx, cz, e))?;
+    Ok(Some(nbt))
+}
+
+/// The region files of one dimension, with regions and chunks loaded on first use
+#[derive(Debug)]
+pub struct World {
+    region_dir: PathBuf,
+    entities_dir: PathBuf,
+    regions: HashMap<(i32, i32), Option<Vec<u8>>>,
+    entity_regions: HashMap<(i32, i32), Option<Vec<u8>>>,
+    chunks: HashMap<(i32, i32), Option<Chunk>>,
+}
+
+impl World {
+    /// Open a world folder (using its `region` folder) or a region folder
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, SchemError> {
+        let path = path.as_ref();
+        let region_dir = if path.join("region").is_dir() { path.join("region") } else { path.to_path_buf() };
+        if !region_dir.is_dir() {
+            return Err(SchemError::region(&region_dir, "not a directory"));
+        }
+        let entities_dir = region_dir.parent().unwrap_or(&region_dir).join("entities");
+        Ok(World {
+            region_dir,
+            entities_dir,
+            regions: HashMap::new(),
+            entity_regions: HashMap::new(),
+            chunks: HashMap::new(),
+        })
+    }
+
+    pub fn region_dir(&self) -> &Path {
+        &self.region_dir
+    }
+
+    /// The chunk at chunk coordinates, `None` if it isn't generated
+    pub fn chunk(&mut self, cx: i32, cz: i32) -> Result<Option<&Chunk>, SchemError> {
+        let chunk = match self.chunks.entry((cx, cz)) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let nbt = read_region_chunk(&self.region_dir, &mut self.regions, cx, cz)?;
+                entry.insert(match nbt {
+                    Some((nbt, path)) => Chunk::from_nbt(&nbt).map_err(|e| chunk_error(&path, cx, cz, e))?,
+                    None => None,
+                })
+            }
+        };
+        Ok(chunk.as_ref())
+    }
+
+    /// Entities in a chunk at world coordinates, from the `entities` folder (1.17+) or
+    /// the chunk itself
+    pub fn entities(&mut self, cx: i32, cz: i32) -> Result<Vec<Entity>, SchemError> {
+        let mut entities = self.chunk(cx, cz)?.map(|chunk| chunk.entities.clone()).unwrap_or_default();
+        if let Some((nbt, path)) = read_region_chunk(&self.entities_dir, &mut self.entity_regions, cx, cz)? {
+            let chunk: EntityChunkNbt = fastnbt::from_bytes(&nbt).map_err(|e| chunk_error(&path, cx, cz, e))?;
+            entities.extend(chunk.entities.into_iter().filter_map(to_entity));
+        }
+        Ok(entities)
+    }
+
+    /// Block at world coordinates, `None` if its chunk isn't generated
+    pub fn block(&mut self, x: i32, y: i32, z: i32) -> Result<Option<Block>, SchemError> {
+        let chunk = self.chunk(x.div_euclid(16), z.div_euclid(16))?;
+        Ok(chunk.map(|chunk| chunk.block(x.rem_euclid(16) as u32, y, z.rem_euclid(16) as u32).cloned().unwrap_or_else(Block::air)))
+    }
+}
+
+fn chunk_error(path: &Path, cx: i32, cz: i32, error: impl std::fmt::Display) -> SchemError {
+    SchemError::region(path, format!("chunk {}, {}: {}", cx, cz, error))
+}
+
+/// Decompressed NBT of a chunk and the region file it's in, loading the file into
+/// `regions` on first use
+fn read_region_chunk(
+    dir: &Path,
+    regions: &mut HashMap<(i32, i32), Option<Vec<u8>>>,
+    cx: i32,
+    cz: i32,
+) -> Result<Option<(Vec<u8>, PathBuf)>, SchemError> {
+    let (rx, rz) = (cx >> 5, cz >> 5);
+    let path = dir.join(format!("r.{}.{}.mca", rx, rz));
+    let region = match regions.entry((rx, rz)) {
+        Entry::Occupied(entry) => entry.into_mut(),
+        Entry::Vacant(entry) => entry.insert(match std::fs::read(&path) {
+            Ok(bytes) => Some(bytes),
+            Err(e) if e.kind() == ErrorKind::NotFound => None,
+            Err(e) => return Err(SchemError::region(&path, e)),
+        }),
+    };
+    let Some(region) = region else { return Ok(None) };
+    let nbt = read_chunk(region, dir, cx, cz).map_err(|e| SchemError::region(&path, e))?;
+    Ok(nbt.map(|nbt| (nbt, path)))
+}
+
+/// A cuboid read out of a world
+#[derive(Debug, Clone)]
+pub struct Extraction {
+    pub schematic: UnifiedSchematic,
+    /// Chunk sections in the cuboid that were missing or not generated, left as air
+    pub missing_sections: usize,
+}
+
+/// Read the box between two corners (inclusive, world coordinates) into a schematic
+/// whose offset is the minimum corner
+///
+/// Block entities and entities inside the box come along at local coordinates.
+pub fn extract(world: &mut World, a: (i32, i32, i32), b: (i32, i32, i32)) -> Result<Extraction, SchemError> {
+    let min = (a.0.min(b.0), a.1.min(b.1), a.2.min(b.2));
+    let max = (a.0.max(b.0), a.1.max(b.1), a.2.max(b.2));
+    let size = |lo: i32, hi: i32| u32::try_from(hi as i64 - lo as i64 + 1).unwrap_or(u32::MAX);
+    let (width, height, length) = (size(min.0, max.0), size(min.1, max.1), size(min.2, max.2));
+    let mut blocks = vec![Block::air(); checked_volume(width, height, length)?];
+    let index = |x: i32, y: i32, z: i32| {
+        ((y - min.1) as usize * length as usize + (z - min.2) as usize) * width as usize + (x - min.0) as usize
+    };
+    let inside = |(x, y, z): (f64, f64, f64)| {
+        (min.0 as f64..max.0 as f64 + 1.0).contains(&x)
+            && (min.1 as f64..max.1 as f64 + 1.0).contains(&y)
+            && (min.2 as f64..max.2 as f64 + 1.0).contains(&z)
+    };
+
+    let mut block_entities = Vec::new();
+    let mut entities = Vec::new();
+    let mut missing_sections = 0;
+    let mut data_version = None;
+    let section_ys = min.1.div_euclid(16)..=max.1.div_euclid(16);
+
+    for cz in min.2.div_euclid(16)..=max.2.div_euclid(16) {
+        for cx in min.0.div_euclid(16)..=max.0.div_euclid(16) {
+            let Some(chunk) = world.chunk(cx, cz)? else {
+                missing_sections += section_ys.clone().count();
+                continue;
+            };
+            data_version = data_version.or(chunk.data_version);
+            // Sections beyond the chunk's height range are outside the world, not missing
+            if let Some((bottom, top)) = chunk.y_range() {
+                missing_sections += section_ys.clone()
+                    .filter(|sy| (bottom..=top).contains(&(sy * 16)) && !chunk.sections.contains_key(sy))
+                    .count();
+            }
+
+            let (x0, x1) = (min.0.max(cx * 16), max.0.min(cx * 16 + 15));
+            let (z0, z1) = (min.2.max(cz * 16), max.2.min(cz * 16 + 15));
+            for y in min.1..=max.1 {
+                for z in z0..=z1 {
+                    for x in x0..=x1 {
+                        if let Some(block) = chunk.block(x.rem_euclid(16) as u32, y, z.rem_euclid(16) as u32) {
+                            blocks[index(x, y, z)] = block.clone();
+                        }
+                    }
+                }
+            }
+            for be in &chunk.block_entities {
+                let (x, y, z) = be.pos;
+                if inside((x as f64, y as f64, z as f64)) {
+                    block_entities.push(BlockEntity { pos: (x - min.0, y - min.1, z - min.2), ..be.clone() });
+                }
+            }
+
+            for entity in world.entities(cx, cz)? {
+                if inside(entity.pos) {
+                    let (x, y, z) = entity.pos;
+                    entities.push(Entity { pos: (x - min.0 as f64, y - min.1 as f64, z - min.2 as f64), ..entity });
+                }
+            }
+        }
+    }
+
+    let schematic = UnifiedSchematic {
+        format: SchematicFormat::SpongeV3,
+        width,
+        height,
+        length,
+        offset: min,
+        blocks,
+        block_entities,
+        entities,
+        metadata: Metadata { data_version, offset: Some(min), ..Default::default() },
+        load_report: Default::default(),
+    };
+    Ok(Extraction { schematic, missing_sections })
+}
+
+/// How much of a schematic is built in a world
+#[derive(Debug, Clone, Default)]
+pub struct BuildProgress {
+    /// Blocks the schematic places (air and technical blocks don't count)
+    pub total: usize,
+    /// Positions holding the right block, possibly in a different state
+    pub placed: usize,
+    /// Of `placed`, positions whose block state differs (facing, half, ...)
+    pub wrong_state: usize,
+    /// Positions holding a different block
+    pub wrong_block: usize,
+    /// Positions in chunks that aren't generated
+    pub unloaded: usize,
+    /// Blocks still to place (missing or wrong) by block id
+    pub remaining: BTreeMap<String, usize>,
+    /// Schematic-local positions of the blocks still to place
+    pub missing: Vec<(u32, u32, u32)>,
+    /// Data version of the compared chunks
+    pub data_version: Option<i32>,
+}
+
+impl BuildProgress {
+    pub fn percent(&self) -> f64 {
+        if self.total == 0 {
+            100.0
+        } else {
+            self.placed as f64 * 100.0 / self.total as f64
+        }
+    }
+
+    /// Copy of `schem` with only the blocks still to place
+    pub fn remaining_schematic(&self, schem: &UnifiedSchematic) -> UnifiedSchematic {
+        let mut keep = vec![false; schem.blocks.len()];
+        let (w, l) = (schem.width as usize, schem.length as usize);
+        let index = |(x, y, z): (u32, u32, u32)| (y as usize * l + z as usize) * w + x as usize;
+        for &pos in &self.missing {
+            keep[index(pos)] = true;
+        }
+
+        let mut remaining = schem.clone();
+        for (block, &keep) in remaining.blocks.iter_mut().zip(&keep) {
+            if !keep {
+                *block = Block::air();
+            }
+        }
+        remaining.block_entities.retain(|be| match (u32::try_from(be.pos.0), u32::try_from(be.pos.1), u32::try_from(be.pos.2)) {
+            (Ok(x), Ok(y), Ok(z)) => schem.get_block(x, y, z).is_some() && keep[index((x, y, z))],
+            _ => false,
+        });
+        remaining.entities.clear();
+        if remaining.metadata.data_version.is_none() {
+            remaining.metadata.data_version = self.data_version;
+        }
+        remaining
+    }
+}
+
+/// Compare `schem`, with its (0, 0, 0) placed at world position `origin`, against the world
+pub fn compare(schem: &UnifiedSchematic, world: &mut World, origin: (i32, i32, i32)) -> Result<BuildProgress, SchemError> {
+    let mut progress = BuildProgress::default();
+    for (pos, expected) in schem.iter_blocks() {
+        if matches!(expected.class(), BlockClass::Air | BlockClass::Technical) {
+            continue;
+        }
+        progress.total += 1;
+
+        let world_pos = (
+            i32::try_from(origin.0 as i64 + pos.0 as i64),
+            i32::try_from(origin.1 as i64 + pos.1 as i64),
+            i32::try_from(origin.2 as i64 + pos.2 as i64),
+        );
+        let (Ok(x), Ok(y), Ok(z)) = world_pos else {
+            progress.unloaded += 1;
+            continue;
+        };
+        let Some(chunk) = world.chunk(x.div_euclid(16), z.div_euclid(16))? else {
+            progress.unloaded += 1;
+            continue;
+        };
+        progress.data_version = progress.data_version.or(chunk.data_version);
+
+        let actual = chunk.block(x.rem_euclid(16) as u32, y, z.rem_euclid(16) as u32);
+        match actual {
+            Some(actual) if actual.name == expected.name => {
+                progress.placed += 1;
+                if actual.state != expected.state {
+                    progress.wrong_state += 1;
+                }
+                continue;
+            }
+            Some(actual) if !actual.is_air() => progress.wrong_block += 1,
+            _ => {}
+        }
+        *progress.remaining.entry(expected.name.clone()).or_default() += 1;
+        progress.missing.push(pos);
+    }
+    Ok(progress)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::SchematicBuilder;
+
+    fn palette(names: &[&str]) -> Vec<LitematicaBlockState> {
+        names.iter().map(|name| LitematicaBlockState { name: name.to_string(), properties: None }).collect()
+    }
+
+    /// 1.18 chunk at chunk (-1, 0) with a section at y=-64..-48 holding stone at local
+    /// (1, -64, 0) and (0, -63, 0), a chest block entity and an all-dirt section at y=0..16
+    fn chunk_nbt() -> ChunkNbt {
+        // 4 bits per entry, 16 entries per long: index 1 is x=1, index 256 is y+1
+        let mut data = vec![0i64; 256];
+        data[0] = 1 << 4;
+        data[16] = 1;
+        ChunkNbt {
+            data_version: Some(3953),
+            status: Some("minecraft:full".to_string()),
+            sections: vec![
+                SectionNbt {
+                    y: -4,
+                    block_states: Some(BlockStatesNbt {
+                        palette: palette(&["minecraft:air", "minecraft:stone"]),
+                        data: Some(fastnbt::LongArray::new(data)),
+                    }),
+                },
+                SectionNbt {
+                    y: 0,
+                    block_states: Some(BlockStatesNbt { palette: palette(&["minecraft:dirt"]), data: None }),
+                },
+            ],
+            block_entities: vec![HashMap::from([
+                ("id".to_string(), fastnbt::Value::String("minecraft:chest".to_string())),
+                ("x".to_string(), fastnbt::Value::Int(-15)),
+                ("y".to_string(), fastnbt::Value::Int(-64)),
+                ("z".to_string(), fastnbt::Value::Int(0)),
+                ("Items".to_string(), fastnbt::Value::List(Vec::new())),
+            ])],
+            level: None,
+        }
+    }
+
+    /// World with the test chunk at chunk (-1, 0) and an ungenerated chunk east of it
+    fn world() -> World {
+        World {
+            region_dir: PathBuf::new(),
+            entities_dir: PathBuf::from("no-entities"),
+            regions: HashMap::new(),
+            entity_regions: HashMap::new(),
+            chunks: HashMap::from([((-1, 0), Chunk::from_parsed(chunk_nbt())), ((0, 0), None)]),
+        }
+    }
+
+    #[test]
+    fn test_chunk_sections_below_zero() {
+        let chunk = Chunk::from_parsed(chunk_nbt()).unwrap();
+        assert_eq!(chunk.block(1, -64, 0).unwrap().name, "minecraft:stone");
+        assert_eq!(chunk.block(0, -63, 0).unwrap().name, "minecraft:stone");
+        assert!(chunk.block(0, -64, 0).unwrap().is_air());
+        assert_eq!(chunk.block(15, 15, 15).unwrap().name, "minecraft:dirt");
+        assert!(chunk.block(0, 16, 0).is_none());
+        assert_eq!(chunk.y_range(), Some((-64, 15)));
+
+        let mut proto = chunk_nbt();
+        proto.status = Some("minecraft:features".to_string());
+        assert!(Chunk::from_parsed(proto).is_none());
+    }
+
+    #[test]
+    fn test_compare_against_world() {
+        let mut world = world();
+
+        // 3x2x1 at x=-16..-14, y=-64..-63: a row of stone, one more stone on top and a barrier
+        let stone = Block::new("minecraft:stone");
+        let mut builder = SchematicBuilder::new(3, 2, 1);
+        builder.fill((0, 0, 0), (2, 0, 0), stone.clone()).unwrap();
+        builder.set_block(0, 1, 0, stone).unwrap();
+        builder.set_block(2, 1, 0, Block::new("minecraft:barrier")).unwrap();
+        let schem = builder.build();
+
+        let progress = compare(&schem, &mut world, (-16, -64, 0)).unwrap();
+        assert_eq!((progress.total, progress.placed, progress.wrong_block, progress.unloaded), (4, 2, 0, 0));
+        assert_eq!(progress.percent(), 50.0);
+        assert_eq!(progress.missing, vec![(0, 0, 0), (2, 0, 0)]);
+        assert_eq!(progress.remaining.get("minecraft:stone"), Some(&2));
+
+        let remaining = progress.remaining_schematic(&schem);
+        assert_eq!(remaining.solid_blocks(), 2);
+        assert!(remaining.get_block(1, 0, 0).unwrap().is_air());
+
+        // Shifted so the last column falls in the ungenerated chunk at x >= 0
+        let progress = compare(&schem, &mut world, (-2, -64, 0)).unwrap();
+        assert_eq!((progress.placed, progress.unloaded, progress.missing.len()), (0, 1, 3));
+    }
+
+    #[test]
+    fn test_extract_cuboid() {
+        let mut world = world();
+        // Corners in either order; the box reaches one block into the ungenerated chunk
+        let extraction = extract(&mut world, (0, -63, 1), (-15, -64, 0)).unwrap();
+        let schem = &extraction.schematic;
+        assert_eq!((schem.width, schem.height, schem.length), (16, 2, 2));
+        assert_eq!(schem.offset, (-15, -64, 0));
+        assert_eq!(extraction.missing_sections, 1);
+
+        assert_eq!(schem.get_block(0, 0, 0).unwrap().name, "minecraft:stone");
+        assert_eq!(schem.solid_blocks(), 1);
+        assert_eq!(schem.block_entities.len(), 1);
+        assert_eq!(schem.block_entities[0].pos, (0, 0, 0));
+        assert!(schem.block_entities[0].data.contains_key("Items"));
+    }
+}
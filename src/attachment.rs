@@ -0,0 +1,75 @@
+//! Which blocks hang on a neighbor, and which neighbor
+//!
+//! Torches, buttons, rails, carpets, doors and the like pop off when the block
+//! they're attached to is missing. The build order places them after their
+//! support, and the paste check looks for ones without any.
+
+use crate::Block;
+use crate::direction::Axis;
+
+/// Where a block needs its supporting block
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Attachment {
+    Below,
+    Above,
+    /// The block behind it, i.e. opposite the direction it faces
+    Behind,
+}
+
+/// Supporting side for blocks that can't float, from their name and state
+pub fn attachment(block: &Block) -> Option<Attachment> {
+    let name = block.display_name();
+    let prop = |key: &str| block.get_property(key).map(String::as_str);
+
+    if name.ends_with("wall_torch") || name.ends_with("wall_sign") || name.ends_with("wall_banner") || name == "ladder" {
+        return Some(Attachment::Behind);
+    }
+    if name.ends_with("_button") || name == "lever" {
+        return Some(match prop("face") {
+            Some("floor") => Attachment::Below,
+            Some("ceiling") => Attachment::Above,
+            _ => Attachment::Behind,
+        });
+    }
+    if name.ends_with("lantern") && name != "sea_lantern" {
+        return Some(if prop("hanging") == Some("true") { Attachment::Above } else { Attachment::Below });
+    }
+    if name.ends_with("torch")
+        || name.ends_with("carpet")
+        || name.ends_with("rail")
+        || name.ends_with("pressure_plate")
+        || (name.ends_with("_door") && !name.ends_with("trapdoor"))
+        || name == "redstone_wire"
+        || name == "repeater"
+        || name == "comparator"
+    {
+        return Some(Attachment::Below);
+    }
+    None
+}
+
+/// Position of the supporting block, if it's inside the schematic's coordinate range
+pub fn support_pos(block: &Block, (x, y, z): (u32, u32, u32)) -> Option<(u32, u32, u32)> {
+    match attachment(block)? {
+        Attachment::Below => Some((x, y.checked_sub(1)?, z)),
+        Attachment::Above => Some((x, y + 1, z)),
+        Attachment::Behind => {
+            let facing = block.state.get_facing().filter(|facing| facing.axis() != Axis::Y)?;
+            let (dx, _, dz) = facing.opposite().to_offset();
+            Some((x.checked_add_signed(dx)?, y, z.checked_add_signed(dz)?))
+        }
+    }
+}
+
+/// Whether `support` can hold the attached `block`: anything but air, fluids
+/// and other attached blocks (a torch can't stand on a carpet), except that a
+/// door's lower half holds its upper half
+pub fn can_support(block: &Block, support: &Block) -> bool {
+    let half = |block: &Block, half: &str| block.get_property("half").is_some_and(|value| value == half);
+    if support.name == block.name && half(block, "upper") && half(support, "lower") {
+        return true;
+    }
+    !support.is_air()
+        && support.class() != crate::BlockClass::Liquid
+        && attachment(support).is_none()
+}
@@ -1,8 +1,14 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::HashMap;
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+
+pub use crate::direction::{Axis, Direction, Half};
 
 /// Represents a Minecraft block
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Block {
     /// Block name (e.g., "minecraft:stone")
     pub name: String,
@@ -14,7 +20,211 @@ pub struct Block {
 #[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub struct BlockState {
     #[serde(flatten)]
-    pub properties: HashMap<String, String>,
+    pub properties: Properties,
+}
+
+/// Property map shared by clones of a block state
+///
+/// Reads go through `Deref` to the `HashMap`. Cloning only bumps a reference
+/// count, so blocks cloned from a palette entry share one map; mutating through
+/// `DerefMut` copies the map first if it is shared. Everything that formats
+/// the properties (`Debug`, serialization, [`Block::to_selector`]) goes
+/// through [`Properties::sorted`], so equal states always format the same
+/// whatever order the map iterates in.
+#[derive(Clone, PartialEq, Eq, Default)]
+pub struct Properties(Arc<HashMap<String, String>>);
+
+impl Properties {
+    /// Whether both share the same map, without comparing contents
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+
+    /// The properties in name order
+    pub fn sorted(&self) -> Vec<(&String, &String)> {
+        let mut sorted: Vec<_> = self.0.iter().collect();
+        sorted.sort_unstable();
+        sorted
+    }
+}
+
+impl Deref for Properties {
+    type Target = HashMap<String, String>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Properties {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        Arc::make_mut(&mut self.0)
+    }
+}
+
+impl From<HashMap<String, String>> for Properties {
+    fn from(map: HashMap<String, String>) -> Self {
+        Self(Arc::new(map))
+    }
+}
+
+impl FromIterator<(String, String)> for Properties {
+    fn from_iter<I: IntoIterator<Item = (String, String)>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<HashMap<_, _>>())
+    }
+}
+
+impl<'a> IntoIterator for &'a Properties {
+    type Item = (&'a String, &'a String);
+    type IntoIter = std::collections::hash_map::Iter<'a, String, String>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl fmt::Debug for Properties {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.sorted()).finish()
+    }
+}
+
+impl Serialize for Properties {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.sorted())
+    }
+}
+
+impl<'de> Deserialize<'de> for Properties {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        HashMap::deserialize(deserializer).map(Self::from)
+    }
+}
+
+/// Broad kind of block, deciding what gets rendered and counted as material
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BlockClass {
+    /// air, cave_air and void_air
+    Air,
+    /// Invisible or editor-only blocks (barriers, structure voids, light blocks):
+    /// kept when converting, left out of renders and material lists by default
+    Technical,
+    /// Water, lava and bubble columns
+    Liquid,
+    /// Everything else
+    Solid,
+}
+
+/// Names without the `minecraft:` prefix, by class
+const CLASS_TABLE: &[(&str, BlockClass)] = &[
+    ("air", BlockClass::Air),
+    ("cave_air", BlockClass::Air),
+    ("void_air", BlockClass::Air),
+    ("structure_void", BlockClass::Technical),
+    ("barrier", BlockClass::Technical),
+    ("light", BlockClass::Technical),
+    ("moving_piston", BlockClass::Technical),
+    ("water", BlockClass::Liquid),
+    ("lava", BlockClass::Liquid),
+    ("bubble_column", BlockClass::Liquid),
+];
+
+/// Technical blocks with no shape at all, which [`Block::is_effectively_empty`]
+/// counts as empty along with air (barriers are solid to players)
+const INVISIBLE_BLOCKS: &[&str] = &["structure_void", "light"];
+
+impl BlockClass {
+    /// Class of a block name; unknown (e.g. modded) blocks are solid
+    pub fn of(name: &str) -> Self {
+        let name = name.strip_prefix("minecraft:").unwrap_or(name);
+        CLASS_TABLE.iter()
+            .find(|(n, _)| *n == name)
+            .map_or(BlockClass::Solid, |&(_, class)| class)
+    }
+
+    /// Lowercase name for display
+    pub fn name(self) -> &'static str {
+        match self {
+            BlockClass::Air => "air",
+            BlockClass::Technical => "technical",
+            BlockClass::Liquid => "liquid",
+            BlockClass::Solid => "solid",
+        }
+    }
+}
+
+impl BlockState {
+    /// Check if the block holds a water source (`waterlogged=true`)
+    pub fn is_waterlogged(&self) -> bool {
+        self.get_bool("waterlogged") == Some(true)
+    }
+
+    /// A `true`/`false` property like `waterlogged` or `open`
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        self.properties.get_bool(key)
+    }
+
+    /// A numeric property like `level`, `layers` or `age`
+    pub fn get_int(&self, key: &str) -> Option<u32> {
+        self.properties.get_int(key)
+    }
+
+    pub fn get_facing(&self) -> Option<Direction> {
+        self.properties.get_facing()
+    }
+
+    pub fn get_axis(&self) -> Option<Axis> {
+        self.properties.get_axis()
+    }
+
+    pub fn get_half(&self) -> Option<Half> {
+        self.properties.get_half()
+    }
+}
+
+/// Typed reads of property values, behind [`BlockState`]'s accessors and for
+/// code that only has the property map
+///
+/// Values are trimmed first. Anything that doesn't parse as the type asked
+/// for (`none` for a number, `low` for a bool) reads as None, the same as a
+/// missing property.
+pub(crate) trait PropertyValues {
+    fn value(&self, key: &str) -> Option<&str>;
+
+    fn get_bool(&self, key: &str) -> Option<bool> {
+        match self.value(key)?.trim() {
+            "true" => Some(true),
+            "false" => Some(false),
+            _ => None,
+        }
+    }
+
+    fn get_int(&self, key: &str) -> Option<u32> {
+        self.value(key)?.trim().parse().ok()
+    }
+
+    fn get_facing(&self) -> Option<Direction> {
+        self.value("facing")?.trim().parse().ok()
+    }
+
+    fn get_axis(&self) -> Option<Axis> {
+        let value = self.value("axis")?.trim();
+        Axis::ALL.into_iter().find(|axis| axis.name() == value)
+    }
+
+    fn get_half(&self) -> Option<Half> {
+        match self.value("half")?.trim() {
+            "top" | "upper" => Some(Half::Top),
+            "bottom" | "lower" => Some(Half::Bottom),
+            _ => None,
+        }
+    }
+}
+
+impl PropertyValues for HashMap<String, String> {
+    fn value(&self, key: &str) -> Option<&str> {
+        self.get(key).map(String::as_str)
+    }
 }
 
 impl Block {
@@ -36,11 +246,41 @@ impl Block {
         Self::new("minecraft:air")
     }
 
+    /// Air, cave air or void air, with or without the `minecraft:` prefix
+    /// (the [`BlockClass::Air`] entries of the class table)
     pub fn is_air(&self) -> bool {
-        matches!(
-            self.name.as_str(),
-            "minecraft:air" | "minecraft:cave_air" | "minecraft:void_air" | "air"
-        )
+        self.class() == BlockClass::Air
+    }
+
+    /// Air, or a block with nothing to draw: structure voids and light blocks
+    pub fn is_effectively_empty(&self) -> bool {
+        self.is_air() || INVISIBLE_BLOCKS.contains(&self.display_name())
+    }
+
+    /// Broad kind of block (see [`BlockClass`])
+    pub fn class(&self) -> BlockClass {
+        BlockClass::of(&self.name)
+    }
+
+    /// Barriers, structure voids, light blocks and the like
+    pub fn is_technical(&self) -> bool {
+        self.class() == BlockClass::Technical
+    }
+
+    /// Vanilla hardness (negative for unbreakable blocks), if the block is in
+    /// the table in [`crate::mining`]
+    pub fn hardness(&self) -> Option<f32> {
+        crate::mining::block_hardness(&self.name).map(|h| h.hardness)
+    }
+
+    /// Vanilla blast resistance, if the block is in the table in [`crate::mining`]
+    pub fn blast_resistance(&self) -> Option<f32> {
+        crate::mining::block_hardness(&self.name).map(|h| h.blast_resistance)
+    }
+
+    /// Structure voids: positions a structure leaves alone when placed
+    pub fn is_structure_void(&self) -> bool {
+        self.display_name() == "structure_void"
     }
 
     /// Get a property value
@@ -55,7 +295,7 @@ impl Block {
 
     /// Check if block is powered (for redstone)
     pub fn is_powered(&self) -> Option<bool> {
-        self.get_property("powered").map(|v| v == "true")
+        self.state.get_bool("powered")
     }
 
     /// Get display name (without minecraft: prefix)
@@ -65,26 +305,432 @@ impl Block {
             .unwrap_or(&self.name)
     }
 
-    /// Format block with state for display
+    /// Format block with state for display; the same as [`Block::to_selector`]
     pub fn full_name(&self) -> String {
+        self.to_selector()
+    }
+
+    /// The block as commands and WorldEdit take it, like
+    /// `minecraft:oak_stairs[facing=north,half=top]`, with the properties sorted
+    /// by name so equal blocks always format the same
+    pub fn to_selector(&self) -> String {
         if self.state.properties.is_empty() {
-            self.name.clone()
-        } else {
-            let props: Vec<String> = self.state.properties
-                .iter()
-                .map(|(k, v)| format!("{}={}", k, v))
-                .collect();
-            format!("{}[{}]", self.name, props.join(","))
+            return self.name.clone();
+        }
+        let props: Vec<String> = self.state.properties.sorted().into_iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+        format!("{}[{}]", self.name, props.join(","))
+    }
+
+    /// The item and item count this block is built from, as Litematica's material
+    /// list counts it; None for air and for technical blocks and halves that are
+    /// placed together with another block (upper door halves, bed heads, piston heads)
+    pub fn item_form(&self) -> Option<(String, usize)> {
+        let name = self.display_name();
+        let prop = |key: &str| self.get_property(key).map(String::as_str);
+        let count = |key: &str| self.state.get_int(key).map_or(1, |n| n as usize);
+        let item = |item: &str| Some((format!("minecraft:{}", item), 1));
+
+        if self.is_air() {
+            return None;
+        }
+        // Second halves of two-block items
+        let upper_half = prop("half") == Some("upper")
+            && (name.ends_with("_door") && !name.ends_with("trapdoor") || TALL_PLANTS.contains(&name));
+        if upper_half || (name.ends_with("_bed") && prop("part") == Some("head")) {
+            return None;
+        }
+
+        match name {
+            "piston_head" | "moving_piston" | "bubble_column" | "fire" | "soul_fire"
+            | "nether_portal" | "end_portal" | "end_gateway" | "frosted_ice" => None,
+            "wall_torch" => item("torch"),
+            "soul_wall_torch" => item("soul_torch"),
+            "redstone_wall_torch" => item("redstone_torch"),
+            "redstone_wire" => item("redstone"),
+            "tripwire" => item("string"),
+            "wheat" => item("wheat_seeds"),
+            "carrots" => item("carrot"),
+            "potatoes" => item("potato"),
+            "beetroots" => item("beetroot_seeds"),
+            "cocoa" => item("cocoa_beans"),
+            "sweet_berry_bush" => item("sweet_berries"),
+            "cave_vines" | "cave_vines_plant" => item("glow_berries"),
+            "melon_stem" | "attached_melon_stem" => item("melon_seeds"),
+            "pumpkin_stem" | "attached_pumpkin_stem" => item("pumpkin_seeds"),
+            "water" => item("water_bucket"),
+            "lava" => item("lava_bucket"),
+            _ if name.starts_with("potted_") => item("flower_pot"),
+            _ if name.ends_with("_wall_hanging_sign") => item(&name.replace("_wall_hanging_sign", "_hanging_sign")),
+            _ if name.ends_with("_wall_sign") || name.ends_with("_wall_banner") => item(&name.replace("_wall_", "_")),
+            _ if name.ends_with("_wall_head") || name.ends_with("_wall_skull") => item(&name.replace("_wall_", "_")),
+            _ if name.ends_with("_slab") && prop("type") == Some("double") => Some((self.name.clone(), 2)),
+            _ if name.ends_with("candle") => Some((self.name.clone(), count("candles"))),
+            "sea_pickle" => Some((self.name.clone(), count("pickles"))),
+            "turtle_egg" => Some((self.name.clone(), count("eggs"))),
+            _ => Some((self.name.clone(), 1)),
+        }
+    }
+
+    /// Block light level this block emits (0-15), honoring `lit` and similar states
+    pub fn light_emission(&self) -> u8 {
+        let name = self.display_name();
+        let prop = |key: &str| self.get_property(key).map(String::as_str);
+        // Missing `lit` means the block's default state
+        let lit = |default: bool| self.state.get_bool("lit").unwrap_or(default);
+
+        match name {
+            "glowstone" | "sea_lantern" | "jack_o_lantern" | "lantern" | "shroomlight"
+            | "beacon" | "conduit" | "end_gateway" | "end_portal" | "fire" | "lava"
+            | "ochre_froglight" | "verdant_froglight" | "pearlescent_froglight" => 15,
+            "torch" | "wall_torch" | "end_rod" => 14,
+            "campfire" if lit(true) => 15,
+            "redstone_lamp" if lit(false) => 15,
+            "furnace" | "blast_furnace" | "smoker" if lit(false) => 13,
+            "soul_torch" | "soul_wall_torch" | "soul_lantern" | "soul_fire" | "crying_obsidian" => 10,
+            "soul_campfire" if lit(true) => 10,
+            "redstone_ore" | "deepslate_redstone_ore" if lit(false) => 9,
+            "redstone_torch" | "redstone_wall_torch" if lit(true) => 7,
+            "enchanting_table" | "ender_chest" | "glow_lichen" => 7,
+            "sculk_catalyst" => 6,
+            "amethyst_cluster" => 5,
+            "large_amethyst_bud" => 4,
+            "magma_block" => 3,
+            "medium_amethyst_bud" => 2,
+            "small_amethyst_bud" | "brewing_stand" | "brown_mushroom" | "dragon_egg"
+            | "end_portal_frame" | "sculk_sensor" | "calibrated_sculk_sensor" => 1,
+            "respawn_anchor" => match prop("charges") {
+                Some("4") => 15,
+                Some("3") => 11,
+                Some("2") => 7,
+                Some("1") => 3,
+                _ => 0,
+            },
+            "sea_pickle" if prop("waterlogged") != Some("false") => {
+                let pickles: u8 = prop("pickles").and_then(|v| v.parse().ok()).unwrap_or(1);
+                3 * pickles.clamp(1, 4) + 3
+            }
+            _ if name.ends_with("candle") && lit(false) => {
+                let candles: u8 = prop("candles").and_then(|v| v.parse().ok()).unwrap_or(1);
+                3 * candles.clamp(1, 4)
+            }
+            _ => 0,
         }
     }
 }
 
+/// Two-block plants whose upper half comes with the lower one
+const TALL_PLANTS: &[&str] = &[
+    "sunflower", "lilac", "rose_bush", "peony", "tall_grass", "large_fern",
+    "tall_seagrass", "pitcher_plant", "small_dripleaf",
+];
+
 impl std::fmt::Display for Block {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.full_name())
     }
 }
 
+/// Overworld wood types
+pub(crate) const WOODS: &[&str] = &["oak", "spruce", "birch", "jungle", "acacia", "dark_oak", "mangrove", "cherry", "pale_oak"];
+
+/// Blocks made from every overworld wood (`stripped_*` ones are added separately)
+const WOOD_SHAPES: &[&str] = &[
+    "planks", "log", "wood", "leaves", "sapling", "stairs", "slab", "fence", "fence_gate", "door",
+    "trapdoor", "pressure_plate", "button", "sign", "wall_sign", "hanging_sign", "wall_hanging_sign",
+];
+
+/// Blocks made from crimson and warped wood
+const NETHER_WOOD_SHAPES: &[&str] = &[
+    "planks", "stem", "hyphae", "fungus", "roots", "nylium", "stairs", "slab", "fence", "fence_gate",
+    "door", "trapdoor", "pressure_plate", "button", "sign", "wall_sign", "hanging_sign", "wall_hanging_sign",
+];
+
+/// The sixteen dye colors
+const COLORS: &[&str] = &[
+    "white", "orange", "magenta", "light_blue", "yellow", "lime", "pink", "gray",
+    "light_gray", "cyan", "purple", "blue", "brown", "green", "red", "black",
+];
+
+/// Blocks that come in every dye color
+const COLOR_SHAPES: &[&str] = &[
+    "wool", "carpet", "concrete", "concrete_powder", "terracotta", "glazed_terracotta", "stained_glass",
+    "stained_glass_pane", "bed", "banner", "wall_banner", "candle", "candle_cake", "shulker_box",
+];
+
+/// Materials with stairs and slabs
+const STAIRS_AND_SLABS: &[&str] = &[
+    "stone", "cobblestone", "mossy_cobblestone", "stone_brick", "mossy_stone_brick", "granite",
+    "polished_granite", "diorite", "polished_diorite", "andesite", "polished_andesite", "sandstone",
+    "smooth_sandstone", "red_sandstone", "smooth_red_sandstone", "brick", "nether_brick",
+    "red_nether_brick", "prismarine", "prismarine_brick", "dark_prismarine", "purpur", "quartz",
+    "smooth_quartz", "end_stone_brick", "blackstone", "polished_blackstone", "polished_blackstone_brick",
+    "cobbled_deepslate", "polished_deepslate", "deepslate_brick", "deepslate_tile", "mud_brick", "tuff",
+    "polished_tuff", "tuff_brick", "bamboo", "bamboo_mosaic", "cut_copper", "exposed_cut_copper",
+    "weathered_cut_copper", "oxidized_cut_copper", "waxed_cut_copper", "waxed_exposed_cut_copper",
+    "waxed_weathered_cut_copper", "waxed_oxidized_cut_copper",
+];
+
+/// Materials with walls
+const WALLS: &[&str] = &[
+    "cobblestone", "mossy_cobblestone", "stone_brick", "mossy_stone_brick", "granite", "diorite",
+    "andesite", "sandstone", "red_sandstone", "brick", "nether_brick", "red_nether_brick", "prismarine",
+    "end_stone_brick", "blackstone", "polished_blackstone", "polished_blackstone_brick",
+    "cobbled_deepslate", "polished_deepslate", "deepslate_brick", "deepslate_tile", "mud_brick", "tuff",
+    "polished_tuff", "tuff_brick",
+];
+
+/// Ores with a deepslate variant
+const ORES: &[&str] = &["coal", "iron", "copper", "gold", "redstone", "lapis", "diamond", "emerald"];
+
+/// Vanilla blocks outside the families above
+const OTHER_BLOCKS: &[&str] = &[
+    "air", "cave_air", "void_air", "stone", "granite", "polished_granite", "diorite", "polished_diorite",
+    "andesite", "polished_andesite", "deepslate", "polished_deepslate", "chiseled_deepslate",
+    "deepslate_bricks", "cracked_deepslate_bricks", "deepslate_tiles", "cracked_deepslate_tiles",
+    "reinforced_deepslate", "calcite", "tuff", "polished_tuff", "chiseled_tuff", "tuff_bricks",
+    "chiseled_tuff_bricks", "dripstone_block", "pointed_dripstone", "grass_block", "dirt", "coarse_dirt",
+    "rooted_dirt", "podzol", "mycelium", "dirt_path", "farmland", "mud", "packed_mud", "mud_bricks",
+    "clay", "gravel", "sand", "red_sand", "suspicious_sand", "suspicious_gravel", "sandstone",
+    "chiseled_sandstone", "cut_sandstone", "smooth_sandstone", "cut_sandstone_slab", "red_sandstone",
+    "chiseled_red_sandstone", "cut_red_sandstone", "smooth_red_sandstone", "cut_red_sandstone_slab",
+    "smooth_stone", "smooth_stone_slab", "cobblestone", "mossy_cobblestone", "stone_bricks",
+    "mossy_stone_bricks", "cracked_stone_bricks", "chiseled_stone_bricks", "infested_stone",
+    "infested_cobblestone", "infested_stone_bricks", "bricks", "bedrock", "obsidian", "crying_obsidian",
+    "ice", "packed_ice", "blue_ice", "frosted_ice", "snow", "snow_block", "powder_snow", "water", "lava",
+    "bubble_column", "netherrack", "soul_sand", "soul_soil", "basalt", "polished_basalt",
+    "smooth_basalt", "blackstone", "gilded_blackstone", "polished_blackstone", "chiseled_polished_blackstone",
+    "polished_blackstone_bricks", "cracked_polished_blackstone_bricks", "nether_bricks",
+    "cracked_nether_bricks", "chiseled_nether_bricks", "red_nether_bricks", "nether_brick_fence",
+    "nether_wart_block", "warped_wart_block", "nether_wart", "glowstone", "magma_block", "shroomlight",
+    "nether_gold_ore", "nether_quartz_ore", "ancient_debris", "end_stone", "end_stone_bricks",
+    "purpur_block", "purpur_pillar", "chorus_plant", "chorus_flower", "dragon_egg", "end_rod",
+    "end_portal", "end_portal_frame", "end_gateway", "quartz_block", "chiseled_quartz_block",
+    "quartz_pillar", "quartz_bricks", "smooth_quartz", "prismarine", "prismarine_bricks",
+    "dark_prismarine", "sea_lantern", "sponge", "wet_sponge", "terracotta", "glass", "glass_pane",
+    "tinted_glass", "coal_block", "iron_block", "copper_block", "gold_block", "redstone_block",
+    "lapis_block", "diamond_block", "emerald_block", "netherite_block", "amethyst_block",
+    "budding_amethyst", "amethyst_cluster", "large_amethyst_bud", "medium_amethyst_bud",
+    "small_amethyst_bud", "raw_iron_block", "raw_copper_block", "raw_gold_block", "exposed_copper",
+    "weathered_copper", "oxidized_copper", "waxed_copper_block", "waxed_exposed_copper",
+    "waxed_weathered_copper", "waxed_oxidized_copper", "chiseled_copper", "copper_grate", "copper_bulb",
+    "copper_door", "copper_trapdoor", "bamboo", "bamboo_sapling", "bamboo_block", "stripped_bamboo_block",
+    "bamboo_planks", "bamboo_mosaic", "bamboo_fence", "bamboo_fence_gate", "bamboo_door",
+    "bamboo_trapdoor", "bamboo_pressure_plate", "bamboo_button", "bamboo_sign", "bamboo_wall_sign",
+    "bamboo_hanging_sign", "bamboo_wall_hanging_sign", "mangrove_propagule", "mangrove_roots",
+    "muddy_mangrove_roots", "azalea", "flowering_azalea", "azalea_leaves", "flowering_azalea_leaves",
+    "short_grass", "tall_grass", "fern", "large_fern", "dead_bush", "seagrass", "tall_seagrass", "kelp",
+    "kelp_plant", "dried_kelp_block", "sea_pickle", "lily_pad", "vine", "glow_lichen", "moss_block",
+    "moss_carpet", "pale_moss_block", "pale_moss_carpet", "hanging_roots", "spore_blossom", "big_dripleaf",
+    "big_dripleaf_stem", "small_dripleaf", "cave_vines", "cave_vines_plant", "twisting_vines",
+    "twisting_vines_plant", "weeping_vines", "weeping_vines_plant", "nether_sprouts", "sugar_cane",
+    "cactus", "dandelion", "poppy", "blue_orchid", "allium", "azure_bluet", "red_tulip", "orange_tulip",
+    "white_tulip", "pink_tulip", "oxeye_daisy", "cornflower", "lily_of_the_valley", "wither_rose",
+    "torchflower", "pitcher_plant", "pink_petals", "sunflower", "lilac", "rose_bush", "peony",
+    "brown_mushroom", "red_mushroom", "brown_mushroom_block", "red_mushroom_block", "mushroom_stem",
+    "wheat", "carrots", "potatoes", "beetroots", "melon", "melon_stem", "attached_melon_stem", "pumpkin",
+    "carved_pumpkin", "jack_o_lantern", "pumpkin_stem", "attached_pumpkin_stem", "sweet_berry_bush",
+    "cocoa", "hay_block", "bone_block", "slime_block", "honey_block", "honeycomb_block", "bee_nest",
+    "beehive", "sculk", "sculk_vein", "sculk_catalyst", "sculk_shrieker", "sculk_sensor",
+    "calibrated_sculk_sensor", "ochre_froglight", "verdant_froglight", "pearlescent_froglight",
+    "frogspawn", "turtle_egg", "sniffer_egg", "tube_coral_block", "brain_coral_block",
+    "bubble_coral_block", "fire_coral_block", "horn_coral_block", "cobweb", "torch", "wall_torch",
+    "soul_torch", "soul_wall_torch", "redstone_torch", "redstone_wall_torch", "lantern", "soul_lantern",
+    "campfire", "soul_campfire", "fire", "soul_fire", "candle", "cake", "chest", "trapped_chest",
+    "ender_chest", "barrel", "shulker_box", "crafting_table", "furnace", "blast_furnace", "smoker",
+    "cartography_table", "fletching_table", "smithing_table", "stonecutter", "grindstone", "loom",
+    "anvil", "chipped_anvil", "damaged_anvil", "enchanting_table", "brewing_stand", "cauldron",
+    "water_cauldron", "lava_cauldron", "powder_snow_cauldron", "composter", "lectern", "bookshelf",
+    "chiseled_bookshelf", "jukebox", "note_block", "beacon", "conduit", "bell", "lodestone",
+    "respawn_anchor", "flower_pot", "decorated_pot", "ladder", "scaffolding", "iron_bars", "chain",
+    "iron_door", "iron_trapdoor", "lightning_rod", "crafter", "trial_spawner", "vault", "heavy_core",
+    "spawner", "redstone_wire", "redstone_lamp", "redstone_ore", "deepslate_redstone_ore", "repeater",
+    "comparator", "piston", "sticky_piston", "piston_head", "moving_piston", "observer", "hopper",
+    "dropper", "dispenser", "lever", "stone_button", "stone_pressure_plate",
+    "light_weighted_pressure_plate", "heavy_weighted_pressure_plate", "tripwire", "tripwire_hook",
+    "daylight_detector", "target", "tnt", "rail", "powered_rail", "detector_rail", "activator_rail",
+    "command_block", "chain_command_block", "repeating_command_block", "structure_block", "jigsaw",
+    "barrier", "light", "structure_void", "nether_portal", "player_head", "player_wall_head",
+    "skeleton_skull", "wither_skeleton_skull", "zombie_head", "creeper_head", "dragon_head",
+    "piglin_head", "infested_deepslate",
+];
+
+/// Vanilla block names without the `minecraft:` prefix, sorted
+pub fn known_block_ids() -> &'static [String] {
+    static IDS: std::sync::OnceLock<Vec<String>> = std::sync::OnceLock::new();
+    IDS.get_or_init(|| {
+        let mut ids: Vec<String> = OTHER_BLOCKS.iter().map(|s| s.to_string()).collect();
+        for wood in WOODS {
+            for shape in WOOD_SHAPES {
+                // Mangroves grow from propagules instead
+                if !(*wood == "mangrove" && *shape == "sapling") {
+                    ids.push(format!("{}_{}", wood, shape));
+                }
+            }
+            ids.push(format!("stripped_{}_log", wood));
+            ids.push(format!("stripped_{}_wood", wood));
+        }
+        for wood in ["crimson", "warped"] {
+            ids.extend(NETHER_WOOD_SHAPES.iter().map(|shape| format!("{}_{}", wood, shape)));
+            ids.push(format!("stripped_{}_stem", wood));
+            ids.push(format!("stripped_{}_hyphae", wood));
+        }
+        for color in COLORS {
+            ids.extend(COLOR_SHAPES.iter().map(|shape| format!("{}_{}", color, shape)));
+        }
+        ids.extend(STAIRS_AND_SLABS.iter().flat_map(|m| [format!("{}_stairs", m), format!("{}_slab", m)]));
+        ids.extend(WALLS.iter().map(|m| format!("{}_wall", m)));
+        ids.extend(ORES.iter().flat_map(|ore| [format!("{}_ore", ore), format!("deepslate_{}_ore", ore)]));
+        ids.sort();
+        ids.dedup();
+        ids
+    })
+}
+
+/// The closest vanilla block name to a mistyped `query`, as "minecraft:name"
+///
+/// Returns `None` when `query` is already a known name or nothing is close:
+/// within an edit distance of 2, or 1 for names under 8 characters and none
+/// under 4, so short substrings like "ore" don't produce guesses.
+pub fn suggest_block_name(query: &str) -> Option<String> {
+    let query = query.to_lowercase();
+    let query = query.strip_prefix("minecraft:").unwrap_or(&query);
+    let ids = known_block_ids();
+    if query.contains(':') || ids.binary_search_by(|id| id.as_str().cmp(query)).is_ok() {
+        return None;
+    }
+    let max = match query.len() {
+        0..=3 => return None,
+        4..=7 => 1,
+        _ => 2,
+    };
+    ids.iter()
+        .filter(|id| id.len().abs_diff(query.len()) <= max)
+        .map(|id| (edit_distance(query, id), id))
+        .filter(|&(distance, _)| distance <= max)
+        // The first of the closest, so ties go to the alphabetically first name
+        .min_by_key(|&(distance, _)| distance)
+        .map(|(_, id)| format!("minecraft:{}", id))
+}
+
+/// Block name patterns as `search` takes them: a block matches when its name
+/// contains any of them, ignoring ASCII case. Patterns are literal, so `.`
+/// and `*` only match themselves.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BlockSelector {
+    patterns: Vec<String>,
+}
+
+impl BlockSelector {
+    pub fn new<I, S>(patterns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self { patterns: patterns.into_iter().map(|p| p.as_ref().to_ascii_lowercase()).collect() }
+    }
+
+    /// The patterns, lowercased
+    pub fn patterns(&self) -> &[String] {
+        &self.patterns
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    pub fn matches(&self, block: &Block) -> bool {
+        self.matches_name(&block.name)
+    }
+
+    pub fn matches_name(&self, name: &str) -> bool {
+        let name = name.as_bytes();
+        self.patterns.iter().any(|pattern| {
+            pattern.is_empty() || name.windows(pattern.len()).any(|window| window.eq_ignore_ascii_case(pattern.as_bytes()))
+        })
+    }
+}
+
+/// Ways to fold block variants into one row when counting
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregation {
+    /// The sixteen colors of wool, concrete, terracotta, glass, ... into one
+    /// row per block
+    Color,
+    /// Wood species into one row per wood block
+    Material,
+    /// Everything into stairs, slabs, walls, fences, ..., full blocks and other
+    Shape,
+}
+
+/// Wood families for [`Aggregation::Material`]: species and the blocks each
+/// one comes in
+const WOOD_FAMILIES: &[(&[&str], &[&str])] = &[
+    (WOODS, WOOD_SHAPES),
+    (&["crimson", "warped"], NETHER_WOOD_SHAPES),
+];
+
+/// Name suffixes for [`Aggregation::Shape`] and the rows they go in
+const SHAPE_SUFFIXES: &[(&str, &str)] = &[
+    ("_stairs", "stairs"), ("_slab", "slab"), ("_wall", "wall"), ("_fence", "fence"),
+    ("_fence_gate", "fence_gate"), ("_pane", "pane"), ("_door", "door"), ("_trapdoor", "trapdoor"),
+    ("_carpet", "carpet"), ("_button", "button"), ("_pressure_plate", "pressure_plate"),
+];
+
+impl Aggregation {
+    /// The row a block (or item) name is counted under: `minecraft:*_wool` for
+    /// every color of wool, `minecraft:*_planks` for every species of planks,
+    /// `stairs` for any stairs. Names that don't fold come back unchanged in
+    /// the color and material modes; in shape mode they are `full` or `other`.
+    pub fn group(self, name: &str) -> String {
+        let id = name.strip_prefix("minecraft:");
+        match self {
+            Aggregation::Color => id
+                .and_then(|id| COLORS.iter().find_map(|color| id.strip_prefix(color)?.strip_prefix('_')))
+                .filter(|shape| COLOR_SHAPES.contains(shape))
+                .map_or_else(|| name.to_string(), |shape| format!("minecraft:*_{}", shape)),
+            Aggregation::Material => id
+                .and_then(|id| {
+                    let (stripped, id) = id.strip_prefix("stripped_").map_or(("", id), |rest| ("stripped_", rest));
+                    WOOD_FAMILIES.iter().find_map(|(species, shapes)| {
+                        let shape = species.iter().find_map(|wood| id.strip_prefix(wood)?.strip_prefix('_'))?;
+                        shapes.contains(&shape).then(|| format!("minecraft:{}*_{}", stripped, shape))
+                    })
+                })
+                .unwrap_or_else(|| name.to_string()),
+            Aggregation::Shape => {
+                if let Some((_, shape)) = SHAPE_SUFFIXES.iter().find(|(suffix, _)| name.ends_with(suffix)) {
+                    return shape.to_string();
+                }
+                let full = crate::block_geometry::get_block_geometry(name, &HashMap::new()).is_full();
+                if full && BlockClass::of(name) == BlockClass::Solid { "full" } else { "other" }.to_string()
+            }
+        }
+    }
+}
+
+/// Edit distance over bytes where swapping two neighbors counts as one edit,
+/// like a single insertion or deletion; block names are ASCII
+fn edit_distance(a: &str, b: &str) -> usize {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    // Rows i-2, i-1 and i of the distance table
+    let mut before: Vec<usize> = vec![0; b.len() + 1];
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut row = vec![0; b.len() + 1];
+    for i in 0..a.len() {
+        row[0] = i + 1;
+        for j in 0..b.len() {
+            let mut d = (prev[j] + usize::from(a[i] != b[j])).min(prev[j + 1] + 1).min(row[j] + 1);
+            if i > 0 && j > 0 && a[i] == b[j - 1] && a[i - 1] == b[j] {
+                d = d.min(before[j - 1] + 1);
+            }
+            row[j + 1] = d;
+        }
+        std::mem::swap(&mut before, &mut prev);
+        std::mem::swap(&mut prev, &mut row);
+    }
+    prev[b.len()]
+}
+
 /// Legacy block ID mapping (for .schematic format)
 /// Maps numeric IDs to block names
 pub fn legacy_id_to_name(id: u8, data: u8) -> String {
@@ -548,5 +1194,198 @@ pub fn legacy_data_to_state(id: u8, data: u8) -> BlockState {
         _ => {}
     }
 
-    BlockState { properties: props }
+    BlockState { properties: props.into() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(name: &str, props: &[(&str, &str)]) -> Block {
+        let properties = props.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        Block::with_state(name, BlockState { properties })
+    }
+
+    #[test]
+    fn test_block_selector() {
+        let ores = BlockSelector::new(["_ore", "Ancient"]);
+        assert!(ores.matches_name("minecraft:deepslate_iron_ore"));
+        assert!(ores.matches(&Block::new("minecraft:ancient_debris")));
+        assert!(!ores.matches_name("minecraft:stone"));
+        // Substrings are literal, not regexes
+        assert!(!BlockSelector::new(["oak.log"]).matches_name("minecraft:oak_log"));
+        assert!(!BlockSelector::new(Vec::<String>::new()).matches_name("minecraft:stone"));
+    }
+
+    #[test]
+    fn test_item_form_collapses_multi_block_items() {
+        let item = |name: &str| Some((name.to_string(), 1));
+
+        assert_eq!(block("minecraft:oak_door", &[("half", "lower")]).item_form(), item("minecraft:oak_door"));
+        assert_eq!(block("minecraft:oak_door", &[("half", "upper")]).item_form(), None);
+        assert_eq!(block("minecraft:oak_trapdoor", &[("half", "upper")]).item_form(), item("minecraft:oak_trapdoor"));
+
+        assert_eq!(block("minecraft:red_bed", &[("part", "foot")]).item_form(), item("minecraft:red_bed"));
+        assert_eq!(block("minecraft:red_bed", &[("part", "head")]).item_form(), None);
+
+        assert_eq!(block("minecraft:sunflower", &[("half", "lower")]).item_form(), item("minecraft:sunflower"));
+        assert_eq!(block("minecraft:sunflower", &[("half", "upper")]).item_form(), None);
+
+        assert_eq!(block("minecraft:sticky_piston", &[("extended", "true")]).item_form(), item("minecraft:sticky_piston"));
+        assert_eq!(block("minecraft:piston_head", &[("type", "sticky")]).item_form(), None);
+
+        // Waterlogged variants and wall forms fold into the plain item
+        let stairs = block("minecraft:oak_stairs", &[("waterlogged", "true")]);
+        assert!(stairs.state.is_waterlogged());
+        assert_eq!(stairs.item_form(), item("minecraft:oak_stairs"));
+        assert_eq!(block("minecraft:wall_torch", &[("facing", "north")]).item_form(), item("minecraft:torch"));
+        assert_eq!(block("minecraft:stone_slab", &[("type", "double")]).item_form(), Some(("minecraft:stone_slab".to_string(), 2)));
+    }
+
+    #[test]
+    fn test_block_classes() {
+        assert_eq!(Block::air().class(), BlockClass::Air);
+        assert_eq!(Block::new("air").class(), BlockClass::Air);
+        assert!(Block::new("minecraft:structure_void").is_technical());
+        assert!(block("minecraft:light", &[("level", "15")]).is_technical());
+        assert_eq!(Block::new("minecraft:water").class(), BlockClass::Liquid);
+        assert_eq!(Block::new("create:brass_casing").class(), BlockClass::Solid);
+        // Light sources aren't the technical light block
+        assert_eq!(Block::new("minecraft:sea_lantern").class(), BlockClass::Solid);
+    }
+
+    #[test]
+    fn test_typed_properties() {
+        let state = block("minecraft:oak_door", &[
+            ("open", "true"), ("powered", " false "), ("level", " 7 "), ("north", "none"), ("age", "-1"),
+            ("facing", "east"), ("half", "upper"), ("axis", "Y"),
+        ]).state;
+        assert_eq!(state.get_bool("open"), Some(true));
+        assert_eq!(state.get_bool("powered"), Some(false));
+        assert_eq!(state.get_int("level"), Some(7));
+        // Walls' "none" is neither a bool nor a number
+        assert_eq!((state.get_bool("north"), state.get_int("north")), (None, None));
+        assert_eq!(state.get_int("age"), None);
+        assert_eq!(state.get_bool("missing"), None);
+        assert_eq!(state.get_facing(), Some(Direction::East));
+        assert_eq!(state.get_half(), Some(Half::Top));
+        // Values are lowercase in the game, so "Y" isn't an axis
+        assert_eq!(state.get_axis(), None);
+        assert_eq!(block("minecraft:oak_log", &[("axis", "x")]).state.get_axis(), Some(Axis::X));
+        assert!(block("minecraft:oak_stairs", &[("waterlogged", "true")]).state.is_waterlogged());
+        assert_eq!(block("minecraft:water_cauldron", &[("level", "3")]).state.get_int("level"), Some(3));
+    }
+
+    #[test]
+    fn test_air_and_empty_blocks() {
+        let air: Vec<&str> = known_block_ids().iter().map(String::as_str).filter(|name| Block::new(*name).is_air()).collect();
+        assert_eq!(air, ["air", "cave_air", "void_air"]);
+        let empty: Vec<&str> = known_block_ids().iter().map(String::as_str).filter(|name| Block::new(*name).is_effectively_empty()).collect();
+        assert_eq!(empty, ["air", "cave_air", "light", "structure_void", "void_air"]);
+
+        for name in ["minecraft:air", "minecraft:cave_air", "minecraft:void_air"] {
+            assert!(Block::new(name).is_air() && Block::new(name).is_effectively_empty(), "{}", name);
+        }
+        assert!(!Block::new("minecraft:structure_void").is_air());
+        assert!(block("minecraft:light", &[("level", "0")]).is_effectively_empty());
+        // Only exact names, and only vanilla ones
+        for name in ["minecraft:oak_stairs", "minecraft:barrier", "somemod:air", "minecraft:air_block"] {
+            assert!(!Block::new(name).is_effectively_empty(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_cloned_states_share_properties() {
+        let leaves = block("minecraft:oak_leaves", &[("distance", "7"), ("persistent", "false")]);
+        let copy = leaves.clone();
+        assert!(copy.state.properties.ptr_eq(&leaves.state.properties));
+
+        // Changing a clone copies its map and leaves the original alone
+        let mut changed = leaves.clone();
+        changed.state.properties.insert("persistent".to_string(), "true".to_string());
+        assert!(!changed.state.properties.ptr_eq(&leaves.state.properties));
+        assert_eq!(leaves.get_property("persistent").map(String::as_str), Some("false"));
+        assert_ne!(changed, leaves);
+        assert_eq!(copy, leaves);
+    }
+
+    #[test]
+    fn test_property_order_is_canonical() {
+        let props = [("facing", "north"), ("half", "top"), ("shape", "straight"), ("waterlogged", "false")];
+        let forward = block("minecraft:oak_stairs", &props);
+        let mut reversed = props;
+        reversed.reverse();
+        // Separate maps, so each iterates in its own order
+        for _ in 0..8 {
+            let backward = block("minecraft:oak_stairs", &reversed);
+            assert_eq!(backward, forward);
+            assert_eq!(backward.display_name(), forward.display_name());
+            assert_eq!(backward.full_name(), "minecraft:oak_stairs[facing=north,half=top,shape=straight,waterlogged=false]");
+            assert_eq!(format!("{:?}", backward.state), format!("{:?}", forward.state));
+            assert_eq!(serde_json::to_string(&backward.state).unwrap(), serde_json::to_string(&forward.state).unwrap());
+        }
+        let keys: Vec<&str> = forward.state.properties.sorted().into_iter().map(|(k, _)| k.as_str()).collect();
+        assert_eq!(keys, ["facing", "half", "shape", "waterlogged"]);
+    }
+
+    #[test]
+    fn test_selector_round_trip() {
+        let stairs = block("minecraft:oak_stairs", &[("waterlogged", "false"), ("half", "top"), ("facing", "north")]);
+        let selector = stairs.to_selector();
+        assert_eq!(selector, "minecraft:oak_stairs[facing=north,half=top,waterlogged=false]");
+        assert_eq!(crate::schem::Schem::parse_block_state(&selector), stairs);
+        assert_eq!(stairs.full_name(), selector);
+
+        for selector in ["minecraft:stone", "minecraft:light[level=15]", "create:shaft[axis=y]"] {
+            assert_eq!(crate::schem::Schem::parse_block_state(selector).to_selector(), selector);
+        }
+    }
+
+    #[test]
+    fn test_aggregation_groups() {
+        let color = |name: &str| Aggregation::Color.group(name);
+        assert_eq!(color("minecraft:light_blue_concrete"), "minecraft:*_concrete");
+        assert_eq!(color("minecraft:blue_concrete_powder"), "minecraft:*_concrete_powder");
+        assert_eq!(color("minecraft:red_stained_glass_pane"), "minecraft:*_stained_glass_pane");
+        // Uncolored blocks, other families and other namespaces stay as they are
+        assert_eq!(color("minecraft:terracotta"), "minecraft:terracotta");
+        assert_eq!(color("minecraft:red_sandstone"), "minecraft:red_sandstone");
+        assert_eq!(color("somemod:white_wool"), "somemod:white_wool");
+
+        let material = |name: &str| Aggregation::Material.group(name);
+        assert_eq!(material("minecraft:dark_oak_stairs"), "minecraft:*_stairs");
+        assert_eq!(material("minecraft:oak_stairs"), "minecraft:*_stairs");
+        assert_eq!(material("minecraft:warped_stem"), "minecraft:*_stem");
+        assert_eq!(material("minecraft:stripped_birch_log"), "minecraft:stripped_*_log");
+        assert_eq!(material("minecraft:stone_stairs"), "minecraft:stone_stairs");
+        // Mangrove roots aren't the nether's roots
+        assert_eq!(material("minecraft:mangrove_roots"), "minecraft:mangrove_roots");
+
+        let shape = |name: &str| Aggregation::Shape.group(name);
+        assert_eq!(shape("minecraft:cobblestone_wall"), "wall");
+        assert_eq!(shape("minecraft:oak_fence"), "fence");
+        assert_eq!(shape("minecraft:oak_fence_gate"), "fence_gate");
+        assert_eq!(shape("minecraft:stone_brick_stairs"), "stairs");
+        assert_eq!(shape("minecraft:iron_trapdoor"), "trapdoor");
+        assert_eq!(shape("minecraft:white_concrete"), "full");
+        assert_eq!(shape("minecraft:torch"), "other");
+        assert_eq!(shape("minecraft:air"), "other");
+    }
+
+    #[test]
+    fn test_suggest_block_name() {
+        assert_eq!(suggest_block_name("minecraft:stone_brick").as_deref(), Some("minecraft:stone_bricks"));
+        assert_eq!(suggest_block_name("oak_plank").as_deref(), Some("minecraft:oak_planks"));
+        assert_eq!(suggest_block_name("redstone_wrie").as_deref(), Some("minecraft:redstone_wire"));
+        assert_eq!(suggest_block_name("Diamnod_Block").as_deref(), Some("minecraft:diamond_block"));
+        assert_eq!(suggest_block_name("deepslate_tile_wal").as_deref(), Some("minecraft:deepslate_tile_wall"));
+        // Known names, short substrings, other namespaces and far-off names get nothing
+        assert_eq!(suggest_block_name("minecraft:stone"), None);
+        assert_eq!(suggest_block_name("ore"), None);
+        assert_eq!(suggest_block_name("create:andesite_casing"), None);
+        assert_eq!(suggest_block_name("xyzzy_block"), None);
+        assert!(known_block_ids().windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(edit_distance("wrie", "wire"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
 }
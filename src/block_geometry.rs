@@ -7,6 +7,8 @@
 
 use std::collections::HashMap;
 
+use crate::block::{Axis, Direction, PropertyValues};
+
 /// Axis-aligned bounding box (coordinates 0.0-1.0 within block space)
 #[derive(Debug, Clone, Copy)]
 pub struct AABB {
@@ -24,44 +26,55 @@ impl AABB {
         Self::new((0.0, 0.0, 0.0), (1.0, 1.0, 1.0))
     }
 
+    /// The rectangle this box's `face` side takes up on the block's boundary,
+    /// as min and max corners in the two axes across the face (lowest axis
+    /// first), or None if that side of the box is inside the block
+    pub fn boundary_face(&self, face: Face) -> Option<([f32; 2], [f32; 2])> {
+        const E: f32 = 0.001;
+
+        let (min, max) = ([self.min.0, self.min.1, self.min.2], [self.max.0, self.max.1, self.max.2]);
+        let axis = face.axis().index();
+        let on_boundary = if face.is_positive() { max[axis] >= 1.0 - E } else { min[axis] <= E };
+        if !on_boundary {
+            return None;
+        }
+        let [a, b] = match axis {
+            0 => [1, 2],
+            1 => [0, 2],
+            _ => [0, 1],
+        };
+        Some(([min[a], min[b]], [max[a], max[b]]))
+    }
+
     /// Check if this AABB fully covers a face of the unit cube
     pub fn covers_face(&self, face: Face) -> bool {
         const E: f32 = 0.001; // epsilon for float comparison
 
         match face {
-            Face::XNeg => self.min.0 <= E &&
+            Face::West => self.min.0 <= E &&
                           self.min.1 <= E && self.max.1 >= 1.0 - E &&
                           self.min.2 <= E && self.max.2 >= 1.0 - E,
-            Face::XPos => self.max.0 >= 1.0 - E &&
+            Face::East => self.max.0 >= 1.0 - E &&
                           self.min.1 <= E && self.max.1 >= 1.0 - E &&
                           self.min.2 <= E && self.max.2 >= 1.0 - E,
-            Face::YNeg => self.min.1 <= E &&
+            Face::Down => self.min.1 <= E &&
                           self.min.0 <= E && self.max.0 >= 1.0 - E &&
                           self.min.2 <= E && self.max.2 >= 1.0 - E,
-            Face::YPos => self.max.1 >= 1.0 - E &&
+            Face::Up => self.max.1 >= 1.0 - E &&
                           self.min.0 <= E && self.max.0 >= 1.0 - E &&
                           self.min.2 <= E && self.max.2 >= 1.0 - E,
-            Face::ZNeg => self.min.2 <= E &&
+            Face::North => self.min.2 <= E &&
                           self.min.0 <= E && self.max.0 >= 1.0 - E &&
                           self.min.1 <= E && self.max.1 >= 1.0 - E,
-            Face::ZPos => self.max.2 >= 1.0 - E &&
+            Face::South => self.max.2 >= 1.0 - E &&
                           self.min.0 <= E && self.max.0 >= 1.0 - E &&
                           self.min.1 <= E && self.max.1 >= 1.0 - E,
         }
     }
 }
 
-/// Face direction
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum Face {
-    XNeg, XPos, // -X, +X
-    YNeg, YPos, // -Y (bottom), +Y (top)
-    ZNeg, ZPos, // -Z, +Z
-}
-
-impl Face {
-    pub const ALL: [Face; 6] = [Face::XNeg, Face::XPos, Face::YNeg, Face::YPos, Face::ZNeg, Face::ZPos];
-}
+/// Face of a block, named by the direction it faces
+pub type Face = Direction;
 
 /// Block geometry - one or more AABBs
 #[derive(Debug, Clone)]
@@ -200,8 +213,8 @@ pub const CAULDRON: AABB = AABB::new((0.0, 0.0, 0.0), (1.0, 1.0, 1.0)); // Simpl
 /// Anvil (simplified)
 pub const ANVIL: AABB = AABB::new((0.125, 0.0, 0.0), (0.875, 1.0, 1.0));
 
-/// Bell
-pub const BELL: AABB = AABB::new((0.25, 0.25, 0.25), (0.75, 1.0, 0.75));
+/// Bell (the bell itself, without what it hangs from)
+pub const BELL: AABB = AABB::new((0.25, 0.25, 0.25), (0.75, 0.8125, 0.75));
 
 /// Brewing stand
 pub const BREWING_STAND: AABB = AABB::new((0.0, 0.0, 0.0), (1.0, 0.875, 1.0));
@@ -217,6 +230,16 @@ pub const LANTERN_STANDING: AABB = AABB::new((0.3125, 0.0, 0.3125), (0.6875, 0.4
 /// Candle (single)
 pub const CANDLE: AABB = AABB::new((0.4375, 0.0, 0.4375), (0.5625, 0.375, 0.5625));
 
+/// Candles, by how many share the block (1 to 4)
+pub fn candles(count: u32) -> AABB {
+    match count {
+        2 => AABB::new((0.3125, 0.0, 0.375), (0.6875, 0.375, 0.5625)),
+        3 => AABB::new((0.3125, 0.0, 0.375), (0.625, 0.375, 0.6875)),
+        4 => AABB::new((0.3125, 0.0, 0.3125), (0.6875, 0.375, 0.625)),
+        _ => CANDLE,
+    }
+}
+
 /// Torch
 pub const TORCH_STANDING: AABB = AABB::new((0.4375, 0.0, 0.4375), (0.5625, 0.625, 0.5625));
 
@@ -263,6 +286,40 @@ pub fn lever(face: &str, facing: &str) -> AABB {
     }
 }
 
+/// A box standing on the face opposite `facing` and reaching `length` along
+/// it, `inset` in from the sides; both are in 1/16ths of a block, like the
+/// shapes of amethyst buds, pointed dripstone and rods
+pub fn pointing_box(facing: Direction, inset: f32, length: f32) -> AABB {
+    let (mut min, mut max) = ([inset / 16.0; 3], [1.0 - inset / 16.0; 3]);
+    let axis = facing.axis().index();
+    (min[axis], max[axis]) = if facing.is_positive() { (0.0, length / 16.0) } else { (1.0 - length / 16.0, 1.0) };
+    AABB::new((min[0], min[1], min[2]), (max[0], max[1], max[2]))
+}
+
+/// Bell, with the frame or beam it hangs from
+pub fn bell(attachment: &str, facing: Direction) -> BlockGeometry {
+    let along_z = facing.axis() == Axis::Z;
+    let holder = match attachment {
+        "floor" => {
+            return BlockGeometry::Single(if along_z {
+                AABB::new((0.0, 0.0, 0.25), (1.0, 1.0, 0.75))
+            } else {
+                AABB::new((0.25, 0.0, 0.0), (0.75, 1.0, 1.0))
+            });
+        }
+        "double_wall" if along_z => AABB::new((0.4375, 0.8125, 0.0), (0.5625, 0.9375, 1.0)),
+        "double_wall" => AABB::new((0.0, 0.8125, 0.4375), (1.0, 0.9375, 0.5625)),
+        "single_wall" => match facing {
+            Direction::North => AABB::new((0.4375, 0.8125, 0.0), (0.5625, 0.9375, 0.8125)),
+            Direction::South => AABB::new((0.4375, 0.8125, 0.1875), (0.5625, 0.9375, 1.0)),
+            Direction::East => AABB::new((0.1875, 0.8125, 0.4375), (1.0, 0.9375, 0.5625)),
+            _ => AABB::new((0.0, 0.8125, 0.4375), (0.8125, 0.9375, 0.5625)),
+        },
+        _ => AABB::new((0.4375, 0.8125, 0.4375), (0.5625, 1.0, 0.5625)),
+    };
+    BlockGeometry::Multi(vec![BELL, holder])
+}
+
 /// Rail (flat)
 pub const RAIL_FLAT: AABB = AABB::new((0.0, 0.0, 0.0), (1.0, 0.125, 1.0));
 
@@ -339,7 +396,7 @@ pub fn get_block_geometry(name: &str, properties: &HashMap<String, String>) -> B
     let name = name.strip_prefix("minecraft:").unwrap_or(name);
 
     // Air and related
-    if matches!(name, "air" | "cave_air" | "void_air") {
+    if crate::BlockClass::of(name) == crate::BlockClass::Air {
         return BlockGeometry::Empty;
     }
 
@@ -355,17 +412,17 @@ pub fn get_block_geometry(name: &str, properties: &HashMap<String, String>) -> B
 
     // Stairs
     if name.contains("stairs") {
-        let facing = properties.get("facing").map(|s| s.as_str()).unwrap_or("north");
-        let half = properties.get("half").map(|s| s.as_str()).unwrap_or("bottom");
+        let facing = properties.get_facing().map_or("north", Direction::name);
+        let half = properties.get_half().map_or("bottom", crate::block::Half::name);
         let shape = properties.get("shape").map(|s| s.as_str()).unwrap_or("straight");
         return stair_geometry(facing, half, shape);
     }
 
     // Doors
     if name.contains("door") && !name.contains("trapdoor") {
-        let facing = properties.get("facing").map(|s| s.as_str()).unwrap_or("north");
+        let facing = properties.get_facing().map_or("north", Direction::name);
         let hinge = properties.get("hinge").map(|s| s.as_str()).unwrap_or("left");
-        let open = properties.get("open").map(|s| s.as_str()).unwrap_or("false") == "true";
+        let open = properties.get_bool("open").unwrap_or(false);
 
         // Calculate actual facing based on hinge and open state
         let actual_facing = if open {
@@ -395,11 +452,11 @@ pub fn get_block_geometry(name: &str, properties: &HashMap<String, String>) -> B
 
     // Trapdoors
     if name.contains("trapdoor") {
-        let half = properties.get("half").map(|s| s.as_str()).unwrap_or("bottom");
-        let open = properties.get("open").map(|s| s.as_str()).unwrap_or("false") == "true";
+        let half = properties.get_half().map_or("bottom", crate::block::Half::name);
+        let open = properties.get_bool("open").unwrap_or(false);
 
         if open {
-            let facing = properties.get("facing").map(|s| s.as_str()).unwrap_or("north");
+            let facing = properties.get_facing().map_or("north", Direction::name);
             return BlockGeometry::Single(match facing {
                 "north" => LADDER_SOUTH, // When open, acts like a ladder
                 "south" => LADDER_NORTH,
@@ -415,8 +472,8 @@ pub fn get_block_geometry(name: &str, properties: &HashMap<String, String>) -> B
     // Fence gates
     if name.contains("fence_gate") {
         // Simplified - gates are thin when closed
-        let facing = properties.get("facing").map(|s| s.as_str()).unwrap_or("north");
-        let open = properties.get("open").map(|s| s.as_str()).unwrap_or("false") == "true";
+        let facing = properties.get_facing().map_or("north", Direction::name);
+        let open = properties.get_bool("open").unwrap_or(false);
 
         if open {
             // When open, gate is on the sides - very simplified
@@ -434,16 +491,16 @@ pub fn get_block_geometry(name: &str, properties: &HashMap<String, String>) -> B
         let mut boxes = vec![FENCE_POST];
 
         // Check connection properties
-        if properties.get("north").map(|s| s.as_str()) == Some("true") {
+        if properties.get_bool("north") == Some(true) {
             boxes.push(FENCE_NORTH);
         }
-        if properties.get("south").map(|s| s.as_str()) == Some("true") {
+        if properties.get_bool("south") == Some(true) {
             boxes.push(FENCE_SOUTH);
         }
-        if properties.get("west").map(|s| s.as_str()) == Some("true") {
+        if properties.get_bool("west") == Some(true) {
             boxes.push(FENCE_WEST);
         }
-        if properties.get("east").map(|s| s.as_str()) == Some("true") {
+        if properties.get_bool("east") == Some(true) {
             boxes.push(FENCE_EAST);
         }
 
@@ -485,10 +542,10 @@ pub fn get_block_geometry(name: &str, properties: &HashMap<String, String>) -> B
 
     // Glass panes, iron bars (read connection state from properties)
     if name.contains("pane") || name == "iron_bars" {
-        let north = properties.get("north").map(|s| s.as_str()) == Some("true");
-        let south = properties.get("south").map(|s| s.as_str()) == Some("true");
-        let west = properties.get("west").map(|s| s.as_str()) == Some("true");
-        let east = properties.get("east").map(|s| s.as_str()) == Some("true");
+        let north = properties.get_bool("north") == Some(true);
+        let south = properties.get_bool("south") == Some(true);
+        let west = properties.get_bool("west") == Some(true);
+        let east = properties.get_bool("east") == Some(true);
 
         let mut boxes = Vec::new();
 
@@ -534,9 +591,7 @@ pub fn get_block_geometry(name: &str, properties: &HashMap<String, String>) -> B
 
     // Snow layers
     if name == "snow" {
-        let layers: u8 = properties.get("layers")
-            .and_then(|s| s.parse().ok())
-            .unwrap_or(1);
+        let layers = properties.get_int("layers").map_or(1, |layers| layers.min(8) as u8);
         return BlockGeometry::Single(snow_layer(layers));
     }
 
@@ -548,40 +603,69 @@ pub fn get_block_geometry(name: &str, properties: &HashMap<String, String>) -> B
     // Buttons
     if name.contains("button") {
         let face = properties.get("face").map(|s| s.as_str()).unwrap_or("wall");
-        let facing = properties.get("facing").map(|s| s.as_str()).unwrap_or("north");
+        let facing = properties.get_facing().map_or("north", Direction::name);
         return BlockGeometry::Single(button(face, facing));
     }
 
     // Levers
     if name == "lever" {
         let face = properties.get("face").map(|s| s.as_str()).unwrap_or("wall");
-        let facing = properties.get("facing").map(|s| s.as_str()).unwrap_or("north");
+        let facing = properties.get_facing().map_or("north", Direction::name);
         return BlockGeometry::Single(lever(face, facing));
     }
 
     // Torches
     if name.contains("torch") {
         if name.contains("wall") {
-            let facing = properties.get("facing").map(|s| s.as_str()).unwrap_or("north");
+            let facing = properties.get_facing().map_or("north", Direction::name);
             return BlockGeometry::Single(wall_torch(facing));
         }
         return BlockGeometry::Single(TORCH_STANDING);
     }
 
-    // Lanterns
-    if name.contains("lantern") {
-        let hanging = properties.get("hanging").map(|s| s.as_str()).unwrap_or("false") == "true";
+    // Lanterns (sea lanterns and jack o'lanterns are full blocks)
+    if name.ends_with("lantern") && !matches!(name, "sea_lantern" | "jack_o_lantern") {
+        let hanging = properties.get_bool("hanging").unwrap_or(false);
         return BlockGeometry::Single(if hanging { LANTERN_HANGING } else { LANTERN_STANDING });
     }
 
     // Candles
     if name.contains("candle") {
-        return BlockGeometry::Single(CANDLE);
+        return BlockGeometry::Single(candles(properties.get_int("candles").unwrap_or(1)));
+    }
+
+    // Amethyst buds and clusters grow out of the block they're on
+    let amethyst = match name {
+        "small_amethyst_bud" => Some((4.0, 3.0)),
+        "medium_amethyst_bud" => Some((3.0, 4.0)),
+        "large_amethyst_bud" => Some((3.0, 5.0)),
+        "amethyst_cluster" => Some((3.0, 7.0)),
+        _ => None,
+    };
+    if let Some((inset, length)) = amethyst {
+        let facing = properties.get_facing().unwrap_or(Direction::Up);
+        return BlockGeometry::Single(pointing_box(facing, inset, length));
+    }
+
+    // Pointed dripstone narrows from its base to the tip
+    if name == "pointed_dripstone" {
+        let direction = match properties.get("vertical_direction").map(|s| s.as_str()) {
+            Some("down") => Direction::Down,
+            _ => Direction::Up,
+        };
+        let (inset, length) = match properties.get("thickness").map(|s| s.as_str()) {
+            Some("tip") => (5.0, 11.0),
+            Some("tip_merge") => (5.0, 16.0),
+            Some("middle") => (3.0, 16.0),
+            Some("base") => (2.0, 16.0),
+            _ => (4.0, 16.0),
+        };
+        return BlockGeometry::Single(pointing_box(direction, inset, length));
     }
 
     // Ladders
     if name == "ladder" {
-        let facing = properties.get("facing").map(|s| s.as_str()).unwrap_or("north");
+        let facing = properties.get_facing().map_or("north", Direction::name);
         return BlockGeometry::Single(match facing {
             "north" => LADDER_NORTH,
             "south" => LADDER_SOUTH,
@@ -648,7 +732,8 @@ pub fn get_block_geometry(name: &str, properties: &HashMap<String, String>) -> B
 
     // Bell
     if name == "bell" {
-        return BlockGeometry::Single(BELL);
+        let attachment = properties.get("attachment").map(|s| s.as_str()).unwrap_or("floor");
+        return bell(attachment, properties.get_facing().unwrap_or(Direction::North));
     }
 
     // Flower pot
@@ -656,17 +741,16 @@ pub fn get_block_geometry(name: &str, properties: &HashMap<String, String>) -> B
         return BlockGeometry::Single(FLOWER_POT);
     }
 
-    // Chain
-    if name == "chain" {
-        return BlockGeometry::Single(CHAIN_Y);
+    // Chains (iron and copper) run along their axis
+    if name == "chain" || name.ends_with("_chain") {
+        let axis = properties.get_axis().unwrap_or(Axis::Y);
+        return BlockGeometry::Single(pointing_box(Direction::from_axis(axis, true), 6.5, 16.0));
     }
 
-    // End rod, lightning rod
-    if name == "end_rod" {
-        return BlockGeometry::Single(END_ROD_Y);
-    }
-    if name == "lightning_rod" {
-        return BlockGeometry::Single(LIGHTNING_ROD);
+    // End rods and lightning rods (including the copper ages) point along their facing
+    if name == "end_rod" || name.ends_with("lightning_rod") {
+        let facing = properties.get_facing().unwrap_or(Direction::Up);
+        return BlockGeometry::Single(pointing_box(facing, 6.0, 16.0));
     }
 
     // Signs (very thin)
@@ -725,6 +809,78 @@ pub fn block_covers_face(name: &str, properties: &HashMap<String, String>, face:
     get_block_geometry(name, properties).covers_face(face)
 }
 
+/// Blocks that fill their space but can be seen through (glass, leaves, ice,
+/// water, ...), so they don't hide the faces of blocks next to them
+pub fn is_see_through(name: &str) -> bool {
+    let name = name.strip_prefix("minecraft:").unwrap_or(name);
+    name.contains("glass")
+        || name.contains("leaves")
+        || matches!(
+            name,
+            "ice" | "frosted_ice" | "water" | "bubble_column" | "slime_block" | "honey_block"
+                | "spawner" | "beacon" | "barrier" | "structure_void" | "light" | "mangrove_roots"
+                | "cobweb"
+        )
+}
+
+/// See-through blocks whose faces between two of the same kind aren't drawn,
+/// like vanilla does for glass, ice and water
+fn culls_same_kind(name: &str) -> bool {
+    let name = name.strip_prefix("minecraft:").unwrap_or(name);
+    name.ends_with("glass") || matches!(name, "ice" | "frosted_ice" | "water")
+}
+
+/// Whether `neighbor` hides `block`'s face touching it; `face` is the
+/// neighbor's own face on that side
+///
+/// Opaque neighbors hide whatever part of the face they cover. See-through
+/// neighbors only hide faces of the same block, e.g. between two panes of
+/// the same stained glass.
+pub fn hides_face(block: &crate::Block, neighbor: &crate::Block, face: Face) -> bool {
+    if neighbor.is_effectively_empty() {
+        return false;
+    }
+    if is_see_through(&neighbor.name) {
+        return neighbor.name == block.name && culls_same_kind(&block.name);
+    }
+    block_covers_face(&neighbor.name, &neighbor.state.properties, face)
+}
+
+/// Whether `neighbor` hides the `face` side of one box of `block`: that side
+/// lies on the block's boundary and one of the neighbor's boxes covers it
+/// there. Transparency works as in [`hides_face`].
+pub fn hides_box_face(block: &crate::Block, aabb: &AABB, neighbor: &crate::Block, face: Face) -> bool {
+    const E: f32 = 0.001;
+
+    let Some((min, max)) = aabb.boundary_face(face) else { return false };
+    if neighbor.is_effectively_empty() {
+        return false;
+    }
+    if is_see_through(&neighbor.name) && !(neighbor.name == block.name && culls_same_kind(&block.name)) {
+        return false;
+    }
+    get_block_geometry(&neighbor.name, &neighbor.state.properties).get_boxes().iter().any(|other| {
+        other.boundary_face(face.opposite()).is_some_and(|(other_min, other_max)| {
+            (0..2).all(|i| other_min[i] <= min[i] + E && other_max[i] >= max[i] - E)
+        })
+    })
+}
+
+/// Check if a block has at least one exposed face: it's on the schematic's boundary
+/// or a neighbor doesn't hide the face touching it (see [`hides_face`])
+pub fn is_exposed(schematic: &crate::UnifiedSchematic, x: u32, y: u32, z: u32) -> bool {
+    let (w, h, l) = (schematic.width, schematic.height, schematic.length);
+    if x == 0 || x + 1 >= w || y == 0 || y + 1 >= h || z == 0 || z + 1 >= l {
+        return true;
+    }
+    // The neighbor's face that touches us is opposite to our face
+    let Some(block) = schematic.get_block(x, y, z) else { return false };
+    Face::ALL.into_iter().any(|face| match schematic.get_neighbor((x, y, z), face) {
+        Some(neighbor) => !hides_face(block, neighbor, face.opposite()),
+        None => true,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -743,8 +899,8 @@ mod tests {
 
         let geom = get_block_geometry("stone_slab", &props);
         assert!(matches!(geom, BlockGeometry::Single(_)));
-        assert!(geom.covers_face(Face::YNeg));
-        assert!(!geom.covers_face(Face::YPos));
+        assert!(geom.covers_face(Face::Down));
+        assert!(!geom.covers_face(Face::Up));
     }
 
     #[test]
@@ -764,4 +920,30 @@ mod tests {
         let geom = get_block_geometry("oak_stairs", &props);
         assert!(matches!(geom, BlockGeometry::Multi(_)));
     }
+
+    #[test]
+    fn test_decorative_blocks() {
+        let props = |pairs: &[(&str, &str)]| -> HashMap<String, String> {
+            pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+        };
+        let single = |name: &str, pairs: &[(&str, &str)]| match get_block_geometry(name, &props(pairs)) {
+            BlockGeometry::Single(aabb) => (aabb.min, aabb.max),
+            other => panic!("{} is {:?}", name, other),
+        };
+
+        assert_eq!(single("candle", &[("candles", "3")]), ((0.3125, 0.0, 0.375), (0.625, 0.375, 0.6875)));
+        assert_eq!(single("amethyst_cluster", &[("facing", "down")]), ((0.1875, 0.5625, 0.1875), (0.8125, 1.0, 0.8125)));
+        assert_eq!(single("small_amethyst_bud", &[("facing", "east")]), ((0.0, 0.25, 0.25), (0.1875, 0.75, 0.75)));
+        let tip = [("vertical_direction", "down"), ("thickness", "tip")];
+        assert_eq!(single("pointed_dripstone", &tip), ((0.3125, 0.3125, 0.3125), (0.6875, 1.0, 0.6875)));
+        assert_eq!(single("chain", &[("axis", "x")]), ((0.0, 0.40625, 0.40625), (1.0, 0.59375, 0.59375)));
+        assert_eq!(single("lightning_rod", &[("facing", "north")]), ((0.375, 0.375, 0.0), (0.625, 0.625, 1.0)));
+        assert_eq!(single("lantern", &[("hanging", "true")]), (LANTERN_HANGING.min, LANTERN_HANGING.max));
+        assert!(matches!(get_block_geometry("sea_lantern", &props(&[])), BlockGeometry::Full));
+
+        let bell = get_block_geometry("bell", &props(&[("attachment", "single_wall"), ("facing", "east")]));
+        assert_eq!(bell.get_boxes().len(), 2);
+        assert!(!bell.covers_face(Face::East));
+        assert_eq!(single("bell", &[("attachment", "floor"), ("facing", "west")]), ((0.25, 0.0, 0.0), (0.75, 1.0, 1.0)));
+    }
 }
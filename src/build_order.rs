@@ -0,0 +1,136 @@
+//! Layer-by-layer placement lists for building by hand
+//!
+//! Blocks are listed bottom-up as runs of identical blocks along X. Within a
+//! layer, free-standing blocks come first and attached blocks (torches,
+//! buttons, rails, carpets, doors, ...) after them, so whatever they hang on is
+//! already placed. Blocks hanging from the ceiling move to the layer of the
+//! block they hang from. Technical halves that appear by themselves (upper
+//! door halves, bed heads) are left out.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use crate::attachment::support_pos;
+pub use crate::attachment::{attachment, Attachment};
+use crate::{Block, UnifiedSchematic};
+
+/// Halves placed automatically together with their other half
+fn is_technical_half(block: &Block) -> bool {
+    let name = block.display_name();
+    let prop = |key: &str| block.get_property(key).map(String::as_str);
+    (name.ends_with("_door") && !name.ends_with("trapdoor") && prop("half") == Some("upper"))
+        || (name.ends_with("_bed") && prop("part") == Some("head"))
+}
+
+/// A run of identical blocks along X
+#[derive(Debug, Clone, PartialEq)]
+pub struct Placement<'a> {
+    pub from: (u32, u32, u32),
+    pub to: (u32, u32, u32),
+    pub block: &'a Block,
+    pub count: u32,
+}
+
+impl fmt::Display for Placement<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (a, b) = (self.from, self.to);
+        if self.count == 1 {
+            write!(f, "y={}: at ({},{},{}) {}", a.1, a.0, a.1, a.2, self.block.full_name())
+        } else {
+            write!(f, "y={}: from ({},{},{}) to ({},{},{}) {} \u{d7}{}",
+                a.1, a.0, a.1, a.2, b.0, b.1, b.2, self.block.full_name(), self.count)
+        }
+    }
+}
+
+/// Merge X-adjacent identical blocks (given in YZX order) into runs
+fn runs<'a>(blocks: impl IntoIterator<Item = ((u32, u32, u32), &'a Block)>) -> Vec<Placement<'a>> {
+    let mut runs: Vec<Placement> = Vec::new();
+    for (pos, block) in blocks {
+        if let Some(last) = runs.last_mut() {
+            let (x, y, z) = last.to;
+            if (x + 1, y, z) == pos && last.block == block {
+                last.to = pos;
+                last.count += 1;
+                continue;
+            }
+        }
+        runs.push(Placement { from: pos, to: pos, block, count: 1 });
+    }
+    runs
+}
+
+/// Placements in buildable order, grouped by the layer they're placed in
+pub fn build_order(schem: &UnifiedSchematic) -> BTreeMap<u32, Vec<Placement<'_>>> {
+    let mut free: BTreeMap<u32, Vec<_>> = BTreeMap::new();
+    let mut attached: BTreeMap<u32, Vec<_>> = BTreeMap::new();
+
+    for (pos, block) in schem.iter_blocks() {
+        if block.is_air() || is_technical_half(block) {
+            continue;
+        }
+        if attachment(block).is_some() {
+            // Hanging blocks wait for the layer their support is in
+            let layer = support_pos(block, pos).map_or(pos.1, |s| s.1.max(pos.1));
+            attached.entry(layer).or_default().push((pos, block));
+        } else {
+            free.entry(pos.1).or_default().push((pos, block));
+        }
+    }
+
+    let mut layers: BTreeMap<u32, Vec<Placement>> = BTreeMap::new();
+    for (y, blocks) in free {
+        layers.entry(y).or_default().extend(runs(blocks));
+    }
+    for (y, blocks) in attached {
+        layers.entry(y).or_default().extend(runs(blocks));
+    }
+    layers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::BlockState;
+    use crate::builder::SchematicBuilder;
+
+    fn with_props(name: &str, props: &[(&str, &str)]) -> Block {
+        let properties = props.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        Block::with_state(name, BlockState { properties })
+    }
+
+    #[test]
+    fn test_attached_blocks_follow_support() {
+        let mut builder = SchematicBuilder::new(4, 3, 2);
+        builder.fill((0, 0, 0), (3, 0, 0), Block::new("minecraft:stone_bricks")).unwrap();
+        builder.set_block(0, 1, 0, Block::new("minecraft:torch")).unwrap();
+        builder.set_block(1, 1, 0, Block::new("minecraft:oak_planks")).unwrap();
+        // Wall torch on the south face of the planks
+        builder.set_block(1, 1, 1, with_props("minecraft:wall_torch", &[("facing", "south")])).unwrap();
+        builder.set_block(2, 1, 0, with_props("minecraft:oak_door", &[("half", "lower")])).unwrap();
+        builder.set_block(2, 2, 0, with_props("minecraft:oak_door", &[("half", "upper")])).unwrap();
+        builder.set_block(1, 2, 0, Block::new("minecraft:stone")).unwrap();
+        builder.set_block(0, 2, 1, Block::new("minecraft:glass")).unwrap();
+        // Button under the stone at y=2 has to wait for layer 2
+        builder.set_block(3, 1, 0, with_props("minecraft:stone_button", &[("face", "ceiling")])).unwrap();
+        builder.set_block(3, 2, 0, Block::new("minecraft:stone")).unwrap();
+        let schem = builder.build();
+
+        let order = build_order(&schem);
+        let lines = |y: u32| order[&y].iter().map(|p| p.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(lines(0), ["y=0: from (0,0,0) to (3,0,0) minecraft:stone_bricks \u{d7}4"]);
+
+        let layer1 = lines(1);
+        assert_eq!(layer1[0], "y=1: at (1,1,0) minecraft:oak_planks");
+        assert!(layer1.iter().any(|l| l.contains("wall_torch")));
+        assert!(layer1.iter().any(|l| l.contains("oak_door")));
+        assert!(!layer1.iter().any(|l| l.contains("button")));
+
+        // The upper door half is skipped and the button comes after its stone
+        let layer2 = lines(2);
+        assert!(!layer2.iter().any(|l| l.contains("oak_door")));
+        assert!(layer2.last().unwrap().contains("stone_button"));
+        let stone = layer2.iter().position(|l| l.contains("(3,2,0) minecraft:stone")).unwrap();
+        assert!(stone < layer2.len() - 1);
+    }
+}
@@ -0,0 +1,191 @@
+//! Programmatic construction of schematics
+//!
+//! `SchematicBuilder` takes care of the YZX block ordering used by
+//! `UnifiedSchematic`, so generators can place blocks by coordinate.
+
+use crate::{checked_volume, Block, BlockEntity, Entity, Metadata, SchemError, SchematicFormat, UnifiedSchematic};
+
+/// Builder for a `UnifiedSchematic` of fixed dimensions
+///
+/// Positions that are never set stay air.
+#[derive(Debug)]
+pub struct SchematicBuilder {
+    width: u32,
+    height: u32,
+    length: u32,
+    offset: (i32, i32, i32),
+    format: SchematicFormat,
+    blocks: Vec<Block>,
+    block_entities: Vec<BlockEntity>,
+    entities: Vec<Entity>,
+    metadata: Metadata,
+}
+
+impl SchematicBuilder {
+    /// Create a builder filled with air
+    ///
+    /// # Panics
+    /// If the schematic would be too large; use [`SchematicBuilder::try_new`] to handle that.
+    pub fn new(width: u32, height: u32, length: u32) -> Self {
+        Self::try_new(width, height, length).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Create a builder filled with air, or `SchemError::TooLarge` if the volume can't be allocated
+    pub fn try_new(width: u32, height: u32, length: u32) -> Result<Self, SchemError> {
+        let volume = checked_volume(width, height, length)?;
+        Ok(Self {
+            width,
+            height,
+            length,
+            offset: (0, 0, 0),
+            format: SchematicFormat::SpongeV3,
+            blocks: vec![Block::air(); volume],
+            block_entities: Vec::new(),
+            entities: Vec::new(),
+            metadata: Metadata::default(),
+        })
+    }
+
+    /// Index of a position in the YZX block array
+    fn index(&self, x: u32, y: u32, z: u32) -> Result<usize, SchemError> {
+        if x >= self.width || y >= self.height || z >= self.length {
+            return Err(SchemError::OutOfBounds(x as i64, y as i64, z as i64));
+        }
+        // Can't overflow: the volume was checked in `try_new`
+        Ok((y as usize * self.length as usize + z as usize) * self.width as usize + x as usize)
+    }
+
+    /// Place a block at a position
+    pub fn set_block(&mut self, x: u32, y: u32, z: u32, block: Block) -> Result<&mut Self, SchemError> {
+        let index = self.index(x, y, z)?;
+        self.blocks[index] = block;
+        Ok(self)
+    }
+
+    /// Fill the box between two corners (inclusive) with a block
+    pub fn fill(&mut self, min: (u32, u32, u32), max: (u32, u32, u32), block: Block) -> Result<&mut Self, SchemError> {
+        let (x0, x1) = (min.0.min(max.0), min.0.max(max.0));
+        let (y0, y1) = (min.1.min(max.1), min.1.max(max.1));
+        let (z0, z1) = (min.2.min(max.2), min.2.max(max.2));
+
+        // Validate both corners before touching anything
+        self.index(x0, y0, z0)?;
+        self.index(x1, y1, z1)?;
+
+        for y in y0..=y1 {
+            for z in z0..=z1 {
+                for x in x0..=x1 {
+                    let index = self.index(x, y, z)?;
+                    self.blocks[index] = block.clone();
+                }
+            }
+        }
+        Ok(self)
+    }
+
+    /// Add a block entity; its position must lie inside the schematic
+    pub fn add_block_entity(&mut self, block_entity: BlockEntity) -> Result<&mut Self, SchemError> {
+        let (x, y, z) = block_entity.pos;
+        let in_bounds = x >= 0 && y >= 0 && z >= 0
+            && (x as u32) < self.width && (y as u32) < self.height && (z as u32) < self.length;
+        if !in_bounds {
+            return Err(SchemError::OutOfBounds(x as i64, y as i64, z as i64));
+        }
+        self.block_entities.push(block_entity);
+        Ok(self)
+    }
+
+    /// Add an entity (entities may sit outside the block grid)
+    pub fn add_entity(&mut self, entity: Entity) -> &mut Self {
+        self.entities.push(entity);
+        self
+    }
+
+    /// Replace the metadata
+    pub fn set_metadata(&mut self, metadata: Metadata) -> &mut Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Set the world position of local (0, 0, 0)
+    pub fn set_offset(&mut self, x: i32, y: i32, z: i32) -> &mut Self {
+        self.offset = (x, y, z);
+        self
+    }
+
+    /// Set the format reported by the built schematic (defaults to Sponge v3)
+    pub fn set_format(&mut self, format: SchematicFormat) -> &mut Self {
+        self.format = format;
+        self
+    }
+
+    /// Finish building
+    pub fn build(self) -> UnifiedSchematic {
+        UnifiedSchematic {
+            format: self.format,
+            width: self.width,
+            height: self.height,
+            length: self.length,
+            offset: self.offset,
+            blocks: self.blocks,
+            block_entities: self.block_entities,
+            entities: self.entities,
+            metadata: self.metadata,
+            load_report: Default::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_and_fill() {
+        let mut builder = SchematicBuilder::new(4, 3, 2);
+        builder.set_block(1, 2, 1, Block::new("minecraft:stone")).unwrap();
+        builder.fill((0, 0, 0), (3, 0, 1), Block::new("minecraft:dirt")).unwrap();
+        let schem = builder.build();
+
+        assert_eq!(schem.get_block(1, 2, 1).unwrap().name, "minecraft:stone");
+        assert_eq!(schem.get_block(3, 0, 1).unwrap().name, "minecraft:dirt");
+        assert!(schem.get_block(0, 1, 0).unwrap().is_air());
+    }
+
+    #[test]
+    fn test_out_of_bounds() {
+        let mut builder = SchematicBuilder::new(2, 2, 2);
+        assert!(matches!(
+            builder.set_block(2, 0, 0, Block::new("minecraft:stone")),
+            Err(SchemError::OutOfBounds(2, 0, 0))
+        ));
+        assert!(builder.fill((0, 0, 0), (1, 5, 1), Block::air()).is_err());
+        let be = BlockEntity { id: "minecraft:chest".to_string(), pos: (-1, 0, 0), ..Default::default() };
+        assert!(builder.add_block_entity(be).is_err());
+    }
+
+    #[test]
+    fn test_too_large() {
+        assert!(matches!(
+            SchematicBuilder::try_new(u32::MAX, u32::MAX, u32::MAX),
+            Err(SchemError::TooLarge { .. })
+        ));
+        assert!(SchematicBuilder::try_new(i32::MAX as u32 + 1, 1, 1).is_err());
+        // Long map-art strips exceed the old u16 limit on one axis
+        let builder = SchematicBuilder::try_new(70_000, 1, 2).unwrap();
+        assert_eq!(builder.build().get_block(69_999, 0, 1).map(|b| b.is_air()), Some(true));
+    }
+
+    #[test]
+    fn test_world_coordinates() {
+        let mut builder = SchematicBuilder::new(2, 2, 2);
+        builder.set_offset(-100, 60, 5).set_block(1, 0, 1, Block::new("minecraft:stone")).unwrap();
+        let schem = builder.build();
+
+        assert_eq!(schem.to_world(1, 0, 1), (-99, 60, 6));
+        assert_eq!(schem.to_local(-99, 60, 6), Some((1, 0, 1)));
+        assert_eq!(schem.get_block_world(-99, 60, 6).unwrap().name, "minecraft:stone");
+        assert_eq!(schem.to_local(-101, 60, 5), None);
+        assert_eq!(schem.to_local(-100, 62, 5), None);
+    }
+}
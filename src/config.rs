@@ -0,0 +1,174 @@
+//! CLI defaults from `config.toml` (part of the schem-tool binary)
+//!
+//! Loaded from `<config dir>/schem-tool/config.toml` (e.g. `~/.config` on Linux) or
+//! the path given with `--config`. Every key is optional, and explicit command-line
+//! flags always win over the file:
+//!
+//! ```toml
+//! minecraft = "~/.minecraft"
+//! resource_pack = "~/packs/faithful.zip"
+//! color = "auto"          # auto | always | never
+//! plain = false           # ASCII table borders
+//! biome = "plains"        # grass/foliage tint for textured exports
+//!
+//! [render]
+//! textures = true
+//! models = false
+//! greedy = true
+//! hollow = false
+//! ```
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use schem_tool::textures::Biome;
+
+/// When to emit ANSI colors (`--color` and the `color` key)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorChoice {
+    /// Color when stdout is a terminal and NO_COLOR is unset
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    /// Resolve to on/off given the `NO_COLOR` variable and whether stdout is a terminal
+    pub fn enabled(self, no_color: bool, is_terminal: bool) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => !no_color && is_terminal,
+        }
+    }
+}
+
+/// Default switches for the render-* commands
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RenderDefaults {
+    pub textures: Option<bool>,
+    pub models: Option<bool>,
+    pub greedy: Option<bool>,
+    pub hollow: Option<bool>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    pub minecraft: Option<PathBuf>,
+    pub resource_pack: Option<PathBuf>,
+    pub color: Option<ColorChoice>,
+    pub plain: Option<bool>,
+    pub biome: Option<String>,
+    #[serde(default)]
+    pub render: RenderDefaults,
+
+    /// File the config was read from, if any
+    #[serde(skip)]
+    pub source: Option<PathBuf>,
+}
+
+/// Default config location, e.g. `~/.config/schem-tool/config.toml`
+pub fn default_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|p| p.join("schem-tool").join("config.toml"))
+}
+
+/// Expand a leading `~/` to the home directory
+fn expand_home(path: PathBuf) -> PathBuf {
+    match (path.strip_prefix("~"), dirs::home_dir()) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => path,
+    }
+}
+
+impl Config {
+    /// Load `explicit` (which must exist) or the default file (which may be missing)
+    pub fn load(explicit: Option<&Path>) -> Result<Self> {
+        let path = match explicit {
+            Some(p) => p.to_path_buf(),
+            None => match default_path() {
+                Some(p) if p.exists() => p,
+                _ => return Ok(Self::default()),
+            },
+        };
+
+        let text = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read config file {}", path.display()))?;
+        let mut config = Self::parse(&text)
+            .with_context(|| format!("Invalid config file {}", path.display()))?;
+        config.source = Some(path);
+        Ok(config)
+    }
+
+    /// Parse and validate config text
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut config: Config = toml::from_str(text)?;
+
+        if let Some(ref biome) = config.biome {
+            biome.parse::<Biome>()
+                .map_err(|e| anyhow::anyhow!("key 'biome': {}", e))?;
+        }
+        config.minecraft = config.minecraft.map(expand_home);
+        config.resource_pack = config.resource_pack.map(expand_home);
+
+        Ok(config)
+    }
+
+    /// Biome tint, already validated by `parse`
+    pub fn biome(&self) -> Option<Biome> {
+        self.biome.as_deref().and_then(|b| b.parse().ok())
+    }
+
+    /// The effective configuration as TOML
+    pub fn to_toml(&self) -> Result<String> {
+        Ok(toml::to_string_pretty(self)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_full_config() {
+        let config = Config::parse(r#"
+            minecraft = "/opt/minecraft/client.jar"
+            color = "never"
+            biome = "minecraft:jungle"
+
+            [render]
+            textures = true
+            greedy = true
+        "#).unwrap();
+
+        assert_eq!(config.minecraft, Some(PathBuf::from("/opt/minecraft/client.jar")));
+        assert_eq!(config.color, Some(ColorChoice::Never));
+        assert_eq!(config.biome(), Some(Biome::Jungle));
+        assert_eq!(config.render.textures, Some(true));
+        assert_eq!(config.render.models, None);
+    }
+
+    #[test]
+    fn test_errors_name_the_bad_key() {
+        let err = format!("{:#}", Config::parse("biome = \"moon\"").unwrap_err());
+        assert!(err.contains("biome"), "{}", err);
+
+        let err = format!("{:#}", Config::parse("[render]\ntextures = true\nshadows = true").unwrap_err());
+        assert!(err.contains("shadows"), "{}", err);
+
+        let err = format!("{:#}", Config::parse("color = \"sometimes\"").unwrap_err());
+        assert!(err.contains("color"), "{}", err);
+    }
+
+    #[test]
+    fn test_color_choice() {
+        assert!(ColorChoice::Always.enabled(true, false));
+        assert!(!ColorChoice::Never.enabled(false, true));
+        assert!(ColorChoice::Auto.enabled(false, true));
+        assert!(!ColorChoice::Auto.enabled(true, true));
+        assert!(!ColorChoice::Auto.enabled(false, false));
+    }
+}
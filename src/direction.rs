@@ -0,0 +1,210 @@
+//! Directions, axes and halves shared by block properties, geometry and models
+//!
+//! Block states (`facing`, `axis`, `half`), face culling in [`block_geometry`]
+//! and blockstate rotations in [`mc_models`] all name the same six directions,
+//! so they use one [`Direction`] type. Rotations follow the blockstate files:
+//! `y: 90` turns north to east, `x: 90` turns up to north.
+//!
+//! [`block_geometry`]: crate::block_geometry
+//! [`mc_models`]: crate::mc_models
+
+use std::str::FromStr;
+
+/// One of the six directions, as in a block's `facing` or the face of a cube
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// -X
+    West,
+    /// +X
+    East,
+    /// -Y
+    Down,
+    /// +Y
+    Up,
+    /// -Z
+    North,
+    /// +Z
+    South,
+}
+
+impl Direction {
+    /// Every direction, by axis with the negative side first; exporters emit
+    /// faces in this order
+    pub const ALL: [Direction; 6] =
+        [Direction::West, Direction::East, Direction::Down, Direction::Up, Direction::North, Direction::South];
+
+    /// The property value, like `north`
+    pub fn name(self) -> &'static str {
+        match self {
+            Direction::West => "west",
+            Direction::East => "east",
+            Direction::Down => "down",
+            Direction::Up => "up",
+            Direction::North => "north",
+            Direction::South => "south",
+        }
+    }
+
+    /// The direction along `axis`, towards its positive end if `positive`
+    pub fn from_axis(axis: Axis, positive: bool) -> Self {
+        match (axis, positive) {
+            (Axis::X, false) => Direction::West,
+            (Axis::X, true) => Direction::East,
+            (Axis::Y, false) => Direction::Down,
+            (Axis::Y, true) => Direction::Up,
+            (Axis::Z, false) => Direction::North,
+            (Axis::Z, true) => Direction::South,
+        }
+    }
+
+    pub fn axis(self) -> Axis {
+        match self {
+            Direction::West | Direction::East => Axis::X,
+            Direction::Down | Direction::Up => Axis::Y,
+            Direction::North | Direction::South => Axis::Z,
+        }
+    }
+
+    /// Whether this points towards the positive end of its axis
+    pub fn is_positive(self) -> bool {
+        matches!(self, Direction::East | Direction::Up | Direction::South)
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::from_axis(self.axis(), !self.is_positive())
+    }
+
+    /// One block step this way, as (x, y, z)
+    pub fn to_offset(self) -> (i32, i32, i32) {
+        let step = if self.is_positive() { 1 } else { -1 };
+        match self.axis() {
+            Axis::X => (step, 0, 0),
+            Axis::Y => (0, step, 0),
+            Axis::Z => (0, 0, step),
+        }
+    }
+
+    /// Turn by `degrees` around the Y axis, clockwise seen from above (north
+    /// to east), as a blockstate's `y` does; angles that aren't a multiple of
+    /// 90 leave the direction as it is
+    pub fn rotate_y(self, degrees: i32) -> Self {
+        self.rotate(degrees, [Direction::North, Direction::East, Direction::South, Direction::West])
+    }
+
+    /// Turn by `degrees` around the X axis (up to north), as a blockstate's
+    /// `x` does
+    pub fn rotate_x(self, degrees: i32) -> Self {
+        self.rotate(degrees, [Direction::Up, Direction::North, Direction::Down, Direction::South])
+    }
+
+    /// Step `degrees / 90` places along `ring` if this direction is on it
+    fn rotate(self, degrees: i32, ring: [Direction; 4]) -> Self {
+        let degrees = degrees.rem_euclid(360);
+        if degrees % 90 != 0 {
+            return self;
+        }
+        match ring.iter().position(|&direction| direction == self) {
+            Some(i) => ring[(i + degrees as usize / 90) % 4],
+            None => self,
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        Direction::ALL
+            .into_iter()
+            .find(|direction| direction.name() == s)
+            .ok_or_else(|| format!("unknown direction '{}'", s))
+    }
+}
+
+/// A block's `axis` (logs, pillars, chains, ...)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+impl Axis {
+    pub const ALL: [Axis; 3] = [Axis::X, Axis::Y, Axis::Z];
+
+    /// The property value, like `y`
+    pub fn name(self) -> &'static str {
+        match self {
+            Axis::X => "x",
+            Axis::Y => "y",
+            Axis::Z => "z",
+        }
+    }
+
+    /// 0, 1 or 2, for indexing (x, y, z) triples
+    pub fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// A block's `half`: `top` or `bottom` on stairs and trapdoors, `upper` or
+/// `lower` on doors and tall plants
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Half {
+    Top,
+    Bottom,
+}
+
+impl Half {
+    /// The stairs and trapdoor value, `top` or `bottom`
+    pub fn name(self) -> &'static str {
+        match self {
+            Half::Top => "top",
+            Half::Bottom => "bottom",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANGLES: [i32; 4] = [0, 90, 180, 270];
+
+    #[test]
+    fn test_rotation_composition() {
+        for direction in Direction::ALL {
+            assert_eq!(direction.name().parse(), Ok(direction));
+            assert_eq!(direction.opposite().opposite(), direction);
+            let (x, y, z) = direction.to_offset();
+            assert_eq!(direction.opposite().to_offset(), (-x, -y, -z));
+            assert_eq!(Direction::from_axis(direction.axis(), direction.is_positive()), direction);
+
+            for a in ANGLES {
+                // Turning preserves opposites, and a turn is undone by the reverse turn
+                assert_eq!(direction.rotate_y(a).opposite(), direction.opposite().rotate_y(a));
+                assert_eq!(direction.rotate_x(a).opposite(), direction.opposite().rotate_x(a));
+                assert_eq!(direction.rotate_y(a).rotate_y(-a), direction);
+                assert_eq!(direction.rotate_x(a).rotate_x(-a), direction);
+                assert_eq!(direction.rotate_y(a + 360), direction.rotate_y(a));
+                for b in ANGLES {
+                    assert_eq!(direction.rotate_y(a).rotate_y(b), direction.rotate_y(a + b));
+                    assert_eq!(direction.rotate_x(a).rotate_x(b), direction.rotate_x(a + b));
+                }
+            }
+
+            // Turning matches rotating the offset: y by 90 maps (x, z) to (-z, x)
+            let turned = direction.rotate_y(90).to_offset();
+            assert_eq!(turned, (-z, y, x));
+            let tipped = direction.rotate_x(90).to_offset();
+            assert_eq!(tipped, (x, z, -y));
+            assert_eq!(direction.rotate_y(45), direction);
+        }
+
+        assert_eq!(Direction::North.rotate_y(90), Direction::East);
+        assert_eq!(Direction::Up.rotate_x(90), Direction::North);
+        assert_eq!(Direction::Up.rotate_y(90), Direction::Up);
+        assert_eq!(Direction::East.rotate_x(270), Direction::East);
+        assert!("sideways".parse::<Direction>().is_err());
+    }
+}
@@ -0,0 +1,259 @@
+//! Converting a schematic for an older release
+//!
+//! [`downgrade`] finds the blocks [introduced](crate::versions::introduced_in)
+//! after the target release and swaps each for a substitute that exists there.
+//! A [`ModMapping`] (the same `[blocks]` table `strip-mods` reads) is asked
+//! first, then the builtin substitutes: newer woods become older ones (cherry
+//! and pale oak to birch, mangrove to jungle, bamboo to oak, crimson to dark oak,
+//! warped to spruce), deepslate variants become their stone counterparts, and
+//! `short_grass` is `grass` again. Substitutes that are still too new are looked
+//! up again, so `cherry_sign` ends up as `sign` for 1.13.
+//!
+//! Blocks without a substitute are irreplaceable; they stay unless the caller
+//! asks for the mapping's placeholder. Block state properties other releases
+//! renamed (like wall sides before 1.16) aren't converted.
+
+use std::collections::{BTreeMap, HashSet};
+
+use crate::mods::ModMapping;
+use crate::versions::{introduced_in, Release};
+use crate::{Block, BlockEntityPolicy, UnifiedSchematic};
+
+/// Same block shape under an older name; properties are kept
+const RENAMED: &[(&str, &str)] = &[
+    ("short_grass", "grass"),
+    ("crimson_stem", "dark_oak_log"), ("stripped_crimson_stem", "stripped_dark_oak_log"),
+    ("crimson_hyphae", "dark_oak_wood"), ("stripped_crimson_hyphae", "stripped_dark_oak_wood"),
+    ("warped_stem", "spruce_log"), ("stripped_warped_stem", "stripped_spruce_log"),
+    ("warped_hyphae", "spruce_wood"), ("stripped_warped_hyphae", "stripped_spruce_wood"),
+    ("bamboo_block", "oak_log"), ("stripped_bamboo_block", "stripped_oak_log"),
+    ("bamboo_mosaic_stairs", "oak_stairs"), ("bamboo_mosaic_slab", "oak_slab"),
+    ("polished_deepslate_wall", "andesite_wall"),
+];
+
+/// A different block that looks close enough; properties are dropped
+const REPLACED: &[(&str, &str)] = &[
+    ("deepslate", "stone"), ("reinforced_deepslate", "deepslate"), ("infested_deepslate", "infested_stone"),
+    ("polished_deepslate", "polished_andesite"), ("chiseled_deepslate", "chiseled_stone_bricks"),
+    ("deepslate_tiles", "stone_bricks"), ("cracked_deepslate_tiles", "cracked_stone_bricks"),
+    ("bamboo_mosaic", "oak_planks"), ("potted_bamboo", "flower_pot"),
+    ("mangrove_roots", "jungle_fence"), ("muddy_mangrove_roots", "coarse_dirt"),
+    ("mangrove_propagule", "jungle_sapling"), ("potted_mangrove_propagule", "potted_jungle_sapling"),
+    ("crimson_nylium", "netherrack"), ("warped_nylium", "netherrack"), ("warped_wart_block", "nether_wart_block"),
+    ("crimson_fungus", "red_mushroom"), ("warped_fungus", "brown_mushroom"),
+    ("potted_crimson_fungus", "potted_red_mushroom"), ("potted_warped_fungus", "potted_brown_mushroom"),
+    ("crimson_roots", "dead_bush"), ("warped_roots", "dead_bush"),
+    ("potted_crimson_roots", "potted_dead_bush"), ("potted_warped_roots", "potted_dead_bush"),
+];
+
+/// Part of the name swapped for another material, first match only;
+/// properties are kept
+const SWAPS: &[(&str, &str)] = &[
+    ("pale_oak", "birch"), ("cherry", "birch"), ("mangrove", "jungle"), ("bamboo_", "oak_"),
+    ("crimson", "dark_oak"), ("warped", "spruce"),
+    ("cobbled_deepslate", "cobblestone"), ("polished_deepslate", "polished_andesite"),
+    ("deepslate_bricks", "stone_bricks"), ("deepslate_brick", "stone_brick"), ("deepslate_tile", "stone_brick"),
+    ("deepslate_", ""),
+];
+
+/// A builtin substitute for a block, which may itself be too new
+pub fn builtin_substitute(block: &Block) -> Option<Block> {
+    let name = block.name.strip_prefix("minecraft:").unwrap_or(&block.name);
+    let renamed = |to: &str| Block::with_state(format!("minecraft:{}", to), block.state.clone());
+    if let Some((_, to)) = RENAMED.iter().find(|(from, _)| *from == name) {
+        return Some(renamed(to));
+    }
+    if let Some((_, to)) = REPLACED.iter().find(|(from, _)| *from == name) {
+        return Some(Block::new(format!("minecraft:{}", to)));
+    }
+    if let Some((from, to)) = SWAPS.iter().find(|(from, _)| name.contains(from)) {
+        return Some(renamed(&name.replacen(from, to, 1)));
+    }
+    // Before 1.14 every sign was an oak sign named `sign`
+    if name.ends_with("_wall_sign") && !name.ends_with("_hanging_sign") {
+        return Some(renamed("wall_sign"));
+    }
+    if name.ends_with("_sign") && !name.ends_with("_hanging_sign") {
+        return Some(renamed("sign"));
+    }
+    None
+}
+
+/// Where a substitute came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Source {
+    /// The mapping named it
+    Map,
+    /// One of the builtin substitutes
+    Builtin,
+    /// The mapping's placeholder for an irreplaceable block
+    Placeholder,
+}
+
+/// One kind of substitution made by [`downgrade`]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct Substitution {
+    /// Block name too new for the target
+    pub from: String,
+    /// Block name it was replaced with
+    pub to: String,
+    /// Number of blocks replaced
+    pub count: usize,
+    pub source: Source,
+}
+
+/// What [`downgrade`] changed and couldn't change
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct DowngradeReport {
+    /// Release converted for
+    pub target: &'static str,
+    /// Substitutions, by most blocks first
+    pub substitutions: Vec<Substitution>,
+    /// Blocks too new for the target without a substitute, with counts, by
+    /// most blocks first; replaced with the placeholder if asked to
+    pub irreplaceable: Vec<(String, usize)>,
+    /// Block entities removed with their blocks
+    pub block_entities: usize,
+}
+
+impl DowngradeReport {
+    /// Blocks replaced in total
+    pub fn blocks(&self) -> usize {
+        self.substitutions.iter().map(|s| s.count).sum()
+    }
+}
+
+/// Replace every block in `schematic` that `target` doesn't have, and lower
+/// the declared `DataVersion` to the target's
+///
+/// Irreplaceable blocks become the mapping's placeholder when `force` is set
+/// and are left alone otherwise. Block entities stay with builtin substitutes
+/// (a birch sign keeps a cherry sign's text) and are removed with mapped and
+/// placeholder ones, as in `strip-mods`.
+pub fn downgrade(schematic: &mut UnifiedSchematic, target: &Release, mapping: &ModMapping, force: bool) -> DowngradeReport {
+    let too_new = |name: &str| introduced_in(name).is_some_and(|release| release.data_version > target.data_version);
+    let counts = schematic.block_counts();
+    let newer: HashSet<&String> = counts.keys().filter(|name| too_new(name)).collect();
+
+    // The substitute for a block and where it came from, following builtin
+    // substitutes until one exists in the target
+    let resolve = |block: &Block| -> Option<(Block, Source)> {
+        let mut current = block.clone();
+        let mut source = Source::Builtin;
+        for _ in 0..4 {
+            current = match mapping.substitute(&current.name) {
+                (substitute, true) => {
+                    source = Source::Map;
+                    substitute.clone()
+                }
+                _ => builtin_substitute(&current)?,
+            };
+            if !too_new(&current.name) {
+                return Some((current, source));
+            }
+        }
+        None
+    };
+
+    // Builtin substitutes first, keeping block entities; what's left too new
+    // is mapped or irreplaceable, which only depends on the name
+    let mut irreplaceable: BTreeMap<String, usize> = BTreeMap::new();
+    let block_entities = schematic.block_entities.len();
+    let builtin = schematic.replace_blocks(BlockEntityPolicy::Keep, |block| {
+        if !newer.contains(&block.name) {
+            return None;
+        }
+        match resolve(block) {
+            Some((substitute, Source::Builtin)) => Some(substitute),
+            Some(_) => None,
+            None => {
+                *irreplaceable.entry(block.name.clone()).or_default() += 1;
+                None
+            }
+        }
+    });
+    let mapped = schematic.replace_blocks(BlockEntityPolicy::Remove, |block| {
+        if !newer.contains(&block.name) {
+            return None;
+        }
+        match resolve(block) {
+            Some((substitute, _)) => Some(substitute),
+            None => force.then(|| mapping.placeholder().clone()),
+        }
+    });
+
+    if schematic.metadata.data_version.is_none_or(|declared| declared > target.data_version) {
+        schematic.metadata.data_version = Some(target.data_version);
+    }
+
+    let source = |from: &String| if irreplaceable.contains_key(from) { Source::Placeholder } else { Source::Map };
+    let mut substitutions: Vec<Substitution> = builtin.into_iter()
+        .map(|((from, to), count)| Substitution { from, to, count, source: Source::Builtin })
+        .chain(mapped.into_iter().map(|((from, to), count)| Substitution { source: source(&from), from, to, count }))
+        .collect();
+    substitutions.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.from.cmp(&b.from)));
+    let mut irreplaceable: Vec<(String, usize)> = irreplaceable.into_iter().collect();
+    irreplaceable.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    DowngradeReport {
+        target: target.name,
+        substitutions,
+        irreplaceable,
+        block_entities: block_entities - schematic.block_entities.len(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::SchematicBuilder;
+    use crate::schem::Schem;
+    use crate::versions::find_release;
+    use crate::{BlockEntity, Metadata};
+
+    #[test]
+    fn test_downgrade() {
+        let mut builder = SchematicBuilder::new(6, 1, 1);
+        builder.set_block(0, 0, 0, Schem::parse_block_state("minecraft:cherry_stairs[facing=east,half=bottom]")).unwrap();
+        builder.set_block(1, 0, 0, Block::new("minecraft:cherry_sign")).unwrap();
+        builder.set_block(2, 0, 0, Block::new("minecraft:deepslate_brick_wall")).unwrap();
+        builder.set_block(3, 0, 0, Block::new("minecraft:decorated_pot")).unwrap();
+        builder.set_block(4, 0, 0, Block::new("minecraft:copper_block")).unwrap();
+        builder.set_block(5, 0, 0, Block::new("minecraft:stone")).unwrap();
+        builder.add_block_entity(BlockEntity { id: "minecraft:sign".to_string(), pos: (1, 0, 0), ..Default::default() }).unwrap();
+        builder.add_block_entity(BlockEntity { id: "minecraft:decorated_pot".to_string(), pos: (3, 0, 0), ..Default::default() }).unwrap();
+        builder.set_metadata(Metadata { data_version: Some(3953), ..Default::default() });
+        let schem = builder.build();
+        let target = find_release("1.16.5").unwrap();
+        let mapping = ModMapping::new().with_substitute("minecraft:decorated_pot", "minecraft:flower_pot");
+
+        let mut unforced = schem.clone();
+        let report = downgrade(&mut unforced, target, &mapping, false);
+        assert_eq!(unforced.get_block(0, 0, 0).unwrap().full_name(), "minecraft:birch_stairs[facing=east,half=bottom]");
+        assert_eq!(unforced.get_block(1, 0, 0).unwrap().name, "minecraft:birch_sign");
+        assert_eq!(unforced.get_block(2, 0, 0).unwrap().name, "minecraft:stone_brick_wall");
+        assert_eq!(unforced.get_block(3, 0, 0).unwrap().name, "minecraft:flower_pot");
+        assert_eq!(unforced.get_block(4, 0, 0).unwrap().name, "minecraft:copper_block");
+        assert_eq!(report.irreplaceable, [("minecraft:copper_block".to_string(), 1)]);
+        assert_eq!(report.blocks(), 4);
+        let pot = report.substitutions.iter().find(|s| s.from == "minecraft:decorated_pot").unwrap();
+        assert_eq!(pot.source, Source::Map);
+        // The sign keeps its text, the pot's data goes
+        assert_eq!(report.block_entities, 1);
+        assert_eq!(unforced.block_entities[0].pos, (1, 0, 0));
+        assert_eq!(unforced.metadata.data_version, Some(target.data_version));
+
+        let mut forced = schem;
+        let report = downgrade(&mut forced, target, &mapping.with_placeholder("minecraft:iron_block"), true);
+        assert_eq!(forced.get_block(4, 0, 0).unwrap().name, "minecraft:iron_block");
+        assert_eq!(report.substitutions.iter().filter(|s| s.source == Source::Placeholder).count(), 1);
+        assert_eq!(report.irreplaceable.len(), 1);
+
+        let old = |name: &str| builtin_substitute(&Block::new(name)).map(|block| block.name);
+        assert_eq!(old("minecraft:short_grass").as_deref(), Some("minecraft:grass"));
+        assert_eq!(old("minecraft:deepslate_iron_ore").as_deref(), Some("minecraft:iron_ore"));
+        assert_eq!(old("minecraft:birch_wall_sign").as_deref(), Some("minecraft:wall_sign"));
+        assert_eq!(old("minecraft:cherry_hanging_sign").as_deref(), Some("minecraft:birch_hanging_sign"));
+        assert_eq!(old("minecraft:birch_hanging_sign"), None);
+    }
+}
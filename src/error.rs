@@ -1,3 +1,6 @@
+use std::path::PathBuf;
+#[cfg(feature = "fs")]
+use std::path::Path;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -8,6 +11,9 @@ pub enum SchemError {
     #[error("NBT parsing error: {0}")]
     Nbt(#[from] fastnbt::error::Error),
 
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
     #[error("Unknown schematic format")]
     UnknownFormat,
 
@@ -22,4 +28,92 @@ pub enum SchemError {
 
     #[error("Invalid block data at index {0}")]
     InvalidBlockData(usize),
+
+    /// Block data that can't be decoded; `expected`/`actual` are block counts
+    /// for a short stream, or palette size and offending id for a bad index
+    #[error("Corrupt block data at byte {offset}: {reason}")]
+    CorruptData {
+        offset: usize,
+        reason: String,
+        expected: usize,
+        actual: usize,
+    },
+
+    #[error("Operation cancelled")]
+    Cancelled,
+
+    #[error("Position ({0}, {1}, {2}) is outside the schematic")]
+    OutOfBounds(i64, i64, i64),
+
+    #[error("Schematic of {width}x{height}x{length} blocks is too large to load")]
+    TooLarge {
+        width: u32,
+        height: u32,
+        length: u32,
+    },
+
+    #[error("Failed to write {}: {source}", path.display())]
+    ExportIo {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("Texture error in {}: {message}", path.display())]
+    TextureError {
+        path: PathBuf,
+        message: String,
+    },
+
+    #[error("Model error in {}: {message}", path.display())]
+    ModelError {
+        path: PathBuf,
+        message: String,
+    },
+
+    #[error("Region file error in {}: {message}", path.display())]
+    RegionError {
+        path: PathBuf,
+        message: String,
+    },
+
+    #[error("SNBT error at line {line}, column {column}: {message}")]
+    Snbt {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+
+    #[error("Signature error: {0}")]
+    Signature(String),
+}
+
+impl SchemError {
+    #[cfg(feature = "fs")]
+    pub(crate) fn export_io(path: &Path, source: std::io::Error) -> Self {
+        SchemError::ExportIo { path: path.to_path_buf(), source }
+    }
+
+    #[cfg(feature = "fs")]
+    pub(crate) fn texture(path: &Path, message: impl std::fmt::Display) -> Self {
+        SchemError::TextureError { path: path.to_path_buf(), message: message.to_string() }
+    }
+
+    #[cfg(feature = "fs")]
+    pub(crate) fn model(path: &Path, message: impl std::fmt::Display) -> Self {
+        SchemError::ModelError { path: path.to_path_buf(), message: message.to_string() }
+    }
+
+    #[cfg(feature = "fs")]
+    pub(crate) fn region(path: &Path, message: impl std::fmt::Display) -> Self {
+        SchemError::RegionError { path: path.to_path_buf(), message: message.to_string() }
+    }
+
+    #[cfg(feature = "fs")]
+    /// Attach the file being written to a bare I/O error
+    pub(crate) fn at_path(self, path: &Path) -> Self {
+        match self {
+            SchemError::Io(source) => SchemError::export_io(path, source),
+            other => other,
+        }
+    }
 }
@@ -3,20 +3,31 @@
 //! Supports exporting to OBJ format with MTL materials and optional textures
 //! Includes greedy meshing algorithm for dramatically reduced polygon counts
 
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::io::{BufWriter, Write};
 use std::path::Path;
-use indicatif::{ProgressBar, ProgressStyle};
-use crate::UnifiedSchematic;
+use std::sync::Mutex;
+use crate::{Block, BlockSelector, SchemError, UnifiedSchematic};
+use crate::progress::{self, NoProgress, Progress};
+use crate::random::RandomContext;
 use crate::textures::TextureManager;
 use crate::block_geometry::{self, Face};
+use crate::direction::Axis;
 use crate::mc_models::{self, ModelManager, GeneratedQuad};
 
+/// Color for blocks missing from the builtin table
+pub const DEFAULT_COLOR: (f32, f32, f32) = (0.5, 0.5, 0.5);
+
 /// Block color mapping (approximate Minecraft colors)
 pub fn get_block_color(name: &str) -> (f32, f32, f32) {
+    builtin_color(name).unwrap_or(DEFAULT_COLOR)
+}
+
+fn builtin_color(name: &str) -> Option<(f32, f32, f32)> {
     let name = name.strip_prefix("minecraft:").unwrap_or(name);
 
-    match name {
+    let color = match name {
         // Stone variants
         "stone" => (0.5, 0.5, 0.5),
         "cobblestone" | "mossy_cobblestone" => (0.45, 0.45, 0.45),
@@ -182,34 +193,651 @@ pub fn get_block_color(name: &str) -> (f32, f32, f32) {
         "hopper" => (0.4, 0.4, 0.45),
         "water" => (0.2, 0.4, 0.8),
         "lava" => (0.9, 0.45, 0.1),
-        _ => (0.5, 0.5, 0.5),
+        _ => return None,
+    };
+    Some(color)
+}
+
+/// Sets of blocks to build voxelized models and pixel art from, with the colors
+/// they're matched by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BlockPalette {
+    /// The 16 wool colors
+    #[default]
+    Wool,
+    /// The 16 concrete colors
+    Concrete,
+    /// Ten shades from white concrete to black concrete
+    Grayscale,
+    /// One block per map color, as it shows on a map when placed flat (no
+    /// staircasing for the lighter and darker shades)
+    MapArt,
+}
+
+const DYES: [&str; 16] = [
+    "white", "light_gray", "gray", "black", "brown", "red", "orange", "yellow",
+    "lime", "green", "cyan", "light_blue", "blue", "purple", "magenta", "pink",
+];
+
+/// Map base colors (1.17+) with a full block that has each; water is left out
+/// since its shade depends on depth
+const MAP_COLORS: [(&str, [u8; 3]); 60] = [
+    ("grass_block", [127, 178, 56]),
+    ("sandstone", [247, 233, 163]),
+    ("mushroom_stem", [199, 199, 199]),
+    ("redstone_block", [255, 0, 0]),
+    ("packed_ice", [160, 160, 255]),
+    ("iron_block", [167, 167, 167]),
+    ("oak_leaves[persistent=true]", [0, 124, 0]),
+    ("white_concrete", [255, 255, 255]),
+    ("clay", [164, 168, 184]),
+    ("dirt", [151, 109, 77]),
+    ("stone", [112, 112, 112]),
+    ("oak_planks", [143, 119, 72]),
+    ("quartz_block", [255, 252, 245]),
+    ("orange_concrete", [216, 127, 51]),
+    ("magenta_concrete", [178, 76, 216]),
+    ("light_blue_concrete", [102, 153, 216]),
+    ("yellow_concrete", [229, 229, 51]),
+    ("lime_concrete", [127, 204, 25]),
+    ("pink_concrete", [242, 127, 165]),
+    ("gray_concrete", [76, 76, 76]),
+    ("light_gray_concrete", [153, 153, 153]),
+    ("cyan_concrete", [76, 127, 153]),
+    ("purple_concrete", [127, 63, 178]),
+    ("blue_concrete", [51, 76, 178]),
+    ("brown_concrete", [102, 76, 51]),
+    ("green_concrete", [102, 127, 51]),
+    ("red_concrete", [153, 51, 51]),
+    ("black_concrete", [25, 25, 25]),
+    ("gold_block", [250, 238, 77]),
+    ("diamond_block", [92, 219, 213]),
+    ("lapis_block", [74, 128, 255]),
+    ("emerald_block", [0, 217, 58]),
+    ("spruce_planks", [129, 86, 49]),
+    ("netherrack", [112, 2, 0]),
+    ("white_terracotta", [209, 177, 161]),
+    ("orange_terracotta", [159, 82, 36]),
+    ("magenta_terracotta", [149, 87, 108]),
+    ("light_blue_terracotta", [112, 108, 138]),
+    ("yellow_terracotta", [186, 133, 36]),
+    ("lime_terracotta", [103, 117, 53]),
+    ("pink_terracotta", [160, 77, 78]),
+    ("gray_terracotta", [57, 41, 35]),
+    ("light_gray_terracotta", [135, 107, 98]),
+    ("cyan_terracotta", [87, 92, 92]),
+    ("purple_terracotta", [122, 73, 88]),
+    ("blue_terracotta", [76, 62, 92]),
+    ("brown_terracotta", [76, 50, 35]),
+    ("green_terracotta", [76, 82, 42]),
+    ("red_terracotta", [142, 60, 46]),
+    ("black_terracotta", [37, 22, 16]),
+    ("crimson_nylium", [189, 48, 49]),
+    ("crimson_planks", [148, 63, 97]),
+    ("crimson_hyphae", [92, 25, 29]),
+    ("warped_nylium", [22, 126, 134]),
+    ("warped_planks", [58, 142, 140]),
+    ("warped_hyphae", [86, 44, 62]),
+    ("warped_wart_block", [20, 180, 133]),
+    ("cobbled_deepslate", [100, 100, 100]),
+    ("raw_iron_block", [216, 175, 147]),
+    ("verdant_froglight", [127, 167, 150]),
+];
+
+impl BlockPalette {
+    pub const ALL: [BlockPalette; 4] = [BlockPalette::Wool, BlockPalette::Concrete, BlockPalette::Grayscale, BlockPalette::MapArt];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            BlockPalette::Wool => "wool",
+            BlockPalette::Concrete => "concrete",
+            BlockPalette::Grayscale => "grayscale",
+            BlockPalette::MapArt => "map-art",
+        }
+    }
+
+    /// Block states in the palette with their colors: the builtin table's, or
+    /// for [`BlockPalette::MapArt`] the flat map shade (220/255 of the base color)
+    pub fn blocks(self) -> Vec<(String, (f32, f32, f32))> {
+        let names: Vec<String> = match self {
+            BlockPalette::Wool => DYES.iter().map(|dye| format!("minecraft:{}_wool", dye)).collect(),
+            BlockPalette::Concrete => DYES.iter().map(|dye| format!("minecraft:{}_concrete", dye)).collect(),
+            BlockPalette::Grayscale => [
+                "white_concrete", "calcite", "light_gray_wool", "light_gray_concrete", "stone",
+                "gray_wool", "gray_concrete", "smooth_basalt", "polished_blackstone", "black_concrete",
+            ].iter().map(|name| format!("minecraft:{}", name)).collect(),
+            BlockPalette::MapArt => {
+                let shade = |c: u8| (c as u32 * 220 / 255) as f32 / 255.0;
+                return MAP_COLORS.iter()
+                    .map(|(name, [r, g, b])| (format!("minecraft:{}", name), (shade(*r), shade(*g), shade(*b))))
+                    .collect();
+            }
+        };
+        names.into_iter().map(|name| {
+            let color = get_block_color(&name);
+            (name, color)
+        }).collect()
+    }
+}
+
+impl std::fmt::Display for BlockPalette {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for BlockPalette {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        BlockPalette::ALL.into_iter().find(|p| p.name() == s).ok_or_else(|| {
+            let names: Vec<_> = BlockPalette::ALL.iter().map(|p| p.name()).collect();
+            format!("unknown palette '{}' (expected one of: {})", s, names.join(", "))
+        })
+    }
+}
+
+/// sRGB (components 0-1) to CIELAB, D65 white
+pub fn rgb_to_lab((r, g, b): (f32, f32, f32)) -> [f32; 3] {
+    let linear = |c: f32| if c <= 0.04045 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) };
+    let (r, g, b) = (linear(r.clamp(0.0, 1.0)), linear(g.clamp(0.0, 1.0)), linear(b.clamp(0.0, 1.0)));
+    let x = (0.4124 * r + 0.3576 * g + 0.1805 * b) / 0.95047;
+    let y = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+    let z = (0.0193 * r + 0.1192 * g + 0.9505 * b) / 1.08883;
+    let f = |t: f32| if t > 0.008856 { t.cbrt() } else { 7.787 * t + 16.0 / 116.0 };
+    let (fx, fy, fz) = (f(x), f(y), f(z));
+    [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+}
+
+/// Nearest block to a color, out of a fixed set, by distance in CIELAB
+#[derive(Debug, Clone)]
+pub struct ColorMatcher {
+    blocks: Vec<MatcherBlock>,
+}
+
+#[derive(Debug, Clone)]
+struct MatcherBlock {
+    block: Block,
+    rgb: (f32, f32, f32),
+    lab: [f32; 3],
+}
+
+impl ColorMatcher {
+    /// Match against block states (like `minecraft:oak_leaves[persistent=true]`)
+    /// with their colors, e.g. [`BlockPalette::blocks`]
+    pub fn new(blocks: impl IntoIterator<Item = (String, (f32, f32, f32))>) -> Self {
+        let blocks = blocks.into_iter()
+            .map(|(state, rgb)| MatcherBlock { block: crate::schem::Schem::parse_block_state(&state), rgb, lab: rgb_to_lab(rgb) })
+            .collect();
+        Self { blocks }
+    }
+
+    /// The block closest to `rgb` and that block's own color, or None for an
+    /// empty matcher
+    pub fn nearest(&self, rgb: (f32, f32, f32)) -> Option<(&Block, (f32, f32, f32))> {
+        let lab = rgb_to_lab(rgb);
+        let distance = |other: &[f32; 3]| (0..3).map(|i| (other[i] - lab[i]).powi(2)).sum::<f32>();
+        self.blocks.iter()
+            .min_by(|a, b| distance(&a.lab).total_cmp(&distance(&b.lab)))
+            .map(|entry| (&entry.block, entry.rgb))
     }
 }
 
-/// Create a progress bar with consistent styling
-fn create_progress_bar(total: u64, message: &str) -> ProgressBar {
-    let pb = ProgressBar::new(total);
-    pb.set_style(
-        ProgressStyle::default_bar()
-            .template("{msg} [{bar:40.cyan/blue}] {pos}/{len} ({percent}%) {elapsed_precise}")
-            .unwrap()
-            .progress_chars("=>-")
-    );
-    pb.set_message(message.to_string());
-    pb
+/// Block colors for exports: user overrides, then averaged texture colors,
+/// then the builtin table
+///
+/// Patterns are block names where `*` matches any run of characters, e.g.
+/// `create:*_casing`; names without a namespace mean `minecraft:`. Exact names
+/// win over patterns, and later patterns over earlier ones. Blocks that end up
+/// with [`DEFAULT_COLOR`] are remembered and listed by [`ColorMap::unknown`].
+#[derive(Debug, Default)]
+pub struct ColorMap {
+    exact: HashMap<String, (f32, f32, f32)>,
+    patterns: Vec<(String, (f32, f32, f32))>,
+    textures: HashMap<String, (f32, f32, f32)>,
+    unknown: Mutex<BTreeSet<String>>,
 }
 
-/// Face direction for greedy meshing
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-enum FaceDir {
-    XNeg, XPos,  // -X, +X
-    YNeg, YPos,  // -Y (bottom), +Y (top)
-    ZNeg, ZPos,  // -Z, +Z
+impl ColorMap {
+    /// Only the builtin table
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Color blocks matching `pattern` with `rgb` (components 0-1)
+    pub fn insert(&mut self, pattern: &str, rgb: (f32, f32, f32)) {
+        let pattern = if pattern.contains(':') { pattern.to_string() } else { format!("minecraft:{}", pattern) };
+        if pattern.contains('*') {
+            self.patterns.push((pattern, rgb));
+        } else {
+            self.exact.insert(pattern, rgb);
+        }
+    }
+
+    /// Color `names` by averaging their textures; returns how many had a texture
+    pub fn add_texture_colors<'n>(&mut self, textures: &TextureManager, names: impl IntoIterator<Item = &'n str>) -> usize {
+        let mut added = 0;
+        for name in names {
+            if let Some(rgb) = textures.average_color(name) {
+                self.textures.insert(name.to_string(), rgb);
+                added += 1;
+            }
+        }
+        added
+    }
+
+    /// Number of override entries
+    pub fn len(&self) -> usize {
+        self.exact.len() + self.patterns.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The overriding color for `name`, if any entry matches
+    pub fn get_override(&self, name: &str) -> Option<(f32, f32, f32)> {
+        if let Some(&rgb) = self.exact.get(name) {
+            return Some(rgb);
+        }
+        self.patterns.iter().rev()
+            .find(|(pattern, _)| wildcard_match(pattern, name))
+            .map(|&(_, rgb)| rgb)
+    }
+
+    /// Color for a block name, falling back to the builtin table and then gray
+    pub fn get(&self, name: &str) -> (f32, f32, f32) {
+        self.get_override(name)
+            .or_else(|| self.textures.get(name).copied())
+            .or_else(|| builtin_color(name))
+            .unwrap_or_else(|| {
+                self.unknown.lock().unwrap_or_else(|e| e.into_inner()).insert(name.to_string());
+                DEFAULT_COLOR
+            })
+    }
+
+    /// Blocks looked up so far that had no color, sorted by name
+    pub fn unknown(&self) -> Vec<String> {
+        self.unknown.lock().unwrap_or_else(|e| e.into_inner()).iter().cloned().collect()
+    }
 }
 
-impl FaceDir {
-    fn all() -> [FaceDir; 6] {
-        [FaceDir::XNeg, FaceDir::XPos, FaceDir::YNeg, FaceDir::YPos, FaceDir::ZNeg, FaceDir::ZPos]
+/// Match `name` against a pattern where `*` stands for any run of characters
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or("");
+    let Some(mut rest) = name.strip_prefix(first) else { return false };
+    let mut parts: Vec<&str> = parts.collect();
+    let Some(last) = parts.pop() else { return rest.is_empty() };
+    for part in parts {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+/// Why a block didn't come out as its model and textures describe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MissingKind {
+    /// No blockstate file (or no variant for its state) in the jar or resource pack
+    Blockstate,
+    /// A model the blockstate names couldn't be loaded or resolved
+    Model,
+    /// A texture the block uses wasn't found
+    Texture,
+}
+
+impl MissingKind {
+    /// Key in [`ExportReport::to_json`]
+    pub fn key(self) -> &'static str {
+        match self {
+            MissingKind::Blockstate => "missing_blockstate",
+            MissingKind::Model => "unresolvable_model",
+            MissingKind::Texture => "missing_texture",
+        }
+    }
+
+    fn describe(self) -> &'static str {
+        match self {
+            MissingKind::Blockstate => "had no model definition",
+            MissingKind::Model => "had a model that failed to resolve",
+            MissingKind::Texture => "had missing textures",
+        }
+    }
+}
+
+/// Blocks that fell back or were skipped in model and texture exports, by
+/// reason, with how many of each
+///
+/// Pass one to [`ExportOptions::with_report`]; exporters add to it as they finish.
+#[derive(Debug, Default)]
+pub struct ExportReport {
+    missing: Mutex<BTreeMap<MissingKind, BTreeMap<String, usize>>>,
+}
+
+impl ExportReport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn add(&self, kind: MissingKind, counts: &HashMap<String, usize>) {
+        let mut missing = self.missing.lock().unwrap_or_else(|e| e.into_inner());
+        let blocks = missing.entry(kind).or_default();
+        for (name, &count) in counts {
+            *blocks.entry(name.clone()).or_default() += count;
+        }
+    }
+
+    /// Block names with their counts, most common first
+    pub fn blocks(&self, kind: MissingKind) -> Vec<(String, usize)> {
+        let missing = self.missing.lock().unwrap_or_else(|e| e.into_inner());
+        let mut blocks: Vec<(String, usize)> = missing.get(&kind)
+            .map(|blocks| blocks.iter().map(|(name, &count)| (name.clone(), count)).collect())
+            .unwrap_or_default();
+        blocks.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        blocks
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.missing.lock().unwrap_or_else(|e| e.into_inner()).values().all(BTreeMap::is_empty)
+    }
+
+    /// `{"missing_blockstate": {"mod:block": 12, ...}, "unresolvable_model": {...}, "missing_texture": {...}}`
+    pub fn to_json(&self) -> serde_json::Value {
+        let missing = self.missing.lock().unwrap_or_else(|e| e.into_inner());
+        let categories = [MissingKind::Blockstate, MissingKind::Model, MissingKind::Texture].map(|kind| {
+            let blocks = missing.get(&kind).map(|blocks| serde_json::json!(blocks)).unwrap_or_else(|| serde_json::json!({}));
+            (kind.key().to_string(), blocks)
+        });
+        serde_json::Value::Object(categories.into_iter().collect())
+    }
+}
+
+/// How OBJ exports split their faces into objects (`o` lines), so importers
+/// can show, hide and select parts separately
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ObjGroups {
+    /// One object per material
+    #[default]
+    Material,
+    /// One object per 16x16x16 region, named `chunk_<x>_<y>_<z>`
+    Chunk,
+    /// Everything in one object
+    None,
+}
+
+impl ObjGroups {
+    pub const ALL: [ObjGroups; 3] = [ObjGroups::Material, ObjGroups::Chunk, ObjGroups::None];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ObjGroups::Material => "material",
+            ObjGroups::Chunk => "chunk",
+            ObjGroups::None => "none",
+        }
+    }
+}
+
+impl std::fmt::Display for ObjGroups {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for ObjGroups {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ObjGroups::ALL.into_iter().find(|g| g.name() == s).ok_or_else(|| {
+            let names: Vec<_> = ObjGroups::ALL.iter().map(|g| g.name()).collect();
+            format!("unknown grouping '{}' (expected one of: {})", s, names.join(", "))
+        })
+    }
+}
+
+/// Box of local block positions to export, both corners included
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExportRegion {
+    pub min: (i32, i32, i32),
+    pub max: (i32, i32, i32),
+}
+
+impl ExportRegion {
+    /// The box between two opposite corners, given in any order
+    pub fn new(a: (i32, i32, i32), b: (i32, i32, i32)) -> Self {
+        Self {
+            min: (a.0.min(b.0), a.1.min(b.1), a.2.min(b.2)),
+            max: (a.0.max(b.0), a.1.max(b.1), a.2.max(b.2)),
+        }
+    }
+
+    /// The part of the box inside `schematic` as inclusive `(min, max)` ranges
+    /// for x, y and z, or `None` when the box misses the schematic
+    pub fn clamp(&self, schematic: &UnifiedSchematic) -> Option<[(u32, u32); 3]> {
+        let axis = |min: i32, max: i32, extent: u32| {
+            (max >= 0 && (min as i64) < extent as i64)
+                .then(|| (min.max(0) as u32, (max as u32).min(extent - 1)))
+        };
+        Some([
+            axis(self.min.0, self.max.0, schematic.width)?,
+            axis(self.min.1, self.max.1, schematic.height)?,
+            axis(self.min.2, self.max.2, schematic.length)?,
+        ])
+    }
+}
+
+/// Exploded view: every slice of `every` Y layers moves up by `gap` blocks
+/// more than the one below it, so floors can be looked at apart. Only moves
+/// geometry; materials and counts stay the same. GLB exports only.
+///
+/// Liquid surfaces are still culled against the liquid in the next slice.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Explode {
+    pub gap: f32,
+    pub every: u32,
+}
+
+impl Explode {
+    /// Pull every layer apart by `gap` blocks
+    pub fn new(gap: f32) -> Self {
+        Self { gap, every: 1 }
+    }
+
+    /// Move `layers` layers at a time instead of one
+    pub fn every(mut self, layers: u32) -> Self {
+        self.every = layers.max(1);
+        self
+    }
+
+    /// How far blocks in layer `y` move up
+    pub fn lift(&self, y: u32) -> f32 {
+        (y / self.every.max(1)) as f32 * self.gap
+    }
+
+    /// Whether layer `y` is the bottom or top layer of its slice, so the
+    /// faces on that side are visible once the slices are apart
+    pub fn at_slice_edge(&self, y: u32) -> bool {
+        let every = self.every.max(1);
+        let layer = y % every;
+        layer == 0 || layer == every - 1
+    }
+}
+
+/// Options shared by all exporters
+///
+/// Build with `ExportOptions::new()` and the `with_*` methods; the default
+/// reports no progress and never cancels.
+#[derive(Clone, Copy)]
+pub struct ExportOptions<'a> {
+    /// Receiver for progress updates and cancellation checks
+    pub progress: &'a dyn Progress,
+    /// Block colors; `None` uses the builtin table
+    pub colors: Option<&'a ColorMap>,
+    /// Export technical blocks (barriers, structure voids, ...) instead of
+    /// treating them as air
+    pub include_technical: bool,
+    /// Where to collect blocks without a model or texture
+    pub report: Option<&'a ExportReport>,
+    /// How OBJ exports split faces into objects
+    pub obj_groups: ObjGroups,
+    /// Only export this box; everything outside it counts as air, and the
+    /// box's min corner becomes the model's origin
+    pub region: Option<ExportRegion>,
+    /// Only export blocks matching this
+    pub only: Option<&'a BlockSelector>,
+    /// Leave out blocks matching this
+    pub exclude: Option<&'a BlockSelector>,
+    /// Pull Y layers apart (GLB only)
+    pub explode: Option<Explode>,
+    /// Seeds for choosing between weighted model variants
+    pub random: RandomContext,
+}
+
+impl Default for ExportOptions<'_> {
+    fn default() -> Self {
+        Self {
+            progress: &NoProgress,
+            colors: None,
+            include_technical: false,
+            report: None,
+            obj_groups: ObjGroups::default(),
+            region: None,
+            only: None,
+            exclude: None,
+            explode: None,
+            random: RandomContext::default(),
+        }
+    }
+}
+
+impl<'a> ExportOptions<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Report progress to (and accept cancellation from) `progress`
+    pub fn with_progress(mut self, progress: &'a dyn Progress) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// Look block colors up in `colors` instead of the builtin table
+    pub fn with_colors(mut self, colors: &'a ColorMap) -> Self {
+        self.colors = Some(colors);
+        self
+    }
+
+    /// Keep technical blocks in the export
+    pub fn with_technical(mut self, include: bool) -> Self {
+        self.include_technical = include;
+        self
+    }
+
+    /// Collect blocks without a model or texture in `report`
+    pub fn with_report(mut self, report: &'a ExportReport) -> Self {
+        self.report = Some(report);
+        self
+    }
+
+    /// Split OBJ output into objects by `groups`
+    pub fn with_obj_groups(mut self, groups: ObjGroups) -> Self {
+        self.obj_groups = groups;
+        self
+    }
+
+    /// Only export the blocks inside `region`
+    pub fn with_region(mut self, region: ExportRegion) -> Self {
+        self.region = Some(region);
+        self
+    }
+
+    /// Only export blocks matching `selector`
+    pub fn with_only(mut self, selector: &'a BlockSelector) -> Self {
+        self.only = Some(selector);
+        self
+    }
+
+    /// Leave out blocks matching `selector`
+    pub fn with_exclude(mut self, selector: &'a BlockSelector) -> Self {
+        self.exclude = Some(selector);
+        self
+    }
+
+    /// Pull the export's Y layers apart as `explode` says
+    pub fn with_explode(mut self, explode: Explode) -> Self {
+        self.explode = Some(explode);
+        self
+    }
+
+    /// Choose model variants with `random` instead of the default seed
+    pub fn with_random(mut self, random: RandomContext) -> Self {
+        self.random = random;
+        self
+    }
+
+    /// How far blocks in layer `y` move up; 0 unless exploding
+    pub(crate) fn lift(&self, y: u32) -> f32 {
+        self.explode.map_or(0.0, |explode| explode.lift(y))
+    }
+
+    /// Whether `block` gets left out, and treated as air
+    fn hides(&self, block: &Block) -> bool {
+        !block.is_air() && (
+            (!self.include_technical && block.is_technical())
+            || self.only.is_some_and(|only| !only.matches(block))
+            || self.exclude.is_some_and(|exclude| exclude.matches(block))
+        )
+    }
+
+    /// Log the blocks an export found `kind` missing for, most common first,
+    /// and add them to the report
+    pub(crate) fn report_missing(&self, kind: MissingKind, counts: &HashMap<String, usize>) {
+        if counts.is_empty() {
+            return;
+        }
+        let mut blocks: Vec<(&String, &usize)> = counts.iter().collect();
+        blocks.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+        let mut listed: Vec<String> = blocks.iter().take(10).map(|(name, count)| format!("{} ({})", name, count)).collect();
+        if blocks.len() > 10 {
+            listed.push(format!("and {} more", blocks.len() - 10));
+        }
+        let total: usize = counts.values().sum();
+        log::warn!("{} blocks {}: {}", total, kind.describe(), listed.join(", "));
+        if let Some(report) = self.report {
+            report.add(kind, counts);
+        }
+    }
+
+    /// The schematic as it gets exported: cropped to the region, with technical
+    /// blocks (unless included) and filtered-out blocks turned to air. Every
+    /// exporter goes through this, so faces on the region's border or behind a
+    /// removed block count as exposed everywhere. Only copies the schematic
+    /// when there's something to change.
+    pub(crate) fn visible<'s>(&self, schematic: &'s UnifiedSchematic) -> Cow<'s, UnifiedSchematic> {
+        let mut visible = match self.region {
+            Some(region) => {
+                let [x, y, z] = region.clamp(schematic).unwrap_or([(1, 0); 3]);
+                Cow::Owned(schematic.crop((x.0, y.0, z.0), (x.1, y.1, z.1)))
+            }
+            None => Cow::Borrowed(schematic),
+        };
+        if !visible.blocks.iter().any(|b| self.hides(b)) {
+            return visible;
+        }
+        for block in visible.to_mut().blocks.iter_mut().filter(|b| self.hides(b)) {
+            *block = Block::air();
+        }
+        visible
+    }
+
+    /// Color for a block name from the configured colors
+    pub fn block_color(&self, name: &str) -> (f32, f32, f32) {
+        match self.colors {
+            Some(colors) => colors.get(name),
+            None => get_block_color(name),
+        }
     }
 }
 
@@ -226,16 +854,16 @@ struct GreedyQuad {
 
 /// Get UV coordinates for a quad based on face direction and size
 /// The UV mapping must match the vertex order for each face direction
-fn get_uv_coords(dir: FaceDir, width: usize, height: usize) -> [(f32, f32); 4] {
+fn get_uv_coords(dir: Face, width: usize, height: usize) -> [(f32, f32); 4] {
     let (w, h) = (width as f32, height as f32);
     match dir {
         // These directions have standard UV mapping (0,0) -> (w,0) -> (w,h) -> (0,h)
-        FaceDir::XNeg | FaceDir::YPos => [(0.0, 0.0), (w, 0.0), (w, h), (0.0, h)],
+        Face::West | Face::Up => [(0.0, 0.0), (w, 0.0), (w, h), (0.0, h)],
         // These need swapped U coordinates
-        FaceDir::XPos | FaceDir::YNeg => [(w, 0.0), (0.0, 0.0), (0.0, h), (w, h)],
+        Face::East | Face::Down => [(w, 0.0), (0.0, 0.0), (0.0, h), (w, h)],
         // Z faces have width/height swapped in UV space
-        FaceDir::ZNeg => [(h, 0.0), (0.0, 0.0), (0.0, w), (h, w)],
-        FaceDir::ZPos => [(0.0, 0.0), (h, 0.0), (h, w), (0.0, w)],
+        Face::North => [(h, 0.0), (0.0, 0.0), (0.0, w), (h, w)],
+        Face::South => [(0.0, 0.0), (h, 0.0), (h, w), (0.0, w)],
     }
 }
 
@@ -251,11 +879,6 @@ fn get_block_transparency(name: &str) -> f32 {
     }
 }
 
-/// Check if a block is waterlogged
-pub(crate) fn is_waterlogged(properties: &std::collections::HashMap<String, String>) -> bool {
-    properties.get("waterlogged").map(|v| v == "true").unwrap_or(false)
-}
-
 /// Check if neighbor position has water or waterlogged block
 fn neighbor_has_water(
     schematic: &UnifiedSchematic,
@@ -270,12 +893,12 @@ fn neighbor_has_water(
         return false;
     }
 
-    if let Some(block) = schematic.get_block(x as u16, y as u16, z as u16) {
+    if let Some(block) = schematic.get_block(x as u32, y as u32, z as u32) {
         // Check if it's water block or waterlogged
         if block.name == "minecraft:water" || block.name == "water" {
             return true;
         }
-        return is_waterlogged(&block.state.properties);
+        return block.state.is_waterlogged();
     }
     false
 }
@@ -294,7 +917,7 @@ fn neighbor_has_lava(
         return false;
     }
 
-    if let Some(block) = schematic.get_block(x as u16, y as u16, z as u16) {
+    if let Some(block) = schematic.get_block(x as u32, y as u32, z as u32) {
         return block.name == "minecraft:lava" || block.name == "lava";
     }
     false
@@ -330,6 +953,7 @@ pub(crate) fn generate_lava_quads_culled(
             texture: "block/lava_still".to_string(),
             face_dir: mc_models::FaceDirection::Up,
             tint_index: 0,
+            cullface: None,
         });
     }
 
@@ -341,6 +965,7 @@ pub(crate) fn generate_lava_quads_culled(
             texture: "block/lava_still".to_string(),
             face_dir: mc_models::FaceDirection::Down,
             tint_index: 0,
+            cullface: None,
         });
     }
 
@@ -352,6 +977,7 @@ pub(crate) fn generate_lava_quads_culled(
             texture: "block/lava_still".to_string(),
             face_dir: mc_models::FaceDirection::North,
             tint_index: 0,
+            cullface: None,
         });
     }
 
@@ -363,6 +989,7 @@ pub(crate) fn generate_lava_quads_culled(
             texture: "block/lava_still".to_string(),
             face_dir: mc_models::FaceDirection::South,
             tint_index: 0,
+            cullface: None,
         });
     }
 
@@ -374,6 +1001,7 @@ pub(crate) fn generate_lava_quads_culled(
             texture: "block/lava_still".to_string(),
             face_dir: mc_models::FaceDirection::West,
             tint_index: 0,
+            cullface: None,
         });
     }
 
@@ -385,6 +1013,7 @@ pub(crate) fn generate_lava_quads_culled(
             texture: "block/lava_still".to_string(),
             face_dir: mc_models::FaceDirection::East,
             tint_index: 0,
+            cullface: None,
         });
     }
 
@@ -428,6 +1057,7 @@ pub(crate) fn generate_cauldron_liquid_quads(
         texture: texture.to_string(),
         face_dir: mc_models::FaceDirection::Up,
         tint_index: 0,
+        cullface: None,
     });
 
     // Side faces (visible through cauldron gap)
@@ -438,6 +1068,7 @@ pub(crate) fn generate_cauldron_liquid_quads(
         texture: texture.to_string(),
         face_dir: mc_models::FaceDirection::North,
         tint_index: 0,
+        cullface: None,
     });
 
     // South
@@ -447,6 +1078,7 @@ pub(crate) fn generate_cauldron_liquid_quads(
         texture: texture.to_string(),
         face_dir: mc_models::FaceDirection::South,
         tint_index: 0,
+        cullface: None,
     });
 
     // West
@@ -456,6 +1088,7 @@ pub(crate) fn generate_cauldron_liquid_quads(
         texture: texture.to_string(),
         face_dir: mc_models::FaceDirection::West,
         tint_index: 0,
+        cullface: None,
     });
 
     // East
@@ -465,6 +1098,7 @@ pub(crate) fn generate_cauldron_liquid_quads(
         texture: texture.to_string(),
         face_dir: mc_models::FaceDirection::East,
         tint_index: 0,
+        cullface: None,
     });
 
     quads
@@ -499,6 +1133,7 @@ pub(crate) fn generate_water_quads_culled(
             texture: "block/water_still".to_string(),
             face_dir: mc_models::FaceDirection::Up,
             tint_index: 0,
+            cullface: None,
         });
     }
 
@@ -510,6 +1145,7 @@ pub(crate) fn generate_water_quads_culled(
             texture: "block/water_still".to_string(),
             face_dir: mc_models::FaceDirection::Down,
             tint_index: 0,
+            cullface: None,
         });
     }
 
@@ -521,6 +1157,7 @@ pub(crate) fn generate_water_quads_culled(
             texture: "block/water_still".to_string(),
             face_dir: mc_models::FaceDirection::North,
             tint_index: 0,
+            cullface: None,
         });
     }
 
@@ -532,6 +1169,7 @@ pub(crate) fn generate_water_quads_culled(
             texture: "block/water_still".to_string(),
             face_dir: mc_models::FaceDirection::South,
             tint_index: 0,
+            cullface: None,
         });
     }
 
@@ -543,6 +1181,7 @@ pub(crate) fn generate_water_quads_culled(
             texture: "block/water_still".to_string(),
             face_dir: mc_models::FaceDirection::West,
             tint_index: 0,
+            cullface: None,
         });
     }
 
@@ -554,6 +1193,7 @@ pub(crate) fn generate_water_quads_culled(
             texture: "block/water_still".to_string(),
             face_dir: mc_models::FaceDirection::East,
             tint_index: 0,
+            cullface: None,
         });
     }
 
@@ -584,13 +1224,6 @@ fn is_transparent_texture(name: &str) -> bool {
     n.contains("iron_bars") || n.contains("glass_pane") || n.contains("leaves")
 }
 
-/// Check if a block covers a specific face (used for face culling)
-/// Uses the block_geometry module for accurate geometry data
-#[inline]
-fn block_covers_face(block: &crate::Block, face: Face) -> bool {
-    block_geometry::block_covers_face(&block.name, &block.state.properties, face)
-}
-
 /// Check if a block is a full cube (can be greedy meshed)
 #[inline]
 fn is_full_block(block: &crate::Block) -> bool {
@@ -721,99 +1354,136 @@ fn generate_aabb_quads(
     quads
 }
 
-/// Determine which faces of an AABB are visible based on neighbors
+/// Determine which faces of an AABB are visible based on neighbors (see
+/// [`block_geometry::hides_box_face`])
 fn get_visible_faces_for_aabb(
     aabb: &block_geometry::AABB,
+    block: &Block,
     x: usize, y: usize, z: usize,
     schematic: &UnifiedSchematic,
-    w: usize, h: usize, l: usize,
 ) -> [bool; 6] {
-    let mut visible = [true; 6];
-
-    // Helper to check if neighbor fully occludes a face
-    let check_neighbor = |nx: isize, ny: isize, nz: isize, face: Face| -> bool {
-        if nx < 0 || ny < 0 || nz < 0 {
-            return true; // Edge of schematic - visible
-        }
-        let (nx, ny, nz) = (nx as usize, ny as usize, nz as usize);
-        if nx >= w || ny >= h || nz >= l {
-            return true; // Edge of schematic - visible
-        }
-
-        if let Some(neighbor) = schematic.get_block(nx as u16, ny as u16, nz as u16) {
-            if neighbor.is_air() {
-                return true; // Air neighbor - visible
-            }
-
-            let neighbor_geom = block_geometry::get_block_geometry(&neighbor.name, &neighbor.state.properties);
-
-            // If neighbor is a full block, check if our AABB touches the edge
-            if matches!(neighbor_geom, block_geometry::BlockGeometry::Full) {
-                // Full block occludes if our AABB extends to that face
-                let occludes = match face {
-                    Face::XNeg => aabb.min.0 <= 0.001,
-                    Face::XPos => aabb.max.0 >= 0.999,
-                    Face::YNeg => aabb.min.1 <= 0.001,
-                    Face::YPos => aabb.max.1 >= 0.999,
-                    Face::ZNeg => aabb.min.2 <= 0.001,
-                    Face::ZPos => aabb.max.2 >= 0.999,
-                };
-                return !occludes;
-            }
-
-            // For partial neighbors, be conservative - show the face
-            // (proper AABB intersection would be more complex)
-            true
-        } else {
-            true // No block - visible
-        }
-    };
-
-    // Check each direction
-    visible[0] = check_neighbor(x as isize - 1, y as isize, z as isize, Face::XNeg);
-    visible[1] = check_neighbor(x as isize + 1, y as isize, z as isize, Face::XPos);
-    visible[2] = check_neighbor(x as isize, y as isize - 1, z as isize, Face::YNeg);
-    visible[3] = check_neighbor(x as isize, y as isize + 1, z as isize, Face::YPos);
-    visible[4] = check_neighbor(x as isize, y as isize, z as isize - 1, Face::ZNeg);
-    visible[5] = check_neighbor(x as isize, y as isize, z as isize + 1, Face::ZPos);
-
-    visible
+    Face::ALL.map(|face| match schematic.get_neighbor((x as u32, y as u32, z as u32), face) {
+        Some(neighbor) => !block_geometry::hides_box_face(block, aabb, neighbor, face),
+        None => true, // Edge of schematic - visible
+    })
 }
 
 /// Generate all quads for a partial block
-fn generate_partial_block_quads(
-    info: &PartialBlockInfo,
-    schematic: &UnifiedSchematic,
-    w: usize, h: usize, l: usize,
-) -> Vec<GreedyQuad> {
+fn generate_partial_block_quads(info: &PartialBlockInfo, schematic: &UnifiedSchematic) -> Vec<GreedyQuad> {
     let mut quads = Vec::new();
+    let Some(block) = schematic.get_block(info.x as u32, info.y as u32, info.z as u32) else { return quads };
 
     let boxes = info.geometry.get_boxes();
     let (x, y, z) = (info.x as f32, info.y as f32, info.z as f32);
 
     for aabb in &boxes {
-        let visible_faces = get_visible_faces_for_aabb(
-            aabb, info.x, info.y, info.z, schematic, w, h, l
-        );
-
-        // Also check internal face culling: if AABB doesn't touch block edge, face is visible
-        let mut actual_visible = visible_faces;
-
-        // If AABB doesn't extend to block edge, that face is always visible (internal face)
-        if aabb.min.0 > 0.001 { actual_visible[0] = true; }
-        if aabb.max.0 < 0.999 { actual_visible[1] = true; }
-        if aabb.min.1 > 0.001 { actual_visible[2] = true; }
-        if aabb.max.1 < 0.999 { actual_visible[3] = true; }
-        if aabb.min.2 > 0.001 { actual_visible[4] = true; }
-        if aabb.max.2 < 0.999 { actual_visible[5] = true; }
-
-        let aabb_quads = generate_aabb_quads(aabb, x, y, z, &info.material, actual_visible);
+        let visible_faces = get_visible_faces_for_aabb(aabb, block, info.x, info.y, info.z, schematic);
+        let aabb_quads = generate_aabb_quads(aabb, x, y, z, &info.material, visible_faces);
         quads.extend(aabb_quads);
     }
 
     quads
 }
 
+/// Material properties: (r, g, b, opacity, texture_file)
+/// (sorted by name, so the MTL comes out the same on every run)
+type MaterialMap = BTreeMap<String, (f32, f32, f32, f32, Option<String>)>;
+
+/// Edge of the regions `ObjGroups::Chunk` groups by
+const GROUP_CHUNK: u32 = 16;
+
+/// Writes the `o` and `usemtl` lines in front of OBJ faces as they move on to
+/// another object or material. Faces have to arrive with each object's
+/// together (see `ObjGroups`), or objects get split.
+struct ObjGrouper {
+    groups: ObjGroups,
+    chunk: Option<[u32; 3]>,
+    material: String,
+}
+
+impl ObjGrouper {
+    fn new(groups: ObjGroups) -> Self {
+        Self { groups, chunk: None, material: String::new() }
+    }
+
+    /// Start a face of `material` in the region `chunk`
+    fn begin<W: Write>(&mut self, out: &mut W, material: &str, chunk: [u32; 3]) -> std::io::Result<()> {
+        let new_object = match self.groups {
+            ObjGroups::Material => material != self.material,
+            ObjGroups::Chunk => self.chunk != Some(chunk),
+            ObjGroups::None => false,
+        };
+        if new_object {
+            match self.groups {
+                ObjGroups::Chunk => writeln!(out, "o chunk_{}_{}_{}", chunk[0], chunk[1], chunk[2])?,
+                _ => writeln!(out, "o {}", material)?,
+            }
+            self.chunk = Some(chunk);
+            // Importers don't agree on whether the material carries over
+            self.material.clear();
+        }
+        if material != self.material {
+            writeln!(out, "usemtl {}", material)?;
+            self.material.clear();
+            self.material.push_str(material);
+        }
+        Ok(())
+    }
+}
+
+/// Region of the block at `(x, y, z)`
+fn block_chunk(x: u32, y: u32, z: u32) -> [u32; 3] {
+    [x / GROUP_CHUNK, y / GROUP_CHUNK, z / GROUP_CHUNK]
+}
+
+/// Region of the block a quad is a face of: a step back from its center
+/// against the (outward) face normal
+fn quad_chunk(vertices: &[(f32, f32, f32); 4]) -> [u32; 3] {
+    let [v0, v1, v2, _] = vertices.map(|(x, y, z)| [x, y, z]);
+    let (a, b) = ([0, 1, 2].map(|i| v1[i] - v0[i]), [0, 1, 2].map(|i| v2[i] - v0[i]));
+    let normal = [a[1] * b[2] - a[2] * b[1], a[2] * b[0] - a[0] * b[2], a[0] * b[1] - a[1] * b[0]];
+    let length = normal.iter().map(|n| n * n).sum::<f32>().sqrt().max(f32::EPSILON);
+    [0, 1, 2].map(|i| {
+        let center = vertices.iter().map(|v| [v.0, v.1, v.2][i]).sum::<f32>() / 4.0;
+        ((center - normal[i] / length * 0.25).max(0.0) as u32) / GROUP_CHUNK
+    })
+}
+
+/// Write an MTL file for the collected materials
+fn write_mtl(mtl_path: &Path, materials: &MaterialMap) -> std::io::Result<()> {
+    let mut mtl_file = BufWriter::with_capacity(64 * 1024, std::fs::File::create(mtl_path)?);
+    writeln!(mtl_file, "# Minecraft Block Materials")?;
+    writeln!(mtl_file)?;
+
+    for (name, (r, g, b, opacity, tex_file)) in materials {
+        writeln!(mtl_file, "newmtl {}", name)?;
+        writeln!(mtl_file, "Kd {} {} {}", r, g, b)?;
+        writeln!(mtl_file, "Ka 0.2 0.2 0.2")?;
+        if tex_file.is_some() {
+            writeln!(mtl_file, "Ks 0.1 0.1 0.1")?;
+            writeln!(mtl_file, "Ns 50.0")?;
+        } else {
+            writeln!(mtl_file, "Ks 0.0 0.0 0.0")?;
+            writeln!(mtl_file, "Ns 10.0")?;
+        }
+        writeln!(mtl_file, "d {}", opacity)?;
+        // Check if texture likely has alpha channel
+        let has_alpha = is_transparent_texture(name);
+        if has_alpha {
+            writeln!(mtl_file, "illum 4")?;  // Transparency with raytracing
+        } else {
+            writeln!(mtl_file, "illum 2")?;
+        }
+        if let Some(tex) = tex_file {
+            writeln!(mtl_file, "map_Kd {}", tex)?;
+            if has_alpha {
+                writeln!(mtl_file, "map_d {}", tex)?;  // Alpha map
+            }
+        }
+        writeln!(mtl_file)?;
+    }
+    mtl_file.flush()
+}
 
 /// Generate OBJ file from schematic (simple per-block cubes)
 pub fn export_obj<P: AsRef<Path>>(
@@ -821,8 +1491,11 @@ pub fn export_obj<P: AsRef<Path>>(
     obj_path: P,
     hollow: bool,
     skip_air: bool,
-) -> std::io::Result<()> {
-    export_obj_internal(schematic, obj_path, hollow, skip_air, None, false)
+    options: &ExportOptions,
+) -> Result<(), SchemError> {
+    let obj_path = obj_path.as_ref();
+    export_obj_internal(&options.visible(schematic), obj_path, hollow, skip_air, None, false, options)
+        .map_err(|e| e.at_path(obj_path))
 }
 
 /// Generate OBJ file from schematic with optional textures
@@ -832,8 +1505,11 @@ pub fn export_obj_with_textures<P: AsRef<Path>>(
     hollow: bool,
     skip_air: bool,
     textures: Option<&TextureManager>,
-) -> std::io::Result<()> {
-    export_obj_internal(schematic, obj_path, hollow, skip_air, textures, false)
+    options: &ExportOptions,
+) -> Result<(), SchemError> {
+    let obj_path = obj_path.as_ref();
+    export_obj_internal(&options.visible(schematic), obj_path, hollow, skip_air, textures, false, options)
+        .map_err(|e| e.at_path(obj_path))
 }
 
 /// Generate OBJ file with greedy meshing (dramatically reduced polygon count)
@@ -841,8 +1517,11 @@ pub fn export_obj_greedy<P: AsRef<Path>>(
     schematic: &UnifiedSchematic,
     obj_path: P,
     textures: Option<&TextureManager>,
-) -> std::io::Result<()> {
-    export_obj_internal(schematic, obj_path, true, true, textures, true)
+    options: &ExportOptions,
+) -> Result<(), SchemError> {
+    let obj_path = obj_path.as_ref();
+    export_obj_internal(&options.visible(schematic), obj_path, true, true, textures, true, options)
+        .map_err(|e| e.at_path(obj_path))
 }
 
 /// Generate OBJ file using Minecraft JSON models for accurate geometry
@@ -853,15 +1532,29 @@ pub fn export_obj_with_models<P: AsRef<Path>>(
     jar_path: &Path,
     textures: Option<&TextureManager>,
     resource_pack: Option<&Path>,
-) -> std::io::Result<()> {
+    options: &ExportOptions,
+) -> Result<(), SchemError> {
     let obj_path = obj_path.as_ref();
+    export_obj_with_models_internal(&options.visible(schematic), obj_path, jar_path, textures, resource_pack, options)
+        .map_err(|e| e.at_path(obj_path))
+}
+
+fn export_obj_with_models_internal(
+    schematic: &UnifiedSchematic,
+    obj_path: &Path,
+    jar_path: &Path,
+    textures: Option<&TextureManager>,
+    resource_pack: Option<&Path>,
+    options: &ExportOptions,
+) -> Result<(), SchemError> {
+    let progress = options.progress;
     let mtl_path = obj_path.with_extension("mtl");
     let use_textures = textures.map(|t| t.has_textures()).unwrap_or(false);
 
     // Create textures subdirectory if using textures
     let tex_dir = if use_textures {
         let dir = obj_path.parent().unwrap_or(Path::new(".")).join("textures");
-        std::fs::create_dir_all(&dir)?;
+        std::fs::create_dir_all(&dir).map_err(|e| SchemError::export_io(&dir, e))?;
         Some(dir)
     } else {
         None
@@ -871,22 +1564,27 @@ pub fn export_obj_with_models<P: AsRef<Path>>(
     let mut model_manager = ModelManager::from_jar_with_resource_pack(jar_path, resource_pack)?;
 
     let (w, h, l) = (schematic.width as usize, schematic.height as usize, schematic.length as usize);
-    let total_blocks = (w * h * l) as u64;
+    let total_blocks = schematic.volume() as u64;
 
     // Phase 1: Collect materials only (no quads stored)
-    let pb = create_progress_bar(total_blocks, "Collecting materials");
-    let mut materials: HashMap<String, (f32, f32, f32, f32, Option<String>)> = HashMap::new();
+    progress.start(total_blocks, "Collecting materials");
+    let mut materials = MaterialMap::new();
     let mut processed = 0u64;
+    // Block name -> count, for blocks drawn as colored cubes or without some of their parts
+    let mut no_blockstate: HashMap<String, usize> = HashMap::new();
+    let mut no_model: HashMap<String, usize> = HashMap::new();
+    let mut no_texture: HashMap<String, usize> = HashMap::new();
 
     for y in 0..h {
         for z in 0..l {
             for x in 0..w {
                 processed += 1;
                 if processed % 100_000 == 0 {
-                    pb.set_position(processed);
+                    progress.set_position(processed);
+                    progress::check_cancel(progress)?;
                 }
 
-                let Some(block) = schematic.get_block(x as u16, y as u16, z as u16) else { continue };
+                let Some(block) = schematic.get_block(x as u32, y as u32, z as u32) else { continue };
                 if block.is_air() { continue; }
 
                 // Handle water/lava blocks
@@ -895,13 +1593,14 @@ pub fn export_obj_with_models<P: AsRef<Path>>(
                 let is_water_cauldron = block.name == "minecraft:water_cauldron";
                 let is_lava_cauldron = block.name == "minecraft:lava_cauldron";
 
-                if is_water_block || is_water_cauldron || is_waterlogged(&block.state.properties) {
+                if is_water_block || is_water_cauldron || block.state.is_waterlogged() {
                     // Will need water material
                     materials.entry("water_still".to_string()).or_insert_with(|| {
                         let texture_file = if let (Some(tex_mgr), Some(tex_out_dir)) = (textures, &tex_dir) {
                             if let Some(tex_path) = tex_mgr.get_texture("water_still") {
                                 let dest = tex_out_dir.join("water_still.png");
-                                if std::fs::copy(tex_path, &dest).is_ok() {
+                                // Left untinted; the material color tints it
+                                if tex_mgr.copy_texture(tex_path, &dest, "water_still").is_ok() {
                                     Some("textures/water_still.png".to_string())
                                 } else { None }
                             } else { None }
@@ -915,7 +1614,7 @@ pub fn export_obj_with_models<P: AsRef<Path>>(
                         let texture_file = if let (Some(tex_mgr), Some(tex_out_dir)) = (textures, &tex_dir) {
                             if let Some(tex_path) = tex_mgr.get_texture("lava_still") {
                                 let dest = tex_out_dir.join("lava_still.png");
-                                if std::fs::copy(tex_path, &dest).is_ok() {
+                                if tex_mgr.copy_texture(tex_path, &dest, "lava_still").is_ok() {
                                     Some("textures/lava_still.png".to_string())
                                 } else { None }
                             } else { None }
@@ -928,32 +1627,42 @@ pub fn export_obj_with_models<P: AsRef<Path>>(
                     continue;
                 }
 
-                // Get models for this block from JSON
-                let model_refs = model_manager.get_models_for_block(&block.name, &block.state.properties);
+                // Get models for this block from JSON, with the variants the geometry pass picks
+                let mut rng = options.random.rng_at((x as i32, y as i32, z as i32));
+                let model_refs = model_manager.pick_models_for_block(&block.name, &block.state.properties, &mut rng);
 
                 if model_refs.is_empty() {
                     // Fallback material
                     let mat_name = block.display_name().replace([':', '[', ']', '=', ','], "_");
                     if !materials.contains_key(&mat_name) {
-                        let color = get_block_color(&block.name);
+                        let color = options.block_color(&block.name);
                         let opacity = get_block_transparency(&block.name);
                         let texture_file = if let (Some(tex_mgr), Some(tex_out_dir)) = (textures, &tex_dir) {
                             if let Some(tex_path) = tex_mgr.get_texture(&block.name) {
                                 let tex_name = format!("{}.png", mat_name);
                                 let dest = tex_out_dir.join(&tex_name);
-                                if crate::textures::copy_texture_with_tint(tex_path, &dest, &block.name).is_ok() {
+                                if tex_mgr.copy_texture(tex_path, &dest, &block.name).is_ok() {
                                     Some(format!("textures/{}", tex_name))
                                 } else { None }
                             } else { None }
                         } else { None };
-                        materials.insert(mat_name, (color.0, color.1, color.2, opacity, texture_file));
+                        materials.insert(mat_name.clone(), (color.0, color.1, color.2, opacity, texture_file));
+                    }
+                    *no_blockstate.entry(block.name.clone()).or_default() += 1;
+                    if use_textures && materials[&mat_name].4.is_none() {
+                        *no_texture.entry(block.name.clone()).or_default() += 1;
                     }
                     continue;
                 }
 
                 // Collect materials from model textures
+                let mut unresolved = false;
+                let mut untextured = false;
                 for (model_ref, _block_name) in &model_refs {
-                    let Some(resolved) = model_manager.resolve_model(&model_ref.model) else { continue };
+                    let Some(resolved) = model_manager.resolve_model(&model_ref.model) else {
+                        unresolved = true;
+                        continue;
+                    };
 
                     // Get unique textures from this model
                     for (_key, tex_path) in &resolved.textures {
@@ -962,7 +1671,7 @@ pub fn export_obj_with_models<P: AsRef<Path>>(
                         let mat_name = s.replace(['/', ':'], "_");
 
                         if !materials.contains_key(&mat_name) {
-                            let color = get_block_color(&block.name);
+                            let color = options.block_color(&block.name);
                             let opacity = get_block_transparency(&block.name);
                             let texture_file = if let (Some(tex_mgr), Some(tex_out_dir)) = (textures, &tex_dir) {
                                 let s2 = tex_path.strip_prefix("minecraft:").unwrap_or(tex_path);
@@ -971,52 +1680,32 @@ pub fn export_obj_with_models<P: AsRef<Path>>(
                                 if let Some(src_path) = tex_mgr.get_texture(tex_lookup) {
                                     let tex_name = format!("{}.png", mat_name);
                                     let dest = tex_out_dir.join(&tex_name);
-                                    if crate::textures::copy_texture_with_tint(src_path, &dest, &block.name).is_ok() {
+                                    if tex_mgr.copy_texture(src_path, &dest, &block.name).is_ok() {
                                         Some(format!("textures/{}", tex_name))
                                     } else { None }
                                 } else { None }
                             } else { None };
-                            materials.insert(mat_name, (color.0, color.1, color.2, opacity, texture_file));
+                            materials.insert(mat_name.clone(), (color.0, color.1, color.2, opacity, texture_file));
                         }
+                        untextured |= use_textures && materials[&mat_name].4.is_none();
                     }
                 }
+                if unresolved {
+                    *no_model.entry(block.name.clone()).or_default() += 1;
+                }
+                if untextured {
+                    *no_texture.entry(block.name.clone()).or_default() += 1;
+                }
             }
         }
     }
-    pb.finish_with_message(format!("Found {} unique materials", materials.len()));
+    progress.finish(&format!("Found {} unique materials", materials.len()));
+    options.report_missing(MissingKind::Blockstate, &no_blockstate);
+    options.report_missing(MissingKind::Model, &no_model);
+    options.report_missing(MissingKind::Texture, &no_texture);
 
     // Write MTL file
-    let mut mtl_file = BufWriter::with_capacity(64 * 1024, std::fs::File::create(&mtl_path)?);
-    writeln!(mtl_file, "# Minecraft Block Materials")?;
-    writeln!(mtl_file)?;
-
-    for (name, (r, g, b, opacity, tex_file)) in &materials {
-        writeln!(mtl_file, "newmtl {}", name)?;
-        writeln!(mtl_file, "Kd {} {} {}", r, g, b)?;
-        writeln!(mtl_file, "Ka 0.2 0.2 0.2")?;
-        if tex_file.is_some() {
-            writeln!(mtl_file, "Ks 0.1 0.1 0.1")?;
-            writeln!(mtl_file, "Ns 50.0")?;
-        } else {
-            writeln!(mtl_file, "Ks 0.0 0.0 0.0")?;
-            writeln!(mtl_file, "Ns 10.0")?;
-        }
-        writeln!(mtl_file, "d {}", opacity)?;
-        let has_alpha = is_transparent_texture(name);
-        if has_alpha {
-            writeln!(mtl_file, "illum 4")?;
-        } else {
-            writeln!(mtl_file, "illum 2")?;
-        }
-        if let Some(tex) = tex_file {
-            writeln!(mtl_file, "map_Kd {}", tex)?;
-            if has_alpha {
-                writeln!(mtl_file, "map_d {}", tex)?;
-            }
-        }
-        writeln!(mtl_file)?;
-    }
-    mtl_file.flush()?;
+    write_mtl(&mtl_path, &materials).map_err(|e| SchemError::export_io(&mtl_path, e))?;
 
     // Phase 2: Generate and write quads in Y-layer chunks to limit memory
     // Process CHUNK_SIZE Y-layers at a time
@@ -1032,34 +1721,45 @@ pub fn export_obj_with_models<P: AsRef<Path>>(
     writeln!(obj_file)?;
 
     let num_chunks = (h + CHUNK_SIZE - 1) / CHUNK_SIZE;
-    let pb = create_progress_bar(num_chunks as u64, "Writing OBJ (chunked)");
+    progress.start(num_chunks as u64, "Writing OBJ (chunked)");
 
+    let groups = options.obj_groups;
     let mut vertex_index = 1u32;
     let mut vt_index = 1u32;
-    let mut current_material = String::new();
+    let mut grouper = ObjGrouper::new(groups);
     let mut total_quads = 0usize;
 
+    // One object per material needs each material's faces in one run, but
+    // layers are written as they're generated. Their runs go to a spill file
+    // with relative face indices, and are copied out by material at the end.
+    let mut spill = match groups {
+        ObjGroups::Material => Some(SpillFile::create(obj_path.with_extension("obj.spill"))?),
+        _ => None,
+    };
+
     for chunk_idx in 0..num_chunks {
-        pb.set_position(chunk_idx as u64);
+        progress.set_position(chunk_idx as u64);
+        progress::check_cancel(progress)?;
 
         let y_start = chunk_idx * CHUNK_SIZE;
         let y_end = ((chunk_idx + 1) * CHUNK_SIZE).min(h);
 
         // Generate quads for this Y-chunk
-        let mut chunk_quads: Vec<(GeneratedQuad, String)> = Vec::new();
+        let mut chunk_quads: Vec<(GeneratedQuad, String, [u32; 3])> = Vec::new();
 
         for y in y_start..y_end {
             for z in 0..l {
                 for x in 0..w {
-                    let Some(block) = schematic.get_block(x as u16, y as u16, z as u16) else { continue };
+                    let Some(block) = schematic.get_block(x as u32, y as u32, z as u32) else { continue };
                     if block.is_air() { continue; }
+                    let chunk = block_chunk(x as u32, y as u32, z as u32);
 
                     // Handle water blocks
                     let is_water_block = block.name == "minecraft:water" || block.name == "water";
                     if is_water_block {
                         let water_quads = generate_water_quads_culled(x, y, z, schematic, w, h, l);
                         for quad in water_quads {
-                            chunk_quads.push((quad, "water_still".to_string()));
+                            chunk_quads.push((quad, "water_still".to_string(), chunk));
                         }
                         continue;
                     }
@@ -1069,7 +1769,7 @@ pub fn export_obj_with_models<P: AsRef<Path>>(
                     if is_lava_block {
                         let lava_quads = generate_lava_quads_culled(x, y, z, schematic, w, h, l);
                         for quad in lava_quads {
-                            chunk_quads.push((quad, "lava_still".to_string()));
+                            chunk_quads.push((quad, "lava_still".to_string(), chunk));
                         }
                         continue;
                     }
@@ -1078,10 +1778,7 @@ pub fn export_obj_with_models<P: AsRef<Path>>(
                     let is_water_cauldron = block.name == "minecraft:water_cauldron";
                     let is_lava_cauldron = block.name == "minecraft:lava_cauldron";
                     if is_water_cauldron || is_lava_cauldron {
-                        let level: u8 = block.state.properties
-                            .get("level")
-                            .and_then(|v| v.parse().ok())
-                            .unwrap_or(3);
+                        let level = block.state.get_int("level").map_or(3, |level| level.min(3) as u8);
 
                         if level > 0 {
                             let liquid_quads = generate_cauldron_liquid_quads(
@@ -1091,13 +1788,14 @@ pub fn export_obj_with_models<P: AsRef<Path>>(
                             );
                             let mat_name = if is_lava_cauldron { "lava_still" } else { "water_still" };
                             for quad in liquid_quads {
-                                chunk_quads.push((quad, mat_name.to_string()));
+                                chunk_quads.push((quad, mat_name.to_string(), chunk));
                             }
                         }
                     }
 
                     // Get models for this block from JSON
-                    let model_refs = model_manager.get_models_for_block(&block.name, &block.state.properties);
+                    let mut rng = options.random.rng_at((x as i32, y as i32, z as i32));
+                    let model_refs = model_manager.pick_models_for_block(&block.name, &block.state.properties, &mut rng);
 
                     if model_refs.is_empty() {
                         continue; // Skip blocks without models (fallback not rendered)
@@ -1117,67 +1815,147 @@ pub fn export_obj_with_models<P: AsRef<Path>>(
                         );
 
                         for quad in quads {
+                            if is_culled(&quad, schematic, block, (x as u32, y as u32, z as u32)) {
+                                continue;
+                            }
                             let s = quad.texture.strip_prefix("minecraft:").unwrap_or(&quad.texture);
                             let mat_name = s.strip_prefix("block/").unwrap_or(s)
                                 .replace(['/', ':'], "_");
 
-                            chunk_quads.push((quad, mat_name));
+                            chunk_quads.push((quad, mat_name, chunk));
                         }
                     }
 
                     // Check if block is waterlogged - add water quads
-                    if is_waterlogged(&block.state.properties) {
+                    if block.state.is_waterlogged() {
                         let water_quads = generate_water_quads_culled(x, y, z, schematic, w, h, l);
                         for quad in water_quads {
-                            chunk_quads.push((quad, "water_still".to_string()));
+                            chunk_quads.push((quad, "water_still".to_string(), chunk));
                         }
                     }
                 }
             }
         }
 
-        // Sort chunk quads by material for better grouping
+        // Sort chunk quads by material for better grouping, and by region
+        // first when those are the objects (both sorts keep the order of equal quads)
         chunk_quads.sort_by(|a, b| a.1.cmp(&b.1));
+        if groups == ObjGroups::Chunk {
+            chunk_quads.sort_by_key(|quad| quad.2);
+        }
         total_quads += chunk_quads.len();
 
         // Write chunk quads to file
-        for (quad, mat_name) in chunk_quads {
-            if mat_name != current_material {
-                writeln!(obj_file, "usemtl {}", mat_name)?;
-                current_material = mat_name;
-            }
-
-            // Write 4 vertices
-            for v in &quad.vertices {
-                writeln!(obj_file, "v {} {} {}", v.0, v.1, v.2)?;
+        if let Some(spill) = &mut spill {
+            for run in chunk_quads.chunk_by(|a, b| a.1 == b.1) {
+                let mut lines = Vec::new();
+                for (quad, _, _) in run {
+                    write_model_quad(&mut lines, quad, use_textures, None)?;
+                }
+                spill.append(&run[0].1, &lines)?;
             }
-
-            // Write face with UV coordinates
+            continue;
+        }
+        for (quad, mat_name, chunk) in chunk_quads {
+            grouper.begin(&mut obj_file, &mat_name, chunk)?;
+            write_model_quad(&mut obj_file, &quad, use_textures, Some((vertex_index, vt_index)))?;
+            vertex_index += 4;
             if use_textures {
-                for uv in &quad.uv_coords {
-                    writeln!(obj_file, "vt {} {}", uv.0, 1.0 - uv.1)?;
-                }
-
-                writeln!(obj_file, "f {}/{} {}/{} {}/{} {}/{}",
-                    vertex_index, vt_index,
-                    vertex_index + 1, vt_index + 1,
-                    vertex_index + 2, vt_index + 2,
-                    vertex_index + 3, vt_index + 3)?;
                 vt_index += 4;
-            } else {
-                writeln!(obj_file, "f {} {} {} {}",
-                    vertex_index, vertex_index + 1, vertex_index + 2, vertex_index + 3)?;
             }
-            vertex_index += 4;
         }
         // chunk_quads is dropped here, freeing memory
     }
 
-    pb.finish_with_message(format!("Written {} quads ({} vertices)", total_quads, vertex_index - 1));
+    if let Some(spill) = spill {
+        spill.copy_runs(&mut obj_file, &mut grouper)?;
+    }
+
+    progress.finish(&format!("Written {} quads ({} vertices)", total_quads, total_quads * 4));
     obj_file.flush()?;
     Ok(())
 }
 
+/// Whether a model face with a `cullface` is hidden by the block on that side
+/// of `pos` (see [`block_geometry::hides_face`]); faces on the schematic's edge stay
+pub(crate) fn is_culled(quad: &GeneratedQuad, schematic: &UnifiedSchematic, block: &Block, pos: (u32, u32, u32)) -> bool {
+    let Some(side) = quad.cullface else { return false };
+    schematic.get_neighbor(pos, side).is_some_and(|neighbor| block_geometry::hides_face(block, neighbor, side.opposite()))
+}
+
+/// Write a model quad's vertices, UVs and face. `index` holds the 1-based
+/// indices of its first vertex and UV; without it the face counts back from
+/// its own lines, which stay valid wherever the lines end up.
+fn write_model_quad<W: Write>(out: &mut W, quad: &GeneratedQuad, use_textures: bool, index: Option<(u32, u32)>) -> std::io::Result<()> {
+    // Write 4 vertices
+    for v in &quad.vertices {
+        writeln!(out, "v {} {} {}", v.0, v.1, v.2)?;
+    }
+
+    let (vi, ti) = index.map(|(v, t)| (v as i64, t as i64)).unwrap_or((-4, -4));
+    // Write face with UV coordinates
+    if use_textures {
+        for uv in &quad.uv_coords {
+            writeln!(out, "vt {} {}", uv.0, 1.0 - uv.1)?;
+        }
+
+        writeln!(out, "f {}/{} {}/{} {}/{} {}/{}",
+            vi, ti,
+            vi + 1, ti + 1,
+            vi + 2, ti + 2,
+            vi + 3, ti + 3)?;
+    } else {
+        writeln!(out, "f {} {} {} {}", vi, vi + 1, vi + 2, vi + 3)?;
+    }
+    Ok(())
+}
+
+/// OBJ lines set aside by material, to be written out one material at a time.
+/// Removed when dropped.
+struct SpillFile {
+    path: std::path::PathBuf,
+    file: BufWriter<std::fs::File>,
+    len: u64,
+    /// Material -> (offset, length) of each of its runs
+    runs: BTreeMap<String, Vec<(u64, u64)>>,
+}
+
+impl SpillFile {
+    fn create(path: std::path::PathBuf) -> Result<Self, SchemError> {
+        let file = std::fs::File::create(&path).map_err(|e| SchemError::export_io(&path, e))?;
+        Ok(Self { path, file: BufWriter::with_capacity(4 * 1024 * 1024, file), len: 0, runs: BTreeMap::new() })
+    }
+
+    fn append(&mut self, material: &str, lines: &[u8]) -> std::io::Result<()> {
+        self.file.write_all(lines)?;
+        self.runs.entry(material.to_string()).or_default().push((self.len, lines.len() as u64));
+        self.len += lines.len() as u64;
+        Ok(())
+    }
+
+    /// Copy the runs to `out`, each material's together and in name order
+    fn copy_runs<W: Write>(mut self, out: &mut W, grouper: &mut ObjGrouper) -> std::io::Result<()> {
+        use std::io::{Read, Seek, SeekFrom};
+
+        self.file.flush()?;
+        let mut file = std::fs::File::open(&self.path)?;
+        for (material, runs) in &self.runs {
+            grouper.begin(out, material, [0; 3])?;
+            for &(offset, len) in runs {
+                file.seek(SeekFrom::Start(offset))?;
+                std::io::copy(&mut (&mut file).take(len), out)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Drop for SpillFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
 /// Internal function for OBJ export with all options
 fn export_obj_internal<P: AsRef<Path>>(
     schematic: &UnifiedSchematic,
@@ -1186,7 +1964,9 @@ fn export_obj_internal<P: AsRef<Path>>(
     skip_air: bool,
     textures: Option<&TextureManager>,
     greedy: bool,
-) -> std::io::Result<()> {
+    options: &ExportOptions,
+) -> Result<(), SchemError> {
+    let progress = options.progress;
     let obj_path = obj_path.as_ref();
     let mtl_path = obj_path.with_extension("mtl");
     let use_textures = textures.map(|t| t.has_textures()).unwrap_or(false);
@@ -1194,7 +1974,7 @@ fn export_obj_internal<P: AsRef<Path>>(
     // Create textures subdirectory if using textures
     let tex_dir = if use_textures {
         let dir = obj_path.parent().unwrap_or(Path::new(".")).join("textures");
-        std::fs::create_dir_all(&dir)?;
+        std::fs::create_dir_all(&dir).map_err(|e| SchemError::export_io(&dir, e))?;
         Some(dir)
     } else {
         None
@@ -1202,7 +1982,6 @@ fn export_obj_internal<P: AsRef<Path>>(
 
     // Use BufWriter for much faster I/O
     let mut obj_file = BufWriter::with_capacity(1024 * 1024, std::fs::File::create(obj_path)?);
-    let mut mtl_file = BufWriter::with_capacity(64 * 1024, std::fs::File::create(&mtl_path)?);
 
     // Write headers
     writeln!(obj_file, "# Minecraft Schematic Export")?;
@@ -1222,15 +2001,11 @@ fn export_obj_internal<P: AsRef<Path>>(
         writeln!(obj_file)?;
     }
 
-    writeln!(mtl_file, "# Minecraft Block Materials")?;
-    writeln!(mtl_file)?;
-
     // Collect materials
-    let total_positions = schematic.width as u64 * schematic.height as u64 * schematic.length as u64;
-    let pb = create_progress_bar(total_positions, "Collecting materials");
+    let total_positions = schematic.volume() as u64;
+    progress.start(total_positions, "Collecting materials");
 
-    // Materials: (r, g, b, opacity, texture_file)
-    let mut materials: HashMap<String, (f32, f32, f32, f32, Option<String>)> = HashMap::new();
+    let mut materials = MaterialMap::new();
     let mut processed = 0u64;
 
     for y in 0..schematic.height {
@@ -1238,20 +2013,21 @@ fn export_obj_internal<P: AsRef<Path>>(
             for x in 0..schematic.width {
                 processed += 1;
                 if processed % 100_000 == 0 {
-                    pb.set_position(processed);
+                    progress.set_position(processed);
+                    progress::check_cancel(progress)?;
                 }
                 if let Some(block) = schematic.get_block(x, y, z) {
                     if skip_air && block.is_air() { continue; }
                     let mat_name = block.display_name().replace([':', '[', ']', '=', ','], "_");
                     if !materials.contains_key(&mat_name) {
-                        let color = get_block_color(&block.name);
+                        let color = options.block_color(&block.name);
                         let opacity = get_block_transparency(&block.name);
                         let texture_file = if let (Some(tex_mgr), Some(tex_out_dir)) = (textures, &tex_dir) {
                             if let Some(tex_path) = tex_mgr.get_texture(&block.name) {
                                 let tex_name = format!("{}.png", mat_name);
                                 let dest = tex_out_dir.join(&tex_name);
-                                // Use copy_texture_with_tint to apply biome colors to leaves/grass
-                                if crate::textures::copy_texture_with_tint(tex_path, &dest, &block.name).is_ok() {
+                                // Apply biome colors to leaves/grass
+                                if tex_mgr.copy_texture(tex_path, &dest, &block.name).is_ok() {
                                     Some(format!("textures/{}", tex_name))
                                 } else { None }
                             } else { None }
@@ -1262,43 +2038,16 @@ fn export_obj_internal<P: AsRef<Path>>(
             }
         }
     }
-    pb.finish_with_message(format!("Found {} unique materials", materials.len()));
+    progress.finish(&format!("Found {} unique materials", materials.len()));
 
     // Write materials
-    for (name, (r, g, b, opacity, tex_file)) in &materials {
-        writeln!(mtl_file, "newmtl {}", name)?;
-        writeln!(mtl_file, "Kd {} {} {}", r, g, b)?;
-        writeln!(mtl_file, "Ka 0.2 0.2 0.2")?;
-        if tex_file.is_some() {
-            writeln!(mtl_file, "Ks 0.1 0.1 0.1")?;
-            writeln!(mtl_file, "Ns 50.0")?;
-        } else {
-            writeln!(mtl_file, "Ks 0.0 0.0 0.0")?;
-            writeln!(mtl_file, "Ns 10.0")?;
-        }
-        writeln!(mtl_file, "d {}", opacity)?;
-        // Check if texture likely has alpha channel
-        let has_alpha = is_transparent_texture(name);
-        if has_alpha {
-            writeln!(mtl_file, "illum 4")?;  // Transparency with raytracing
-        } else {
-            writeln!(mtl_file, "illum 2")?;
-        }
-        if let Some(tex) = tex_file {
-            writeln!(mtl_file, "map_Kd {}", tex)?;
-            if has_alpha {
-                writeln!(mtl_file, "map_d {}", tex)?;  // Alpha map
-            }
-        }
-        writeln!(mtl_file)?;
-    }
-    mtl_file.flush()?;
+    write_mtl(&mtl_path, &materials).map_err(|e| SchemError::export_io(&mtl_path, e))?;
 
     // Generate geometry
     if greedy {
-        generate_greedy_geometry(schematic, &mut obj_file, use_textures)?;
+        generate_greedy_geometry(schematic, &mut obj_file, use_textures, options.obj_groups, progress)?;
     } else {
-        generate_naive_geometry(schematic, &mut obj_file, hollow, skip_air, use_textures)?;
+        generate_naive_geometry(schematic, &mut obj_file, hollow, skip_air, use_textures, options.obj_groups, progress)?;
     }
 
     obj_file.flush()?;
@@ -1312,33 +2061,46 @@ fn generate_naive_geometry<W: Write>(
     hollow: bool,
     skip_air: bool,
     use_textures: bool,
-) -> std::io::Result<()> {
-    let total_positions = schematic.width as u64 * schematic.height as u64 * schematic.length as u64;
-    let pb = create_progress_bar(total_positions, "Generating geometry");
+    groups: ObjGroups,
+    progress: &dyn Progress,
+) -> Result<(), SchemError> {
+    let total_positions = schematic.volume() as u64;
+    progress.start(total_positions, "Generating geometry");
 
     let mut vertex_index = 1u32;
-    let mut current_material = String::new();
+    let mut grouper = ObjGrouper::new(groups);
     let mut blocks_written = 0u64;
     let mut processed = 0u64;
     let (w, h, l) = (schematic.width, schematic.height, schematic.length);
+    // With objects, cubes are collected (as material indices) and sorted so
+    // each object's are together; otherwise they're written as they're found
+    let mut names: Vec<String> = Vec::new();
+    let mut name_index: HashMap<String, usize> = HashMap::new();
+    let mut cubes: Vec<(usize, [u32; 3])> = Vec::new();
 
     for y in 0..h {
         for z in 0..l {
             for x in 0..w {
                 processed += 1;
                 if processed % 100_000 == 0 {
-                    pb.set_position(processed);
+                    progress.set_position(processed);
+                    progress::check_cancel(progress)?;
                 }
 
                 if let Some(block) = schematic.get_block(x, y, z) {
                     if skip_air && block.is_air() { continue; }
-                    if hollow && !is_exposed_fast(schematic, x, y, z, w, h, l) { continue; }
+                    if hollow && !block_geometry::is_exposed(schematic, x, y, z) { continue; }
 
                     let mat_name = block.display_name().replace([':', '[', ']', '=', ','], "_");
-                    if mat_name != current_material {
-                        writeln!(obj_file, "usemtl {}", mat_name)?;
-                        current_material = mat_name;
+                    if groups != ObjGroups::None {
+                        let index = *name_index.entry(mat_name).or_insert_with_key(|name| {
+                            names.push(name.clone());
+                            names.len() - 1
+                        });
+                        cubes.push((index, [x, y, z]));
+                        continue;
                     }
+                    grouper.begin(obj_file, &mat_name, [0; 3])?;
 
                     write_cube(obj_file, x as f32, y as f32, z as f32, vertex_index, use_textures)?;
                     vertex_index += 8;
@@ -1348,7 +2110,18 @@ fn generate_naive_geometry<W: Write>(
         }
     }
 
-    pb.finish_with_message(format!("Written {} blocks ({} vertices)", blocks_written, vertex_index - 1));
+    cubes.sort_by(|a, b| names[a.0].cmp(&names[b.0]));
+    if groups == ObjGroups::Chunk {
+        cubes.sort_by_key(|&(_, [x, y, z])| block_chunk(x, y, z));
+    }
+    for (index, [x, y, z]) in cubes {
+        grouper.begin(obj_file, &names[index], block_chunk(x, y, z))?;
+        write_cube(obj_file, x as f32, y as f32, z as f32, vertex_index, use_textures)?;
+        vertex_index += 8;
+        blocks_written += 1;
+    }
+
+    progress.finish(&format!("Written {} blocks ({} vertices)", blocks_written, vertex_index - 1));
     Ok(())
 }
 
@@ -1358,12 +2131,14 @@ fn generate_greedy_geometry<W: Write>(
     schematic: &UnifiedSchematic,
     obj_file: &mut W,
     use_textures: bool,
-) -> std::io::Result<()> {
+    groups: ObjGroups,
+    progress: &dyn Progress,
+) -> Result<(), SchemError> {
     let (w, h, l) = (schematic.width as usize, schematic.height as usize, schematic.length as usize);
 
     // Phase 1: Collect partial blocks for separate processing
-    let total_blocks = (w * h * l) as u64;
-    let pb = create_progress_bar(total_blocks, "Collecting blocks");
+    let total_blocks = schematic.volume() as u64;
+    progress.start(total_blocks, "Collecting blocks");
 
     let mut partial_blocks: Vec<PartialBlockInfo> = Vec::new();
     let mut processed = 0u64;
@@ -1373,10 +2148,11 @@ fn generate_greedy_geometry<W: Write>(
             for x in 0..w {
                 processed += 1;
                 if processed % 100_000 == 0 {
-                    pb.set_position(processed);
+                    progress.set_position(processed);
+                    progress::check_cancel(progress)?;
                 }
 
-                if let Some(block) = schematic.get_block(x as u16, y as u16, z as u16) {
+                if let Some(block) = schematic.get_block(x as u32, y as u32, z as u32) {
                     if block.is_air() { continue; }
 
                     // Check if this is a partial block
@@ -1393,30 +2169,31 @@ fn generate_greedy_geometry<W: Write>(
             }
         }
     }
-    pb.finish_with_message(format!("Found {} partial blocks", partial_blocks.len()));
+    progress.finish(&format!("Found {} partial blocks", partial_blocks.len()));
 
     // Phase 2: Greedy mesh full blocks only
     let mut all_quads: Vec<GreedyQuad> = Vec::new();
 
     let total_slices = (w + h + l) * 2;
-    let pb = create_progress_bar(total_slices as u64, "Greedy meshing full blocks");
+    progress.start(total_slices as u64, "Greedy meshing full blocks");
     let mut slice_count = 0u64;
 
-    for dir in FaceDir::all() {
-        let quads = greedy_mesh_direction_full_only(schematic, dir, w, h, l, &pb, &mut slice_count);
+    for dir in Face::ALL {
+        let quads = greedy_mesh_direction_full_only(schematic, dir, w, h, l, progress, &mut slice_count)?;
         all_quads.extend(quads);
     }
 
     let greedy_quad_count = all_quads.len();
-    pb.finish_with_message(format!("Generated {} greedy quads", greedy_quad_count));
+    progress.finish(&format!("Generated {} greedy quads", greedy_quad_count));
 
     // Phase 3: Generate quads for partial blocks
     if !partial_blocks.is_empty() {
-        let pb = create_progress_bar(partial_blocks.len() as u64, "Generating partial block meshes");
+        progress.start(partial_blocks.len() as u64, "Generating partial block meshes");
 
         for (i, info) in partial_blocks.iter().enumerate() {
             if i % 1000 == 0 {
-                pb.set_position(i as u64);
+                progress.set_position(i as u64);
+                progress::check_cancel(progress)?;
             }
 
             // Skip empty geometry (air-like blocks)
@@ -1424,33 +2201,36 @@ fn generate_greedy_geometry<W: Write>(
                 continue;
             }
 
-            let quads = generate_partial_block_quads(info, schematic, w, h, l);
+            let quads = generate_partial_block_quads(info, schematic);
             all_quads.extend(quads);
         }
 
         let partial_quad_count = all_quads.len() - greedy_quad_count;
-        pb.finish_with_message(format!("Generated {} partial block quads", partial_quad_count));
+        progress.finish(&format!("Generated {} partial block quads", partial_quad_count));
     }
 
-    // Sort quads by material for efficient rendering
+    // Sort quads by material for efficient rendering, and by region first
+    // when those are the objects (both sorts keep the order of equal quads)
     all_quads.sort_by(|a, b| a.material.cmp(&b.material));
+    if groups == ObjGroups::Chunk {
+        all_quads.sort_by_cached_key(|quad| quad_chunk(&quad.vertices));
+    }
 
     // Write quads to OBJ
-    let pb = create_progress_bar(all_quads.len() as u64, "Writing OBJ");
+    progress.start(all_quads.len() as u64, "Writing OBJ");
 
     let mut vertex_index = 1u32;
     let mut vt_index = 1u32;
-    let mut current_material = String::new();
+    let mut grouper = ObjGrouper::new(groups);
 
     for (i, quad) in all_quads.iter().enumerate() {
         if i % 10_000 == 0 {
-            pb.set_position(i as u64);
+            progress.set_position(i as u64);
+            progress::check_cancel(progress)?;
         }
 
-        if quad.material != current_material {
-            writeln!(obj_file, "usemtl {}", quad.material)?;
-            current_material = quad.material.clone();
-        }
+        let chunk = if groups == ObjGroups::Chunk { quad_chunk(&quad.vertices) } else { [0; 3] };
+        grouper.begin(obj_file, &quad.material, chunk)?;
 
         // Write 4 vertices
         for v in &quad.vertices {
@@ -1476,7 +2256,7 @@ fn generate_greedy_geometry<W: Write>(
         vertex_index += 4;
     }
 
-    pb.finish_with_message(format!("Written {} quads ({} vertices)", all_quads.len(), vertex_index - 1));
+    progress.finish(&format!("Written {} quads ({} vertices)", all_quads.len(), vertex_index - 1));
     Ok(())
 }
 
@@ -1484,67 +2264,49 @@ fn generate_greedy_geometry<W: Write>(
 /// Partial blocks are skipped and handled separately
 fn greedy_mesh_direction_full_only(
     schematic: &UnifiedSchematic,
-    dir: FaceDir,
+    dir: Face,
     w: usize, h: usize, l: usize,
-    pb: &ProgressBar,
+    progress: &dyn Progress,
     slice_count: &mut u64,
-) -> Vec<GreedyQuad> {
+) -> Result<Vec<GreedyQuad>, SchemError> {
     let mut quads = Vec::new();
 
-    let (d1_size, d2_size, slice_count_total) = match dir {
-        FaceDir::XNeg | FaceDir::XPos => (h, l, w),
-        FaceDir::YNeg | FaceDir::YPos => (w, l, h),
-        FaceDir::ZNeg | FaceDir::ZPos => (w, h, l),
+    let (d1_size, d2_size, slice_count_total) = match dir.axis() {
+        Axis::X => (h, l, w),
+        Axis::Y => (w, l, h),
+        Axis::Z => (w, h, l),
     };
 
     for slice_idx in 0..slice_count_total {
         *slice_count += 1;
         if *slice_count % 10 == 0 {
-            pb.set_position(*slice_count);
+            progress.set_position(*slice_count);
+            progress::check_cancel(progress)?;
         }
 
         let mut mask: Vec<Vec<Option<String>>> = vec![vec![None; d2_size]; d1_size];
 
         for d1 in 0..d1_size {
             for d2 in 0..d2_size {
-                let (x, y, z) = match dir {
-                    FaceDir::XNeg => (slice_idx, d1, d2),
-                    FaceDir::XPos => (slice_idx, d1, d2),
-                    FaceDir::YNeg => (d1, slice_idx, d2),
-                    FaceDir::YPos => (d1, slice_idx, d2),
-                    FaceDir::ZNeg => (d1, d2, slice_idx),
-                    FaceDir::ZPos => (d1, d2, slice_idx),
+                let (x, y, z) = match dir.axis() {
+                    Axis::X => (slice_idx, d1, d2),
+                    Axis::Y => (d1, slice_idx, d2),
+                    Axis::Z => (d1, d2, slice_idx),
                 };
 
                 if x >= w || y >= h || z >= l { continue; }
 
-                if let Some(block) = schematic.get_block(x as u16, y as u16, z as u16) {
+                if let Some(block) = schematic.get_block(x as u32, y as u32, z as u32) {
                     if block.is_air() { continue; }
 
                     // SKIP partial blocks - they are handled separately
                     if !is_full_block(&block) { continue; }
 
-                    let neighbor = match dir {
-                        FaceDir::XNeg => if x == 0 { None } else { schematic.get_block((x - 1) as u16, y as u16, z as u16) },
-                        FaceDir::XPos => schematic.get_block((x + 1) as u16, y as u16, z as u16),
-                        FaceDir::YNeg => if y == 0 { None } else { schematic.get_block(x as u16, (y - 1) as u16, z as u16) },
-                        FaceDir::YPos => schematic.get_block(x as u16, (y + 1) as u16, z as u16),
-                        FaceDir::ZNeg => if z == 0 { None } else { schematic.get_block(x as u16, y as u16, (z - 1) as u16) },
-                        FaceDir::ZPos => schematic.get_block(x as u16, y as u16, (z + 1) as u16),
-                    };
-
-                    let neighbor_face = match dir {
-                        FaceDir::XNeg => Face::XPos,
-                        FaceDir::XPos => Face::XNeg,
-                        FaceDir::YNeg => Face::YPos,
-                        FaceDir::YPos => Face::YNeg,
-                        FaceDir::ZNeg => Face::ZPos,
-                        FaceDir::ZPos => Face::ZNeg,
-                    };
+                    let neighbor = schematic.get_neighbor((x as u32, y as u32, z as u32), dir);
 
                     let is_exposed = match neighbor {
                         None => true,
-                        Some(n) => neighbor_exposes_face_dir(&n, neighbor_face),
+                        Some(n) => neighbor_exposes_face_dir(block, n, dir.opposite()),
                     };
 
                     if is_exposed {
@@ -1559,7 +2321,7 @@ fn greedy_mesh_direction_full_only(
         quads.extend(slice_quads);
     }
 
-    quads
+    Ok(quads)
 }
 
 /// Greedy mesh a 2D mask into rectangles
@@ -1568,7 +2330,7 @@ fn greedy_mesh_2d(
     d1_size: usize,
     d2_size: usize,
     slice_idx: usize,
-    dir: FaceDir,
+    dir: Face,
     w: usize, h: usize, l: usize,
 ) -> Vec<GreedyQuad> {
     let mut quads = Vec::new();
@@ -1634,7 +2396,7 @@ fn create_quad_vertices(
     d2: usize,
     width: usize,
     height: usize,
-    dir: FaceDir,
+    dir: Face,
     _w: usize, _h: usize, _l: usize,
 ) -> [(f32, f32, f32); 4] {
     let s = slice as f32;
@@ -1642,37 +2404,37 @@ fn create_quad_vertices(
     let (wf, hf) = (width as f32, height as f32);
 
     match dir {
-        FaceDir::XNeg => [
+        Face::West => [
             (s, d1f, d2f),
             (s, d1f, d2f + wf),
             (s, d1f + hf, d2f + wf),
             (s, d1f + hf, d2f),
         ],
-        FaceDir::XPos => [
+        Face::East => [
             (s + 1.0, d1f, d2f + wf),
             (s + 1.0, d1f, d2f),
             (s + 1.0, d1f + hf, d2f),
             (s + 1.0, d1f + hf, d2f + wf),
         ],
-        FaceDir::YNeg => [
+        Face::Down => [
             (d1f, s, d2f + wf),
             (d1f, s, d2f),
             (d1f + hf, s, d2f),
             (d1f + hf, s, d2f + wf),
         ],
-        FaceDir::YPos => [
+        Face::Up => [
             (d1f, s + 1.0, d2f),
             (d1f, s + 1.0, d2f + wf),
             (d1f + hf, s + 1.0, d2f + wf),
             (d1f + hf, s + 1.0, d2f),
         ],
-        FaceDir::ZNeg => [
+        Face::North => [
             (d1f + hf, d2f, s),
             (d1f, d2f, s),
             (d1f, d2f + wf, s),
             (d1f + hf, d2f + wf, s),
         ],
-        FaceDir::ZPos => [
+        Face::South => [
             (d1f, d2f, s + 1.0),
             (d1f + hf, d2f, s + 1.0),
             (d1f + hf, d2f + wf, s + 1.0),
@@ -1684,28 +2446,8 @@ fn create_quad_vertices(
 /// Check if a neighbor block exposes the current block's face
 /// The neighbor_face is the face of the neighbor that touches the current block
 #[inline]
-fn neighbor_exposes_face_dir(block: &crate::Block, neighbor_face: Face) -> bool {
-    if block.is_air() {
-        return true;
-    }
-    // Check if neighbor covers the face that touches us
-    // If it doesn't cover that face, our face is exposed
-    !block_covers_face(block, neighbor_face)
-}
-
-#[inline]
-fn is_exposed_fast(schematic: &UnifiedSchematic, x: u16, y: u16, z: u16, w: u16, h: u16, l: u16) -> bool {
-    if x == 0 || x == w - 1 || y == 0 || y == h - 1 || z == 0 || z == l - 1 {
-        return true;
-    }
-    // Check each neighbor - the neighbor's face that touches us is opposite to our face
-    if let Some(block) = schematic.get_block(x - 1, y, z) { if neighbor_exposes_face_dir(&block, Face::XPos) { return true; } } else { return true; }
-    if let Some(block) = schematic.get_block(x + 1, y, z) { if neighbor_exposes_face_dir(&block, Face::XNeg) { return true; } } else { return true; }
-    if let Some(block) = schematic.get_block(x, y - 1, z) { if neighbor_exposes_face_dir(&block, Face::YPos) { return true; } } else { return true; }
-    if let Some(block) = schematic.get_block(x, y + 1, z) { if neighbor_exposes_face_dir(&block, Face::YNeg) { return true; } } else { return true; }
-    if let Some(block) = schematic.get_block(x, y, z - 1) { if neighbor_exposes_face_dir(&block, Face::ZPos) { return true; } } else { return true; }
-    if let Some(block) = schematic.get_block(x, y, z + 1) { if neighbor_exposes_face_dir(&block, Face::ZNeg) { return true; } } else { return true; }
-    false
+fn neighbor_exposes_face_dir(block: &crate::Block, neighbor: &crate::Block, neighbor_face: Face) -> bool {
+    !block_geometry::hides_face(block, neighbor, neighbor_face)
 }
 
 #[inline]
@@ -1738,8 +2480,23 @@ pub fn export_html<P: AsRef<Path>>(
     schematic: &UnifiedSchematic,
     html_path: P,
     max_blocks: usize,
-) -> std::io::Result<()> {
-    let pb = create_progress_bar(max_blocks as u64, "Building HTML data");
+    options: &ExportOptions,
+) -> Result<(), SchemError> {
+    let html_path = html_path.as_ref();
+    let html = html_viewer(schematic, max_blocks, options)?;
+    let file = std::fs::File::create(html_path).map_err(|e| SchemError::export_io(html_path, e))?;
+    let mut file = BufWriter::new(file);
+    file.write_all(html.as_bytes())
+        .and_then(|_| file.flush())
+        .map_err(|e| SchemError::export_io(html_path, e))?;
+    Ok(())
+}
+
+/// The page [`export_html`] writes: a self-contained three.js viewer
+pub fn html_viewer(schematic: &UnifiedSchematic, max_blocks: usize, options: &ExportOptions) -> Result<String, SchemError> {
+    let progress = options.progress;
+    let schematic = &*options.visible(schematic);
+    progress.start(max_blocks as u64, "Building HTML data");
 
     let mut blocks_json = String::with_capacity(max_blocks * 20);
     blocks_json.push('[');
@@ -1751,25 +2508,27 @@ pub fn export_html<P: AsRef<Path>>(
             for x in 0..w {
                 if let Some(block) = schematic.get_block(x, y, z) {
                     if block.is_air() { continue; }
-                    if !is_exposed_fast(schematic, x, y, z, w, h, l) { continue; }
+                    if !block_geometry::is_exposed(schematic, x, y, z) { continue; }
                     if count >= max_blocks as u64 { break 'outer; }
 
-                    let (r, g, b) = get_block_color(&block.name);
+                    let (r, g, b) = options.block_color(&block.name);
                     let color = ((r * 255.0) as u32) << 16 | ((g * 255.0) as u32) << 8 | (b * 255.0) as u32;
 
                     if count > 0 { blocks_json.push(','); }
                     blocks_json.push_str(&format!("[{},{},{},{}]", x, y, z, color));
                     count += 1;
-                    if count % 10_000 == 0 { pb.set_position(count); }
+                    if count % 10_000 == 0 {
+                        progress.set_position(count);
+                        progress::check_cancel(progress)?;
+                    }
                 }
             }
         }
     }
     blocks_json.push(']');
-    pb.finish_with_message(format!("Included {} blocks", count));
+    progress.finish(&format!("Included {} blocks", count));
 
-    let mut file = BufWriter::new(std::fs::File::create(html_path)?);
-    let html = format!(r#"<!DOCTYPE html>
+    Ok(format!(r#"<!DOCTYPE html>
 <html>
 <head>
     <meta charset="utf-8">
@@ -1822,8 +2581,512 @@ pub fn export_html<P: AsRef<Path>>(
         cx = w as f32 * 1.5, cy = h as f32 * 1.2, cz = l as f32 * 1.5,
         tx = w as f32 / 2.0, ty = h as f32 / 2.0, tz = l as f32 / 2.0,
         grid = w.max(l) as f32 * 1.5,
-    );
-    file.write_all(html.as_bytes())?;
-    file.flush()?;
-    Ok(())
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nearest_block() {
+        let name = |matcher: &ColorMatcher, rgb| matcher.nearest(rgb).map(|(block, _)| block.full_name());
+        let wool = ColorMatcher::new(BlockPalette::Wool.blocks());
+        assert_eq!(name(&wool, (0.75, 0.2, 0.15)).as_deref(), Some("minecraft:red_wool"));
+        assert_eq!(name(&wool, (0.2, 0.3, 0.75)).as_deref(), Some("minecraft:blue_wool"));
+        assert_eq!(name(&wool, (1.0, 1.0, 1.0)).as_deref(), Some("minecraft:white_wool"));
+        let gray = ColorMatcher::new(BlockPalette::Grayscale.blocks());
+        assert_eq!(name(&gray, (0.0, 0.0, 0.0)).as_deref(), Some("minecraft:black_concrete"));
+        assert_eq!(name(&gray, (0.5, 0.5, 0.5)).as_deref(), Some("minecraft:stone"));
+        let map = ColorMatcher::new(BlockPalette::MapArt.blocks());
+        assert_eq!(name(&map, (0.0, 0.45, 0.0)).as_deref(), Some("minecraft:oak_leaves[persistent=true]"));
+        assert_eq!(map.nearest((1.0, 1.0, 1.0)).unwrap().1, (220.0 / 255.0, 220.0 / 255.0, 220.0 / 255.0));
+        assert!(ColorMatcher::new([]).nearest((0.5, 0.5, 0.5)).is_none());
+        assert_eq!("map-art".parse(), Ok(BlockPalette::MapArt));
+    }
+
+    #[test]
+    fn test_color_map_overrides() {
+        let mut colors = ColorMap::new();
+        colors.insert("create:*_casing", (0.6, 0.45, 0.3));
+        colors.insert("create:brass_casing", (0.8, 0.7, 0.3));
+        colors.insert("stone", (1.0, 0.0, 0.0));
+
+        assert_eq!(colors.get("create:andesite_casing"), (0.6, 0.45, 0.3));
+        // Exact names win over patterns
+        assert_eq!(colors.get("create:brass_casing"), (0.8, 0.7, 0.3));
+        assert_eq!(colors.get("minecraft:stone"), (1.0, 0.0, 0.0));
+        assert_eq!(colors.get("minecraft:water"), get_block_color("minecraft:water"));
+        assert_eq!(colors.get("create:shaft"), DEFAULT_COLOR);
+        assert_eq!(colors.get_override("create:casing_frame"), None);
+        assert_eq!(colors.unknown(), ["create:shaft"]);
+    }
+
+    #[test]
+    fn test_stone_inside_glass_keeps_its_faces() {
+        use crate::builder::SchematicBuilder;
+        use crate::Block;
+
+        // Stone sphere filling a glass cube, so every stone neighbor is stone or glass
+        let mut builder = SchematicBuilder::new(9, 9, 9);
+        builder.fill((0, 0, 0), (8, 8, 8), Block::new("minecraft:glass")).unwrap();
+        for (x, y, z) in (1..8).flat_map(|x| (1..8).flat_map(move |y| (1..8).map(move |z| (x, y, z)))) {
+            let d2 = (x - 4i32).pow(2) + (y - 4i32).pow(2) + (z - 4i32).pow(2);
+            if d2 <= 9 {
+                builder.set_block(x as u32, y as u32, z as u32, Block::new("minecraft:stone")).unwrap();
+            }
+        }
+        let schem = builder.build();
+        assert!(block_geometry::is_exposed(&schem, 4, 1, 4));
+        assert!(!block_geometry::is_exposed(&schem, 4, 4, 4));
+
+        let dir = std::env::temp_dir().join(format!("schem-tool-glass-sphere-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let options = ExportOptions::new();
+        let naive = dir.join("naive.obj");
+        export_obj(&schem, &naive, true, true, &options).unwrap();
+        let greedy = dir.join("greedy.obj");
+        export_obj_greedy(&schem, &greedy, None, &options).unwrap();
+        for path in [&naive, &greedy] {
+            let obj = std::fs::read_to_string(path).unwrap();
+            assert!(obj.contains("usemtl stone\n"), "no stone in {}", path.display());
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_glass_cube_exports_only_its_shell() {
+        use crate::builder::SchematicBuilder;
+        use crate::Block;
+
+        let mut builder = SchematicBuilder::new(10, 10, 10);
+        builder.fill((0, 0, 0), (9, 9, 9), Block::new("minecraft:glass")).unwrap();
+        let schem = builder.build();
+        assert_eq!(schem.surface_blocks().count(), 1000 - 8 * 8 * 8);
+
+        let dir = std::env::temp_dir().join(format!("schem-tool-glass-cube-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("cube.obj");
+        export_obj_greedy(&schem, &path, None, &ExportOptions::new()).unwrap();
+        let obj = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(obj.lines().filter(|line| line.starts_with("f ")).count(), 6);
+
+        // Different stained glass colors still show the face between them
+        let red = Block::new("minecraft:red_stained_glass");
+        let blue = Block::new("minecraft:blue_stained_glass");
+        assert!(!block_geometry::hides_face(&red, &blue, Face::West));
+        assert!(block_geometry::hides_face(&red, &red, Face::West));
+    }
+
+    #[test]
+    fn test_partial_block_seams() {
+        use crate::builder::SchematicBuilder;
+
+        let block = |name: &str, properties: &[(&str, &str)]| {
+            let properties = properties.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+            Block::with_state(name, crate::BlockState { properties })
+        };
+        // Three joined fences, and three bottom slabs ending in a double slab
+        let mut builder = SchematicBuilder::new(4, 1, 3);
+        builder.set_block(0, 0, 0, block("minecraft:oak_fence", &[("east", "true")])).unwrap();
+        builder.set_block(1, 0, 0, block("minecraft:oak_fence", &[("west", "true"), ("east", "true")])).unwrap();
+        builder.set_block(2, 0, 0, block("minecraft:oak_fence", &[("west", "true")])).unwrap();
+        builder.fill((0, 0, 2), (2, 0, 2), block("minecraft:oak_slab", &[("type", "bottom")])).unwrap();
+        builder.set_block(3, 0, 2, block("minecraft:oak_slab", &[("type", "double")])).unwrap();
+        let schem = builder.build();
+
+        let dir = std::env::temp_dir().join(format!("schem-tool-seams-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("seams.obj");
+        export_obj_greedy(&schem, &path, None, &ExportOptions::new()).unwrap();
+        let obj = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        let mut material = "";
+        for line in obj.lines() {
+            if let Some(name) = line.strip_prefix("usemtl ") {
+                material = name;
+            } else if line.starts_with("f ") {
+                *counts.entry(material).or_default() += 1;
+            }
+        }
+
+        // Posts keep all six faces; of the four arms, only the ends facing
+        // the other fence's arm go
+        assert_eq!(counts["oak_fence"], 3 * 6 + 4 * 5);
+        // The slabs lose their sides against each other and the double slab,
+        // which still shows the upper half of its side
+        assert_eq!(counts["oak_slab"], 5 + 4 + 4 + 6);
+
+        let bottom = block("minecraft:oak_slab", &[("type", "bottom")]);
+        let top = block("minecraft:oak_slab", &[("type", "top")]);
+        assert!(block_geometry::hides_box_face(&bottom, &block_geometry::SLAB_BOTTOM, &bottom, Face::East));
+        assert!(!block_geometry::hides_box_face(&bottom, &block_geometry::SLAB_BOTTOM, &top, Face::East));
+    }
+
+    #[test]
+    fn test_seeded_model_variants() {
+        use std::io::Write as _;
+        use crate::builder::SchematicBuilder;
+
+        // A client.jar with a stone whose four variants turn a half-block lump
+        let dir = std::env::temp_dir().join(format!("schem-tool-variants-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let jar = dir.join("client.jar");
+        let mut zip = zip::ZipWriter::new(std::fs::File::create(&jar).unwrap());
+        let variants = [0, 90, 180, 270].map(|y| serde_json::json!({ "model": "minecraft:block/stone", "y": y }));
+        let files = [
+            ("assets/minecraft/blockstates/stone.json", serde_json::json!({ "variants": { "": variants } })),
+            ("assets/minecraft/models/block/stone.json", serde_json::json!({
+                "textures": { "all": "minecraft:block/stone" },
+                "elements": [{ "from": [0, 0, 0], "to": [16, 8, 8], "faces": { "up": { "texture": "#all" } } }],
+            })),
+        ];
+        for (name, json) in files {
+            zip.start_file(name, zip::write::SimpleFileOptions::default()).unwrap();
+            zip.write_all(json.to_string().as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+
+        let mut builder = SchematicBuilder::new(8, 1, 8);
+        builder.fill((0, 0, 0), (7, 0, 7), Block::new("minecraft:stone")).unwrap();
+        let floor = builder.build();
+        let export = |name: &str, seed: u64| {
+            let path = dir.join(name);
+            let options = ExportOptions::new().with_random(RandomContext::new(seed));
+            export_obj_with_models(&floor, &path, &jar, None, None, &options).unwrap();
+            // Everything but the mtllib line, which names the file
+            let obj = std::fs::read_to_string(&path).unwrap();
+            obj.lines().filter(|line| !line.starts_with("mtllib")).collect::<Vec<_>>().join("\n")
+        };
+        let first = export("a.obj", 1);
+        let again = export("b.obj", 1);
+        let other = export("c.obj", 2);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(first, again);
+        assert_ne!(first, other);
+    }
+
+    #[test]
+    fn test_cullface() {
+        use std::io::Write as _;
+        use crate::builder::SchematicBuilder;
+
+        // Vanilla-style stairs (modelled facing east, turned to face north) and a stone cube
+        let dir = std::env::temp_dir().join(format!("schem-tool-cullface-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let jar = dir.join("client.jar");
+        let face = |cullface: Option<&str>| match cullface {
+            Some(side) => serde_json::json!({ "texture": "#all", "cullface": side }),
+            None => serde_json::json!({ "texture": "#all" }),
+        };
+        let cube_faces: serde_json::Map<_, _> = Face::ALL.iter().map(|d| (d.name().to_string(), face(Some(d.name())))).collect();
+        let files = [
+            ("assets/minecraft/blockstates/stone.json", serde_json::json!({ "variants": { "": { "model": "minecraft:block/stone" } } })),
+            ("assets/minecraft/models/block/stone.json", serde_json::json!({
+                "textures": { "all": "minecraft:block/stone" },
+                "elements": [{ "from": [0, 0, 0], "to": [16, 16, 16], "faces": cube_faces }],
+            })),
+            ("assets/minecraft/blockstates/oak_stairs.json", serde_json::json!({
+                "variants": { "facing=north": { "model": "minecraft:block/oak_stairs", "y": 270 } },
+            })),
+            ("assets/minecraft/models/block/oak_stairs.json", serde_json::json!({
+                "textures": { "all": "minecraft:block/oak_planks" },
+                "elements": [
+                    { "from": [0, 0, 0], "to": [16, 8, 16], "faces": {
+                        "down": face(Some("down")), "up": face(None), "north": face(Some("north")),
+                        "south": face(Some("south")), "west": face(Some("west")), "east": face(Some("east")),
+                    } },
+                    { "from": [8, 8, 0], "to": [16, 16, 16], "faces": {
+                        "up": face(Some("up")), "north": face(Some("north")), "south": face(Some("south")),
+                        "west": face(None), "east": face(Some("east")),
+                    } },
+                ],
+            })),
+        ];
+        let mut zip = zip::ZipWriter::new(std::fs::File::create(&jar).unwrap());
+        for (name, json) in files {
+            zip.start_file(name, zip::write::SimpleFileOptions::default()).unwrap();
+            zip.write_all(json.to_string().as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+
+        let stairs = Block::with_state("minecraft:oak_stairs", crate::BlockState {
+            properties: [("facing".to_string(), "north".to_string())].into_iter().collect(),
+        });
+        // Faces per material
+        let export = |schem: &UnifiedSchematic| {
+            let path = dir.join("stairs.obj");
+            export_obj_with_models(schem, &path, &jar, None, None, &ExportOptions::new()).unwrap();
+            let mut counts: HashMap<String, usize> = HashMap::new();
+            let mut material = String::new();
+            for line in std::fs::read_to_string(&path).unwrap().lines() {
+                if let Some(name) = line.strip_prefix("usemtl ") {
+                    material = name.to_string();
+                } else if line.starts_with("f ") {
+                    *counts.entry(material.clone()).or_default() += 1;
+                }
+            }
+            counts
+        };
+
+        let mut builder = SchematicBuilder::new(1, 2, 2);
+        builder.set_block(0, 1, 1, stairs).unwrap();
+        let mut schem = builder.build();
+        let alone = export(&schem);
+        // Against a wall to the north, on a floor
+        schem.set_block(0, 1, 0, Block::new("minecraft:stone")).unwrap();
+        schem.set_block(0, 0, 1, Block::new("minecraft:stone")).unwrap();
+        let against_wall = export(&schem);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(alone["oak_planks"], 11);
+        // The underside and both faces turned to the wall are hidden
+        assert_eq!(against_wall["oak_planks"], 8);
+        // The floor's top is under the stairs; the wall's face against them shows
+        assert_eq!(against_wall["stone"], 6 + 5);
+    }
+
+    #[test]
+    fn test_obj_groups() {
+        use crate::builder::SchematicBuilder;
+
+        // Stone fills the first 16-block region, glass starts the next
+        let mut builder = SchematicBuilder::new(20, 1, 1);
+        builder.fill((0, 0, 0), (15, 0, 0), Block::new("minecraft:stone")).unwrap();
+        builder.fill((16, 0, 0), (19, 0, 0), Block::new("minecraft:glass")).unwrap();
+        let schem = builder.build();
+
+        let dir = std::env::temp_dir().join(format!("schem-tool-obj-groups-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let export = |groups: ObjGroups| {
+            let path = dir.join(format!("{}.obj", groups));
+            export_obj_greedy(&schem, &path, None, &ExportOptions::new().with_obj_groups(groups)).unwrap();
+            std::fs::read_to_string(&path).unwrap()
+        };
+        let objects = |obj: &str| obj.lines().filter(|line| line.starts_with("o ")).map(str::to_string).collect::<Vec<_>>();
+        let by_material = export(ObjGroups::Material);
+        let by_chunk = export(ObjGroups::Chunk);
+        let single = export(ObjGroups::None);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(objects(&by_material), ["o glass", "o stone"]);
+        assert_eq!(objects(&by_chunk), ["o chunk_0_0_0", "o chunk_1_0_0"]);
+        assert!(objects(&single).is_empty());
+        // The stone face against the glass belongs to the stone's region
+        let second = &by_chunk[by_chunk.find("o chunk_1_0_0").unwrap()..];
+        assert!(second.lines().filter_map(|line| line.strip_prefix("v ")).all(|v| v.split(' ').next().unwrap().parse::<f32>().unwrap() >= 16.0));
+        assert_eq!("chunk".parse::<ObjGroups>(), Ok(ObjGroups::Chunk));
+    }
+
+    #[test]
+    fn test_spill_file_writes_materials_together() {
+        let path = std::env::temp_dir().join(format!("schem-tool-spill-{}.obj.spill", std::process::id()));
+        let mut spill = SpillFile::create(path.clone()).unwrap();
+        spill.append("stone", b"v 0 0 0\n").unwrap();
+        spill.append("glass", b"v 1 0 0\n").unwrap();
+        spill.append("stone", b"v 2 0 0\n").unwrap();
+
+        let mut out = Vec::new();
+        spill.copy_runs(&mut out, &mut ObjGrouper::new(ObjGroups::Material)).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "o glass\nusemtl glass\nv 1 0 0\no stone\nusemtl stone\nv 0 0 0\nv 2 0 0\n");
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_technical_blocks_are_left_out_by_default() {
+        use crate::builder::SchematicBuilder;
+
+        let mut builder = SchematicBuilder::new(3, 1, 1);
+        builder.set_block(0, 0, 0, Block::new("minecraft:stone")).unwrap();
+        builder.set_block(1, 0, 0, Block::new("minecraft:barrier")).unwrap();
+        builder.set_block(2, 0, 0, Block::new("minecraft:structure_void")).unwrap();
+        let schem = builder.build();
+        assert_eq!(schem.solid_blocks(), 1);
+
+        let options = ExportOptions::new();
+        let visible = options.visible(&schem);
+        assert_eq!(visible.blocks.iter().filter(|b| b.is_air()).count(), 2);
+        assert!(matches!(options.with_technical(true).visible(&schem), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_block_filters() {
+        use crate::builder::SchematicBuilder;
+
+        let mut builder = SchematicBuilder::new(3, 1, 1);
+        builder.set_block(0, 0, 0, Block::new("minecraft:stone")).unwrap();
+        builder.set_block(1, 0, 0, Block::new("minecraft:scaffolding")).unwrap();
+        builder.set_block(2, 0, 0, Block::new("minecraft:oak_planks")).unwrap();
+        let schem = builder.build();
+        let names = |options: ExportOptions| options.visible(&schem).blocks.iter().map(|b| b.name.clone()).collect::<Vec<_>>();
+
+        let scaffolding = BlockSelector::new(["scaffold"]);
+        assert_eq!(names(ExportOptions::new().with_exclude(&scaffolding)), ["minecraft:stone", "minecraft:air", "minecraft:oak_planks"]);
+        let planks = BlockSelector::new(["planks", "scaffold"]);
+        assert_eq!(names(ExportOptions::new().with_only(&planks).with_exclude(&scaffolding)), ["minecraft:air", "minecraft:air", "minecraft:oak_planks"]);
+        // Nothing filtered out, nothing copied
+        let glass = BlockSelector::new(["glass"]);
+        assert!(matches!(ExportOptions::new().with_exclude(&glass).visible(&schem), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_export_region() {
+        use crate::builder::SchematicBuilder;
+
+        let mut builder = SchematicBuilder::new(8, 4, 8);
+        builder.fill((0, 0, 0), (7, 3, 7), Block::new("minecraft:stone")).unwrap();
+        let schem = builder.build();
+
+        let region = ExportRegion::new((5, -2, 9), (2, 1, 3));
+        assert_eq!(region, ExportRegion { min: (2, -2, 3), max: (5, 1, 9) });
+        assert_eq!(region.clamp(&schem), Some([(2, 5), (0, 1), (3, 7)]));
+        assert_eq!(ExportRegion::new((8, 0, 0), (9, 1, 1)).clamp(&schem), None);
+
+        let options = ExportOptions::new().with_region(region);
+        let visible = options.visible(&schem);
+        assert_eq!((visible.width, visible.height, visible.length), (4, 2, 5));
+        // The cut faces of a solid block count as exposed
+        assert!(block_geometry::is_exposed(&visible, 1, 1, 2));
+        assert_eq!(ExportOptions::new().with_region(ExportRegion::new((8, 0, 0), (9, 1, 1))).visible(&schem).volume(), 0);
+    }
+
+    #[test]
+    fn test_explode() {
+        let layers = Explode::new(1.5);
+        assert_eq!([0, 1, 2].map(|y| layers.lift(y)), [0.0, 1.5, 3.0]);
+        assert!(layers.at_slice_edge(7));
+
+        let slices = Explode::new(2.0).every(4);
+        assert_eq!([0, 3, 4, 9].map(|y| slices.lift(y)), [0.0, 0.0, 2.0, 4.0]);
+        assert_eq!([3, 4, 5, 6].map(|y| slices.at_slice_edge(y)), [true, true, false, false]);
+        assert_eq!(Explode::new(1.0).every(0).every, 1);
+        assert_eq!(ExportOptions::new().lift(5), 0.0);
+    }
+
+    #[test]
+    fn test_export_report_merges_counts() {
+        let report = ExportReport::new();
+        let options = ExportOptions::new().with_report(&report);
+        assert!(report.is_empty());
+
+        let counts = |entries: &[(&str, usize)]| entries.iter().map(|&(n, c)| (n.to_string(), c)).collect::<HashMap<_, _>>();
+        options.report_missing(MissingKind::Blockstate, &counts(&[("create:cogwheel", 3), ("create:shaft", 5)]));
+        options.report_missing(MissingKind::Blockstate, &counts(&[("create:cogwheel", 4)]));
+        options.report_missing(MissingKind::Texture, &HashMap::new());
+        assert!(!report.is_empty());
+        assert_eq!(report.blocks(MissingKind::Blockstate), [("create:cogwheel".to_string(), 7), ("create:shaft".to_string(), 5)]);
+        assert_eq!(report.to_json(), serde_json::json!({
+            "missing_blockstate": {"create:cogwheel": 7, "create:shaft": 5},
+            "unresolvable_model": {},
+            "missing_texture": {},
+        }));
+    }
+
+    /// Block positions whose `dir` face the quads cover, with the quad's material.
+    /// Fails on quads that aren't axis-aligned unit-grid rectangles facing `dir`,
+    /// that cover a face twice or that lie outside the volume.
+    fn rasterize(quads: &[GreedyQuad], dir: Face, size: [usize; 3]) -> Result<HashMap<[usize; 3], String>, String> {
+        let (axis, positive) = (dir.axis().index(), dir.is_positive());
+        let mut cells = HashMap::new();
+        for quad in quads {
+            let v = quad.vertices.map(|(x, y, z)| [x, y, z]);
+            let plane = v[0][axis];
+            if v.iter().any(|p| p[axis] != plane) {
+                return Err(format!("{:?} quad isn't flat: {:?}", dir, v));
+            }
+
+            // Counter-clockwise seen from outside: the normal points along dir
+            let (e1, e2) = ([0, 1, 2].map(|i| v[1][i] - v[0][i]), [0, 1, 2].map(|i| v[2][i] - v[0][i]));
+            let normal = [e1[1] * e2[2] - e1[2] * e2[1], e1[2] * e2[0] - e1[0] * e2[2], e1[0] * e2[1] - e1[1] * e2[0]];
+            if (normal[axis] > 0.0) != positive || (0..3).any(|i| i != axis && normal[i] != 0.0) {
+                return Err(format!("{:?} quad faces the wrong way: {:?}", dir, v));
+            }
+
+            let range = |i: usize| {
+                let lo = v.iter().map(|p| p[i]).fold(f32::INFINITY, f32::min);
+                let hi = v.iter().map(|p| p[i]).fold(f32::NEG_INFINITY, f32::max);
+                (lo as usize)..(hi as usize)
+            };
+            let layer = plane as usize;
+            let layer = if positive { layer.checked_sub(1).ok_or("face below the volume")? } else { layer };
+            let mut ranges = [range(0), range(1), range(2)];
+            ranges[axis] = layer..layer + 1;
+            if (0..3).any(|i| ranges[i].end > size[i]) {
+                return Err(format!("{:?} quad leaves the volume: {:?}", dir, v));
+            }
+            for x in ranges[0].clone() {
+                for y in ranges[1].clone() {
+                    for z in ranges[2].clone() {
+                        if cells.insert([x, y, z], quad.material.clone()).is_some() {
+                            return Err(format!("{:?} faces at {:?} covered twice", dir, [x, y, z]));
+                        }
+                    }
+                }
+            }
+        }
+        Ok(cells)
+    }
+
+    /// Exposed `dir` faces of full blocks, one by one
+    fn exposed_faces(schem: &UnifiedSchematic, dir: Face) -> HashMap<[usize; 3], String> {
+        let (axis, positive) = (dir.axis().index(), dir.is_positive());
+        schem.iter_blocks()
+            .filter(|(_, block)| !block.is_air() && is_full_block(block))
+            .filter(|&((x, y, z), block)| {
+                let mut pos = [x as i64, y as i64, z as i64];
+                pos[axis] += if positive { 1 } else { -1 };
+                let neighbor = (pos.iter().all(|&c| c >= 0))
+                    .then(|| schem.get_block(pos[0] as u32, pos[1] as u32, pos[2] as u32))
+                    .flatten();
+                neighbor.is_none_or(|n| !block_geometry::hides_face(block, n, dir.opposite()))
+            })
+            .map(|((x, y, z), block)| {
+                let material = block.display_name().replace([':', '[', ']', '=', ','], "_");
+                ([x as usize, y as usize, z as usize], material)
+            })
+            .collect()
+    }
+
+    fn volume() -> impl proptest::strategy::Strategy<Value = UnifiedSchematic> {
+        use proptest::prelude::*;
+
+        let kinds = ["minecraft:stone", "minecraft:dirt", "minecraft:glass", "minecraft:red_stained_glass"];
+        (1..=16u32, 1..=16u32, 1..=16u32, proptest::sample::subsequence(kinds.to_vec(), 2..=3))
+            .prop_flat_map(|(w, h, l, kinds)| {
+                let cells = proptest::collection::vec(0..=kinds.len(), (w * h * l) as usize);
+                (Just((w, h, l, kinds)), cells)
+            })
+            .prop_map(|((w, h, l, kinds), cells)| {
+                let mut builder = crate::builder::SchematicBuilder::new(w, h, l);
+                let mut cells = cells.into_iter();
+                for y in 0..h {
+                    for z in 0..l {
+                        for x in 0..w {
+                            // Index 0 stays air
+                            if let Some(kind) = cells.next().and_then(|i| i.checked_sub(1)) {
+                                builder.set_block(x, y, z, Block::new(kinds[kind])).unwrap();
+                            }
+                        }
+                    }
+                }
+                builder.build()
+            })
+    }
+
+    proptest::proptest! {
+        #![proptest_config(proptest::test_runner::Config::with_cases(64))]
+
+        #[test]
+        fn test_greedy_mesh_covers_exposed_faces_once(schem in volume()) {
+            let size = [schem.width as usize, schem.height as usize, schem.length as usize];
+            for dir in Face::ALL {
+                let quads = greedy_mesh_direction_full_only(&schem, dir, size[0], size[1], size[2], &NoProgress, &mut 0).unwrap();
+                let covered = rasterize(&quads, dir, size).map_err(proptest::test_runner::TestCaseError::fail)?;
+                proptest::prop_assert_eq!(covered, exposed_faces(&schem, dir), "{:?} faces differ", dir);
+            }
+        }
+    }
 }
@@ -3,29 +3,18 @@
 //! Generates all block geometry at actual world positions, grouped by material.
 //! Supports Minecraft JSON models and embedded textures.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::io::{BufWriter, Write};
 use std::path::Path;
 
-use indicatif::{ProgressBar, ProgressStyle};
 use serde::Serialize;
 
+use crate::export3d::{ColorMap, ExportOptions, MissingKind};
 use crate::mc_models::{ModelManager, GeneratedQuad};
-use crate::textures::TextureManager;
-use crate::UnifiedSchematic;
-
-/// Create a progress bar with consistent style
-fn create_progress_bar(total: u64, message: &str) -> ProgressBar {
-    let pb = ProgressBar::new(total);
-    pb.set_style(
-        ProgressStyle::default_bar()
-            .template("{msg} [{bar:40.cyan/blue}] {pos}/{len} ({percent}%) {eta}")
-            .unwrap()
-            .progress_chars("=>-"),
-    );
-    pb.set_message(message.to_string());
-    pb
-}
+use crate::direction::Axis;
+use crate::progress;
+use crate::textures::{Biome, TextureManager};
+use crate::{SchemError, UnifiedSchematic};
 
 // ============ glTF JSON structures ============
 
@@ -214,8 +203,8 @@ impl MaterialGeometry {
         }
     }
 
-    /// Append a quad (4 vertices, 2 triangles) to this geometry
-    fn append_quad(&mut self, quad: &GeneratedQuad) {
+    /// Append a quad (4 vertices, 2 triangles) to this geometry, moved up by `lift`
+    fn append_quad(&mut self, quad: &GeneratedQuad, lift: f32) {
         let base_idx = (self.positions.len() / 3) as u32;
 
         // Compute normal from first 3 vertices
@@ -245,7 +234,7 @@ impl MaterialGeometry {
         let v_max = quad.uv_coords.iter().map(|uv| uv.1).fold(f32::NEG_INFINITY, f32::max);
 
         for (i, v) in quad.vertices.iter().enumerate() {
-            self.positions.extend_from_slice(&[v.0, v.1, v.2]);
+            self.positions.extend_from_slice(&[v.0, v.1 + lift, v.2]);
             self.normals.extend_from_slice(&[normal.0, normal.1, normal.2]);
             let flipped_v = v_min + v_max - quad.uv_coords[i].1;
             self.uvs.extend_from_slice(&[quad.uv_coords[i].0, flipped_v]);
@@ -271,6 +260,7 @@ fn generate_cube_quads(x: f32, y: f32, z: f32, texture: &str) -> Vec<GeneratedQu
             texture: texture.to_string(),
             face_dir: crate::mc_models::FaceDirection::South,
             tint_index: -1,
+            cullface: None,
         },
         // Back (z-)
         GeneratedQuad {
@@ -279,6 +269,7 @@ fn generate_cube_quads(x: f32, y: f32, z: f32, texture: &str) -> Vec<GeneratedQu
             texture: texture.to_string(),
             face_dir: crate::mc_models::FaceDirection::North,
             tint_index: -1,
+            cullface: None,
         },
         // Top (y+)
         GeneratedQuad {
@@ -287,6 +278,7 @@ fn generate_cube_quads(x: f32, y: f32, z: f32, texture: &str) -> Vec<GeneratedQu
             texture: texture.to_string(),
             face_dir: crate::mc_models::FaceDirection::Up,
             tint_index: -1,
+            cullface: None,
         },
         // Bottom (y-)
         GeneratedQuad {
@@ -295,6 +287,7 @@ fn generate_cube_quads(x: f32, y: f32, z: f32, texture: &str) -> Vec<GeneratedQu
             texture: texture.to_string(),
             face_dir: crate::mc_models::FaceDirection::Down,
             tint_index: -1,
+            cullface: None,
         },
         // Right (x+)
         GeneratedQuad {
@@ -303,6 +296,7 @@ fn generate_cube_quads(x: f32, y: f32, z: f32, texture: &str) -> Vec<GeneratedQu
             texture: texture.to_string(),
             face_dir: crate::mc_models::FaceDirection::East,
             tint_index: -1,
+            cullface: None,
         },
         // Left (x-)
         GeneratedQuad {
@@ -311,37 +305,19 @@ fn generate_cube_quads(x: f32, y: f32, z: f32, texture: &str) -> Vec<GeneratedQu
             texture: texture.to_string(),
             face_dir: crate::mc_models::FaceDirection::West,
             tint_index: -1,
+            cullface: None,
         },
     ]
 }
 
-/// Check if block at (x, y, z) has any neighbor that is air or transparent
-fn is_exposed(schematic: &UnifiedSchematic, x: usize, y: usize, z: usize, w: usize, h: usize, l: usize) -> bool {
-    if x == 0 || x == w - 1 || y == 0 || y == h - 1 || z == 0 || z == l - 1 {
-        return true;
+/// Material color, with overrides from `colors` taking precedence over
+/// the name heuristics below (alpha always comes from the heuristics)
+fn material_color(name: &str, colors: Option<&ColorMap>) -> [f32; 4] {
+    let mut color = get_block_color(name);
+    if let Some((r, g, b)) = colors.and_then(|c| c.get_override(name)) {
+        color[..3].copy_from_slice(&[r, g, b]);
     }
-    let neighbors = [
-        schematic.get_block((x - 1) as u16, y as u16, z as u16),
-        schematic.get_block((x + 1) as u16, y as u16, z as u16),
-        schematic.get_block(x as u16, (y - 1) as u16, z as u16),
-        schematic.get_block(x as u16, (y + 1) as u16, z as u16),
-        schematic.get_block(x as u16, y as u16, (z - 1) as u16),
-        schematic.get_block(x as u16, y as u16, (z + 1) as u16),
-    ];
-    for n in &neighbors {
-        match n {
-            None => return true,
-            Some(b) if b.is_air() => return true,
-            Some(b) => {
-                let name = b.name.strip_prefix("minecraft:").unwrap_or(&b.name);
-                if name.contains("glass") || name.contains("leaves") || name.contains("water")
-                    || name.contains("lava") || name.contains("ice") {
-                    return true;
-                }
-            }
-        }
-    }
-    false
+    color
 }
 
 /// Get block color for material (returns [r, g, b, a])
@@ -438,10 +414,15 @@ fn apply_tint_in_memory(png_bytes: &[u8], tint: (f32, f32, f32)) -> Option<Vec<u
     Some(out.into_inner())
 }
 
+/// Shift a plains tint toward `biome`'s color, keeping this exporter's darker base shade
+fn biome_tint(base: (f32, f32, f32), color: (f32, f32, f32), plains: (f32, f32, f32)) -> (f32, f32, f32) {
+    (base.0 * color.0 / plains.0, base.1 * color.1 / plains.1, base.2 * color.2 / plains.2)
+}
+
 /// Check if a texture name needs foliage/grass tinting
-fn needs_tint(name: &str) -> Option<(f32, f32, f32)> {
-    let grass_tint = (0.44, 0.64, 0.22);
-    let foliage_tint = (0.38, 0.60, 0.18);
+fn needs_tint(name: &str, biome: Biome) -> Option<(f32, f32, f32)> {
+    let grass_tint = biome_tint((0.44, 0.64, 0.22), biome.grass_color(), Biome::Plains.grass_color());
+    let foliage_tint = biome_tint((0.38, 0.60, 0.18), biome.foliage_color(), Biome::Plains.foliage_color());
 
     if name.contains("grass") && !name.contains("dead") {
         Some(grass_tint)
@@ -475,21 +456,47 @@ pub fn export_glb<P: AsRef<Path>>(
     textures: Option<&TextureManager>,
     hollow: bool,
     resource_pack: Option<&Path>,
-) -> std::io::Result<()> {
+    options: &ExportOptions,
+) -> Result<(), SchemError> {
     let output_path = output_path.as_ref();
-
     // Warn if output path doesn't have .glb extension
     match output_path.extension().and_then(|e| e.to_str()) {
         Some("glb") => {}
         Some(ext) => {
-            eprintln!("Warning: Output file has .{} extension, but GLB format requires .glb", ext);
-            eprintln!("  Consider: --output {}.glb", output_path.file_stem().unwrap_or_default().to_string_lossy());
+            log::warn!(
+                "Output file has .{} extension, but GLB format requires .glb (consider --output {}.glb)",
+                ext,
+                output_path.file_stem().unwrap_or_default().to_string_lossy()
+            );
         }
         None => {
-            eprintln!("Warning: Output file has no extension. GLB files should use .glb extension.");
+            log::warn!("Output file has no extension. GLB files should use .glb extension.");
         }
     }
 
+    let write = || -> Result<(), SchemError> {
+        let file = BufWriter::with_capacity(4 * 1024 * 1024, std::fs::File::create(output_path)?);
+        write_glb(schematic, file, jar_path, textures, hollow, resource_pack, options)
+    };
+    write().map_err(|e| e.at_path(output_path))?;
+    log::info!("Exported to: {}", output_path.display());
+    Ok(())
+}
+
+/// [`export_glb`] into any writer, e.g. a `Vec<u8>` to serve the model
+/// without a file
+pub fn write_glb<W: Write>(
+    schematic: &UnifiedSchematic,
+    mut out: W,
+    jar_path: Option<&Path>,
+    textures: Option<&TextureManager>,
+    hollow: bool,
+    resource_pack: Option<&Path>,
+    options: &ExportOptions,
+) -> Result<(), SchemError> {
+    let progress = options.progress;
+    let schematic = &*options.visible(schematic);
+
     let (w, h, l) = (schematic.width as usize, schematic.height as usize, schematic.length as usize);
 
     // Load model manager if jar provided
@@ -497,8 +504,7 @@ pub fn export_glb<P: AsRef<Path>>(
         match ModelManager::from_jar_with_resource_pack(p, resource_pack) {
             Ok(mm) => Some(mm),
             Err(e) => {
-                eprintln!("Warning: Failed to load models from jar: {}", e);
-                eprintln!("  Falling back to simple cube geometry.");
+                log::warn!("Failed to load models from jar: {}; falling back to simple cube geometry", e);
                 None
             }
         }
@@ -508,34 +514,40 @@ pub fn export_glb<P: AsRef<Path>>(
     // Process in Y-layer chunks to limit peak memory (same as OBJ export)
     const CHUNK_SIZE: usize = 16;
     let num_chunks = (h + CHUNK_SIZE - 1) / CHUNK_SIZE;
-    let pb = create_progress_bar(num_chunks as u64, "Generating geometry");
+    progress.start(num_chunks as u64, "Generating geometry");
 
-    // material_name -> accumulated geometry
-    let mut material_geom: HashMap<String, MaterialGeometry> = HashMap::new();
+    // material_name -> accumulated geometry, in name order so meshes and
+    // materials come out the same on every run
+    let mut material_geom: BTreeMap<String, MaterialGeometry> = BTreeMap::new();
     // material_name -> (color, texture_lookup_key for TextureManager)
     // texture_lookup_key is the RAW name (e.g. "oak_planks"), NOT sanitized with _ replacements
-    let mut material_info: HashMap<String, ([f32; 4], Option<String>)> = HashMap::new();
+    let mut material_info: BTreeMap<String, ([f32; 4], Option<String>)> = BTreeMap::new();
     let mut total_quads = 0usize;
-    let mut skipped_no_model = 0usize;
-    let mut skipped_resolve_fail = 0usize;
+    // Block name -> count, for blocks skipped or drawn without some of their parts
+    let mut no_blockstate: HashMap<String, usize> = HashMap::new();
+    let mut no_model: HashMap<String, usize> = HashMap::new();
+    let mut no_texture: HashMap<String, usize> = HashMap::new();
+    // Texture lookups already made, and whether they found a file
+    let mut texture_found: HashMap<String, bool> = HashMap::new();
 
     // Helper: add a quad to a material's geometry
     let add_quad = |mat_name: &str, tex_lookup: Option<&str>, block_name: &str,
-                    quad: &GeneratedQuad,
-                    material_geom: &mut HashMap<String, MaterialGeometry>,
-                    material_info: &mut HashMap<String, ([f32; 4], Option<String>)>,
+                    quad: &GeneratedQuad, lift: f32,
+                    material_geom: &mut BTreeMap<String, MaterialGeometry>,
+                    material_info: &mut BTreeMap<String, ([f32; 4], Option<String>)>,
                     total_quads: &mut usize| {
         material_info.entry(mat_name.to_string()).or_insert_with(|| {
-            let color = get_block_color(block_name);
+            let color = material_color(block_name, options.colors);
             (color, tex_lookup.map(|s| s.to_string()))
         });
         let geom = material_geom.entry(mat_name.to_string()).or_insert_with(MaterialGeometry::new);
-        geom.append_quad(quad);
+        geom.append_quad(quad, lift);
         *total_quads += 1;
     };
 
     for chunk_idx in 0..num_chunks {
-        pb.set_position(chunk_idx as u64);
+        progress.set_position(chunk_idx as u64);
+        progress::check_cancel(progress)?;
 
         let y_start = chunk_idx * CHUNK_SIZE;
         let y_end = ((chunk_idx + 1) * CHUNK_SIZE).min(h);
@@ -543,12 +555,15 @@ pub fn export_glb<P: AsRef<Path>>(
         for y in y_start..y_end {
             for z in 0..l {
                 for x in 0..w {
-                    let Some(block) = schematic.get_block(x as u16, y as u16, z as u16) else { continue };
+                    let Some(block) = schematic.get_block(x as u32, y as u32, z as u32) else { continue };
                     if block.is_air() { continue; }
 
                     let xf = x as f32;
                     let yf = y as f32;
                     let zf = z as f32;
+                    // Exploded view moves the whole block, liquids included, with its layer
+                    let lift = options.lift(y as u32);
+                    let slice_edge = options.explode.is_some_and(|e| e.at_slice_edge(y as u32));
 
                     // === Water/lava handling (matches OBJ exactly) ===
                     let is_water_block = block.name == "minecraft:water" || block.name == "water";
@@ -557,7 +572,7 @@ pub fn export_glb<P: AsRef<Path>>(
                     let is_lava_cauldron = block.name == "minecraft:lava_cauldron";
 
                     // Register water material if needed
-                    if is_water_block || is_water_cauldron || crate::export3d::is_waterlogged(&block.state.properties) {
+                    if is_water_block || is_water_cauldron || block.state.is_waterlogged() {
                         material_info.entry("water_still".to_string()).or_insert_with(|| {
                             ([0.2, 0.4, 0.8, 0.6], Some("water_still".to_string()))
                         });
@@ -573,7 +588,7 @@ pub fn export_glb<P: AsRef<Path>>(
                         let water_quads = crate::export3d::generate_water_quads_culled(x, y, z, schematic, w, h, l);
                         for quad in &water_quads {
                             let geom = material_geom.entry("water_still".to_string()).or_insert_with(MaterialGeometry::new);
-                            geom.append_quad(quad);
+                            geom.append_quad(quad, lift);
                             total_quads += 1;
                         }
                         continue;
@@ -584,7 +599,7 @@ pub fn export_glb<P: AsRef<Path>>(
                         let lava_quads = crate::export3d::generate_lava_quads_culled(x, y, z, schematic, w, h, l);
                         for quad in &lava_quads {
                             let geom = material_geom.entry("lava_still".to_string()).or_insert_with(MaterialGeometry::new);
-                            geom.append_quad(quad);
+                            geom.append_quad(quad, lift);
                             total_quads += 1;
                         }
                         continue;
@@ -603,7 +618,7 @@ pub fn export_glb<P: AsRef<Path>>(
                             let mat_name = if is_lava_cauldron { "lava_still" } else { "water_still" };
                             for quad in &liquid_quads {
                                 let geom = material_geom.entry(mat_name.to_string()).or_insert_with(MaterialGeometry::new);
-                                geom.append_quad(quad);
+                                geom.append_quad(quad, lift);
                                 total_quads += 1;
                             }
                         }
@@ -612,16 +627,19 @@ pub fn export_glb<P: AsRef<Path>>(
 
                     // === Model-based rendering ===
                     if let Some(ref mut mm) = model_manager {
-                        let model_refs = mm.get_models_for_block(&block.name, &block.state.properties);
+                        let mut rng = options.random.rng_at((x as i32, y as i32, z as i32));
+                        let model_refs = mm.pick_models_for_block(&block.name, &block.state.properties, &mut rng);
 
                         if model_refs.is_empty() {
-                            skipped_no_model += 1;
+                            *no_blockstate.entry(block.name.clone()).or_default() += 1;
                             continue;
                         }
 
+                        let mut unresolved = false;
+                        let mut untextured = false;
                         for (model_ref, _) in &model_refs {
                             let Some(resolved) = mm.resolve_model(&model_ref.model) else {
-                                skipped_resolve_fail += 1;
+                                unresolved = true;
                                 continue;
                             };
 
@@ -633,28 +651,44 @@ pub fn export_glb<P: AsRef<Path>>(
                             );
 
                             for quad in &quads {
+                                // Exploded slices pull layers apart, so faces between them show
+                                let split = slice_edge && quad.cullface.is_some_and(|side| side.axis() == Axis::Y);
+                                if !split && crate::export3d::is_culled(quad, schematic, block, (x as u32, y as u32, z as u32)) {
+                                    continue;
+                                }
                                 let mat_name = texture_to_mat_name(&quad.texture);
                                 // Use ORIGINAL texture path for TextureManager lookup (not sanitized)
                                 let s = quad.texture.strip_prefix("minecraft:").unwrap_or(&quad.texture);
                                 let tex_lookup = s.strip_prefix("block/").unwrap_or(s);
 
-                                add_quad(&mat_name, Some(tex_lookup), &block.name, quad,
+                                add_quad(&mat_name, Some(tex_lookup), &block.name, quad, lift,
                                          &mut material_geom, &mut material_info, &mut total_quads);
+                                if let Some(tm) = textures {
+                                    untextured |= !*texture_found.entry(tex_lookup.to_string())
+                                        .or_insert_with(|| tm.get_texture(tex_lookup).is_some());
+                                }
                             }
                         }
+                        if unresolved {
+                            *no_model.entry(block.name.clone()).or_default() += 1;
+                        }
+                        if untextured {
+                            *no_texture.entry(block.name.clone()).or_default() += 1;
+                        }
 
                         // Waterlogged blocks: add water overlay (matches OBJ)
-                        if crate::export3d::is_waterlogged(&block.state.properties) {
+                        if block.state.is_waterlogged() {
                             let water_quads = crate::export3d::generate_water_quads_culled(x, y, z, schematic, w, h, l);
                             for quad in &water_quads {
                                 let geom = material_geom.entry("water_still".to_string()).or_insert_with(MaterialGeometry::new);
-                                geom.append_quad(quad);
+                                geom.append_quad(quad, lift);
                                 total_quads += 1;
                             }
                         }
                     } else {
-                        // No model manager — all cubes (hollow only applies here, like OBJ)
-                        if hollow && !is_exposed(schematic, x, y, z, w, h, l) {
+                        // No model manager — all cubes (hollow only applies here, like OBJ).
+                        // Exploded slices show their top and bottom layers.
+                        if hollow && !slice_edge && !crate::block_geometry::is_exposed(schematic, x as u32, y as u32, z as u32) {
                             continue;
                         }
                         let mat_name = block.display_name().replace([':', '[', ']', '=', ','], "_");
@@ -663,16 +697,19 @@ pub fn export_glb<P: AsRef<Path>>(
                             tm.get_texture(lookup)
                                 .map(|p| p.file_stem().unwrap().to_string_lossy().to_string())
                         });
+                        if textures.is_some() && tex_lookup_key.is_none() {
+                            *no_texture.entry(block.name.clone()).or_default() += 1;
+                        }
 
                         material_info.entry(mat_name.clone()).or_insert_with(|| {
-                            let color = get_block_color(&block.name);
+                            let color = material_color(&block.name, options.colors);
                             (color, tex_lookup_key.clone())
                         });
 
                         let cube_quads = generate_cube_quads(xf, yf, zf, &mat_name);
                         let geom = material_geom.entry(mat_name).or_insert_with(MaterialGeometry::new);
                         for quad in &cube_quads {
-                            geom.append_quad(quad);
+                            geom.append_quad(quad, lift);
                             total_quads += 1;
                         }
                     }
@@ -680,13 +717,10 @@ pub fn export_glb<P: AsRef<Path>>(
             }
         }
     }
-    pb.finish_with_message(format!("Generated {} quads, {} materials", total_quads, material_geom.len()));
-    if skipped_no_model > 0 {
-        eprintln!("  Note: {} blocks had no model definition (skipped)", skipped_no_model);
-    }
-    if skipped_resolve_fail > 0 {
-        eprintln!("  Warning: {} model references failed to resolve", skipped_resolve_fail);
-    }
+    progress.finish(&format!("Generated {} quads, {} materials", total_quads, material_geom.len()));
+    options.report_missing(MissingKind::Blockstate, &no_blockstate);
+    options.report_missing(MissingKind::Model, &no_model);
+    options.report_missing(MissingKind::Texture, &no_texture);
 
     // Phase 2: Build binary buffer — embed textures first, then geometry
     let mut binary_data: Vec<u8> = Vec::new();
@@ -698,20 +732,15 @@ pub fn export_glb<P: AsRef<Path>>(
     let mut texture_name_to_tex_idx: HashMap<String, usize> = HashMap::new();
 
     if textures.is_some() {
-        // Collect unique texture names
-        let mut unique_tex: Vec<String> = Vec::new();
-        let mut seen = std::collections::HashSet::new();
-        for (_, (_, tex_name)) in &material_info {
-            if let Some(tn) = tex_name {
-                if seen.insert(tn.clone()) {
-                    unique_tex.push(tn.clone());
-                }
-            }
-        }
+        // Unique texture names, sorted so images keep their order between runs
+        let unique_tex: BTreeSet<String> = material_info.values()
+            .filter_map(|(_, tex_name)| tex_name.clone())
+            .collect();
 
         if !unique_tex.is_empty() {
             let tm = textures.unwrap();
-            eprintln!("Embedding {} textures...", unique_tex.len());
+            log::info!("Embedding {} textures...", unique_tex.len());
+            progress.start(unique_tex.len() as u64, "Embedding textures");
 
             gltf_samplers.push(GltfSampler {
                 mag_filter: GLTF_NEAREST,
@@ -721,15 +750,16 @@ pub fn export_glb<P: AsRef<Path>>(
             });
 
             let mut missing_textures: Vec<String> = Vec::new();
-            for tex_name in &unique_tex {
+            for (i, tex_name) in unique_tex.iter().enumerate() {
+                progress.set_position(i as u64);
                 let png_path = tm.get_texture(tex_name);
                 if png_path.is_none() {
                     missing_textures.push(tex_name.clone());
                 }
-                let png_bytes = png_path.and_then(|p| std::fs::read(p).ok());
+                let png_bytes = png_path.and_then(|p| tm.read_texture(p));
 
                 if let Some(mut bytes) = png_bytes {
-                    if let Some(tint) = needs_tint(tex_name) {
+                    if let Some(tint) = needs_tint(tex_name, tm.biome()) {
                         if let Some(tinted) = apply_tint_in_memory(&bytes, tint) {
                             bytes = tinted;
                         }
@@ -758,31 +788,30 @@ pub fn export_glb<P: AsRef<Path>>(
                     texture_name_to_tex_idx.insert(tex_name.clone(), tex_idx);
                 }
             }
-            eprintln!("  Embedded {} textures into GLB", texture_name_to_tex_idx.len());
+            progress.finish(&format!("Embedded {} textures", texture_name_to_tex_idx.len()));
+            log::info!("  Embedded {} textures into GLB", texture_name_to_tex_idx.len());
             if !missing_textures.is_empty() {
-                eprintln!("  Warning: {} textures not found:", missing_textures.len());
+                log::warn!("{} textures not found:", missing_textures.len());
                 for name in missing_textures.iter().take(20) {
-                    eprintln!("    - {}", name);
+                    log::info!("    - {}", name);
                 }
                 if missing_textures.len() > 20 {
-                    eprintln!("    ... and {} more", missing_textures.len() - 20);
+                    log::info!("    ... and {} more", missing_textures.len() - 20);
                 }
             }
         }
     }
 
     // Phase 3: Write geometry per material, create glTF meshes
-    let pb = create_progress_bar(material_geom.len() as u64, "Building GLB");
+    progress.start(material_geom.len() as u64, "Building GLB");
 
     let mut meshes: Vec<GltfMesh> = Vec::new();
     let mut nodes: Vec<GltfNode> = Vec::new();
     let mut materials_gltf: Vec<GltfMaterial> = Vec::new();
 
-    let mut sorted_materials: Vec<_> = material_geom.into_iter().collect();
-    sorted_materials.sort_by(|a, b| a.0.cmp(&b.0));
-
-    for (i, (mat_name, geom)) in sorted_materials.into_iter().enumerate() {
-        pb.set_position(i as u64);
+    for (i, (mat_name, geom)) in material_geom.into_iter().enumerate() {
+        progress.set_position(i as u64);
+        progress::check_cancel(progress)?;
 
         if geom.positions.is_empty() { continue; }
 
@@ -937,7 +966,7 @@ pub fn export_glb<P: AsRef<Path>>(
             name: None,
         });
     }
-    pb.finish_with_message(format!("Created {} meshes", meshes.len()));
+    progress.finish(&format!("Created {} meshes", meshes.len()));
 
     // Build root glTF object
     let scene_nodes: Vec<usize> = (0..nodes.len()).collect();
@@ -977,8 +1006,9 @@ pub fn export_glb<P: AsRef<Path>>(
     let total_size = 12 + 8 + json_chunk_len + 8 + bin_chunk_len;
 
     // Write GLB file
-    eprintln!("Writing GLB file ({:.1} MB)...", total_size as f64 / 1024.0 / 1024.0);
-    let mut file = BufWriter::with_capacity(4 * 1024 * 1024, std::fs::File::create(output_path)?);
+    log::info!("Writing GLB file ({:.1} MB)...", total_size as f64 / 1024.0 / 1024.0);
+    progress.start(2, "Writing GLB");
+    let file = &mut out;
 
     // GLB header
     file.write_all(b"glTF")?;
@@ -990,6 +1020,7 @@ pub fn export_glb<P: AsRef<Path>>(
     file.write_all(&0x4E4F534Au32.to_le_bytes())?;
     file.write_all(json_bytes)?;
     for _ in 0..json_padding { file.write_all(b" ")?; }
+    progress.set_position(1);
 
     // BIN chunk
     file.write_all(&(bin_chunk_len as u32).to_le_bytes())?;
@@ -998,8 +1029,7 @@ pub fn export_glb<P: AsRef<Path>>(
     for _ in 0..bin_padding { file.write_all(&[0u8])?; }
 
     file.flush()?;
-
-    eprintln!("Exported to: {}", output_path.display());
+    progress.finish("Wrote GLB");
 
     Ok(())
 }
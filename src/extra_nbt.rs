@@ -0,0 +1,185 @@
+//! Collection of top-level NBT fields the loaders don't understand
+//!
+//! Mods stash their own compounds in schematic roots (e.g. Axiom's
+//! `axiom:editor_metadata`). They are kept verbatim in `Metadata::raw_extra` so
+//! they aren't silently lost. They're set aside while the loader parses the
+//! known fields, so the data is only read once.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::io::Read;
+use std::marker::PhantomData;
+use serde::de::{DeserializeOwned, DeserializeSeed, Deserialize, Deserializer, IntoDeserializer, MapAccess, Visitor};
+
+/// The top-level keys a format's loader reads
+pub(crate) trait KnownFields {
+    const KNOWN: &'static [&'static str];
+}
+
+/// A `T` together with the fields of its compound whose keys aren't in
+/// `T::KNOWN`, set aside while `T` is parsed so the data is only read once
+struct Collected<T> {
+    value: T,
+    unknown: HashMap<String, fastnbt::Value>,
+}
+
+impl<'de, T: KnownFields + Deserialize<'de>> Deserialize<'de> for Collected<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut unknown = HashMap::new();
+        let value = T::deserialize(Collecting::<D, T> { inner: deserializer, unknown: &mut unknown, known: PhantomData })?;
+        Ok(Collected { value, unknown })
+    }
+}
+
+/// Deserializer handing `K`'s fields of a compound on and keeping the rest
+struct Collecting<'u, D, K> {
+    inner: D,
+    unknown: &'u mut HashMap<String, fastnbt::Value>,
+    known: PhantomData<K>,
+}
+
+impl<'de, D: Deserializer<'de>, K: KnownFields> Deserializer<'de> for Collecting<'_, D, K> {
+    type Error = D::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, D::Error> {
+        self.inner.deserialize_any(CollectingVisitor::<V, K> { inner: visitor, unknown: self.unknown, known: PhantomData })
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, D::Error> {
+        self.inner.deserialize_map(CollectingVisitor::<V, K> { inner: visitor, unknown: self.unknown, known: PhantomData })
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, D::Error> {
+        let visitor = CollectingVisitor::<V, K> { inner: visitor, unknown: self.unknown, known: PhantomData };
+        self.inner.deserialize_struct(name, fields, visitor)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.inner.is_human_readable()
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct enum identifier ignored_any
+    }
+}
+
+struct CollectingVisitor<'u, V, K> {
+    inner: V,
+    unknown: &'u mut HashMap<String, fastnbt::Value>,
+    known: PhantomData<K>,
+}
+
+impl<'de, V: Visitor<'de>, K: KnownFields> Visitor<'de> for CollectingVisitor<'_, V, K> {
+    type Value = V::Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.inner.expecting(f)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+        self.inner.visit_map(CollectingMap::<A, K> { inner: map, unknown: self.unknown, known: PhantomData })
+    }
+}
+
+struct CollectingMap<'u, A, K> {
+    inner: A,
+    unknown: &'u mut HashMap<String, fastnbt::Value>,
+    known: PhantomData<K>,
+}
+
+impl<'de, A: MapAccess<'de>, K: KnownFields> MapAccess<'de> for CollectingMap<'_, A, K> {
+    type Error = A::Error;
+
+    fn next_key_seed<S: DeserializeSeed<'de>>(&mut self, seed: S) -> Result<Option<S::Value>, A::Error> {
+        while let Some(key) = self.inner.next_key::<String>()? {
+            if K::KNOWN.contains(&key.as_str()) {
+                return seed.deserialize(IntoDeserializer::<A::Error>::into_deserializer(key)).map(Some);
+            }
+            let value = self.inner.next_value::<fastnbt::Value>()?;
+            self.unknown.insert(key, value);
+        }
+        Ok(None)
+    }
+
+    fn next_value_seed<S: DeserializeSeed<'de>>(&mut self, seed: S) -> Result<S::Value, A::Error> {
+        self.inner.next_value_seed(seed)
+    }
+}
+
+/// Sponge v3 root: the schematic compound sits under "Schematic"
+#[derive(serde::Deserialize)]
+#[serde(bound = "T: KnownFields + Deserialize<'de>")]
+struct Wrapped<T> {
+    #[serde(rename = "Schematic")]
+    schematic: Collected<T>,
+}
+
+fn unknown_compound(unknown: HashMap<String, fastnbt::Value>) -> Option<fastnbt::Value> {
+    (!unknown.is_empty()).then_some(fastnbt::Value::Compound(unknown))
+}
+
+/// Parse decompressed NBT as `T`, along with its unknown top-level fields as a
+/// compound (None if there are none)
+pub(crate) fn parse<T: KnownFields + DeserializeOwned>(nbt: impl Read) -> Option<(T, Option<fastnbt::Value>)> {
+    let Collected { value, unknown } = fastnbt::from_reader(nbt).ok()?;
+    Some((value, unknown_compound(unknown)))
+}
+
+/// Like [`parse`], for the compound nested under a Sponge v3 "Schematic" root key
+pub(crate) fn parse_wrapped<T: KnownFields + DeserializeOwned>(nbt: impl Read) -> Option<(T, Option<fastnbt::Value>)> {
+    let Wrapped { schematic: Collected { value, unknown } } = fastnbt::from_reader(nbt).ok()?;
+    Some((value, unknown_compound(unknown)))
+}
+
+/// NBT tag type name of a value, e.g. "Compound"
+pub(crate) fn type_name(value: &fastnbt::Value) -> &'static str {
+    match value {
+        fastnbt::Value::Byte(_) => "Byte",
+        fastnbt::Value::Short(_) => "Short",
+        fastnbt::Value::Int(_) => "Int",
+        fastnbt::Value::Long(_) => "Long",
+        fastnbt::Value::Float(_) => "Float",
+        fastnbt::Value::Double(_) => "Double",
+        fastnbt::Value::String(_) => "String",
+        fastnbt::Value::ByteArray(_) => "ByteArray",
+        fastnbt::Value::IntArray(_) => "IntArray",
+        fastnbt::Value::LongArray(_) => "LongArray",
+        fastnbt::Value::List(_) => "List",
+        fastnbt::Value::Compound(_) => "Compound",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Schem;
+
+    #[test]
+    fn test_unknown_fields_set_aside_while_parsing() {
+        let json = r#"{"Version": 2, "Width": 3, "axiom:editor_metadata": {"tool": "brush"}, "Height": 1}"#;
+        let Collected { value, unknown }: Collected<Schem> = serde_json::from_str(json).unwrap();
+
+        // Known fields on either side of the unknown one still reach the parser
+        assert_eq!((value.version, value.width, value.height), (2, Some(3), Some(1)));
+        let keys: Vec<_> = unknown.keys().collect();
+        assert_eq!(keys, ["axiom:editor_metadata"]);
+        assert_eq!(type_name(&unknown["axiom:editor_metadata"]), "Compound");
+
+        let metadata = crate::Metadata {
+            raw_extra: unknown_compound(unknown),
+            ..Default::default()
+        };
+        assert_eq!(metadata.raw_extra_fields(), [("axiom:editor_metadata", "Compound")]);
+
+        let wrapped = format!(r#"{{"Schematic": {}}}"#, json);
+        let Wrapped { schematic }: Wrapped<Schem> = serde_json::from_str(&wrapped).unwrap();
+        assert_eq!((schematic.value.width, schematic.unknown.len()), (Some(3), 1));
+    }
+}
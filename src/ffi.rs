@@ -0,0 +1,204 @@
+//! C ABI for the loader and materials calculator (enabled by the `ffi` feature)
+//!
+//! The header lives in `include/schem_tool.h`; regenerate it after changing
+//! this module with `cbindgen --config cbindgen.toml --output include/schem_tool.h`.
+//!
+//! Conventions:
+//! - Handles come from [`schem_load`] and are released with [`schem_free`].
+//! - Complex data is returned as UTF-8 JSON strings owned by the caller, which
+//!   must release them with [`schem_string_free`].
+//! - Functions returning a pointer return NULL on failure; the reason is available
+//!   from [`schem_last_error_code`] and [`schem_last_error_message`] on the same thread.
+//! - Panics never cross the boundary; they are reported as [`SchemStatus::Panic`].
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::ffi::{c_char, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+use crate::{recipes, UnifiedSchematic};
+
+/// Result codes returned by the FFI functions
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemStatus {
+    Ok = 0,
+    /// A required pointer argument was NULL
+    NullPointer = 1,
+    /// The bytes are not a supported schematic
+    ParseError = 2,
+    /// JSON encoding of the result failed
+    EncodeError = 3,
+    /// Rust code panicked; the message is in `schem_last_error_message`
+    Panic = 4,
+}
+
+/// Opaque handle to a loaded schematic
+pub struct SchemHandle {
+    schem: UnifiedSchematic,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<(SchemStatus, String)> = const { RefCell::new((SchemStatus::Ok, String::new())) };
+}
+
+fn set_error(status: SchemStatus, message: impl Into<String>) {
+    LAST_ERROR.with(|e| *e.borrow_mut() = (status, message.into()));
+}
+
+/// Run `f` with panics converted into [`SchemStatus::Panic`]
+fn guard<T>(f: impl FnOnce() -> Result<T, (SchemStatus, String)>) -> Option<T> {
+    set_error(SchemStatus::Ok, "");
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(value)) => Some(value),
+        Ok(Err((status, message))) => {
+            set_error(status, message);
+            None
+        }
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".to_string());
+            set_error(SchemStatus::Panic, message);
+            None
+        }
+    }
+}
+
+unsafe fn handle_ref<'a>(handle: *const SchemHandle) -> Result<&'a SchemHandle, (SchemStatus, String)> {
+    handle
+        .as_ref()
+        .ok_or_else(|| (SchemStatus::NullPointer, "handle is NULL".to_string()))
+}
+
+fn json_string<T: serde::Serialize>(value: &T) -> Result<*mut c_char, (SchemStatus, String)> {
+    let json = serde_json::to_string(value).map_err(|e| (SchemStatus::EncodeError, e.to_string()))?;
+    // serde_json escapes control characters, so the output never contains NUL
+    let c = CString::new(json).map_err(|e| (SchemStatus::EncodeError, e.to_string()))?;
+    Ok(c.into_raw())
+}
+
+/// Parse schematic file contents (gzipped or raw NBT)
+///
+/// Returns NULL on failure.
+///
+/// # Safety
+/// `bytes` must point to `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn schem_load(bytes: *const u8, len: usize) -> *mut SchemHandle {
+    guard(|| {
+        if bytes.is_null() {
+            return Err((SchemStatus::NullPointer, "bytes is NULL".to_string()));
+        }
+        let data = std::slice::from_raw_parts(bytes, len);
+        let schem = UnifiedSchematic::from_bytes(data).map_err(|e| (SchemStatus::ParseError, e.to_string()))?;
+        Ok(Box::into_raw(Box::new(SchemHandle { schem })))
+    })
+    .unwrap_or(ptr::null_mut())
+}
+
+/// Write the schematic's width, height and length
+///
+/// # Safety
+/// `handle` must come from [`schem_load`]; the out pointers must be writable.
+#[no_mangle]
+pub unsafe extern "C" fn schem_dimensions(
+    handle: *const SchemHandle,
+    width: *mut u32,
+    height: *mut u32,
+    length: *mut u32,
+) -> SchemStatus {
+    guard(|| {
+        let handle = handle_ref(handle)?;
+        if width.is_null() || height.is_null() || length.is_null() {
+            return Err((SchemStatus::NullPointer, "output pointer is NULL".to_string()));
+        }
+        *width = handle.schem.width;
+        *height = handle.schem.height;
+        *length = handle.schem.length;
+        Ok(())
+    })
+    .map(|_| SchemStatus::Ok)
+    .unwrap_or_else(|| schem_last_error_code())
+}
+
+/// Block counts as a JSON object `{ "minecraft:stone": 42, ... }`
+///
+/// Returns NULL on failure. Free the result with [`schem_string_free`].
+///
+/// # Safety
+/// `handle` must come from [`schem_load`].
+#[no_mangle]
+pub unsafe extern "C" fn schem_block_counts_json(handle: *const SchemHandle) -> *mut c_char {
+    guard(|| {
+        let handle = handle_ref(handle)?;
+        let counts: BTreeMap<_, _> = handle.schem.block_counts().into_iter().collect();
+        json_string(&counts)
+    })
+    .unwrap_or(ptr::null_mut())
+}
+
+/// Raw materials as a JSON object `{ "cobblestone": 12.0, ... }`
+///
+/// Returns NULL on failure. Free the result with [`schem_string_free`].
+///
+/// # Safety
+/// `handle` must come from [`schem_load`].
+#[no_mangle]
+pub unsafe extern "C" fn schem_materials_json(handle: *const SchemHandle, stonecutter: bool) -> *mut c_char {
+    guard(|| {
+        let handle = handle_ref(handle)?;
+        let materials: BTreeMap<_, _> =
+            recipes::calculate_materials_with_options(&handle.schem.block_counts(), stonecutter)
+                .into_iter()
+                .collect();
+        json_string(&materials)
+    })
+    .unwrap_or(ptr::null_mut())
+}
+
+/// Status of the last failed call on this thread ([`SchemStatus::Ok`] after a success)
+#[no_mangle]
+pub extern "C" fn schem_last_error_code() -> SchemStatus {
+    LAST_ERROR.with(|e| e.borrow().0)
+}
+
+/// Message of the last failed call on this thread, or NULL if it succeeded
+///
+/// Free the result with [`schem_string_free`].
+#[no_mangle]
+pub extern "C" fn schem_last_error_message() -> *mut c_char {
+    LAST_ERROR.with(|e| {
+        let e = e.borrow();
+        if e.0 == SchemStatus::Ok {
+            return ptr::null_mut();
+        }
+        CString::new(e.1.replace('\0', " "))
+            .map(CString::into_raw)
+            .unwrap_or(ptr::null_mut())
+    })
+}
+
+/// Release a string returned by this library; NULL is ignored
+///
+/// # Safety
+/// `s` must come from this library and not have been freed already.
+#[no_mangle]
+pub unsafe extern "C" fn schem_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+/// Release a handle returned by [`schem_load`]; NULL is ignored
+///
+/// # Safety
+/// `handle` must come from [`schem_load`] and not have been freed already.
+#[no_mangle]
+pub unsafe extern "C" fn schem_free(handle: *mut SchemHandle) {
+    if !handle.is_null() {
+        drop(Box::from_raw(handle));
+    }
+}
@@ -0,0 +1,243 @@
+//! Content fingerprints, for finding duplicate schematics in a library
+//!
+//! Two hashes, both 64-bit FNV-1a printed as 16 hex digits:
+//!
+//! - The **content hash** covers, in this order: the width, height and length
+//!   (little-endian `u32`s); the palette, meaning every distinct block state as
+//!   `name[key=value,...]` with its properties sorted, listed in sorted order
+//!   and preceded by their count; the palette index of every block, as a
+//!   little-endian `u32` each, walking Y, then Z, then X; and the block
+//!   entities sorted by position, each as its position (little-endian `i32`s),
+//!   its id and its data as sorted-key SNBT (see [`crate::snbt::to_string`]).
+//!   Strings are hashed as a little-endian `u32` byte length and their UTF-8
+//!   bytes.
+//! - The **shape hash** covers only which blocks are air: the dimensions and
+//!   one bit per position (1 for anything but air, bytes filled from the low
+//!   bit, same walk order). It's taken for each of the four rotations around
+//!   the Y axis and the smallest is kept, so a rotated copy has the same
+//!   shape hash as the original.
+//!
+//! Nothing else goes in: not the file format or its compression, the name,
+//! author, description or dates, the data version, the offset or origin, the
+//! Litematica region layout, or entities. A schematic saved as `.schem` and
+//! as `.litematic` has the same hashes, and these definitions won't change
+//! without a note in the changelog.
+//!
+//! [`canonical_content`] returns the exact bytes the content hash is taken
+//! over, for anything that needs more than 64 bits, like signatures.
+
+use crate::UnifiedSchematic;
+
+/// `Metadata::extra` key holding the signature over [`canonical_content`]
+/// (written by `schem-tool seal`). Being metadata, it's not part of the content.
+pub const SIGNATURE_KEY: &str = "schem-tool:signature";
+
+/// Content and shape hashes of one schematic
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Fingerprint {
+    /// Blocks, their states and block entity data
+    pub content: u64,
+    /// Air mask only, the same for all four rotations around Y
+    pub shape: u64,
+}
+
+impl Fingerprint {
+    pub fn of(schematic: &UnifiedSchematic) -> Self {
+        Self { content: content_hash(schematic), shape: shape_hash(schematic) }
+    }
+
+    /// Whether `other` may be this schematic turned around: same shape,
+    /// different content
+    pub fn possible_rotation_of(&self, other: &Fingerprint) -> bool {
+        self.shape == other.shape && self.content != other.content
+    }
+}
+
+/// Where the content encoding goes: a hash, or a buffer
+trait Sink {
+    fn write(&mut self, bytes: &[u8]);
+
+    fn write_u32(&mut self, value: u32) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_str(&mut self, text: &str) {
+        self.write_u32(text.len() as u32);
+        self.write(text.as_bytes());
+    }
+}
+
+/// 64-bit FNV-1a
+struct Fnv64(u64);
+
+impl Fnv64 {
+    fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Sink for Fnv64 {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+}
+
+impl Sink for Vec<u8> {
+    fn write(&mut self, bytes: &[u8]) {
+        self.extend_from_slice(bytes);
+    }
+}
+
+/// Hash of the blocks, their states and block entities (see the module docs)
+pub fn content_hash(schematic: &UnifiedSchematic) -> u64 {
+    let mut hash = Fnv64::new();
+    write_content(schematic, &mut hash);
+    hash.0
+}
+
+/// The bytes [`content_hash`] is taken over
+pub fn canonical_content(schematic: &UnifiedSchematic) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    write_content(schematic, &mut bytes);
+    bytes
+}
+
+fn write_content(schematic: &UnifiedSchematic, out: &mut impl Sink) {
+    for extent in [schematic.width, schematic.height, schematic.length] {
+        out.write_u32(extent);
+    }
+
+    let states: Vec<String> = schematic.blocks.iter().map(|block| block.full_name()).collect();
+    let mut palette: Vec<&str> = states.iter().map(String::as_str).collect();
+    palette.sort_unstable();
+    palette.dedup();
+    out.write_u32(palette.len() as u32);
+    for state in &palette {
+        out.write_str(state);
+    }
+
+    // Blocks are stored Y, then Z, then X
+    for state in &states {
+        let index = palette.binary_search(&state.as_str()).expect("state is in the palette");
+        out.write_u32(index as u32);
+    }
+
+    let mut block_entities: Vec<_> = schematic.block_entities.iter().collect();
+    block_entities.sort_by_key(|entity| entity.pos);
+    for entity in block_entities {
+        for coord in [entity.pos.0, entity.pos.1, entity.pos.2] {
+            out.write(&coord.to_le_bytes());
+        }
+        out.write_str(&entity.id);
+        out.write_str(&crate::snbt::to_string(&fastnbt::Value::Compound(entity.nbt.clone())));
+    }
+}
+
+/// Hash of the air mask, the smallest over the four Y rotations (see the module docs)
+pub fn shape_hash(schematic: &UnifiedSchematic) -> u64 {
+    let (width, height, length) = (schematic.width, schematic.height, schematic.length);
+    (0..4)
+        .map(|turns| {
+            // Dimensions of the turned schematic, and where each of its columns comes from
+            let (turned_width, turned_length) = if turns % 2 == 0 { (width, length) } else { (length, width) };
+            let source = |x: u32, z: u32| match turns {
+                0 => (x, z),
+                1 => (z, length - 1 - x),
+                2 => (width - 1 - x, length - 1 - z),
+                _ => (width - 1 - z, x),
+            };
+
+            let mut hash = Fnv64::new();
+            for extent in [turned_width, height, turned_length] {
+                hash.write_u32(extent);
+            }
+            let (mut byte, mut bits) = (0u8, 0);
+            for y in 0..height {
+                for z in 0..turned_length {
+                    for x in 0..turned_width {
+                        let (sx, sz) = source(x, z);
+                        if schematic.get_block(sx, y, sz).is_some_and(|block| !block.is_air()) {
+                            byte |= 1 << bits;
+                        }
+                        bits += 1;
+                        if bits == 8 {
+                            hash.write(&[byte]);
+                            (byte, bits) = (0, 0);
+                        }
+                    }
+                }
+            }
+            if bits > 0 {
+                hash.write(&[byte]);
+            }
+            hash.0
+        })
+        .min()
+        .unwrap_or(Fnv64::new().0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::SchematicBuilder;
+    use crate::Block;
+
+    fn stairs(facing: &str) -> Block {
+        let mut block = Block::new("minecraft:oak_stairs");
+        block.state.properties.insert("facing".to_string(), facing.to_string());
+        block.state.properties.insert("half".to_string(), "bottom".to_string());
+        block
+    }
+
+    #[test]
+    fn test_metadata_is_left_out() {
+        let mut builder = SchematicBuilder::new(3, 2, 2);
+        builder.set_block(0, 0, 0, Block::new("minecraft:stone")).unwrap();
+        builder.set_block(2, 1, 1, stairs("north")).unwrap();
+        let schem = builder.build();
+
+        let mut renamed = schem.clone();
+        renamed.metadata.name = Some("Copy of house".to_string());
+        renamed.metadata.author = Some("someone else".to_string());
+        renamed.offset = (100, 64, -3);
+        assert_eq!(Fingerprint::of(&renamed), Fingerprint::of(&schem));
+
+        let mut restated = schem.clone();
+        restated.blocks.iter_mut().for_each(|block| *block = match block.name.as_str() {
+            "minecraft:oak_stairs" => stairs("south"),
+            _ => block.clone(),
+        });
+        let (before, after) = (Fingerprint::of(&schem), Fingerprint::of(&restated));
+        assert_ne!(before.content, after.content);
+        assert_eq!(before.shape, after.shape);
+    }
+
+    #[test]
+    fn test_rotation_keeps_shape() {
+        // An L of four blocks, and the same L turned a quarter
+        let mut builder = SchematicBuilder::new(2, 1, 3);
+        for (x, z) in [(0, 0), (0, 1), (0, 2), (1, 2)] {
+            builder.set_block(x, 0, z, Block::new("minecraft:stone")).unwrap();
+        }
+        let original = Fingerprint::of(&builder.build());
+
+        let mut builder = SchematicBuilder::new(3, 1, 2);
+        for (x, z) in [(0, 0), (1, 0), (2, 0), (0, 1)] {
+            builder.set_block(x, 0, z, Block::new("minecraft:stone")).unwrap();
+        }
+        let turned = Fingerprint::of(&builder.build());
+        assert_eq!(turned.shape, original.shape);
+        assert!(turned.possible_rotation_of(&original));
+        assert!(!original.possible_rotation_of(&original));
+
+        // Mirrored isn't a rotation
+        let mut builder = SchematicBuilder::new(2, 1, 3);
+        for (x, z) in [(1, 0), (1, 1), (1, 2), (0, 2)] {
+            builder.set_block(x, 0, z, Block::new("minecraft:stone")).unwrap();
+        }
+        assert_ne!(Fingerprint::of(&builder.build()).shape, original.shape);
+    }
+}
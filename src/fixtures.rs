@@ -0,0 +1,108 @@
+//! Deterministic generated schematics for tests and benchmarks
+//!
+//! Shapes are built from a handful of block types so results are easy to
+//! predict, and the noise fill uses a fixed-seed generator, so the same call
+//! always gives the same schematic without keeping binary fixtures around.
+
+use crate::block::BlockState;
+use crate::{Block, SchematicBuilder, UnifiedSchematic};
+
+/// Blocks alternating in all three axes, `a` at the origin
+pub fn checkerboard(size: u32, a: &Block, b: &Block) -> UnifiedSchematic {
+    let mut builder = SchematicBuilder::new(size, size, size);
+    for y in 0..size {
+        for z in 0..size {
+            for x in 0..size {
+                let block = if (x + y + z) % 2 == 0 { a } else { b };
+                builder.set_block(x, y, z, block.clone()).expect("inside the volume");
+            }
+        }
+    }
+    builder.build()
+}
+
+/// A solid ball of `block` filling a cube of side `2 * radius`, air around it
+pub fn sphere(radius: u32, block: &Block) -> UnifiedSchematic {
+    let size = radius * 2;
+    let mut builder = SchematicBuilder::new(size, size, size);
+    // Distances from the block centers, so the ball is symmetric
+    let r = radius as f64;
+    let limit = r * r;
+    for y in 0..size {
+        for z in 0..size {
+            for x in 0..size {
+                let d = |v: u32| v as f64 + 0.5 - r;
+                if d(x) * d(x) + d(y) * d(y) + d(z) * d(z) <= limit {
+                    builder.set_block(x, y, z, block.clone()).expect("inside the volume");
+                }
+            }
+        }
+    }
+    builder.build()
+}
+
+/// Each position picks air or one of `blocks` uniformly, from `seed`
+pub fn noise(size: (u32, u32, u32), blocks: &[Block], seed: u64) -> UnifiedSchematic {
+    let (width, height, length) = size;
+    let mut builder = SchematicBuilder::new(width, height, length);
+    // xorshift64*; a zero state would only ever produce zeros
+    let mut state = seed.max(1);
+    let mut next = move || {
+        state ^= state >> 12;
+        state ^= state << 25;
+        state ^= state >> 27;
+        state.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    };
+    for y in 0..height {
+        for z in 0..length {
+            for x in 0..width {
+                let pick = (next() >> 32) as usize % (blocks.len() + 1);
+                if let Some(block) = blocks.get(pick) {
+                    builder.set_block(x, y, z, block.clone()).expect("inside the volume");
+                }
+            }
+        }
+    }
+    builder.build()
+}
+
+/// A few common block types with and without properties, for `noise`
+pub fn sample_blocks() -> Vec<Block> {
+    let with_props = |name: &str, props: &[(&str, &str)]| {
+        let properties = props.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        Block::with_state(name, BlockState { properties })
+    };
+    vec![
+        Block::new("minecraft:stone"),
+        Block::new("minecraft:oak_planks"),
+        Block::new("minecraft:glass"),
+        with_props("minecraft:oak_leaves", &[("distance", "7"), ("persistent", "false")]),
+        with_props("minecraft:oak_stairs", &[("facing", "east"), ("half", "bottom"), ("shape", "straight")]),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generated_shapes() {
+        let (stone, glass) = (Block::new("minecraft:stone"), Block::new("minecraft:glass"));
+        let board = checkerboard(4, &stone, &glass);
+        assert_eq!(board.block_counts()["minecraft:stone"], 32);
+        assert_eq!(board.get_block(1, 0, 0), Some(&glass));
+
+        let ball = sphere(4, &stone);
+        assert_eq!(ball.dimensions_str(), "8x8x8");
+        assert!(ball.get_block(0, 0, 0).unwrap().is_air());
+        assert_eq!(ball.get_block(4, 4, 4), Some(&stone));
+        // Symmetric about the center
+        assert_eq!(ball.get_block(0, 4, 4), ball.get_block(7, 3, 3));
+
+        let blocks = sample_blocks();
+        let a = noise((8, 8, 8), &blocks, 42);
+        assert_eq!(a.blocks, noise((8, 8, 8), &blocks, 42).blocks);
+        assert_ne!(a.blocks, noise((8, 8, 8), &blocks, 43).blocks);
+        assert_eq!(a.block_counts().len(), blocks.len() + 1);
+    }
+}
@@ -0,0 +1,214 @@
+//! Finding the build in a schematic that also captured the terrain around it
+//!
+//! Blocks are split into terrain and constructed blocks by name, with two
+//! refinements: leaves count as terrain unless they're `persistent` (the game
+//! only sets that on leaves a player placed), and logs count as terrain when
+//! they're part of a tree, a cluster of logs and natural leaves standing on
+//! terrain or the bottom of the schematic. The build is then the bounding box
+//! of the constructed blocks, leaving out clusters too small to be part of it
+//! (a stray torch, a lost rail).
+
+use crate::{Block, BlockClass, BlockSelector, UnifiedSchematic};
+
+/// Blocks found in generated terrain, without the `minecraft:` prefix
+const NATURAL_BLOCKS: &[&str] = &[
+    "stone", "granite", "diorite", "andesite", "deepslate", "tuff", "calcite", "bedrock",
+    "dirt", "coarse_dirt", "rooted_dirt", "grass_block", "podzol", "mycelium", "mud", "clay",
+    "gravel", "sand", "red_sand", "sandstone", "red_sandstone", "terracotta",
+    "snow", "snow_block", "powder_snow", "ice", "packed_ice", "blue_ice",
+    "dripstone_block", "pointed_dripstone", "moss_block", "moss_carpet", "glow_lichen",
+    "netherrack", "soul_sand", "soul_soil", "basalt", "blackstone", "magma_block", "end_stone",
+    "water", "lava", "bubble_column", "seagrass", "tall_seagrass", "kelp", "kelp_plant",
+    "short_grass", "grass", "tall_grass", "fern", "large_fern", "dead_bush", "vine",
+    "sugar_cane", "cactus", "lily_pad", "sweet_berry_bush", "brown_mushroom", "red_mushroom",
+    "dandelion", "poppy", "blue_orchid", "allium", "azure_bluet", "red_tulip", "orange_tulip",
+    "white_tulip", "pink_tulip", "oxeye_daisy", "cornflower", "lily_of_the_valley",
+    "sunflower", "lilac", "rose_bush", "peony",
+];
+
+/// Minimum and maximum corner of a box of blocks, both inclusive
+pub type BlockBox = ((u32, u32, u32), (u32, u32, u32));
+
+/// Tuning for [`find_focus`]
+#[derive(Debug, Clone)]
+pub struct FocusOptions {
+    /// More blocks to count as terrain
+    pub natural_extra: BlockSelector,
+    /// Blocks to count as constructed even if they'd be terrain
+    pub constructed_extra: BlockSelector,
+    /// Clusters of fewer constructed blocks than this are left out of the build
+    pub min_cluster: usize,
+    /// Blocks of terrain to keep around the build on every side
+    pub margin: u32,
+}
+
+impl Default for FocusOptions {
+    fn default() -> Self {
+        Self {
+            natural_extra: BlockSelector::default(),
+            constructed_extra: BlockSelector::default(),
+            min_cluster: 8,
+            margin: 0,
+        }
+    }
+}
+
+/// What [`find_focus`] found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FocusReport {
+    /// The build with its margin, or None if nothing in the schematic looks built
+    pub bounds: Option<BlockBox>,
+    /// Blocks counted as terrain
+    pub terrain_blocks: usize,
+    /// Constructed blocks in the build
+    pub constructed_blocks: usize,
+    /// Constructed blocks left out for being in clusters below `min_cluster`
+    pub stray_blocks: usize,
+    /// Volume of the whole schematic
+    pub volume: usize,
+}
+
+impl FocusReport {
+    /// Share of the schematic's volume that is terrain, in percent
+    pub fn terrain_percent(&self) -> f64 {
+        if self.volume == 0 {
+            return 0.0;
+        }
+        self.terrain_blocks as f64 * 100.0 / self.volume as f64
+    }
+
+    /// Size of the build with its margin
+    pub fn size(&self) -> Option<(u32, u32, u32)> {
+        self.bounds.map(|(min, max)| (max.0 - min.0 + 1, max.1 - min.1 + 1, max.2 - min.2 + 1))
+    }
+}
+
+/// Whether a block is terrain by name alone (logs and leaves need their
+/// surroundings, see [`find_focus`])
+pub fn is_natural(block: &Block, options: &FocusOptions) -> bool {
+    if options.constructed_extra.matches_name(&block.name) {
+        return false;
+    }
+    let name = block.display_name();
+    NATURAL_BLOCKS.contains(&name)
+        || name.ends_with("_ore")
+        || is_natural_leaves(block)
+        || options.natural_extra.matches_name(&block.name)
+}
+
+fn is_log(block: &Block) -> bool {
+    let name = block.display_name();
+    !name.starts_with("stripped_") && (name.ends_with("_log") || name.ends_with("_stem"))
+}
+
+fn is_leaves(block: &Block) -> bool {
+    block.display_name().ends_with("leaves")
+}
+
+/// Leaves the game grew rather than a player placed
+fn is_natural_leaves(block: &Block) -> bool {
+    is_leaves(block) && block.state.get_bool("persistent") != Some(true)
+}
+
+/// Split `schem` into terrain and constructed blocks and find the build's bounding box
+pub fn find_focus(schem: &UnifiedSchematic, options: &FocusOptions) -> FocusReport {
+    let (w, l) = (schem.width as usize, schem.length as usize);
+    let index = |(x, y, z): (u32, u32, u32)| (y as usize * l + z as usize) * w + x as usize;
+    let mut natural: Vec<bool> = schem.blocks.iter().map(|block| is_natural(block, options)).collect();
+
+    // Trees: logs joined to natural leaves, standing on terrain
+    let trees = schem.connected_components(|_, block| {
+        !options.constructed_extra.matches_name(&block.name) && (is_log(block) || is_natural_leaves(block))
+    });
+    for tree in trees {
+        let grounded = tree.iter().any(|&(x, y, z)| {
+            y == 0 || schem.get_block(x, y - 1, z).is_some_and(|below| !is_leaves(below) && natural[index((x, y - 1, z))])
+        });
+        let has_leaves = tree.iter().any(|&(x, y, z)| schem.get_block(x, y, z).is_some_and(is_natural_leaves));
+        if grounded && has_leaves {
+            for pos in tree {
+                natural[index(pos)] = true;
+            }
+        }
+    }
+
+    let counts = |block: &Block| !matches!(block.class(), BlockClass::Air | BlockClass::Technical);
+    let terrain_blocks = schem.blocks.iter().zip(&natural).filter(|(block, natural)| **natural && counts(block)).count();
+    let clusters = schem.connected_components(|pos, block| counts(block) && !natural[index(pos)]);
+
+    let (mut constructed_blocks, mut stray_blocks) = (0, 0);
+    let mut bounds: Option<BlockBox> = None;
+    for cluster in clusters {
+        if cluster.len() < options.min_cluster {
+            stray_blocks += cluster.len();
+            continue;
+        }
+        constructed_blocks += cluster.len();
+        for (x, y, z) in cluster {
+            bounds = Some(match bounds {
+                None => ((x, y, z), (x, y, z)),
+                Some((lo, hi)) => ((lo.0.min(x), lo.1.min(y), lo.2.min(z)), (hi.0.max(x), hi.1.max(y), hi.2.max(z))),
+            });
+        }
+    }
+
+    let margin = options.margin;
+    let bounds = bounds.map(|(lo, hi)| {
+        let grow = |v: u32, extent: u32| v.saturating_add(margin).min(extent - 1);
+        (
+            (lo.0.saturating_sub(margin), lo.1.saturating_sub(margin), lo.2.saturating_sub(margin)),
+            (grow(hi.0, schem.width), grow(hi.1, schem.height), grow(hi.2, schem.length)),
+        )
+    });
+
+    FocusReport { bounds, terrain_blocks, constructed_blocks, stray_blocks, volume: schem.blocks.len() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::BlockState;
+    use crate::SchematicBuilder;
+
+    #[test]
+    fn test_find_focus() {
+        let leaves = |persistent: &str| {
+            let properties = [("persistent".to_string(), persistent.to_string())].into_iter().collect();
+            Block::with_state("minecraft:oak_leaves", BlockState { properties })
+        };
+        let mut builder = SchematicBuilder::new(16, 8, 16);
+        builder.fill((0, 0, 0), (15, 1, 15), Block::new("minecraft:dirt")).unwrap();
+        builder.fill((0, 2, 0), (15, 2, 15), Block::new("minecraft:grass_block")).unwrap();
+        // A tree on the grass
+        builder.fill((1, 3, 1), (1, 5, 1), Block::new("minecraft:oak_log")).unwrap();
+        builder.fill((0, 6, 0), (2, 6, 2), leaves("false")).unwrap();
+        // A 3x3x3 cobblestone hut with an oak log pillar and placed leaves on top
+        builder.fill((8, 3, 8), (10, 5, 10), Block::new("minecraft:cobblestone")).unwrap();
+        builder.fill((11, 3, 8), (11, 5, 8), Block::new("minecraft:oak_log")).unwrap();
+        builder.set_block(11, 6, 8, leaves("true")).unwrap();
+        // A torch on its own
+        builder.set_block(14, 3, 14, Block::new("minecraft:torch")).unwrap();
+        let schem = builder.build();
+
+        let report = find_focus(&schem, &FocusOptions::default());
+        assert_eq!(report.bounds, Some(((8, 3, 8), (11, 6, 10))));
+        assert_eq!((report.constructed_blocks, report.stray_blocks), (27 + 3 + 1, 1));
+        assert_eq!(report.terrain_blocks, 16 * 16 * 3 + 3 + 9);
+        assert!((report.terrain_percent() - 780.0 * 100.0 / 2048.0).abs() < 1e-9);
+
+        // With cobblestone counted as terrain only the pillar is left, with a margin
+        let options = FocusOptions {
+            natural_extra: BlockSelector::new(["cobblestone"]),
+            min_cluster: 2,
+            margin: 1,
+            ..Default::default()
+        };
+        let report = find_focus(&schem, &options);
+        assert_eq!(report.bounds, Some(((10, 2, 7), (12, 7, 9))));
+        assert_eq!(report.size(), Some((3, 6, 3)));
+
+        // Forcing dirt to count as built makes the whole ground the build
+        let options = FocusOptions { constructed_extra: BlockSelector::new(["dirt"]), ..Default::default() };
+        assert_eq!(find_focus(&schem, &options).bounds.map(|(min, _)| min), Some((0, 0, 0)));
+    }
+}
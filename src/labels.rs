@@ -0,0 +1,192 @@
+//! Small schematics for labels: a single sign with text, and text spelled out
+//! in blocks with a 5-pixel-high font
+
+use crate::block::WOODS;
+use crate::schem::Schem;
+use crate::{BlockEntity, Metadata, SchemError, SchematicFormat, SchematicBuilder, SignText, UnifiedSchematic};
+
+/// Data version written with legacy (`Text1`-`Text4`) signs: 1.19.4, the last
+/// before the 1.20 sign format, so the game upgrades them when pasting
+pub const LEGACY_SIGN_DATA_VERSION: i32 = 3337;
+
+/// Lines a sign side holds
+pub const SIGN_LINES: usize = 4;
+
+/// Letters that fit on a sign line in the default font, roughly
+const SIGN_LINE_CHARS: usize = 15;
+
+/// Settings for [`sign_schematic`]
+#[derive(Debug, Clone)]
+pub struct SignOptions {
+    /// Wood type, e.g. "oak", "crimson" or "bamboo"
+    pub wood: String,
+    /// A wall sign rather than a standing one
+    pub wall: bool,
+    /// `Text1`-`Text4` for versions before 1.20, instead of `front_text`
+    pub legacy: bool,
+}
+
+impl Default for SignOptions {
+    fn default() -> Self {
+        Self { wood: "oak".to_string(), wall: false, legacy: false }
+    }
+}
+
+/// A 1x1x1 schematic holding a sign facing south with `lines` on its front
+pub fn sign_schematic(lines: &[&str], options: &SignOptions) -> Result<UnifiedSchematic, SchemError> {
+    let wood = options.wood.strip_prefix("minecraft:").unwrap_or(&options.wood);
+    if !WOODS.iter().chain(&["bamboo", "crimson", "warped"]).any(|w| *w == wood) {
+        return Err(SchemError::Invalid(format!("unknown sign wood '{}'", options.wood)));
+    }
+    if lines.len() > SIGN_LINES {
+        return Err(SchemError::Invalid(format!("a sign holds {} lines, got {}", SIGN_LINES, lines.len())));
+    }
+    for line in lines.iter().filter(|line| line.chars().count() > SIGN_LINE_CHARS) {
+        log::warn!("'{}' is longer than about {} characters and may not fit on the sign", line, SIGN_LINE_CHARS);
+    }
+
+    let block = Schem::parse_block_state(&if options.wall {
+        format!("minecraft:{}_wall_sign[facing=south]", wood)
+    } else {
+        format!("minecraft:{}_sign[rotation=0]", wood)
+    });
+    let text = SignText { front: lines.iter().map(|line| line.to_string()).collect(), back: Vec::new() };
+    let mut sign = BlockEntity { id: "minecraft:sign".to_string(), pos: (0, 0, 0), ..Default::default() };
+    sign.set_nbt(text.to_nbt(options.legacy));
+
+    let mut builder = SchematicBuilder::new(1, 1, 1);
+    builder.set_block(0, 0, 0, block)?.add_block_entity(sign)?;
+    if options.legacy {
+        // Sponge v2 is what pre-1.20 WorldEdit reads
+        builder.set_format(SchematicFormat::SpongeV2);
+        builder.set_metadata(Metadata { data_version: Some(LEGACY_SIGN_DATA_VERSION), ..Default::default() });
+    }
+    Ok(builder.build())
+}
+
+/// Rows of each glyph, top first; '#' is a block
+const FONT: &[(char, [&str; 5])] = &[
+    ('A', [".#.", "#.#", "###", "#.#", "#.#"]),
+    ('B', ["##.", "#.#", "##.", "#.#", "##."]),
+    ('C', [".##", "#..", "#..", "#..", ".##"]),
+    ('D', ["##.", "#.#", "#.#", "#.#", "##."]),
+    ('E', ["###", "#..", "##.", "#..", "###"]),
+    ('F', ["###", "#..", "##.", "#..", "#.."]),
+    ('G', [".##", "#..", "#.#", "#.#", ".##"]),
+    ('H', ["#.#", "#.#", "###", "#.#", "#.#"]),
+    ('I', ["###", ".#.", ".#.", ".#.", "###"]),
+    ('J', ["..#", "..#", "..#", "#.#", ".#."]),
+    ('K', ["#.#", "#.#", "##.", "#.#", "#.#"]),
+    ('L', ["#..", "#..", "#..", "#..", "###"]),
+    ('M', ["#...#", "##.##", "#.#.#", "#...#", "#...#"]),
+    ('N', ["#..#", "##.#", "#.##", "#..#", "#..#"]),
+    ('O', [".#.", "#.#", "#.#", "#.#", ".#."]),
+    ('P', ["##.", "#.#", "##.", "#..", "#.."]),
+    ('Q', [".#.", "#.#", "#.#", "##.", ".##"]),
+    ('R', ["##.", "#.#", "##.", "#.#", "#.#"]),
+    ('S', [".##", "#..", ".#.", "..#", "##."]),
+    ('T', ["###", ".#.", ".#.", ".#.", ".#."]),
+    ('U', ["#.#", "#.#", "#.#", "#.#", "###"]),
+    ('V', ["#.#", "#.#", "#.#", "#.#", ".#."]),
+    ('W', ["#...#", "#...#", "#.#.#", "##.##", "#...#"]),
+    ('X', ["#.#", "#.#", ".#.", "#.#", "#.#"]),
+    ('Y', ["#.#", "#.#", ".#.", ".#.", ".#."]),
+    ('Z', ["###", "..#", ".#.", "#..", "###"]),
+    ('0', ["###", "#.#", "#.#", "#.#", "###"]),
+    ('1', [".#.", "##.", ".#.", ".#.", "###"]),
+    ('2', ["##.", "..#", ".#.", "#..", "###"]),
+    ('3', ["##.", "..#", ".#.", "..#", "##."]),
+    ('4', ["#.#", "#.#", "###", "..#", "..#"]),
+    ('5', ["###", "#..", "##.", "..#", "##."]),
+    ('6', [".##", "#..", "###", "#.#", "###"]),
+    ('7', ["###", "..#", ".#.", ".#.", ".#."]),
+    ('8', ["###", "#.#", "###", "#.#", "###"]),
+    ('9', ["###", "#.#", "###", "..#", "##."]),
+    (' ', ["..", "..", "..", "..", ".."]),
+    ('.', [".", ".", ".", ".", "#"]),
+    (',', [".", ".", ".", "#", "#"]),
+    ('!', ["#", "#", "#", ".", "#"]),
+    ('?', ["##.", "..#", ".#.", "...", ".#."]),
+    (':', [".", "#", ".", "#", "."]),
+    ('\'', ["#", "#", ".", ".", "."]),
+    ('-', ["...", "...", "###", "...", "..."]),
+    ('+', ["...", ".#.", "###", ".#.", "..."]),
+    ('/', ["..#", "..#", ".#.", "#..", "#.."]),
+    ('<', ["..#", ".#.", "#..", ".#.", "..#"]),
+    ('>', ["#..", ".#.", "..#", ".#.", "#.."]),
+];
+
+/// Glyph rows for a character (letters in either case)
+fn glyph(c: char) -> Option<&'static [&'static str; 5]> {
+    let c = c.to_ascii_uppercase();
+    FONT.iter().find(|(g, _)| *g == c).map(|(_, rows)| rows)
+}
+
+/// Text spelled out in `block` (a block state), lying flat and read from the
+/// south: one column between letters, one row between lines (split at `\n`)
+pub fn text_schematic(text: &str, block: &str) -> Result<UnifiedSchematic, SchemError> {
+    let lines: Vec<Vec<&[&str; 5]>> = text.lines()
+        .map(|line| line.chars().map(|c| glyph(c).ok_or_else(|| {
+            SchemError::Invalid(format!("no block letter for '{}' (letters, digits, space and .,!?:'-+/<>)", c))
+        })).collect())
+        .collect::<Result<_, _>>()?;
+    let line_width = |line: &[&[&str; 5]]| line.iter().map(|rows| rows[0].len() + 1).sum::<usize>().saturating_sub(1);
+    let width = lines.iter().map(|line| line_width(line)).max().unwrap_or(0);
+    if width == 0 {
+        return Err(SchemError::Invalid("no text to write".to_string()));
+    }
+    let length = lines.len() * 6 - 1;
+
+    let block = Schem::parse_block_state(block);
+    let mut builder = SchematicBuilder::try_new(width as u32, 1, length as u32)?;
+    for (line_index, line) in lines.iter().enumerate() {
+        let mut x = 0;
+        for rows in line {
+            for (row, pixels) in rows.iter().enumerate() {
+                for (column, _) in pixels.chars().enumerate().filter(|(_, pixel)| *pixel == '#') {
+                    builder.set_block((x + column) as u32, 0, (line_index * 6 + row) as u32, block.clone())?;
+                }
+            }
+            x += rows[0].len() + 1;
+        }
+    }
+    Ok(builder.build())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_schematic() {
+        let schem = sign_schematic(&["Mine Entrance", "Level \"12\""], &SignOptions { wall: true, ..Default::default() }).unwrap();
+        assert_eq!(schem.get_block(0, 0, 0).unwrap().full_name(), "minecraft:oak_wall_sign[facing=south]");
+        let sign = &schem.block_entities[0];
+        let fastnbt::Value::Compound(front) = &sign.nbt["front_text"] else { panic!("no front_text") };
+        let fastnbt::Value::List(messages) = &front["messages"] else { panic!("no messages") };
+        assert_eq!(messages.len(), 4);
+        assert_eq!(messages[1], fastnbt::Value::String(r#"{"text":"Level \"12\""}"#.to_string()));
+        assert_eq!(messages[3], fastnbt::Value::String(r#"{"text":""}"#.to_string()));
+
+        let legacy = sign_schematic(&["Exit"], &SignOptions { wood: "crimson".to_string(), legacy: true, ..Default::default() }).unwrap();
+        assert_eq!(legacy.get_block(0, 0, 0).unwrap().full_name(), "minecraft:crimson_sign[rotation=0]");
+        assert_eq!(legacy.metadata.data_version, Some(LEGACY_SIGN_DATA_VERSION));
+        assert_eq!(legacy.block_entities[0].get_sign_text().unwrap().front, ["Exit"]);
+
+        assert!(sign_schematic(&["a", "b", "c", "d", "e"], &SignOptions::default()).is_err());
+        assert!(sign_schematic(&["a"], &SignOptions { wood: "stone".to_string(), ..Default::default() }).is_err());
+    }
+
+    #[test]
+    fn test_text_schematic() {
+        let schem = text_schematic("Hi\n1", "minecraft:white_concrete").unwrap();
+        // H (3) + gap + I (3) wide, two lines of 5 with a row between
+        assert_eq!((schem.width, schem.height, schem.length), (7, 1, 11));
+        assert_eq!(schem.solid_blocks(), 11 + 9 + 8);
+        assert_eq!(schem.get_block(0, 0, 2).unwrap().name, "minecraft:white_concrete");
+        assert!(schem.get_block(1, 0, 0).unwrap().is_air());
+        assert!(schem.get_block(3, 0, 0).unwrap().is_air());
+        assert!(text_schematic("é", "minecraft:stone").is_err());
+        assert!(text_schematic("", "minecraft:stone").is_err());
+    }
+}
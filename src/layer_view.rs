@@ -0,0 +1,423 @@
+//! Characters for the layer view, a 2D slice through a schematic
+//!
+//! Most blocks map to a glyph by name, but blocks that only make sense with
+//! their orientation use their block state: stairs, pistons, observers and
+//! hoppers show which way they face, repeaters their delay, comparators their
+//! mode and rails their shape. A Y slice looks down with north at the top, so
+//! `facing=north` is an up arrow. Z slices look north and X slices look east,
+//! both with up at the top; facings along the line of sight show as pointing
+//! toward or away from the viewer.
+
+use crate::{Block, UnifiedSchematic};
+
+/// The axis a slice is taken across
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Axis {
+    X,
+    #[default]
+    Y,
+    Z,
+}
+
+impl Axis {
+    pub fn name(self) -> &'static str {
+        match self {
+            Axis::X => "X",
+            Axis::Y => "Y",
+            Axis::Z => "Z",
+        }
+    }
+
+    /// The axes across and down the slice, in screen order
+    pub fn screen_axes(self) -> (Axis, Axis) {
+        match self {
+            Axis::X => (Axis::Z, Axis::Y),
+            Axis::Y => (Axis::X, Axis::Z),
+            Axis::Z => (Axis::X, Axis::Y),
+        }
+    }
+
+    /// Size of `schem` along this axis
+    pub fn extent(self, schem: &UnifiedSchematic) -> u32 {
+        match self {
+            Axis::X => schem.width,
+            Axis::Y => schem.height,
+            Axis::Z => schem.length,
+        }
+    }
+
+    /// Block position of screen cell (`col`, `row`) in the slice at `coord`
+    fn position(self, coord: u32, col: u32, row: u32, height: u32) -> (u32, u32, u32) {
+        // Vertical slices put the top of the schematic on the first row
+        match self {
+            Axis::X => (coord, height - 1 - row, col),
+            Axis::Y => (col, coord, row),
+            Axis::Z => (col, height - 1 - row, coord),
+        }
+    }
+
+    /// Where a block facing `facing` points on screen
+    fn screen_direction(self, facing: &str) -> Option<Screen> {
+        let (right, left, toward, away) = match self {
+            Axis::X => ("south", "north", "west", "east"),
+            Axis::Y => ("east", "west", "up", "down"),
+            Axis::Z => ("east", "west", "south", "north"),
+        };
+        let (up, down) = match self {
+            Axis::Y => ("north", "south"),
+            Axis::X | Axis::Z => ("up", "down"),
+        };
+        [(up, Screen::Up), (down, Screen::Down), (left, Screen::Left), (right, Screen::Right), (toward, Screen::Toward), (away, Screen::Away)]
+            .into_iter()
+            .find_map(|(name, screen)| (name == facing).then_some(screen))
+    }
+}
+
+impl std::fmt::Display for Axis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for Axis {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "x" => Ok(Axis::X),
+            "y" => Ok(Axis::Y),
+            "z" => Ok(Axis::Z),
+            _ => Err(format!("unknown axis '{}' (expected x, y or z)", s)),
+        }
+    }
+}
+
+/// A direction as drawn in a slice
+#[derive(Clone, Copy)]
+enum Screen {
+    Up,
+    Down,
+    Left,
+    Right,
+    Toward,
+    Away,
+}
+
+/// Size of the slice across `axis` as (columns, rows)
+pub fn slice_size(schem: &UnifiedSchematic, axis: Axis) -> (u32, u32) {
+    match axis {
+        Axis::X => (schem.length, schem.height),
+        Axis::Y => (schem.width, schem.length),
+        Axis::Z => (schem.width, schem.height),
+    }
+}
+
+/// Blocks of the slice at `coord` across `axis`, row by row, or `None` when
+/// `coord` is outside the schematic
+///
+/// With a `scale` above 1 each cell stands for a `scale`x`scale` square of
+/// the slice and shows its most common non-air block, so details survive
+/// downsampling as long as they are the majority of what isn't air.
+pub fn slice_cells(schem: &UnifiedSchematic, axis: Axis, coord: u32, scale: u32) -> Option<Vec<Vec<&Block>>> {
+    if coord >= axis.extent(schem) {
+        return None;
+    }
+    let scale = scale.max(1);
+    let (cols, rows) = slice_size(schem, axis);
+    let cell = |col: u32, row: u32| {
+        let mut counts: Vec<(&Block, usize)> = Vec::new();
+        let mut fallback = None;
+        for r in row * scale..((row + 1) * scale).min(rows) {
+            for c in col * scale..((col + 1) * scale).min(cols) {
+                let (x, y, z) = axis.position(coord, c, r, schem.height);
+                let Some(block) = schem.get_block(x, y, z) else { continue };
+                fallback.get_or_insert(block);
+                if block.is_air() {
+                    continue;
+                }
+                match counts.iter_mut().find(|(b, _)| *b == block) {
+                    Some((_, n)) => *n += 1,
+                    None => counts.push((block, 1)),
+                }
+            }
+        }
+        // The first of the most common blocks, so ties go to the top left
+        let common = counts.iter().rev().max_by_key(|(_, n)| *n).map(|&(b, _)| b);
+        common.or(fallback).expect("cells are inside the slice")
+    };
+    let rows = (0..rows.div_ceil(scale))
+        .map(|row| (0..cols.div_ceil(scale)).map(|col| cell(col, row)).collect())
+        .collect();
+    Some(rows)
+}
+
+/// Rows of glyphs for the slice at `coord` across `axis`, downsampled by
+/// `scale` like [`slice_cells`]
+pub fn slice(schem: &UnifiedSchematic, axis: Axis, coord: u32, scale: u32, ascii: bool) -> Option<Vec<String>> {
+    let cells = slice_cells(schem, axis, coord, scale)?;
+    Some(cells.iter().map(|row| row.iter().map(|block| slice_glyph(block, axis, ascii)).collect()).collect())
+}
+
+/// Smallest downsampling factor that fits `cols` cells of `cell_width`
+/// characters into `max_width` characters
+pub fn fit_scale(cols: u32, cell_width: u32, max_width: u32) -> u32 {
+    let max_cells = (max_width / cell_width.max(1)).max(1);
+    cols.div_ceil(max_cells).max(1)
+}
+
+/// A row of colored cells, two spaces each with `color`'s 24-bit background,
+/// or the nearest 256-color one without `truecolor`; air keeps the terminal's
+/// background
+pub fn color_row(cells: &[&Block], color: impl Fn(&Block) -> (u8, u8, u8), truecolor: bool) -> String {
+    let mut row = String::new();
+    for block in cells {
+        if block.is_air() {
+            row.push_str("\x1b[49m  ");
+        } else {
+            let (r, g, b) = color(block);
+            if truecolor {
+                row.push_str(&format!("\x1b[48;2;{};{};{}m  ", r, g, b));
+            } else {
+                row.push_str(&format!("\x1b[48;5;{}m  ", ansi_256((r, g, b))));
+            }
+        }
+    }
+    row.push_str("\x1b[0m");
+    row
+}
+
+/// Nearest color in the xterm 256-color palette's color cube and gray ramp
+pub fn ansi_256(rgb: (u8, u8, u8)) -> u8 {
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+    let distance = |a: (u8, u8, u8)| {
+        let d = |x: u8, y: u8| (x as i32 - y as i32).pow(2);
+        d(a.0, rgb.0) + d(a.1, rgb.1) + d(a.2, rgb.2)
+    };
+    let nearest_level = |v: u8| (0..6).min_by_key(|&i| (LEVELS[i] as i32 - v as i32).abs()).unwrap_or(0);
+    let (r, g, b) = (nearest_level(rgb.0), nearest_level(rgb.1), nearest_level(rgb.2));
+    let cube = (16 + 36 * r + 6 * g + b) as u8;
+    let cube_rgb = (LEVELS[r], LEVELS[g], LEVELS[b]);
+
+    // Grays run from 8 to 238 in steps of 10
+    let mean = (rgb.0 as u32 + rgb.1 as u32 + rgb.2 as u32) / 3;
+    let step = (mean.saturating_sub(3) / 10).min(23) as u8;
+    let level = 8 + 10 * step;
+    if distance((level, level, level)) < distance(cube_rgb) {
+        232 + step
+    } else {
+        cube
+    }
+}
+
+/// Glyph for `block` in ASCII or Unicode, as seen from above
+pub fn glyph_for(block: &Block, ascii: bool) -> char {
+    slice_glyph(block, Axis::Y, ascii)
+}
+
+/// Glyph for `block` in a slice across `axis`
+pub fn slice_glyph(block: &Block, axis: Axis, ascii: bool) -> char {
+    if block.is_air() {
+        return if ascii { '.' } else { ' ' };
+    }
+    state_glyph(block, axis, ascii).unwrap_or_else(|| name_glyph(block.display_name(), ascii))
+}
+
+/// Legend lines for the glyphs `glyph_for` uses
+pub fn legend(ascii: bool) -> &'static [&'static str] {
+    if ascii {
+        &[
+            ". = air, # = stone, ~ = dirt/grass, = = wood",
+            "o = glass, w = water, L = lava, * = ore",
+            "C = chest, D = door, i = torch, r = redstone",
+            "< ^ > v = facing (stairs, pistons, observers, hoppers), + = toward you, x = away",
+            "1-4 = repeater delay, c/s = comparator compare/subtract",
+            "| - / \\ _ = rails",
+        ]
+    } else {
+        &[
+            "█ = stone, ▓ = dirt/grass, ▒ = wood, □ = glass",
+            "≈ = water, ∴ = lava, ◆ = ore, ■ = chest",
+            "† = torch, · = redstone, ░ = other solid",
+            "← ↑ → ↓ = facing (stairs, pistons, observers, hoppers), ⊙ = toward you, ⊗ = away",
+            "1-4 = repeater delay, c/s = comparator compare/subtract",
+            "│ ─ ┌ ┐ └ ┘ ╱ ╲ ▁ = rails",
+        ]
+    }
+}
+
+/// Glyph from the block state, for blocks whose orientation matters
+fn state_glyph(block: &Block, axis: Axis, ascii: bool) -> Option<char> {
+    let name = block.display_name();
+    let directional = name.ends_with("_stairs")
+        || matches!(name, "piston" | "sticky_piston" | "piston_head" | "observer" | "hopper");
+    if directional {
+        return block.facing().and_then(|facing| axis.screen_direction(facing)).map(|screen| {
+            let (a, u) = match screen {
+                Screen::Up => ('^', '↑'),
+                Screen::Down => ('v', '↓'),
+                Screen::Left => ('<', '←'),
+                Screen::Right => ('>', '→'),
+                Screen::Toward => ('+', '⊙'),
+                Screen::Away => ('x', '⊗'),
+            };
+            if ascii { a } else { u }
+        });
+    }
+    match name {
+        "repeater" => block.get_property("delay")
+            .and_then(|delay| delay.parse::<u32>().ok())
+            .and_then(|delay| char::from_digit(delay, 10)),
+        "comparator" => match block.get_property("mode").map(String::as_str) {
+            Some("subtract") => Some('s'),
+            Some(_) => Some('c'),
+            None => None,
+        },
+        _ if name == "rail" || name.ends_with("_rail") => {
+            block.get_property("shape").and_then(|shape| rail_glyph(shape, axis, ascii))
+        }
+        _ => None,
+    }
+}
+
+/// Rail shapes from above, with slopes as the straight piece they run along;
+/// from the side, slopes rise toward their direction and the rest lie flat
+fn rail_glyph(shape: &str, axis: Axis, ascii: bool) -> Option<char> {
+    let (a, u) = match axis {
+        Axis::Y => match shape {
+            "north_south" | "ascending_north" | "ascending_south" => ('|', '│'),
+            "east_west" | "ascending_east" | "ascending_west" => ('-', '─'),
+            "south_east" => ('/', '┌'),
+            "south_west" => ('\\', '┐'),
+            "north_east" => ('\\', '└'),
+            "north_west" => ('/', '┘'),
+            _ => return None,
+        },
+        Axis::X | Axis::Z => {
+            let ascending = shape.strip_prefix("ascending_").and_then(|to| axis.screen_direction(to));
+            match ascending {
+                Some(Screen::Right) => ('/', '╱'),
+                Some(Screen::Left) => ('\\', '╲'),
+                _ => ('_', '▁'),
+            }
+        }
+    };
+    Some(if ascii { a } else { u })
+}
+
+/// Glyph by name substring, for everything without a state glyph
+fn name_glyph(name: &str, ascii: bool) -> char {
+    if ascii {
+        if name.contains("stone") { return '#' }
+        if name.contains("dirt") || name.contains("grass") { return '~' }
+        if name.contains("wood") || name.contains("log") || name.contains("plank") { return '=' }
+        if name.contains("glass") { return 'o' }
+        if name.contains("water") { return 'w' }
+        if name.contains("lava") { return 'L' }
+        if name.contains("ore") { return '*' }
+        if name.contains("chest") { return 'C' }
+        if name.contains("door") { return 'D' }
+        if name.contains("torch") { return 'i' }
+        if name.contains("redstone") { return 'r' }
+        if name.contains("wool") || name.contains("concrete") { return '@' }
+        if name.contains("brick") { return 'B' }
+        if name.contains("iron") { return 'I' }
+        if name.contains("gold") { return 'G' }
+        if name.contains("diamond") { return '$' }
+        '#'
+    } else {
+        if name.contains("stone") { return '\u{2588}' } // █
+        if name.contains("dirt") || name.contains("grass") { return '\u{2593}' } // ▓
+        if name.contains("wood") || name.contains("log") || name.contains("plank") { return '\u{2592}' } // ▒
+        if name.contains("glass") { return '\u{25A1}' } // □
+        if name.contains("water") { return '\u{2248}' } // ≈
+        if name.contains("lava") { return '\u{2234}' } // ∴
+        if name.contains("ore") { return '\u{25C6}' } // ◆
+        if name.contains("chest") { return '\u{25A0}' } // ■
+        if name.contains("door") { return '\u{25AF}' } // ▯
+        if name.contains("torch") { return '\u{2020}' } // †
+        if name.contains("redstone") { return '\u{00B7}' } // ·
+        '\u{2591}' // ░
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::BlockState;
+
+    fn with_props(name: &str, props: &[(&str, &str)]) -> Block {
+        let properties = props.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        Block::with_state(name, BlockState { properties })
+    }
+
+    #[test]
+    fn test_glyph_for() {
+        assert_eq!(glyph_for(&Block::air(), true), '.');
+        assert_eq!(glyph_for(&Block::new("minecraft:stone"), false), '█');
+        assert_eq!(glyph_for(&Block::new("minecraft:oak_planks"), true), '=');
+
+        let stairs = with_props("minecraft:oak_stairs", &[("facing", "east"), ("half", "bottom")]);
+        assert_eq!(glyph_for(&stairs, true), '>');
+        assert_eq!(glyph_for(&stairs, false), '→');
+        assert_eq!(glyph_for(&with_props("minecraft:sticky_piston", &[("facing", "north")]), false), '↑');
+        assert_eq!(glyph_for(&with_props("minecraft:observer", &[("facing", "up")]), true), '+');
+        assert_eq!(glyph_for(&with_props("minecraft:hopper", &[("facing", "down")]), false), '⊗');
+        // Stairs without a state fall back to the name
+        assert_eq!(glyph_for(&Block::new("minecraft:stone_stairs"), true), '#');
+
+        assert_eq!(glyph_for(&with_props("minecraft:repeater", &[("delay", "3"), ("facing", "west")]), true), '3');
+        assert_eq!(glyph_for(&with_props("minecraft:comparator", &[("mode", "subtract")]), false), 's');
+        assert_eq!(glyph_for(&with_props("minecraft:comparator", &[("mode", "compare")]), true), 'c');
+
+        assert_eq!(glyph_for(&with_props("minecraft:rail", &[("shape", "south_east")]), false), '┌');
+        assert_eq!(glyph_for(&with_props("minecraft:powered_rail", &[("shape", "ascending_north")]), true), '|');
+        assert_eq!(glyph_for(&with_props("minecraft:rail", &[("shape", "north_west")]), false), '┘');
+    }
+
+    #[test]
+    fn test_slice_across_axes() {
+        // 3 wide, 2 tall, 2 long: stone floor, stairs facing east and a rail on top
+        let mut builder = crate::SchematicBuilder::new(3, 2, 2);
+        builder.fill((0, 0, 0), (2, 0, 1), Block::new("minecraft:stone")).unwrap();
+        builder.set_block(0, 1, 0, with_props("minecraft:oak_stairs", &[("facing", "east")])).unwrap();
+        builder.set_block(2, 1, 1, with_props("minecraft:rail", &[("shape", "ascending_east")])).unwrap();
+        let schem = builder.build();
+
+        assert_eq!(slice(&schem, Axis::Y, 1, 1, true).unwrap(), [">..", "..-"]);
+        // Looking north, east is to the right and the top row is the highest
+        assert_eq!(slice(&schem, Axis::Z, 0, 1, true).unwrap(), [">..", "###"]);
+        assert_eq!(slice(&schem, Axis::Z, 1, 1, true).unwrap(), ["../", "###"]);
+        // Looking east the stairs face away, with south to the right
+        assert_eq!(slice(&schem, Axis::X, 0, 1, true).unwrap(), ["x.", "##"]);
+        assert_eq!(slice(&schem, Axis::X, 2, 1, false).unwrap(), [" ▁", "██"]);
+        assert_eq!(slice_size(&schem, Axis::X), (2, 2));
+        assert!(slice(&schem, Axis::Z, 2, 1, true).is_none());
+        assert_eq!("Z".parse::<Axis>(), Ok(Axis::Z));
+    }
+
+    #[test]
+    fn test_downsampled_colors() {
+        // Mostly stone with one glass block in the top-left 2x2 square
+        let mut builder = crate::SchematicBuilder::new(5, 1, 3);
+        builder.fill((0, 0, 0), (3, 0, 1), Block::new("minecraft:stone")).unwrap();
+        builder.set_block(0, 0, 0, Block::new("minecraft:glass")).unwrap();
+        builder.set_block(4, 0, 2, Block::new("minecraft:glass")).unwrap();
+        let schem = builder.build();
+
+        assert_eq!(fit_scale(5, 2, 80), 1);
+        assert_eq!(fit_scale(5, 2, 4), 3);
+        assert_eq!(fit_scale(100, 1, 0), 100);
+        // Partial squares at the edges; a square of air stays air
+        assert_eq!(slice(&schem, Axis::Y, 0, 2, true).unwrap(), ["##.", "..o"]);
+
+        let cells = &slice_cells(&schem, Axis::Y, 0, 2).unwrap()[1];
+        let row = color_row(cells, |_| (255, 0, 0), true);
+        assert_eq!(row, "\x1b[49m  \x1b[49m  \x1b[48;2;255;0;0m  \x1b[0m");
+        assert!(color_row(cells, |_| (255, 0, 0), false).contains("\x1b[48;5;196m"));
+
+        assert_eq!(ansi_256((0, 0, 0)), 16);
+        assert_eq!(ansi_256((255, 255, 255)), 231);
+        assert_eq!(ansi_256((128, 128, 128)), 244);
+        assert_eq!(ansi_256((95, 135, 175)), 67);
+    }
+}
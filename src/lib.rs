@@ -2,39 +2,201 @@ pub mod schematic;
 pub mod schem;
 pub mod litematica;
 pub mod block;
+pub mod direction;
 pub mod block_geometry;
+#[cfg(feature = "fs")]
 pub mod mc_models;
 pub mod error;
 pub mod recipes;
+#[cfg(feature = "fs")]
 pub mod export3d;
+#[cfg(feature = "fs")]
 pub mod export_gltf;
+#[cfg(feature = "fs")]
 pub mod textures;
+pub mod progress;
+#[cfg(feature = "fs")]
+pub mod random;
+pub mod timings;
+pub mod builder;
+pub mod structures;
+pub mod lighting;
+pub mod supports;
+pub mod attachment;
+pub mod build_order;
+pub mod fixtures;
+pub mod layer_view;
+pub mod snbt;
+pub mod preview;
+pub mod fingerprint;
+pub mod mods;
+pub mod structure_nbt;
+#[cfg(feature = "fs")]
+pub mod voxelize;
+#[cfg(feature = "fs")]
+pub mod pixel_art;
+pub mod labels;
+pub mod mining;
+pub mod versions;
+pub mod downgrade;
+pub mod sanitize;
+pub mod paste_check;
+pub mod focus;
+#[cfg(feature = "signing")]
+pub mod signing;
+#[cfg(feature = "server")]
+pub mod server;
+#[cfg(feature = "fs")]
+pub mod anvil;
+mod extra_nbt;
+mod probe;
+#[cfg(feature = "serde")]
+mod serialize;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "python")]
+pub mod python;
 
 pub use schematic::Schematic;
 pub use schem::Schem;
 pub use litematica::Litematica;
-pub use block::{Block, BlockState};
+pub use block::{Aggregation, Block, BlockClass, BlockSelector, BlockState};
 pub use error::SchemError;
+pub use progress::{Progress, NoProgress};
+pub use builder::SchematicBuilder;
 
+#[cfg(feature = "fs")]
 use std::path::Path;
+#[cfg(feature = "fs")]
 use std::fs::File;
-use std::io::{BufReader, Read};
+use std::io::{BufRead, BufReader, Read};
 use flate2::read::GzDecoder;
+use probe::Detected;
+
+/// Serialize to NBT and gzip it, as schematic files are stored
+pub(crate) fn gzip_nbt<T: serde::Serialize>(value: &T) -> Result<Vec<u8>, SchemError> {
+    use std::io::Write;
+    let nbt = fastnbt::to_bytes(value)?;
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(&nbt)?;
+    Ok(encoder.finish()?)
+}
+
+/// Largest size along any axis, so every position fits the `i32` block entity coordinates
+pub const MAX_AXIS: u32 = i32::MAX as u32;
+
+/// Number of blocks in a box of the given size, or `SchemError::TooLarge` if it
+/// can't be indexed or allocated on this target
+pub fn checked_volume(width: u32, height: u32, length: u32) -> Result<usize, SchemError> {
+    let too_large = || SchemError::TooLarge { width, height, length };
+    if width > MAX_AXIS || height > MAX_AXIS || length > MAX_AXIS {
+        return Err(too_large());
+    }
+    let volume = (width as u64)
+        .checked_mul(height as u64)
+        .and_then(|v| v.checked_mul(length as u64))
+        .and_then(|v| usize::try_from(v).ok())
+        .ok_or_else(too_large)?;
+    // A Vec can't hold more than isize::MAX bytes
+    match volume.checked_mul(std::mem::size_of::<Block>()) {
+        Some(bytes) if bytes <= isize::MAX as usize => Ok(volume),
+        _ => Err(too_large()),
+    }
+}
+
+/// Options for loading schematics
+///
+/// Build with `LoadOptions::new()` and the `with_*` methods; the default
+/// reports no progress and rejects corrupt block data.
+#[derive(Clone, Copy)]
+pub struct LoadOptions<'a> {
+    /// Receiver for progress updates and cancellation checks
+    pub progress: &'a dyn Progress,
+    /// Substitute air for undecodable blocks instead of failing with `SchemError::CorruptData`
+    pub lenient: bool,
+    /// Keep litematic entities outside the block grid instead of dropping them
+    pub keep_out_of_bounds: bool,
+}
+
+impl Default for LoadOptions<'_> {
+    fn default() -> Self {
+        Self { progress: &NoProgress, lenient: false, keep_out_of_bounds: false }
+    }
+}
+
+impl<'a> LoadOptions<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Report progress to (and accept cancellation from) `progress`
+    pub fn with_progress(mut self, progress: &'a dyn Progress) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// Recover from corrupt block data by filling it with air
+    pub fn with_lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    /// Keep entities outside the block grid (see [`Litematica::to_unified_with`])
+    pub fn with_keep_out_of_bounds(mut self, keep: bool) -> Self {
+        self.keep_out_of_bounds = keep;
+        self
+    }
+}
+
+/// What loading had to work around, from [`UnifiedSchematic::load_report`]
+///
+/// Schematics built in memory or converted from another one have an empty report.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LoadReport {
+    /// Block entities dropped because a later one had the same position
+    pub duplicate_block_entities: usize,
+    /// Block entities kept at a position that is air or outside the schematic
+    pub orphaned_block_entities: usize,
+    /// Blocks filled with air by a lenient load (see [`LoadOptions::lenient`])
+    pub recovered_blocks: usize,
+    /// Litematic entities outside the block grid that were dropped (see
+    /// [`LoadOptions::keep_out_of_bounds`])
+    pub dropped_entities: usize,
+    /// The data couldn't be streamed into the parser for its detected format,
+    /// so it was decompressed into memory and tried as every format
+    pub buffered: bool,
+}
+
+impl LoadReport {
+    /// Whether the block entities need fixing before the schematic is pasted
+    pub fn has_block_entity_issues(&self) -> bool {
+        self.duplicate_block_entities > 0 || self.orphaned_block_entities > 0
+    }
+}
 
 /// Unified schematic representation
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct UnifiedSchematic {
     pub format: SchematicFormat,
-    pub width: u16,
-    pub height: u16,
-    pub length: u16,
+    pub width: u32,
+    pub height: u32,
+    pub length: u32,
+    /// World (or placement-relative) position of local block (0, 0, 0)
+    ///
+    /// Sponge `Offset`, legacy `WEOrigin` + `WEOffset`, or the minimum region
+    /// corner relative to a Litematica placement origin.
+    pub offset: (i32, i32, i32),
     pub blocks: Vec<Block>,
     pub block_entities: Vec<BlockEntity>,
     pub entities: Vec<Entity>,
     pub metadata: Metadata,
+    pub(crate) load_report: LoadReport,
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SchematicFormat {
     /// Old MCEdit format (.schematic)
     Legacy,
@@ -46,19 +208,154 @@ pub enum SchematicFormat {
     Litematica,
 }
 
+/// What happens to a block entity when the block at its position is replaced
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlockEntityPolicy {
+    /// Remove it only when the block type (name) changes
+    #[default]
+    RemoveIfChanged,
+    /// Always keep it
+    Keep,
+    /// Always remove it
+    Remove,
+}
+
+impl BlockEntityPolicy {
+    fn removes(self, name_changed: bool) -> bool {
+        match self {
+            BlockEntityPolicy::RemoveIfChanged => name_changed,
+            BlockEntityPolicy::Keep => false,
+            BlockEntityPolicy::Remove => true,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BlockEntity {
     pub id: String,
     pub pos: (i32, i32, i32),
+    /// Fields besides the id and position as text, for display and lookups
     pub data: std::collections::HashMap<String, String>,
+    /// The same fields as NBT; this is what gets saved
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub nbt: std::collections::HashMap<String, fastnbt::Value>,
 }
 
 impl BlockEntity {
+    /// The whole block entity as one compound with its id and position, the
+    /// way `/data get block` shows it
+    pub fn to_nbt(&self) -> fastnbt::Value {
+        let mut nbt = self.nbt.clone();
+        nbt.insert("id".to_string(), fastnbt::Value::String(self.id.clone()));
+        nbt.insert("x".to_string(), fastnbt::Value::Int(self.pos.0));
+        nbt.insert("y".to_string(), fastnbt::Value::Int(self.pos.1));
+        nbt.insert("z".to_string(), fastnbt::Value::Int(self.pos.2));
+        fastnbt::Value::Compound(nbt)
+    }
+
+    /// Replace the NBT fields, and their text in `data`
+    ///
+    /// A string `id` in `nbt` becomes the block entity's id; position fields
+    /// are dropped, since the block entity stays where it is.
+    pub fn set_nbt(&mut self, mut nbt: std::collections::HashMap<String, fastnbt::Value>) {
+        if let Some(fastnbt::Value::String(id)) = nbt.remove("id").or_else(|| nbt.remove("Id")) {
+            self.id = id;
+        }
+        for key in ["x", "y", "z", "Pos", "keepPacked"] {
+            nbt.remove(key);
+        }
+        self.data = nbt.iter().map(|(key, value)| (key.clone(), schem::format_nbt_value(value))).collect();
+        self.nbt = nbt;
+    }
+
     /// Check if this is a sign
     pub fn is_sign(&self) -> bool {
         self.id.contains("sign")
     }
 
+    /// Check if this is a mob spawner (not a trial spawner)
+    pub fn is_spawner(&self) -> bool {
+        let id = self.id.trim_start_matches("minecraft:").to_ascii_lowercase();
+        id.replace('_', "") == "mobspawner"
+    }
+
+    /// Spawner settings (supports both the pre-1.18 and the 1.18+ SpawnData layout)
+    pub fn get_spawner(&self) -> Option<SpawnerInfo> {
+        if !self.is_spawner() {
+            return None;
+        }
+
+        use fastnbt::Value;
+
+        let short = |key: &str| match self.nbt.get(key) {
+            Some(Value::Short(v)) => Some(*v),
+            _ => None,
+        };
+        let id = |value: Option<&Value>| match value {
+            Some(Value::String(id)) if !id.is_empty() => Some(id.clone()),
+            _ => None,
+        };
+
+        // 1.18+: SpawnData.entity.id; before: SpawnData.id; before 1.9: EntityId
+        let entity = match self.nbt.get("SpawnData") {
+            Some(Value::Compound(data)) => match data.get("entity") {
+                Some(Value::Compound(entity)) => id(entity.get("id")),
+                _ => id(data.get("id")),
+            },
+            _ => None,
+        }.or_else(|| id(self.nbt.get("EntityId")));
+
+        let potentials = match self.nbt.get("SpawnPotentials") {
+            Some(Value::List(list)) => list.len(),
+            _ => 0,
+        };
+
+        Some(SpawnerInfo {
+            entity,
+            delay: short("Delay"),
+            min_spawn_delay: short("MinSpawnDelay"),
+            max_spawn_delay: short("MaxSpawnDelay"),
+            spawn_count: short("SpawnCount"),
+            max_nearby_entities: short("MaxNearbyEntities"),
+            required_player_range: short("RequiredPlayerRange"),
+            spawn_range: short("SpawnRange"),
+            potentials,
+        })
+    }
+
+    /// Check if this is a jigsaw block
+    pub fn is_jigsaw(&self) -> bool {
+        self.id.trim_start_matches("minecraft:") == "jigsaw"
+    }
+
+    /// Jigsaw settings (supports the 1.14-1.15 field names too)
+    pub fn get_jigsaw(&self) -> Option<JigsawInfo> {
+        if !self.is_jigsaw() {
+            return None;
+        }
+
+        let field = |keys: &[&str]| keys.iter().find_map(|key| match self.nbt.get(*key) {
+            Some(fastnbt::Value::String(v)) if !v.is_empty() => Some(v.clone()),
+            _ => None,
+        });
+        let int = |key: &str| match self.nbt.get(key) {
+            Some(fastnbt::Value::Int(v)) => Some(*v),
+            _ => None,
+        };
+
+        Some(JigsawInfo {
+            name: field(&["name"]),
+            // 1.14-1.15 had target_pool and attachement_type (sic), and no name or joint
+            target: field(&["target", "attachement_type"]),
+            pool: field(&["pool", "target_pool"]),
+            final_state: field(&["final_state"]),
+            joint: field(&["joint"]),
+            placement_priority: int("placement_priority"),
+            selection_priority: int("selection_priority"),
+        })
+    }
+
     /// Extract text from a sign (supports both old and new formats)
     pub fn get_sign_text(&self) -> Option<SignText> {
         if !self.is_sign() {
@@ -100,6 +397,41 @@ impl BlockEntity {
     }
 }
 
+/// Parsed mob spawner settings
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpawnerInfo {
+    /// Entity id from SpawnData, e.g. "minecraft:zombie"
+    pub entity: Option<String>,
+    /// Ticks until the next spawn
+    pub delay: Option<i16>,
+    pub min_spawn_delay: Option<i16>,
+    pub max_spawn_delay: Option<i16>,
+    pub spawn_count: Option<i16>,
+    pub max_nearby_entities: Option<i16>,
+    pub required_player_range: Option<i16>,
+    pub spawn_range: Option<i16>,
+    /// Number of weighted SpawnPotentials entries
+    pub potentials: usize,
+}
+
+/// Parsed jigsaw block settings
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JigsawInfo {
+    /// This jigsaw's own name, which other jigsaws target
+    pub name: Option<String>,
+    /// Name of the jigsaw to attach to in the next piece
+    pub target: Option<String>,
+    /// Template pool the next piece comes from
+    pub pool: Option<String>,
+    /// Block state the jigsaw turns into once placed
+    pub final_state: Option<String>,
+    /// "rollable" or "aligned", for jigsaws facing up or down
+    pub joint: Option<String>,
+    /// 1.20.3+ generation order settings
+    pub placement_priority: Option<i32>,
+    pub selection_priority: Option<i32>,
+}
+
 /// Parsed sign text
 #[derive(Debug, Clone, Default)]
 pub struct SignText {
@@ -119,6 +451,39 @@ impl SignText {
     pub fn back_text(&self) -> String {
         self.back.join("\n")
     }
+
+    /// Sign block entity fields holding this text: `front_text`/`back_text`
+    /// (1.20+), or `Text1`-`Text4` for the front only when `legacy`
+    ///
+    /// Lines are written as JSON text components and padded to four.
+    pub fn to_nbt(&self, legacy: bool) -> std::collections::HashMap<String, fastnbt::Value> {
+        use fastnbt::Value;
+        let messages = |lines: &[String]| -> Vec<Value> {
+            (0..4).map(|i| {
+                let line = lines.get(i).map_or("", String::as_str);
+                Value::String(serde_json::json!({ "text": line }).to_string())
+            }).collect()
+        };
+
+        let mut nbt = std::collections::HashMap::new();
+        if legacy {
+            for (i, message) in messages(&self.front).into_iter().enumerate() {
+                nbt.insert(format!("Text{}", i + 1), message);
+            }
+            nbt.insert("Color".to_string(), Value::String("black".to_string()));
+            nbt.insert("GlowingText".to_string(), Value::Byte(0));
+            return nbt;
+        }
+        let side = |lines: &[String]| Value::Compound(std::collections::HashMap::from([
+            ("messages".to_string(), Value::List(messages(lines))),
+            ("color".to_string(), Value::String("black".to_string())),
+            ("has_glowing_text".to_string(), Value::Byte(0)),
+        ]));
+        nbt.insert("front_text".to_string(), side(&self.front));
+        nbt.insert("back_text".to_string(), side(&self.back));
+        nbt.insert("is_waxed".to_string(), Value::Byte(0));
+        nbt
+    }
 }
 
 /// Parse JSON text component to plain text
@@ -209,83 +574,859 @@ fn parse_sign_text_compound(data: &str) -> Vec<String> {
 }
 
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Entity {
     pub id: String,
     pub pos: (f64, f64, f64),
+    /// Fields besides the id and position as text, for display and lookups
     pub data: std::collections::HashMap<String, String>,
+    /// The same fields as NBT
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub nbt: std::collections::HashMap<String, fastnbt::Value>,
+}
+
+/// Commonly useful mob fields
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MobInfo {
+    pub custom_name: Option<String>,
+    pub no_ai: bool,
+    pub health: Option<f32>,
+    /// Villager profession, e.g. "minecraft:farmer"
+    pub profession: Option<String>,
+}
+
+impl MobInfo {
+    pub fn is_empty(&self) -> bool {
+        *self == MobInfo::default()
+    }
+}
+
+impl Entity {
+    /// Extract CustomName, NoAI, Health and villager profession where present
+    pub fn mob_info(&self) -> MobInfo {
+        use fastnbt::Value;
+
+        let custom_name = match self.nbt.get("CustomName") {
+            // A JSON text component before 1.21.5, a compound since
+            Some(Value::String(json)) => Some(parse_json_text(json)),
+            Some(Value::Compound(text)) => match text.get("text") {
+                Some(Value::String(text)) => Some(text.clone()),
+                _ => None,
+            },
+            _ => None,
+        };
+        let profession = match self.nbt.get("VillagerData") {
+            Some(Value::Compound(villager)) => match villager.get("profession") {
+                Some(Value::String(profession)) => Some(profession.clone()),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        MobInfo {
+            custom_name: custom_name.filter(|name| !name.is_empty()),
+            no_ai: matches!(self.nbt.get("NoAI"), Some(Value::Byte(v)) if *v != 0),
+            health: match self.nbt.get("Health") {
+                Some(Value::Float(v)) => Some(*v),
+                // A short before 1.9
+                Some(Value::Short(v)) => Some(*v as f32),
+                _ => None,
+            },
+            profession,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Metadata {
     pub name: Option<String>,
     pub author: Option<String>,
     pub date: Option<i64>,
     pub required_mods: Vec<String>,
     pub extra: std::collections::HashMap<String, String>,
+    /// Minecraft data version (Sponge `DataVersion`, Litematica `MinecraftDataVersion`)
+    pub data_version: Option<i32>,
+    /// Sponge `Offset` as stored in the file
+    pub offset: Option<(i32, i32, i32)>,
+    /// WorldEdit `WEOffsetX/Y/Z`: minimum corner relative to the copy origin
+    pub we_offset: Option<(i32, i32, i32)>,
+    /// WorldEdit `WEOriginX/Y/Z` (legacy format): copy origin in the world
+    pub we_origin: Option<(i32, i32, i32)>,
+    /// Litematica `EnclosingSize`
+    pub enclosing_size: Option<(i32, i32, i32)>,
+    /// Litematica `TotalBlocks` (non-air blocks as counted by Litematica)
+    pub total_blocks: Option<i64>,
+    /// Litematica `TotalVolume`
+    pub total_volume: Option<i64>,
+    /// Litematica `RegionCount`
+    pub region_count: Option<i32>,
+    /// Top-level NBT fields the loader didn't recognise (a Compound), kept
+    /// verbatim so a writer for `UnifiedSchematic::format` can re-emit them
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub raw_extra: Option<fastnbt::Value>,
+}
+
+impl Metadata {
+    /// Prefix of the `extra` keys schem-tool itself writes; writers carry
+    /// these over, since their values are plain strings
+    pub const OWN_PREFIX: &'static str = "schem-tool:";
+
+    /// The `extra` entries under [`Metadata::OWN_PREFIX`], sorted by key
+    pub fn own_extra(&self) -> Vec<(&str, &str)> {
+        let mut entries: Vec<_> = self.extra.iter()
+            .filter(|(key, _)| key.starts_with(Self::OWN_PREFIX))
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .collect();
+        entries.sort();
+        entries
+    }
+
+    /// Set the schematic name
+    pub fn set_name(&mut self, name: impl Into<String>) -> &mut Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Set the author
+    pub fn set_author(&mut self, author: impl Into<String>) -> &mut Self {
+        self.author = Some(author.into());
+        self
+    }
+
+    /// Set the creation date (milliseconds since the Unix epoch)
+    pub fn set_date(&mut self, millis: i64) -> &mut Self {
+        self.date = Some(millis);
+        self
+    }
+
+    /// Names and NBT types of the preserved-but-unparsed fields in `raw_extra`, sorted by name
+    pub fn raw_extra_fields(&self) -> Vec<(&str, &'static str)> {
+        let mut fields: Vec<_> = match &self.raw_extra {
+            Some(fastnbt::Value::Compound(map)) => {
+                map.iter().map(|(k, v)| (k.as_str(), extra_nbt::type_name(v))).collect()
+            }
+            _ => Vec::new(),
+        };
+        fields.sort();
+        fields
+    }
+
+    /// `raw_extra` as root fields for a `written` writer, which keeps them when
+    /// the schematic came from the same format (`keep`) and drops them otherwise
+    pub(crate) fn raw_extra_root(&self, keep: bool, written: &str) -> std::collections::HashMap<String, fastnbt::Value> {
+        match &self.raw_extra {
+            Some(fastnbt::Value::Compound(fields)) if keep => fields.clone(),
+            _ => {
+                self.warn_dropped_raw_extra(written);
+                std::collections::HashMap::new()
+            }
+        }
+    }
+
+    /// Warn that a `written` writer leaves out the `raw_extra` fields, if there are any
+    pub(crate) fn warn_dropped_raw_extra(&self, written: &str) {
+        let names: Vec<_> = self.raw_extra_fields().into_iter().map(|(name, _)| name).collect();
+        if !names.is_empty() {
+            log::warn!("Dropping unrecognised root fields {} from another format in {} output", names.join(", "), written);
+        }
+    }
+}
+
+/// Everything `schem-tool info` shows, from [`UnifiedSchematic::summary`]
+///
+/// Fields are only ever added, never removed or renamed, and keep their
+/// meaning, so the struct and its JSON form are safe to depend on. It can't be
+/// built literally outside this crate for the same reason.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[non_exhaustive]
+pub struct SchematicSummary {
+    /// Format the schematic was loaded from (`Legacy`, `SpongeV2`, `SpongeV3` or `Litematica`)
+    pub format: String,
+    pub width: u32,
+    pub height: u32,
+    pub length: u32,
+    /// `width * height * length`
+    pub volume: usize,
+    /// Position of the minimum corner in the world
+    pub offset: (i32, i32, i32),
+    /// Blocks stored, air included
+    pub total_blocks: usize,
+    /// Blocks that aren't air or technical
+    pub solid_blocks: usize,
+    /// Non-air blocks with an exposed face
+    pub shell_blocks: usize,
+    /// Block count per class (`air`, `technical`, `liquid`, `solid`)
+    pub classes: std::collections::BTreeMap<String, usize>,
+    /// Distinct block names
+    pub unique_types: usize,
+    pub block_entities: usize,
+    pub entities: usize,
+    /// Water source blocks plus waterlogged blocks
+    pub water_sources: usize,
+    pub waterlogged_blocks: usize,
+    /// Counted among the technical blocks too
+    pub structure_voids: usize,
+    pub name: Option<String>,
+    pub author: Option<String>,
+    /// Creation date, milliseconds since the Unix epoch
+    pub date: Option<i64>,
+    pub required_mods: Vec<String>,
+    pub data_version: Option<i32>,
+    /// Most common block names, most common first (ties by name), air included
+    pub top_blocks: Vec<BlockTypeCount>,
+}
+
+/// One row of [`SchematicSummary::top_blocks`]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[non_exhaustive]
+pub struct BlockTypeCount {
+    pub name: String,
+    pub count: usize,
 }
 
 impl UnifiedSchematic {
     /// Load schematic from file, auto-detecting format
+    #[cfg(feature = "fs")]
     pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, SchemError> {
+        Self::load_with_progress(path, &NoProgress)
+    }
+
+    /// Load schematic from file, reporting read/decompress/parse phases
+    #[cfg(feature = "fs")]
+    pub fn load_with_progress<P: AsRef<Path>>(path: P, progress: &dyn Progress) -> Result<Self, SchemError> {
+        Self::load_with_options(path, &LoadOptions::new().with_progress(progress))
+    }
+
+    /// Load schematic from file with explicit options
+    #[cfg(feature = "fs")]
+    pub fn load_with_options<P: AsRef<Path>>(path: P, options: &LoadOptions) -> Result<Self, SchemError> {
         let path = path.as_ref();
+        let progress = options.progress;
+        progress.start(3, "Loading schematic");
 
-        let file = File::open(path)?;
-        let mut reader = BufReader::new(file);
+        if let Some(schematic) = Self::stream(File::open(path)?, options)? {
+            return Ok(schematic);
+        }
 
-        // Read first bytes to check if gzipped
         let mut buf = Vec::new();
-        reader.read_to_end(&mut buf)?;
+        BufReader::new(File::open(path)?).read_to_end(&mut buf)?;
+        progress.set_position(1);
+        progress::check_cancel(progress)?;
+
+        let mut schematic = Self::decode(&buf, options)?;
+        schematic.load_report.buffered = true;
+        Ok(schematic)
+    }
+
+    /// Save as a single-region .litematic (see [`Litematica::from_unified`]),
+    /// with a rendered preview for Litematica's schematic browser
+    #[cfg(feature = "fs")]
+    pub fn save_litematic<P: AsRef<Path>>(&self, path: P) -> Result<(), SchemError> {
+        let preview = preview::PreviewImage::render(self, preview::PREVIEW_SIZE, |block| export3d::get_block_color(&block.name));
+        self.save_litematic_with_preview(path, Some(&preview))
+    }
 
+    /// Save as a single-region .litematic showing `preview` in the schematic
+    /// browser, or no preview at all
+    #[cfg(feature = "fs")]
+    pub fn save_litematic_with_preview<P: AsRef<Path>>(&self, path: P, preview: Option<&preview::PreviewImage>) -> Result<(), SchemError> {
+        let path = path.as_ref();
+        let mut litematic = Litematica::from_unified(self);
+        if let Some(preview) = preview {
+            litematic = litematic.with_preview(preview);
+        }
+        std::fs::write(path, litematic.to_bytes()?).map_err(|e| SchemError::export_io(path, e))
+    }
+
+    /// Save as a Sponge v3 .schem (see [`SchemWrapper::from_unified`](schem::SchemWrapper::from_unified))
+    #[cfg(feature = "fs")]
+    pub fn save_schem<P: AsRef<Path>>(&self, path: P) -> Result<(), SchemError> {
+        let path = path.as_ref();
+        let bytes = schem::SchemWrapper::from_unified(self)?.to_bytes()?;
+        std::fs::write(path, bytes).map_err(|e| SchemError::export_io(path, e))
+    }
+
+    /// Save as a vanilla structure .nbt (see [`StructureNbt::from_unified`](structure_nbt::StructureNbt::from_unified))
+    #[cfg(feature = "fs")]
+    pub fn save_structure<P: AsRef<Path>>(&self, path: P) -> Result<(), SchemError> {
+        let path = path.as_ref();
+        let bytes = structure_nbt::StructureNbt::from_unified(self).to_bytes()?;
+        std::fs::write(path, bytes).map_err(|e| SchemError::export_io(path, e))
+    }
+
+    /// Parse schematic file contents (gzipped or raw NBT), auto-detecting format
+    ///
+    /// This is the entry point for callers without filesystem access, e.g. wasm.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SchemError> {
+        Self::from_bytes_with_options(bytes, &LoadOptions::new())
+    }
+
+    /// Parse schematic file contents with explicit options
+    pub fn from_bytes_with_options(bytes: &[u8], options: &LoadOptions) -> Result<Self, SchemError> {
+        if let Some(schematic) = Self::stream(bytes, options)? {
+            return Ok(schematic);
+        }
+        let mut schematic = Self::decode(bytes, options)?;
+        schematic.load_report.buffered = true;
+        Ok(schematic)
+    }
+
+    /// Decompressed contents of a source, telling gzip apart by its first two bytes
+    fn open_nbt<'a>(source: impl Read + 'a) -> std::io::Result<Box<dyn Read + 'a>> {
+        let mut reader = BufReader::new(source);
+        Ok(if reader.fill_buf()?.starts_with(&[0x1f, 0x8b]) {
+            Box::new(GzDecoder::new(reader))
+        } else {
+            Box::new(reader)
+        })
+    }
+
+    /// Parse without holding the whole file: the format is detected from the
+    /// first kilobyte of decompressed NBT and the rest streams into its parser,
+    /// which sets unrecognised fields aside as it goes.
+    ///
+    /// Returns None when the prefix doesn't identify the format or the stream
+    /// doesn't parse as it, leaving the caller to try every format on a buffer.
+    fn stream(source: impl Read, options: &LoadOptions) -> Result<Option<Self>, SchemError> {
+        let progress = options.progress;
+        let mut reader = Self::open_nbt(source)?;
+        let mut prefix = Vec::with_capacity(probe::PROBE_LEN);
+        reader.by_ref().take(probe::PROBE_LEN as u64).read_to_end(&mut prefix)?;
+        let Some(format) = probe::detect(&prefix) else { return Ok(None) };
+        log::debug!("Streaming {:?} data, detected from the first {} bytes", format, prefix.len());
+        progress.set_position(1);
+        progress::check_cancel(progress)?;
+
+        let parsed = || {
+            progress.set_position(2);
+            progress::check_cancel(progress)
+        };
+        let schematic = Self::parse_as(format, prefix.as_slice().chain(reader), parsed, options)?;
+        if schematic.is_some() {
+            progress.finish("Loaded schematic");
+        }
+        Ok(schematic)
+    }
+
+    /// Parse decompressed NBT as one format, or None if it doesn't parse as it.
+    /// Unrecognised fields are collected in the same pass, and `parsed` runs
+    /// between parsing and conversion.
+    fn parse_as(
+        format: Detected,
+        nbt: impl Read,
+        parsed: impl FnOnce() -> Result<(), SchemError>,
+        options: &LoadOptions,
+    ) -> Result<Option<Self>, SchemError> {
+        let schematic = match format {
+            Detected::Litematica => {
+                let Some((lit, raw_extra)) = extra_nbt::parse::<Litematica>(nbt) else { return Ok(None) };
+                parsed()?;
+                lit.to_unified_with(options.keep_out_of_bounds)?.with_raw_extra(raw_extra)
+            }
+            Detected::SpongeWrapped => {
+                let Some((schem, raw_extra)) = extra_nbt::parse_wrapped::<Schem>(nbt) else { return Ok(None) };
+                parsed()?;
+                schem.to_unified_with(options.lenient)?.with_raw_extra(raw_extra)
+            }
+            Detected::Sponge => {
+                let Some((schem, raw_extra)) = extra_nbt::parse::<Schem>(nbt) else { return Ok(None) };
+                parsed()?;
+                schem.to_unified_with(options.lenient)?.with_raw_extra(raw_extra)
+            }
+            Detected::Legacy => {
+                let Some((schematic, raw_extra)) = extra_nbt::parse::<Schematic>(nbt) else { return Ok(None) };
+                parsed()?;
+                schematic.to_unified()?.with_raw_extra(raw_extra)
+            }
+        };
+        Ok(Some(schematic.with_checked_block_entities()))
+    }
+
+    /// Decompress if needed and parse; reports phases 2 and 3 of loading
+    fn decode(buf: &[u8], options: &LoadOptions) -> Result<Self, SchemError> {
+        let progress = options.progress;
+        let decompressed;
         let data = if buf.starts_with(&[0x1f, 0x8b]) {
             // GZIP compressed
-            let mut decoder = GzDecoder::new(&buf[..]);
-            let mut decompressed = Vec::new();
-            decoder.read_to_end(&mut decompressed)?;
-            decompressed
+            let mut decoder = GzDecoder::new(buf);
+            let mut out = Vec::new();
+            decoder.read_to_end(&mut out)?;
+            decompressed = out;
+            &decompressed[..]
         } else {
             buf
         };
+        progress.set_position(2);
+        progress::check_cancel(progress)?;
 
-        // Try to detect format from content, not just extension
-        // Order matters: try more specific formats first
-
-        // 1. Try Litematica (has "Regions" and "Metadata" fields)
-        if let Ok(lit) = fastnbt::from_bytes::<Litematica>(&data) {
-            return Ok(lit.into());
-        }
+        let schematic = Self::parse_nbt(data, options)?;
+        progress.finish("Loaded schematic");
+        Ok(schematic)
+    }
 
-        // 2. Try Sponge v3 wrapped format (root "Schematic" compound)
-        if let Ok(wrapped) = fastnbt::from_bytes::<schem::SchemWrapper>(&data) {
-            return Ok(wrapped.schematic.into());
-        }
+    /// Detect the format of decompressed NBT data and convert it
+    fn parse_nbt(data: &[u8], options: &LoadOptions) -> Result<Self, SchemError> {
+        let attempt = |format: Detected| {
+            // wasm32 has no clock to read, so attempts there go untimed
+            let started = cfg!(not(target_arch = "wasm32")).then(std::time::Instant::now);
+            let schematic = Self::parse_as(format, data, || Ok(()), options)?;
+            if let Some(started) = started {
+                let outcome = if schematic.is_some() { "parsed" } else { "didn't parse" };
+                log::debug!("{:?} attempt {} in {:.2?}", format, outcome, started.elapsed());
+            }
+            Ok::<_, SchemError>(schematic)
+        };
 
-        // 3. Try Sponge v2/v3 direct format
-        if let Ok(schem) = fastnbt::from_bytes::<Schem>(&data) {
-            return Ok(schem.into());
+        // The root field names usually settle the format, so only one parser runs
+        let detected = probe::detect(data);
+        if let Some(format) = detected {
+            log::debug!("Root fields identify {:?} data", format);
+            if let Some(schematic) = attempt(format)? {
+                return Ok(schematic);
+            }
         }
 
-        // 4. Try legacy .schematic format
-        if let Ok(schematic) = fastnbt::from_bytes::<Schematic>(&data) {
-            return Ok(schematic.into());
+        // Otherwise try each format in turn, more specific formats first
+        log::debug!("Root fields are inconclusive, trying each format");
+        for format in [Detected::Litematica, Detected::SpongeWrapped, Detected::Sponge, Detected::Legacy] {
+            if Some(format) == detected {
+                continue;
+            }
+            if let Some(schematic) = attempt(format)? {
+                return Ok(schematic);
+            }
         }
 
         Err(SchemError::UnknownFormat)
     }
 
-    /// Get block at position
-    pub fn get_block(&self, x: u16, y: u16, z: u16) -> Option<&Block> {
+    fn with_raw_extra(mut self, raw_extra: Option<fastnbt::Value>) -> Self {
+        self.metadata.raw_extra = raw_extra;
+        self
+    }
+
+    /// Drop all but the last block entity at each position and count the
+    /// ones left at air, into the load report
+    fn with_checked_block_entities(mut self) -> Self {
+        let mut seen = std::collections::HashSet::new();
+        let before = self.block_entities.len();
+        // Walk backwards so the last block entity at a position is the one kept
+        self.block_entities.reverse();
+        self.block_entities.retain(|be| seen.insert(be.pos));
+        self.block_entities.reverse();
+        self.load_report.duplicate_block_entities = before - self.block_entities.len();
+        self.load_report.orphaned_block_entities = self.block_entities.iter()
+            .filter(|be| self.get_block_at(be.pos).is_none_or(Block::is_air))
+            .count();
+        self
+    }
+
+    /// Block at a local position given as block entity coordinates
+    fn get_block_at(&self, (x, y, z): (i32, i32, i32)) -> Option<&Block> {
+        let (x, y, z) = (u32::try_from(x).ok()?, u32::try_from(y).ok()?, u32::try_from(z).ok()?);
+        self.get_block(x, y, z)
+    }
+
+    /// What loading worked around: duplicate and orphaned block entities,
+    /// recovered blocks, dropped entities and the buffered fallback
+    pub fn load_report(&self) -> &LoadReport {
+        &self.load_report
+    }
+
+    /// Index of a position in the YZX block array
+    fn index(&self, x: u32, y: u32, z: u32) -> Option<usize> {
         if x >= self.width || y >= self.height || z >= self.length {
             return None;
         }
-        let index = (y as usize * self.length as usize + z as usize) * self.width as usize + x as usize;
-        self.blocks.get(index)
+        (y as usize)
+            .checked_mul(self.length as usize)?
+            .checked_add(z as usize)?
+            .checked_mul(self.width as usize)?
+            .checked_add(x as usize)
+    }
+
+    /// Get block at position
+    pub fn get_block(&self, x: u32, y: u32, z: u32) -> Option<&Block> {
+        self.index(x, y, z).and_then(|i| self.blocks.get(i))
+    }
+
+    /// Get the block next to a position in `direction`, or None past the edge
+    pub fn get_neighbor(&self, (x, y, z): (u32, u32, u32), direction: direction::Direction) -> Option<&Block> {
+        let (dx, dy, dz) = direction.to_offset();
+        self.get_block(x.checked_add_signed(dx)?, y.checked_add_signed(dy)?, z.checked_add_signed(dz)?)
+    }
+
+    /// Get block at a world position (local position plus `offset`)
+    pub fn get_block_world(&self, x: i32, y: i32, z: i32) -> Option<&Block> {
+        self.to_local(x, y, z).and_then(|(x, y, z)| self.get_block(x, y, z))
+    }
+
+    /// Convert a world position to local coordinates, or None outside the schematic
+    pub fn to_local(&self, x: i32, y: i32, z: i32) -> Option<(u32, u32, u32)> {
+        let local = |v: i32, offset: i32, size: u32| {
+            let v = v as i64 - offset as i64;
+            (0..size as i64).contains(&v).then_some(v as u32)
+        };
+        Some((
+            local(x, self.offset.0, self.width)?,
+            local(y, self.offset.1, self.height)?,
+            local(z, self.offset.2, self.length)?,
+        ))
+    }
+
+    /// Every block with its position, in YZX order (x fastest)
+    pub fn iter_blocks(&self) -> impl Iterator<Item = ((u32, u32, u32), &Block)> + '_ {
+        let (w, l) = (self.width as usize, self.length as usize);
+        self.blocks.iter().enumerate().map(move |(i, block)| {
+            (((i % w) as u32, (i / (w * l)) as u32, ((i / w) % l) as u32), block)
+        })
+    }
+
+    /// Every position in the inclusive box `min..=max` (local coordinates, which may
+    /// reach outside the schematic) with its block, or None where it falls outside
+    pub fn blocks_in_box(
+        &self,
+        min: (i64, i64, i64),
+        max: (i64, i64, i64),
+    ) -> impl Iterator<Item = ((i64, i64, i64), Option<&Block>)> + '_ {
+        (min.1..=max.1).flat_map(move |y| {
+            (min.2..=max.2).flat_map(move |z| {
+                (min.0..=max.0).map(move |x| ((x, y, z), self.get_block_signed(x, y, z)))
+            })
+        })
+    }
+
+    /// The inclusive box `min..=max` (local coordinates, clamped to the schematic)
+    /// as a schematic of its own, with the block entities and entities inside it.
+    /// The offset moves with the box, so world positions stay the same.
+    pub fn crop(&self, min: (u32, u32, u32), max: (u32, u32, u32)) -> UnifiedSchematic {
+        let size = |lo: u32, hi: u32, extent: u32| if lo >= extent || lo > hi { 0 } else { hi.min(extent - 1) - lo + 1 };
+        let (width, height, length) = (size(min.0, max.0, self.width), size(min.1, max.1, self.height), size(min.2, max.2, self.length));
+
+        let mut blocks = Vec::with_capacity(width as usize * height as usize * length as usize);
+        for y in min.1..min.1 + height {
+            for z in min.2..min.2 + length {
+                for x in min.0..min.0 + width {
+                    blocks.push(self.get_block(x, y, z).cloned().unwrap_or_else(Block::air));
+                }
+            }
+        }
+        let inside = |pos: [f64; 3]| {
+            [(min.0, width), (min.1, height), (min.2, length)].iter().zip(pos)
+                .all(|(&(lo, len), p)| p >= lo as f64 && p < (lo + len) as f64)
+        };
+        let block_entities = self.block_entities.iter()
+            .filter(|be| inside([be.pos.0 as f64, be.pos.1 as f64, be.pos.2 as f64]))
+            .map(|be| BlockEntity { pos: (be.pos.0 - min.0 as i32, be.pos.1 - min.1 as i32, be.pos.2 - min.2 as i32), ..be.clone() })
+            .collect();
+        let entities = self.entities.iter()
+            .filter(|e| inside([e.pos.0, e.pos.1, e.pos.2]))
+            .map(|e| Entity { pos: (e.pos.0 - min.0 as f64, e.pos.1 - min.1 as f64, e.pos.2 - min.2 as f64), ..e.clone() })
+            .collect();
+
+        UnifiedSchematic {
+            format: self.format.clone(),
+            width,
+            height,
+            length,
+            offset: self.to_world(min.0, min.1, min.2),
+            blocks,
+            block_entities,
+            entities,
+            metadata: self.metadata.clone(),
+            load_report: Default::default(),
+        }
+    }
+
+    /// Groups of face-adjacent positions whose blocks match `include`
+    pub fn connected_components<F>(&self, include: F) -> Vec<Vec<(u32, u32, u32)>>
+    where
+        F: Fn((u32, u32, u32), &Block) -> bool,
+    {
+        let (w, l) = (self.width as usize, self.length as usize);
+        let pos = |i: usize| ((i % w) as u32, (i / (w * l)) as u32, ((i / w) % l) as u32);
+
+        let mut member: Vec<bool> = self.blocks.iter().enumerate().map(|(i, b)| include(pos(i), b)).collect();
+        let mut components = Vec::new();
+
+        for start in 0..member.len() {
+            if !member[start] {
+                continue;
+            }
+            member[start] = false;
+            let mut component = Vec::new();
+            let mut stack = vec![start];
+            while let Some(i) = stack.pop() {
+                let (x, y, z) = pos(i);
+                component.push((x, y, z));
+                let neighbors = [
+                    (x > 0).then(|| i - 1),
+                    (x + 1 < self.width).then(|| i + 1),
+                    (z > 0).then(|| i - w),
+                    (z + 1 < self.length).then(|| i + w),
+                    (y > 0).then(|| i - w * l),
+                    (y + 1 < self.height).then(|| i + w * l),
+                ];
+                for n in neighbors.into_iter().flatten() {
+                    if member[n] {
+                        member[n] = false;
+                        stack.push(n);
+                    }
+                }
+            }
+            components.push(component);
+        }
+        components
+    }
+
+    /// Copy keeping only blocks within `thickness` layers of an exposed face,
+    /// with the interior turned to air
+    ///
+    /// The outer layer is the blocks with an exposed face (see
+    /// [`block_geometry::is_exposed`]); each further layer is the non-air
+    /// blocks face-adjacent to the one before. Block entities of removed blocks
+    /// are dropped, entities are kept. A thickness of 0 keeps nothing.
+    pub fn shell(&self, thickness: u8) -> UnifiedSchematic {
+        let (w, l) = (self.width as usize, self.length as usize);
+        let pos = |i: usize| ((i % w) as u32, (i / (w * l)) as u32, ((i / w) % l) as u32);
+
+        // Layer of each block counted from the outside, by breadth-first search inward
+        let mut layer: Vec<Option<u8>> = vec![None; self.blocks.len()];
+        let mut queue = std::collections::VecDeque::new();
+        for (i, block) in self.blocks.iter().enumerate() {
+            let (x, y, z) = pos(i);
+            if !block.is_air() && block_geometry::is_exposed(self, x, y, z) {
+                layer[i] = Some(0);
+                queue.push_back(i);
+            }
+        }
+        while let Some(i) = queue.pop_front() {
+            let next = layer[i].unwrap_or(0) + 1;
+            if next >= thickness {
+                continue;
+            }
+            let (x, y, z) = pos(i);
+            let neighbors = [
+                (x > 0).then(|| i - 1),
+                (x + 1 < self.width).then(|| i + 1),
+                (z > 0).then(|| i - w),
+                (z + 1 < self.length).then(|| i + w),
+                (y > 0).then(|| i - w * l),
+                (y + 1 < self.height).then(|| i + w * l),
+            ];
+            for n in neighbors.into_iter().flatten() {
+                if layer[n].is_none() && !self.blocks[n].is_air() {
+                    layer[n] = Some(next);
+                    queue.push_back(n);
+                }
+            }
+        }
+
+        let interior: Vec<_> = self.blocks.iter().enumerate()
+            .filter(|&(i, block)| !block.is_air() && layer[i].is_none_or(|depth| depth >= thickness))
+            .map(|(i, _)| (pos(i), Block::air()))
+            .collect();
+        let mut shell = self.clone();
+        shell.set_blocks(interior, BlockEntityPolicy::Remove).expect("positions come from the schematic");
+        shell
+    }
+
+    /// Block at a signed local position, None outside the schematic
+    fn get_block_signed(&self, x: i64, y: i64, z: i64) -> Option<&Block> {
+        let (x, y, z) = (u32::try_from(x).ok()?, u32::try_from(y).ok()?, u32::try_from(z).ok()?);
+        self.get_block(x, y, z)
+    }
+
+    /// Convert local coordinates to a world position
+    pub fn to_world(&self, x: u32, y: u32, z: u32) -> (i32, i32, i32) {
+        (
+            self.offset.0.wrapping_add(x as i32),
+            self.offset.1.wrapping_add(y as i32),
+            self.offset.2.wrapping_add(z as i32),
+        )
+    }
+
+    /// Where local block (0, 0, 0) lands relative to the point a paste is
+    /// anchored at (the player, for WorldEdit and Litematica)
+    ///
+    /// This is `WEOffset` when the file has one, otherwise `offset` for Sponge v3
+    /// and Litematica, which store it relative to the paste origin, and (0, 0, 0)
+    /// for Sponge v2 and legacy files, which WorldEdit pastes from their minimum corner.
+    pub fn paste_offset(&self) -> (i32, i32, i32) {
+        match (self.metadata.we_offset, &self.format) {
+            (Some(offset), _) => offset,
+            (None, SchematicFormat::SpongeV3 | SchematicFormat::Litematica) => self.offset,
+            (None, SchematicFormat::SpongeV2 | SchematicFormat::Legacy) => (0, 0, 0),
+        }
+    }
+
+    /// Anchor pastes at local position `anchor` without moving any blocks
+    ///
+    /// Sets `metadata.we_offset` and `offset` to `-anchor`; the writers store
+    /// them as `WEOffset`, the Sponge `Offset` and the Litematica region
+    /// position. The world position in `offset` is replaced, since Sponge v3
+    /// and Litematica keep the paste anchor in its place.
+    pub fn set_paste_anchor(&mut self, anchor: (i32, i32, i32)) {
+        let offset = (anchor.0.saturating_neg(), anchor.1.saturating_neg(), anchor.2.saturating_neg());
+        self.metadata.we_offset = Some(offset);
+        self.metadata.offset = self.metadata.offset.map(|_| offset);
+        self.offset = offset;
+    }
+
+    /// Get mutable block at position
+    ///
+    /// Editing through this reference bypasses block entity bookkeeping;
+    /// prefer `set_block` when replacing the block type.
+    pub fn get_block_mut(&mut self, x: u32, y: u32, z: u32) -> Option<&mut Block> {
+        self.index(x, y, z).and_then(move |i| self.blocks.get_mut(i))
+    }
+
+    /// Replace the block at a position, dropping a block entity there if the block type changes
+    pub fn set_block(&mut self, x: u32, y: u32, z: u32, block: Block) -> Result<(), SchemError> {
+        self.set_block_with_policy(x, y, z, block, BlockEntityPolicy::default())
+    }
+
+    /// Replace the block at a position, handling its block entity according to `policy`
+    pub fn set_block_with_policy(
+        &mut self,
+        x: u32, y: u32, z: u32,
+        block: Block,
+        policy: BlockEntityPolicy,
+    ) -> Result<(), SchemError> {
+        let index = self.index(x, y, z)
+            .ok_or(SchemError::OutOfBounds(x as i64, y as i64, z as i64))?;
+        let name_changed = self.blocks[index].name != block.name;
+        self.blocks[index] = block;
+
+        if policy.removes(name_changed) {
+            let pos = (x as i32, y as i32, z as i32);
+            self.block_entities.retain(|be| be.pos != pos);
+        }
+        Ok(())
+    }
+
+    /// Replace many blocks at once, returning how many were set
+    ///
+    /// All positions are validated before anything is modified, and block
+    /// entities are pruned in a single pass at the end.
+    pub fn set_blocks<I>(&mut self, blocks: I, policy: BlockEntityPolicy) -> Result<usize, SchemError>
+    where
+        I: IntoIterator<Item = ((u32, u32, u32), Block)>,
+    {
+        let mut updates = Vec::new();
+        for ((x, y, z), block) in blocks {
+            let index = self.index(x, y, z)
+                .ok_or(SchemError::OutOfBounds(x as i64, y as i64, z as i64))?;
+            updates.push((index, (x as i32, y as i32, z as i32), block));
+        }
+
+        let mut removed = std::collections::HashSet::new();
+        for (index, pos, block) in &mut updates {
+            let name_changed = self.blocks[*index].name != block.name;
+            if policy.removes(name_changed) {
+                removed.insert(*pos);
+            }
+            self.blocks[*index] = std::mem::replace(block, Block::air());
+        }
+
+        if !removed.is_empty() {
+            self.block_entities.retain(|be| !removed.contains(&be.pos));
+        }
+        Ok(updates.len())
+    }
+
+    /// Replace every block `replace` returns a substitute for, handling block
+    /// entities according to `policy`
+    ///
+    /// Returns how many blocks were replaced, by old and new block name.
+    pub fn replace_blocks<F>(&mut self, policy: BlockEntityPolicy, mut replace: F) -> std::collections::BTreeMap<(String, String), usize>
+    where
+        F: FnMut(&Block) -> Option<Block>,
+    {
+        let mut counts = std::collections::BTreeMap::new();
+        let updates: Vec<_> = self.iter_blocks()
+            .filter_map(|(pos, block)| {
+                let substitute = replace(block)?;
+                *counts.entry((block.name.clone(), substitute.name.clone())).or_insert(0) += 1;
+                Some((pos, substitute))
+            })
+            .collect();
+        self.set_blocks(updates, policy).expect("positions come from the schematic");
+        counts
+    }
+
+    /// Swap the blocks (and any block entities) at two positions
+    pub fn swap_blocks(&mut self, a: (u32, u32, u32), b: (u32, u32, u32)) -> Result<(), SchemError> {
+        let ia = self.index(a.0, a.1, a.2)
+            .ok_or(SchemError::OutOfBounds(a.0 as i64, a.1 as i64, a.2 as i64))?;
+        let ib = self.index(b.0, b.1, b.2)
+            .ok_or(SchemError::OutOfBounds(b.0 as i64, b.1 as i64, b.2 as i64))?;
+        self.blocks.swap(ia, ib);
+
+        let pa = (a.0 as i32, a.1 as i32, a.2 as i32);
+        let pb = (b.0 as i32, b.1 as i32, b.2 as i32);
+        for be in &mut self.block_entities {
+            if be.pos == pa {
+                be.pos = pb;
+            } else if be.pos == pb {
+                be.pos = pa;
+            }
+        }
+        Ok(())
     }
 
     /// Count blocks by type
     pub fn block_counts(&self) -> std::collections::HashMap<String, usize> {
+        // Count runs of the same name under borrowed keys; only the distinct
+        // names are allocated at the end
+        let mut counts: std::collections::HashMap<&str, usize> = std::collections::HashMap::new();
+        let mut blocks = self.blocks.iter().map(|b| b.name.as_str()).peekable();
+        while let Some(name) = blocks.next() {
+            let mut run = 1;
+            while blocks.next_if_eq(&name).is_some() {
+                run += 1;
+            }
+            *counts.entry(name).or_insert(0) += run;
+        }
+        counts.into_iter().map(|(name, count)| (name.to_string(), count)).collect()
+    }
+
+    /// Non-air blocks with at least one exposed face (see [`block_geometry::is_exposed`])
+    pub fn surface_blocks(&self) -> impl Iterator<Item = &Block> + '_ {
+        self.iter_blocks()
+            .filter(|((x, y, z), block)| !block.is_air() && block_geometry::is_exposed(self, *x, *y, *z))
+            .map(|(_, block)| block)
+    }
+
+    /// Number of blocks in the visible shell
+    pub fn shell_blocks(&self) -> usize {
+        self.surface_blocks().count()
+    }
+
+    /// Number of waterlogged blocks, each holding one water source
+    pub fn waterlogged_blocks(&self) -> usize {
+        self.blocks.iter().filter(|b| b.state.is_waterlogged()).count()
+    }
+
+    /// Water sources: water source blocks (`level=0` or unset) plus waterlogged blocks
+    pub fn water_sources(&self) -> usize {
+        self.blocks.iter()
+            .filter(|b| {
+                b.state.is_waterlogged()
+                    || (b.display_name() == "water" && b.state.get_int("level").is_none_or(|level| level == 0))
+            })
+            .count()
+    }
+
+    /// Count blocks as the items needed to place them (see [`Block::item_form`]),
+    /// matching Litematica's material list
+    pub fn item_counts(&self) -> std::collections::HashMap<String, usize> {
         let mut counts = std::collections::HashMap::new();
         for block in &self.blocks {
-            *counts.entry(block.name.clone()).or_insert(0) += 1;
+            if let Some((item, count)) = block.item_form() {
+                *counts.entry(item).or_insert(0) += count;
+            }
         }
         counts
     }
@@ -294,9 +1435,19 @@ impl UnifiedSchematic {
     pub fn unique_blocks(&self) -> Vec<&Block> {
         let mut seen = std::collections::HashSet::new();
         let mut unique = Vec::new();
+        let mut previous: Option<&Block> = None;
         for block in &self.blocks {
-            let key = format!("{}{:?}", block.name, block.state);
-            if seen.insert(key) {
+            // Neighbors are often the same block, which needs no key at all
+            if previous == Some(block) {
+                continue;
+            }
+            previous = Some(block);
+            let mut properties: Vec<(&str, &str)> = block.state.properties
+                .iter()
+                .map(|(k, v)| (k.as_str(), v.as_str()))
+                .collect();
+            properties.sort_unstable();
+            if seen.insert((block.name.as_str(), properties)) {
                 unique.push(block);
             }
         }
@@ -308,18 +1459,74 @@ impl UnifiedSchematic {
         format!("{}x{}x{}", self.width, self.height, self.length)
     }
 
-    /// Total volume
+    /// Total volume (saturating; equals `blocks.len()` for a consistent schematic)
     pub fn volume(&self) -> usize {
-        self.width as usize * self.height as usize * self.length as usize
+        (self.width as usize)
+            .saturating_mul(self.height as usize)
+            .saturating_mul(self.length as usize)
     }
 
-    /// Non-air block count
+    /// Count of blocks that aren't air or technical (see [`BlockClass`])
     pub fn solid_blocks(&self) -> usize {
         self.blocks.iter()
-            .filter(|b| !b.is_air())
+            .filter(|b| !matches!(b.class(), BlockClass::Air | BlockClass::Technical))
             .count()
     }
 
+    /// Block count per [`BlockClass`]
+    pub fn class_counts(&self) -> std::collections::BTreeMap<BlockClass, usize> {
+        let mut counts = std::collections::BTreeMap::new();
+        for block in &self.blocks {
+            *counts.entry(block.class()).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Dimensions, counts, metadata and the `top_n` most common block types
+    /// in one struct (see [`SchematicSummary`])
+    pub fn summary(&self, top_n: usize) -> SchematicSummary {
+        let counts = self.block_counts();
+        let mut top_blocks: Vec<BlockTypeCount> = counts.iter()
+            .map(|(name, &count)| BlockTypeCount { name: name.clone(), count })
+            .collect();
+        top_blocks.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+        top_blocks.truncate(top_n);
+
+        SchematicSummary {
+            format: format!("{:?}", self.format),
+            width: self.width,
+            height: self.height,
+            length: self.length,
+            volume: self.volume(),
+            offset: self.offset,
+            total_blocks: self.blocks.len(),
+            solid_blocks: self.solid_blocks(),
+            shell_blocks: self.shell_blocks(),
+            classes: self.class_counts().into_iter().map(|(class, count)| (class.name().to_string(), count)).collect(),
+            unique_types: counts.len(),
+            block_entities: self.block_entities.len(),
+            entities: self.entities.len(),
+            water_sources: self.water_sources(),
+            waterlogged_blocks: self.waterlogged_blocks(),
+            structure_voids: self.structure_voids(),
+            name: self.metadata.name.clone(),
+            author: self.metadata.author.clone(),
+            date: self.metadata.date,
+            required_mods: self.metadata.required_mods.clone(),
+            data_version: self.metadata.data_version,
+            top_blocks,
+        }
+    }
+
+    /// Entities grouped by id
+    pub fn entities_by_type(&self) -> std::collections::HashMap<String, Vec<&Entity>> {
+        let mut groups: std::collections::HashMap<String, Vec<&Entity>> = std::collections::HashMap::new();
+        for entity in &self.entities {
+            groups.entry(entity.id.clone()).or_default().push(entity);
+        }
+        groups
+    }
+
     /// Get all signs with their text
     pub fn get_signs(&self) -> Vec<(&BlockEntity, SignText)> {
         self.block_entities.iter()
@@ -328,4 +1535,382 @@ impl UnifiedSchematic {
             })
             .collect()
     }
+
+    /// All jigsaw blocks with their settings
+    pub fn get_jigsaws(&self) -> Vec<(&BlockEntity, JigsawInfo)> {
+        self.block_entities.iter()
+            .filter_map(|be| be.get_jigsaw().map(|jigsaw| (be, jigsaw)))
+            .collect()
+    }
+
+    /// Number of structure voids
+    pub fn structure_voids(&self) -> usize {
+        self.blocks.iter().filter(|b| b.is_structure_void()).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_mob_info_from_nbt() {
+        use fastnbt::Value;
+        let string = |s: &str| Value::String(s.to_string());
+
+        let villager = Entity {
+            id: "minecraft:villager".to_string(),
+            pos: (0.5, 64.0, 0.5),
+            nbt: HashMap::from([
+                ("CustomName".to_string(), string(r#"{"text":"Bob"}"#)),
+                ("NoAI".to_string(), Value::Byte(1)),
+                ("Health".to_string(), Value::Float(20.0)),
+                ("VillagerData".to_string(), Value::Compound(HashMap::from([
+                    ("level".to_string(), Value::Int(2)),
+                    ("profession".to_string(), string("minecraft:farmer")),
+                    ("type".to_string(), string("minecraft:plains")),
+                ]))),
+            ]),
+            ..Default::default()
+        };
+        assert_eq!(villager.mob_info(), MobInfo {
+            custom_name: Some("Bob".to_string()),
+            no_ai: true,
+            health: Some(20.0),
+            profession: Some("minecraft:farmer".to_string()),
+        });
+
+        // 1.21.5+ compound names; a text `data` entry alone isn't read
+        let zombie = Entity {
+            id: "minecraft:zombie_villager".to_string(),
+            nbt: HashMap::from([
+                ("CustomName".to_string(), Value::Compound(HashMap::from([("text".to_string(), string("Gus"))]))),
+                ("NoAI".to_string(), Value::Byte(0)),
+                ("Health".to_string(), Value::Float(12.5)),
+                ("VillagerData".to_string(), Value::Compound(HashMap::from([
+                    ("profession".to_string(), string("minecraft:mason")),
+                ]))),
+            ]),
+            data: HashMap::from([("NoAI".to_string(), "1".to_string())]),
+            ..Default::default()
+        };
+        assert_eq!(zombie.mob_info(), MobInfo {
+            custom_name: Some("Gus".to_string()),
+            no_ai: false,
+            health: Some(12.5),
+            profession: Some("minecraft:mason".to_string()),
+        });
+
+        let item = Entity { id: "minecraft:item".to_string(), ..Default::default() };
+        assert!(item.mob_info().is_empty());
+    }
+
+    #[test]
+    fn test_spawner_layouts() {
+        use fastnbt::Value;
+        let spawner = |nbt: &[(&str, Value)]| {
+            let mut be = BlockEntity { id: "minecraft:mob_spawner".to_string(), ..Default::default() };
+            be.set_nbt(nbt.iter().map(|(k, v)| (k.to_string(), v.clone())).collect());
+            be
+        };
+        let compound = |fields: &[(&str, Value)]| {
+            Value::Compound(fields.iter().map(|(k, v)| (k.to_string(), v.clone())).collect())
+        };
+        let id = |id: &str| ("id", Value::String(id.to_string()));
+        let potential = |weight: &str| compound(&[(weight, Value::Int(1)), ("data", compound(&[]))]);
+
+        // 1.18+
+        let modern = spawner(&[
+            ("SpawnData", compound(&[("entity", compound(&[
+                id("minecraft:zombie"),
+                ("uuid", Value::IntArray(fastnbt::IntArray::new(vec![1, 2, 3, 4]))),
+            ]))])),
+            ("Delay", Value::Short(20)),
+            ("MinSpawnDelay", Value::Short(200)),
+            ("MaxSpawnDelay", Value::Short(800)),
+            ("SpawnCount", Value::Short(4)),
+            ("RequiredPlayerRange", Value::Short(16)),
+            ("SpawnPotentials", Value::List(vec![potential("weight"), potential("weight")])),
+        ]).get_spawner().unwrap();
+        assert_eq!(modern.entity.as_deref(), Some("minecraft:zombie"));
+        assert_eq!((modern.min_spawn_delay, modern.max_spawn_delay), (Some(200), Some(800)));
+        assert_eq!(modern.required_player_range, Some(16));
+        assert_eq!(modern.potentials, 2);
+
+        // Pre-1.18
+        let legacy = spawner(&[
+            ("SpawnData", compound(&[id("minecraft:skeleton")])),
+            ("SpawnCount", Value::Short(2)),
+            ("SpawnPotentials", Value::List(vec![potential("Weight")])),
+        ]).get_spawner().unwrap();
+        assert_eq!(legacy.entity.as_deref(), Some("minecraft:skeleton"));
+        assert_eq!(legacy.spawn_count, Some(2));
+        assert_eq!(legacy.potentials, 1);
+
+        // Pre-1.9
+        let ancient = spawner(&[("EntityId", Value::String("Creeper".to_string())), ("Delay", Value::Short(5))]).get_spawner().unwrap();
+        assert_eq!((ancient.entity.as_deref(), ancient.delay), (Some("Creeper"), Some(5)));
+
+        assert!(BlockEntity { id: "minecraft:trial_spawner".to_string(), ..Default::default() }.get_spawner().is_none());
+    }
+
+    #[test]
+    fn test_raw_extra_round_trip() {
+        let mut builder = SchematicBuilder::new(2, 1, 1);
+        builder.set_block(1, 0, 0, Block::new("minecraft:stone")).unwrap();
+        let built = builder.build();
+        let editor = fastnbt::Value::Compound(HashMap::from([
+            ("tool".to_string(), fastnbt::Value::String("brush".to_string())),
+        ]));
+        let raw_extra = Some(fastnbt::Value::Compound(HashMap::from([("axiom:editor_metadata".to_string(), editor.clone())])));
+
+        let mut lit = Litematica::from_unified(&built);
+        lit.extra.insert("axiom:editor_metadata".to_string(), editor.clone());
+        let mut sponge = schem::SchemWrapper::from_unified(&built).unwrap();
+        sponge.schematic.extra.insert("axiom:editor_metadata".to_string(), editor);
+        let (lit, sponge) = (lit.to_bytes().unwrap(), sponge.to_bytes().unwrap());
+
+        // Load, save in the same format, load again
+        for bytes in [&lit, &sponge] {
+            let loaded = UnifiedSchematic::from_bytes(bytes).unwrap();
+            assert_eq!(loaded.metadata.raw_extra, raw_extra);
+            let saved = match loaded.format {
+                SchematicFormat::Litematica => Litematica::from_unified(&loaded).to_bytes(),
+                _ => schem::SchemWrapper::from_unified(&loaded).unwrap().to_bytes(),
+            }.unwrap();
+            let reloaded = UnifiedSchematic::from_bytes(&saved).unwrap();
+            assert_eq!(reloaded.metadata.raw_extra, raw_extra);
+            assert_eq!(reloaded.blocks, built.blocks);
+        }
+
+        // Other formats don't carry them over
+        let loaded = UnifiedSchematic::from_bytes(&lit).unwrap();
+        assert!(schem::SchemWrapper::from_unified(&loaded).unwrap().schematic.extra.is_empty());
+        let loaded = UnifiedSchematic::from_bytes(&sponge).unwrap();
+        assert!(Litematica::from_unified(&loaded).extra.is_empty());
+    }
+
+    #[test]
+    fn test_paste_anchor() {
+        let mut builder = SchematicBuilder::new(4, 2, 3);
+        builder.set_block(3, 1, 2, Block::new("minecraft:stone")).unwrap();
+        let mut schem = builder.build();
+        schem.offset = (100, 64, -20);
+        assert_eq!(schem.paste_offset(), (100, 64, -20));
+        schem.format = SchematicFormat::SpongeV2;
+        assert_eq!(schem.paste_offset(), (0, 0, 0));
+
+        schem.set_paste_anchor((2, 0, 1));
+        assert_eq!(schem.paste_offset(), (-2, 0, -1));
+        assert_eq!(schem.get_block(3, 1, 2).unwrap().name, "minecraft:stone");
+
+        // Both writers keep the anchor
+        let lit = Litematica::from_unified(&schem).to_unified().unwrap();
+        assert_eq!(lit.paste_offset(), (-2, 0, -1));
+        assert_eq!(lit.blocks, schem.blocks);
+        let sponge = schem::SchemWrapper::from_unified(&schem).unwrap().schematic.to_unified().unwrap();
+        assert_eq!(sponge.metadata.we_offset, Some((-2, 0, -1)));
+        assert_eq!(sponge.paste_offset(), (-2, 0, -1));
+    }
+
+    #[test]
+    fn test_shell() {
+        // A solid 5x5x5 cube with a chest in the middle
+        let mut builder = SchematicBuilder::new(5, 5, 5);
+        builder.fill((0, 0, 0), (4, 4, 4), Block::new("minecraft:stone")).unwrap();
+        builder.set_block(2, 2, 2, Block::new("minecraft:chest")).unwrap();
+        builder.add_block_entity(BlockEntity { id: "minecraft:chest".to_string(), pos: (2, 2, 2), ..Default::default() }).unwrap();
+        let cube = builder.build();
+
+        // The chest doesn't hide the faces of the six stones around it, so those
+        // count as exposed too
+        let shell = cube.shell(1);
+        assert_eq!(shell.solid_blocks(), 125 - 27 + 6);
+        assert!(shell.get_block(1, 1, 1).unwrap().is_air());
+        assert!(shell.get_block(2, 2, 2).unwrap().is_air());
+        assert!(shell.block_entities.is_empty());
+        assert_eq!(cube.shell(2).solid_blocks(), 125);
+        assert_eq!(cube.shell(2).block_entities.len(), 1);
+        assert_eq!(cube.shell(0).solid_blocks(), 0);
+    }
+
+    #[test]
+    fn test_jigsaw_layouts() {
+        use fastnbt::Value;
+        let jigsaw = |nbt: &[(&str, Value)]| {
+            let mut be = BlockEntity { id: "minecraft:jigsaw".to_string(), ..Default::default() };
+            be.set_nbt(nbt.iter().map(|(k, v)| (k.to_string(), v.clone())).collect());
+            be
+        };
+        let string = |s: &str| Value::String(s.to_string());
+
+        let modern = jigsaw(&[
+            ("name", string("minecraft:building_entrance")),
+            ("target", string("minecraft:street")),
+            ("pool", string("minecraft:village/plains/streets")),
+            ("final_state", string("minecraft:dirt_path")),
+            ("joint", string("aligned")),
+            ("placement_priority", Value::Int(2)),
+        ]).get_jigsaw().unwrap();
+        assert_eq!(modern.pool.as_deref(), Some("minecraft:village/plains/streets"));
+        assert_eq!(modern.joint.as_deref(), Some("aligned"));
+        assert_eq!((modern.placement_priority, modern.selection_priority), (Some(2), None));
+
+        // 1.14-1.15
+        let legacy = jigsaw(&[("attachement_type", string("minecraft:bottom")), ("target_pool", string("minecraft:empty")), ("name", string(""))]).get_jigsaw().unwrap();
+        assert_eq!((legacy.target.as_deref(), legacy.pool.as_deref(), legacy.name), (Some("minecraft:bottom"), Some("minecraft:empty"), None));
+
+        assert!(BlockEntity { id: "minecraft:chest".to_string(), ..Default::default() }.get_jigsaw().is_none());
+    }
+
+    #[test]
+    fn test_block_counts_and_unique_blocks() {
+        let stairs = |props: &[(&str, &str)]| {
+            let properties = props.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+            Block::with_state("minecraft:oak_stairs", BlockState { properties })
+        };
+        let mut builder = SchematicBuilder::new(4, 1, 2);
+        builder.fill((0, 0, 0), (2, 0, 0), Block::new("minecraft:stone")).unwrap();
+        builder.set_block(3, 0, 0, stairs(&[("facing", "east"), ("half", "top")])).unwrap();
+        builder.set_block(0, 0, 1, stairs(&[("half", "top"), ("facing", "east")])).unwrap();
+        builder.set_block(1, 0, 1, stairs(&[("facing", "west"), ("half", "top")])).unwrap();
+        builder.set_block(2, 0, 1, Block::new("minecraft:stone")).unwrap();
+        let schem = builder.build();
+
+        let counts = schem.block_counts();
+        assert_eq!(counts["minecraft:stone"], 4);
+        assert_eq!(counts["minecraft:oak_stairs"], 3);
+        assert_eq!(counts["minecraft:air"], 1);
+
+        let unique: Vec<String> = schem.unique_blocks().iter().map(|b| b.full_name()).collect();
+        assert_eq!(unique, [
+            "minecraft:stone",
+            "minecraft:oak_stairs[facing=east,half=top]",
+            "minecraft:oak_stairs[facing=west,half=top]",
+            "minecraft:air",
+        ]);
+    }
+
+    #[test]
+    fn test_summary() {
+        let mut builder = SchematicBuilder::new(3, 1, 2);
+        builder.fill((0, 0, 0), (2, 0, 0), Block::new("minecraft:stone")).unwrap();
+        builder.set_block(0, 0, 1, Block::new("minecraft:glass")).unwrap();
+        builder.set_block(1, 0, 1, Block::new("minecraft:barrier")).unwrap();
+        let mut schem = builder.build();
+        schem.metadata.set_name("Wall");
+        let summary = schem.summary(2);
+
+        assert_eq!((summary.volume, summary.solid_blocks, summary.unique_types), (6, 4, 4));
+        assert_eq!(summary.classes["technical"], 1);
+        assert_eq!(summary.name.as_deref(), Some("Wall"));
+        let top: Vec<(&str, usize)> = summary.top_blocks.iter().map(|b| (b.name.as_str(), b.count)).collect();
+        assert_eq!(top, [("minecraft:stone", 3), ("minecraft:air", 1)]);
+
+        let json = serde_json::to_value(&summary).unwrap();
+        assert_eq!(json["offset"], serde_json::json!([0, 0, 0]));
+        assert_eq!(json["top_blocks"][0], serde_json::json!({"name": "minecraft:stone", "count": 3}));
+    }
+
+    #[test]
+    fn test_crop() {
+        let mut builder = SchematicBuilder::new(4, 3, 2);
+        builder.set_block(2, 1, 1, Block::new("minecraft:stone")).unwrap();
+        builder.set_block(3, 2, 1, Block::new("minecraft:chest")).unwrap();
+        builder.add_block_entity(BlockEntity { id: "minecraft:chest".to_string(), pos: (3, 2, 1), ..Default::default() }).unwrap();
+        let mut schem = builder.build();
+        schem.offset = (100, 64, -10);
+        schem.entities.push(Entity { id: "minecraft:pig".to_string(), pos: (0.5, 0.0, 0.5), ..Default::default() });
+
+        // The max corner reaches past the schematic and gets clamped
+        let cropped = schem.crop((2, 1, 1), (9, 9, 9));
+        assert_eq!((cropped.width, cropped.height, cropped.length), (2, 2, 1));
+        assert_eq!(cropped.blocks.len(), 4);
+        assert_eq!(cropped.get_block(0, 0, 0).unwrap().name, "minecraft:stone");
+        assert_eq!(cropped.to_world(0, 0, 0), schem.to_world(2, 1, 1));
+        assert_eq!(cropped.block_entities[0].pos, (1, 1, 0));
+        assert!(cropped.entities.is_empty());
+        assert_eq!(schem.crop((5, 0, 0), (6, 2, 1)).volume(), 0);
+    }
+
+    #[test]
+    fn test_checked_block_entities() {
+        let chest = |pos, name: &str| {
+            let data = HashMap::from([("CustomName".to_string(), name.to_string())]);
+            BlockEntity { id: "minecraft:chest".to_string(), pos, data, ..Default::default() }
+        };
+        let mut builder = SchematicBuilder::new(3, 1, 1);
+        builder.set_block(0, 0, 0, Block::new("minecraft:chest")).unwrap();
+        builder.add_block_entity(chest((0, 0, 0), "first")).unwrap();
+        builder.add_block_entity(chest((1, 0, 0), "orphan")).unwrap();
+        builder.add_block_entity(chest((0, 0, 0), "second")).unwrap();
+        let mut schem = builder.build();
+        schem.block_entities.push(chest((0, -1, 0), "outside"));
+        assert_eq!(*schem.load_report(), LoadReport::default());
+
+        let schem = schem.with_checked_block_entities();
+        let report = schem.load_report();
+        assert_eq!((report.duplicate_block_entities, report.orphaned_block_entities), (1, 2));
+        assert!(report.has_block_entity_issues());
+        let names: Vec<&str> = schem.block_entities.iter().map(|be| be.data["CustomName"].as_str()).collect();
+        assert_eq!(names, ["orphan", "second", "outside"]);
+    }
+
+    #[test]
+    fn test_set_blocks_and_block_entities() {
+        let chest = Block::new("minecraft:chest");
+        let stone = Block::new("minecraft:stone");
+        let chests = || {
+            let mut builder = SchematicBuilder::new(4, 1, 1);
+            for x in 0..3 {
+                builder.set_block(x, 0, 0, Block::new("minecraft:chest")).unwrap();
+                let id = "minecraft:chest".to_string();
+                builder.add_block_entity(BlockEntity { id, pos: (x as i32, 0, 0), ..Default::default() }).unwrap();
+            }
+            builder.build()
+        };
+        let positions = |schem: &UnifiedSchematic| schem.block_entities.iter().map(|be| be.pos).collect::<Vec<_>>();
+
+        // The default keeps a block entity under the same block type and drops
+        // it when the type changes
+        let mut schem = chests();
+        let turned = Block::with_state("minecraft:chest", BlockState {
+            properties: [("facing".to_string(), "east".to_string())].into_iter().collect(),
+        });
+        schem.set_block(0, 0, 0, turned.clone()).unwrap();
+        schem.set_block(1, 0, 0, stone.clone()).unwrap();
+        assert_eq!(positions(&schem), [(0, 0, 0), (2, 0, 0)]);
+        assert_eq!(schem.get_block(0, 0, 0), Some(&turned));
+
+        let mut schem = chests();
+        schem.set_block_with_policy(0, 0, 0, stone.clone(), BlockEntityPolicy::Keep).unwrap();
+        schem.set_block_with_policy(1, 0, 0, chest.clone(), BlockEntityPolicy::Remove).unwrap();
+        assert_eq!(positions(&schem), [(0, 0, 0), (2, 0, 0)]);
+        assert!(matches!(schem.set_block(4, 0, 0, stone.clone()), Err(SchemError::OutOfBounds(4, 0, 0))));
+
+        // A batch with a position outside changes nothing
+        let mut schem = chests();
+        let batch = vec![((0, 0, 0), stone.clone()), ((9, 0, 0), stone.clone())];
+        assert!(matches!(schem.set_blocks(batch, BlockEntityPolicy::Remove), Err(SchemError::OutOfBounds(9, 0, 0))));
+        assert_eq!(schem.get_block(0, 0, 0), Some(&chest));
+        assert_eq!(positions(&schem).len(), 3);
+
+        let batch = vec![((0, 0, 0), stone.clone()), ((1, 0, 0), chest.clone()), ((3, 0, 0), stone.clone())];
+        assert_eq!(schem.set_blocks(batch, BlockEntityPolicy::default()).unwrap(), 3);
+        assert_eq!(positions(&schem), [(1, 0, 0), (2, 0, 0)]);
+        assert_eq!(schem.get_block(3, 0, 0), Some(&stone));
+
+        // Swapping moves a block entity along with its block
+        schem.swap_blocks((2, 0, 0), (3, 0, 0)).unwrap();
+        assert_eq!(positions(&schem), [(1, 0, 0), (3, 0, 0)]);
+        assert_eq!((schem.get_block(2, 0, 0), schem.get_block(3, 0, 0)), (Some(&stone), Some(&chest)));
+        assert!(schem.swap_blocks((0, 0, 0), (0, 1, 0)).is_err());
+
+        // get_block_mut edits in place and leaves block entities alone
+        schem.get_block_mut(1, 0, 0).unwrap().name = "minecraft:barrel".to_string();
+        assert_eq!(schem.get_block(1, 0, 0).unwrap().name, "minecraft:barrel");
+        assert_eq!(positions(&schem).len(), 2);
+        assert!(schem.get_block_mut(0, 5, 0).is_none());
+    }
 }
@@ -0,0 +1,219 @@
+//! Block and sky light propagation for mob farm analysis
+//!
+//! Block light spreads from emitting blocks (see [`Block::light_emission`]) with
+//! the usual taxicab falloff of one level per step, losing extra levels in
+//! water and leaves and stopping at opaque blocks. Sky light is approximated by
+//! casting straight down each column from the top of the schematic, so it
+//! doesn't spread sideways under overhangs. Everything outside the schematic
+//! is treated as unlit open air.
+
+use std::collections::VecDeque;
+use crate::block_geometry::{self, BlockGeometry, Face};
+use crate::{Block, UnifiedSchematic};
+
+/// Light level cutoff: monsters spawn where block light is below this
+pub const SPAWN_LIGHT: u8 = 1;
+
+/// Levels of light lost when passing through a block; 15 blocks light entirely
+fn opacity(block: &Block) -> u8 {
+    let name = block.display_name();
+    if block.is_air() {
+        return 0;
+    }
+    if name == "water" || name.contains("leaves") || name == "ice" || name == "frosted_ice" {
+        return 1;
+    }
+    let transparent = (name.contains("glass") && name != "tinted_glass")
+        || matches!(name, "slime_block" | "honey_block" | "beacon" | "spawner" | "barrier" | "lava");
+    let geometry = block_geometry::get_block_geometry(&block.name, &block.state.properties);
+    if geometry.is_full() && !transparent {
+        15
+    } else {
+        0
+    }
+}
+
+/// Per-position block and sky light levels for a schematic
+#[derive(Debug, Clone)]
+pub struct LightMap {
+    width: u32,
+    height: u32,
+    length: u32,
+    block: Vec<u8>,
+    sky: Vec<u8>,
+}
+
+impl LightMap {
+    /// Propagate light through `schem`; without `skylight` all sky light is 0
+    pub fn compute(schem: &UnifiedSchematic, skylight: bool) -> Self {
+        let (w, h, l) = (schem.width as usize, schem.height as usize, schem.length as usize);
+
+        let opacities: Vec<u8> = schem.blocks.iter().map(opacity).collect();
+
+        let mut block = vec![0u8; schem.blocks.len()];
+        let mut queue = VecDeque::new();
+        for (i, b) in schem.blocks.iter().enumerate() {
+            let emission = b.light_emission();
+            if emission > 0 {
+                block[i] = emission;
+                queue.push_back(i);
+            }
+        }
+
+        // Breadth-first spread; a position is revisited only when it gets brighter
+        while let Some(i) = queue.pop_front() {
+            let (x, y, z) = (i % w, i / (w * l), (i / w) % l);
+            let level = block[i];
+            let neighbors = [
+                (x > 0).then(|| i - 1),
+                (x + 1 < w).then(|| i + 1),
+                (z > 0).then(|| i - w),
+                (z + 1 < l).then(|| i + w),
+                (y > 0).then(|| i - w * l),
+                (y + 1 < h).then(|| i + w * l),
+            ];
+            for n in neighbors.into_iter().flatten() {
+                let next = level.saturating_sub(1 + opacities[n]);
+                if next > block[n] {
+                    block[n] = next;
+                    queue.push_back(n);
+                }
+            }
+        }
+
+        let mut sky = vec![0u8; schem.blocks.len()];
+        if skylight {
+            for z in 0..l {
+                for x in 0..w {
+                    let mut level = 15u8;
+                    for y in (0..h).rev() {
+                        let i = (y * l + z) * w + x;
+                        level = level.saturating_sub(opacities[i]);
+                        sky[i] = level;
+                    }
+                }
+            }
+        }
+
+        LightMap { width: schem.width, height: schem.height, length: schem.length, block, sky }
+    }
+
+    fn index(&self, x: u32, y: u32, z: u32) -> Option<usize> {
+        if x >= self.width || y >= self.height || z >= self.length {
+            return None;
+        }
+        Some((y as usize * self.length as usize + z as usize) * self.width as usize + x as usize)
+    }
+
+    /// Block light at a position (0 outside the schematic)
+    pub fn block_light(&self, x: u32, y: u32, z: u32) -> u8 {
+        self.index(x, y, z).map_or(0, |i| self.block[i])
+    }
+
+    /// Sky light at a position (0 outside the schematic)
+    pub fn sky_light(&self, x: u32, y: u32, z: u32) -> u8 {
+        self.index(x, y, z).map_or(0, |i| self.sky[i])
+    }
+}
+
+/// Blocks a mob can't stand in (anything with collision, plus liquids)
+fn obstructs(block: &Block) -> bool {
+    let name = block.display_name();
+    name == "water" || name == "lava"
+        || !matches!(block_geometry::get_block_geometry(&block.name, &block.state.properties), BlockGeometry::Empty)
+}
+
+/// Floor positions where a monster could spawn in the dark
+///
+/// The floor must be opaque with a full top face and have two free blocks above it;
+/// the space above must have block light below [`SPAWN_LIGHT`] and, if `map` has sky
+/// light, no sky light either. Free space above the top of the schematic counts as
+/// open sky.
+pub fn dark_spawnable_surfaces(schem: &UnifiedSchematic, map: &LightMap, skylight: bool) -> Vec<(u32, u32, u32)> {
+    let mut spots = Vec::new();
+    for y in 0..schem.height {
+        for z in 0..schem.length {
+            for x in 0..schem.width {
+                let Some(floor) = schem.get_block(x, y, z) else { continue };
+                if opacity(floor) < 15
+                    || !block_geometry::block_covers_face(&floor.name, &floor.state.properties, Face::Up)
+                {
+                    continue;
+                }
+                let free = |dy: u32| schem.get_block(x, y + dy, z).is_none_or(|b| !obstructs(b));
+                if !free(1) || !free(2) {
+                    continue;
+                }
+                let above_top = y + 1 >= schem.height;
+                let sky = if above_top { 15 } else { map.sky_light(x, y + 1, z) };
+                if map.block_light(x, y + 1, z) < SPAWN_LIGHT && !(skylight && sky > 0) {
+                    spots.push((x, y, z));
+                }
+            }
+        }
+    }
+    spots
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::SchematicBuilder;
+
+    /// 9x3x1 corridor: stone floor, air above
+    fn corridor() -> SchematicBuilder {
+        let mut builder = SchematicBuilder::new(9, 3, 1);
+        builder.fill((0, 0, 0), (8, 0, 0), Block::new("minecraft:stone")).unwrap();
+        builder
+    }
+
+    #[test]
+    fn test_taxicab_falloff() {
+        let mut builder = corridor();
+        builder.set_block(0, 1, 0, Block::new("minecraft:torch")).unwrap();
+        let schem = builder.build();
+
+        let map = LightMap::compute(&schem, false);
+        assert_eq!(map.block_light(0, 1, 0), 14);
+        assert_eq!(map.block_light(5, 1, 0), 9);
+        assert_eq!(map.block_light(5, 2, 0), 8);
+        // Opaque floor stays dark
+        assert_eq!(map.block_light(3, 0, 0), 0);
+    }
+
+    #[test]
+    fn test_opaque_wall_blocks_light() {
+        let mut builder = corridor();
+        builder.set_block(0, 1, 0, Block::new("minecraft:glowstone")).unwrap();
+        builder.fill((4, 1, 0), (4, 2, 0), Block::new("minecraft:stone")).unwrap();
+        let schem = builder.build();
+
+        let map = LightMap::compute(&schem, false);
+        assert_eq!(map.block_light(3, 1, 0), 12);
+        assert_eq!(map.block_light(5, 1, 0), 0);
+
+        // The floor behind the wall is dark, and so is the top of the wall (open to
+        // the unlit space above the schematic). The floor under the glowstone and the
+        // wall has no room above it.
+        let spots = dark_spawnable_surfaces(&schem, &map, false);
+        assert_eq!(spots, vec![(5, 0, 0), (6, 0, 0), (7, 0, 0), (8, 0, 0), (4, 2, 0)]);
+    }
+
+    #[test]
+    fn test_skylight_columns() {
+        let mut builder = corridor();
+        // Roof over the right half
+        builder.fill((5, 2, 0), (8, 2, 0), Block::new("minecraft:stone")).unwrap();
+        let schem = builder.build();
+
+        let map = LightMap::compute(&schem, true);
+        assert_eq!(map.sky_light(2, 1, 0), 15);
+        assert_eq!(map.sky_light(6, 1, 0), 0);
+
+        // Under the roof there's only one free block, and the open floor and the
+        // roof's top are in sky light
+        assert!(dark_spawnable_surfaces(&schem, &map, true).is_empty());
+        // Without sky light the open floor and the roof's top are dark
+        assert_eq!(dark_spawnable_surfaces(&schem, &map, false).len(), 9);
+    }
+}
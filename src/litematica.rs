@@ -8,22 +8,28 @@
 //!   - RegionCount, TotalBlocks, TotalVolume
 //!   - TimeCreated, TimeModified
 //!   - EnclosingSize: {x, y, z}
+//!   - PreviewImageData: IntArray (optional, see [`crate::preview`])
 //! - Regions: compound
 //!   - [region_name]: compound
-//!     - Position: {x, y, z}
-//!     - Size: {x, y, z}
+//!     - Position: {x, y, z} (one corner, relative to the schematic origin)
+//!     - Size: {x, y, z} (negative components extend from Position towards -infinity)
 //!     - BlockStatePalette: list of block state compounds
 //!     - BlockStates: LongArray (packed bit storage)
-//!     - TileEntities: list
-//!     - Entities: list
+//!     - TileEntities: list (x/y/z relative to the region's minimum corner)
+//!     - Entities: list (Pos relative to the region's minimum corner)
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use crate::fingerprint::SIGNATURE_KEY;
+use crate::preview::PreviewImage;
 use crate::{
-    Block, BlockState, BlockEntity, Entity, Metadata,
-    SchematicFormat, UnifiedSchematic,
+    checked_volume, Block, BlockState, BlockEntity, Entity, LoadReport, Metadata,
+    SchemError, SchematicFormat, UnifiedSchematic,
 };
 
+/// Data version written when the schematic doesn't have one (1.21)
+pub const DEFAULT_DATA_VERSION: i32 = 3953;
+
 /// Litematica format
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Litematica {
@@ -38,6 +44,16 @@ pub struct Litematica {
 
     #[serde(rename = "Regions")]
     pub regions: HashMap<String, LitematicaRegion>,
+
+    /// Root fields written back from `Metadata::raw_extra`; loading collects
+    /// them separately, so this stays empty on a loaded litematic
+    #[serde(flatten, skip_deserializing)]
+    pub extra: HashMap<String, fastnbt::Value>,
+}
+
+/// Root keys read by [`Litematica`]
+impl crate::extra_nbt::KnownFields for Litematica {
+    const KNOWN: &'static [&'static str] = &["Version", "MinecraftDataVersion", "Metadata", "Regions"];
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -68,6 +84,14 @@ pub struct LitematicaMetadata {
 
     #[serde(rename = "EnclosingSize", default)]
     pub enclosing_size: Option<LitematicaSize>,
+
+    /// Thumbnail for Litematica's schematic browser, ARGB pixels of a square image
+    #[serde(rename = "PreviewImageData", default, skip_serializing_if = "Option::is_none")]
+    pub preview_image_data: Option<fastnbt::IntArray>,
+
+    /// Signature written by `schem-tool seal`, kept in `Metadata::extra` under the same key
+    #[serde(rename = "schem-tool:signature", default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, Default)]
@@ -115,9 +139,13 @@ pub struct LitematicaBlockState {
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct LitematicaTileEntity {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub x: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub y: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub z: Option<i32>,
     #[serde(flatten)]
     pub extra: HashMap<String, fastnbt::Value>,
@@ -132,57 +160,116 @@ pub struct LitematicaEntity {
     pub extra: HashMap<String, fastnbt::Value>,
 }
 
+/// Integer (x, y, z) triple, wide enough that corner arithmetic on i32 NBT values can't overflow
+type Vec3 = (i64, i64, i64);
+
+/// Lowest coordinate covered along one axis; a negative size extends from `pos` towards -infinity
+fn axis_min(pos: i32, size: i32) -> i64 {
+    if size < 0 { pos as i64 + size as i64 + 1 } else { pos as i64 }
+}
+
+impl LitematicaRegion {
+    /// Minimum corner relative to the schematic origin
+    ///
+    /// Block data, tile entity and entity positions are all stored relative to this corner.
+    pub fn min_corner(&self) -> Vec3 {
+        let pos = self.position.clone().unwrap_or_default();
+        let size = self.size.clone().unwrap_or_default();
+        (axis_min(pos.x, size.x), axis_min(pos.y, size.y), axis_min(pos.z, size.z))
+    }
+
+    /// Absolute size as (x, y, z)
+    pub fn extent(&self) -> (u32, u32, u32) {
+        let size = self.size.clone().unwrap_or_default();
+        (size.x.unsigned_abs(), size.y.unsigned_abs(), size.z.unsigned_abs())
+    }
+}
+
 impl Litematica {
+    /// Bounding box of all regions as (min corner, size), or None without sized regions
+    pub fn bounds(&self) -> Option<(Vec3, Vec3)> {
+        let mut bounds: Option<(Vec3, Vec3)> = None;
+
+        for region in self.regions.values().filter(|r| r.size.is_some()) {
+            let min = region.min_corner();
+            let ext = region.extent();
+            let max = (min.0 + ext.0 as i64, min.1 + ext.1 as i64, min.2 + ext.2 as i64);
+            bounds = Some(match bounds {
+                None => (min, max),
+                Some((lo, hi)) => (
+                    (lo.0.min(min.0), lo.1.min(min.1), lo.2.min(min.2)),
+                    (hi.0.max(max.0), hi.1.max(max.1), hi.2.max(max.2)),
+                ),
+            });
+        }
+
+        bounds.map(|(lo, hi)| (lo, (hi.0 - lo.0, hi.1 - lo.1, hi.2 - lo.2)))
+    }
+
     /// Convert to unified format
-    pub fn to_unified(&self) -> UnifiedSchematic {
-        // Get enclosing size from metadata
-        let (width, height, length) = if let Some(ref size) = self.metadata.enclosing_size {
-            (size.x.unsigned_abs() as u16, size.y.unsigned_abs() as u16, size.z.unsigned_abs() as u16)
-        } else {
-            // Calculate from regions
-            let mut max_x = 0i32;
-            let mut max_y = 0i32;
-            let mut max_z = 0i32;
-
-            for region in self.regions.values() {
-                if let (Some(pos), Some(size)) = (&region.position, &region.size) {
-                    max_x = max_x.max(pos.x.abs() + size.x.abs());
-                    max_y = max_y.max(pos.y.abs() + size.y.abs());
-                    max_z = max_z.max(pos.z.abs() + size.z.abs());
-                }
-            }
-            (max_x as u16, max_y as u16, max_z as u16)
+    ///
+    /// The block grid starts at the minimum corner of the regions' bounding box, and
+    /// tile entity and entity positions are rebased into the same space. Fails with
+    /// `SchemError::TooLarge` if the grid can't be allocated or its positions don't fit in i32.
+    /// Entities outside the grid are dropped (see [`Litematica::to_unified_with`]).
+    pub fn to_unified(&self) -> Result<UnifiedSchematic, SchemError> {
+        self.to_unified_with(false)
+    }
+
+    /// Convert to unified format, keeping entities outside the block grid if
+    /// `keep_out_of_bounds` is set
+    ///
+    /// Some exporters store entity positions in world coordinates rather than
+    /// relative to the region, and a litematic doesn't record where in the world
+    /// it was taken from, so those can't be moved back into the grid. Entities
+    /// outside it are dropped with a warning, and counted in
+    /// [`LoadReport::dropped_entities`].
+    pub fn to_unified_with(&self, keep_out_of_bounds: bool) -> Result<UnifiedSchematic, SchemError> {
+        let bounds = self.bounds();
+        let origin = bounds.map(|(min, _)| min).unwrap_or((0, 0, 0));
+
+        let clamp = |v: i64| u32::try_from(v).unwrap_or(u32::MAX);
+        let (width, height, length) = match (bounds, &self.metadata.enclosing_size) {
+            (Some((_, size)), _) => (clamp(size.0), clamp(size.1), clamp(size.2)),
+            (None, Some(size)) => (size.x.unsigned_abs(), size.y.unsigned_abs(), size.z.unsigned_abs()),
+            (None, None) => (0, 0, 0),
+        };
+
+        let volume = checked_volume(width, height, length)?;
+        let offset = match (i32::try_from(origin.0), i32::try_from(origin.1), i32::try_from(origin.2)) {
+            (Ok(x), Ok(y), Ok(z)) => (x, y, z),
+            _ => return Err(SchemError::TooLarge { width, height, length }),
         };
 
-        let volume = width as usize * height as usize * length as usize;
         let mut blocks = vec![Block::air(); volume];
         let mut block_entities = Vec::new();
         let mut entities = Vec::new();
+        let mut dropped_entities = 0;
+        let inside = |(x, y, z): (f64, f64, f64)| {
+            (0.0..=width as f64).contains(&x) && (0.0..=height as f64).contains(&y) && (0.0..=length as f64).contains(&z)
+        };
 
         // Process each region
         for region in self.regions.values() {
-            let region_size = region.size.as_ref().map(|s| (s.x, s.y, s.z)).unwrap_or((0, 0, 0));
-            let region_pos = region.position.as_ref().map(|p| (p.x, p.y, p.z)).unwrap_or((0, 0, 0));
+            let (region_width, region_height, region_length) = region.extent();
+            let region_volume = checked_volume(region_width, region_height, region_length)?;
+            let (region_width, region_length) = (region_width as usize, region_length as usize);
+
+            // Offset of the region's minimum corner inside the block grid
+            let min = region.min_corner();
+            let base = (min.0 - origin.0, min.1 - origin.1, min.2 - origin.2);
 
             // Build palette
             let palette: Vec<Block> = region.block_state_palette.iter().map(|bs| {
                 let state = BlockState {
-                    properties: bs.properties.clone().unwrap_or_default(),
+                    properties: bs.properties.clone().unwrap_or_default().into(),
                 };
                 Block::with_state(&bs.name, state)
             }).collect();
 
-            if palette.is_empty() {
-                continue;
-            }
-
             // Decode packed block states
-            if let Some(ref block_states) = region.block_states {
+            if let Some(block_states) = region.block_states.as_ref().filter(|_| !palette.is_empty()) {
                 let bits_per_block = calculate_bits_per_block(palette.len());
-                let region_width = region_size.0.unsigned_abs() as usize;
-                let region_height = region_size.1.unsigned_abs() as usize;
-                let region_length = region_size.2.unsigned_abs() as usize;
-                let region_volume = region_width * region_height * region_length;
 
                 // Decode blocks
                 let decoded = decode_packed_array(block_states, bits_per_block, region_volume);
@@ -198,34 +285,14 @@ impl Litematica {
                     let rz = (i / region_width) % region_length;
                     let rx = i % region_width;
 
-                    // Apply region offset (handle negative sizes)
-                    let gx = if region_size.0 < 0 {
-                        region_pos.0 + region_size.0 + 1 + rx as i32
-                    } else {
-                        region_pos.0 + rx as i32
-                    };
-                    let gy = if region_size.1 < 0 {
-                        region_pos.1 + region_size.1 + 1 + ry as i32
-                    } else {
-                        region_pos.1 + ry as i32
-                    };
-                    let gz = if region_size.2 < 0 {
-                        region_pos.2 + region_size.2 + 1 + rz as i32
-                    } else {
-                        region_pos.2 + rz as i32
-                    };
-
-                    if gx >= 0 && gy >= 0 && gz >= 0 {
-                        let gx = gx as u16;
-                        let gy = gy as u16;
-                        let gz = gz as u16;
-
-                        if gx < width && gy < height && gz < length {
-                            let idx = (gy as usize * length as usize + gz as usize) * width as usize + gx as usize;
-                            if idx < blocks.len() {
-                                blocks[idx] = palette[palette_idx].clone();
-                            }
-                        }
+                    let gx = base.0 + rx as i64;
+                    let gy = base.1 + ry as i64;
+                    let gz = base.2 + rz as i64;
+
+                    // In bounds means the index is below `volume`, so this can't overflow
+                    if (0..width as i64).contains(&gx) && (0..height as i64).contains(&gy) && (0..length as i64).contains(&gz) {
+                        let idx = (gy as usize * length as usize + gz as usize) * width as usize + gx as usize;
+                        blocks[idx] = palette[palette_idx].clone();
                     }
                 }
             }
@@ -234,15 +301,22 @@ impl Litematica {
             for te in &region.tile_entities {
                 let id = te.id.clone().unwrap_or_else(|| "unknown".to_string());
                 let pos = (
-                    te.x.unwrap_or(0) + region_pos.0,
-                    te.y.unwrap_or(0) + region_pos.1,
-                    te.z.unwrap_or(0) + region_pos.2,
+                    te.x.unwrap_or(0) as i64 + base.0,
+                    te.y.unwrap_or(0) as i64 + base.1,
+                    te.z.unwrap_or(0) as i64 + base.2,
                 );
+                let pos = match (i32::try_from(pos.0), i32::try_from(pos.1), i32::try_from(pos.2)) {
+                    (Ok(x), Ok(y), Ok(z)) => (x, y, z),
+                    _ => {
+                        log::warn!("Skipping {} at {:?}: position out of range", id, pos);
+                        continue;
+                    }
+                };
                 let mut data = HashMap::new();
                 for (key, value) in &te.extra {
                     data.insert(key.clone(), format!("{:?}", value));
                 }
-                block_entities.push(BlockEntity { id, pos, data });
+                block_entities.push(BlockEntity { id, pos, data, nbt: te.extra.clone() });
             }
 
             // Process entities
@@ -251,60 +325,179 @@ impl Litematica {
                     if let Some(ref pos_vec) = e.pos {
                         if pos_vec.len() >= 3 {
                             let pos = (
-                                pos_vec[0] + region_pos.0 as f64,
-                                pos_vec[1] + region_pos.1 as f64,
-                                pos_vec[2] + region_pos.2 as f64,
+                                pos_vec[0] + base.0 as f64,
+                                pos_vec[1] + base.1 as f64,
+                                pos_vec[2] + base.2 as f64,
                             );
+                            if !keep_out_of_bounds && !inside(pos) {
+                                dropped_entities += 1;
+                                continue;
+                            }
                             let mut data = HashMap::new();
                             for (key, value) in &e.extra {
                                 data.insert(key.clone(), format!("{:?}", value));
                             }
-                            entities.push(Entity { id: id.clone(), pos, data });
+                            entities.push(Entity { id: id.clone(), pos, data, nbt: e.extra.clone() });
                         }
                     }
                 }
             }
         }
 
+        if dropped_entities > 0 {
+            log::warn!(
+                "Dropped {} entities outside the {}x{}x{} schematic (stored in world coordinates?)",
+                dropped_entities, width, height, length
+            );
+        }
+
         // Build metadata
         let metadata = Metadata {
             name: self.metadata.name.clone(),
             author: self.metadata.author.clone(),
             date: self.metadata.time_created,
             required_mods: Vec::new(),
-            extra: HashMap::new(),
+            extra: self.metadata.signature.iter()
+                .map(|signature| (SIGNATURE_KEY.to_string(), signature.clone()))
+                .collect(),
+            data_version: self.minecraft_data_version,
+            enclosing_size: self.metadata.enclosing_size.as_ref().map(|s| (s.x, s.y, s.z)),
+            total_blocks: self.metadata.total_blocks,
+            total_volume: self.metadata.total_volume,
+            region_count: self.metadata.region_count,
+            ..Default::default()
         };
 
-        UnifiedSchematic {
+        Ok(UnifiedSchematic {
             format: SchematicFormat::Litematica,
             width,
             height,
             length,
+            offset,
             blocks,
             block_entities,
             entities,
             metadata,
+            load_report: LoadReport { dropped_entities, ..Default::default() },
+        })
+    }
+
+    /// Single-region litematic holding a unified schematic's blocks
+    ///
+    /// Block entities and entities are written from their `nbt`, at the same
+    /// region-relative positions the loader reads them from. The creation time comes
+    /// from `metadata.date`, and the region sits at
+    /// [`paste_offset`](UnifiedSchematic::paste_offset) from the placement origin.
+    /// Unrecognised root fields in `metadata.raw_extra` are written back if the
+    /// schematic was loaded from a litematic, and dropped with a warning otherwise.
+    pub fn from_unified(schem: &UnifiedSchematic) -> Self {
+        let size = LitematicaSize { x: schem.width as i32, y: schem.height as i32, z: schem.length as i32 };
+
+        // Air comes first, as Litematica expects
+        let air = Block::air();
+        let mut palette = vec![&air];
+        let mut ids = HashMap::from([(air.full_name(), 0)]);
+        let indices: Vec<usize> = schem.blocks.iter().map(|block| {
+            *ids.entry(block.full_name()).or_insert_with(|| {
+                palette.push(block);
+                palette.len() - 1
+            })
+        }).collect();
+        let block_states = encode_packed_array(&indices, calculate_bits_per_block(palette.len()));
+
+        let region = LitematicaRegion {
+            position: Some({
+                let (x, y, z) = schem.paste_offset();
+                LitematicaSize { x, y, z }
+            }),
+            size: Some(size.clone()),
+            block_state_palette: palette
+                .into_iter()
+                .map(|block| LitematicaBlockState {
+                    name: block.name.clone(),
+                    properties: (!block.state.properties.is_empty()).then(|| (*block.state.properties).clone()),
+                })
+                .collect(),
+            block_states: Some(fastnbt::LongArray::new(block_states)),
+            tile_entities: schem.block_entities.iter()
+                .map(|be| LitematicaTileEntity {
+                    id: Some(be.id.clone()),
+                    x: Some(be.pos.0),
+                    y: Some(be.pos.1),
+                    z: Some(be.pos.2),
+                    extra: be.nbt.clone(),
+                })
+                .collect(),
+            entities: schem.entities.iter()
+                .map(|entity| LitematicaEntity {
+                    id: Some(entity.id.clone()),
+                    pos: Some(vec![entity.pos.0, entity.pos.1, entity.pos.2]),
+                    extra: entity.nbt.clone(),
+                })
+                .collect(),
+            pending_block_ticks: Vec::new(),
+            pending_fluid_ticks: Vec::new(),
+        };
+
+        let name = schem.metadata.name.clone().unwrap_or_else(|| "Unnamed".to_string());
+        let time = schem.metadata.date.unwrap_or(0);
+        Litematica {
+            version: 6,
+            minecraft_data_version: Some(schem.metadata.data_version.unwrap_or(DEFAULT_DATA_VERSION)),
+            metadata: LitematicaMetadata {
+                name: Some(name.clone()),
+                author: Some(schem.metadata.author.clone().unwrap_or_default()),
+                description: Some(String::new()),
+                region_count: Some(1),
+                total_blocks: Some(schem.blocks.iter().filter(|b| !b.is_air()).count() as i64),
+                total_volume: Some(schem.blocks.len() as i64),
+                time_created: Some(time),
+                time_modified: Some(time),
+                enclosing_size: Some(size),
+                preview_image_data: None,
+                signature: schem.metadata.extra.get(SIGNATURE_KEY).cloned(),
+            },
+            regions: HashMap::from([(name, region)]),
+            extra: schem.metadata.raw_extra_root(matches!(schem.format, SchematicFormat::Litematica), "litematic"),
         }
     }
+
+    /// Show `preview` in Litematica's schematic browser
+    pub fn with_preview(mut self, preview: &PreviewImage) -> Self {
+        self.metadata.preview_image_data = Some(preview.to_int_array());
+        self
+    }
+
+    /// The schematic browser thumbnail, if there is one
+    pub fn preview(&self) -> Option<PreviewImage> {
+        PreviewImage::from_int_array(self.metadata.preview_image_data.as_ref()?)
+    }
+
+    /// Gzipped NBT, as stored in .litematic files
+    pub fn to_bytes(&self) -> Result<Vec<u8>, SchemError> {
+        crate::gzip_nbt(self)
+    }
 }
 
-impl From<Litematica> for UnifiedSchematic {
-    fn from(lit: Litematica) -> Self {
+impl TryFrom<Litematica> for UnifiedSchematic {
+    type Error = SchemError;
+
+    fn try_from(lit: Litematica) -> Result<Self, SchemError> {
         lit.to_unified()
     }
 }
 
-/// Calculate bits per block based on palette size
-fn calculate_bits_per_block(palette_size: usize) -> usize {
+/// Calculate bits per block based on palette size (Litematica never uses fewer than 2)
+pub(crate) fn calculate_bits_per_block(palette_size: usize) -> usize {
     if palette_size <= 1 {
-        return 1; // Minimum 1 bit
+        return 2;
     }
     let bits = (palette_size as f64).log2().ceil() as usize;
-    bits.max(1)
+    bits.max(2)
 }
 
 /// Decode packed long array into block indices
-fn decode_packed_array(data: &fastnbt::LongArray, bits_per_block: usize, count: usize) -> Vec<usize> {
+pub(crate) fn decode_packed_array(data: &fastnbt::LongArray, bits_per_block: usize, count: usize) -> Vec<usize> {
     let mut result = Vec::with_capacity(count);
     let mask = (1u64 << bits_per_block) - 1;
 
@@ -347,3 +540,186 @@ fn decode_packed_array(data: &fastnbt::LongArray, bits_per_block: usize, count:
 
     result
 }
+
+/// Pack block indices into longs, letting entries span two longs like Litematica does
+fn encode_packed_array(indices: &[usize], bits_per_block: usize) -> Vec<i64> {
+    let mut longs = vec![0u64; (indices.len() * bits_per_block).div_ceil(64)];
+    for (i, &value) in indices.iter().enumerate() {
+        let bit_offset = i * bits_per_block;
+        let (long_index, bit_in_long) = (bit_offset / 64, bit_offset % 64);
+        longs[long_index] |= (value as u64) << bit_in_long;
+        if bit_in_long + bits_per_block > 64 {
+            longs[long_index + 1] |= (value as u64) >> (64 - bit_in_long);
+        }
+    }
+    longs.into_iter().map(|long| long as i64).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn region(pos: (i32, i32, i32), size: (i32, i32, i32), palette: &[&str], states: Vec<i64>) -> LitematicaRegion {
+        LitematicaRegion {
+            position: Some(LitematicaSize { x: pos.0, y: pos.1, z: pos.2 }),
+            size: Some(LitematicaSize { x: size.0, y: size.1, z: size.2 }),
+            block_state_palette: palette
+                .iter()
+                .map(|name| LitematicaBlockState { name: name.to_string(), properties: None })
+                .collect(),
+            block_states: Some(fastnbt::LongArray::new(states)),
+            tile_entities: Vec::new(),
+            entities: Vec::new(),
+            pending_block_ticks: Vec::new(),
+            pending_fluid_ticks: Vec::new(),
+        }
+    }
+
+    fn litematic(regions: Vec<(&str, LitematicaRegion)>) -> Litematica {
+        Litematica {
+            version: 6,
+            minecraft_data_version: None,
+            metadata: LitematicaMetadata {
+                name: None,
+                author: None,
+                description: None,
+                region_count: Some(regions.len() as i32),
+                total_blocks: None,
+                total_volume: None,
+                time_created: None,
+                time_modified: None,
+                enclosing_size: None,
+                preview_image_data: None,
+                signature: None,
+            },
+            regions: regions.into_iter().map(|(name, r)| (name.to_string(), r)).collect(),
+            extra: HashMap::new(),
+        }
+    }
+
+    fn sign_at(x: i32, y: i32, z: i32) -> LitematicaTileEntity {
+        LitematicaTileEntity {
+            id: Some("minecraft:sign".to_string()),
+            x: Some(x),
+            y: Some(y),
+            z: Some(z),
+            extra: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_entities_share_block_grid_space() {
+        // 2x1x2 of stone reaching into negative coordinates
+        let stone = region((-1, 0, -1), (2, 1, 2), &["minecraft:air", "minecraft:stone"], vec![0b01010101]);
+
+        // 2x2x2 with a negative size, spanning (4, 0, 4)..=(5, 1, 5), with signs
+        // in the first and last cells of its block data (2 bits each)
+        let mut signs = region((5, 1, 5), (-2, -2, -2), &["minecraft:air", "minecraft:oak_sign"], vec![1 | 1 << 14]);
+        signs.tile_entities = vec![sign_at(0, 0, 0), sign_at(1, 1, 1)];
+        signs.entities = vec![LitematicaEntity {
+            id: Some("minecraft:armor_stand".to_string()),
+            pos: Some(vec![0.5, 0.0, 0.5]),
+            extra: HashMap::new(),
+        }];
+
+        let mut lit = litematic(vec![("stone", stone), ("signs", signs)]);
+        lit.metadata.enclosing_size = Some(LitematicaSize { x: 7, y: 2, z: 7 });
+
+        let schem = lit.to_unified().unwrap();
+        assert_eq!((schem.width, schem.height, schem.length), (7, 2, 7));
+        assert_eq!(schem.get_block(0, 0, 0).unwrap().name, "minecraft:stone");
+        assert_eq!(schem.get_block(1, 0, 1).unwrap().name, "minecraft:stone");
+
+        let positions: Vec<_> = schem.block_entities.iter().map(|be| be.pos).collect();
+        assert_eq!(positions, vec![(5, 0, 5), (6, 1, 6)]);
+        for be in &schem.block_entities {
+            let (x, y, z) = be.pos;
+            assert_eq!(schem.get_block(x as u32, y as u32, z as u32).unwrap().name, "minecraft:oak_sign");
+        }
+
+        assert_eq!(schem.entities[0].pos, (5.5, 0.0, 5.5));
+        assert_eq!(schem.metadata.enclosing_size, Some((7, 2, 7)));
+        assert_eq!(schem.metadata.region_count, Some(2));
+    }
+
+    #[test]
+    fn test_entities_outside_grid() {
+        let mut stone = region((0, 0, 0), (4, 2, 4), &["minecraft:stone"], Vec::new());
+        let entity = |pos: [f64; 3]| LitematicaEntity {
+            id: Some("minecraft:armor_stand".to_string()),
+            pos: Some(pos.to_vec()),
+            extra: HashMap::new(),
+        };
+        // One on the top face, one in world coordinates
+        stone.entities = vec![entity([4.0, 2.0, 0.5]), entity([10234.5, 72.0, -940.2])];
+        let lit = litematic(vec![("stone", stone)]);
+
+        let schem = lit.to_unified().unwrap();
+        assert_eq!(schem.entities.len(), 1);
+        assert_eq!(schem.entities[0].pos, (4.0, 2.0, 0.5));
+        assert_eq!(schem.load_report().dropped_entities, 1);
+
+        let kept = lit.to_unified_with(true).unwrap();
+        assert_eq!(kept.entities.len(), 2);
+        assert_eq!(kept.load_report().dropped_entities, 0);
+    }
+
+    #[test]
+    fn test_region_longer_than_u16() {
+        let strip = region((0, 0, 0), (-70_000, 1, 1), &["minecraft:white_wool"], Vec::new());
+        let lit = litematic(vec![("strip", strip)]);
+
+        let schem = lit.to_unified().unwrap();
+        assert_eq!((schem.width, schem.height, schem.length), (70_000, 1, 1));
+        assert_eq!(schem.offset, (-69_999, 0, 0));
+        assert_eq!(schem.get_block(69_999, 0, 0).unwrap().name, "minecraft:white_wool");
+    }
+
+    #[test]
+    fn test_from_unified_round_trip() {
+        use crate::builder::SchematicBuilder;
+
+        // Six palette entries take 3 bits, so some entries span two longs
+        let mut builder = SchematicBuilder::new(5, 3, 4);
+        let names = ["stone", "dirt", "oak_planks", "glass"];
+        for (i, (x, y, z)) in (0..3).flat_map(|y| (0..4).flat_map(move |z| (0..5).map(move |x| (x, y, z)))).enumerate() {
+            if i % 5 != 0 {
+                builder.set_block(x, y, z, Block::new(format!("minecraft:{}", names[i % 4]))).unwrap();
+            }
+        }
+        let mut schem = builder.build();
+        schem.set_block(1, 0, 0, Block::with_state("minecraft:oak_stairs", BlockState {
+            properties: HashMap::from([("facing".to_string(), "east".to_string())]).into(),
+        })).unwrap();
+        let nbt = HashMap::from([("Items".to_string(), fastnbt::Value::List(Vec::new()))]);
+        schem.block_entities.push(crate::BlockEntity { id: "minecraft:barrel".to_string(), pos: (4, 2, 3), nbt, ..Default::default() });
+        let nbt = HashMap::from([("NoAI".to_string(), fastnbt::Value::Byte(1))]);
+        schem.entities.push(crate::Entity { id: "minecraft:cow".to_string(), pos: (2.5, 3.0, 1.5), nbt, ..Default::default() });
+
+        let lit = Litematica::from_unified(&schem);
+        let region = &lit.regions["Unnamed"];
+        assert_eq!(region.block_state_palette[0].name, "minecraft:air");
+        assert_eq!(lit.metadata.total_blocks, Some(schem.blocks.iter().filter(|b| !b.is_air()).count() as i64));
+
+        let back = lit.to_unified().unwrap();
+        assert_eq!((back.width, back.height, back.length), (5, 3, 4));
+        assert_eq!(back.blocks, schem.blocks);
+        assert_eq!(back.block_entities[0].pos, (4, 2, 3));
+        assert_eq!(back.block_entities[0].nbt, schem.block_entities[0].nbt);
+        assert_eq!((back.entities[0].id.as_str(), back.entities[0].pos), ("minecraft:cow", (2.5, 3.0, 1.5)));
+        assert_eq!(back.entities[0].nbt, schem.entities[0].nbt);
+        assert!(back.entities[0].mob_info().no_ai);
+
+        // Two entries still take 2 bits
+        let mut single = SchematicBuilder::new(3, 1, 1);
+        single.set_block(2, 0, 0, Block::new("minecraft:stone")).unwrap();
+        let lit = Litematica::from_unified(&single.build());
+        assert_eq!(&lit.regions["Unnamed"].block_states.as_ref().unwrap()[..], &[0b01_00_00]);
+        assert!(lit.preview().is_none());
+
+        let preview = PreviewImage::render(&schem, 16, |_| (0.5, 0.5, 0.5));
+        let lit = lit.with_preview(&preview);
+        assert_eq!(lit.metadata.preview_image_data.as_ref().map(|data| data.len()), Some(256));
+        assert_eq!(lit.preview(), Some(preview));
+    }
+}
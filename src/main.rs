@@ -1,11 +1,82 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
-use clap::{Parser, Subcommand};
+use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum};
 use colored::Colorize;
-use schem_tool::UnifiedSchematic;
-use std::path::PathBuf;
+use schem_tool::{BlockClass, BlockSelector, LoadOptions, SchemError, UnifiedSchematic};
+use schem_tool::export3d::{BlockPalette, ColorMap, Explode, ExportOptions, ExportRegion, ExportReport, ObjGroups};
+use schem_tool::layer_view::{self, Axis};
+use schem_tool::preview::{PreviewImage, PREVIEW_SIZE};
+use schem_tool::progress::{ConsoleProgress, NoProgress, Progress};
+use schem_tool::random::RandomContext;
+use schem_tool::recipes::{Fuel, MaterialOptions};
+use schem_tool::textures::{Biome, TextureManager};
+use schem_tool::timings::{TimedProgress, Timings};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, Ordering};
 use tabled::{Table, Tabled, settings::Style};
 
+mod config;
+mod nbt_dump;
+
+use config::{ColorChoice, Config};
+
+/// ASCII-only output: set from `--plain`, the config file or a non-UTF-8 locale
+static PLAIN: AtomicBool = AtomicBool::new(false);
+
+fn plain() -> bool {
+    PLAIN.load(Ordering::Relaxed)
+}
+
+/// Fill undecodable block data with air instead of failing (`--lenient`)
+static LENIENT: AtomicBool = AtomicBool::new(false);
+
+/// Fail on duplicate or orphaned block entities instead of warning (`--strict-block-entities`)
+static STRICT: AtomicBool = AtomicBool::new(false);
+
+/// Keep litematic entities outside the block grid (`--keep-out-of-bounds`)
+static KEEP_OUT_OF_BOUNDS: AtomicBool = AtomicBool::new(false);
+
+/// Time spent in each phase, printed at the end with `--timings`
+static TIMINGS: Timings = Timings::new();
+
+/// Write .litematic files without a preview image (`--no-preview`)
+static NO_PREVIEW: AtomicBool = AtomicBool::new(false);
+
+/// Image to use as the .litematic preview instead of a rendered one (`--preview-from`)
+static PREVIEW_FROM: OnceLock<PathBuf> = OnceLock::new();
+
+/// Block entity data to remove from written schematics (`--clear-inventories`,
+/// `--strip-signs`, `--strip-names`, `--strip-block-entities`)
+static SANITIZE: OnceLock<schem_tool::sanitize::SanitizeOptions> = OnceLock::new();
+
+/// Seed for random choices such as model variants (`--seed`)
+static SEED: OnceLock<RandomContext> = OnceLock::new();
+
+/// Render rows as a table, with ASCII borders in plain mode
+fn render_table<T: Tabled>(rows: Vec<T>) -> String {
+    if plain() {
+        Table::new(rows).with(Style::ascii()).to_string()
+    } else {
+        Table::new(rows).with(Style::rounded()).to_string()
+    }
+}
+
+/// Whether the locale variables ask for UTF-8 (unset counts as yes)
+fn utf8_locale() -> bool {
+    ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .map(|value| {
+            let value = value.to_lowercase();
+            value.contains("utf-8") || value.contains("utf8")
+        })
+        .unwrap_or(true)
+}
+
 /// Format Unix timestamp (milliseconds) to human-readable date
 fn format_timestamp(millis: i64) -> String {
     DateTime::from_timestamp_millis(millis)
@@ -18,490 +89,2862 @@ fn format_timestamp(millis: i64) -> String {
 #[command(about = "Minecraft schematic file parser and analyzer", long_about = None)]
 #[command(version)]
 struct Cli {
+    /// Only print errors on stderr
+    #[arg(short, long, global = true)]
+    quiet: bool,
+
+    /// How much to log on stderr: off, error, warn, info (default), debug or
+    /// trace; -q takes precedence
+    #[arg(long, global = true, value_name = "LEVEL")]
+    log_level: Option<log::LevelFilter>,
+
+    /// Config file to use instead of the default location
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+
+    /// When to use colors (auto honors NO_COLOR and checks for a terminal)
+    #[arg(long, global = true, value_enum, value_name = "WHEN")]
+    color: Option<ColorChoice>,
+
+    /// ASCII-only tables and layer glyphs (default in non-UTF-8 locales)
+    #[arg(long, global = true)]
+    plain: bool,
+
+    /// Load schematics with corrupt block data, filling unreadable blocks with air
+    #[arg(long, global = true)]
+    lenient: bool,
+
+    /// Refuse schematics with duplicate block entities or block entities on air
+    #[arg(long, global = true)]
+    strict_block_entities: bool,
+
+    /// Keep .litematic entities outside the schematic's box instead of dropping them
+    #[arg(long, global = true)]
+    keep_out_of_bounds: bool,
+
+    /// Print the time spent in each phase (loading, geometry, writing, ...) and
+    /// peak memory use when the command finishes
+    #[arg(long, global = true)]
+    timings: bool,
+
+    /// Write .litematic files without the rendered preview for Litematica's schematic browser
+    #[arg(long, global = true)]
+    no_preview: bool,
+
+    /// Use this image as the preview of written .litematic files, scaled to 140x140
+    #[arg(long, global = true, value_name = "IMAGE", conflicts_with = "no_preview")]
+    preview_from: Option<PathBuf>,
+
+    /// Empty chests and other containers in written schematics (keeps their names and locks)
+    #[arg(long, global = true)]
+    clear_inventories: bool,
+
+    /// Blank the text of signs in written schematics
+    #[arg(long, global = true)]
+    strip_signs: bool,
+
+    /// Remove custom names and container locks from written schematics
+    #[arg(long, global = true)]
+    strip_names: bool,
+
+    /// Remove block entities whose id contains PATTERN from written schematics,
+    /// keeping the blocks (repeatable)
+    #[arg(long, global = true, value_name = "PATTERN")]
+    strip_block_entities: Vec<String>,
+
+    /// Seed for random choices such as which model variant a block uses; a
+    /// number, or "random" for different choices every run
+    #[arg(long, global = true, value_name = "SEED")]
+    seed: Option<RandomContext>,
+
     #[command(subcommand)]
     command: Commands,
 }
 
+/// The full command definition, shared by parsing, completions and the man page
+fn cli_command() -> clap::Command {
+    Cli::command()
+}
+
 #[derive(Subcommand)]
 enum Commands {
     /// Show general information about the schematic
-    Info {
-        /// Path to the schematic file (.schematic or .schem)
-        file: PathBuf,
-    },
+    Info(InfoArgs),
 
     /// List all blocks with counts
-    Blocks {
-        /// Path to the schematic file
-        file: PathBuf,
-
-        /// Show only non-air blocks
-        #[arg(short, long)]
-        no_air: bool,
-
-        /// Sort by count (descending)
-        #[arg(short, long)]
-        sort: bool,
-
-        /// Limit number of results
-        #[arg(short, long)]
-        limit: Option<usize>,
-    },
+    Blocks(BlocksArgs),
 
     /// List unique block types with their states
-    Palette {
-        /// Path to the schematic file
-        file: PathBuf,
-    },
+    Palette(PaletteArgs),
 
     /// List block entities (chests, signs, etc.)
-    BlockEntities {
-        /// Path to the schematic file
-        file: PathBuf,
-
-        /// Filter by entity type
-        #[arg(short = 't', long)]
-        entity_type: Option<String>,
+    BlockEntities(BlockEntitiesArgs),
 
-        /// Show full data
-        #[arg(short, long)]
-        verbose: bool,
-    },
+    /// Read or replace one block entity's NBT as SNBT
+    BlockEntity(BlockEntityArgs),
 
     /// List entities (mobs, items, etc.)
-    Entities {
-        /// Path to the schematic file
-        file: PathBuf,
+    Entities(EntitiesArgs),
 
-        /// Show full data
-        #[arg(short, long)]
-        verbose: bool,
-    },
+    /// List mob spawners with what and how they spawn
+    Spawners(SpawnersArgs),
+
+    /// Check beacon pyramids and conduit frames
+    Beacons(BeaconsArgs),
 
     /// List signs with their text content
-    Signs {
-        /// Path to the schematic file
-        file: PathBuf,
-    },
+    Signs(SignsArgs),
+
+    /// List jigsaw blocks with their pools, targets and final states
+    Jigsaws(JigsawsArgs),
+
+    /// Estimate how long breaking every block takes, for demolition planning
+    Mining(MiningArgs),
+
+    /// Minimum Minecraft version for a schematic's blocks, checked against its DataVersion
+    MinVersion(MinVersionArgs),
+
+    /// Find blocks that fall, pop off, decay or flow away right after pasting
+    PasteCheck(PasteCheckArgs),
+
+    /// Find the build in a schematic that also captured the terrain around it
+    Focus(FocusArgs),
 
     /// Show metadata
-    Metadata {
-        /// Path to the schematic file
-        file: PathBuf,
-    },
+    Metadata(MetadataArgs),
+
+    /// Change the name, author or creation date, keeping everything else
+    SetMetadata(SetMetadataArgs),
 
     /// Get block at specific position
-    GetBlock {
-        /// Path to the schematic file
-        file: PathBuf,
+    GetBlock(GetBlockArgs),
 
-        /// X coordinate
-        #[arg(short)]
-        x: u16,
+    /// Search for blocks by name
+    Search(SearchArgs),
 
-        /// Y coordinate
-        #[arg(short)]
-        y: u16,
+    /// Export block list to CSV
+    Export(ExportArgs),
 
-        /// Z coordinate
-        #[arg(short)]
-        z: u16,
-    },
+    /// Fingerprint schematics by content, ignoring metadata and file format, to
+    /// find duplicates (`hash *.schem | sort | uniq -D -w 16`)
+    Hash(HashArgs),
 
-    /// Search for blocks by name
-    Search {
-        /// Path to the schematic file
-        file: PathBuf,
+    /// Keep only the visible outer layers of blocks, for pasting a facade
+    Shell(ShellArgs),
 
-        /// Block name pattern (partial match)
-        pattern: String,
+    /// Save a schematic in another format, chosen by the output's extension
+    Convert(ConvertArgs),
 
-        /// Show positions
-        #[arg(short, long)]
-        positions: bool,
+    /// Move the point pastes are anchored at, without changing the blocks
+    Reanchor(ReanchorArgs),
 
-        /// Limit number of results
-        #[arg(short, long)]
-        limit: Option<usize>,
-    },
+    /// Build a 3D model (OBJ) out of blocks, colored from its vertex colors,
+    /// textures or materials
+    Voxelize(VoxelizeArgs),
 
-    /// Export block list to CSV
-    Export {
-        /// Path to the schematic file
-        file: PathBuf,
+    /// Build an image out of blocks, one block thick, for map art or pixel art;
+    /// prints the blocks needed
+    FromImage(FromImageArgs),
 
-        /// Output CSV file
-        #[arg(short, long)]
-        output: PathBuf,
-    },
+    /// Make a one-block schematic holding a sign with text
+    MakeSign(MakeSignArgs),
 
-    /// Calculate raw materials needed (break down crafted items)
-    Materials {
-        /// Path to the schematic file
-        file: PathBuf,
+    /// Spell text out in blocks lying flat, with a 5-block-high font
+    MakeText(MakeTextArgs),
 
-        /// Sort by count (descending)
-        #[arg(short, long)]
-        sort: bool,
+    /// Replace blocks from mods with vanilla ones, from a mapping file or a placeholder
+    StripMods(StripModsArgs),
 
-        /// Show intermediate crafting steps
-        #[arg(short, long)]
-        verbose: bool,
+    /// Replace blocks an older release doesn't have, for pasting on an older server
+    Downgrade(DowngradeArgs),
 
-        /// Limit number of results
-        #[arg(short, long)]
-        limit: Option<usize>,
+    /// Sign a schematic's content with an ed25519 key and save the signed copy
+    #[cfg(feature = "signing")]
+    Seal(SealArgs),
 
-        /// Use stonecutter recipes (more efficient 1:1 ratios for stairs/slabs)
-        #[arg(long)]
-        stonecutter: bool,
-    },
+    /// Check a sealed schematic's signature; exits non-zero unless it matches
+    #[cfg(feature = "signing")]
+    Verify(VerifyArgs),
+
+    /// Browse a folder of schematics in a web browser (no authentication: LAN use only)
+    #[cfg(feature = "server")]
+    Serve(ServeArgs),
+
+    /// Write the position of every matching block, for mapping and pathing tools
+    ExportPositions(ExportPositionsArgs),
+
+    /// Calculate raw materials needed (break down crafted items)
+    Materials(MaterialsArgs),
+
+    /// Compare the raw materials of two schematics (e.g. two revisions of a build)
+    MaterialsDiff(MaterialsDiffArgs),
 
     /// Show layer-by-layer view (2D slice)
-    Layer {
-        /// Path to the schematic file
-        file: PathBuf,
+    Layer(LayerArgs),
 
-        /// Y level to show
-        #[arg(short)]
-        y: u16,
+    /// List placements layer by layer in a buildable order
+    BuildOrder(BuildOrderArgs),
 
-        /// Use simple ASCII characters
-        #[arg(short, long)]
-        ascii: bool,
-    },
+    /// Estimate scaffolding needed to reach floating parts in survival
+    Supports(SupportsArgs),
+
+    /// Compare a schematic against a world to see how much of it is built
+    Progress(ProgressArgs),
+
+    /// Copy a box of blocks out of a world into a schematic
+    FromWorld(FromWorldArgs),
+
+    /// Find dark floor where monsters can spawn (for mob farms)
+    Lighting(LightingArgs),
 
     /// Export to OBJ 3D model (viewable in Blender, Windows 3D Viewer, etc.)
-    RenderObj {
-        /// Path to the schematic file
-        file: PathBuf,
+    RenderObj(RenderObjArgs),
 
-        /// Output OBJ file path
-        #[arg(short, long)]
-        output: PathBuf,
+    /// Export to interactive HTML viewer (Three.js)
+    RenderHtml(RenderHtmlArgs),
 
-        /// Only export visible (exposed) blocks
-        #[arg(long)]
-        hollow: bool,
+    /// Export to GLB (binary glTF) with GPU instancing (much smaller files for large schematics)
+    #[command(alias = "render-glb")]
+    RenderGltf(RenderGltfArgs),
 
-        /// Use greedy meshing to reduce polygon count (10-100x smaller files)
-        #[arg(short, long)]
-        greedy: bool,
+    /// Run an analysis over every schematic in a directory
+    Batch(BatchArgs),
 
-        /// Use Minecraft JSON models for accurate block geometry
-        #[arg(long)]
-        models: bool,
+    /// Write a CSV row of metadata for every schematic in a directory, to spot
+    /// missing authors or duplicates in a library
+    Catalog(CatalogArgs),
 
-        /// Extract and apply textures from Minecraft installation
-        #[arg(short, long)]
-        textures: bool,
+    /// Re-run an export whenever the schematic changes
+    Watch(WatchArgs),
 
-        /// Path to Minecraft directory or client.jar (e.g., ~/.minecraft or client.jar)
-        #[arg(short, long)]
-        minecraft: Option<PathBuf>,
+    /// Inspect the config file
+    Config(ConfigArgs),
 
-        /// Path to resource pack (ZIP file) for custom textures and models
-        #[arg(short, long)]
-        resource_pack: Option<PathBuf>,
-    },
+    /// Print a shell completion script to stdout
+    Completions(CompletionsArgs),
 
-    /// Export to interactive HTML viewer (Three.js)
-    RenderHtml {
-        /// Path to the schematic file
-        file: PathBuf,
+    /// Generate a roff man page
+    Man(ManArgs),
 
-        /// Output HTML file path
-        #[arg(short, long)]
-        output: PathBuf,
+    /// Dump raw NBT structure for debugging
+    Debug(DebugArgs),
+}
 
-        /// Maximum blocks to render (default: 100000)
-        #[arg(short, long, default_value = "100000")]
-        max_blocks: usize,
-    },
+#[derive(Args)]
+struct InfoArgs {
+    /// Path to the schematic file (.schematic or .schem)
+    file: PathBuf,
 
-    /// Export to GLB (binary glTF) with GPU instancing (much smaller files for large schematics)
-    RenderGltf {
-        /// Path to the schematic file
-        file: PathBuf,
+    /// Number of most common block types to list
+    #[arg(long, value_name = "N", default_value_t = 10)]
+    top: usize,
 
-        /// Output GLB file path
-        #[arg(short, long)]
-        output: PathBuf,
+    /// Print the summary as JSON (fields are only ever added, never renamed)
+    #[arg(long)]
+    json: bool,
+}
 
-        /// Only export visible (exposed) blocks
-        #[arg(long)]
-        hollow: bool,
+#[derive(Args)]
+struct BlocksArgs {
+    /// Path to the schematic file
+    file: PathBuf,
+
+    /// Show only non-air blocks
+    #[arg(short, long)]
+    no_air: bool,
+
+    /// Sort by count (descending)
+    #[arg(short, long)]
+    sort: bool,
+
+    /// Limit number of results
+    #[arg(short, long)]
+    limit: Option<usize>,
+
+    /// Count items needed to place the blocks, like Litematica's material list
+    /// (doors and beds once, no piston heads, wall torches as torches)
+    #[arg(long)]
+    as_items: bool,
+
+    /// Count a water bucket for each waterlogged block
+    #[arg(long)]
+    waterlogged: bool,
+
+    /// Only count blocks with at least one exposed face (the visible shell)
+    #[arg(long)]
+    surface_only: bool,
+
+    /// Bucket the blocks into categories (wood, stone, glass, ...) with subtotals
+    #[arg(long, value_name = "GROUPING")]
+    group_by: Option<CountGrouping>,
+
+    /// Fold variants into one row: every color of a block, every wood species
+    /// of a block, or every block by shape
+    #[arg(long, value_name = "MODE", conflicts_with = "group_by")]
+    aggregate: Option<AggregateBy>,
+
+    /// With --group-by, list each category's blocks under its subtotal; with
+    /// --aggregate, each row's variants
+    #[arg(short, long)]
+    verbose: bool,
+
+    /// Print the counts as JSON
+    #[arg(long)]
+    json: bool,
+}
 
-        /// Accepted for CLI compatibility (GLB uses instancing instead of greedy meshing)
-        #[arg(short, long)]
-        greedy: bool,
+#[derive(Args)]
+struct PaletteArgs {
+    /// Path to the schematic file
+    file: PathBuf,
 
-        /// Use Minecraft JSON models for accurate block geometry
-        #[arg(long)]
-        models: bool,
+    /// Print one selector per state, like `minecraft:oak_stairs[facing=north,half=top]`,
+    /// ready to paste into /fill or WorldEdit
+    #[arg(long)]
+    selectors: bool,
 
-        /// Extract and apply textures from Minecraft installation
-        #[arg(short, long)]
-        textures: bool,
+    /// With --selectors, add how many blocks of each state there are
+    #[arg(long, requires = "selectors")]
+    count: bool,
+}
 
-        /// Path to Minecraft directory or client.jar
-        #[arg(short, long)]
-        minecraft: Option<PathBuf>,
+#[derive(Args)]
+struct BlockEntitiesArgs {
+    /// Path to the schematic file
+    file: PathBuf,
 
-        /// Path to resource pack (ZIP file) for custom textures and models
-        #[arg(short, long)]
-        resource_pack: Option<PathBuf>,
-    },
+    /// Filter by entity type
+    #[arg(short = 't', long)]
+    entity_type: Option<String>,
 
-    /// Dump raw NBT structure for debugging
-    Debug {
-        /// Path to the schematic file
-        file: PathBuf,
-    },
+    /// Show full data
+    #[arg(short, long)]
+    verbose: bool,
 }
 
-#[derive(Tabled)]
-struct BlockCount {
-    #[tabled(rename = "Block")]
-    name: String,
-    #[tabled(rename = "Count")]
-    count: usize,
-    #[tabled(rename = "%")]
-    percent: String,
+#[derive(Args)]
+struct BlockEntityArgs {
+    #[command(subcommand)]
+    action: BlockEntityAction,
 }
 
-#[derive(Tabled)]
-struct BlockEntityRow {
-    #[tabled(rename = "Type")]
-    entity_type: String,
-    #[tabled(rename = "Position")]
-    position: String,
-    #[tabled(rename = "Data")]
-    data: String,
+#[derive(Args)]
+struct EntitiesArgs {
+    /// Path to the schematic file
+    file: PathBuf,
+
+    /// Filter by entity type
+    #[arg(short = 't', long)]
+    entity_type: Option<String>,
+
+    /// Only print the number of entities of each type
+    #[arg(short, long, conflicts_with = "verbose")]
+    count_only: bool,
+
+    /// Show full data
+    #[arg(short, long)]
+    verbose: bool,
 }
 
-fn main() -> Result<()> {
-    let cli = Cli::parse();
+#[derive(Args)]
+struct SpawnersArgs {
+    /// Path to the schematic file
+    file: PathBuf,
+}
 
-    match cli.command {
-        Commands::Info { file } => cmd_info(&file)?,
-        Commands::Blocks { file, no_air, sort, limit } => cmd_blocks(&file, no_air, sort, limit)?,
-        Commands::Palette { file } => cmd_palette(&file)?,
-        Commands::BlockEntities { file, entity_type, verbose } => cmd_block_entities(&file, entity_type, verbose)?,
-        Commands::Entities { file, verbose } => cmd_entities(&file, verbose)?,
-        Commands::Signs { file } => cmd_signs(&file)?,
-        Commands::Metadata { file } => cmd_metadata(&file)?,
-        Commands::GetBlock { file, x, y, z } => cmd_get_block(&file, x, y, z)?,
-        Commands::Search { file, pattern, positions, limit } => cmd_search(&file, &pattern, positions, limit)?,
-        Commands::Export { file, output } => cmd_export(&file, &output)?,
-        Commands::Materials { file, sort, verbose, limit, stonecutter } => cmd_materials(&file, sort, verbose, limit, stonecutter)?,
-        Commands::Layer { file, y, ascii } => cmd_layer(&file, y, ascii)?,
-        Commands::RenderObj { file, output, hollow, greedy, models, textures, minecraft, resource_pack } => cmd_render_obj(&file, &output, hollow, greedy, models, textures, minecraft.as_deref(), resource_pack.as_deref())?,
-        Commands::RenderHtml { file, output, max_blocks } => cmd_render_html(&file, &output, max_blocks)?,
-        Commands::RenderGltf { file, output, hollow, greedy: _, models, textures, minecraft, resource_pack } => cmd_render_gltf(&file, &output, hollow, models, textures, minecraft.as_deref(), resource_pack.as_deref())?,
-        Commands::Debug { file } => cmd_debug(&file)?,
-    }
+#[derive(Args)]
+struct BeaconsArgs {
+    /// Path to the schematic file
+    file: PathBuf,
+}
 
-    Ok(())
+#[derive(Args)]
+struct SignsArgs {
+    /// Path to the schematic file
+    file: PathBuf,
 }
 
-fn cmd_info(file: &PathBuf) -> Result<()> {
-    let schem = UnifiedSchematic::load(file)?;
+#[derive(Args)]
+struct JigsawsArgs {
+    /// Path to the schematic file
+    file: PathBuf,
+}
 
-    println!("{}", "=== Schematic Info ===".bold().cyan());
-    println!();
+#[derive(Args)]
+struct MiningArgs {
+    /// Path to the schematic file
+    file: PathBuf,
 
-    println!("{}  {}", "File:".bold(), file.display());
-    println!("{}  {:?}", "Format:".bold(), schem.format);
-    println!();
+    /// hand, shears or a tool like netherite_pickaxe
+    #[arg(long, default_value_t = schem_tool::mining::Tool::Hand)]
+    tool: schem_tool::mining::Tool,
 
-    println!("{}", "--- Dimensions ---".yellow());
-    println!("  Width (X):  {}", schem.width);
-    println!("  Height (Y): {}", schem.height);
-    println!("  Length (Z): {}", schem.length);
-    println!("  Volume:     {} blocks", schem.volume());
-    println!();
+    /// Efficiency enchantment level
+    #[arg(long, default_value_t = 0)]
+    efficiency: u8,
 
-    println!("{}", "--- Contents ---".yellow());
-    println!("  Total blocks:    {}", schem.blocks.len());
-    println!("  Solid blocks:    {}", schem.solid_blocks());
-    println!("  Unique types:    {}", schem.block_counts().len());
-    println!("  Block entities:  {}", schem.block_entities.len());
-    println!("  Entities:        {}", schem.entities.len());
-    println!();
+    /// Haste effect level
+    #[arg(long, default_value_t = 0)]
+    haste: u8,
+}
 
-    if schem.metadata.name.is_some() || schem.metadata.author.is_some() || schem.metadata.date.is_some() {
-        println!("{}", "--- Metadata ---".yellow());
-        if let Some(ref name) = schem.metadata.name {
-            println!("  Name:   {}", name);
-        }
-        if let Some(ref author) = schem.metadata.author {
-            println!("  Author: {}", author);
-        }
-        if let Some(date) = schem.metadata.date {
-            println!("  Date:   {}", format_timestamp(date));
-        }
-        if !schem.metadata.required_mods.is_empty() {
-            println!("  Mods:   {}", schem.metadata.required_mods.join(", "));
-        }
-    }
+#[derive(Args)]
+struct MinVersionArgs {
+    /// Path to the schematic file
+    file: PathBuf,
+}
 
-    Ok(())
+#[derive(Args)]
+struct PasteCheckArgs {
+    /// Path to the schematic file
+    file: PathBuf,
 }
 
-fn cmd_blocks(file: &PathBuf, no_air: bool, sort: bool, limit: Option<usize>) -> Result<()> {
-    let schem = UnifiedSchematic::load(file)?;
-    let mut counts: Vec<(String, usize)> = schem.block_counts().into_iter().collect();
+#[derive(Args)]
+struct FocusArgs {
+    /// Path to the schematic file
+    file: PathBuf,
 
-    if no_air {
-        counts.retain(|(name, _)| {
-            !matches!(name.as_str(), "minecraft:air" | "minecraft:cave_air" | "minecraft:void_air" | "air")
-        });
-    }
+    /// Write the build, cropped out of the terrain, to this file
+    #[arg(short, long)]
+    output: Option<PathBuf>,
 
-    if sort {
-        counts.sort_by(|a, b| b.1.cmp(&a.1));
-    } else {
-        counts.sort_by(|a, b| a.0.cmp(&b.0));
-    }
+    /// Also count blocks whose name contains PATTERN as terrain (repeatable)
+    #[arg(long, value_name = "PATTERN")]
+    natural_extra: Vec<String>,
 
-    let total: usize = counts.iter().map(|(_, c)| c).sum();
+    /// Count blocks whose name contains PATTERN as built, even if they'd be terrain (repeatable)
+    #[arg(long, value_name = "PATTERN")]
+    constructed_extra: Vec<String>,
 
-    let rows: Vec<BlockCount> = counts.iter()
-        .take(limit.unwrap_or(usize::MAX))
-        .map(|(name, count)| {
-            let percent = if total > 0 {
-                format!("{:.1}", (*count as f64 / total as f64) * 100.0)
-            } else {
-                "0.0".to_string()
-            };
-            BlockCount {
-                name: name.clone(),
-                count: *count,
-                percent,
-            }
-        })
-        .collect();
+    /// Leave out clusters of fewer built blocks than this
+    #[arg(long, default_value_t = 8)]
+    min_cluster: usize,
 
-    let table = Table::new(rows).with(Style::rounded()).to_string();
-    println!("{}", table);
+    /// Blocks of terrain to keep around the build
+    #[arg(long, default_value_t = 0)]
+    margin: u32,
+}
 
-    println!("\nTotal: {} blocks ({} types)", total, counts.len());
+#[derive(Args)]
+struct MetadataArgs {
+    /// Path to the schematic file
+    file: PathBuf,
 
-    Ok(())
+    /// Print as JSON
+    #[arg(long)]
+    json: bool,
 }
 
-fn cmd_palette(file: &PathBuf) -> Result<()> {
-    let schem = UnifiedSchematic::load(file)?;
+#[derive(Args)]
+#[command(group(clap::ArgGroup::new("fields").args(["name", "author", "date"]).required(true).multiple(true)))]
+struct SetMetadataArgs {
+    /// Path to the schematic file
+    file: PathBuf,
 
-    println!("{}", "=== Block Palette ===".bold().cyan());
-    println!();
+    /// New name
+    #[arg(long)]
+    name: Option<String>,
 
-    let unique = schem.unique_blocks();
-    for block in &unique {
-        if block.state.properties.is_empty() {
-            println!("  {}", block.name);
-        } else {
-            println!("  {}", block.full_name().green());
-            for (key, value) in &block.state.properties {
-                println!("    {} = {}", key.yellow(), value);
-            }
-        }
-    }
+    /// New author
+    #[arg(long)]
+    author: Option<String>,
 
-    println!("\nTotal: {} unique block states", unique.len());
+    /// New creation date: now, an ISO 8601 date or date and time, or
+    /// milliseconds since the Unix epoch
+    #[arg(long, value_parser = parse_date)]
+    date: Option<i64>,
 
-    Ok(())
+    /// Output file (.litematic, .schem or structure .nbt)
+    #[arg(short, long)]
+    output: PathBuf,
 }
 
-fn cmd_block_entities(file: &PathBuf, filter_type: Option<String>, verbose: bool) -> Result<()> {
-    let schem = UnifiedSchematic::load(file)?;
+#[derive(Args)]
+struct GetBlockArgs {
+    /// Path to the schematic file
+    file: PathBuf,
 
-    let entities: Vec<_> = schem.block_entities.iter()
-        .filter(|be| {
-            if let Some(ref filter) = filter_type {
-                be.id.to_lowercase().contains(&filter.to_lowercase())
-            } else {
-                true
-            }
-        })
-        .collect();
+    /// X coordinate
+    #[arg(short, allow_negative_numbers = true)]
+    x: i32,
 
-    if entities.is_empty() {
-        println!("No block entities found.");
-        return Ok(());
-    }
+    /// Y coordinate
+    #[arg(short, allow_negative_numbers = true)]
+    y: i32,
 
-    let rows: Vec<BlockEntityRow> = entities.iter().map(|be| {
-        let data = if verbose {
-            be.data.iter()
-                .map(|(k, v)| format!("{}={}", k, v))
-                .collect::<Vec<_>>()
-                .join(", ")
-        } else {
-            format!("{} fields", be.data.len())
-        };
+    /// Z coordinate
+    #[arg(short, allow_negative_numbers = true)]
+    z: i32,
 
-        BlockEntityRow {
-            entity_type: be.id.clone(),
-            position: format!("{}, {}, {}", be.pos.0, be.pos.1, be.pos.2),
-            data,
-        }
-    }).collect();
+    /// Interpret the coordinates as world positions (using the schematic's offset)
+    #[arg(short, long)]
+    world: bool,
+}
 
-    let table = Table::new(rows).with(Style::rounded()).to_string();
-    println!("{}", table);
+#[derive(Args)]
+struct SearchArgs {
+    /// Path to the schematic file
+    file: PathBuf,
 
-    println!("\nTotal: {} block entities", entities.len());
+    /// Block name patterns (partial match); a block matching any of them is shown
+    #[arg(required = true)]
+    patterns: Vec<String>,
 
-    Ok(())
+    /// Treat patterns as regular expressions on the full name (e.g. "^minecraft:(oak|spruce)_")
+    #[arg(long)]
+    regex: bool,
+
+    /// Show blocks matching none of the patterns instead
+    #[arg(long)]
+    invert: bool,
+
+    /// Show positions
+    #[arg(short, long)]
+    positions: bool,
+
+    /// Limit number of results
+    #[arg(short, long)]
+    limit: Option<usize>,
 }
 
-fn cmd_entities(file: &PathBuf, verbose: bool) -> Result<()> {
-    let schem = UnifiedSchematic::load(file)?;
+#[derive(Args)]
+struct ExportArgs {
+    /// Path to the schematic file
+    file: PathBuf,
 
-    if schem.entities.is_empty() {
-        println!("No entities found.");
-        return Ok(());
-    }
+    /// Output CSV file
+    #[arg(short, long)]
+    output: PathBuf,
+}
 
-    println!("{}", "=== Entities ===".bold().cyan());
-    println!();
+#[derive(Args)]
+struct HashArgs {
+    /// Schematic files
+    #[arg(required = true)]
+    files: Vec<PathBuf>,
+}
 
-    for entity in &schem.entities {
-        println!("  {} at ({:.1}, {:.1}, {:.1})",
-            entity.id.green(),
-            entity.pos.0, entity.pos.1, entity.pos.2
-        );
-        if verbose {
-            for (key, value) in &entity.data {
-                println!("    {}: {}", key.yellow(), value);
-            }
-        }
-    }
+#[derive(Args)]
+struct ShellArgs {
+    /// Path to the schematic file
+    file: PathBuf,
 
-    println!("\nTotal: {} entities", schem.entities.len());
+    /// Layers to keep, counted inward from the exposed faces
+    #[arg(short, long, default_value_t = 1)]
+    thickness: u8,
 
-    Ok(())
+    /// Output file (.litematic, .schem or structure .nbt)
+    #[arg(short, long)]
+    output: PathBuf,
+
+    /// Use stonecutter recipes for the material savings
+    #[arg(long)]
+    stonecutter: bool,
 }
 
-fn cmd_signs(file: &PathBuf) -> Result<()> {
-    let schem = UnifiedSchematic::load(file)?;
-    let signs = schem.get_signs();
+#[derive(Args)]
+struct ConvertArgs {
+    /// Path to the schematic file
+    file: PathBuf,
 
-    if signs.is_empty() {
-        println!("No signs with text found.");
-        return Ok(());
-    }
+    /// Output file (.litematic, .schem or structure .nbt)
+    #[arg(short, long)]
+    output: PathBuf,
+}
 
-    println!("{}", "=== Signs ===".bold().cyan());
-    println!();
+#[derive(Args)]
+struct ReanchorArgs {
+    /// Path to the schematic file
+    file: PathBuf,
 
-    for (i, (block_entity, text)) in signs.iter().enumerate() {
-        let pos = block_entity.pos;
-        println!("{}. Sign at ({}, {}, {})", (i + 1).to_string().bold(), pos.0, pos.1, pos.2);
+    /// New anchor: min (the minimum corner), center (middle of the bottom
+    /// layer) or a local position x,y,z
+    #[arg(long, value_parser = parse_anchor, allow_hyphen_values = true)]
+    origin: Anchor,
 
-        if !text.front.is_empty() {
+    /// Output file (.litematic, .schem or structure .nbt)
+    #[arg(short, long)]
+    output: PathBuf,
+}
+
+#[derive(Args)]
+struct VoxelizeArgs {
+    /// Path to the .obj file (its .mtl files and textures are read from
+    /// the same directory)
+    file: PathBuf,
+
+    /// Blocks along the model's longest side
+    #[arg(long, default_value_t = 64)]
+    size: u32,
+
+    /// Output file (.litematic, .schem or structure .nbt)
+    #[arg(short, long)]
+    output: PathBuf,
+
+    /// Blocks to match colors against
+    #[arg(long, value_name = "PALETTE", default_value_t = BlockPalette::Wool)]
+    palette: BlockPalette,
+
+    /// Fill the inside of closed models too, not just their surface
+    #[arg(long)]
+    solid: bool,
+
+    /// Block for parts of the model without a color
+    #[arg(long, default_value = "minecraft:white_concrete")]
+    block: String,
+}
+
+#[derive(Args)]
+struct FromImageArgs {
+    /// Path to the image (PNG, JPEG, ...)
+    file: PathBuf,
+
+    /// Output file (.litematic, .schem or structure .nbt)
+    #[arg(short, long)]
+    output: PathBuf,
+
+    /// Blocks to match colors against
+    #[arg(long, value_name = "PALETTE", default_value_t = BlockPalette::Concrete)]
+    palette: BlockPalette,
+
+    /// flat (on the ground, top of the image to the north) or wall
+    #[arg(long, default_value_t = schem_tool::pixel_art::Orientation::Flat)]
+    orientation: schem_tool::pixel_art::Orientation,
+
+    /// none or floyd-steinberg
+    #[arg(long, default_value_t = schem_tool::pixel_art::Dither::None)]
+    dither: schem_tool::pixel_art::Dither,
+
+    /// Scale the image down so neither side is longer than this many blocks
+    #[arg(long, value_name = "BLOCKS")]
+    max_size: Option<u32>,
+}
+
+#[derive(Args)]
+struct MakeSignArgs {
+    /// Up to four lines, separated by newlines or a literal \n
+    text: String,
+
+    /// Output file (.litematic, .schem or structure .nbt)
+    #[arg(short, long)]
+    output: PathBuf,
+
+    /// Wood type (oak, spruce, ..., bamboo, crimson, warped)
+    #[arg(long, value_name = "WOOD", default_value = "oak")]
+    sign: String,
+
+    /// Wall sign instead of a standing sign
+    #[arg(long)]
+    wall: bool,
+
+    /// Text1-Text4 fields and a 1.19.4 data version, for versions before 1.20
+    #[arg(long)]
+    legacy: bool,
+}
+
+#[derive(Args)]
+struct MakeTextArgs {
+    /// Letters, digits and basic punctuation; newlines or a literal \n start a new line
+    text: String,
+
+    /// Output file (.litematic, .schem or structure .nbt)
+    #[arg(short, long)]
+    output: PathBuf,
+
+    /// Block state to write with
+    #[arg(long, default_value = "minecraft:white_concrete")]
+    block: String,
+}
+
+#[derive(Args)]
+struct StripModsArgs {
+    /// Path to the schematic file
+    file: PathBuf,
+
+    /// TOML file mapping mod blocks to vanilla block states, with an
+    /// optional `placeholder` for the rest
+    #[arg(long)]
+    map: Option<PathBuf>,
+
+    /// Block for mod blocks the mapping doesn't name [default: minecraft:stone]
+    #[arg(long)]
+    placeholder: Option<String>,
+
+    /// Output file (.litematic, .schem or structure .nbt)
+    #[arg(short, long)]
+    output: PathBuf,
+
+    /// Also write the report of replacements as JSON
+    #[arg(long)]
+    report: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct DowngradeArgs {
+    /// Path to the schematic file
+    file: PathBuf,
+
+    /// Release to convert for, e.g. 1.16.5
+    #[arg(long)]
+    target: String,
+
+    /// Output file (.litematic, .schem or structure .nbt)
+    #[arg(short, long)]
+    output: PathBuf,
+
+    /// TOML file mapping blocks to substitutes (the `strip-mods` format), tried
+    /// before the builtin ones
+    #[arg(long)]
+    map: Option<PathBuf>,
+
+    /// Block for irreplaceable blocks with --force [default: minecraft:stone]
+    #[arg(long)]
+    placeholder: Option<String>,
+
+    /// Write the output even with irreplaceable blocks, replacing them with the placeholder
+    #[arg(long)]
+    force: bool,
+
+    /// Also write the report of substitutions as JSON
+    #[arg(long)]
+    report: Option<PathBuf>,
+}
+
+#[cfg(feature = "signing")]
+#[derive(Args)]
+struct SealArgs {
+    /// Path to the schematic file
+    file: PathBuf,
+
+    /// Private key (PKCS#8 PEM, e.g. from `openssl genpkey -algorithm ed25519`)
+    #[arg(long)]
+    key: PathBuf,
+
+    /// Output file (.litematic, .schem or structure .nbt)
+    #[arg(short, long)]
+    output: PathBuf,
+}
+
+#[cfg(feature = "signing")]
+#[derive(Args)]
+struct VerifyArgs {
+    /// Path to the schematic file
+    file: PathBuf,
+
+    /// Public key (SPKI PEM, e.g. from `openssl pkey -pubout`)
+    #[arg(long)]
+    pubkey: PathBuf,
+}
+
+#[cfg(feature = "server")]
+#[derive(Args)]
+struct ServeArgs {
+    /// Folder to serve, subfolders included
+    dir: PathBuf,
+
+    /// Port to listen on
+    #[arg(long, default_value_t = 8080)]
+    port: u16,
+
+    /// Address to listen on; 127.0.0.1 keeps it to this computer
+    #[arg(long, default_value = "0.0.0.0")]
+    bind: String,
+
+    /// Maximum blocks the viewer shows
+    #[arg(short, long, default_value_t = 100_000)]
+    max_blocks: usize,
+}
+
+#[derive(Args)]
+struct ExportPositionsArgs {
+    /// Path to the schematic file
+    file: PathBuf,
+
+    /// Block name pattern (partial match, as in search); repeat for several
+    #[arg(short, long = "pattern", value_name = "PATTERN", required = true)]
+    patterns: Vec<String>,
+
+    /// Output file (stdout if not given)
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Row format: csv (x,y,z,name,properties) or jsonl (one JSON object per line)
+    #[arg(long, value_enum, default_value = "csv")]
+    format: PositionFormat,
+
+    /// Shift positions by the schematic's stored offset into world coordinates
+    #[arg(long)]
+    world_coords: bool,
+}
+
+#[derive(Args)]
+struct MaterialsArgs {
+    /// Path to the schematic file
+    file: PathBuf,
+
+    /// Sort by count (descending)
+    #[arg(short, long)]
+    sort: bool,
+
+    /// Show intermediate crafting steps
+    #[arg(short, long)]
+    verbose: bool,
+
+    /// Limit number of results
+    #[arg(short, long)]
+    limit: Option<usize>,
+
+    /// Use stonecutter recipes (more efficient 1:1 ratios for stairs/slabs)
+    #[arg(long)]
+    stonecutter: bool,
+
+    /// Break dyes down to bone meal, ink sacs, cactus, lapis, cocoa beans and
+    /// flowers, and list the flowers needed
+    #[arg(long)]
+    consolidate_dyes: bool,
+
+    /// Don't count a water bucket for each waterlogged block
+    #[arg(long)]
+    no_waterlogged: bool,
+
+    /// Only count blocks with at least one exposed face (the visible shell)
+    #[arg(long)]
+    surface_only: bool,
+
+    /// Count barriers, structure voids and light blocks too
+    #[arg(long)]
+    include_technical: bool,
+
+    /// Bucket the materials into categories (wood, stone, glass, ...) with
+    /// subtotals; --verbose lists each category's materials
+    #[arg(long, value_name = "GROUPING")]
+    group_by: Option<CountGrouping>,
+
+    /// Print the materials as JSON
+    #[arg(long)]
+    json: bool,
+
+    /// Fail when some items have no known recipe (also SCHEM_TOOL_STRICT_RECIPES=1)
+    #[arg(long)]
+    strict: bool,
+
+    /// Fuel for the smelting summary: coal, lava or kelp (dried kelp blocks)
+    #[arg(long, value_name = "FUEL", default_value_t = Fuel::Coal)]
+    fuel: Fuel,
+}
+
+#[derive(Args)]
+struct MaterialsDiffArgs {
+    /// Schematic you already gathered materials for
+    old: PathBuf,
+
+    /// Schematic to compare against it
+    new: PathBuf,
+
+    /// Sort by amount (descending)
+    #[arg(short, long)]
+    sort: bool,
+
+    /// Limit number of results per section
+    #[arg(short, long)]
+    limit: Option<usize>,
+
+    /// Use stonecutter recipes (more efficient 1:1 ratios for stairs/slabs)
+    #[arg(long)]
+    stonecutter: bool,
+
+    /// Don't count a water bucket for each waterlogged block
+    #[arg(long)]
+    no_waterlogged: bool,
+
+    /// Print as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Args)]
+struct LayerArgs {
+    /// Path to the schematic file
+    file: PathBuf,
+
+    /// Coordinate to slice at: the Y level, or X/Z with --axis
+    #[arg(short)]
+    y: u32,
+
+    /// Axis to slice across; x and z show vertical cross-sections
+    #[arg(long, default_value_t = Axis::Y)]
+    axis: Axis,
+
+    /// Use simple ASCII characters
+    #[arg(short, long)]
+    ascii: bool,
+
+    /// Don't print the glyph legend after the grid
+    #[arg(long)]
+    legend_off: bool,
+
+    /// Draw blocks as colored cells (24-bit, or 256 colors unless COLORTERM says truecolor)
+    #[arg(long)]
+    color_blocks: bool,
+
+    /// Scale the slice down to fit this many columns (default: terminal width)
+    #[arg(long, value_name = "COLUMNS")]
+    max_width: Option<u32>,
+
+    /// With --color-blocks, color blocks by averaging their textures
+    #[arg(long)]
+    texture_colors: bool,
+
+    /// Path to Minecraft directory or client.jar
+    #[arg(long)]
+    minecraft: Option<PathBuf>,
+
+    /// Extract the textures from client.jar again instead of using the cache
+    #[arg(long)]
+    refresh_textures: bool,
+
+    /// Path to resource pack (ZIP file) for custom textures
+    #[arg(short, long)]
+    resource_pack: Option<PathBuf>,
+
+    /// Biome for grass/foliage/water tints (e.g. plains, jungle, swamp)
+    #[arg(long)]
+    biome: Option<Biome>,
+
+    /// TOML file mapping block names or patterns (e.g. "create:*_casing") to colors
+    #[arg(long, value_name = "FILE")]
+    colors: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct BuildOrderArgs {
+    /// Path to the schematic file
+    file: PathBuf,
+
+    /// Write to a file instead of stdout
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct SupportsArgs {
+    /// Path to the schematic file
+    file: PathBuf,
+
+    /// How far (in blocks) you can place from the grounded structure
+    #[arg(long, default_value_t = schem_tool::supports::DEFAULT_REACH)]
+    max_reach: u32,
+
+    /// Number of worst clusters to list
+    #[arg(short, long, default_value = "10")]
+    limit: usize,
+}
+
+#[derive(Args)]
+struct ProgressArgs {
+    /// Path to the schematic file
+    file: PathBuf,
+
+    /// World folder or its region folder
+    #[arg(long)]
+    world: PathBuf,
+
+    /// World position of the schematic's (0, 0, 0) corner, as x,y,z
+    #[arg(long, value_parser = parse_pos, allow_hyphen_values = true)]
+    origin: (i32, i32, i32),
+
+    /// Write the blocks still to place to a .litematic, .schem or structure .nbt
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Number of remaining block types to list
+    #[arg(short, long, default_value = "20")]
+    limit: usize,
+}
+
+#[derive(Args)]
+struct FromWorldArgs {
+    /// World folder or its region folder
+    #[arg(long)]
+    world: PathBuf,
+
+    /// One corner of the box, as x,y,z
+    #[arg(long, value_parser = parse_pos, allow_hyphen_values = true)]
+    min: (i32, i32, i32),
+
+    /// The opposite corner (inclusive), as x,y,z
+    #[arg(long, value_parser = parse_pos, allow_hyphen_values = true)]
+    max: (i32, i32, i32),
+
+    /// Output file; .litematic, .schem or .nbt picks the format
+    #[arg(short, long)]
+    output: PathBuf,
+}
+
+#[derive(Args)]
+struct LightingArgs {
+    /// Path to the schematic file
+    file: PathBuf,
+
+    /// Ignore sky light (for enclosed or underground farms)
+    #[arg(long)]
+    no_skylight: bool,
+
+    /// Print a map of every layer with dark spawnable floor
+    #[arg(short, long)]
+    maps: bool,
+}
+
+#[derive(Args)]
+struct RenderObjArgs {
+    /// Path to the schematic file
+    file: PathBuf,
+
+    /// Output OBJ file path
+    #[arg(short, long)]
+    output: PathBuf,
+
+    /// Only export visible (exposed) blocks
+    #[arg(long, overrides_with = "no_hollow")]
+    hollow: bool,
+
+    /// Turn off --hollow when the config file turns it on
+    #[arg(long, overrides_with = "hollow")]
+    no_hollow: bool,
+
+    /// Use greedy meshing to reduce polygon count (10-100x smaller files)
+    #[arg(short, long, overrides_with = "no_greedy")]
+    greedy: bool,
+
+    /// Turn off --greedy when the config file turns it on
+    #[arg(long, overrides_with = "greedy")]
+    no_greedy: bool,
+
+    /// Use Minecraft JSON models for accurate block geometry
+    #[arg(long, overrides_with = "no_models")]
+    models: bool,
+
+    /// Turn off --models when the config file turns it on
+    #[arg(long, overrides_with = "models")]
+    no_models: bool,
+
+    /// Split the OBJ into objects: material, chunk (16x16x16 regions) or none.
+    /// Blender imports each object separately; MeshLab merges them into one
+    /// layer. Greedy faces that cross a region border go with one region.
+    #[arg(long, value_name = "MODE", default_value_t = ObjGroups::Material)]
+    group_by: ObjGroups,
+
+    /// Extract and apply textures from Minecraft installation
+    #[arg(short, long, overrides_with = "no_textures")]
+    textures: bool,
+
+    /// Turn off --textures when the config file turns it on
+    #[arg(long, overrides_with = "textures")]
+    no_textures: bool,
+
+    /// Path to Minecraft directory or client.jar (e.g., ~/.minecraft or client.jar)
+    #[arg(short, long)]
+    minecraft: Option<PathBuf>,
+
+    /// Extract the textures from client.jar again instead of using the cache
+    #[arg(long)]
+    refresh_textures: bool,
+
+    /// Frame of animated textures (water, lava, magma, ...) to use, counting from 0
+    #[arg(long, value_name = "N", default_value_t = 0)]
+    anim_frame: usize,
+
+    /// Path to resource pack (ZIP file) for custom textures and models
+    #[arg(short, long)]
+    resource_pack: Option<PathBuf>,
+
+    /// Only export the box from this corner, as x,y,z in schematic coordinates
+    #[arg(long, value_parser = parse_pos, allow_hyphen_values = true)]
+    min: Option<(i32, i32, i32)>,
+
+    /// ... to this corner (inclusive); either defaults to the schematic's edge
+    #[arg(long, value_parser = parse_pos, allow_hyphen_values = true)]
+    max: Option<(i32, i32, i32)>,
+
+    /// Leave out blocks whose name contains PATTERN (repeatable, as in `search`);
+    /// the faces behind them show
+    #[arg(long, value_name = "PATTERN")]
+    exclude: Vec<String>,
+
+    /// Only export blocks whose name contains PATTERN (repeatable)
+    #[arg(long, value_name = "PATTERN")]
+    only: Vec<String>,
+
+    /// Biome for grass/foliage tints (e.g. plains, jungle, swamp)
+    #[arg(long)]
+    biome: Option<Biome>,
+
+    /// TOML file mapping block names or patterns (e.g. "create:*_casing") to colors
+    #[arg(long, value_name = "FILE")]
+    colors: Option<PathBuf>,
+
+    /// Write blocks without a known color to FILE, in --colors format
+    #[arg(long, value_name = "FILE")]
+    dump_unknown_colors: Option<PathBuf>,
+
+    /// Write the blocks that had no blockstate, model or texture to FILE as JSON
+    #[arg(long, value_name = "FILE")]
+    report: Option<PathBuf>,
+
+    /// Render barriers, structure voids and light blocks instead of leaving them out
+    #[arg(long)]
+    include_technical: bool,
+}
+
+#[derive(Args)]
+struct RenderHtmlArgs {
+    /// Path to the schematic file
+    file: PathBuf,
+
+    /// Output HTML file path
+    #[arg(short, long)]
+    output: PathBuf,
+
+    /// Maximum blocks to render (default: 100000)
+    #[arg(short, long, default_value = "100000")]
+    max_blocks: usize,
+
+    /// Color blocks by averaging their textures from the Minecraft installation
+    #[arg(long)]
+    texture_colors: bool,
+
+    /// Path to Minecraft directory or client.jar
+    #[arg(long)]
+    minecraft: Option<PathBuf>,
+
+    /// Extract the textures from client.jar again instead of using the cache
+    #[arg(long)]
+    refresh_textures: bool,
+
+    /// Path to resource pack (ZIP file) for custom textures
+    #[arg(short, long)]
+    resource_pack: Option<PathBuf>,
+
+    /// Only export the box from this corner, as x,y,z in schematic coordinates
+    #[arg(long, value_parser = parse_pos, allow_hyphen_values = true)]
+    min: Option<(i32, i32, i32)>,
+
+    /// ... to this corner (inclusive); either defaults to the schematic's edge
+    #[arg(long, value_parser = parse_pos, allow_hyphen_values = true)]
+    max: Option<(i32, i32, i32)>,
+
+    /// Leave out blocks whose name contains PATTERN (repeatable, as in `search`);
+    /// the faces behind them show
+    #[arg(long, value_name = "PATTERN")]
+    exclude: Vec<String>,
+
+    /// Only export blocks whose name contains PATTERN (repeatable)
+    #[arg(long, value_name = "PATTERN")]
+    only: Vec<String>,
+
+    /// Biome for grass/foliage/water tints (e.g. plains, jungle, swamp)
+    #[arg(long)]
+    biome: Option<Biome>,
+
+    /// TOML file mapping block names or patterns (e.g. "create:*_casing") to colors
+    #[arg(long, value_name = "FILE")]
+    colors: Option<PathBuf>,
+
+    /// Write blocks without a known color to FILE, in --colors format
+    #[arg(long, value_name = "FILE")]
+    dump_unknown_colors: Option<PathBuf>,
+
+    /// Render barriers, structure voids and light blocks instead of leaving them out
+    #[arg(long)]
+    include_technical: bool,
+}
+
+#[derive(Args)]
+struct RenderGltfArgs {
+    /// Path to the schematic file
+    file: PathBuf,
+
+    /// Output GLB file path
+    #[arg(short, long)]
+    output: PathBuf,
+
+    /// Only export visible (exposed) blocks
+    #[arg(long, overrides_with = "no_hollow")]
+    hollow: bool,
+
+    /// Turn off --hollow when the config file turns it on
+    #[arg(long, overrides_with = "hollow")]
+    no_hollow: bool,
+
+    /// Accepted for CLI compatibility (GLB uses instancing instead of greedy meshing)
+    #[arg(short, long, overrides_with = "no_greedy")]
+    greedy: bool,
+
+    /// Accepted for CLI compatibility, like --greedy
+    #[arg(long, overrides_with = "greedy")]
+    no_greedy: bool,
+
+    /// Use Minecraft JSON models for accurate block geometry
+    #[arg(long, overrides_with = "no_models")]
+    models: bool,
+
+    /// Turn off --models when the config file turns it on
+    #[arg(long, overrides_with = "models")]
+    no_models: bool,
+
+    /// Extract and apply textures from Minecraft installation
+    #[arg(short, long, overrides_with = "no_textures")]
+    textures: bool,
+
+    /// Turn off --textures when the config file turns it on
+    #[arg(long, overrides_with = "textures")]
+    no_textures: bool,
+
+    /// Path to Minecraft directory or client.jar
+    #[arg(short, long)]
+    minecraft: Option<PathBuf>,
+
+    /// Extract the textures from client.jar again instead of using the cache
+    #[arg(long)]
+    refresh_textures: bool,
+
+    /// Frame of animated textures (water, lava, magma, ...) to use, counting from 0
+    #[arg(long, value_name = "N", default_value_t = 0)]
+    anim_frame: usize,
+
+    /// Path to resource pack (ZIP file) for custom textures and models
+    #[arg(short, long)]
+    resource_pack: Option<PathBuf>,
+
+    /// Only export the box from this corner, as x,y,z in schematic coordinates
+    #[arg(long, value_parser = parse_pos, allow_hyphen_values = true)]
+    min: Option<(i32, i32, i32)>,
+
+    /// ... to this corner (inclusive); either defaults to the schematic's edge
+    #[arg(long, value_parser = parse_pos, allow_hyphen_values = true)]
+    max: Option<(i32, i32, i32)>,
+
+    /// Leave out blocks whose name contains PATTERN (repeatable, as in `search`);
+    /// the faces behind them show
+    #[arg(long, value_name = "PATTERN")]
+    exclude: Vec<String>,
+
+    /// Only export blocks whose name contains PATTERN (repeatable)
+    #[arg(long, value_name = "PATTERN")]
+    only: Vec<String>,
+
+    /// Exploded view: move each Y layer up by GAP blocks more than the one below
+    #[arg(long, value_name = "GAP", value_parser = parse_gap)]
+    explode: Option<f32>,
+
+    /// With --explode, move N layers at a time (floors N blocks tall)
+    #[arg(long, value_name = "N", default_value_t = 1, requires = "explode",
+          value_parser = clap::value_parser!(u32).range(1..))]
+    explode_every: u32,
+
+    /// Biome for grass/foliage tints (e.g. plains, jungle, swamp)
+    #[arg(long)]
+    biome: Option<Biome>,
+
+    /// TOML file mapping block names or patterns (e.g. "create:*_casing") to colors
+    #[arg(long, value_name = "FILE")]
+    colors: Option<PathBuf>,
+
+    /// Write the blocks that had no blockstate, model or texture to FILE as JSON
+    #[arg(long, value_name = "FILE")]
+    report: Option<PathBuf>,
+
+    /// Render barriers, structure voids and light blocks instead of leaving them out
+    #[arg(long)]
+    include_technical: bool,
+}
+
+#[derive(Args)]
+struct BatchArgs {
+    /// Directory to scan for .schem/.schematic/.litematic/.nbt files
+    dir: PathBuf,
+
+    /// Analysis to run on each file
+    #[arg(short, long, value_enum, default_value = "info")]
+    command: BatchCommand,
+
+    /// Output format
+    #[arg(short, long, value_enum, default_value = "json")]
+    format: BatchFormat,
+
+    /// Write to a file instead of stdout
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Scan subdirectories too
+    #[arg(short, long)]
+    recursive: bool,
+
+    /// Number of files to load in parallel (default: number of CPUs)
+    #[arg(short, long)]
+    jobs: Option<usize>,
+
+    /// Use stonecutter recipes for the materials analysis
+    #[arg(long)]
+    stonecutter: bool,
+}
+
+#[derive(Args)]
+struct CatalogArgs {
+    /// Directory to scan for .schem/.schematic/.litematic/.nbt files
+    dir: PathBuf,
+
+    /// Write to a file instead of stdout
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Scan subdirectories too
+    #[arg(short, long)]
+    recursive: bool,
+
+    /// Number of files to load in parallel (default: number of CPUs)
+    #[arg(short, long)]
+    jobs: Option<usize>,
+}
+
+#[derive(Args)]
+struct WatchArgs {
+    /// Path to the schematic file
+    file: PathBuf,
+
+    /// Export command to run (render-obj, render-html, render-gltf)
+    #[arg(short, long)]
+    exec: String,
+
+    /// Output file passed to the export command
+    #[arg(short, long)]
+    output: PathBuf,
+
+    /// How long the file must stay unchanged before exporting (ms)
+    #[arg(long, default_value = "500")]
+    settle_ms: u64,
+
+    /// Extra flags for the export command (after --)
+    #[arg(last = true)]
+    args: Vec<String>,
+}
+
+#[derive(Args)]
+struct ConfigArgs {
+    #[command(subcommand)]
+    action: ConfigAction,
+}
+
+#[derive(Args)]
+struct CompletionsArgs {
+    /// Shell to generate completions for
+    #[arg(value_enum)]
+    shell: clap_complete::Shell,
+}
+
+#[derive(Args)]
+struct ManArgs {
+    /// Write to a file instead of stdout
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct DebugArgs {
+    /// Path to the schematic file
+    file: PathBuf,
+
+    /// Only show the value at this path (e.g. Regions.main.BlockStates or Entities[0])
+    #[arg(long)]
+    path: Option<String>,
+
+    /// Show every list entry and array value instead of the first few
+    #[arg(long)]
+    full: bool,
+
+    /// How to print byte, int and long array contents
+    #[arg(long, value_enum, default_value_t = nbt_dump::ArrayFormat::None)]
+    arrays: nbt_dump::ArrayFormat,
+
+    /// Levels of compounds and lists to expand
+    #[arg(long)]
+    depth: Option<usize>,
+
+    /// Write the (selected) structure as SNBT to FILE instead, complete and with sorted keys
+    #[arg(short, long, value_name = "FILE")]
+    output: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum ConfigAction {
+    /// Print the effective configuration and where it was loaded from
+    Show,
+}
+
+#[derive(Subcommand)]
+enum BlockEntityAction {
+    /// Print the block entity at a position as SNBT, with its id and position
+    Get {
+        /// Path to the schematic file
+        file: PathBuf,
+
+        /// X coordinate
+        #[arg(short, allow_negative_numbers = true)]
+        x: i32,
+
+        /// Y coordinate
+        #[arg(short, allow_negative_numbers = true)]
+        y: i32,
+
+        /// Z coordinate
+        #[arg(short, allow_negative_numbers = true)]
+        z: i32,
+
+        /// Interpret the coordinates as world positions (using the schematic's offset)
+        #[arg(short, long)]
+        world: bool,
+
+        /// Write the SNBT to a file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Replace the block entity at a position with SNBT from a file, adding it if
+    /// there is none (the SNBT then needs an id)
+    Set {
+        /// Path to the schematic file
+        file: PathBuf,
+
+        /// X coordinate
+        #[arg(short, allow_negative_numbers = true)]
+        x: i32,
+
+        /// Y coordinate
+        #[arg(short, allow_negative_numbers = true)]
+        y: i32,
+
+        /// Z coordinate
+        #[arg(short, allow_negative_numbers = true)]
+        z: i32,
+
+        /// Interpret the coordinates as world positions (using the schematic's offset)
+        #[arg(short, long)]
+        world: bool,
+
+        /// SNBT compound to use; position fields in it are ignored
+        #[arg(short, long)]
+        input: PathBuf,
+
+        /// Output file (.litematic, .schem or structure .nbt)
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum BatchCommand {
+    /// Dimensions, block and entity totals, name and author
+    Info,
+    /// Block counts
+    Blocks,
+    /// Raw materials needed
+    Materials,
+    /// Content and shape hashes (see `hash`)
+    Hash,
+}
+
+/// How `blocks` and `materials` group their rows
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum CountGrouping {
+    /// Wood, stone, ores & metals, glass, colored blocks by color, redstone,
+    /// plants, misc and other
+    Category,
+}
+
+/// How `blocks --aggregate` folds variants together
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum AggregateBy {
+    /// The sixteen colors of wool, concrete, terracotta, glass, beds, ...
+    Color,
+    /// Wood species: oak, spruce, ..., crimson and warped
+    Material,
+    /// Stairs, slabs, walls, fences, doors, ..., full blocks and other
+    Shape,
+}
+
+impl AggregateBy {
+    fn aggregation(self) -> schem_tool::Aggregation {
+        match self {
+            AggregateBy::Color => schem_tool::Aggregation::Color,
+            AggregateBy::Material => schem_tool::Aggregation::Material,
+            AggregateBy::Shape => schem_tool::Aggregation::Shape,
+        }
+    }
+}
+
+/// Row format of `export-positions`
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum PositionFormat {
+    /// Comma-separated x,y,z,name,properties under a header row
+    Csv,
+    /// One JSON object per line
+    Jsonl,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum BatchFormat {
+    Json,
+    Csv,
+}
+
+#[derive(Tabled)]
+struct BlockCount {
+    #[tabled(rename = "Block")]
+    name: String,
+    #[tabled(rename = "Count")]
+    count: u64,
+    #[tabled(rename = "%")]
+    percent: String,
+}
+
+#[derive(Tabled)]
+struct BlockEntityRow {
+    #[tabled(rename = "Type")]
+    entity_type: String,
+    #[tabled(rename = "Position")]
+    position: String,
+    #[tabled(rename = "Data")]
+    data: String,
+}
+
+#[derive(Tabled)]
+struct SpawnerRow {
+    #[tabled(rename = "Position")]
+    position: String,
+    #[tabled(rename = "Entity")]
+    entity: String,
+    #[tabled(rename = "Delay")]
+    delay: String,
+    #[tabled(rename = "Count")]
+    count: String,
+    #[tabled(rename = "Max nearby")]
+    max_nearby: String,
+    #[tabled(rename = "Player range")]
+    player_range: String,
+    #[tabled(rename = "Potentials")]
+    potentials: usize,
+}
+
+#[derive(Tabled)]
+struct MiningRow {
+    #[tabled(rename = "Block")]
+    block: String,
+    #[tabled(rename = "Count")]
+    count: usize,
+    #[tabled(rename = "Each")]
+    each: String,
+    #[tabled(rename = "Total")]
+    total: String,
+    #[tabled(rename = "Drops")]
+    drops: &'static str,
+}
+
+#[derive(Tabled)]
+struct JigsawRow {
+    #[tabled(rename = "Position")]
+    position: String,
+    #[tabled(rename = "Orientation")]
+    orientation: String,
+    #[tabled(rename = "Name")]
+    name: String,
+    #[tabled(rename = "Target")]
+    target: String,
+    #[tabled(rename = "Pool")]
+    pool: String,
+    #[tabled(rename = "Final state")]
+    final_state: String,
+    #[tabled(rename = "Joint")]
+    joint: String,
+}
+
+/// Route status messages to stderr; stdout carries only command output
+fn init_logging(quiet: bool, log_level: Option<log::LevelFilter>) {
+    use log::{Level, LevelFilter};
+    use std::io::Write;
+
+    let level = if quiet { LevelFilter::Error } else { log_level.unwrap_or(LevelFilter::Info) };
+
+    env_logger::Builder::new()
+        // Keep dependencies quiet unless something is wrong
+        .filter_level(LevelFilter::Warn.min(level))
+        .filter_module("schem_tool", level)
+        .format(|buf, record| match record.level() {
+            Level::Error => writeln!(buf, "{} {}", "Error:".red().bold(), record.args()),
+            Level::Warn => writeln!(buf, "{} {}", "Warning:".yellow(), record.args()),
+            _ => writeln!(buf, "{}", record.args()),
+        })
+        .init();
+}
+
+/// Load a schematic, logging format and timing at debug level
+fn load_schematic(file: &Path) -> Result<UnifiedSchematic> {
+    let started = std::time::Instant::now();
+    let lenient = LENIENT.load(Ordering::Relaxed);
+    let options = LoadOptions::new()
+        .with_lenient(lenient)
+        .with_keep_out_of_bounds(KEEP_OUT_OF_BOUNDS.load(Ordering::Relaxed));
+    let schem = match TIMINGS.time("Loading schematic", || UnifiedSchematic::load_with_options(file, &options)) {
+        Err(e @ SchemError::CorruptData { .. }) if !lenient => {
+            return Err(anyhow::Error::new(e).context(format!("Failed to load {} (use --lenient to fill unreadable blocks with air)", file.display())));
+        }
+        result => result?,
+    };
+    log::debug!(
+        "Loaded {} ({:?}, {}) in {:.2}s",
+        file.display(), schem.format, schem.dimensions_str(), started.elapsed().as_secs_f64()
+    );
+    check_load_report(file, schem.load_report())?;
+    Ok(schem)
+}
+
+/// Warn about block entities that can crash the game on paste, or fail with `--strict-block-entities`
+fn check_load_report(file: &Path, report: &schem_tool::LoadReport) -> Result<()> {
+    if report.buffered {
+        log::debug!("{} couldn't be streamed and was parsed from memory", file.display());
+    }
+    let mut issues = Vec::new();
+    if report.duplicate_block_entities > 0 {
+        issues.push(format!("{} duplicate block entities (kept the last at each position)", report.duplicate_block_entities));
+    }
+    if report.orphaned_block_entities > 0 {
+        issues.push(format!("{} block entities on air or outside the schematic", report.orphaned_block_entities));
+    }
+    if issues.is_empty() {
+        return Ok(());
+    }
+    if STRICT.load(Ordering::Relaxed) {
+        anyhow::bail!("{}: {}", file.display(), issues.join(", "));
+    }
+    for issue in issues {
+        log::warn!("{}: {}", file.display(), issue);
+    }
+    Ok(())
+}
+
+/// Progress bars for an exporter (unless status output is suppressed), timing
+/// its phases for `--timings`
+fn export_progress(console: &ConsoleProgress) -> TimedProgress<'_> {
+    let bars: &dyn Progress = if log::log_enabled!(log::Level::Info) { console } else { &NoProgress };
+    TimedProgress::new(bars, &TIMINGS)
+}
+
+/// Export options reporting to `progress`
+fn export_options<'a>(
+    progress: &'a TimedProgress<'a>,
+    colors: &'a ColorMap,
+    source: &'a RenderSource,
+    region: Option<ExportRegion>,
+) -> ExportOptions<'a> {
+    let mut options = ExportOptions::new()
+        .with_colors(colors)
+        .with_technical(source.include_technical)
+        .with_obj_groups(source.obj_groups)
+        .with_random(SEED.get().copied().unwrap_or_default());
+    if let Some(region) = region {
+        options = options.with_region(region);
+    }
+    if let Some(only) = &source.only {
+        options = options.with_only(only);
+    }
+    if let Some(exclude) = &source.exclude {
+        options = options.with_exclude(exclude);
+    }
+    if let Some(explode) = source.explode {
+        options = options.with_explode(explode);
+    }
+    options.with_progress(progress)
+}
+
+/// Parse a colors file: block names or patterns mapped to "#rrggbb" or [r, g, b] (0-255)
+fn parse_colors(text: &str) -> Result<ColorMap> {
+    let table: toml::Table = text.parse()?;
+    let mut colors = ColorMap::new();
+    for (pattern, value) in &table {
+        let rgb = match value {
+            toml::Value::String(hex) => hex.strip_prefix('#')
+                .filter(|h| h.len() == 6)
+                .and_then(|h| u32::from_str_radix(h, 16).ok())
+                .map(|v| [(v >> 16) as u8, (v >> 8) as u8, v as u8]),
+            toml::Value::Array(items) if items.len() == 3 => items.iter()
+                .map(|i| i.as_integer().and_then(|i| u8::try_from(i).ok()))
+                .collect::<Option<Vec<u8>>>()
+                .map(|v| [v[0], v[1], v[2]]),
+            _ => None,
+        };
+        let [r, g, b] = rgb.ok_or_else(|| anyhow::anyhow!("{}: expected \"#rrggbb\" or [r, g, b] with values 0-255", pattern))?;
+        colors.insert(pattern, (r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0));
+    }
+    Ok(colors)
+}
+
+/// Mapping file for `strip-mods` and `downgrade`
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ModMapFile {
+    placeholder: Option<String>,
+    #[serde(default)]
+    blocks: BTreeMap<String, String>,
+}
+
+/// Parse a mod mapping file: an optional `placeholder` and a `[blocks]` table
+/// of mod block names mapped to vanilla block states
+fn parse_mod_mapping(text: &str) -> Result<schem_tool::mods::ModMapping> {
+    let file: ModMapFile = toml::from_str(text)?;
+    let mut mapping = schem_tool::mods::ModMapping::new();
+    if let Some(placeholder) = &file.placeholder {
+        mapping = mapping.with_placeholder(placeholder);
+    }
+    for (modded, vanilla) in &file.blocks {
+        mapping = mapping.with_substitute(modded, vanilla);
+    }
+    Ok(mapping)
+}
+
+/// Colors for a render command, with overrides from `--colors` if given
+fn load_colors(path: Option<&Path>) -> Result<ColorMap> {
+    let Some(path) = path else { return Ok(ColorMap::new()) };
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read colors file {}", path.display()))?;
+    parse_colors(&text).with_context(|| format!("Invalid colors file {}", path.display()))
+}
+
+/// Colors for a render command: the `--colors` file over averaged texture colors,
+/// if textures are loaded, over the builtin table
+fn render_colors(schem: &UnifiedSchematic, source: &RenderSource, textures: Option<&TextureManager>) -> Result<ColorMap> {
+    let mut colors = load_colors(source.colors)?;
+    if let Some(tm) = textures {
+        let names: BTreeSet<&str> = schem.blocks.iter()
+            .filter(|b| !b.is_air())
+            .map(|b| b.name.as_str())
+            .collect();
+        let averaged = colors.add_texture_colors(tm, names);
+        log::debug!("Averaged texture colors for {} block types", averaged);
+        if let Err(e) = tm.save_color_cache() {
+            log::debug!("Could not save texture colors: {}", e);
+        }
+    }
+    Ok(colors)
+}
+
+/// Write the blocks that fell back to gray as a colors file to fill in
+fn dump_unknown_colors(colors: &ColorMap, path: &Path) -> Result<()> {
+    let unknown = colors.unknown();
+    let (r, g, b) = schem_tool::export3d::DEFAULT_COLOR;
+    let gray = format!("#{:02x}{:02x}{:02x}", (r * 255.0).round() as u8, (g * 255.0).round() as u8, (b * 255.0).round() as u8);
+    let mut text = String::from("# Blocks without a known color; edit and pass with --colors\n");
+    for name in &unknown {
+        text.push_str(&format!("\"{}\" = \"{}\"\n", name, gray));
+    }
+    std::fs::write(path, text).with_context(|| format!("Failed to write {}", path.display()))?;
+    log::info!("  Unknown colors: {} blocks written to {}", unknown.len(), path.display());
+    Ok(())
+}
+
+/// Write `--report`: the blocks an export found without a blockstate, model or texture
+fn write_export_report(report: &ExportReport, path: &Path) -> Result<()> {
+    let json = serde_json::to_string_pretty(&report.to_json())?;
+    std::fs::write(path, json + "\n").with_context(|| format!("Failed to write {}", path.display()))?;
+    log::info!("  Missing blocks report: {}", path.display());
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    init_logging(cli.quiet, cli.log_level);
+    let config = Config::load(cli.config.as_deref())?;
+
+    let no_color = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+    let color = cli.color.or(config.color).unwrap_or_default();
+    colored::control::set_override(color.enabled(no_color, std::io::IsTerminal::is_terminal(&std::io::stdout())));
+    PLAIN.store(cli.plain || config.plain.unwrap_or(false) || !utf8_locale(), Ordering::Relaxed);
+    LENIENT.store(cli.lenient, Ordering::Relaxed);
+    STRICT.store(cli.strict_block_entities, Ordering::Relaxed);
+    KEEP_OUT_OF_BOUNDS.store(cli.keep_out_of_bounds, Ordering::Relaxed);
+    NO_PREVIEW.store(cli.no_preview, Ordering::Relaxed);
+    if let Some(image) = cli.preview_from {
+        let _ = PREVIEW_FROM.set(image);
+    }
+    let _ = SANITIZE.set(schem_tool::sanitize::SanitizeOptions {
+        clear_inventories: cli.clear_inventories,
+        strip_signs: cli.strip_signs,
+        strip_names: cli.strip_names,
+        remove: schem_tool::BlockSelector::new(&cli.strip_block_entities),
+    });
+    if let Some(seed) = cli.seed {
+        log::info!("Using seed {}", seed);
+        let _ = SEED.set(seed);
+    }
+
+    let started = std::time::Instant::now();
+    let result = run(cli.command, &config);
+    if cli.timings {
+        print_timings(started.elapsed());
+    }
+    result
+}
+
+#[derive(Tabled)]
+struct TimingRow {
+    #[tabled(rename = "Phase")]
+    phase: String,
+    #[tabled(rename = "Runs")]
+    runs: usize,
+    #[tabled(rename = "Time")]
+    time: String,
+    #[tabled(rename = "Share")]
+    share: String,
+}
+
+/// The `--timings` summary, on stderr so it doesn't mix with the command's output
+fn print_timings(elapsed: std::time::Duration) {
+    let share = |duration: std::time::Duration| format!("{:.1}%", duration.as_secs_f64() * 100.0 / elapsed.as_secs_f64().max(f64::EPSILON));
+    let row = |phase: String, runs: usize, duration: std::time::Duration| TimingRow {
+        phase,
+        runs,
+        time: format!("{:.3} s", duration.as_secs_f64()),
+        share: share(duration),
+    };
+    let mut rows: Vec<TimingRow> = TIMINGS.phases().into_iter().map(|phase| row(phase.name, phase.count, phase.duration)).collect();
+    rows.push(row("Other".to_string(), 1, elapsed.saturating_sub(TIMINGS.total())));
+    rows.push(row("Total".to_string(), 1, elapsed));
+    eprintln!("{}", render_table(rows));
+    match schem_tool::timings::peak_rss() {
+        Some(bytes) => eprintln!("Peak memory: {:.1} MB", bytes as f64 / 1024.0 / 1024.0),
+        None => eprintln!("Peak memory: not available on this platform"),
+    }
+}
+
+/// A render switch from its --X and --no-X flags, falling back to the config
+fn render_flag(on: bool, off: bool, config: Option<bool>) -> bool {
+    let flag = (on || off).then_some(on);
+    flag.or(config).unwrap_or(false)
+}
+
+fn run(command: Commands, config: &Config) -> Result<()> {
+    let render = &config.render;
+    match command {
+        Commands::Info(InfoArgs { file, top, json }) => cmd_info(&file, top, json)?,
+        Commands::Blocks(BlocksArgs { file, no_air, sort, limit, as_items, waterlogged, surface_only, group_by, aggregate, verbose, json }) => {
+            let counting = CountOptions { as_items, surface_only, waterlogged, technical: true };
+            let style = ListStyle { sort, limit, group_by, verbose, json };
+            cmd_blocks(&file, no_air, counting, style, aggregate.map(AggregateBy::aggregation))?
+        }
+        Commands::Palette(PaletteArgs { file, selectors, count }) => cmd_palette(&file, selectors, count)?,
+        Commands::BlockEntities(BlockEntitiesArgs { file, entity_type, verbose }) => cmd_block_entities(&file, entity_type, verbose)?,
+        Commands::BlockEntity(BlockEntityArgs { action: BlockEntityAction::Get { file, x, y, z, world, output } }) => {
+            cmd_block_entity_get(&file, (x, y, z), world, output.as_deref())?
+        }
+        Commands::BlockEntity(BlockEntityArgs { action: BlockEntityAction::Set { file, x, y, z, world, input, output } }) => {
+            cmd_block_entity_set(&file, (x, y, z), world, &input, &output)?
+        }
+        Commands::Entities(EntitiesArgs { file, entity_type, count_only, verbose }) => {
+            cmd_entities(&file, entity_type, count_only, verbose)?
+        }
+        Commands::Spawners(SpawnersArgs { file }) => cmd_spawners(&file)?,
+        Commands::Beacons(BeaconsArgs { file }) => cmd_beacons(&file)?,
+        Commands::Signs(SignsArgs { file }) => cmd_signs(&file)?,
+        Commands::Jigsaws(JigsawsArgs { file }) => cmd_jigsaws(&file)?,
+        Commands::MinVersion(MinVersionArgs { file }) => cmd_min_version(&file)?,
+        Commands::PasteCheck(PasteCheckArgs { file }) => cmd_paste_check(&file)?,
+        Commands::Focus(FocusArgs { file, output, natural_extra, constructed_extra, min_cluster, margin }) => {
+            let options = schem_tool::focus::FocusOptions {
+                natural_extra: BlockSelector::new(&natural_extra),
+                constructed_extra: BlockSelector::new(&constructed_extra),
+                min_cluster,
+                margin,
+            };
+            cmd_focus(&file, output.as_deref(), &options)?
+        }
+        Commands::Mining(MiningArgs { file, tool, efficiency, haste }) => {
+            cmd_mining(&file, &schem_tool::mining::MiningOptions { tool, efficiency, haste })?
+        }
+        Commands::Metadata(MetadataArgs { file, json }) => cmd_metadata(&file, json)?,
+        Commands::SetMetadata(SetMetadataArgs { file, name, author, date, output }) => {
+            cmd_set_metadata(&file, name.as_deref(), author.as_deref(), date, &output)?
+        }
+        Commands::GetBlock(GetBlockArgs { file, x, y, z, world }) => cmd_get_block(&file, (x, y, z), world)?,
+        Commands::Search(SearchArgs { file, patterns, regex, invert, positions, limit }) => {
+            cmd_search(&file, &patterns, regex, invert, positions, limit)?
+        }
+        Commands::Export(ExportArgs { file, output }) => cmd_export(&file, &output)?,
+        Commands::Hash(HashArgs { files }) => cmd_hash(&files)?,
+        Commands::Shell(ShellArgs { file, thickness, output, stonecutter }) => cmd_shell(&file, thickness, &output, stonecutter)?,
+        Commands::Convert(ConvertArgs { file, output }) => cmd_convert(&file, &output)?,
+        Commands::Reanchor(ReanchorArgs { file, origin, output }) => cmd_reanchor(&file, origin, &output)?,
+        Commands::Voxelize(VoxelizeArgs { file, size, output, palette, solid, block }) => {
+            cmd_voxelize(&file, size, &output, palette, solid, &block)?
+        }
+        Commands::FromImage(FromImageArgs { file, output, palette, orientation, dither, max_size }) => {
+            let options = schem_tool::pixel_art::PixelArtOptions { palette, orientation, dither, max_size };
+            cmd_from_image(&file, &output, &options)?
+        }
+        Commands::MakeSign(MakeSignArgs { text, output, sign, wall, legacy }) => {
+            let options = schem_tool::labels::SignOptions { wood: sign, wall, legacy };
+            cmd_make_sign(&text, &output, &options)?
+        }
+        Commands::MakeText(MakeTextArgs { text, output, block }) => cmd_make_text(&text, &output, &block)?,
+        Commands::Downgrade(DowngradeArgs { file, target, output, map, placeholder, force, report }) => {
+            let mapping = load_mod_mapping(map.as_deref(), placeholder.as_deref())?;
+            cmd_downgrade(&file, &target, &mapping, force, &output, report.as_deref())?
+        }
+        Commands::StripMods(StripModsArgs { file, map, placeholder, output, report }) => {
+            cmd_strip_mods(&file, map.as_deref(), placeholder.as_deref(), &output, report.as_deref())?
+        }
+        #[cfg(feature = "signing")]
+        Commands::Seal(SealArgs { file, key, output }) => cmd_seal(&file, &key, &output)?,
+        #[cfg(feature = "signing")]
+        Commands::Verify(VerifyArgs { file, pubkey }) => cmd_verify(&file, &pubkey)?,
+        #[cfg(feature = "server")]
+        Commands::Serve(ServeArgs { dir, port, bind, max_blocks }) => cmd_serve(&dir, &bind, port, max_blocks)?,
+        Commands::ExportPositions(ExportPositionsArgs { file, patterns, output, format, world_coords }) => {
+            cmd_export_positions(&file, &patterns, output.as_deref(), format, world_coords)?
+        }
+        Commands::Materials(MaterialsArgs { file, sort, verbose, limit, stonecutter, consolidate_dyes, no_waterlogged, surface_only, include_technical, group_by, json, strict, fuel }) => {
+            let counting = CountOptions { as_items: false, surface_only, waterlogged: !no_waterlogged, technical: include_technical };
+            let recipes = MaterialOptions::new().with_stonecutter(stonecutter).with_consolidated_dyes(consolidate_dyes);
+            let strict = strict || std::env::var_os("SCHEM_TOOL_STRICT_RECIPES").is_some_and(|v| !v.is_empty());
+            cmd_materials(&file, recipes, counting, ListStyle { sort, limit, group_by, verbose, json }, strict, fuel)?
+        }
+        Commands::BuildOrder(BuildOrderArgs { file, output }) => cmd_build_order(&file, output.as_deref())?,
+        Commands::Supports(SupportsArgs { file, max_reach, limit }) => cmd_supports(&file, max_reach, limit)?,
+        Commands::Progress(ProgressArgs { file, world, origin, output, limit }) => cmd_progress(&file, &world, origin, output.as_deref(), limit)?,
+        Commands::FromWorld(FromWorldArgs { world, min, max, output }) => cmd_from_world(&world, min, max, &output)?,
+        Commands::Lighting(LightingArgs { file, no_skylight, maps }) => cmd_lighting(&file, !no_skylight, maps)?,
+        Commands::MaterialsDiff(MaterialsDiffArgs { old, new, sort, limit, stonecutter, no_waterlogged, json }) => {
+            cmd_materials_diff(&old, &new, sort, limit, stonecutter, !no_waterlogged, json)?
+        }
+        Commands::Layer(LayerArgs { file, y, axis, ascii, legend_off, color_blocks, max_width, texture_colors, minecraft, refresh_textures, resource_pack, biome, colors }) => {
+            let minecraft = minecraft.or_else(|| config.minecraft.clone());
+            let resource_pack = resource_pack.or_else(|| config.resource_pack.clone());
+            let source = RenderSource {
+                minecraft: minecraft.as_deref(),
+                resource_pack: resource_pack.as_deref(),
+                refresh_textures,
+                anim_frame: 0,
+                biome: biome.or(config.biome()).unwrap_or_default(),
+                colors: colors.as_deref(),
+                dump_unknown_colors: None,
+                report: None,
+                obj_groups: ObjGroups::default(),
+                region_min: None,
+                region_max: None,
+                only: None,
+                exclude: None,
+                explode: None,
+                include_technical: true,
+            };
+            let style = LayerStyle {
+                ascii: ascii || plain(),
+                legend_off,
+                max_width,
+                color_blocks: color_blocks.then_some((&source, texture_colors)),
+            };
+            cmd_layer(&file, axis, y, &style)?
+        }
+        Commands::RenderObj(RenderObjArgs { file, output, hollow, no_hollow, greedy, no_greedy, models, no_models, group_by, textures, no_textures, minecraft, refresh_textures, anim_frame, resource_pack, min, max, exclude, only, biome, colors, dump_unknown_colors, report, include_technical }) => {
+            let minecraft = minecraft.or_else(|| config.minecraft.clone());
+            let resource_pack = resource_pack.or_else(|| config.resource_pack.clone());
+            let source = RenderSource {
+                minecraft: minecraft.as_deref(),
+                resource_pack: resource_pack.as_deref(),
+                refresh_textures,
+                anim_frame,
+                biome: biome.or(config.biome()).unwrap_or_default(),
+                colors: colors.as_deref(),
+                dump_unknown_colors: dump_unknown_colors.as_deref(),
+                report: report.as_deref(),
+                obj_groups: group_by,
+                region_min: min,
+                region_max: max,
+                only: (!only.is_empty()).then(|| BlockSelector::new(&only)),
+                exclude: (!exclude.is_empty()).then(|| BlockSelector::new(&exclude)),
+                explode: None,
+                include_technical,
+            };
+            cmd_render_obj(
+                &file, &output,
+                render_flag(hollow, no_hollow, render.hollow),
+                render_flag(greedy, no_greedy, render.greedy),
+                render_flag(models, no_models, render.models),
+                render_flag(textures, no_textures, render.textures),
+                &source,
+            )?
+        }
+        Commands::RenderHtml(RenderHtmlArgs { file, output, max_blocks, texture_colors, minecraft, refresh_textures, resource_pack, min, max, exclude, only, biome, colors, dump_unknown_colors, include_technical }) => {
+            let minecraft = minecraft.or_else(|| config.minecraft.clone());
+            let resource_pack = resource_pack.or_else(|| config.resource_pack.clone());
+            let source = RenderSource {
+                minecraft: minecraft.as_deref(),
+                resource_pack: resource_pack.as_deref(),
+                refresh_textures,
+                anim_frame: 0,
+                biome: biome.or(config.biome()).unwrap_or_default(),
+                colors: colors.as_deref(),
+                dump_unknown_colors: dump_unknown_colors.as_deref(),
+                report: None,
+                obj_groups: ObjGroups::default(),
+                region_min: min,
+                region_max: max,
+                only: (!only.is_empty()).then(|| BlockSelector::new(&only)),
+                exclude: (!exclude.is_empty()).then(|| BlockSelector::new(&exclude)),
+                explode: None,
+                include_technical,
+            };
+            cmd_render_html(&file, &output, max_blocks, texture_colors, &source)?
+        }
+        Commands::RenderGltf(RenderGltfArgs { file, output, hollow, no_hollow, greedy: _, no_greedy: _, models, no_models, textures, no_textures, minecraft, refresh_textures, anim_frame, resource_pack, min, max, exclude, only, explode, explode_every, biome, colors, report, include_technical }) => {
+            let minecraft = minecraft.or_else(|| config.minecraft.clone());
+            let resource_pack = resource_pack.or_else(|| config.resource_pack.clone());
+            let source = RenderSource {
+                minecraft: minecraft.as_deref(),
+                resource_pack: resource_pack.as_deref(),
+                refresh_textures,
+                anim_frame,
+                biome: biome.or(config.biome()).unwrap_or_default(),
+                colors: colors.as_deref(),
+                dump_unknown_colors: None,
+                report: report.as_deref(),
+                obj_groups: ObjGroups::default(),
+                region_min: min,
+                region_max: max,
+                only: (!only.is_empty()).then(|| BlockSelector::new(&only)),
+                exclude: (!exclude.is_empty()).then(|| BlockSelector::new(&exclude)),
+                explode: explode.map(|gap| Explode::new(gap).every(explode_every)),
+                include_technical,
+            };
+            cmd_render_gltf(
+                &file, &output,
+                render_flag(hollow, no_hollow, render.hollow),
+                render_flag(models, no_models, render.models),
+                render_flag(textures, no_textures, render.textures),
+                &source,
+            )?
+        }
+        Commands::Batch(BatchArgs { dir, command, format, output, recursive, jobs, stonecutter }) => cmd_batch(&dir, command, format, output.as_deref(), recursive, jobs, stonecutter)?,
+        Commands::Catalog(CatalogArgs { dir, output, recursive, jobs }) => cmd_catalog(&dir, output.as_deref(), recursive, jobs)?,
+        Commands::Watch(WatchArgs { file, exec, output, settle_ms, args }) => cmd_watch(&file, &exec, &output, settle_ms, &args, config)?,
+        Commands::Config(ConfigArgs { action: ConfigAction::Show }) => cmd_config_show(config)?,
+        Commands::Completions(CompletionsArgs { shell }) => cmd_completions(shell),
+        Commands::Man(ManArgs { output }) => cmd_man(output.as_deref())?,
+        Commands::Debug(DebugArgs { file, path, full, arrays, depth, output }) => {
+            let options = nbt_dump::DumpOptions { full, arrays, depth };
+            cmd_debug(&file, path.as_deref(), &options, output.as_deref())?
+        }
+    }
+
+    Ok(())
+}
+
+fn cmd_info(file: &Path, top: usize, json: bool) -> Result<()> {
+    let schem = load_schematic(file)?;
+    let summary = schem.summary(top);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&summary)?);
+        return Ok(());
+    }
+
+    log::info!("{}", "=== Schematic Info ===".bold().cyan());
+    log::info!("");
+
+    println!("{}  {}", "File:".bold(), file.display());
+    println!("{}  {}", "Format:".bold(), summary.format);
+    println!();
+
+    println!("{}", "--- Dimensions ---".yellow());
+    println!("  Width (X):  {}", summary.width);
+    println!("  Height (Y): {}", summary.height);
+    println!("  Length (Z): {}", summary.length);
+    println!("  Volume:     {} blocks", summary.volume);
+    if summary.offset != (0, 0, 0) {
+        println!("  Offset:     {}, {}, {}", summary.offset.0, summary.offset.1, summary.offset.2);
+    }
+    println!();
+
+    println!("{}", "--- Contents ---".yellow());
+    println!("  Total blocks:    {}", summary.total_blocks);
+    println!("  Solid blocks:    {}", summary.solid_blocks);
+    let classes: Vec<String> = summary.classes.iter()
+        .map(|(class, count)| format!("{} {}", count, class))
+        .collect();
+    println!("  By class:        {}", classes.join(", "));
+    println!("  Shell blocks:    {}", summary.shell_blocks);
+    println!("  Unique types:    {}", summary.unique_types);
+    println!("  Block entities:  {}", summary.block_entities);
+    println!("  Entities:        {}", summary.entities);
+    if summary.structure_voids > 0 {
+        println!("  Structure voids: {}", summary.structure_voids);
+    }
+    if summary.water_sources > 0 {
+        println!("  Water sources:   {} ({} waterlogged)", summary.water_sources, summary.waterlogged_blocks);
+    }
+    println!();
+
+    if !summary.top_blocks.is_empty() {
+        println!("{}", "--- Most Common Blocks ---".yellow());
+        for block in &summary.top_blocks {
+            println!("  {:>10} x {}", block.count, block.name);
+        }
+        println!();
+    }
+
+    if summary.name.is_some() || summary.author.is_some() || summary.date.is_some() {
+        println!("{}", "--- Metadata ---".yellow());
+        if let Some(ref name) = summary.name {
+            println!("  Name:   {}", name);
+        }
+        if let Some(ref author) = summary.author {
+            println!("  Author: {}", author);
+        }
+        if let Some(date) = summary.date {
+            println!("  Date:   {}", format_timestamp(date));
+        }
+        if !summary.required_mods.is_empty() {
+            println!("  Mods:   {}", summary.required_mods.join(", "));
+        }
+    }
+
+    Ok(())
+}
+
+/// Which blocks the counting commands include, and how
+#[derive(Clone, Copy, Default)]
+struct CountOptions {
+    /// Count items like Litematica's material list instead of blocks
+    as_items: bool,
+    /// Only blocks with at least one exposed face
+    surface_only: bool,
+    /// A water bucket per waterlogged block
+    waterlogged: bool,
+    /// Keep barriers, structure voids and other technical blocks
+    technical: bool,
+}
+
+/// Block (or item) counts for the analysis commands
+fn count_blocks(schem: &UnifiedSchematic, options: CountOptions) -> std::collections::HashMap<String, usize> {
+    let CountOptions { as_items, surface_only, waterlogged, technical } = options;
+    if !surface_only {
+        let mut counts = if as_items { schem.item_counts() } else { schem.block_counts() };
+        if !technical {
+            counts.retain(|name, _| BlockClass::of(name) != BlockClass::Technical);
+        }
+        let water = if waterlogged { schem.waterlogged_blocks() } else { 0 };
+        if water > 0 {
+            *counts.entry("minecraft:water_bucket".to_string()).or_insert(0) += water;
+        }
+        return counts;
+    }
+
+    let mut counts = std::collections::HashMap::new();
+    for block in schem.surface_blocks() {
+        if !technical && block.is_technical() {
+            continue;
+        }
+        let (name, count) = if as_items {
+            match block.item_form() {
+                Some(item) => item,
+                None => continue,
+            }
+        } else {
+            (block.name.clone(), 1)
+        };
+        *counts.entry(name).or_insert(0) += count;
+        if waterlogged && block.state.is_waterlogged() {
+            *counts.entry("minecraft:water_bucket".to_string()).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+/// How `blocks` and `materials` lay out their lists
+#[derive(Clone, Copy)]
+struct ListStyle {
+    /// Largest first instead of by name
+    sort: bool,
+    /// Rows to show (per category when grouped)
+    limit: Option<usize>,
+    group_by: Option<CountGrouping>,
+    /// List the rows under each category's subtotal
+    verbose: bool,
+    json: bool,
+}
+
+/// One category of a grouped list
+struct CategoryGroup {
+    category: schem_tool::recipes::Category,
+    total: u64,
+    items: Vec<(String, u64)>,
+}
+
+impl ListStyle {
+    /// Sort `items` by name, or largest first
+    fn order(&self, items: &mut [(String, u64)]) {
+        if self.sort {
+            items.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        } else {
+            items.sort_by(|a, b| a.0.cmp(&b.0));
+        }
+    }
+
+    /// Items in their categories, in table order (largest first when sorting)
+    fn categories(&self, items: &[(String, u64)]) -> Vec<CategoryGroup> {
+        let mut groups: Vec<_> = schem_tool::recipes::group_by_category(items.iter().cloned())
+            .into_iter()
+            .map(|(category, items)| CategoryGroup { category, total: items.iter().map(|(_, n)| n).sum(), items })
+            .collect();
+        if self.sort {
+            groups.sort_by_key(|g| std::cmp::Reverse(g.total));
+        }
+        groups
+    }
+
+    /// Counts as JSON: `{name: count}`, or `{category: {"total": n, "items": {name: count}}}`
+    /// when grouped
+    fn json(&self, items: &[(String, u64)]) -> serde_json::Value {
+        let limit = self.limit.unwrap_or(usize::MAX);
+        let map = |items: &[(String, u64)]| items.iter().take(limit).map(|(name, n)| (name.clone(), serde_json::json!(n))).collect::<serde_json::Map<_, _>>();
+        if self.group_by.is_none() {
+            return serde_json::Value::Object(map(items));
+        }
+        serde_json::Value::Object(self.categories(items).into_iter()
+            .map(|group| (group.category.key(), serde_json::json!({"total": group.total, "items": map(&group.items)})))
+            .collect())
+    }
+
+    /// Category subtotals, and under --verbose each category's rows as
+    /// `detail` renders them
+    fn print_categories(&self, items: &[(String, u64)], detail: impl Fn(&[(String, u64)]) -> String) {
+        #[derive(Tabled)]
+        struct CategoryRow {
+            #[tabled(rename = "Category")]
+            name: String,
+            #[tabled(rename = "Types")]
+            types: usize,
+            #[tabled(rename = "Count")]
+            count: u64,
+            #[tabled(rename = "%")]
+            percent: String,
+        }
+
+        let total: u64 = items.iter().map(|(_, n)| n).sum();
+        let percent = |n: u64| format!("{:.1}", if total > 0 { n as f64 / total as f64 * 100.0 } else { 0.0 });
+        let groups = self.categories(items);
+        if self.verbose {
+            let limit = self.limit.unwrap_or(usize::MAX);
+            for group in &groups {
+                log::info!("{}", format!("=== {} ({}, {}%) ===", group.category.label(), group.total, percent(group.total)).bold().cyan());
+                println!("{}", detail(&group.items[..group.items.len().min(limit)]));
+                if group.items.len() > limit {
+                    log::info!("... and {} more", group.items.len() - limit);
+                }
+                log::info!("");
+            }
+        }
+        let rows: Vec<CategoryRow> = groups.iter()
+            .map(|group| CategoryRow { name: group.category.label(), types: group.items.len(), count: group.total, percent: percent(group.total) })
+            .collect();
+        println!("{}", render_table(rows));
+    }
+}
+
+/// One row of `blocks --aggregate` and the counts it adds up
+struct AggregateGroup {
+    name: String,
+    total: u64,
+    variants: Vec<(String, u64)>,
+}
+
+/// `counts` folded into rows by `aggregation`, in `style`'s order; each row's
+/// variants keep the order they had in `counts`
+fn aggregate_counts(counts: &[(String, u64)], aggregation: schem_tool::Aggregation, style: ListStyle) -> Vec<AggregateGroup> {
+    let mut variants: BTreeMap<String, Vec<(String, u64)>> = BTreeMap::new();
+    for (name, count) in counts {
+        variants.entry(aggregation.group(name)).or_default().push((name.clone(), *count));
+    }
+    let mut totals: Vec<(String, u64)> = variants.iter()
+        .map(|(name, variants)| (name.clone(), variants.iter().map(|(_, n)| n).sum()))
+        .collect();
+    style.order(&mut totals);
+    totals.into_iter()
+        .map(|(name, total)| AggregateGroup { variants: variants.remove(&name).unwrap_or_default(), name, total })
+        .collect()
+}
+
+/// Aggregated rows as JSON, `{row: {"total": n, "items": {name: count}}}` like
+/// the grouped counts
+fn aggregate_json(groups: &[AggregateGroup]) -> serde_json::Value {
+    serde_json::Value::Object(groups.iter()
+        .map(|group| {
+            let variants: serde_json::Map<_, _> = group.variants.iter().map(|(name, n)| (name.clone(), serde_json::json!(n))).collect();
+            (group.name.clone(), serde_json::json!({"total": group.total, "items": variants}))
+        })
+        .collect())
+}
+
+/// `blocks --aggregate`: a row per group, with the variants under each one
+/// with --verbose, or nested in the JSON
+fn print_aggregated(counts: &[(String, u64)], aggregation: schem_tool::Aggregation, style: ListStyle) -> Result<()> {
+    #[derive(Tabled)]
+    struct AggregateRow {
+        #[tabled(rename = "Block")]
+        name: String,
+        #[tabled(rename = "Types")]
+        types: usize,
+        #[tabled(rename = "Count")]
+        count: u64,
+        #[tabled(rename = "%")]
+        percent: String,
+    }
+
+    let groups = aggregate_counts(counts, aggregation, style);
+    let groups = &groups[..groups.len().min(style.limit.unwrap_or(usize::MAX))];
+    if style.json {
+        println!("{}", serde_json::to_string_pretty(&aggregate_json(groups))?);
+        return Ok(());
+    }
+
+    let total: u64 = counts.iter().map(|(_, n)| n).sum();
+    let percent = |n: u64| format!("{:.1}", if total > 0 { n as f64 / total as f64 * 100.0 } else { 0.0 });
+    if style.verbose {
+        for group in groups.iter().filter(|group| group.variants.len() > 1) {
+            log::info!("{}", format!("=== {} ({}, {}%) ===", group.name, group.total, percent(group.total)).bold().cyan());
+            let rows: Vec<BlockCount> = group.variants.iter()
+                .map(|(name, count)| BlockCount { name: name.clone(), count: *count, percent: percent(*count) })
+                .collect();
+            println!("{}", render_table(rows));
+            log::info!("");
+        }
+    }
+    let rows: Vec<AggregateRow> = groups.iter()
+        .map(|group| AggregateRow { name: group.name.clone(), types: group.variants.len(), count: group.total, percent: percent(group.total) })
+        .collect();
+    println!("{}", render_table(rows));
+    log::info!("\nTotal: {} blocks ({} types)", total, counts.len());
+    Ok(())
+}
+
+fn cmd_blocks(
+    file: &Path,
+    no_air: bool,
+    counting: CountOptions,
+    style: ListStyle,
+    aggregate: Option<schem_tool::Aggregation>,
+) -> Result<()> {
+    let schem = load_schematic(file)?;
+    let counts = count_blocks(&schem, counting);
+    let mut counts: Vec<(String, u64)> = counts.into_iter().map(|(name, n)| (name, n as u64)).collect();
+
+    if no_air {
+        counts.retain(|(name, _)| BlockClass::of(name) != BlockClass::Air);
+    }
+    style.order(&mut counts);
+    if let Some(aggregation) = aggregate {
+        return print_aggregated(&counts, aggregation, style);
+    }
+
+    if style.json {
+        println!("{}", serde_json::to_string_pretty(&style.json(&counts))?);
+        return Ok(());
+    }
+
+    let total: u64 = counts.iter().map(|(_, c)| c).sum();
+    let table = |counts: &[(String, u64)]| {
+        let rows: Vec<BlockCount> = counts.iter()
+            .map(|(name, count)| {
+                let percent = if total > 0 {
+                    format!("{:.1}", (*count as f64 / total as f64) * 100.0)
+                } else {
+                    "0.0".to_string()
+                };
+                BlockCount {
+                    name: name.clone(),
+                    count: *count,
+                    percent,
+                }
+            })
+            .collect();
+        render_table(rows)
+    };
+
+    if style.group_by.is_some() {
+        style.print_categories(&counts, table);
+    } else {
+        println!("{}", table(&counts[..counts.len().min(style.limit.unwrap_or(usize::MAX))]));
+    }
+
+    log::info!("\nTotal: {} blocks ({} types)", total, counts.len());
+
+    Ok(())
+}
+
+fn cmd_palette(file: &Path, selectors: bool, count: bool) -> Result<()> {
+    let schem = load_schematic(file)?;
+    if selectors {
+        print!("{}", palette_selectors(&schem, count));
+        return Ok(());
+    }
+
+    log::info!("{}", "=== Block Palette ===".bold().cyan());
+    log::info!("");
+
+    let unique = schem.unique_blocks();
+    for block in &unique {
+        if block.state.properties.is_empty() {
+            println!("  {}", block.name);
+        } else {
+            println!("  {}", block.full_name().green());
+            for (key, value) in block.state.properties.sorted() {
+                println!("    {} = {}", key.yellow(), value);
+            }
+        }
+    }
+
+    log::info!("\nTotal: {} unique block states", unique.len());
+
+    Ok(())
+}
+
+/// One selector per block state, sorted, each followed by a tab and its block
+/// count with `count`
+fn palette_selectors(schem: &UnifiedSchematic, count: bool) -> String {
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+    let mut blocks = schem.blocks.iter().peekable();
+    while let Some(block) = blocks.next() {
+        let mut run = 1;
+        while blocks.next_if_eq(&block).is_some() {
+            run += 1;
+        }
+        *counts.entry(block.to_selector()).or_insert(0) += run;
+    }
+    counts.into_iter()
+        .map(|(selector, n)| if count { format!("{}\t{}\n", selector, n) } else { selector + "\n" })
+        .collect()
+}
+
+fn cmd_block_entities(file: &Path, filter_type: Option<String>, verbose: bool) -> Result<()> {
+    let schem = load_schematic(file)?;
+
+    let entities: Vec<_> = schem.block_entities.iter()
+        .filter(|be| {
+            if let Some(ref filter) = filter_type {
+                be.id.to_lowercase().contains(&filter.to_lowercase())
+            } else {
+                true
+            }
+        })
+        .collect();
+
+    if entities.is_empty() {
+        log::info!("No block entities found.");
+        return Ok(());
+    }
+
+    let rows: Vec<BlockEntityRow> = entities.iter().map(|be| {
+        let data = if verbose {
+            let fields = be.data.iter()
+                .map(|(k, v)| format!("{}={}", k, v))
+                .collect::<Vec<_>>()
+                .join(", ");
+            match be.get_spawner() {
+                Some(spawner) => format!("{}\n{}", spawner_summary(&spawner), fields),
+                None => fields,
+            }
+        } else {
+            format!("{} fields", be.data.len())
+        };
+
+        BlockEntityRow {
+            entity_type: be.id.clone(),
+            position: format!("{}, {}, {}", be.pos.0, be.pos.1, be.pos.2),
+            data,
+        }
+    }).collect();
+
+    println!("{}", render_table(rows));
+
+    log::info!("\nTotal: {} block entities", entities.len());
+
+    Ok(())
+}
+
+/// One-line summary of the mob fields, e.g. `"Bob", farmer, 20 HP, no AI`
+fn mob_summary(info: &schem_tool::MobInfo) -> String {
+    let mut parts = Vec::new();
+    if let Some(ref name) = info.custom_name {
+        parts.push(format!("\"{}\"", name));
+    }
+    if let Some(ref profession) = info.profession {
+        parts.push(profession.trim_start_matches("minecraft:").to_string());
+    }
+    if let Some(health) = info.health {
+        parts.push(format!("{} HP", health));
+    }
+    if info.no_ai {
+        parts.push("no AI".to_string());
+    }
+    parts.join(", ")
+}
+
+fn cmd_entities(file: &Path, filter_type: Option<String>, count_only: bool, verbose: bool) -> Result<()> {
+    let schem = load_schematic(file)?;
+
+    let mut groups: Vec<_> = schem.entities_by_type().into_iter()
+        .filter(|(id, _)| {
+            if let Some(ref filter) = filter_type {
+                id.to_lowercase().contains(&filter.to_lowercase())
+            } else {
+                true
+            }
+        })
+        .collect();
+
+    if groups.is_empty() {
+        log::info!("No entities found.");
+        return Ok(());
+    }
+
+    // Most common first, then by id
+    groups.sort_by(|(a_id, a), (b_id, b)| b.len().cmp(&a.len()).then(a_id.cmp(b_id)));
+    let total: usize = groups.iter().map(|(_, entities)| entities.len()).sum();
+
+    if count_only {
+        for (id, entities) in &groups {
+            println!("{:>8}  {}", entities.len(), id);
+        }
+        log::info!("\nTotal: {} entities", total);
+        return Ok(());
+    }
+
+    log::info!("{}", "=== Entities ===".bold().cyan());
+    log::info!("");
+
+    for (id, entities) in &groups {
+        if !verbose {
+            println!("  {} ({})", id.green(), entities.len());
+        }
+        for entity in entities {
+            let summary = mob_summary(&entity.mob_info());
+            let summary = if summary.is_empty() { summary } else { format!(" - {}", summary) };
+            if verbose {
+                println!("  {} at ({:.1}, {:.1}, {:.1}){}",
+                    entity.id.green(),
+                    entity.pos.0, entity.pos.1, entity.pos.2,
+                    summary
+                );
+                for (key, value) in &entity.data {
+                    println!("    {}: {}", key.yellow(), value);
+                }
+            } else {
+                println!("    ({:.1}, {:.1}, {:.1}){}", entity.pos.0, entity.pos.1, entity.pos.2, summary);
+            }
+        }
+    }
+
+    log::info!("\nTotal: {} entities", total);
+
+    Ok(())
+}
+
+fn opt<T: std::fmt::Display>(value: Option<T>) -> String {
+    value.map_or_else(|| "-".to_string(), |v| v.to_string())
+}
+
+/// Spawner delay as "min-max ticks"
+fn spawner_delay(spawner: &schem_tool::SpawnerInfo) -> String {
+    match (spawner.min_spawn_delay, spawner.max_spawn_delay) {
+        (None, None) => "-".to_string(),
+        (min, max) => format!("{}-{} ticks", opt(min), opt(max)),
+    }
+}
+
+fn spawner_summary(spawner: &schem_tool::SpawnerInfo) -> String {
+    format!("spawns {} x{} every {}, player range {}",
+        spawner.entity.as_deref().unwrap_or("(unset)"),
+        opt(spawner.spawn_count),
+        spawner_delay(spawner),
+        opt(spawner.required_player_range),
+    )
+}
+
+fn cmd_spawners(file: &Path) -> Result<()> {
+    let schem = load_schematic(file)?;
+
+    let rows: Vec<SpawnerRow> = schem.block_entities.iter()
+        .filter_map(|be| be.get_spawner().map(|spawner| (be, spawner)))
+        .map(|(be, spawner)| SpawnerRow {
+            position: format!("{}, {}, {}", be.pos.0, be.pos.1, be.pos.2),
+            entity: spawner.entity.clone().unwrap_or_else(|| "(unset)".to_string()),
+            delay: spawner_delay(&spawner),
+            count: opt(spawner.spawn_count),
+            max_nearby: opt(spawner.max_nearby_entities),
+            player_range: opt(spawner.required_player_range),
+            potentials: spawner.potentials,
+        })
+        .collect();
+
+    if rows.is_empty() {
+        log::info!("No spawners found.");
+        return Ok(());
+    }
+
+    let total = rows.len();
+    println!("{}", render_table(rows));
+    log::info!("\nTotal: {} spawners", total);
+
+    Ok(())
+}
+
+fn cmd_jigsaws(file: &Path) -> Result<()> {
+    let schem = load_schematic(file)?;
+    let text = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+
+    let rows: Vec<JigsawRow> = schem.get_jigsaws().into_iter()
+        .map(|(be, jigsaw)| {
+            let (x, y, z) = be.pos;
+            let orientation = u32::try_from(x).ok()
+                .zip(u32::try_from(y).ok())
+                .zip(u32::try_from(z).ok())
+                .and_then(|((x, y), z)| schem.get_block(x, y, z))
+                .and_then(|block| block.get_property("orientation").cloned());
+            JigsawRow {
+                position: format!("{}, {}, {}", x, y, z),
+                orientation: text(orientation),
+                name: text(jigsaw.name),
+                target: text(jigsaw.target),
+                pool: text(jigsaw.pool),
+                final_state: text(jigsaw.final_state),
+                joint: text(jigsaw.joint),
+            }
+        })
+        .collect();
+
+    let voids = schem.structure_voids();
+    if rows.is_empty() {
+        log::info!("No jigsaws found.");
+    } else {
+        let total = rows.len();
+        println!("{}", render_table(rows));
+        log::info!("\nTotal: {} jigsaws", total);
+    }
+    if voids > 0 {
+        log::info!("{} structure voids", voids);
+    }
+    Ok(())
+}
+
+fn cmd_min_version(file: &Path) -> Result<()> {
+    use schem_tool::versions::release_for_data_version;
+
+    let schem = load_schematic(file)?;
+    let report = schem_tool::versions::minimum_version(&schem);
+    match report.minimum {
+        Some(release) => {
+            let culprits: Vec<String> = report.culprits.iter()
+                .map(|(name, count)| format!("{} x{}", schem_tool::recipes::material_label(name), count))
+                .collect();
+            println!("Minimum version: {} (because of {})", release, culprits.join(", "));
+            for (release, blocks) in report.by_release.iter().skip(1) {
+                let names: Vec<String> = blocks.iter().map(|name| schem_tool::recipes::material_label(name)).collect();
+                println!("  {:<7} {}", release.name, names.join(", "));
+            }
+        }
+        None => println!("Minimum version: 1.13 (no blocks added since the flattening)"),
+    }
+
+    match report.declared {
+        Some(declared) => {
+            let name = release_for_data_version(declared).map_or_else(|| "before 1.13".to_string(), |release| release.to_string());
+            println!("DataVersion: {} ({})", declared, name);
+            if !report.newer_than_declared.is_empty() {
+                let names: Vec<String> = report.newer_than_declared.iter().map(|name| schem_tool::recipes::material_label(name)).collect();
+                log::warn!(
+                    "Blocks newer than the DataVersion, so the exporter wrote a wrong one: {}",
+                    names.join(", ")
+                );
+            }
+        }
+        None => println!("DataVersion: not set"),
+    }
+    Ok(())
+}
+
+fn cmd_paste_check(file: &Path) -> Result<()> {
+    use schem_tool::paste_check::PasteIssue;
+
+    let schem = load_schematic(file)?;
+    let report = schem_tool::paste_check::check(&schem);
+    if report.is_empty() {
+        log::info!("Nothing will fall, pop off, decay or spill after pasting.");
+        return Ok(());
+    }
+    for issue in PasteIssue::ALL {
+        let Some(blocks) = report.issues.get(&issue) else { continue };
+        println!("{} ({}):", issue.description().yellow(), report.count(issue));
+        for (name, positions) in blocks {
+            let positions: Vec<(i64, i64, i64)> = positions.iter().map(|&(x, y, z)| (x as i64, y as i64, z as i64)).collect();
+            println!("  {} x{}: {}", schem_tool::recipes::material_label(name), positions.len(), position_list(&positions, 8));
+        }
+    }
+    Ok(())
+}
+
+fn cmd_focus(file: &Path, output: Option<&Path>, options: &schem_tool::focus::FocusOptions) -> Result<()> {
+    let schem = load_schematic(file)?;
+    let report = schem_tool::focus::find_focus(&schem, options);
+    println!("Terrain: {:.1}% of the volume ({} blocks)", report.terrain_percent(), report.terrain_blocks);
+
+    let (Some((min, max)), Some((w, h, l))) = (report.bounds, report.size()) else {
+        anyhow::bail!("No built blocks found; try --constructed-extra or a lower --min-cluster");
+    };
+    println!("Build: {}x{}x{} from ({}, {}, {}) to ({}, {}, {})", w, h, l, min.0, min.1, min.2, max.0, max.1, max.2);
+    let kept = w as f64 * h as f64 * l as f64 * 100.0 / report.volume as f64;
+    println!("  {} built blocks, {:.1}% of the volume", report.constructed_blocks, kept);
+    if report.stray_blocks > 0 {
+        println!("  {} built blocks in clusters under {} left out", report.stray_blocks, options.min_cluster);
+    }
+
+    if let Some(output) = output {
+        save_schematic(&schem.crop(min, max), output)?;
+        log::info!("Wrote {}", output.display());
+    }
+    Ok(())
+}
+
+/// Seconds as seconds, minutes or hours, whichever reads best
+fn format_duration(seconds: f64) -> String {
+    if seconds < 60.0 {
+        format!("{:.1} s", seconds)
+    } else if seconds < 3600.0 {
+        format!("{:.1} min", seconds / 60.0)
+    } else {
+        format!("{:.1} h", seconds / 3600.0)
+    }
+}
+
+fn cmd_mining(file: &Path, options: &schem_tool::mining::MiningOptions) -> Result<()> {
+    let schem = load_schematic(file)?;
+    let estimate = schem_tool::mining::estimate(&schem, options);
+
+    let rows: Vec<MiningRow> = estimate.blocks.iter()
+        .map(|time| MiningRow {
+            block: schem_tool::recipes::material_label(&time.block),
+            count: time.count,
+            each: format!("{:.2} s", time.ticks as f64 / 20.0),
+            total: format_duration(time.total_ticks() as f64 / 20.0),
+            drops: if time.harvested { "yes" } else { "no" },
+        })
+        .collect();
+    if !rows.is_empty() {
+        println!("{}", render_table(rows));
+    }
+    let blocks: usize = estimate.blocks.iter().map(|time| time.count).sum();
+    println!("Total: {} to break {} blocks with {} (digging only)", format_duration(estimate.total_seconds()), blocks, options.tool);
+
+    let list = |counts: &[(String, usize)]| counts.iter()
+        .map(|(name, count)| format!("{} x{}", schem_tool::recipes::material_label(name), count))
+        .collect::<Vec<_>>()
+        .join(", ");
+    if !estimate.unbreakable.is_empty() {
+        log::warn!("Can't be mined: {}", list(&estimate.unbreakable));
+    }
+    if !estimate.unknown.is_empty() {
+        log::warn!("No hardness known for: {} (not counted)", list(&estimate.unknown));
+    }
+    Ok(())
+}
+
+/// Up to `limit` positions, with a count of the rest
+fn position_list(positions: &[(i64, i64, i64)], limit: usize) -> String {
+    let mut list = positions.iter()
+        .take(limit)
+        .map(|(x, y, z)| format!("({}, {}, {})", x, y, z))
+        .collect::<Vec<_>>()
+        .join(" ");
+    if positions.len() > limit {
+        list.push_str(&format!(" and {} more", positions.len() - limit));
+    }
+    list
+}
+
+fn cmd_beacons(file: &Path) -> Result<()> {
+    use schem_tool::structures::{self, CONDUIT_FULL_FRAME, CONDUIT_MIN_FRAME};
+
+    let schem = load_schematic(file)?;
+    let beacons = structures::beacons(&schem);
+    let conduits = structures::conduits(&schem);
+
+    if beacons.is_empty() && conduits.is_empty() {
+        log::info!("No beacons or conduits found.");
+        return Ok(());
+    }
+
+    for beacon in &beacons {
+        let (x, y, z) = beacon.pos;
+        let status = match beacon.tier {
+            0 => "inactive".red(),
+            4 => "tier 4 (complete)".green(),
+            tier => format!("tier {}", tier).yellow(),
+        };
+        println!("{} at ({}, {}, {}): {}", "Beacon".bold(), x, y, z, status);
+        if let Some(layer) = beacon.next_layer() {
+            println!("  Layer {} ({}x{}): {}/{} base blocks, missing at {}",
+                layer.level, 2 * layer.level + 1, 2 * layer.level + 1,
+                layer.valid, layer.required, position_list(&layer.missing, 8));
+        }
+    }
+
+    for conduit in &conduits {
+        let (x, y, z) = conduit.pos;
+        let status = if conduit.is_full_power() {
+            "full power".green()
+        } else if conduit.is_active() {
+            "active".yellow()
+        } else {
+            "inactive".red()
+        };
+        println!("{} at ({}, {}, {}): {}", "Conduit".bold(), x, y, z, status);
+        println!("  Frame: {}/{} blocks (needs {} to activate)", conduit.frame, CONDUIT_FULL_FRAME, CONDUIT_MIN_FRAME);
+        if !conduit.is_full_power() && !conduit.missing_frame.is_empty() {
+            println!("  Missing frame at {}", position_list(&conduit.missing_frame, 8));
+        }
+        if !conduit.dry.is_empty() {
+            println!("  Not water at {}", position_list(&conduit.dry, 8));
+        }
+    }
+
+    log::info!("\nTotal: {} beacons, {} conduits", beacons.len(), conduits.len());
+
+    Ok(())
+}
+
+fn cmd_signs(file: &Path) -> Result<()> {
+    let schem = load_schematic(file)?;
+    let signs = schem.get_signs();
+
+    if signs.is_empty() {
+        log::info!("No signs with text found.");
+        return Ok(());
+    }
+
+    log::info!("{}", "=== Signs ===".bold().cyan());
+    log::info!("");
+
+    for (i, (block_entity, text)) in signs.iter().enumerate() {
+        let pos = block_entity.pos;
+        println!("{}. Sign at ({}, {}, {})", (i + 1).to_string().bold(), pos.0, pos.1, pos.2);
+
+        if !text.front.is_empty() {
             let has_content = text.front.iter().any(|s| !s.is_empty());
             if has_content {
                 println!("   {}:", "Front".yellow());
@@ -513,381 +2956,1413 @@ fn cmd_signs(file: &PathBuf) -> Result<()> {
             }
         }
 
-        if !text.back.is_empty() {
-            let has_content = text.back.iter().any(|s| !s.is_empty());
-            if has_content {
-                println!("   {}:", "Back".yellow());
-                for line in &text.back {
-                    if !line.is_empty() {
-                        println!("     \"{}\"", line.green());
-                    }
-                }
-            }
-        }
+        if !text.back.is_empty() {
+            let has_content = text.back.iter().any(|s| !s.is_empty());
+            if has_content {
+                println!("   {}:", "Back".yellow());
+                for line in &text.back {
+                    if !line.is_empty() {
+                        println!("     \"{}\"", line.green());
+                    }
+                }
+            }
+        }
+
+        println!();
+    }
+
+    log::info!("Total: {} signs", signs.len());
+
+    Ok(())
+}
+
+/// `metadata --json` output; field names are stable
+#[derive(Serialize)]
+struct MetadataJson<'a> {
+    format: String,
+    name: Option<&'a str>,
+    author: Option<&'a str>,
+    date: Option<i64>,
+    required_mods: &'a [String],
+    data_version: Option<i32>,
+    offset: Option<[i32; 3]>,
+    we_offset: Option<[i32; 3]>,
+    we_origin: Option<[i32; 3]>,
+    enclosing_size: Option<[i32; 3]>,
+    total_blocks: Option<i64>,
+    total_volume: Option<i64>,
+    region_count: Option<i32>,
+    extra: BTreeMap<&'a str, &'a str>,
+    preserved_nbt: BTreeMap<&'a str, &'static str>,
+}
+
+fn xyz((x, y, z): (i32, i32, i32)) -> [i32; 3] {
+    [x, y, z]
+}
+
+fn cmd_metadata(file: &Path, json: bool) -> Result<()> {
+    let schem = load_schematic(file)?;
+    let meta = &schem.metadata;
+
+    if json {
+        let out = MetadataJson {
+            format: format!("{:?}", schem.format),
+            name: meta.name.as_deref(),
+            author: meta.author.as_deref(),
+            date: meta.date,
+            required_mods: &meta.required_mods,
+            data_version: meta.data_version,
+            offset: meta.offset.map(xyz),
+            we_offset: meta.we_offset.map(xyz),
+            we_origin: meta.we_origin.map(xyz),
+            enclosing_size: meta.enclosing_size.map(xyz),
+            total_blocks: meta.total_blocks,
+            total_volume: meta.total_volume,
+            region_count: meta.region_count,
+            extra: meta.extra.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect(),
+            preserved_nbt: meta.raw_extra_fields().into_iter().collect(),
+        };
+        println!("{}", serde_json::to_string_pretty(&out)?);
+        return Ok(());
+    }
+
+    log::info!("{}", "=== Metadata ===".bold().cyan());
+    log::info!("");
+
+    println!("  Name:   {}", meta.name.as_deref().unwrap_or("(not set)"));
+    println!("  Author: {}", meta.author.as_deref().unwrap_or("(not set)"));
+
+    if let Some(date) = meta.date {
+        println!("  Date:   {}", format_timestamp(date));
+    } else {
+        println!("  Date:   (not set)");
+    }
+
+    if meta.required_mods.is_empty() {
+        println!("  Mods:   (none)");
+    } else {
+        println!("  Mods:");
+        for mod_name in &meta.required_mods {
+            println!("    - {}", mod_name);
+        }
+    }
+
+    let triple = |(x, y, z): (i32, i32, i32)| format!("{}, {}, {}", x, y, z);
+    let mut placement = Vec::new();
+    if let Some(v) = meta.data_version {
+        placement.push(("Data version", v.to_string()));
+    }
+    if let Some(v) = meta.offset {
+        placement.push(("Offset", triple(v)));
+    }
+    if let Some(v) = meta.we_origin {
+        placement.push(("WE origin", triple(v)));
+    }
+    if let Some(v) = meta.we_offset {
+        placement.push(("WE offset", triple(v)));
+    }
+    if let Some(v) = meta.enclosing_size {
+        placement.push(("Enclosing size", triple(v)));
+    }
+    if let Some(v) = meta.region_count {
+        placement.push(("Regions", v.to_string()));
+    }
+    if let Some(v) = meta.total_blocks {
+        placement.push(("Total blocks", v.to_string()));
+    }
+    if let Some(v) = meta.total_volume {
+        placement.push(("Total volume", v.to_string()));
+    }
+    if !placement.is_empty() {
+        println!();
+        println!("  Placement:");
+        for (label, value) in placement {
+            println!("    {:<15} {}", format!("{}:", label), value);
+        }
+    }
+
+    if !meta.extra.is_empty() {
+        println!();
+        println!("  Extra fields:");
+        for (key, value) in &meta.extra {
+            println!("    {}: {}", key.yellow(), value);
+        }
+    }
+
+    let raw_fields = meta.raw_extra_fields();
+    if !raw_fields.is_empty() {
+        println!();
+        println!("  Preserved NBT fields (not parsed):");
+        for (key, tag) in raw_fields {
+            println!("    {}: {}", key.yellow(), tag);
+        }
+    }
+
+    Ok(())
+}
+
+/// Local coordinates of a position given on the command line, if it's inside
+fn local_position(schem: &UnifiedSchematic, (x, y, z): (i32, i32, i32), world: bool) -> Option<(u32, u32, u32)> {
+    let local = if world {
+        schem.to_local(x, y, z)
+    } else {
+        match (u32::try_from(x), u32::try_from(y), u32::try_from(z)) {
+            (Ok(x), Ok(y), Ok(z)) => Some((x, y, z)),
+            _ => None,
+        }
+    };
+    local.filter(|&(lx, ly, lz)| lx < schem.width && ly < schem.height && lz < schem.length)
+}
+
+fn cmd_get_block(file: &Path, (x, y, z): (i32, i32, i32), world: bool) -> Result<()> {
+    let schem = load_schematic(file)?;
+    let local = local_position(&schem, (x, y, z), world);
+
+    match local.and_then(|(lx, ly, lz)| schem.get_block(lx, ly, lz).map(|b| (b, (lx, ly, lz)))) {
+        Some((block, (lx, ly, lz))) => {
+            if world {
+                println!("Block at world ({}, {}, {}), local ({}, {}, {}): {}", x, y, z, lx, ly, lz, block.full_name().green());
+            } else {
+                println!("Block at ({}, {}, {}): {}", x, y, z, block.full_name().green());
+            }
+
+            if !block.state.properties.is_empty() {
+                println!();
+                println!("Properties:");
+                for (key, value) in block.state.properties.sorted() {
+                    println!("  {} = {}", key.yellow(), value);
+                }
+            }
+        }
+        None if world => {
+            let (ox, oy, oz) = schem.offset;
+            let (mx, my, mz) = schem.to_world(
+                schem.width.saturating_sub(1), schem.height.saturating_sub(1), schem.length.saturating_sub(1),
+            );
+            log::warn!("World position ({}, {}, {}) is out of bounds (schematic spans ({}, {}, {}) to ({}, {}, {}))", x, y, z, ox, oy, oz, mx, my, mz);
+        }
+        None => {
+            log::warn!("Position ({}, {}, {}) is out of bounds (schematic is {}x{}x{})", x, y, z, schem.width, schem.height, schem.length);
+        }
+    }
+
+    Ok(())
+}
+
+/// Local position of a block entity command's coordinates, or an error naming the bounds
+fn block_entity_position(schem: &UnifiedSchematic, (x, y, z): (i32, i32, i32), world: bool) -> Result<(i32, i32, i32)> {
+    match local_position(schem, (x, y, z), world) {
+        Some((lx, ly, lz)) => Ok((lx as i32, ly as i32, lz as i32)),
+        None if world => anyhow::bail!("World position ({}, {}, {}) is outside the schematic", x, y, z),
+        None => anyhow::bail!("Position ({}, {}, {}) is outside the schematic ({}x{}x{})", x, y, z, schem.width, schem.height, schem.length),
+    }
+}
+
+fn cmd_block_entity_get(file: &Path, position: (i32, i32, i32), world: bool, output: Option<&Path>) -> Result<()> {
+    let schem = load_schematic(file)?;
+    let pos = block_entity_position(&schem, position, world)?;
+    let Some(be) = schem.block_entities.iter().find(|be| be.pos == pos) else {
+        let block = schem.get_block(pos.0 as u32, pos.1 as u32, pos.2 as u32).map(|b| b.full_name()).unwrap_or_default();
+        anyhow::bail!("No block entity at {:?} (the block there is {})", position, block);
+    };
+
+    let snbt = schem_tool::snbt::to_string(&be.to_nbt());
+    match output {
+        Some(path) => {
+            std::fs::write(path, snbt).with_context(|| format!("Failed to write {}", path.display()))?;
+            log::info!("Wrote {} at {:?} to {}", be.id, position, path.display());
+        }
+        None => print!("{}", snbt),
+    }
+    Ok(())
+}
+
+fn cmd_block_entity_set(file: &Path, position: (i32, i32, i32), world: bool, input: &Path, output: &Path) -> Result<()> {
+    let mut schem = load_schematic(file)?;
+    let pos = block_entity_position(&schem, position, world)?;
+
+    let text = std::fs::read_to_string(input).with_context(|| format!("Failed to read {}", input.display()))?;
+    let value = schem_tool::snbt::from_str(&text).with_context(|| format!("Failed to parse {}", input.display()))?;
+    let fastnbt::Value::Compound(nbt) = value else {
+        anyhow::bail!("{} must hold a compound ({{...}})", input.display());
+    };
+
+    let index = match schem.block_entities.iter().position(|be| be.pos == pos) {
+        Some(index) => index,
+        None => {
+            if !matches!(nbt.get("id").or_else(|| nbt.get("Id")), Some(fastnbt::Value::String(_))) {
+                anyhow::bail!("No block entity at {:?}; add an id to {} to create one", position, input.display());
+            }
+            schem.block_entities.push(schem_tool::BlockEntity { pos, ..Default::default() });
+            schem.block_entities.len() - 1
+        }
+    };
+    let be = &mut schem.block_entities[index];
+    be.set_nbt(nbt);
+    let id = be.id.clone();
+
+    save_schematic(&schem, output)?;
+    log::info!("Set {} at {:?}; wrote {}", id, position, output.display());
+    Ok(())
+}
+
+/// One regex for search patterns: any of them, as case-insensitive substrings
+/// or as regexes on the full name
+/// `search --regex` patterns as one regex on the full name
+fn search_regex(patterns: &[String]) -> Result<regex::Regex> {
+    let alternatives: Vec<String> = patterns.iter().map(|p| format!("(?:{})", p)).collect();
+    regex::Regex::new(&alternatives.join("|")).context("Invalid search pattern")
+}
+
+fn cmd_search(file: &Path, patterns: &[String], regex_mode: bool, invert: bool, show_positions: bool, limit: Option<usize>) -> Result<()> {
+    let schem = load_schematic(file)?;
+    let regex = if regex_mode { Some(search_regex(patterns)?) } else { None };
+    let selector = BlockSelector::new(patterns);
+    let is_match = |block: &schem_tool::Block| match &regex {
+        Some(regex) => regex.is_match(&block.name) != invert,
+        None => selector.matches(block) != invert,
+    };
+    let description = format!(
+        "{}{}",
+        if invert { "not " } else { "" },
+        patterns.iter().map(|p| format!("'{}'", p)).collect::<Vec<_>>().join(" or "),
+    );
+
+    let mut matches: Vec<(u32, u32, u32, &schem_tool::Block)> = Vec::new();
+
+    for y in 0..schem.height {
+        for z in 0..schem.length {
+            for x in 0..schem.width {
+                if let Some(block) = schem.get_block(x, y, z) {
+                    if is_match(block) {
+                        matches.push((x, y, z, block));
+                    }
+                }
+            }
+        }
+    }
+
+    // Substring patterns that found nothing may be typos of a vanilla name
+    if !regex_mode && !invert {
+        for pattern in patterns {
+            let pattern_lower = pattern.to_lowercase();
+            let found = matches.iter().any(|(_, _, _, block)| block.name.to_lowercase().contains(&pattern_lower));
+            if let Some(suggestion) = schem_tool::block::suggest_block_name(pattern).filter(|_| !found) {
+                log::info!("No blocks matching '{}'; did you mean {}?", pattern, suggestion);
+            }
+        }
+    }
+
+    if matches.is_empty() {
+        log::info!("No blocks matching {} found.", description);
+        return Ok(());
+    }
+
+    let display_count = limit.unwrap_or(matches.len()).min(matches.len());
+
+    log::info!("Found {} blocks matching {}:", matches.len(), description);
+    log::info!("");
+
+    if show_positions {
+        for (x, y, z, block) in matches.iter().take(display_count) {
+            println!("  ({:3}, {:3}, {:3}): {}", x, y, z, block.full_name());
+        }
+    } else {
+        // Group by block type
+        let mut by_type: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+        for (_, _, _, block) in &matches {
+            *by_type.entry(block.full_name()).or_insert(0) += 1;
+        }
+
+        let mut sorted: Vec<_> = by_type.into_iter().collect();
+        sorted.sort_by(|a, b| b.1.cmp(&a.1));
+
+        for (name, count) in sorted.iter().take(display_count) {
+            println!("  {} x{}", name, count);
+        }
+    }
+
+    if display_count < matches.len() {
+        log::info!("\n... and {} more", matches.len() - display_count);
+    }
+
+    Ok(())
+}
+
+fn cmd_export(file: &Path, output: &Path) -> Result<()> {
+    let schem = load_schematic(file)?;
+
+    let mut csv = String::from("block,count,percent\n");
+
+    let counts = schem.block_counts();
+    let total: usize = counts.values().sum();
+
+    let mut sorted: Vec<_> = counts.into_iter().collect();
+    sorted.sort_by(|a, b| b.1.cmp(&a.1));
+
+    for (name, count) in sorted {
+        let percent = if total > 0 {
+            (count as f64 / total as f64) * 100.0
+        } else {
+            0.0
+        };
+        csv.push_str(&format!("\"{}\",{},{:.2}\n", name, count, percent));
+    }
+
+    std::fs::write(output, csv)?;
+    log::info!("Exported block list to: {}", output.display());
+
+    Ok(())
+}
+
+fn cmd_hash(files: &[PathBuf]) -> Result<()> {
+    let mut seen: Vec<(&Path, schem_tool::fingerprint::Fingerprint)> = Vec::new();
+    for file in files {
+        let fingerprint = schem_tool::fingerprint::Fingerprint::of(&load_schematic(file)?);
+        println!("{:016x}  {:016x}  {}", fingerprint.content, fingerprint.shape, file.display());
+
+        if let Some((original, _)) = seen.iter().find(|(_, other)| other.content == fingerprint.content) {
+            log::info!("{}: same content as {}", file.display(), original.display());
+        } else if let Some((original, _)) = seen.iter().find(|(_, other)| fingerprint.possible_rotation_of(other)) {
+            log::info!("{}: possible rotation of {}", file.display(), original.display());
+        }
+        seen.push((file, fingerprint));
+    }
+    Ok(())
+}
+
+fn cmd_shell(file: &Path, thickness: u8, output: &Path, stonecutter: bool) -> Result<()> {
+    let schem = load_schematic(file)?;
+    let shell = schem.shell(thickness);
+
+    // Materials of the blocks that were taken out
+    let counting = CountOptions { waterlogged: true, ..CountOptions::default() };
+    let mut removed = count_blocks(&schem, counting);
+    for (name, count) in count_blocks(&shell, counting) {
+        if let Some(before) = removed.get_mut(&name) {
+            *before -= count;
+        }
+    }
+    removed.retain(|_, count| *count > 0);
+    let saved = schem_tool::recipes::round_materials(&schem_tool::recipes::calculate_materials_with_options(&removed, stonecutter));
+
+    let (before, after) = (schem.solid_blocks(), shell.solid_blocks());
+    println!("Removed {} of {} blocks ({} kept)", before - after, before, after);
+    if !saved.is_empty() {
+        let mut saved: Vec<_> = saved.into_iter().collect();
+        saved.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        println!("Materials saved:");
+        for (name, count) in saved {
+            println!("  {:>8}  {}", count, schem_tool::recipes::material_label(&name));
+        }
+    }
+
+    save_schematic(&shell, output)?;
+    log::info!("Wrote {}", output.display());
+    Ok(())
+}
+
+fn cmd_convert(file: &Path, output: &Path) -> Result<()> {
+    let schem = load_schematic(file)?;
+    save_schematic(&schem, output)?;
+    log::info!("Wrote {}", output.display());
+    Ok(())
+}
+
+fn cmd_set_metadata(file: &Path, name: Option<&str>, author: Option<&str>, date: Option<i64>, output: &Path) -> Result<()> {
+    let mut schem = load_schematic(file)?;
+    set_metadata(&mut schem.metadata, name, author, date);
+    if output.extension().is_some_and(|ext| ext == "nbt") && (name.is_some() || date.is_some()) {
+        log::warn!("Structure files only keep the author; the name and date aren't written");
+    }
+    save_schematic(&schem, output)?;
+    log::info!("Wrote {}", output.display());
+    Ok(())
+}
+
+fn set_metadata(metadata: &mut schem_tool::Metadata, name: Option<&str>, author: Option<&str>, date: Option<i64>) {
+    if let Some(name) = name {
+        metadata.set_name(name);
+    }
+    if let Some(author) = author {
+        metadata.set_author(author);
+    }
+    if let Some(date) = date {
+        metadata.set_date(date);
+    }
+}
+
+fn cmd_reanchor(file: &Path, origin: Anchor, output: &Path) -> Result<()> {
+    let mut schem = load_schematic(file)?;
+    let anchor = match origin {
+        Anchor::Min => (0, 0, 0),
+        Anchor::Center => ((schem.width / 2) as i32, 0, (schem.length / 2) as i32),
+        Anchor::At(pos) => pos,
+    };
+
+    let local = |(x, y, z): (i32, i32, i32)| format!("({}, {}, {})", -x, -y, -z);
+    let before = schem.paste_offset();
+    schem.set_paste_anchor(anchor);
+    println!("Paste anchor: local {} -> {}", local(before), local(schem.paste_offset()));
+    save_schematic(&schem, output)?;
+    log::info!("Wrote {}", output.display());
+    Ok(())
+}
+
+fn cmd_voxelize(file: &Path, size: u32, output: &Path, palette: BlockPalette, solid: bool, block: &str) -> Result<()> {
+    use schem_tool::export3d::ColorMatcher;
+    use schem_tool::voxelize::{Mesh, VoxelGrid};
+
+    let mesh = Mesh::load_obj(file)?;
+    let grid = VoxelGrid::from_mesh(&mesh, size, solid)?;
+    let matcher = ColorMatcher::new(palette.blocks());
+    let fallback = schem_tool::Block::new(block);
+    let schem = grid.to_schematic(|color| match color.and_then(|color| matcher.nearest(color)) {
+        Some((block, _)) => block.clone(),
+        None => fallback.clone(),
+    });
+
+    println!("{} triangles -> {}x{}x{}, {} blocks", mesh.triangles.len(), grid.width, grid.height, grid.length, grid.filled());
+    save_schematic(&schem, output)?;
+    log::info!("Wrote {}", output.display());
+    Ok(())
+}
+
+fn cmd_from_image(file: &Path, output: &Path, options: &schem_tool::pixel_art::PixelArtOptions) -> Result<()> {
+    let image = image::open(file).with_context(|| format!("Failed to read image {}", file.display()))?.to_rgba8();
+    let schem = schem_tool::pixel_art::from_image(&image, options)?;
 
-        println!();
+    let mut blocks: Vec<(String, usize)> = schem.block_counts().into_iter().filter(|(name, _)| BlockClass::of(name) != BlockClass::Air).collect();
+    blocks.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    println!("{}x{} pixels -> {} blocks", schem.width, schem.height.max(schem.length), schem.solid_blocks());
+    println!("Blocks needed:");
+    for (name, count) in blocks {
+        println!("  {:>8}  {}", count, schem_tool::recipes::material_label(&name));
     }
 
-    println!("Total: {} signs", signs.len());
+    save_schematic(&schem, output)?;
+    log::info!("Wrote {}", output.display());
+    Ok(())
+}
+
+/// Command line text with `\n` typed literally turned into newlines
+fn unescape_lines(text: &str) -> String {
+    text.replace("\\n", "\n")
+}
 
+fn cmd_make_sign(text: &str, output: &Path, options: &schem_tool::labels::SignOptions) -> Result<()> {
+    let text = unescape_lines(text);
+    let lines: Vec<&str> = text.lines().collect();
+    let schem = schem_tool::labels::sign_schematic(&lines, options)?;
+    save_schematic(&schem, output)?;
+    log::info!("Wrote {}", output.display());
     Ok(())
 }
 
-fn cmd_metadata(file: &PathBuf) -> Result<()> {
-    let schem = UnifiedSchematic::load(file)?;
-    let meta = &schem.metadata;
+fn cmd_make_text(text: &str, output: &Path, block: &str) -> Result<()> {
+    let schem = schem_tool::labels::text_schematic(&unescape_lines(text), block)?;
+    println!("{}x{} blocks, {} {}", schem.width, schem.length, schem.solid_blocks(), schem_tool::recipes::material_label(block));
+    save_schematic(&schem, output)?;
+    log::info!("Wrote {}", output.display());
+    Ok(())
+}
 
-    println!("{}", "=== Metadata ===".bold().cyan());
-    println!();
+/// The `--map` file of `strip-mods` and `downgrade`, with `--placeholder` over its own
+fn load_mod_mapping(map: Option<&Path>, placeholder: Option<&str>) -> Result<schem_tool::mods::ModMapping> {
+    let mut mapping = match map {
+        Some(path) => {
+            let text = std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+            parse_mod_mapping(&text).with_context(|| format!("Invalid mapping file {}", path.display()))?
+        }
+        None => schem_tool::mods::ModMapping::new(),
+    };
+    if let Some(placeholder) = placeholder {
+        mapping = mapping.with_placeholder(placeholder);
+    }
+    Ok(mapping)
+}
 
-    println!("  Name:   {}", meta.name.as_deref().unwrap_or("(not set)"));
-    println!("  Author: {}", meta.author.as_deref().unwrap_or("(not set)"));
+fn cmd_strip_mods(file: &Path, map: Option<&Path>, placeholder: Option<&str>, output: &Path, report_path: Option<&Path>) -> Result<()> {
+    let mapping = load_mod_mapping(map, placeholder)?;
+    let modded = mapping.modded_substitutes();
+    if !modded.is_empty() {
+        anyhow::bail!("Substitutes must be vanilla blocks: {}", modded.join(", "));
+    }
 
-    if let Some(date) = meta.date {
-        println!("  Date:   {}", format_timestamp(date));
-    } else {
-        println!("  Date:   (not set)");
+    let mut schem = load_schematic(file)?;
+    let report = schem_tool::mods::strip_mods(&mut schem, &mapping);
+    for replacement in &report.replacements {
+        let note = if replacement.mapped { "" } else { " (placeholder)" };
+        println!("{:>8}  {} -> {}{}", replacement.count, replacement.from, replacement.to, note);
     }
+    println!("Replaced {} blocks of {} mod block types; removed {} block entities",
+        report.blocks, report.replacements.len(), report.block_entities);
 
-    if meta.required_mods.is_empty() {
-        println!("  Mods:   (none)");
-    } else {
-        println!("  Mods:");
-        for mod_name in &meta.required_mods {
-            println!("    - {}", mod_name);
-        }
+    if let Some(path) = report_path {
+        std::fs::write(path, serde_json::to_string_pretty(&report)?)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
     }
+    save_schematic(&schem, output)?;
+    log::info!("Wrote {}", output.display());
+    Ok(())
+}
 
-    if !meta.extra.is_empty() {
+fn cmd_downgrade(file: &Path, target: &str, mapping: &schem_tool::mods::ModMapping, force: bool, output: &Path, report_path: Option<&Path>) -> Result<()> {
+    use schem_tool::downgrade::Source;
+
+    let Some(release) = schem_tool::versions::find_release(target) else {
+        let names: Vec<&str> = schem_tool::versions::RELEASES.iter().map(|release| release.name).collect();
+        anyhow::bail!("Unknown release '{}' (expected one of: {})", target, names.join(", "));
+    };
+    let mut schem = load_schematic(file)?;
+    let report = schem_tool::downgrade::downgrade(&mut schem, release, mapping, force);
+
+    for substitution in &report.substitutions {
+        let note = match substitution.source {
+            Source::Map => " (map)",
+            Source::Builtin => "",
+            Source::Placeholder => " (placeholder)",
+        };
+        println!("{:>8}  {} -> {}{}", substitution.count, substitution.from, substitution.to, note);
+    }
+    println!("Replaced {} blocks of {} types for {}; removed {} block entities",
+        report.blocks(), report.substitutions.len(), release, report.block_entities);
+    if !report.irreplaceable.is_empty() {
         println!();
-        println!("  Extra fields:");
-        for (key, value) in &meta.extra {
-            println!("    {}: {}", key.yellow(), value);
+        println!("{}", format!("Not in {} and no substitute:", release).yellow());
+        for (name, count) in &report.irreplaceable {
+            println!("{:>8}  {}", count, name);
         }
     }
 
+    if let Some(path) = report_path {
+        std::fs::write(path, serde_json::to_string_pretty(&report)?)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+    if !report.irreplaceable.is_empty() && !force {
+        anyhow::bail!(
+            "{} block types don't exist in {}; map them with --map, or use --force to replace them with the placeholder",
+            report.irreplaceable.len(), release
+        );
+    }
+    save_schematic(&schem, output)?;
+    log::info!("Wrote {}", output.display());
     Ok(())
 }
 
-fn cmd_get_block(file: &PathBuf, x: u16, y: u16, z: u16) -> Result<()> {
-    let schem = UnifiedSchematic::load(file)?;
+#[cfg(feature = "signing")]
+fn cmd_seal(file: &Path, key: &Path, output: &Path) -> Result<()> {
+    use schem_tool::signing;
 
-    if let Some(block) = schem.get_block(x, y, z) {
-        println!("Block at ({}, {}, {}): {}", x, y, z, block.full_name().green());
+    let pem = std::fs::read_to_string(key).with_context(|| format!("Failed to read {}", key.display()))?;
+    let key = signing::signing_key_from_pem(&pem)?;
+    let mut schem = load_schematic(file)?;
+    signing::seal(&mut schem, &key);
+    save_schematic(&schem, output)?;
+    log::info!("Sealed {} as {}", file.display(), output.display());
+    Ok(())
+}
 
-        if !block.state.properties.is_empty() {
-            println!();
-            println!("Properties:");
-            for (key, value) in &block.state.properties {
-                println!("  {} = {}", key.yellow(), value);
-            }
-        }
-    } else {
-        println!("Position ({}, {}, {}) is out of bounds", x, y, z);
-        println!("Schematic dimensions: {}x{}x{}", schem.width, schem.height, schem.length);
-    }
+#[cfg(feature = "signing")]
+fn cmd_verify(file: &Path, pubkey: &Path) -> Result<()> {
+    use schem_tool::signing;
 
+    let pem = std::fs::read_to_string(pubkey).with_context(|| format!("Failed to read {}", pubkey.display()))?;
+    let key = signing::verifying_key_from_pem(&pem)?;
+    let schem = load_schematic(file)?;
+    signing::verify(&schem, &key).with_context(|| format!("{} failed verification", file.display()))?;
+    println!("{}: signature OK", file.display());
     Ok(())
 }
 
-fn cmd_search(file: &PathBuf, pattern: &str, show_positions: bool, limit: Option<usize>) -> Result<()> {
-    let schem = UnifiedSchematic::load(file)?;
-    let pattern_lower = pattern.to_lowercase();
+#[cfg(feature = "server")]
+fn cmd_serve(dir: &Path, bind: &str, port: u16, max_blocks: usize) -> Result<()> {
+    anyhow::ensure!(dir.is_dir(), "{} is not a folder", dir.display());
+    let options = schem_tool::server::ServeOptions { max_blocks, ..Default::default() };
+    log::info!("{}", "=== Serving Schematics ===".bold().cyan());
+    log::info!("");
+    log::info!("  Folder: {}", dir.display());
+    log::info!("  Open:   http://{}:{}/", if bind == "0.0.0.0" { "localhost" } else { bind }, port);
+    log::warn!("No authentication: anyone who can reach port {} can read these schematics", port);
+    log::info!("");
+    schem_tool::server::serve(dir, (bind, port), options)?;
+    Ok(())
+}
 
-    let mut matches: Vec<(u16, u16, u16, &schem_tool::Block)> = Vec::new();
+fn cmd_export_positions(file: &Path, patterns: &[String], output: Option<&Path>, format: PositionFormat, world: bool) -> Result<()> {
+    let schem = load_schematic(file)?;
+    let selector = BlockSelector::new(patterns);
+    match output {
+        Some(path) => {
+            let file = std::fs::File::create(path).with_context(|| format!("Failed to create {}", path.display()))?;
+            let mut out = std::io::BufWriter::new(file);
+            let count = write_positions(&schem, &selector, format, world, &mut out)?;
+            std::io::Write::flush(&mut out)?;
+            log::info!("Wrote {} positions to {}", count, path.display());
+        }
+        None => {
+            write_positions(&schem, &selector, format, world, &mut std::io::stdout().lock())?;
+        }
+    }
+    Ok(())
+}
 
+/// Write a row per matching block as it's found, so the matches are never
+/// all held at once; returns how many were written
+fn write_positions(
+    schem: &UnifiedSchematic,
+    selector: &BlockSelector,
+    format: PositionFormat,
+    world: bool,
+    out: &mut impl std::io::Write,
+) -> Result<u64> {
+    if format == PositionFormat::Csv {
+        writeln!(out, "x,y,z,name,properties")?;
+    }
+    let mut count = 0u64;
     for y in 0..schem.height {
         for z in 0..schem.length {
             for x in 0..schem.width {
-                if let Some(block) = schem.get_block(x, y, z) {
-                    if block.name.to_lowercase().contains(&pattern_lower) {
-                        matches.push((x, y, z, block));
+                let Some(block) = schem.get_block(x, y, z).filter(|block| selector.matches(block)) else {
+                    continue;
+                };
+                let (px, py, pz) = if world { schem.to_world(x, y, z) } else { (x as i32, y as i32, z as i32) };
+                let properties: BTreeMap<&String, &String> = block.state.properties.iter().collect();
+                match format {
+                    PositionFormat::Csv => {
+                        let properties: Vec<String> = properties.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+                        let properties = properties.join(",");
+                        if properties.contains([',', '"']) {
+                            writeln!(out, "{},{},{},{},\"{}\"", px, py, pz, block.name, properties.replace('"', "\"\""))?;
+                        } else {
+                            writeln!(out, "{},{},{},{},{}", px, py, pz, block.name, properties)?;
+                        }
+                    }
+                    PositionFormat::Jsonl => {
+                        let row = serde_json::json!({ "x": px, "y": py, "z": pz, "name": block.name, "properties": properties });
+                        serde_json::to_writer(&mut *out, &row)?;
+                        writeln!(out)?;
                     }
                 }
+                count += 1;
             }
         }
     }
+    Ok(count)
+}
 
-    if matches.is_empty() {
-        println!("No blocks matching '{}' found.", pattern);
-        return Ok(());
-    }
+fn cmd_materials(file: &Path, recipes: MaterialOptions, counting: CountOptions, style: ListStyle, strict: bool, fuel: Fuel) -> Result<()> {
+    let schem = load_schematic(file)?;
+    let block_counts = count_blocks(&schem, counting);
+    let report = schem_tool::recipes::material_report(&block_counts, recipes);
+    let materials = &report.materials;
+    let mut sorted: Vec<(String, u64)> = schem_tool::recipes::round_materials(materials).into_iter().collect();
+    style.order(&mut sorted);
+    let check_strict = || {
+        if strict && !report.unknown.is_empty() {
+            anyhow::bail!("No recipe known for {} item type(s)", report.unknown.len());
+        }
+        Ok(())
+    };
 
-    let display_count = limit.unwrap_or(matches.len()).min(matches.len());
+    if style.json {
+        let out = serde_json::json!({
+            "materials": style.json(&sorted),
+            "no_recipe": report.unknown,
+            "smelts": report.smelts.ceil() as u64,
+            "fuel": { fuel.item(): fuel.needed(report.smelts) },
+        });
+        println!("{}", serde_json::to_string_pretty(&out)?);
+        return check_strict();
+    }
 
-    println!("Found {} blocks matching '{}':", matches.len(), pattern);
-    println!();
+    if style.verbose {
+        log::info!("{}", "=== Original Blocks ===".bold().cyan());
+        let mut original: Vec<_> = block_counts.iter()
+            .filter(|(name, _)| BlockClass::of(name) != BlockClass::Air)
+            .collect();
+        original.sort_by(|a, b| b.1.cmp(a.1));
 
-    if show_positions {
-        for (x, y, z, block) in matches.iter().take(display_count) {
-            println!("  ({:3}, {:3}, {:3}): {}", x, y, z, block.full_name());
+        for (name, count) in original.iter().take(20) {
+            println!("  {:>10} x {}", count, name);
+        }
+        if original.len() > 20 {
+            println!("  ... and {} more types", original.len() - 20);
         }
+        log::info!("");
+    }
+
+    if recipes.stonecutter {
+        log::info!("{}", "=== Raw Materials Needed (Stonecutter Mode) ===".bold().cyan());
     } else {
-        // Group by block type
-        let mut by_type: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
-        for (_, _, _, block) in &matches {
-            *by_type.entry(block.full_name()).or_insert(0) += 1;
+        log::info!("{}", "=== Raw Materials Needed ===".bold().cyan());
+    }
+    log::info!("");
+
+    let display_limit = style.limit.unwrap_or(usize::MAX);
+
+    #[derive(tabled::Tabled)]
+    struct MaterialRow {
+        #[tabled(rename = "Material")]
+        name: String,
+        #[tabled(rename = "Count")]
+        count: String,
+        #[tabled(rename = "Stacks")]
+        stacks: String,
+    }
+
+    let table = |materials: &[(String, u64)]| {
+        let rows: Vec<MaterialRow> = materials.iter()
+            .map(|(name, count)| {
+                let stacks = count / 64;
+                let remainder = count % 64;
+                let stacks_str = if stacks > 0 {
+                    if remainder > 0 {
+                        format!("{} + {}", stacks, remainder)
+                    } else {
+                        format!("{} stacks", stacks)
+                    }
+                } else {
+                    format!("{}", remainder)
+                };
+
+                MaterialRow {
+                    name: schem_tool::recipes::material_label(name),
+                    count: format!("{}", count),
+                    stacks: stacks_str,
+                }
+            })
+            .collect();
+        render_table(rows)
+    };
+
+    if style.group_by.is_some() {
+        style.print_categories(&sorted, table);
+    } else {
+        println!("{}", table(&sorted[..sorted.len().min(display_limit)]));
+        if sorted.len() > display_limit {
+            log::info!("\n... and {} more materials", sorted.len() - display_limit);
         }
+    }
 
-        let mut sorted: Vec<_> = by_type.into_iter().collect();
-        sorted.sort_by(|a, b| b.1.cmp(&a.1));
+    if !report.unknown.is_empty() {
+        let names: Vec<String> = report.unknown.iter().map(|name| schem_tool::recipes::material_label(name)).collect();
+        log::warn!("No recipe known for: {} (counted as raw materials, so the totals may be incomplete)", names.join(", "));
+    }
 
-        for (name, count) in sorted.iter().take(display_count) {
-            println!("  {} x{}", name, count);
+    if recipes.consolidate_dyes {
+        let flowers: Vec<String> = sorted.iter()
+            .filter(|(name, _)| schem_tool::recipes::is_flower(name))
+            .map(|(name, count)| format!("{} x {}", count, schem_tool::recipes::material_label(name)))
+            .collect();
+        if !flowers.is_empty() {
+            log::info!("\n{}: {}", "Flowers needed".bold(), flowers.join(", "));
         }
     }
 
-    if display_count < matches.len() {
-        println!("\n... and {} more", matches.len() - display_count);
+    // Summary
+    let total_items: f64 = materials.values().sum();
+    let total_stacks = (total_items / 64.0).ceil() as u64;
+    log::info!("\n{}: ~{} items (~{} stacks)", "Total".bold(), total_items.ceil() as u64, total_stacks);
+    if report.smelts > 0.0 {
+        let amount = match fuel {
+            Fuel::Coal => format!("{} coal ({} blocks of coal)", fuel.needed(report.smelts), (report.smelts / 80.0).ceil()),
+            Fuel::Lava => format!("{} lava buckets", fuel.needed(report.smelts)),
+            Fuel::Kelp => format!("{} dried kelp blocks", fuel.needed(report.smelts)),
+        };
+        log::info!("{}: {} items to smelt, {}", "Fuel".bold(), report.smelts.ceil() as u64, amount);
     }
 
-    Ok(())
+    check_strict()
 }
 
-fn cmd_export(file: &PathBuf, output: &PathBuf) -> Result<()> {
-    let schem = UnifiedSchematic::load(file)?;
+/// Whole-item raw materials for a schematic file
+fn file_materials(file: &Path, stonecutter: bool, waterlogged: bool) -> Result<BTreeMap<String, u64>> {
+    let schem = load_schematic(file)?;
+    let block_counts = count_blocks(&schem, CountOptions { waterlogged, ..CountOptions::default() });
+    let materials = schem_tool::recipes::calculate_materials_with_options(&block_counts, stonecutter);
+    Ok(schem_tool::recipes::round_materials(&materials))
+}
 
-    let mut csv = String::from("block,count,percent\n");
+fn cmd_materials_diff(
+    old: &Path,
+    new: &Path,
+    sort: bool,
+    limit: Option<usize>,
+    stonecutter: bool,
+    waterlogged: bool,
+    json: bool,
+) -> Result<()> {
+    let old_materials = file_materials(old, stonecutter, waterlogged)?;
+    let new_materials = file_materials(new, stonecutter, waterlogged)?;
+    let mut diff = schem_tool::recipes::diff_materials(&old_materials, &new_materials);
+
+    if json {
+        let section = |items: &[(String, u64)]| items.iter().cloned().collect::<BTreeMap<_, _>>();
+        let out = serde_json::json!({
+            "additional": section(&diff.additional),
+            "surplus": section(&diff.surplus),
+            "unchanged": section(&diff.unchanged),
+        });
+        println!("{}", serde_json::to_string_pretty(&out)?);
+        return Ok(());
+    }
 
-    let counts = schem.block_counts();
-    let total: usize = counts.values().sum();
+    if sort {
+        for section in [&mut diff.additional, &mut diff.surplus, &mut diff.unchanged] {
+            section.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        }
+    }
 
-    let mut sorted: Vec<_> = counts.into_iter().collect();
-    sorted.sort_by(|a, b| b.1.cmp(&a.1));
+    #[derive(tabled::Tabled)]
+    struct DiffRow {
+        #[tabled(rename = "Material")]
+        name: String,
+        #[tabled(rename = "Old")]
+        old: u64,
+        #[tabled(rename = "New")]
+        new: u64,
+        #[tabled(rename = "Change")]
+        change: String,
+    }
 
-    for (name, count) in sorted {
-        let percent = if total > 0 {
-            (count as f64 / total as f64) * 100.0
+    let display_limit = limit.unwrap_or(usize::MAX);
+    let sections = [
+        ("Additional materials needed", &diff.additional, "+"),
+        ("Materials freed up", &diff.surplus, "-"),
+        ("Unchanged", &diff.unchanged, ""),
+    ];
+    for (title, items, sign) in sections {
+        log::info!("{}", format!("=== {} ===", title).bold().cyan());
+        if items.is_empty() {
+            println!("  (none)");
         } else {
-            0.0
-        };
-        csv.push_str(&format!("\"{}\",{},{:.2}\n", name, count, percent));
+            let rows: Vec<DiffRow> = items.iter().take(display_limit).map(|(name, amount)| DiffRow {
+                name: schem_tool::recipes::material_label(name),
+                old: old_materials.get(name).copied().unwrap_or(0),
+                new: new_materials.get(name).copied().unwrap_or(0),
+                change: if sign.is_empty() { "0".to_string() } else { format!("{}{}", sign, amount) },
+            }).collect();
+            println!("{}", render_table(rows));
+            if items.len() > display_limit {
+                log::info!("... and {} more materials", items.len() - display_limit);
+            }
+        }
+        log::info!("");
     }
 
-    std::fs::write(output, csv)?;
-    println!("Exported block list to: {}", output.display());
+    let additional: u64 = diff.additional.iter().map(|(_, n)| n).sum();
+    let surplus: u64 = diff.surplus.iter().map(|(_, n)| n).sum();
+    log::info!("{}: {} more items, {} freed up", "Total".bold(), additional, surplus);
 
     Ok(())
 }
 
-fn cmd_materials(file: &PathBuf, sort: bool, verbose: bool, limit: Option<usize>, stonecutter: bool) -> Result<()> {
-    let schem = UnifiedSchematic::load(file)?;
-    let block_counts = schem.block_counts();
-
-    if verbose {
-        println!("{}", "=== Original Blocks ===".bold().cyan());
-        let mut original: Vec<_> = block_counts.iter()
-            .filter(|(name, _)| !name.contains("air"))
-            .collect();
-        original.sort_by(|a, b| b.1.cmp(a.1));
+fn cmd_build_order(file: &Path, output: Option<&Path>) -> Result<()> {
+    let schem = load_schematic(file)?;
+    let order = schem_tool::build_order::build_order(&schem);
+
+    let mut text = String::new();
+    let mut total = 0u64;
+    for placements in order.values() {
+        for placement in placements {
+            text.push_str(&placement.to_string());
+            text.push('\n');
+            total += placement.count as u64;
+        }
+    }
 
-        for (name, count) in original.iter().take(20) {
-            println!("  {:>10} x {}", count, name);
+    match output {
+        Some(path) => {
+            std::fs::write(path, text)?;
+            log::info!("Wrote {} placements to {}", total, path.display());
         }
-        if original.len() > 20 {
-            println!("  ... and {} more types", original.len() - 20);
+        None => {
+            print!("{}", text);
+            log::info!("\nTotal: {} blocks in {} layers", total, order.len());
         }
-        println!();
     }
 
-    if stonecutter {
-        println!("{}", "=== Raw Materials Needed (Stonecutter Mode) ===".bold().cyan());
-    } else {
-        println!("{}", "=== Raw Materials Needed ===".bold().cyan());
+    Ok(())
+}
+
+fn cmd_supports(file: &Path, max_reach: u32, limit: usize) -> Result<()> {
+    let schem = load_schematic(file)?;
+    let report = schem_tool::supports::analyze(&schem, max_reach);
+
+    println!("Floating blocks:       {}", report.floating);
+    println!("Out of reach:          {}", report.unreachable());
+    println!("Clusters needing help: {}", report.clusters.len());
+    println!("Scaffold blocks (est): {}", report.scaffold().to_string().bold());
+
+    if report.clusters.is_empty() {
+        return Ok(());
     }
+
     println!();
+    println!("Worst clusters:");
+    for cluster in report.clusters.iter().take(limit) {
+        let (min, max) = (cluster.min, cluster.max);
+        println!("  ({}, {}, {}) to ({}, {}, {}): {} blocks, {} out of reach, ~{} scaffold{}",
+            min.0, min.1, min.2, max.0, max.1, max.2,
+            cluster.blocks, cluster.unreachable, cluster.scaffold,
+            if cluster.attached { "" } else { " (detached)" });
+    }
+    if report.clusters.len() > limit {
+        println!("  ... and {} more", report.clusters.len() - limit);
+    }
 
-    let materials = schem_tool::recipes::calculate_materials_with_options(&block_counts, stonecutter);
+    Ok(())
+}
 
-    let mut sorted: Vec<_> = materials.into_iter().collect();
-    if sort {
-        sorted.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
-    } else {
-        sorted.sort_by(|a, b| a.0.cmp(&b.0));
+/// The `--min`/`--max` box clamped to the schematic, shown in the banner
+fn render_region(schem: &UnifiedSchematic, source: &RenderSource) -> Result<Option<ExportRegion>> {
+    let (min, max) = (source.region_min, source.region_max);
+    if min.is_none() && max.is_none() {
+        return Ok(None);
+    }
+    let edge = (schem.width as i32 - 1, schem.height as i32 - 1, schem.length as i32 - 1);
+    let requested = ExportRegion::new(min.unwrap_or((0, 0, 0)), max.unwrap_or(edge));
+    let pos = |(x, y, z): (i32, i32, i32)| format!("{},{},{}", x, y, z);
+    let Some([x, y, z]) = requested.clamp(schem) else {
+        anyhow::bail!("Region {} to {} is outside the {} schematic", pos(requested.min), pos(requested.max), schem.dimensions_str());
+    };
+    let region = ExportRegion::new((x.0 as i32, y.0 as i32, z.0 as i32), (x.1 as i32, y.1 as i32, z.1 as i32));
+    if region != requested {
+        log::warn!("Region {} to {} reaches outside the schematic; clamped to {} to {}",
+            pos(requested.min), pos(requested.max), pos(region.min), pos(region.max));
     }
+    log::info!("  Region: {} to {} ({}x{}x{})", pos(region.min), pos(region.max),
+        x.1 - x.0 + 1, y.1 - y.0 + 1, z.1 - z.0 + 1);
+    Ok(Some(region))
+}
 
-    let display_limit = limit.unwrap_or(usize::MAX);
+/// Show the `--only`/`--exclude` filters in the banner, warning about any
+/// that match nothing in the schematic
+fn log_block_filters(schem: &UnifiedSchematic, source: &RenderSource) {
+    if source.only.is_none() && source.exclude.is_none() {
+        return;
+    }
+    let blocks = schem.unique_blocks();
+    for (label, selector) in [("Only", &source.only), ("Excluding", &source.exclude)] {
+        let Some(selector) = selector else { continue };
+        log::info!("  {}: {}", label, selector.patterns().join(", "));
+        for pattern in selector.patterns() {
+            let single = BlockSelector::new([pattern]);
+            if !blocks.iter().any(|block| single.matches(block)) {
+                log::warn!("No blocks in the schematic match '{}'", pattern);
+            }
+        }
+    }
+}
 
-    #[derive(tabled::Tabled)]
-    struct MaterialRow {
-        #[tabled(rename = "Material")]
-        name: String,
-        #[tabled(rename = "Count")]
-        count: String,
-        #[tabled(rename = "Stacks")]
-        stacks: String,
+/// Parse an `x,y,z` position
+fn parse_pos(text: &str) -> std::result::Result<(i32, i32, i32), String> {
+    let parts: Vec<_> = text.split(',').map(|part| part.trim().parse::<i32>()).collect();
+    match parts[..] {
+        [Ok(x), Ok(y), Ok(z)] => Ok((x, y, z)),
+        _ => Err(format!("expected x,y,z, got '{}'", text)),
     }
+}
 
-    let rows: Vec<MaterialRow> = sorted.iter()
-        .take(display_limit)
-        .map(|(name, count)| {
-            let rounded = count.ceil() as u64;
-            let stacks = rounded / 64;
-            let remainder = rounded % 64;
-            let stacks_str = if stacks > 0 {
-                if remainder > 0 {
-                    format!("{} + {}", stacks, remainder)
-                } else {
-                    format!("{} stacks", stacks)
-                }
-            } else {
-                format!("{}", remainder)
-            };
+/// Where `reanchor` puts the paste anchor
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Anchor {
+    Min,
+    /// Middle of the bottom layer, rounded down
+    Center,
+    /// Local position
+    At((i32, i32, i32)),
+}
+
+fn parse_anchor(text: &str) -> std::result::Result<Anchor, String> {
+    match text {
+        "min" => Ok(Anchor::Min),
+        "center" => Ok(Anchor::Center),
+        _ => parse_pos(text).map(Anchor::At).map_err(|_| format!("expected min, center or x,y,z, got '{}'", text)),
+    }
+}
+
+/// `now`, an RFC 3339 time, a date and time (taken as UTC), a date (midnight
+/// UTC) or milliseconds since the epoch, as milliseconds since the epoch
+fn parse_date(text: &str) -> std::result::Result<i64, String> {
+    use chrono::{NaiveDate, NaiveDateTime};
+    let text = text.trim();
+    if text == "now" {
+        return Ok(Utc::now().timestamp_millis());
+    }
+    if let Ok(millis) = text.parse::<i64>() {
+        return Ok(millis);
+    }
+    DateTime::parse_from_rfc3339(text)
+        .map(|time| time.timestamp_millis())
+        .or_else(|_| NaiveDateTime::parse_from_str(text, "%Y-%m-%dT%H:%M:%S").map(|time| time.and_utc().timestamp_millis()))
+        .or_else(|_| NaiveDate::parse_from_str(text, "%Y-%m-%d").map(|date| date.and_time(Default::default()).and_utc().timestamp_millis()))
+        .map_err(|_| format!("expected now, an ISO 8601 date or milliseconds since the epoch, got '{}'", text))
+}
+
+fn parse_gap(text: &str) -> std::result::Result<f32, String> {
+    match text.trim().parse::<f32>() {
+        Ok(gap) if gap.is_finite() && gap >= 0.0 => Ok(gap),
+        _ => Err(format!("expected a gap in blocks (0 or more), got '{}'", text)),
+    }
+}
+
+fn cmd_progress(file: &Path, world: &Path, origin: (i32, i32, i32), output: Option<&Path>, limit: usize) -> Result<()> {
+    let schem = load_schematic(file)?;
+    let mut world = schem_tool::anvil::World::open(world)?;
+    let progress = schem_tool::anvil::compare(&schem, &mut world, origin)?;
+
+    println!("Placed:        {} / {} ({})", progress.placed, progress.total,
+        format!("{:.1}%", progress.percent()).bold());
+    println!("Wrong state:   {}", progress.wrong_state);
+    println!("Wrong block:   {}", progress.wrong_block);
+    if progress.unloaded > 0 {
+        log::warn!("{} positions are in chunks that aren't generated and count as missing", progress.unloaded);
+    }
+
+    if !progress.remaining.is_empty() {
+        let mut remaining: Vec<_> = progress.remaining.iter().collect();
+        remaining.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+        println!();
+        println!("Remaining:");
+        for (name, count) in remaining.iter().take(limit) {
+            println!("  {:>8} x {}", count, name.strip_prefix("minecraft:").unwrap_or(name));
+        }
+        if remaining.len() > limit {
+            println!("  ... and {} more types", remaining.len() - limit);
+        }
+    }
+
+    if let Some(path) = output {
+        let mut remaining = progress.remaining_schematic(&schem);
+        let name = remaining.metadata.name.clone().unwrap_or_else(|| {
+            file.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default()
+        });
+        remaining.metadata.set_name(format!("{} (remaining)", name)).set_date(Utc::now().timestamp_millis());
+        save_schematic(&remaining, path)?;
+        log::info!("Wrote {} remaining blocks to {}", progress.missing.len(), path.display());
+    }
+
+    Ok(())
+}
+
+fn cmd_from_world(world: &Path, min: (i32, i32, i32), max: (i32, i32, i32), output: &Path) -> Result<()> {
+    let mut world = schem_tool::anvil::World::open(world)?;
+    let extraction = schem_tool::anvil::extract(&mut world, min, max)?;
+    let mut schem = extraction.schematic;
 
-            MaterialRow {
-                name: name.strip_prefix("minecraft:").unwrap_or(name).to_string(),
-                count: format!("{}", rounded),
-                stacks: stacks_str,
+    if extraction.missing_sections > 0 {
+        log::warn!("{} chunk sections were missing or not generated and are left as air", extraction.missing_sections);
+    }
+    let name = output.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default();
+    schem.metadata.set_name(name).set_date(Utc::now().timestamp_millis());
+    save_schematic(&schem, output)?;
+
+    log::info!("Wrote {}x{}x{} ({} blocks, {} block entities, {} entities) to {}",
+        schem.width, schem.height, schem.length, schem.solid_blocks(),
+        schem.block_entities.len(), schem.entities.len(), output.display());
+    if !schem.entities.is_empty() {
+        log::warn!("Entity data isn't written to the output file yet");
+    }
+    Ok(())
+}
+
+/// Write a schematic in the format its extension names, without the block
+/// entity data the `--strip-*` and `--clear-inventories` flags remove
+fn save_schematic(schem: &UnifiedSchematic, path: &Path) -> Result<()> {
+    TIMINGS.time("Writing schematic", || write_schematic(schem, path))
+}
+
+fn write_schematic(schem: &UnifiedSchematic, path: &Path) -> Result<()> {
+    let sanitized;
+    let schem = match SANITIZE.get().filter(|options| !options.is_empty()) {
+        Some(options) => {
+            let mut copy = schem.clone();
+            let report = schem_tool::sanitize::sanitize(&mut copy, options);
+            log_sanitize_report(&report);
+            sanitized = copy;
+            &sanitized
+        }
+        None => schem,
+    };
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("litematic") if NO_PREVIEW.load(Ordering::Relaxed) => schem.save_litematic_with_preview(path, None)?,
+        Some("litematic") => match PREVIEW_FROM.get() {
+            Some(image) => {
+                let image = image::open(image).with_context(|| format!("Failed to read preview image {}", image.display()))?;
+                schem.save_litematic_with_preview(path, Some(&PreviewImage::from_image(&image, PREVIEW_SIZE)))?
             }
-        })
-        .collect();
+            None => schem.save_litematic(path)?,
+        },
+        Some("schem") => schem.save_schem(path)?,
+        Some("nbt") => schem.save_structure(path)?,
+        _ => anyhow::bail!("Can't write {}: use a .litematic, .schem or .nbt file name", path.display()),
+    }
+    Ok(())
+}
+
+fn log_sanitize_report(report: &schem_tool::sanitize::SanitizeReport) {
+    if report.is_empty() {
+        log::info!("No block entity data to remove");
+        return;
+    }
+    if report.inventories > 0 {
+        log::info!("Emptied {} containers ({} item stacks)", report.inventories, report.stacks);
+    }
+    if report.signs > 0 {
+        log::info!("Blanked {} signs", report.signs);
+    }
+    if report.names > 0 {
+        log::info!("Removed names and locks from {} block entities", report.names);
+    }
+    for (id, count) in &report.removed {
+        log::info!("Removed {} {} block entities", count, id);
+    }
+}
+
+fn cmd_lighting(file: &Path, skylight: bool, maps: bool) -> Result<()> {
+    use schem_tool::lighting::{self, LightMap};
 
-    let table = Table::new(rows).with(Style::rounded()).to_string();
-    println!("{}", table);
+    let schem = load_schematic(file)?;
+    let map = LightMap::compute(&schem, skylight);
+    let spots = lighting::dark_spawnable_surfaces(&schem, &map, skylight);
+
+    let mut by_layer: BTreeMap<u32, Vec<(u32, u32)>> = BTreeMap::new();
+    for &(x, y, z) in &spots {
+        by_layer.entry(y).or_default().push((x, z));
+    }
 
-    if sorted.len() > display_limit {
-        println!("\n... and {} more materials", sorted.len() - display_limit);
+    if maps {
+        for (&y, layer) in &by_layer {
+            println!("Floor at Y={} ({} dark spots):", y, layer.len());
+            for z in 0..schem.length {
+                let row: String = (0..schem.width).map(|x| {
+                    if layer.contains(&(x, z)) {
+                        'X'
+                    } else if schem.get_block(x, y + 1, z).is_some_and(|b| b.light_emission() > 0) {
+                        '*'
+                    } else if schem.get_block(x, y, z).is_some_and(|b| !b.is_air()) {
+                        '#'
+                    } else {
+                        '.'
+                    }
+                }).collect();
+                println!("{}", row);
+            }
+            println!();
+        }
+        log::info!("Legend: X = dark spawnable floor, * = light source, # = block, . = air");
     }
 
-    // Summary
-    let total_items: f64 = sorted.iter().map(|(_, c)| c).sum();
-    let total_stacks = (total_items / 64.0).ceil() as u64;
-    println!("\n{}: ~{} items (~{} stacks)", "Total".bold(), total_items.ceil() as u64, total_stacks);
+    println!("Dark spawnable surfaces: {}{}", spots.len(), if skylight { "" } else { " (sky light ignored)" });
+    for (y, layer) in &by_layer {
+        println!("  Y={}: {}", y, layer.len());
+    }
 
     Ok(())
 }
 
-fn cmd_layer(file: &PathBuf, y: u16, ascii: bool) -> Result<()> {
-    let schem = UnifiedSchematic::load(file)?;
+/// How `layer` draws its slice
+struct LayerStyle<'a> {
+    ascii: bool,
+    legend_off: bool,
+    /// `--max-width`; the terminal width when unset and printing to a terminal
+    max_width: Option<u32>,
+    /// With `--color-blocks`: where colors come from, and whether to average textures
+    color_blocks: Option<(&'a RenderSource<'a>, bool)>,
+}
+
+/// Columns available for the layer view: `$COLUMNS` or 80 on a terminal, unlimited otherwise
+fn terminal_width() -> Option<u32> {
+    if !std::io::IsTerminal::is_terminal(&std::io::stdout()) {
+        return None;
+    }
+    Some(std::env::var("COLUMNS").ok().and_then(|c| c.parse().ok()).unwrap_or(80))
+}
+
+fn cmd_layer(file: &Path, axis: Axis, coord: u32, style: &LayerStyle) -> Result<()> {
+    let schem = load_schematic(file)?;
 
-    if y >= schem.height {
-        println!("Y level {} is out of bounds (max: {})", y, schem.height - 1);
+    if coord >= axis.extent(&schem) {
+        log::warn!("{} {} is out of bounds (max: {})", axis, coord, axis.extent(&schem).saturating_sub(1));
         return Ok(());
     }
 
-    println!("Layer at Y={} ({}x{})", y, schem.width, schem.length);
-    println!();
+    let (cols, height) = layer_view::slice_size(&schem, axis);
+    let cell_width = if style.color_blocks.is_some() { 2 } else { 1 };
+    let scale = style.max_width.or_else(terminal_width)
+        .map_or(1, |max_width| layer_view::fit_scale(cols, cell_width, max_width));
+    let (across, down) = axis.screen_axes();
+    let down = if down == Axis::Y { "Y from the top".to_string() } else { down.to_string() };
+    log::info!("Layer at {}={} ({}x{}; columns are {}, rows are {})", axis, coord, cols, height, across, down);
+    if scale > 1 {
+        log::info!("Scaled down 1:{} to fit; each cell shows the most common block in its square", scale);
+    }
+    log::info!("");
 
-    // Simple character mapping
-    let get_char = |block: &schem_tool::Block| -> char {
-        let name = block.display_name();
-
-        if block.is_air() {
-            return if ascii { '.' } else { ' ' };
-        }
-
-        if ascii {
-            // ASCII mode
-            if name.contains("stone") { return '#' }
-            if name.contains("dirt") || name.contains("grass") { return '~' }
-            if name.contains("wood") || name.contains("log") || name.contains("plank") { return '=' }
-            if name.contains("glass") { return 'o' }
-            if name.contains("water") { return 'w' }
-            if name.contains("lava") { return 'L' }
-            if name.contains("ore") { return '*' }
-            if name.contains("chest") { return 'C' }
-            if name.contains("door") { return 'D' }
-            if name.contains("torch") { return 'i' }
-            if name.contains("redstone") { return 'r' }
-            if name.contains("wool") || name.contains("concrete") { return '@' }
-            if name.contains("brick") { return 'B' }
-            if name.contains("iron") { return 'I' }
-            if name.contains("gold") { return 'G' }
-            if name.contains("diamond") { return '$' }
-            '#'
-        } else {
-            // Unicode mode
-            if name.contains("stone") { return '\u{2588}' } // █
-            if name.contains("dirt") || name.contains("grass") { return '\u{2593}' } // ▓
-            if name.contains("wood") || name.contains("log") || name.contains("plank") { return '\u{2592}' } // ▒
-            if name.contains("glass") { return '\u{25A1}' } // □
-            if name.contains("water") { return '\u{2248}' } // ≈
-            if name.contains("lava") { return '\u{2234}' } // ∴
-            if name.contains("ore") { return '\u{25C6}' } // ◆
-            if name.contains("chest") { return '\u{25A0}' } // ■
-            if name.contains("door") { return '\u{25AF}' } // ▯
-            if name.contains("torch") { return '\u{2020}' } // †
-            if name.contains("redstone") { return '\u{00B7}' } // ·
-            '\u{2591}' // ░
+    let cells = layer_view::slice_cells(&schem, axis, coord, scale).expect("coordinate checked above");
+    let Some((source, texture_colors)) = style.color_blocks else {
+        for row in &cells {
+            println!("{}", row.iter().map(|block| layer_view::slice_glyph(block, axis, style.ascii)).collect::<String>());
         }
-    };
-
-    // Print grid
-    for z in 0..schem.length {
-        for x in 0..schem.width {
-            if let Some(block) = schem.get_block(x, y, z) {
-                print!("{}", get_char(block));
-            } else {
-                print!("?");
+        if !style.legend_off {
+            log::info!("");
+            log::info!("Legend ({}mode):", if style.ascii { "ASCII " } else { "Unicode " });
+            for line in layer_view::legend(style.ascii) {
+                log::info!("  {}", line);
             }
         }
-        println!();
+        return Ok(());
+    };
+
+    let textures = if texture_colors { load_textures(source) } else { None };
+    let colors = render_colors(&schem, source, textures.as_ref())?;
+    let to_u8 = |v: f32| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+    let color = |block: &schem_tool::Block| {
+        let (r, g, b) = colors.get(&block.name);
+        (to_u8(r), to_u8(g), to_u8(b))
+    };
+    let truecolor = std::env::var("COLORTERM").is_ok_and(|v| v == "truecolor" || v == "24bit");
+    for row in &cells {
+        println!("{}", layer_view::color_row(row, color, truecolor));
     }
 
-    println!();
-    println!("Legend ({}mode):", if ascii { "ASCII " } else { "Unicode " });
-    if ascii {
-        println!("  . = air, # = stone, ~ = dirt/grass, = = wood");
-        println!("  o = glass, w = water, L = lava, * = ore");
-        println!("  C = chest, D = door, i = torch, r = redstone");
-    } else {
-        println!("  █ = stone, ▓ = dirt/grass, ▒ = wood, □ = glass");
-        println!("  ≈ = water, ∴ = lava, ◆ = ore, ■ = chest");
-        println!("  † = torch, · = redstone, ░ = other solid");
+    if !style.legend_off {
+        // The block types shown, most common first
+        let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for block in cells.iter().flatten().filter(|b| !b.is_air()) {
+            *counts.entry(block.name.as_str()).or_default() += 1;
+        }
+        let mut counts: Vec<(&str, usize)> = counts.into_iter().collect();
+        counts.sort_by_key(|&(_, n)| std::cmp::Reverse(n));
+        log::info!("");
+        log::info!("Legend:");
+        for &(name, _) in counts.iter().take(16) {
+            let swatch = layer_view::color_row(&[&schem_tool::Block::new(name)], color, truecolor);
+            log::info!("  {} {}", swatch, name.strip_prefix("minecraft:").unwrap_or(name));
+        }
+        if counts.len() > 16 {
+            log::info!("  ... and {} more", counts.len() - 16);
+        }
     }
 
     Ok(())
 }
 
-fn cmd_render_obj(file: &PathBuf, output: &PathBuf, hollow: bool, greedy: bool, use_models: bool, use_textures: bool, minecraft_path: Option<&std::path::Path>, resource_pack: Option<&std::path::Path>) -> Result<()> {
-    let schem = UnifiedSchematic::load(file)?;
+/// Where render commands get textures, models and colors from, and what they include
+struct RenderSource<'a> {
+    minecraft: Option<&'a Path>,
+    resource_pack: Option<&'a Path>,
+    /// Extract textures again instead of using the cache
+    refresh_textures: bool,
+    /// Frame of animated textures in exports
+    anim_frame: usize,
+    biome: Biome,
+    /// `--colors` override file
+    colors: Option<&'a Path>,
+    /// Where to list blocks that had no color after exporting
+    dump_unknown_colors: Option<&'a Path>,
+    /// Where to write the blocks that had no blockstate, model or texture
+    report: Option<&'a Path>,
+    /// How OBJ exports split into objects
+    obj_groups: ObjGroups,
+    /// `--min` and `--max` corners of the box to export
+    region_min: Option<(i32, i32, i32)>,
+    region_max: Option<(i32, i32, i32)>,
+    /// `--only` and `--exclude` block filters
+    only: Option<BlockSelector>,
+    exclude: Option<BlockSelector>,
+    /// `--explode` gap and slice height (GLB only)
+    explode: Option<Explode>,
+    /// Render barriers, structure voids and light blocks
+    include_technical: bool,
+}
 
-    println!("{}", "=== Exporting to OBJ ===".bold().cyan());
-    println!();
-    println!("  Schematic: {}x{}x{}", schem.width, schem.height, schem.length);
-    println!("  Solid blocks: {}", schem.solid_blocks());
+/// Load textures for a render command, reporting what was found
+fn load_textures(source: &RenderSource) -> Option<TextureManager> {
+    log::info!("  Textures: {}", "loading...".yellow());
+    let tm = TIMINGS.time("Loading textures", || {
+        TextureManager::from_minecraft_with_options(source.minecraft, source.resource_pack, source.refresh_textures)
+    });
+    match tm {
+        Some(tm) => {
+            let count = tm.texture_count();
+            let origin = if count.extracted > 0 { "extracted" } else { "from cache" };
+            let rp_count = tm.resource_pack_texture_count();
+            if rp_count > 0 {
+                log::info!("  Textures: {} vanilla ({}) + {} from resource pack",
+                    count.total().to_string().green(), origin,
+                    rp_count.to_string().green());
+            } else {
+                log::info!("  Textures: {} textures loaded ({})", count.total().to_string().green(), origin);
+            }
+            if source.biome != Biome::Plains {
+                log::info!("  Biome tint: {}", source.biome.to_string().green());
+            }
+            Some(tm.with_biome(source.biome).with_anim_frame(source.anim_frame))
+        }
+        None => {
+            log::info!("  Textures: {} (Minecraft not found, using colors)", "unavailable".red());
+            if source.minecraft.is_none() {
+                log::info!("  {}: Use --minecraft <path> to specify Minecraft directory or client.jar", "Hint".yellow());
+            }
+            None
+        }
+    }
+}
+
+fn cmd_render_obj(
+    file: &Path,
+    output: &Path,
+    hollow: bool,
+    greedy: bool,
+    use_models: bool,
+    use_textures: bool,
+    source: &RenderSource,
+) -> Result<()> {
+    let minecraft_path = source.minecraft;
+    let resource_pack = source.resource_pack;
+    let schem = load_schematic(file)?;
+
+    log::info!("{}", "=== Exporting to OBJ ===".bold().cyan());
+    log::info!("");
+    log::info!("  Schematic: {}x{}x{}", schem.width, schem.height, schem.length);
+    log::info!("  Solid blocks: {}", schem.solid_blocks());
+    let region = render_region(&schem, source)?;
+    log_block_filters(&schem, source);
 
     if use_models {
-        println!("  Mode: {} (accurate Minecraft geometry)", "JSON models".green());
+        log::info!("  Mode: {} (accurate Minecraft geometry)", "JSON models".green());
     } else if greedy {
-        println!("  Mode: {} (optimized polygon count)", "greedy meshing".green());
+        log::info!("  Mode: {} (optimized polygon count)", "greedy meshing".green());
     } else {
-        println!("  Hollow mode: {}", if hollow { "yes (only visible faces)" } else { "no (all blocks)" });
+        log::info!("  Hollow mode: {}", if hollow { "yes (only visible faces)" } else { "no (all blocks)" });
     }
+    log::info!("  Objects: {}", match source.obj_groups {
+        ObjGroups::Material => "one per material",
+        ObjGroups::Chunk => "one per 16x16x16 region",
+        ObjGroups::None => "one for everything",
+    });
 
     if let Some(rp) = resource_pack {
-        println!("  Resource pack: {}", rp.display().to_string().green());
+        log::info!("  Resource pack: {}", rp.display().to_string().green());
     }
 
     // Try to load textures if requested
     let textures = if use_textures {
-        println!("  Textures: {}", "loading...".yellow());
-        let tm = schem_tool::textures::TextureManager::from_minecraft_with_path(minecraft_path, resource_pack);
-        match tm {
-            Some(tm) => {
-                let rp_count = tm.resource_pack_texture_count();
-                if rp_count > 0 {
-                    println!("  Textures: {} vanilla + {} from resource pack",
-                        tm.texture_count().to_string().green(),
-                        rp_count.to_string().green());
-                } else {
-                    println!("  Textures: {} textures loaded", tm.texture_count().to_string().green());
-                }
-                Some(tm)
-            }
-            None => {
-                println!("  Textures: {} (Minecraft not found, using colors)", "unavailable".red());
-                if minecraft_path.is_none() {
-                    println!("  {}: Use --minecraft <path> to specify Minecraft directory or client.jar", "Hint".yellow());
-                }
-                None
-            }
-        }
+        load_textures(source)
     } else {
-        println!("  Textures: disabled (use --textures to enable)");
+        log::info!("  Textures: disabled (use --textures to enable)");
         None
     };
-    println!();
+    log::info!("");
+
+    let colors = render_colors(&schem, source, textures.as_ref())?;
+    let console = ConsoleProgress::new();
+    let progress = export_progress(&console);
+    let report = ExportReport::new();
+    let options = export_options(&progress, &colors, source, region).with_report(&report);
 
     if use_models {
         // Find Minecraft jar for models
@@ -904,101 +4379,103 @@ fn cmd_render_obj(file: &PathBuf, output: &PathBuf, hollow: bool, greedy: bool,
             schem_tool::textures::find_client_jar(&mc_dir)
                 .ok_or_else(|| anyhow::anyhow!("Could not find Minecraft client.jar"))?
         };
-        println!("  Using models from: {}", jar_path.display());
-        schem_tool::export3d::export_obj_with_models(&schem, output, &jar_path, textures.as_ref(), resource_pack)?;
+        log::info!("  Using models from: {}", jar_path.display());
+        schem_tool::export3d::export_obj_with_models(&schem, output, &jar_path, textures.as_ref(), resource_pack, &options)?;
     } else if greedy {
-        schem_tool::export3d::export_obj_greedy(&schem, output, textures.as_ref())?;
+        schem_tool::export3d::export_obj_greedy(&schem, output, textures.as_ref(), &options)?;
     } else {
-        schem_tool::export3d::export_obj_with_textures(&schem, output, hollow, true, textures.as_ref())?;
+        schem_tool::export3d::export_obj_with_textures(&schem, output, hollow, true, textures.as_ref(), &options)?;
+    }
+    if let Some(path) = source.dump_unknown_colors {
+        dump_unknown_colors(&colors, path)?;
+    }
+    if let Some(path) = source.report {
+        write_export_report(&report, path)?;
     }
 
     let mtl_path = output.with_extension("mtl");
-    println!();
-    println!("{}:", "Exported files".green());
-    println!("  OBJ: {}", output.display());
-    println!("  MTL: {}", mtl_path.display());
+    log::info!("");
+    log::info!("{}:", "Exported files".green());
+    log::info!("  OBJ: {}", output.display());
+    log::info!("  MTL: {}", mtl_path.display());
 
     if textures.is_some() {
         let tex_dir = output.parent().unwrap_or(std::path::Path::new(".")).join("textures");
-        println!("  Textures: {}", tex_dir.display());
+        log::info!("  Textures: {}", tex_dir.display());
     }
 
-    println!();
-    println!("Open in: Blender, Windows 3D Viewer, online viewers, etc.");
+    log::info!("");
+    log::info!("Open in: Blender, Windows 3D Viewer, online viewers, etc.");
     if textures.is_some() {
-        println!("{}: In Blender, ensure the textures folder is in the same directory as the OBJ file.", "Tip".yellow());
+        log::info!("{}: In Blender, ensure the textures folder is in the same directory as the OBJ file.", "Tip".yellow());
     }
 
     Ok(())
 }
 
-fn cmd_render_html(file: &PathBuf, output: &PathBuf, max_blocks: usize) -> Result<()> {
-    let schem = UnifiedSchematic::load(file)?;
-
-    println!("{}", "=== Exporting to HTML Viewer ===".bold().cyan());
-    println!();
-    println!("  Schematic: {}x{}x{}", schem.width, schem.height, schem.length);
-    println!("  Max blocks to render: {}", max_blocks);
-    println!();
-
-    schem_tool::export3d::export_html(&schem, output, max_blocks)?;
+fn cmd_render_html(file: &Path, output: &Path, max_blocks: usize, texture_colors: bool, source: &RenderSource) -> Result<()> {
+    let schem = load_schematic(file)?;
+
+    log::info!("{}", "=== Exporting to HTML Viewer ===".bold().cyan());
+    log::info!("");
+    log::info!("  Schematic: {}x{}x{}", schem.width, schem.height, schem.length);
+    let region = render_region(&schem, source)?;
+    log_block_filters(&schem, source);
+    log::info!("  Max blocks to render: {}", max_blocks);
+    let textures = if texture_colors { load_textures(source) } else { None };
+    log::info!("");
+
+    let colors = render_colors(&schem, source, textures.as_ref())?;
+    let console = ConsoleProgress::new();
+    let progress = export_progress(&console);
+    let options = export_options(&progress, &colors, source, region);
+    schem_tool::export3d::export_html(&schem, output, max_blocks, &options)?;
+    if let Some(path) = source.dump_unknown_colors {
+        dump_unknown_colors(&colors, path)?;
+    }
 
-    println!("{}:", "Exported".green());
-    println!("  HTML: {}", output.display());
-    println!();
-    println!("Open in any web browser for interactive 3D view.");
-    println!("Controls: drag to rotate, scroll to zoom.");
+    log::info!("{}:", "Exported".green());
+    log::info!("  HTML: {}", output.display());
+    log::info!("");
+    log::info!("Open in any web browser for interactive 3D view.");
+    log::info!("Controls: drag to rotate, scroll to zoom.");
 
     Ok(())
 }
 
 fn cmd_render_gltf(
-    file: &PathBuf,
-    output: &PathBuf,
+    file: &Path,
+    output: &Path,
     hollow: bool,
     models: bool,
     use_textures: bool,
-    minecraft: Option<&std::path::Path>,
-    resource_pack: Option<&std::path::Path>,
+    source: &RenderSource,
 ) -> Result<()> {
-    let schem = UnifiedSchematic::load(file)?;
-
-    println!("{}", "=== Exporting to GLB ===".bold().cyan());
-    println!();
-    println!("  Schematic: {}x{}x{}", schem.width, schem.height, schem.length);
-    println!("  Solid blocks: {}", schem.solid_blocks());
-    println!("  Mode: {}", if models { "JSON models (accurate geometry)".green() } else { "cubes".green() });
-    if hollow { println!("  Hollow: only visible blocks"); }
+    let minecraft = source.minecraft;
+    let resource_pack = source.resource_pack;
+    let schem = load_schematic(file)?;
+
+    log::info!("{}", "=== Exporting to GLB ===".bold().cyan());
+    log::info!("");
+    log::info!("  Schematic: {}x{}x{}", schem.width, schem.height, schem.length);
+    log::info!("  Solid blocks: {}", schem.solid_blocks());
+    let region = render_region(&schem, source)?;
+    log_block_filters(&schem, source);
+    log::info!("  Mode: {}", if models { "JSON models (accurate geometry)".green() } else { "cubes".green() });
+    if hollow { log::info!("  Hollow: only visible blocks"); }
+    if let Some(explode) = source.explode {
+        let slices = if explode.every == 1 { "each layer".to_string() } else { format!("every {} layers", explode.every) };
+        log::info!("  Exploded: {} apart by {} blocks", slices, explode.gap);
+    }
 
     // Load textures if requested
     let textures = if use_textures {
-        println!("  Textures: {}", "loading...".yellow());
-        let tm = schem_tool::textures::TextureManager::from_minecraft_with_path(minecraft, resource_pack);
-        match tm {
-            Some(tm) => {
-                let rp_count = tm.resource_pack_texture_count();
-                if rp_count > 0 {
-                    println!("  Textures: {} vanilla + {} from resource pack",
-                        tm.texture_count().to_string().green(),
-                        rp_count.to_string().green());
-                } else {
-                    println!("  Textures: {} textures loaded", tm.texture_count().to_string().green());
-                }
-                Some(tm)
-            }
-            None => {
-                println!("  Textures: {} (Minecraft not found, using colors)", "unavailable".red());
-                if minecraft.is_none() {
-                    println!("  {}: Use --minecraft <path> to specify Minecraft directory or client.jar", "Hint".yellow());
-                }
-                None
-            }
-        }
+        load_textures(source)
     } else {
-        println!("  Textures: disabled (use --textures to enable)");
+        log::info!("  Textures: disabled (use --textures to enable)");
         None
     };
-    println!();
+    log::info!("");
 
     let jar_path = if models || use_textures {
         if let Some(mc_path) = minecraft {
@@ -1016,13 +4493,17 @@ fn cmd_render_gltf(
     };
 
     if models && jar_path.is_none() {
-        println!("  {}: Could not find Minecraft client.jar. Use --minecraft to specify path.", "Warning".yellow());
-        println!("  Falling back to simple cube geometry.");
-        println!();
+        log::warn!("Could not find Minecraft client.jar (use --minecraft to specify path); falling back to simple cube geometry");
+        log::info!("");
     } else if let Some(ref p) = jar_path {
-        println!("  Using models from: {}", p.display());
+        log::info!("  Using models from: {}", p.display());
     }
 
+    let colors = render_colors(&schem, source, textures.as_ref())?;
+    let console = ConsoleProgress::new();
+    let progress = export_progress(&console);
+    let report = ExportReport::new();
+    let options = export_options(&progress, &colors, source, region).with_report(&report);
     schem_tool::export_gltf::export_glb(
         &schem,
         output,
@@ -1030,18 +4511,463 @@ fn cmd_render_gltf(
         textures.as_ref(),
         hollow,
         resource_pack,
+        &options,
     )?;
+    if let Some(path) = source.report {
+        write_export_report(&report, path)?;
+    }
 
-    println!();
-    println!("{}:", "Exported".green());
-    println!("  GLB: {}", output.display());
-    println!();
-    println!("Open in: Blender, Windows 3D Viewer, online viewers, etc.");
+    log::info!("");
+    log::info!("{}:", "Exported".green());
+    log::info!("  GLB: {}", output.display());
+    log::info!("");
+    log::info!("Open in: Blender, Windows 3D Viewer, online viewers, etc.");
+
+    Ok(())
+}
+
+/// Extensions picked up by `batch`
+const SCHEMATIC_EXTENSIONS: &[&str] = &["schem", "schematic", "litematic", "nbt"];
+
+/// Schematic files under `dir`, sorted by path
+fn find_schematics(dir: &Path, recursive: bool) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+
+    while let Some(dir) = pending.pop() {
+        for entry in std::fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                if recursive {
+                    pending.push(path);
+                }
+            } else if path.extension()
+                .and_then(|e| e.to_str())
+                .map(|e| SCHEMATIC_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+                .unwrap_or(false)
+            {
+                files.push(path);
+            }
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+#[derive(Serialize)]
+struct BatchInfo {
+    format: String,
+    width: u32,
+    height: u32,
+    length: u32,
+    volume: usize,
+    solid_blocks: usize,
+    unique_types: usize,
+    block_entities: usize,
+    entities: usize,
+    name: Option<String>,
+    author: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "lowercase")]
+enum BatchData {
+    Info(BatchInfo),
+    Blocks(BTreeMap<String, usize>),
+    Materials(BTreeMap<String, u64>),
+    Hash { hash: String, shape_hash: String },
+}
+
+#[derive(Serialize)]
+struct BatchResult {
+    file: String,
+    #[serde(flatten)]
+    data: BatchData,
+}
+
+#[derive(Serialize)]
+struct BatchError {
+    file: String,
+    error: String,
+}
+
+fn analyze_file(path: &Path, command: BatchCommand, stonecutter: bool) -> Result<BatchData> {
+    let schem = load_schematic(path)?;
+    let data = match command {
+        BatchCommand::Info => BatchData::Info(BatchInfo {
+            format: format!("{:?}", schem.format),
+            width: schem.width,
+            height: schem.height,
+            length: schem.length,
+            volume: schem.volume(),
+            solid_blocks: schem.solid_blocks(),
+            unique_types: schem.block_counts().len(),
+            block_entities: schem.block_entities.len(),
+            entities: schem.entities.len(),
+            name: schem.metadata.name.clone(),
+            author: schem.metadata.author.clone(),
+        }),
+        BatchCommand::Blocks => BatchData::Blocks(schem.block_counts().into_iter().collect()),
+        BatchCommand::Materials => {
+            let materials = schem_tool::recipes::calculate_materials_with_options(&schem.block_counts(), stonecutter);
+            BatchData::Materials(materials.into_iter().map(|(name, count)| (name, count.ceil() as u64)).collect())
+        }
+        BatchCommand::Hash => {
+            let fingerprint = schem_tool::fingerprint::Fingerprint::of(&schem);
+            BatchData::Hash { hash: format!("{:016x}", fingerprint.content), shape_hash: format!("{:016x}", fingerprint.shape) }
+        }
+    };
+    Ok(data)
+}
+
+/// Quote a CSV field if it contains separators or quotes
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Every file's rows in file order; files that failed get one row with only the
+/// file and the error, so the CSV accounts for every input
+fn batch_csv(command: BatchCommand, outcomes: &[(String, Result<BatchData, String>)]) -> String {
+    let header = match command {
+        BatchCommand::Info => "file,format,width,height,length,volume,solid_blocks,unique_types,block_entities,entities,name,author,error\n",
+        BatchCommand::Blocks => "file,block,count,error\n",
+        BatchCommand::Materials => "file,material,count,error\n",
+        BatchCommand::Hash => "file,hash,shape_hash,error\n",
+    };
+    let mut csv = String::from(header);
+
+    for (file, outcome) in outcomes {
+        let file = csv_field(file);
+        let data = match outcome {
+            Ok(data) => data,
+            Err(error) => {
+                let empty = ",".repeat(header.matches(',').count() - 1);
+                csv.push_str(&format!("{}{},{}\n", file, empty, csv_field(error)));
+                continue;
+            }
+        };
+        match data {
+            BatchData::Info(info) => {
+                csv.push_str(&format!(
+                    "{},{},{},{},{},{},{},{},{},{},{},{},\n",
+                    file, info.format, info.width, info.height, info.length, info.volume,
+                    info.solid_blocks, info.unique_types, info.block_entities, info.entities,
+                    csv_field(info.name.as_deref().unwrap_or("")),
+                    csv_field(info.author.as_deref().unwrap_or("")),
+                ));
+            }
+            BatchData::Blocks(counts) => {
+                for (name, count) in counts {
+                    csv.push_str(&format!("{},{},{},\n", file, csv_field(name), count));
+                }
+            }
+            BatchData::Materials(counts) => {
+                for (name, count) in counts {
+                    csv.push_str(&format!("{},{},{},\n", file, csv_field(name), count));
+                }
+            }
+            BatchData::Hash { hash, shape_hash } => csv.push_str(&format!("{},{},{},\n", file, hash, shape_hash)),
+        }
+    }
+
+    csv
+}
+
+/// Run `analyze` on each of `files` on `jobs` threads, passing every outcome
+/// to `emit` in file order as soon as it and all the files before it are done,
+/// so only the files being worked on are loaded at once
+fn process_files<T: Send>(
+    files: &[PathBuf],
+    jobs: Option<usize>,
+    analyze: impl Fn(&Path) -> Result<T> + Sync,
+    mut emit: impl FnMut(&Path, Result<T, String>) -> Result<()>,
+) -> Result<()> {
+    use std::sync::atomic::AtomicUsize;
+
+    let jobs = jobs
+        .unwrap_or_else(|| std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1))
+        .clamp(1, files.len().max(1));
+
+    // Workers pull the next index, so one slow file doesn't hold up a whole chunk
+    let next = AtomicUsize::new(0);
+    let stop = AtomicBool::new(false);
+    let (sender, receiver) = std::sync::mpsc::channel();
+    std::thread::scope(|scope| {
+        for _ in 0..jobs {
+            let sender = sender.clone();
+            let (next, stop, analyze) = (&next, &stop, &analyze);
+            scope.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(path) = files.get(i).filter(|_| !stop.load(Ordering::Relaxed)) else { break };
+                // A panic on one malformed file shouldn't abort the whole batch
+                let outcome = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| analyze(path)))
+                    .unwrap_or_else(|_| Err(anyhow::anyhow!("parser panicked")))
+                    .map_err(|e| e.to_string());
+                if sender.send((i, outcome)).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+
+        // Outcomes that finished ahead of an earlier file
+        let mut waiting = BTreeMap::new();
+        let mut emitted = 0;
+        for (i, outcome) in receiver {
+            waiting.insert(i, outcome);
+            while let Some(outcome) = waiting.remove(&emitted) {
+                if let Err(e) = emit(&files[emitted], outcome) {
+                    stop.store(true, Ordering::Relaxed);
+                    return Err(e);
+                }
+                emitted += 1;
+            }
+        }
+        Ok(())
+    })
+}
+
+fn cmd_batch(
+    dir: &Path,
+    command: BatchCommand,
+    format: BatchFormat,
+    output: Option<&Path>,
+    recursive: bool,
+    jobs: Option<usize>,
+    stonecutter: bool,
+) -> Result<()> {
+    let files = find_schematics(dir, recursive)?;
+    let mut outcomes = Vec::new();
+    process_files(&files, jobs, |path| analyze_file(path, command, stonecutter), |path, outcome| {
+        outcomes.push((path.display().to_string(), outcome));
+        Ok(())
+    })?;
+
+    let failed = outcomes.iter().filter(|(_, outcome)| outcome.is_err()).count();
+    for (file, outcome) in &outcomes {
+        if let Err(error) = outcome {
+            log::error!("{}: {}", file, error);
+        }
+    }
+
+    let text = match format {
+        BatchFormat::Json => {
+            #[derive(Serialize)]
+            struct Report {
+                results: Vec<BatchResult>,
+                errors: Vec<BatchError>,
+            }
+            let mut report = Report { results: Vec::new(), errors: Vec::new() };
+            for (file, outcome) in outcomes {
+                match outcome {
+                    Ok(data) => report.results.push(BatchResult { file, data }),
+                    Err(error) => report.errors.push(BatchError { file, error }),
+                }
+            }
+            serde_json::to_string_pretty(&report)? + "\n"
+        }
+        BatchFormat::Csv => batch_csv(command, &outcomes),
+    };
+
+    match output {
+        Some(path) => std::fs::write(path, text)?,
+        None => print!("{}", text),
+    }
+
+    log::info!("Processed {} files ({} failed)", files.len(), failed);
+
+    Ok(())
+}
+
+/// Header of the `catalog` CSV
+const CATALOG_HEADER: &str =
+    "file,format,width,height,length,volume,solid_blocks,unique_types,author,name,date,data_version,fingerprint,error\n";
+
+/// One schematic's `catalog` row, or the row for a file that failed to load
+fn catalog_row(path: &Path) -> Result<String> {
+    let schem = load_schematic(path)?;
+    let metadata = &schem.metadata;
+    let fingerprint = schem_tool::fingerprint::Fingerprint::of(&schem);
+    Ok(format!(
+        "{},{:?},{},{},{},{},{},{},{},{},{},{},{:016x},\n",
+        csv_field(&path.display().to_string()), schem.format, schem.width, schem.height, schem.length,
+        schem.volume(), schem.solid_blocks(), schem.block_counts().len(),
+        csv_field(metadata.author.as_deref().unwrap_or("")),
+        csv_field(metadata.name.as_deref().unwrap_or("")),
+        metadata.date.map(format_timestamp).unwrap_or_default(),
+        metadata.data_version.map(|v| v.to_string()).unwrap_or_default(),
+        fingerprint.content,
+    ))
+}
+
+fn cmd_catalog(dir: &Path, output: Option<&Path>, recursive: bool, jobs: Option<usize>) -> Result<()> {
+    use std::io::Write;
+
+    let files = find_schematics(dir, recursive)?;
+    let mut out: Box<dyn Write> = match output {
+        Some(path) => Box::new(std::io::BufWriter::new(std::fs::File::create(path)?)),
+        None => Box::new(std::io::stdout().lock()),
+    };
+    out.write_all(CATALOG_HEADER.as_bytes())?;
+
+    let mut failed = 0;
+    process_files(&files, jobs, catalog_row, |path, row| {
+        let row = row.unwrap_or_else(|error| {
+            failed += 1;
+            log::warn!("{}: {}", path.display(), error);
+            format!("{},,,,,,,,,,,,,{}\n", csv_field(&path.display().to_string()), csv_field(&error))
+        });
+        Ok(out.write_all(row.as_bytes())?)
+    })?;
+    out.flush()?;
+
+    log::info!("Cataloged {} files ({} failed)", files.len(), failed);
+    Ok(())
+}
+
+/// Size and modification time, used to tell when a file is still being written
+fn file_stamp(path: &Path) -> Option<(u64, std::time::SystemTime)> {
+    let meta = std::fs::metadata(path).ok()?;
+    Some((meta.len(), meta.modified().ok()?))
+}
+
+/// Block until the file exists and its stamp has been unchanged for `settle`
+fn wait_until_settled(path: &Path, settle: std::time::Duration) -> (u64, std::time::SystemTime) {
+    let poll = std::time::Duration::from_millis(100).min(settle);
+    let mut last = file_stamp(path);
+    let mut stable_since = std::time::Instant::now();
+
+    loop {
+        std::thread::sleep(poll);
+        let current = file_stamp(path);
+        if current != last {
+            last = current;
+            stable_since = std::time::Instant::now();
+        } else if let Some(stamp) = last.filter(|(len, _)| *len > 0) {
+            if stable_since.elapsed() >= settle {
+                return stamp;
+            }
+        }
+    }
+}
+
+fn cmd_watch(file: &Path, exec: &str, output: &Path, settle_ms: u64, args: &[String], config: &Config) -> Result<()> {
+    use notify::{RecursiveMode, Watcher};
+    use std::sync::mpsc;
+
+    if !matches!(exec, "render-obj" | "render-html" | "render-gltf" | "render-glb") {
+        anyhow::bail!("watch can only run render-obj, render-html or render-gltf, not '{}'", exec);
+    }
 
+    // Parse the export command up front so typos fail immediately instead of on the first save
+    let argv = {
+        let mut argv = vec![
+            "schem-tool".into(),
+            exec.into(),
+            file.as_os_str().to_owned(),
+            "--output".into(),
+            output.as_os_str().to_owned(),
+        ];
+        argv.extend(args.iter().map(Into::into));
+        argv
+    };
+    let parse = || -> Result<Commands> { Ok(Cli::try_parse_from(&argv)?.command) };
+    parse()?;
+
+    let file_name = file.file_name()
+        .ok_or_else(|| anyhow::anyhow!("{} is not a file path", file.display()))?
+        .to_owned();
+    // Watch the directory: many savers write a temp file and rename it over the original
+    let dir = match file.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+    let settle = std::time::Duration::from_millis(settle_ms);
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx)?;
+    watcher.watch(&dir, RecursiveMode::NonRecursive)?;
+
+    log::info!("Watching {} (Ctrl+C to stop)", file.display());
+
+    let run_export = || {
+        loop {
+            let stamp = wait_until_settled(file, settle);
+            let started = std::time::Instant::now();
+            let result = parse().and_then(|command| run(command, config));
+            let time = chrono::Local::now().format("%H:%M:%S");
+
+            match result {
+                Ok(()) => {
+                    log::info!("[{}] {} {} ({:.1}s)", time, "Rebuilt".green(), output.display(), started.elapsed().as_secs_f64());
+                    return;
+                }
+                // Still being written when we read it; wait for it to settle again
+                Err(_) if file_stamp(file) != Some(stamp) => continue,
+                Err(e) => {
+                    log::error!("[{}] Export failed: {:#}", time, e);
+                    return;
+                }
+            }
+        }
+    };
+
+    run_export();
+
+    for event in rx.iter() {
+        let event = match event {
+            Ok(event) => event,
+            Err(e) => {
+                log::warn!("Watch error: {}", e);
+                continue;
+            }
+        };
+        if event.kind.is_access() || !event.paths.iter().any(|p| p.file_name() == Some(file_name.as_os_str())) {
+            continue;
+        }
+
+        // Collapse the burst of events from a single save
+        while rx.recv_timeout(settle).is_ok() {}
+        run_export();
+    }
+
+    Ok(())
+}
+
+fn cmd_config_show(config: &Config) -> Result<()> {
+    match (&config.source, config::default_path()) {
+        (Some(path), _) => log::info!("# Loaded from {}", path.display()),
+        (None, Some(path)) => log::info!("# No config file (would be read from {})", path.display()),
+        (None, None) => log::info!("# No config file"),
+    }
+    print!("{}", config.to_toml()?);
+    Ok(())
+}
+
+fn cmd_completions(shell: clap_complete::Shell) {
+    let mut cmd = cli_command();
+    let name = cmd.get_name().to_string();
+    clap_complete::generate(shell, &mut cmd, name, &mut std::io::stdout());
+}
+
+fn cmd_man(output: Option<&Path>) -> Result<()> {
+    let man = clap_mangen::Man::new(cli_command());
+    match output {
+        Some(path) => {
+            let mut file = std::fs::File::create(path)?;
+            man.render(&mut file)?;
+        }
+        None => man.render(&mut std::io::stdout())?,
+    }
     Ok(())
 }
 
-fn cmd_debug(file: &PathBuf) -> Result<()> {
+fn cmd_debug(file: &Path, path: Option<&str>, options: &nbt_dump::DumpOptions, output: Option<&Path>) -> Result<()> {
     use std::io::Read;
     use flate2::read::GzDecoder;
 
@@ -1060,66 +4986,339 @@ fn cmd_debug(file: &PathBuf) -> Result<()> {
 
     // Parse as generic NBT value
     let nbt: fastnbt::Value = fastnbt::from_bytes(&data)?;
+    let value = match path {
+        Some(path) => nbt_dump::select(&nbt, path).map_err(|e| anyhow::anyhow!("--path {}: {}", path, e))?,
+        None => &nbt,
+    };
 
-    println!("{}", "=== Raw NBT Structure ===".bold().cyan());
-    println!();
-    print_nbt_value(&nbt, 0);
+    if let Some(output) = output {
+        std::fs::write(output, nbt_dump::to_snbt(value))
+            .with_context(|| format!("Failed to write {}", output.display()))?;
+        log::info!("{} {}", "Wrote".green(), output.display());
+        return Ok(());
+    }
+
+    log::info!("{}", "=== Raw NBT Structure ===".bold().cyan());
+    if let Some(path) = path {
+        log::info!("Path: {}", path);
+    }
+    log::info!("");
+    print!("{}", nbt_dump::dump(value, options));
 
     Ok(())
 }
 
-fn print_nbt_value(value: &fastnbt::Value, indent: usize) {
-    let pad = "  ".repeat(indent);
-
-    match value {
-        fastnbt::Value::Byte(b) => println!("{}Byte: {}", pad, b),
-        fastnbt::Value::Short(s) => println!("{}Short: {}", pad, s),
-        fastnbt::Value::Int(i) => println!("{}Int: {}", pad, i),
-        fastnbt::Value::Long(l) => println!("{}Long: {}", pad, l),
-        fastnbt::Value::Float(f) => println!("{}Float: {}", pad, f),
-        fastnbt::Value::Double(d) => println!("{}Double: {}", pad, d),
-        fastnbt::Value::String(s) => println!("{}String: \"{}\"", pad, s),
-        fastnbt::Value::ByteArray(arr) => println!("{}ByteArray[{}]", pad, arr.len()),
-        fastnbt::Value::IntArray(arr) => println!("{}IntArray[{}]", pad, arr.len()),
-        fastnbt::Value::LongArray(arr) => println!("{}LongArray[{}]", pad, arr.len()),
-        fastnbt::Value::List(list) => {
-            println!("{}List[{}]:", pad, list.len());
-            for (i, item) in list.iter().enumerate().take(5) {
-                println!("{}  [{}]:", pad, i);
-                print_nbt_value(item, indent + 2);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn completion_script(shell: clap_complete::Shell) -> String {
+        let mut buf = Vec::new();
+        clap_complete::generate(shell, &mut cli_command(), "schem-tool", &mut buf);
+        String::from_utf8(buf).unwrap()
+    }
+
+    #[test]
+    fn test_cli_definition_is_valid() {
+        cli_command().debug_assert();
+    }
+
+    #[test]
+    fn test_log_level_is_global() {
+        let parse = |argv: &[&str]| {
+            let cli = Cli::try_parse_from(argv).unwrap();
+            match cli.command {
+                Commands::Blocks(BlocksArgs { verbose, .. }) => (cli.log_level, verbose),
+                _ => panic!("parsed the wrong command"),
             }
-            if list.len() > 5 {
-                println!("{}  ... and {} more", pad, list.len() - 5);
+        };
+        assert_eq!(parse(&["schem-tool", "blocks", "a.schem", "-v"]), (None, true));
+        assert_eq!(parse(&["schem-tool", "--log-level", "debug", "blocks", "a.schem"]), (Some(log::LevelFilter::Debug), false));
+        assert_eq!(parse(&["schem-tool", "blocks", "a.schem", "-v", "--log-level", "trace"]), (Some(log::LevelFilter::Trace), true));
+        assert!(Cli::try_parse_from(["schem-tool", "--log-level", "loud", "blocks", "a.schem"]).is_err());
+    }
+
+    #[test]
+    fn test_strict_flags_are_separate() {
+        let parse = |argv: &[&str]| {
+            let cli = Cli::try_parse_from(argv).unwrap();
+            match cli.command {
+                Commands::Materials(MaterialsArgs { strict, .. }) => (cli.strict_block_entities, strict),
+                _ => panic!("parsed the wrong command"),
             }
-        }
-        fastnbt::Value::Compound(map) => {
-            println!("{}Compound {{", pad);
-            for (key, val) in map {
-                print!("{}  {}: ", pad, key.yellow());
-                match val {
-                    fastnbt::Value::Compound(_) | fastnbt::Value::List(_) => {
-                        println!();
-                        print_nbt_value(val, indent + 2);
-                    }
-                    _ => {
-                        // Print simple values on same line
-                        match val {
-                            fastnbt::Value::Byte(b) => println!("{}", b),
-                            fastnbt::Value::Short(s) => println!("{}", s),
-                            fastnbt::Value::Int(i) => println!("{}", i),
-                            fastnbt::Value::Long(l) => println!("{}", l),
-                            fastnbt::Value::Float(f) => println!("{}", f),
-                            fastnbt::Value::Double(d) => println!("{}", d),
-                            fastnbt::Value::String(s) => println!("\"{}\"", s),
-                            fastnbt::Value::ByteArray(arr) => println!("ByteArray[{}]", arr.len()),
-                            fastnbt::Value::IntArray(arr) => println!("IntArray[{}]", arr.len()),
-                            fastnbt::Value::LongArray(arr) => println!("LongArray[{}]", arr.len()),
-                            _ => println!("{:?}", val),
-                        }
-                    }
-                }
+        };
+        assert_eq!(parse(&["schem-tool", "materials", "a.schem", "--strict"]), (false, true));
+        assert_eq!(parse(&["schem-tool", "--strict-block-entities", "materials", "a.schem"]), (true, false));
+        assert_eq!(parse(&["schem-tool", "materials", "a.schem", "--strict-block-entities"]), (true, false));
+    }
+
+    #[test]
+    fn test_completions_cover_commands_and_flags() {
+        // A renamed subcommand or flag must be caught here, not by users' shells
+        for shell in [clap_complete::Shell::Bash, clap_complete::Shell::Zsh, clap_complete::Shell::Fish] {
+            let script = completion_script(shell);
+            for word in ["render-obj", "render-gltf", "materials", "batch", "resource-pack", "stonecutter", "greedy"] {
+                assert!(script.contains(word), "{:?} completions missing {}", shell, word);
             }
-            println!("{}}}", pad);
         }
     }
+
+    #[test]
+    fn test_parse_colors_file() {
+        let colors = parse_colors(r##"
+            "create:*_casing" = "#9a7b4f"
+            "create:shaft" = [128, 128, 128]
+        "##).unwrap();
+        assert_eq!(colors.get("create:brass_casing"), (154.0 / 255.0, 123.0 / 255.0, 79.0 / 255.0));
+        assert_eq!(colors.get("create:shaft"), (128.0 / 255.0, 128.0 / 255.0, 128.0 / 255.0));
+        assert!(parse_colors(r#"stone = "gray""#).is_err());
+        assert!(parse_colors("stone = [300, 0, 0]").is_err());
+    }
+
+    #[test]
+    fn test_parse_anchor() {
+        assert_eq!(parse_anchor("min"), Ok(Anchor::Min));
+        assert_eq!(parse_anchor("center"), Ok(Anchor::Center));
+        assert_eq!(parse_anchor("-3,0,12"), Ok(Anchor::At((-3, 0, 12))));
+        assert!(parse_anchor("middle").is_err());
+    }
+
+    #[test]
+    fn test_set_metadata_round_trip() {
+        assert_eq!(parse_date("1714564800000"), Ok(1_714_564_800_000));
+        assert_eq!(parse_date("2024-05-01T12:00:00Z"), Ok(1_714_564_800_000));
+        assert_eq!(parse_date("2024-05-01T14:00:00+02:00"), Ok(1_714_564_800_000));
+        assert_eq!(parse_date("2024-05-01T12:00:00"), Ok(1_714_564_800_000));
+        assert_eq!(parse_date("2024-05-01"), Ok(1_714_521_600_000));
+        assert!(parse_date("now").unwrap() > 1_714_564_800_000);
+        assert!(parse_date("yesterday").is_err());
+
+        let mut builder = schem_tool::builder::SchematicBuilder::new(2, 1, 1);
+        builder.set_block(1, 0, 0, schem_tool::Block::new("minecraft:stone")).unwrap();
+        let mut schem = builder.build();
+        schem.metadata.set_name("Before").set_author("Alice").set_date(1000);
+        schem.metadata.data_version = Some(3700);
+        schem.metadata.raw_extra = Some(fastnbt::Value::Compound(std::collections::HashMap::from([
+            ("axiom:editor_metadata".to_string(), fastnbt::Value::String("kept".to_string())),
+        ])));
+
+        let dir = std::env::temp_dir().join(format!("schem-tool-set-metadata-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for (ext, format) in [("litematic", schem_tool::SchematicFormat::Litematica), ("schem", schem_tool::SchematicFormat::SpongeV3)] {
+            schem.format = format;
+            let (input, output) = (dir.join(format!("in.{}", ext)), dir.join(format!("out.{}", ext)));
+            save_schematic(&schem, &input).unwrap();
+            cmd_set_metadata(&input, Some("After"), None, Some(1_714_564_800_000), &output).unwrap();
+
+            let edited = load_schematic(&output).unwrap();
+            let meta = &edited.metadata;
+            assert_eq!((meta.name.as_deref(), meta.date), (Some("After"), Some(1_714_564_800_000)), "{}", ext);
+            assert_eq!((meta.author.as_deref(), meta.data_version), (Some("Alice"), Some(3700)), "{}", ext);
+            assert_eq!(meta.raw_extra, schem.metadata.raw_extra, "{}", ext);
+            assert_eq!(edited.blocks, schem.blocks);
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_parse_mod_mapping() {
+        let mapping = parse_mod_mapping(r#"
+            placeholder = "minecraft:cobblestone"
+
+            [blocks]
+            "create:andesite_casing" = "minecraft:stripped_spruce_wood[axis=y]"
+        "#).unwrap();
+        assert_eq!(mapping.substitute("create:andesite_casing").0.full_name(), "minecraft:stripped_spruce_wood[axis=y]");
+        assert_eq!(mapping.substitute("create:shaft"), (&schem_tool::Block::new("minecraft:cobblestone"), false));
+        assert!(parse_mod_mapping("fallback = \"minecraft:stone\"").is_err());
+    }
+
+    #[test]
+    fn test_get_block_accepts_negative_world_coordinates() {
+        let cli = Cli::try_parse_from(["schem-tool", "get-block", "a.schem", "-x", "-12", "-y", "64", "-z", "-3", "--world"]).unwrap();
+        match cli.command {
+            Commands::GetBlock(GetBlockArgs { x, y, z, world, .. }) => assert_eq!((x, y, z, world), (-12, 64, -3, true)),
+            _ => panic!("parsed the wrong command"),
+        }
+    }
+
+    #[test]
+    fn test_progress_parses_negative_origin() {
+        let cli = Cli::try_parse_from(["schem-tool", "progress", "a.schem", "--world", "w", "--origin", "-120,-60,8"]).unwrap();
+        match cli.command {
+            Commands::Progress(ProgressArgs { origin, .. }) => assert_eq!(origin, (-120, -60, 8)),
+            _ => panic!("parsed the wrong command"),
+        }
+        assert!(parse_pos("1,2").is_err());
+    }
+
+    #[test]
+    fn test_grouped_json() {
+        let mut items = vec![
+            ("minecraft:glass".to_string(), 3),
+            ("minecraft:oak_log".to_string(), 5),
+            ("minecraft:oak_slab".to_string(), 9),
+            ("minecraft:red_wool".to_string(), 2),
+        ];
+        let style = ListStyle { sort: true, limit: Some(1), group_by: Some(CountGrouping::Category), verbose: false, json: true };
+        style.order(&mut items);
+        assert_eq!(style.json(&items), serde_json::json!({
+            "wood": {"total": 14, "items": {"minecraft:oak_slab": 9}},
+            "glass": {"total": 3, "items": {"minecraft:glass": 3}},
+            "colored_red": {"total": 2, "items": {"minecraft:red_wool": 2}},
+        }));
+        let groups: Vec<_> = style.categories(&items).into_iter().map(|g| g.category.key()).collect();
+        assert_eq!(groups, ["wood", "glass", "colored_red"]);
+
+        let flat = ListStyle { group_by: None, limit: None, ..style };
+        assert_eq!(flat.json(&items)["minecraft:glass"], 3);
+    }
+
+    #[test]
+    fn test_aggregated_json() {
+        let items = [
+            ("minecraft:black_concrete".to_string(), 4),
+            ("minecraft:stone".to_string(), 5),
+            ("minecraft:white_concrete".to_string(), 2),
+        ];
+        let style = ListStyle { sort: true, limit: None, group_by: None, verbose: false, json: true };
+        let groups = aggregate_counts(&items, schem_tool::Aggregation::Color, style);
+        assert_eq!(groups.iter().map(|g| g.name.as_str()).collect::<Vec<_>>(), ["minecraft:*_concrete", "minecraft:stone"]);
+        assert_eq!(aggregate_json(&groups), serde_json::json!({
+            "minecraft:*_concrete": {"total": 6, "items": {"minecraft:black_concrete": 4, "minecraft:white_concrete": 2}},
+            "minecraft:stone": {"total": 5, "items": {"minecraft:stone": 5}},
+        }));
+    }
+
+    #[test]
+    fn test_palette_selectors() {
+        let mut builder = schem_tool::builder::SchematicBuilder::new(3, 1, 1);
+        let stairs = schem_tool::Block::with_state("minecraft:oak_stairs", schem_tool::BlockState {
+            properties: [("half", "top"), ("facing", "north")].into_iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+        });
+        builder.set_block(0, 0, 0, stairs.clone()).unwrap();
+        builder.set_block(1, 0, 0, stairs).unwrap();
+        let schem = builder.build();
+        assert_eq!(palette_selectors(&schem, true), "minecraft:air\t1\nminecraft:oak_stairs[facing=north,half=top]\t2\n");
+        assert_eq!(palette_selectors(&schem, false).lines().count(), 2);
+    }
+
+    #[test]
+    fn test_process_files_in_order() {
+        let files: Vec<PathBuf> = (0..20).map(|i| PathBuf::from(format!("{}.schem", i))).collect();
+        let analyze = |path: &Path| {
+            let i: u64 = path.file_stem().unwrap().to_str().unwrap().parse()?;
+            // Later files finish first
+            std::thread::sleep(std::time::Duration::from_millis(20 - i));
+            anyhow::ensure!(i != 7, "file {} is broken", i);
+            Ok(i)
+        };
+        let mut seen = Vec::new();
+        process_files(&files, Some(4), analyze, |path, outcome| {
+            seen.push((path.to_path_buf(), outcome.unwrap_or(99)));
+            Ok(())
+        }).unwrap();
+        let expected: Vec<(PathBuf, u64)> = files.iter().cloned().zip((0..20).map(|i| if i == 7 { 99 } else { i })).collect();
+        assert_eq!(seen, expected);
+
+        // An error from `emit` stops the run
+        let mut emitted = 0;
+        let result = process_files(&files, Some(2), analyze, |_, _| {
+            emitted += 1;
+            anyhow::ensure!(emitted < 3, "disk full");
+            Ok(())
+        });
+        assert_eq!((result.unwrap_err().to_string(), emitted), ("disk full".to_string(), 3));
+    }
+
+    #[test]
+    fn test_batch_csv_reports_errors() {
+        let outcomes = vec![
+            ("a.schem".to_string(), Ok(BatchData::Hash { hash: "01".to_string(), shape_hash: "02".to_string() })),
+            ("b.schem".to_string(), Err("unexpected end of file, at 3".to_string())),
+        ];
+        assert_eq!(
+            batch_csv(BatchCommand::Hash, &outcomes),
+            "file,hash,shape_hash,error\na.schem,01,02,\nb.schem,,,\"unexpected end of file, at 3\"\n",
+        );
+        // Failed rows line up with the header's columns
+        let info = batch_csv(BatchCommand::Info, &[("c.schem".to_string(), Err("truncated".to_string()))]);
+        let commas: Vec<usize> = info.lines().map(|line| line.matches(',').count()).collect();
+        assert_eq!(commas, [12, 12]);
+        assert!(info.ends_with(",truncated\n"));
+    }
+
+    #[test]
+    fn test_render_glb_explode_args() {
+        let parse = |extra: &[&str]| Cli::try_parse_from(["schem-tool", "render-glb", "a.schem", "-o", "a.glb"].iter().chain(extra));
+        match parse(&["--explode", "2.5", "--explode-every", "4"]).unwrap().command {
+            Commands::RenderGltf(RenderGltfArgs { explode, explode_every, .. }) => assert_eq!((explode, explode_every), (Some(2.5), 4)),
+            _ => panic!("parsed the wrong command"),
+        }
+        assert!(parse(&["--explode-every", "4"]).is_err());
+        assert!(parse(&["--explode", "2", "--explode-every", "0"]).is_err());
+        assert!(parse(&["--explode", "-1"]).is_err());
+    }
+
+    #[test]
+    fn test_render_flags_override_config() {
+        let parse = |extra: &[&str]| {
+            let argv = ["schem-tool", "render-obj", "a.schem", "-o", "a.obj"].iter().chain(extra);
+            match Cli::try_parse_from(argv).unwrap().command {
+                Commands::RenderObj(RenderObjArgs { textures, no_textures, .. }) => (textures, no_textures),
+                _ => panic!("parsed the wrong command"),
+            }
+        };
+        let textures = |extra: &[&str], config| {
+            let (on, off) = parse(extra);
+            render_flag(on, off, config)
+        };
+        assert!(!textures(&[], None));
+        assert!(textures(&[], Some(true)));
+        assert!(!textures(&["--no-textures"], Some(true)));
+        assert!(textures(&["-t"], Some(false)));
+        // The last of --textures and --no-textures wins
+        assert!(textures(&["--no-textures", "-t"], Some(false)));
+        assert!(!textures(&["-t", "--no-textures"], None));
+    }
+
+    #[test]
+    fn test_search_patterns() {
+        let patterns = |p: &[&str]| p.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let woods = search_regex(&patterns(&["^minecraft:(oak|spruce)_", "birch$"])).unwrap();
+        assert!(woods.is_match("minecraft:spruce_planks"));
+        assert!(woods.is_match("minecraft:birch"));
+        assert!(!woods.is_match("minecraft:stripped_oak_log"));
+        assert!(search_regex(&patterns(&["(oak"])).is_err());
+    }
+
+    #[test]
+    fn test_man_page_renders() {
+        let mut buf = Vec::new();
+        clap_mangen::Man::new(cli_command()).render(&mut buf).unwrap();
+        let page = String::from_utf8(buf).unwrap();
+        assert!(page.contains(".TH schem-tool"));
+    }
+
+    #[test]
+    fn test_write_positions() {
+        let mut builder = schem_tool::builder::SchematicBuilder::new(2, 1, 2);
+        let mut chest = schem_tool::Block::new("minecraft:chest");
+        chest.state.properties.insert("facing".to_string(), "north".to_string());
+        chest.state.properties.insert("type".to_string(), "left".to_string());
+        builder.set_block(1, 0, 0, chest).unwrap();
+        builder.set_block(0, 0, 1, schem_tool::Block::new("minecraft:stone")).unwrap();
+        let mut schem = builder.build();
+        schem.offset = (100, 64, -20);
+
+        let write = |format, world| {
+            let mut out = Vec::new();
+            let count = write_positions(&schem, &BlockSelector::new(["chest"]), format, world, &mut out).unwrap();
+            (count, String::from_utf8(out).unwrap())
+        };
+        assert_eq!(write(PositionFormat::Csv, false), (1, "x,y,z,name,properties\n1,0,0,minecraft:chest,\"facing=north,type=left\"\n".to_string()));
+        let (_, jsonl) = write(PositionFormat::Jsonl, true);
+        let row: serde_json::Value = serde_json::from_str(jsonl.trim_end()).unwrap();
+        assert_eq!(row, serde_json::json!({"x": 101, "y": 64, "z": -20, "name": "minecraft:chest", "properties": {"facing": "north", "type": "left"}}));
+    }
 }
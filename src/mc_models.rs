@@ -8,6 +8,7 @@ use std::io::Read;
 use std::path::Path;
 use serde::Deserialize;
 use zip::ZipArchive;
+use crate::SchemError;
 
 /// A 3D point in model space (0-16 scale)
 #[derive(Debug, Clone, Copy, Default, Deserialize)]
@@ -217,7 +218,7 @@ pub struct ModelManager {
 
 impl ModelManager {
     /// Create a new model manager from a Minecraft client.jar
-    pub fn from_jar<P: AsRef<Path>>(jar_path: P) -> std::io::Result<Self> {
+    pub fn from_jar<P: AsRef<Path>>(jar_path: P) -> Result<Self, SchemError> {
         Self::from_jar_with_resource_pack(jar_path, None::<&std::path::Path>)
     }
 
@@ -225,11 +226,11 @@ impl ModelManager {
     pub fn from_jar_with_resource_pack<P: AsRef<Path>, R: AsRef<Path>>(
         jar_path: P,
         resource_pack: Option<R>,
-    ) -> std::io::Result<Self> {
+    ) -> Result<Self, SchemError> {
         let jar_path = jar_path.as_ref();
-        let file = std::fs::File::open(jar_path)?;
+        let file = std::fs::File::open(jar_path).map_err(|e| SchemError::model(jar_path, e))?;
         let mut archive = ZipArchive::new(file)
-            .map_err(|e| std::io::Error::other(format!("Failed to open jar: {}", e)))?;
+            .map_err(|e| SchemError::model(jar_path, format!("Failed to open jar: {}", e)))?;
 
         let mut blockstates = HashMap::new();
         let mut models = HashMap::new();
@@ -237,7 +238,7 @@ impl ModelManager {
         // Load all blockstates
         for i in 0..archive.len() {
             let mut file = archive.by_index(i)
-                .map_err(|e| std::io::Error::other(e.to_string()))?;
+                .map_err(|e| SchemError::model(jar_path, e))?;
             let name = file.name().to_string();
 
             if name.starts_with("assets/minecraft/blockstates/") && name.ends_with(".json") {
@@ -248,27 +249,28 @@ impl ModelManager {
                     .unwrap();
 
                 let mut content = String::new();
-                file.read_to_string(&mut content)?;
+                file.read_to_string(&mut content)
+                    .map_err(|e| SchemError::model(jar_path, format!("{}: {}", name, e)))?;
 
                 match serde_json::from_str::<Blockstate>(&content) {
                     Ok(bs) => {
                         blockstates.insert(block_name.to_string(), bs);
                     }
                     Err(e) => {
-                        eprintln!("Warning: Failed to parse blockstate {}: {}", block_name, e);
+                        log::warn!("Failed to parse blockstate {}: {}", block_name, e);
                     }
                 }
             }
         }
 
         // Load all block models
-        let file = std::fs::File::open(jar_path)?;
+        let file = std::fs::File::open(jar_path).map_err(|e| SchemError::model(jar_path, e))?;
         let mut archive = ZipArchive::new(file)
-            .map_err(|e| std::io::Error::other(e.to_string()))?;
+            .map_err(|e| SchemError::model(jar_path, e))?;
 
         for i in 0..archive.len() {
             let mut file = archive.by_index(i)
-                .map_err(|e| std::io::Error::other(e.to_string()))?;
+                .map_err(|e| SchemError::model(jar_path, e))?;
             let name = file.name().to_string();
 
             if name.starts_with("assets/minecraft/models/block/") && name.ends_with(".json") {
@@ -279,20 +281,21 @@ impl ModelManager {
                     .unwrap();
 
                 let mut content = String::new();
-                file.read_to_string(&mut content)?;
+                file.read_to_string(&mut content)
+                    .map_err(|e| SchemError::model(jar_path, format!("{}: {}", name, e)))?;
 
                 match serde_json::from_str::<BlockModel>(&content) {
                     Ok(model) => {
                         models.insert(model_name.to_string(), model);
                     }
                     Err(e) => {
-                        eprintln!("Warning: Failed to parse model {}: {}", model_name, e);
+                        log::warn!("Failed to parse model {}: {}", model_name, e);
                     }
                 }
             }
         }
 
-        eprintln!("Loaded {} blockstates and {} models", blockstates.len(), models.len());
+        log::info!("Loaded {} blockstates and {} models", blockstates.len(), models.len());
 
         let mut manager = Self {
             blockstates,
@@ -307,11 +310,11 @@ impl ModelManager {
             match manager.load_resource_pack(pack_path.as_ref()) {
                 Ok((bs_count, model_count)) => {
                     if bs_count > 0 || model_count > 0 {
-                        eprintln!("Loaded {} blockstates and {} models from resource pack", bs_count, model_count);
+                        log::info!("Loaded {} blockstates and {} models from resource pack", bs_count, model_count);
                     }
                 }
                 Err(e) => {
-                    eprintln!("Warning: Failed to load resource pack: {}", e);
+                    log::warn!("Failed to load resource pack: {}", e);
                 }
             }
         }
@@ -320,10 +323,10 @@ impl ModelManager {
     }
 
     /// Load blockstates and models from a resource pack (ZIP file)
-    pub fn load_resource_pack(&mut self, pack_path: &Path) -> std::io::Result<(usize, usize)> {
-        let file = std::fs::File::open(pack_path)?;
+    pub fn load_resource_pack(&mut self, pack_path: &Path) -> Result<(usize, usize), SchemError> {
+        let file = std::fs::File::open(pack_path).map_err(|e| SchemError::model(pack_path, e))?;
         let mut archive = ZipArchive::new(file)
-            .map_err(|e| std::io::Error::other(format!("Failed to open resource pack: {}", e)))?;
+            .map_err(|e| SchemError::model(pack_path, format!("Failed to open resource pack: {}", e)))?;
 
         let mut bs_count = 0;
         let mut model_count = 0;
@@ -331,7 +334,7 @@ impl ModelManager {
         // Load blockstates
         for i in 0..archive.len() {
             let mut file = archive.by_index(i)
-                .map_err(|e| std::io::Error::other(e.to_string()))?;
+                .map_err(|e| SchemError::model(pack_path, e))?;
             let name = file.name().to_string();
 
             if name.starts_with("assets/minecraft/blockstates/") && name.ends_with(".json") {
@@ -342,7 +345,8 @@ impl ModelManager {
                     .unwrap();
 
                 let mut content = String::new();
-                file.read_to_string(&mut content)?;
+                file.read_to_string(&mut content)
+                    .map_err(|e| SchemError::model(pack_path, format!("{}: {}", name, e)))?;
 
                 match serde_json::from_str::<Blockstate>(&content) {
                     Ok(bs) => {
@@ -350,21 +354,21 @@ impl ModelManager {
                         bs_count += 1;
                     }
                     Err(e) => {
-                        eprintln!("Warning: Failed to parse resource pack blockstate {}: {}", block_name, e);
+                        log::warn!("Failed to parse resource pack blockstate {}: {}", block_name, e);
                     }
                 }
             }
         }
 
         // Reload archive for models
-        let file = std::fs::File::open(pack_path)?;
+        let file = std::fs::File::open(pack_path).map_err(|e| SchemError::model(pack_path, e))?;
         let mut archive = ZipArchive::new(file)
-            .map_err(|e| std::io::Error::other(e.to_string()))?;
+            .map_err(|e| SchemError::model(pack_path, e))?;
 
         // Load models
         for i in 0..archive.len() {
             let mut file = archive.by_index(i)
-                .map_err(|e| std::io::Error::other(e.to_string()))?;
+                .map_err(|e| SchemError::model(pack_path, e))?;
             let name = file.name().to_string();
 
             if name.starts_with("assets/minecraft/models/block/") && name.ends_with(".json") {
@@ -375,7 +379,8 @@ impl ModelManager {
                     .unwrap();
 
                 let mut content = String::new();
-                file.read_to_string(&mut content)?;
+                file.read_to_string(&mut content)
+                    .map_err(|e| SchemError::model(pack_path, format!("{}: {}", name, e)))?;
 
                 match serde_json::from_str::<BlockModel>(&content) {
                     Ok(model) => {
@@ -383,7 +388,7 @@ impl ModelManager {
                         model_count += 1;
                     }
                     Err(e) => {
-                        eprintln!("Warning: Failed to parse resource pack model {}: {}", model_name, e);
+                        log::warn!("Failed to parse resource pack model {}: {}", model_name, e);
                     }
                 }
             }
@@ -395,11 +400,34 @@ impl ModelManager {
         Ok((bs_count, model_count))
     }
 
-    /// Get model references for a block with given properties
+    /// Get model references for a block with given properties, taking the
+    /// first of any weighted alternatives
     /// Checks resource pack first, then falls back to vanilla
     pub fn get_models_for_block(&self, block_name: &str, properties: &HashMap<String, String>) -> Vec<(ModelRef, String)> {
         let name = block_name.strip_prefix("minecraft:").unwrap_or(block_name);
+        self.model_choices(name, properties).into_iter()
+            .filter_map(|choices| choices.first())
+            .map(|model_ref| (model_ref.clone(), name.to_string()))
+            .collect()
+    }
+
+    /// Like [`get_models_for_block`](Self::get_models_for_block), choosing
+    /// between weighted alternatives with `rng`
+    pub fn pick_models_for_block(
+        &self,
+        block_name: &str,
+        properties: &HashMap<String, String>,
+        rng: &mut impl rand::Rng,
+    ) -> Vec<(ModelRef, String)> {
+        let name = block_name.strip_prefix("minecraft:").unwrap_or(block_name);
+        self.model_choices(name, properties).into_iter()
+            .filter_map(|choices| crate::random::pick_weighted(choices, |model_ref| model_ref.weight, rng))
+            .map(|model_ref| (model_ref.clone(), name.to_string()))
+            .collect()
+    }
 
+    /// The models that apply to a block, each as its list of weighted alternatives
+    fn model_choices(&self, name: &str, properties: &HashMap<String, String>) -> Vec<&[ModelRef]> {
         // Check resource pack first, then vanilla
         let blockstate = self.resource_pack_blockstates.get(name)
             .or_else(|| self.blockstates.get(name));
@@ -458,17 +486,8 @@ impl ModelManager {
                 };
 
                 match variant {
-                    Some(Variants::Single(model_ref)) => {
-                        vec![(model_ref.clone(), name.to_string())]
-                    }
-                    Some(Variants::Multiple(refs)) => {
-                        // Just use the first one (or could be random weighted)
-                        if let Some(r) = refs.first() {
-                            vec![(r.clone(), name.to_string())]
-                        } else {
-                            Vec::new()
-                        }
-                    }
+                    Some(Variants::Single(model_ref)) => vec![std::slice::from_ref(model_ref)],
+                    Some(Variants::Multiple(refs)) => vec![refs.as_slice()],
                     None => Vec::new(),
                 }
             }
@@ -511,14 +530,9 @@ impl ModelManager {
 
                     if matches {
                         match &entry.apply {
-                            MultipartApply::Single(model_ref) => {
-                                result.push((model_ref.clone(), name.to_string()));
-                            }
-                            MultipartApply::Multiple(refs) => {
-                                for r in refs {
-                                    result.push((r.clone(), name.to_string()));
-                                }
-                            }
+                            MultipartApply::Single(model_ref) => result.push(std::slice::from_ref(model_ref)),
+                            // A list is one weighted choice, like variants
+                            MultipartApply::Multiple(refs) => result.push(refs.as_slice()),
                         }
                     }
                 }
@@ -675,102 +689,16 @@ pub fn rotate_point(point: (f32, f32, f32), x_rot: i32, y_rot: i32) -> (f32, f32
     (x + cx, y + cy, z + cz)
 }
 
-/// Face direction enum for rotation
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum FaceDirection {
-    Down, Up, North, South, West, East,
-}
-
-impl FaceDirection {
-    /// Parse from string
-    pub fn from_str(s: &str) -> Option<Self> {
-        match s {
-            "down" => Some(Self::Down),
-            "up" => Some(Self::Up),
-            "north" => Some(Self::North),
-            "south" => Some(Self::South),
-            "west" => Some(Self::West),
-            "east" => Some(Self::East),
-            _ => None,
-        }
-    }
-
-    /// Convert to string
-    pub fn as_str(&self) -> &'static str {
-        match self {
-            Self::Down => "down",
-            Self::Up => "up",
-            Self::North => "north",
-            Self::South => "south",
-            Self::West => "west",
-            Self::East => "east",
-        }
-    }
-
-    /// Rotate around X axis
-    pub fn rotate_x(self, angle: i32) -> Self {
-        match angle {
-            90 => match self {
-                Self::Up => Self::North,
-                Self::North => Self::Down,
-                Self::Down => Self::South,
-                Self::South => Self::Up,
-                other => other,
-            },
-            180 => match self {
-                Self::Up => Self::Down,
-                Self::Down => Self::Up,
-                Self::North => Self::South,
-                Self::South => Self::North,
-                other => other,
-            },
-            270 => match self {
-                Self::Up => Self::South,
-                Self::South => Self::Down,
-                Self::Down => Self::North,
-                Self::North => Self::Up,
-                other => other,
-            },
-            _ => self,
-        }
-    }
-
-    /// Rotate around Y axis
-    pub fn rotate_y(self, angle: i32) -> Self {
-        match angle {
-            90 => match self {
-                Self::North => Self::East,
-                Self::East => Self::South,
-                Self::South => Self::West,
-                Self::West => Self::North,
-                other => other,
-            },
-            180 => match self {
-                Self::North => Self::South,
-                Self::South => Self::North,
-                Self::East => Self::West,
-                Self::West => Self::East,
-                other => other,
-            },
-            270 => match self {
-                Self::North => Self::West,
-                Self::West => Self::South,
-                Self::South => Self::East,
-                Self::East => Self::North,
-                other => other,
-            },
-            _ => self,
-        }
-    }
-}
+/// Face of a model element, named by the direction it faces
+pub type FaceDirection = crate::direction::Direction;
 
 /// Rotate a face direction based on x/y rotation
 pub fn rotate_face_direction(face: &str, x_rot: i32, y_rot: i32) -> &'static str {
-    let Some(dir) = FaceDirection::from_str(face) else {
+    let Ok(dir) = face.parse::<FaceDirection>() else {
         return "north"; // Default fallback
     };
 
-    dir.rotate_x(x_rot).rotate_y(y_rot).as_str()
+    dir.rotate_x(x_rot).rotate_y(y_rot).name()
 }
 
 /// A generated quad ready for OBJ export
@@ -784,6 +712,9 @@ pub struct GeneratedQuad {
     pub texture: String,
     /// Face direction for culling
     pub face_dir: FaceDirection,
+    /// Side where a neighbor covering this face hides it: the model's
+    /// `cullface`, rotated with the model
+    pub cullface: Option<FaceDirection>,
     /// Tint index (-1 = no tint)
     pub tint_index: i32,
 }
@@ -844,7 +775,7 @@ pub fn generate_model_quads(
 
         // Generate quad for each face
         for (face_name, face) in &element.faces {
-            let Some(face_dir) = FaceDirection::from_str(face_name) else {
+            let Ok(face_dir) = face_name.parse::<FaceDirection>() else {
                 continue;
             };
 
@@ -950,6 +881,9 @@ pub fn generate_model_quads(
                 texture,
                 face_dir: rotated_face_dir,
                 tint_index: face.tintindex,
+                cullface: face.cullface.as_deref()
+                    .and_then(|name| name.parse::<FaceDirection>().ok())
+                    .map(|dir| dir.rotate_x(x_rot).rotate_y(y_rot)),
             });
         }
     }
@@ -960,9 +894,7 @@ pub fn generate_model_quads(
 /// Check if a model fully covers a face (for face culling)
 pub fn model_covers_face(model: &ResolvedModel, face: FaceDirection, x_rot: i32, y_rot: i32) -> bool {
     // Get the face direction in model space (reverse rotation)
-    let model_face = face
-        .rotate_y((-y_rot).rem_euclid(360))
-        .rotate_x((-x_rot).rem_euclid(360));
+    let model_face = face.rotate_y(-y_rot).rotate_x(-x_rot);
 
     // Check if any element fully covers this face
     for element in &model.elements {
@@ -978,7 +910,7 @@ pub fn model_covers_face(model: &ResolvedModel, face: FaceDirection, x_rot: i32,
             FaceDirection::East => x1 >= 0.999 && y0 <= 0.001 && z0 <= 0.001 && y1 >= 0.999 && z1 >= 0.999,
         };
 
-        if covers && element.faces.contains_key(model_face.as_str()) {
+        if covers && element.faces.contains_key(model_face.name()) {
             return true;
         }
     }
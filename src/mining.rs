@@ -0,0 +1,535 @@
+//! Block hardness and how long it takes to break blocks
+//!
+//! [`block_hardness`] looks blocks up in a table of vanilla hardness, blast
+//! resistance and preferred tool; stairs, slabs and walls use the values of the
+//! block they're made from. [`break_ticks`] is the vanilla dig-time formula for
+//! a player standing on the ground, out of water, without mining fatigue:
+//!
+//! - speed is the tool tier's (2 wood, 4 stone, 6 iron, 8 diamond, 9 netherite,
+//!   12 gold) when the tool suits the block, else 1; Efficiency adds level² + 1
+//!   to speeds above 1, and Haste multiplies by 1 + 0.2 × level
+//! - each tick does speed / hardness / 30 damage, or / 100 when the tool can't
+//!   harvest the block; the block breaks after ⌈1 / damage⌉ ticks, or instantly
+//!   when one tick's damage reaches 1
+
+use std::collections::BTreeMap;
+
+use crate::block::WOODS;
+use crate::UnifiedSchematic;
+
+/// Kind of tool a block is mined fastest with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ToolKind {
+    Pickaxe,
+    Axe,
+    Shovel,
+    Hoe,
+    Shears,
+    Sword,
+}
+
+impl ToolKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            ToolKind::Pickaxe => "pickaxe",
+            ToolKind::Axe => "axe",
+            ToolKind::Shovel => "shovel",
+            ToolKind::Hoe => "hoe",
+            ToolKind::Shears => "shears",
+            ToolKind::Sword => "sword",
+        }
+    }
+}
+
+/// Tool material
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ToolTier {
+    Wood,
+    Stone,
+    Iron,
+    Diamond,
+    Netherite,
+    Gold,
+}
+
+impl ToolTier {
+    pub const ALL: [ToolTier; 6] = [ToolTier::Wood, ToolTier::Stone, ToolTier::Iron, ToolTier::Diamond, ToolTier::Netherite, ToolTier::Gold];
+
+    /// Prefix in item names, e.g. "wooden" in `wooden_pickaxe`
+    pub fn name(self) -> &'static str {
+        match self {
+            ToolTier::Wood => "wooden",
+            ToolTier::Stone => "stone",
+            ToolTier::Iron => "iron",
+            ToolTier::Diamond => "diamond",
+            ToolTier::Netherite => "netherite",
+            ToolTier::Gold => "golden",
+        }
+    }
+
+    /// Mining speed on blocks the tool suits
+    pub fn speed(self) -> f32 {
+        match self {
+            ToolTier::Wood => 2.0,
+            ToolTier::Stone => 4.0,
+            ToolTier::Iron => 6.0,
+            ToolTier::Diamond => 8.0,
+            ToolTier::Netherite => 9.0,
+            ToolTier::Gold => 12.0,
+        }
+    }
+
+    /// Harvest level: what the tier can mine (gold mines like wood)
+    pub fn level(self) -> u8 {
+        match self {
+            ToolTier::Wood | ToolTier::Gold => 0,
+            ToolTier::Stone => 1,
+            ToolTier::Iron => 2,
+            ToolTier::Diamond => 3,
+            ToolTier::Netherite => 4,
+        }
+    }
+}
+
+/// What a player mines with
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Tool {
+    #[default]
+    Hand,
+    Shears,
+    Tiered(ToolTier, ToolKind),
+}
+
+impl Tool {
+    pub fn kind(self) -> Option<ToolKind> {
+        match self {
+            Tool::Hand => None,
+            Tool::Shears => Some(ToolKind::Shears),
+            Tool::Tiered(_, kind) => Some(kind),
+        }
+    }
+}
+
+impl std::fmt::Display for Tool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Tool::Hand => f.write_str("hand"),
+            Tool::Shears => f.write_str("shears"),
+            Tool::Tiered(tier, kind) => write!(f, "{}_{}", tier.name(), kind.name()),
+        }
+    }
+}
+
+impl std::str::FromStr for Tool {
+    type Err = String;
+
+    /// `hand`, `shears` or an item name like `netherite_pickaxe`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.strip_prefix("minecraft:").unwrap_or(s);
+        match name {
+            "hand" => return Ok(Tool::Hand),
+            "shears" => return Ok(Tool::Shears),
+            _ => {}
+        }
+        let kinds = [ToolKind::Pickaxe, ToolKind::Axe, ToolKind::Shovel, ToolKind::Hoe, ToolKind::Sword];
+        ToolTier::ALL.into_iter()
+            .flat_map(|tier| kinds.map(|kind| Tool::Tiered(tier, kind)))
+            .find(|tool| tool.to_string() == name)
+            .ok_or_else(|| format!("unknown tool '{}' (expected hand, shears or an item like iron_pickaxe)", s))
+    }
+}
+
+/// Vanilla hardness data for a block
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BlockHardness {
+    /// Negative for blocks that can't be broken
+    pub hardness: f32,
+    pub blast_resistance: f32,
+    /// Tool that mines it faster
+    pub tool: Option<ToolKind>,
+    /// Lowest tier that drops it, for blocks that need the right tool to drop
+    pub min_tier: Option<ToolTier>,
+}
+
+impl BlockHardness {
+    const fn new(hardness: f32, blast_resistance: f32, tool: Option<ToolKind>, min_tier: Option<ToolTier>) -> Self {
+        Self { hardness, blast_resistance, tool, min_tier }
+    }
+
+    pub fn is_unbreakable(&self) -> bool {
+        self.hardness < 0.0
+    }
+}
+
+const UNBREAKABLE: BlockHardness = BlockHardness::new(-1.0, 3_600_000.0, None, None);
+
+/// Blocks that need a pickaxe of at least `tier` to drop
+const fn pickaxe(hardness: f32, blast_resistance: f32, tier: ToolTier) -> BlockHardness {
+    BlockHardness::new(hardness, blast_resistance, Some(ToolKind::Pickaxe), Some(tier))
+}
+
+/// Blocks that drop with anything but mine faster with `tool`
+const fn faster(hardness: f32, blast_resistance: f32, tool: ToolKind) -> BlockHardness {
+    BlockHardness::new(hardness, blast_resistance, Some(tool), None)
+}
+
+const fn by_hand(hardness: f32, blast_resistance: f32) -> BlockHardness {
+    BlockHardness::new(hardness, blast_resistance, None, None)
+}
+
+fn table(name: &str) -> Option<BlockHardness> {
+    use ToolKind::*;
+    use ToolTier::{Diamond, Iron, Stone, Wood};
+
+    let hardness = match name {
+        "bedrock" | "barrier" | "light" | "command_block" | "chain_command_block" | "repeating_command_block"
+        | "structure_block" | "jigsaw" | "end_portal_frame" | "end_portal" | "end_gateway" | "nether_portal"
+        | "moving_piston" | "test_block" | "test_instance_block" => UNBREAKABLE,
+        "reinforced_deepslate" => pickaxe(55.0, 1200.0, Wood),
+
+        "stone" | "granite" | "polished_granite" | "diorite" | "polished_diorite" | "andesite"
+        | "polished_andesite" | "stone_bricks" | "mossy_stone_bricks" | "cracked_stone_bricks"
+        | "chiseled_stone_bricks" | "tuff" | "polished_tuff" | "tuff_bricks" | "chiseled_tuff"
+        | "blackstone" | "prismarine" | "prismarine_bricks" | "dark_prismarine" | "purpur_block"
+        | "purpur_pillar" | "dripstone_block" => pickaxe(1.5, 6.0, Wood),
+        "cobblestone" | "mossy_cobblestone" | "smooth_stone" | "bricks" | "nether_bricks"
+        | "red_nether_bricks" | "cracked_nether_bricks" | "chiseled_nether_bricks" | "polished_blackstone"
+        | "polished_blackstone_bricks" | "cracked_polished_blackstone_bricks" | "chiseled_polished_blackstone"
+        | "mud_bricks" => pickaxe(2.0, 6.0, Wood),
+        "deepslate" => pickaxe(3.0, 6.0, Wood),
+        "cobbled_deepslate" | "polished_deepslate" | "deepslate_bricks" | "cracked_deepslate_bricks"
+        | "deepslate_tiles" | "cracked_deepslate_tiles" | "chiseled_deepslate" => pickaxe(3.5, 6.0, Wood),
+        "calcite" => pickaxe(0.75, 0.75, Wood),
+        "basalt" | "polished_basalt" | "smooth_basalt" => pickaxe(1.25, 4.2, Wood),
+        "netherrack" => pickaxe(0.4, 0.4, Wood),
+        "end_stone" | "end_stone_bricks" => pickaxe(3.0, 9.0, Wood),
+        "obsidian" | "crying_obsidian" | "respawn_anchor" => pickaxe(50.0, 1200.0, Diamond),
+        "sandstone" | "chiseled_sandstone" | "cut_sandstone" | "red_sandstone" | "chiseled_red_sandstone"
+        | "cut_red_sandstone" => pickaxe(0.8, 0.8, Wood),
+        "smooth_sandstone" | "smooth_red_sandstone" | "smooth_quartz" => pickaxe(2.0, 6.0, Wood),
+        "quartz_block" | "chiseled_quartz_block" | "quartz_pillar" | "quartz_bricks" => pickaxe(0.8, 0.8, Wood),
+        "terracotta" => pickaxe(1.25, 4.2, Wood),
+        "amethyst_block" | "budding_amethyst" => pickaxe(1.5, 1.5, Wood),
+        "magma_block" => pickaxe(0.5, 0.5, Wood),
+        "packed_mud" => faster(1.0, 3.0, Pickaxe),
+        "ice" | "packed_ice" | "frosted_ice" => faster(0.5, 0.5, Pickaxe),
+        "blue_ice" => faster(2.8, 2.8, Pickaxe),
+
+        "coal_ore" | "nether_gold_ore" | "nether_quartz_ore" => pickaxe(3.0, 3.0, Wood),
+        "iron_ore" | "copper_ore" | "lapis_ore" => pickaxe(3.0, 3.0, Stone),
+        "gold_ore" | "redstone_ore" | "diamond_ore" | "emerald_ore" => pickaxe(3.0, 3.0, Iron),
+        "deepslate_coal_ore" => pickaxe(4.5, 3.0, Wood),
+        "deepslate_iron_ore" | "deepslate_copper_ore" | "deepslate_lapis_ore" => pickaxe(4.5, 3.0, Stone),
+        "deepslate_gold_ore" | "deepslate_redstone_ore" | "deepslate_diamond_ore" | "deepslate_emerald_ore" => pickaxe(4.5, 3.0, Iron),
+        "ancient_debris" => pickaxe(30.0, 1200.0, Diamond),
+        "coal_block" | "redstone_block" => pickaxe(5.0, 6.0, Wood),
+        "iron_block" | "raw_iron_block" | "raw_copper_block" => pickaxe(5.0, 6.0, Stone),
+        "copper_block" | "cut_copper" | "chiseled_copper" | "copper_grate" | "lapis_block" => pickaxe(3.0, 6.0, Stone),
+        "gold_block" => pickaxe(3.0, 6.0, Iron),
+        "diamond_block" | "emerald_block" | "raw_gold_block" => pickaxe(5.0, 6.0, Iron),
+        "netherite_block" => pickaxe(50.0, 1200.0, Diamond),
+        "iron_bars" | "iron_door" | "iron_trapdoor" | "chain" => pickaxe(5.0, 6.0, Wood),
+        "anvil" | "chipped_anvil" | "damaged_anvil" | "enchanting_table" => pickaxe(5.0, 1200.0, Wood),
+        "furnace" | "blast_furnace" | "smoker" | "dispenser" | "dropper" => pickaxe(3.5, 3.5, Wood),
+        "observer" => pickaxe(3.0, 3.0, Wood),
+        "hopper" => pickaxe(3.0, 4.8, Wood),
+        "cauldron" | "water_cauldron" | "lava_cauldron" | "powder_snow_cauldron" => pickaxe(2.0, 2.0, Wood),
+        "stonecutter" => pickaxe(3.5, 3.5, Wood),
+        "ender_chest" => pickaxe(22.5, 600.0, Wood),
+        "spawner" => pickaxe(5.0, 5.0, Wood),
+        "rail" | "powered_rail" | "detector_rail" | "activator_rail" => faster(0.7, 0.7, Pickaxe),
+        "piston" | "sticky_piston" | "piston_head" => faster(1.5, 1.5, Pickaxe),
+        "beacon" | "conduit" => by_hand(3.0, 3.0),
+
+        "dirt" | "coarse_dirt" | "rooted_dirt" | "podzol" | "sand" | "red_sand" | "soul_sand"
+        | "soul_soil" | "mud" | "suspicious_sand" => faster(0.5, 0.5, Shovel),
+        "grass_block" | "mycelium" | "gravel" | "clay" | "farmland" | "suspicious_gravel" => faster(0.6, 0.6, Shovel),
+        "dirt_path" => faster(0.65, 0.65, Shovel),
+        "snow_block" => BlockHardness::new(0.2, 0.2, Some(Shovel), Some(Wood)),
+        "snow" => BlockHardness::new(0.1, 0.1, Some(Shovel), Some(Wood)),
+        "powder_snow" => faster(0.25, 0.25, Shovel),
+
+        "crafting_table" | "chest" | "trapped_chest" | "barrel" | "cartography_table" | "fletching_table"
+        | "smithing_table" | "loom" => faster(2.5, 2.5, Axe),
+        "bookshelf" | "chiseled_bookshelf" | "lectern" => faster(1.5, 1.5, Axe),
+        "jukebox" | "note_block" => faster(2.0, 6.0, Axe),
+        "ladder" => faster(0.4, 0.4, Axe),
+        "composter" | "beehive" | "campfire" | "soul_campfire" => faster(0.6, 0.6, Axe),
+        "pumpkin" | "carved_pumpkin" | "jack_o_lantern" | "melon" => faster(1.0, 1.0, Axe),
+        "bamboo_block" | "stripped_bamboo_block" => faster(2.0, 3.0, Axe),
+
+        "hay_block" | "target" | "dried_kelp_block" => faster(0.5, 0.5, Hoe),
+        "sponge" | "wet_sponge" => faster(0.6, 0.6, Hoe),
+        "nether_wart_block" | "warped_wart_block" | "shroomlight" => faster(1.0, 1.0, Hoe),
+        "moss_block" | "moss_carpet" => faster(0.1, 0.1, Hoe),
+        "sculk" | "sculk_vein" => faster(0.2, 0.2, Hoe),
+
+        "cobweb" => BlockHardness::new(4.0, 4.0, Some(Shears), None),
+        "vine" | "glow_lichen" => faster(0.2, 0.2, Shears),
+        "glass" | "tinted_glass" | "glass_pane" | "glowstone" | "sea_lantern" | "redstone_lamp" => by_hand(0.3, 0.3),
+        "tnt" | "slime_block" | "honey_block" | "torch" | "wall_torch" | "redstone_torch" | "redstone_wall_torch"
+        | "redstone_wire" | "repeater" | "comparator" | "lever" | "tripwire" | "tripwire_hook" | "flower_pot"
+        | "short_grass" | "grass" | "fern" | "tall_grass" | "large_fern" | "dead_bush" | "sugar_cane" | "kelp"
+        | "kelp_plant" | "seagrass" | "tall_seagrass" | "lily_pad" | "wheat" | "carrots" | "potatoes" | "beetroots"
+        | "sweet_berry_bush" | "structure_void" | "scaffolding" => by_hand(0.0, 0.0),
+        "cactus" => by_hand(0.4, 0.4),
+        "bed" => by_hand(0.2, 0.2),
+        _ => return None,
+    };
+    Some(hardness)
+}
+
+/// Hardness, blast resistance and preferred tool for a block name, if known
+pub fn block_hardness(name: &str) -> Option<BlockHardness> {
+    use ToolKind::*;
+    use ToolTier::Wood;
+
+    let name = name.strip_prefix("minecraft:").unwrap_or(name);
+    if let Some(hardness) = table(name) {
+        return Some(hardness);
+    }
+
+    // Colored and wooden families, by their shared suffix
+    let wood = WOODS.iter().chain(&["bamboo", "crimson", "warped"])
+        .find_map(|wood| name.strip_prefix("stripped_").unwrap_or(name).strip_prefix(wood).and_then(|rest| rest.strip_prefix('_')));
+    if let Some(shape) = wood {
+        return Some(match shape {
+            "log" | "wood" | "stem" | "hyphae" => faster(2.0, 2.0, Axe),
+            "planks" | "stairs" | "slab" | "fence" | "fence_gate" | "mosaic" | "mosaic_stairs" | "mosaic_slab" => faster(2.0, 3.0, Axe),
+            "door" | "trapdoor" => faster(3.0, 3.0, Axe),
+            "sign" | "wall_sign" | "hanging_sign" | "wall_hanging_sign" => faster(1.0, 1.0, Axe),
+            "pressure_plate" | "button" => faster(0.5, 0.5, Axe),
+            "leaves" => faster(0.2, 0.2, Hoe),
+            "nylium" => pickaxe(0.4, 0.4, Wood),
+            _ => by_hand(0.0, 0.0),
+        });
+    }
+    let suffixed = |suffix: &str| name.strip_suffix(suffix);
+    if suffixed("_wool").is_some() {
+        return Some(faster(0.8, 0.8, Shears));
+    }
+    if suffixed("_carpet").is_some() {
+        return Some(by_hand(0.1, 0.1));
+    }
+    if suffixed("_concrete").is_some() {
+        return Some(pickaxe(1.8, 1.8, Wood));
+    }
+    if suffixed("_concrete_powder").is_some() {
+        return Some(faster(0.5, 0.5, Shovel));
+    }
+    if suffixed("_glazed_terracotta").is_some() {
+        return Some(pickaxe(1.4, 1.4, Wood));
+    }
+    if suffixed("_terracotta").is_some() {
+        return Some(pickaxe(1.25, 4.2, Wood));
+    }
+    if suffixed("_stained_glass").is_some() || suffixed("_stained_glass_pane").is_some() {
+        return Some(by_hand(0.3, 0.3));
+    }
+    if suffixed("_shulker_box").is_some() || name == "shulker_box" {
+        return Some(faster(2.0, 2.0, Pickaxe));
+    }
+    if suffixed("_bed").is_some() {
+        return Some(by_hand(0.2, 0.2));
+    }
+    if suffixed("_leaves").is_some() {
+        return Some(faster(0.2, 0.2, Hoe));
+    }
+    if suffixed("_candle").is_some() || name == "candle" {
+        return Some(by_hand(0.1, 0.1));
+    }
+    if ["_tulip", "_orchid", "dandelion", "poppy", "allium", "azure_bluet", "oxeye_daisy", "cornflower", "lily_of_the_valley", "_sapling", "_mushroom"]
+        .iter().any(|part| name.contains(part))
+    {
+        return Some(by_hand(0.0, 0.0));
+    }
+
+    // Stairs, slabs and walls are as hard as their block
+    for suffix in ["_stairs", "_slab", "_wall"] {
+        if let Some(base) = suffixed(suffix) {
+            let base = base.strip_suffix("_brick").map(|b| format!("{}_bricks", b)).unwrap_or_else(|| base.to_string());
+            return [base.clone(), format!("{}s", base), format!("{}_block", base)].iter().find_map(|name| table(name));
+        }
+    }
+    None
+}
+
+/// Tool and effects used for [`break_ticks`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MiningOptions {
+    pub tool: Tool,
+    /// Efficiency enchantment level
+    pub efficiency: u8,
+    /// Haste effect level
+    pub haste: u8,
+}
+
+/// Whether breaking a block with `tool` drops it
+pub fn can_harvest(block: &BlockHardness, tool: Tool) -> bool {
+    match block.min_tier {
+        None => true,
+        Some(min) => match tool {
+            Tool::Tiered(tier, kind) => Some(kind) == block.tool && tier.level() >= min.level(),
+            Tool::Shears => block.tool == Some(ToolKind::Shears),
+            Tool::Hand => false,
+        },
+    }
+}
+
+/// Game ticks to break a block, 0 for instantly, or None when it can't be broken
+pub fn break_ticks(name: &str, block: &BlockHardness, options: &MiningOptions) -> Option<u32> {
+    if block.is_unbreakable() {
+        return None;
+    }
+    if block.hardness == 0.0 {
+        return Some(0);
+    }
+
+    let suits = block.tool.is_some() && block.tool == options.tool.kind();
+    let bare = name.strip_prefix("minecraft:").unwrap_or(name);
+    let mut speed = match options.tool {
+        Tool::Shears if bare == "cobweb" || bare.ends_with("leaves") => 15.0,
+        Tool::Shears if bare.ends_with("_wool") => 5.0,
+        Tool::Shears if suits => 2.0,
+        Tool::Tiered(_, ToolKind::Sword) if bare == "cobweb" => 15.0,
+        Tool::Tiered(tier, _) if suits => tier.speed(),
+        _ => 1.0,
+    };
+    if speed > 1.0 && options.efficiency > 0 {
+        speed += (options.efficiency as f32).powi(2) + 1.0;
+    }
+    speed *= 1.0 + 0.2 * options.haste as f32;
+
+    let harvests = can_harvest(block, options.tool);
+    let damage = speed / block.hardness / if harvests { 30.0 } else { 100.0 };
+    if damage >= 1.0 {
+        return Some(0);
+    }
+    Some((1.0 / damage).ceil() as u32)
+}
+
+/// Time to break every block of one kind
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct MiningTime {
+    pub block: String,
+    pub count: usize,
+    /// Ticks for one block
+    pub ticks: u32,
+    /// Whether the tool makes it drop
+    pub harvested: bool,
+}
+
+impl MiningTime {
+    pub fn total_ticks(&self) -> u64 {
+        self.ticks as u64 * self.count as u64
+    }
+}
+
+/// What [`estimate`] found
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+pub struct MiningEstimate {
+    /// Breakable blocks, slowest in total first
+    pub blocks: Vec<MiningTime>,
+    /// Blocks that can't be broken (bedrock, barriers, ...) and fluids, with counts
+    pub unbreakable: Vec<(String, usize)>,
+    /// Blocks missing from the hardness table, with counts
+    pub unknown: Vec<(String, usize)>,
+}
+
+impl MiningEstimate {
+    /// Ticks to break all the breakable blocks, one after another
+    pub fn total_ticks(&self) -> u64 {
+        self.blocks.iter().map(MiningTime::total_ticks).sum()
+    }
+
+    /// The same in seconds
+    pub fn total_seconds(&self) -> f64 {
+        self.total_ticks() as f64 / 20.0
+    }
+}
+
+/// Break times for every non-air block in a schematic with one tool
+///
+/// The times are only digging: walking and the 5 ticks between breaking one
+/// block and starting the next aren't included.
+pub fn estimate(schem: &UnifiedSchematic, options: &MiningOptions) -> MiningEstimate {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for block in schem.blocks.iter().filter(|b| !b.is_air() && !b.is_structure_void()) {
+        *counts.entry(block.name.as_str()).or_insert(0) += 1;
+    }
+
+    let mut estimate = MiningEstimate::default();
+    for (name, count) in counts {
+        // Fluids can't be mined, only scooped up or drained
+        if crate::BlockClass::of(name) == crate::BlockClass::Liquid {
+            estimate.unbreakable.push((name.to_string(), count));
+            continue;
+        }
+        let Some(hardness) = block_hardness(name) else {
+            estimate.unknown.push((name.to_string(), count));
+            continue;
+        };
+        match break_ticks(name, &hardness, options) {
+            Some(ticks) => estimate.blocks.push(MiningTime {
+                block: name.to_string(),
+                count,
+                ticks,
+                harvested: can_harvest(&hardness, options.tool),
+            }),
+            None => estimate.unbreakable.push((name.to_string(), count)),
+        }
+    }
+    estimate.blocks.sort_by(|a, b| b.total_ticks().cmp(&a.total_ticks()).then_with(|| a.block.cmp(&b.block)));
+    estimate
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seconds(block: &str, tool: &str, efficiency: u8, haste: u8) -> Option<f32> {
+        let options = MiningOptions { tool: tool.parse().unwrap(), efficiency, haste };
+        break_ticks(block, &block_hardness(block).unwrap(), &options).map(|ticks| ticks as f32 / 20.0)
+    }
+
+    #[test]
+    fn test_break_times() {
+        // Known values from the wiki's breaking speed tables
+        assert_eq!(seconds("minecraft:stone", "hand", 0, 0), Some(7.5));
+        assert_eq!(seconds("minecraft:stone", "wooden_pickaxe", 0, 0), Some(1.15));
+        assert_eq!(seconds("minecraft:stone", "stone_pickaxe", 0, 0), Some(0.6));
+        assert_eq!(seconds("minecraft:stone", "iron_pickaxe", 0, 0), Some(0.4));
+        assert_eq!(seconds("minecraft:obsidian", "diamond_pickaxe", 0, 0), Some(9.4));
+        assert_eq!(seconds("minecraft:obsidian", "iron_pickaxe", 0, 0), Some(41.7));
+        assert_eq!(seconds("minecraft:oak_planks", "hand", 0, 0), Some(3.0));
+        assert_eq!(seconds("minecraft:dirt", "wooden_shovel", 0, 0), Some(0.4));
+        assert_eq!(seconds("minecraft:cobweb", "shears", 0, 0), Some(0.4));
+        // Efficiency V netherite with Haste II instamines stone
+        assert_eq!(seconds("minecraft:stone", "netherite_pickaxe", 5, 2), Some(0.0));
+        assert_eq!(seconds("minecraft:bedrock", "netherite_pickaxe", 5, 2), None);
+
+        // Stairs and slabs take their block's values
+        assert_eq!(block_hardness("minecraft:stone_brick_stairs"), block_hardness("minecraft:stone_bricks"));
+        assert_eq!(block_hardness("minecraft:cobblestone_wall"), block_hardness("minecraft:cobblestone"));
+        assert_eq!(block_hardness("spruce_stairs").unwrap().tool, Some(ToolKind::Axe));
+        assert_eq!(block_hardness("minecraft:obsidian").unwrap().blast_resistance, 1200.0);
+        assert!(block_hardness("create:cogwheel").is_none());
+        assert!("golden_axe".parse::<Tool>().is_ok() && "wood_pickaxe".parse::<Tool>().is_err());
+    }
+
+    #[test]
+    fn test_estimate() {
+        let mut builder = crate::SchematicBuilder::new(4, 1, 1);
+        builder.set_block(0, 0, 0, crate::Block::new("minecraft:stone")).unwrap();
+        builder.set_block(1, 0, 0, crate::Block::new("minecraft:stone")).unwrap();
+        builder.set_block(2, 0, 0, crate::Block::new("minecraft:bedrock")).unwrap();
+        builder.set_block(3, 0, 0, crate::Block::new("create:cogwheel")).unwrap();
+        let options = MiningOptions { tool: Tool::Tiered(ToolTier::Iron, ToolKind::Pickaxe), ..Default::default() };
+        let estimate = estimate(&builder.build(), &options);
+
+        assert_eq!(estimate.blocks, [MiningTime { block: "minecraft:stone".to_string(), count: 2, ticks: 8, harvested: true }]);
+        assert_eq!(estimate.total_seconds(), 0.8);
+        assert_eq!(estimate.unbreakable, [("minecraft:bedrock".to_string(), 1)]);
+        assert_eq!(estimate.unknown, [("create:cogwheel".to_string(), 1)]);
+    }
+}
@@ -0,0 +1,164 @@
+//! Replacing modded blocks with vanilla ones
+//!
+//! Schematics saved on modded servers hold blocks like `create:andesite_casing`
+//! that vanilla tools and servers don't know. [`strip_mods`] swaps every block
+//! outside the `minecraft` namespace for its substitute in a [`ModMapping`], or
+//! the mapping's placeholder when it has none, and reports what it replaced.
+//! Block entities at replaced positions are removed, since their data belongs
+//! to the modded block.
+
+use std::collections::HashMap;
+
+use crate::schem::Schem;
+use crate::{Block, BlockEntityPolicy, UnifiedSchematic};
+
+/// Block used for modded blocks without a substitute, unless the mapping names another
+pub const DEFAULT_PLACEHOLDER: &str = "minecraft:stone";
+
+/// Whether a block name is outside the `minecraft` namespace (names without
+/// a namespace are vanilla)
+pub fn is_modded(name: &str) -> bool {
+    name.split_once(':').is_some_and(|(namespace, _)| namespace != "minecraft")
+}
+
+/// Vanilla substitutes for modded blocks
+#[derive(Debug, Clone)]
+pub struct ModMapping {
+    substitutes: HashMap<String, Block>,
+    placeholder: Block,
+}
+
+impl Default for ModMapping {
+    fn default() -> Self {
+        Self { substitutes: HashMap::new(), placeholder: Block::new(DEFAULT_PLACEHOLDER) }
+    }
+}
+
+impl ModMapping {
+    /// No substitutes, with [`DEFAULT_PLACEHOLDER`] for everything
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace `modded` blocks with `vanilla`, a block state like
+    /// `minecraft:stripped_spruce_log[axis=y]`
+    pub fn with_substitute(mut self, modded: impl Into<String>, vanilla: &str) -> Self {
+        self.substitutes.insert(modded.into(), Schem::parse_block_state(vanilla));
+        self
+    }
+
+    /// Replace modded blocks without a substitute with `vanilla`
+    pub fn with_placeholder(mut self, vanilla: &str) -> Self {
+        self.placeholder = Schem::parse_block_state(vanilla);
+        self
+    }
+
+    /// The block for modded blocks without a substitute
+    pub fn placeholder(&self) -> &Block {
+        &self.placeholder
+    }
+
+    /// The block a modded block becomes, and whether the mapping named it
+    /// (false for the placeholder)
+    pub fn substitute(&self, name: &str) -> (&Block, bool) {
+        match self.substitutes.get(name) {
+            Some(block) => (block, true),
+            None => (&self.placeholder, false),
+        }
+    }
+
+    /// Substitutes that are themselves modded, sorted
+    pub fn modded_substitutes(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.substitutes.values()
+            .chain([&self.placeholder])
+            .filter(|block| is_modded(&block.name))
+            .map(Block::full_name)
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+}
+
+/// One kind of replacement made by [`strip_mods`]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct Replacement {
+    /// Modded block name
+    pub from: String,
+    /// Block state it was replaced with
+    pub to: String,
+    /// Number of blocks replaced
+    pub count: usize,
+    /// Whether the mapping named the substitute, rather than it being the placeholder
+    pub mapped: bool,
+}
+
+/// What [`strip_mods`] changed
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct StripReport {
+    /// Replacements, by most blocks first
+    pub replacements: Vec<Replacement>,
+    /// Blocks replaced in total
+    pub blocks: usize,
+    /// Block entities removed with their blocks
+    pub block_entities: usize,
+}
+
+/// Replace every modded block in `schematic` according to `mapping`
+pub fn strip_mods(schematic: &mut UnifiedSchematic, mapping: &ModMapping) -> StripReport {
+    let block_entities = schematic.block_entities.len();
+    let counts = schematic.replace_blocks(BlockEntityPolicy::Remove, |block| {
+        is_modded(&block.name).then(|| mapping.substitute(&block.name).0.clone())
+    });
+
+    let mut replacements: Vec<Replacement> = counts.into_iter()
+        .map(|((from, _), count)| {
+            let (block, mapped) = mapping.substitute(&from);
+            Replacement { to: block.to_selector(), from, count, mapped }
+        })
+        .collect();
+    replacements.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.from.cmp(&b.from)));
+    StripReport {
+        blocks: replacements.iter().map(|r| r.count).sum(),
+        block_entities: block_entities - schematic.block_entities.len(),
+        replacements,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::SchematicBuilder;
+    use crate::BlockEntity;
+
+    #[test]
+    fn test_strip_mods() {
+        let mut builder = SchematicBuilder::new(4, 1, 1);
+        builder.set_block(0, 0, 0, Block::new("create:andesite_casing")).unwrap();
+        builder.set_block(1, 0, 0, Block::new("create:andesite_casing")).unwrap();
+        builder.set_block(2, 0, 0, Block::new("create:cogwheel")).unwrap();
+        builder.set_block(3, 0, 0, Block::new("minecraft:chest")).unwrap();
+        builder.add_block_entity(BlockEntity { id: "create:cogwheel".to_string(), pos: (2, 0, 0), ..Default::default() }).unwrap();
+        builder.add_block_entity(BlockEntity { id: "minecraft:chest".to_string(), pos: (3, 0, 0), ..Default::default() }).unwrap();
+        let mut schem = builder.build();
+
+        let mapping = ModMapping::new()
+            .with_substitute("create:andesite_casing", "minecraft:stripped_spruce_wood[axis=y]")
+            .with_placeholder("minecraft:cobblestone");
+        let report = strip_mods(&mut schem, &mapping);
+
+        assert_eq!(report.replacements, [
+            Replacement { from: "create:andesite_casing".to_string(), to: "minecraft:stripped_spruce_wood[axis=y]".to_string(), count: 2, mapped: true },
+            Replacement { from: "create:cogwheel".to_string(), to: "minecraft:cobblestone".to_string(), count: 1, mapped: false },
+        ]);
+        assert_eq!((report.blocks, report.block_entities), (3, 1));
+        assert_eq!(schem.get_block(0, 0, 0).unwrap().full_name(), "minecraft:stripped_spruce_wood[axis=y]");
+        assert_eq!(schem.get_block(3, 0, 0).unwrap().name, "minecraft:chest");
+        assert_eq!(schem.block_entities.len(), 1);
+        assert!(!schem.blocks.iter().any(|block| is_modded(&block.name)));
+
+        assert_eq!(strip_mods(&mut schem, &mapping), StripReport::default());
+        assert!(!is_modded("stone"));
+        assert_eq!(ModMapping::new().with_substitute("a:b", "create:shaft").modded_substitutes(), ["create:shaft"]);
+    }
+}
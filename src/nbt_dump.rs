@@ -0,0 +1,258 @@
+//! Raw NBT printing for the `debug` command (part of the schem-tool binary)
+//!
+//! The tree view is for reading: lists are cut after a few entries and arrays
+//! only show their length unless asked otherwise. The SNBT output is for
+//! diffing two files, so it is always complete and sorts compound keys, which
+//! fastnbt keeps in hash order.
+
+use colored::Colorize;
+use fastnbt::Value;
+use std::fmt::Write;
+
+/// Entries shown per list without `--full`
+const LIST_PREVIEW: usize = 5;
+/// Array values shown without `--full`
+const ARRAY_PREVIEW: usize = 128;
+/// Array values per line
+const ARRAY_WRAP: usize = 16;
+
+/// How to print the contents of byte, int and long arrays (`--arrays`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ArrayFormat {
+    /// Two's complement hex, zero-padded to the element width
+    Hex,
+    Dec,
+    /// Only the length
+    #[default]
+    None,
+}
+
+/// How much of the tree `dump` prints
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DumpOptions {
+    /// Show every list entry and array value
+    pub full: bool,
+    pub arrays: ArrayFormat,
+    /// Levels of compounds and lists to expand; deeper ones only show their size
+    pub depth: Option<usize>,
+}
+
+/// The value at a dotted `path` such as `Regions.main.BlockStates`; list
+/// entries are selected with `Entities[0]` or `Entities.0`
+pub fn select<'a>(root: &'a Value, path: &str) -> Result<&'a Value, String> {
+    let mut value = root;
+    let mut walked = String::new();
+    for segment in path.split('.').filter(|s| !s.is_empty()) {
+        let (key, indices) = match segment.find('[') {
+            Some(i) => segment.split_at(i),
+            None => (segment, ""),
+        };
+        let mut steps: Vec<&str> = vec![key];
+        steps.extend(indices.split(['[', ']']).filter(|s| !s.is_empty()));
+        for step in steps.into_iter().filter(|s| !s.is_empty()) {
+            value = match value {
+                Value::Compound(map) => map.get(step).ok_or_else(|| {
+                    let mut keys: Vec<&str> = map.keys().map(String::as_str).collect();
+                    keys.sort_unstable();
+                    format!("no key '{}' in {} (keys: {})", step, describe(&walked), keys.join(", "))
+                })?,
+                Value::List(list) => {
+                    let index: usize = step.parse().map_err(|_| format!("{} is a list; expected an index, not '{}'", describe(&walked), step))?;
+                    list.get(index).ok_or_else(|| format!("index {} is past the end of {} ({} entries)", index, describe(&walked), list.len()))?
+                }
+                _ => return Err(format!("{} has no children", describe(&walked))),
+            };
+            if !walked.is_empty() {
+                walked.push('.');
+            }
+            walked.push_str(step);
+        }
+    }
+    Ok(value)
+}
+
+fn describe(walked: &str) -> String {
+    if walked.is_empty() { "the root".to_string() } else { format!("'{}'", walked) }
+}
+
+/// Tree view of `value`, one line per entry
+pub fn dump(value: &Value, options: &DumpOptions) -> String {
+    let mut out = String::new();
+    write_tree(&mut out, value, 0, options);
+    out
+}
+
+fn write_tree(out: &mut String, value: &Value, indent: usize, options: &DumpOptions) {
+    let pad = "  ".repeat(indent);
+    let nested_too_deep = options.depth.is_some_and(|depth| indent / 2 >= depth);
+    match value {
+        Value::List(list) if nested_too_deep => {
+            let _ = writeln!(out, "{}List[{}] ...", pad, list.len());
+        }
+        Value::Compound(map) if nested_too_deep => {
+            let _ = writeln!(out, "{}Compound {{{} entries}} ...", pad, map.len());
+        }
+        Value::List(list) => {
+            let _ = writeln!(out, "{}List[{}]:", pad, list.len());
+            let shown = if options.full { list.len() } else { LIST_PREVIEW };
+            for (i, item) in list.iter().enumerate().take(shown) {
+                let _ = writeln!(out, "{}  [{}]:", pad, i);
+                write_tree(out, item, indent + 2, options);
+            }
+            if list.len() > shown {
+                let _ = writeln!(out, "{}  ... and {} more", pad, list.len() - shown);
+            }
+        }
+        Value::Compound(map) => {
+            let _ = writeln!(out, "{}Compound {{", pad);
+            for (key, val) in sorted(map) {
+                let _ = write!(out, "{}  {}: ", pad, key.yellow());
+                match val {
+                    Value::Compound(_) | Value::List(_) => {
+                        out.push('\n');
+                        write_tree(out, val, indent + 2, options);
+                    }
+                    // Simple values on the same line
+                    _ => write_scalar(out, val, &format!("{}    ", pad), options),
+                }
+            }
+            let _ = writeln!(out, "{}}}", pad);
+        }
+        _ => {
+            let _ = write!(out, "{}{}: ", pad, type_name(value));
+            write_scalar(out, value, &format!("{}  ", pad), options);
+        }
+    }
+}
+
+/// A non-container value and its newline; array contents go on lines under it
+fn write_scalar(out: &mut String, value: &Value, pad: &str, options: &DumpOptions) {
+    let (len, lines) = match value {
+        Value::ByteArray(arr) => (arr.len(), format_array(arr.iter().map(|&v| v as i64), 2, options)),
+        Value::IntArray(arr) => (arr.len(), format_array(arr.iter().map(|&v| v as i64), 8, options)),
+        Value::LongArray(arr) => (arr.len(), format_array(arr.iter().copied(), 16, options)),
+        _ => {
+            let text = match value {
+                Value::Byte(b) => b.to_string(),
+                Value::Short(s) => s.to_string(),
+                Value::Int(i) => i.to_string(),
+                Value::Long(l) => l.to_string(),
+                Value::Float(f) => f.to_string(),
+                Value::Double(d) => d.to_string(),
+                Value::String(s) => format!("\"{}\"", s),
+                _ => format!("{:?}", value),
+            };
+            let _ = writeln!(out, "{}", text);
+            return;
+        }
+    };
+    let _ = writeln!(out, "{}[{}]", type_name(value), len);
+    for line in lines {
+        let _ = writeln!(out, "{}{}", pad, line);
+    }
+}
+
+/// Array values as wrapped lines, hex values `hex_digits` wide
+fn format_array(values: impl ExactSizeIterator<Item = i64>, hex_digits: usize, options: &DumpOptions) -> Vec<String> {
+    let len = values.len();
+    let shown = if options.full { len } else { len.min(ARRAY_PREVIEW) };
+    let mask = if hex_digits >= 16 { u64::MAX } else { (1u64 << (hex_digits * 4)) - 1 };
+    let text: Vec<String> = match options.arrays {
+        ArrayFormat::None => return Vec::new(),
+        ArrayFormat::Dec => values.take(shown).map(|v| v.to_string()).collect(),
+        ArrayFormat::Hex => values.take(shown).map(|v| format!("{:0width$x}", v as u64 & mask, width = hex_digits)).collect(),
+    };
+    let mut lines: Vec<String> = text.chunks(ARRAY_WRAP).map(|chunk| chunk.join(" ")).collect();
+    if shown < len {
+        lines.push(format!("... and {} more", len - shown));
+    }
+    lines
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Byte(_) => "Byte",
+        Value::Short(_) => "Short",
+        Value::Int(_) => "Int",
+        Value::Long(_) => "Long",
+        Value::Float(_) => "Float",
+        Value::Double(_) => "Double",
+        Value::String(_) => "String",
+        Value::ByteArray(_) => "ByteArray",
+        Value::IntArray(_) => "IntArray",
+        Value::LongArray(_) => "LongArray",
+        Value::List(_) => "List",
+        Value::Compound(_) => "Compound",
+    }
+}
+
+fn sorted(map: &std::collections::HashMap<String, Value>) -> Vec<(&String, &Value)> {
+    let mut entries: Vec<_> = map.iter().collect();
+    entries.sort_unstable_by_key(|&(key, _)| key);
+    entries
+}
+
+/// `value` as indented SNBT, the text format of `/data get`, with sorted keys
+pub fn to_snbt(value: &Value) -> String {
+    schem_tool::snbt::to_string(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compound(entries: Vec<(&str, Value)>) -> Value {
+        Value::Compound(entries.into_iter().map(|(k, v)| (k.to_string(), v)).collect())
+    }
+
+    fn sample() -> Value {
+        let states = Value::LongArray(fastnbt::LongArray::new((0..20).collect()));
+        let region = compound(vec![("BlockStates", states), ("Size", Value::Int(3))]);
+        compound(vec![
+            ("Regions", compound(vec![("main", region)])),
+            ("Entities", Value::List(vec![compound(vec![("id", Value::String("minecraft:pig".into()))])])),
+            ("Version", Value::Byte(6)),
+        ])
+    }
+
+    #[test]
+    fn test_select_path() {
+        let root = sample();
+        assert!(matches!(select(&root, "Regions.main.BlockStates"), Ok(Value::LongArray(_))));
+        assert!(matches!(select(&root, "Entities[0].id"), Ok(Value::String(s)) if s == "minecraft:pig"));
+        assert!(matches!(select(&root, "Entities.0"), Ok(Value::Compound(_))));
+        assert!(matches!(select(&root, ""), Ok(Value::Compound(_))));
+        let err = select(&root, "Regions.other").unwrap_err();
+        assert!(err.contains("no key 'other' in 'Regions'") && err.contains("keys: main"), "{}", err);
+        assert!(select(&root, "Entities[3]").unwrap_err().contains("1 entries"));
+        assert!(select(&root, "Version.x").unwrap_err().contains("no children"));
+    }
+
+    #[test]
+    fn test_array_formats() {
+        let options = |arrays, full| DumpOptions { full, arrays, depth: None };
+        let bytes = [-1i64, 16, 2];
+        assert_eq!(format_array(bytes.into_iter(), 2, &options(ArrayFormat::Hex, false)), ["ff 10 02"]);
+        assert_eq!(format_array(bytes.into_iter(), 2, &options(ArrayFormat::Dec, false)), ["-1 16 2"]);
+        assert!(format_array(bytes.into_iter(), 2, &options(ArrayFormat::None, false)).is_empty());
+
+        let long = format_array((0..200).map(i64::from), 16, &options(ArrayFormat::Dec, false));
+        assert_eq!(long.len(), ARRAY_PREVIEW / ARRAY_WRAP + 1);
+        assert_eq!(long.last().unwrap(), "... and 72 more");
+        assert_eq!(format_array((0..200).map(i64::from), 16, &options(ArrayFormat::Dec, true)).len(), 13);
+    }
+
+    #[test]
+    fn test_snbt() {
+        let snbt = to_snbt(&select(&sample(), "Regions.main").unwrap().clone());
+        let expected = "{\n    BlockStates: [L;\n        0L, 1L, 2L, 3L, 4L, 5L, 6L, 7L, 8L, 9L, 10L, 11L, 12L, 13L, 14L, 15L,\n        16L, 17L, 18L, 19L\n    ],\n    Size: 3\n}\n";
+        assert_eq!(snbt, expected);
+
+        let value = compound(vec![
+            ("a b", Value::String("say \"hi\"".into())),
+            ("f", Value::Float(0.5)),
+            ("l", Value::List(vec![])),
+        ]);
+        assert_eq!(to_snbt(&value), "{\n    \"a b\": \"say \\\"hi\\\"\",\n    f: 0.5f,\n    l: []\n}\n");
+    }
+}
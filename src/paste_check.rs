@@ -0,0 +1,201 @@
+//! Blocks that won't stay as they are once pasted
+//!
+//! Pasting places blocks without the checks a player's placement goes through,
+//! and the first block update afterwards undoes what couldn't have been built:
+//!
+//! - gravity blocks (sand, gravel, concrete powder, anvils, ...) over air or a
+//!   fluid fall
+//! - attached blocks (see [`crate::attachment`]) without something to hang on
+//!   pop off as items
+//! - leaves with `persistent=false` more than 6 steps of leaves away from a log
+//!   decay
+//! - fluid sources on the sides of the schematic flow out of it
+//!
+//! Blocks whose support would be outside the schematic depend on the world
+//! they're pasted into, so they aren't reported.
+
+use std::collections::{BTreeMap, VecDeque};
+
+use crate::attachment::{can_support, support_pos};
+use crate::{Block, BlockClass, UnifiedSchematic};
+
+/// Steps of leaves a log keeps leaves alive for
+pub const LEAF_DISTANCE: u32 = 6;
+
+/// Why a block won't survive pasting
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PasteIssue {
+    /// A gravity block with air or a fluid below
+    Falls,
+    /// An attached block with nothing to hang on
+    Unsupported,
+    /// Non-persistent leaves too far from a log
+    Decays,
+    /// A fluid source on the schematic's side, flowing out of it
+    Spills,
+}
+
+impl PasteIssue {
+    pub const ALL: [PasteIssue; 4] = [PasteIssue::Falls, PasteIssue::Unsupported, PasteIssue::Decays, PasteIssue::Spills];
+
+    /// What happens, for reports
+    pub fn description(self) -> &'static str {
+        match self {
+            PasteIssue::Falls => "Gravity blocks with nothing below",
+            PasteIssue::Unsupported => "Attached blocks without a support",
+            PasteIssue::Decays => "Leaves that will decay (no log within 6, not persistent)",
+            PasteIssue::Spills => "Fluid sources flowing out over the edge",
+        }
+    }
+}
+
+/// Positions of blocks, by block name
+pub type BlockPositions = BTreeMap<String, Vec<(u32, u32, u32)>>;
+
+/// Problems found by [`check`], with the blocks affected by each
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PasteReport {
+    pub issues: BTreeMap<PasteIssue, BlockPositions>,
+}
+
+impl PasteReport {
+    pub fn is_empty(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Number of blocks with `issue`
+    pub fn count(&self, issue: PasteIssue) -> usize {
+        self.issues.get(&issue).map_or(0, |blocks| blocks.values().map(Vec::len).sum())
+    }
+
+    fn add(&mut self, issue: PasteIssue, block: &Block, pos: (u32, u32, u32)) {
+        self.issues.entry(issue).or_default().entry(block.name.clone()).or_default().push(pos);
+    }
+}
+
+/// Blocks that fall when there's nothing under them
+pub fn has_gravity(block: &Block) -> bool {
+    let name = block.display_name();
+    matches!(name, "sand" | "red_sand" | "gravel" | "suspicious_sand" | "suspicious_gravel" | "dragon_egg")
+        || name.ends_with("concrete_powder")
+        || name.ends_with("anvil")
+}
+
+fn is_log(block: &Block) -> bool {
+    let name = block.display_name();
+    ["_log", "_wood", "_stem", "_hyphae"].iter().any(|suffix| name.ends_with(suffix))
+}
+
+fn is_leaves(block: &Block) -> bool {
+    block.display_name().ends_with("leaves")
+}
+
+/// Find blocks that will fall, pop off, decay or flow away after pasting
+pub fn check(schem: &UnifiedSchematic) -> PasteReport {
+    let mut report = PasteReport::default();
+    let below = |(x, y, z): (u32, u32, u32)| y.checked_sub(1).and_then(|y| schem.get_block(x, y, z));
+
+    for (pos, block) in schem.iter_blocks() {
+        if has_gravity(block) {
+            if below(pos).is_some_and(|b| b.is_air() || b.class() == BlockClass::Liquid) {
+                report.add(PasteIssue::Falls, block, pos);
+            }
+        } else if let Some(support) = support_pos(block, pos) {
+            if schem.get_block(support.0, support.1, support.2).is_some_and(|b| !can_support(block, b)) {
+                report.add(PasteIssue::Unsupported, block, pos);
+            }
+        } else if block.class() == BlockClass::Liquid
+            && block.state.get_int("level").is_none_or(|level| level == 0)
+            && (pos.0 == 0 || pos.2 == 0 || pos.0 + 1 == schem.width || pos.2 + 1 == schem.length)
+        {
+            report.add(PasteIssue::Spills, block, pos);
+        }
+    }
+
+    // Leaf distance spreads from logs through leaves only, like the game's
+    // `distance` property
+    let (w, l) = (schem.width as usize, schem.length as usize);
+    let index = |(x, y, z): (u32, u32, u32)| (y as usize * l + z as usize) * w + x as usize;
+    let mut distance = vec![u32::MAX; schem.blocks.len()];
+    let mut queue = VecDeque::new();
+    for (pos, _) in schem.iter_blocks().filter(|(_, block)| is_log(block)) {
+        distance[index(pos)] = 0;
+        queue.push_back(pos);
+    }
+    while let Some(pos @ (x, y, z)) = queue.pop_front() {
+        let next = distance[index(pos)] + 1;
+        if next > LEAF_DISTANCE {
+            continue;
+        }
+        let neighbors = [
+            x.checked_sub(1).map(|x| (x, y, z)), Some((x + 1, y, z)),
+            y.checked_sub(1).map(|y| (x, y, z)), Some((x, y + 1, z)),
+            z.checked_sub(1).map(|z| (x, y, z)), Some((x, y, z + 1)),
+        ];
+        for n in neighbors.into_iter().flatten() {
+            if schem.get_block(n.0, n.1, n.2).is_some_and(is_leaves) && distance[index(n)] > next {
+                distance[index(n)] = next;
+                queue.push_back(n);
+            }
+        }
+    }
+    for (pos, block) in schem.iter_blocks() {
+        if is_leaves(block) && block.state.get_bool("persistent") == Some(false) && distance[index(pos)] > LEAF_DISTANCE {
+            report.add(PasteIssue::Decays, block, pos);
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::BlockState;
+    use crate::builder::SchematicBuilder;
+
+    fn with_props(name: &str, props: &[(&str, &str)]) -> Block {
+        let properties = props.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        Block::with_state(name, BlockState { properties })
+    }
+
+    #[test]
+    fn test_paste_check() {
+        let mut builder = SchematicBuilder::new(10, 3, 3);
+        builder.fill((0, 0, 1), (9, 0, 1), Block::new("minecraft:stone")).unwrap();
+        // Sand on stone stays, sand over air falls
+        builder.set_block(0, 1, 1, Block::new("minecraft:sand")).unwrap();
+        builder.set_block(1, 2, 1, Block::new("minecraft:sand")).unwrap();
+        // A torch on stone is fine, one on a carpet pops off
+        builder.set_block(2, 1, 1, Block::new("minecraft:torch")).unwrap();
+        builder.set_block(3, 1, 1, Block::new("minecraft:white_carpet")).unwrap();
+        builder.set_block(3, 2, 1, Block::new("minecraft:torch")).unwrap();
+        // A door's upper half stands on its lower half, a torch on a door doesn't
+        builder.fill((0, 0, 2), (1, 0, 2), Block::new("minecraft:stone")).unwrap();
+        builder.set_block(0, 1, 2, with_props("minecraft:oak_door", &[("half", "lower")])).unwrap();
+        builder.set_block(0, 2, 2, with_props("minecraft:oak_door", &[("half", "upper")])).unwrap();
+        builder.set_block(1, 1, 2, with_props("minecraft:oak_door", &[("half", "lower")])).unwrap();
+        builder.set_block(1, 2, 2, Block::new("minecraft:torch")).unwrap();
+        // Leaves one step from a log survive, the ones 7 steps away don't
+        builder.set_block(4, 1, 1, Block::new("minecraft:oak_log")).unwrap();
+        builder.fill((5, 1, 1), (9, 1, 1), with_props("minecraft:oak_leaves", &[("persistent", "false")])).unwrap();
+        builder.fill((5, 2, 1), (9, 2, 1), with_props("minecraft:oak_leaves", &[("persistent", "false")])).unwrap();
+        builder.set_block(9, 2, 0, with_props("minecraft:oak_leaves", &[("persistent", "true")])).unwrap();
+        // Water source on the edge, and a flowing one that doesn't count
+        builder.set_block(0, 1, 0, with_props("minecraft:water", &[("level", "0")])).unwrap();
+        builder.set_block(1, 1, 0, with_props("minecraft:water", &[("level", "1")])).unwrap();
+        let report = check(&builder.build());
+
+        assert_eq!(report.issues[&PasteIssue::Falls]["minecraft:sand"], [(1, 2, 1)]);
+        assert_eq!(report.issues[&PasteIssue::Unsupported]["minecraft:torch"], [(3, 2, 1), (1, 2, 2)]);
+        assert!(!report.issues[&PasteIssue::Unsupported].contains_key("minecraft:oak_door"));
+        assert_eq!(report.count(PasteIssue::Decays), 0);
+        assert_eq!(report.issues[&PasteIssue::Spills]["minecraft:water"], [(0, 1, 0)]);
+
+        // Without the log every non-persistent leaf decays
+        let mut builder = SchematicBuilder::new(8, 1, 1);
+        builder.fill((0, 0, 0), (7, 0, 0), with_props("minecraft:oak_leaves", &[("persistent", "false")])).unwrap();
+        builder.set_block(0, 0, 0, Block::new("minecraft:oak_log")).unwrap();
+        let report = check(&builder.build());
+        assert_eq!(report.issues[&PasteIssue::Decays]["minecraft:oak_leaves"], [(7, 0, 0)]);
+    }
+}
@@ -0,0 +1,206 @@
+//! Pixel art and map art from images
+//!
+//! [`from_image`] turns an image into a schematic one block thick, lying flat
+//! (the image's top edge to the north, as on a map) or standing as a wall facing
+//! south. Each pixel becomes the palette block nearest in color (see
+//! [`ColorMatcher`]), optionally with Floyd-Steinberg dithering to spread the
+//! difference to the pixels after it. Mostly transparent pixels are left as air.
+
+use crate::export3d::{BlockPalette, ColorMatcher};
+use crate::{SchemError, SchematicBuilder, UnifiedSchematic};
+
+/// How the image is laid out in the world
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Orientation {
+    /// On the ground, one block high (for maps)
+    #[default]
+    Flat,
+    /// Upright, one block deep
+    Wall,
+}
+
+impl Orientation {
+    pub const ALL: [Orientation; 2] = [Orientation::Flat, Orientation::Wall];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Orientation::Flat => "flat",
+            Orientation::Wall => "wall",
+        }
+    }
+}
+
+impl std::fmt::Display for Orientation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for Orientation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Orientation::ALL.into_iter().find(|o| o.name() == s).ok_or_else(|| {
+            let names: Vec<_> = Orientation::ALL.iter().map(|o| o.name()).collect();
+            format!("unknown orientation '{}' (expected one of: {})", s, names.join(", "))
+        })
+    }
+}
+
+/// Dithering applied while matching pixels to blocks
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Dither {
+    /// Each pixel gets its nearest block
+    #[default]
+    None,
+    /// Floyd-Steinberg error diffusion
+    FloydSteinberg,
+}
+
+impl Dither {
+    pub const ALL: [Dither; 2] = [Dither::None, Dither::FloydSteinberg];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Dither::None => "none",
+            Dither::FloydSteinberg => "floyd-steinberg",
+        }
+    }
+}
+
+impl std::fmt::Display for Dither {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for Dither {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Dither::ALL.into_iter().find(|d| d.name() == s).ok_or_else(|| {
+            let names: Vec<_> = Dither::ALL.iter().map(|d| d.name()).collect();
+            format!("unknown dithering '{}' (expected one of: {})", s, names.join(", "))
+        })
+    }
+}
+
+/// Settings for [`from_image`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PixelArtOptions {
+    pub palette: BlockPalette,
+    pub orientation: Orientation,
+    pub dither: Dither,
+    /// Scale the image down so neither side is longer than this
+    pub max_size: Option<u32>,
+}
+
+/// Pixels with less alpha than this are left as air
+const MIN_ALPHA: u8 = 128;
+
+/// Build an image out of blocks
+pub fn from_image(image: &image::RgbaImage, options: &PixelArtOptions) -> Result<UnifiedSchematic, SchemError> {
+    let (mut width, mut height) = image.dimensions();
+    if width == 0 || height == 0 {
+        return Err(SchemError::Invalid("the image is empty".to_string()));
+    }
+    let scaled;
+    let image = match options.max_size {
+        Some(max) if width.max(height) > max => {
+            if max == 0 {
+                return Err(SchemError::Invalid("the maximum size must be at least 1".to_string()));
+            }
+            let scale = max as f64 / width.max(height) as f64;
+            width = ((width as f64 * scale).round() as u32).clamp(1, max);
+            height = ((height as f64 * scale).round() as u32).clamp(1, max);
+            scaled = image::imageops::resize(image, width, height, image::imageops::FilterType::Triangle);
+            &scaled
+        }
+        _ => image,
+    };
+
+    let matcher = ColorMatcher::new(options.palette.blocks());
+    let mut builder = match options.orientation {
+        Orientation::Flat => SchematicBuilder::try_new(width, 1, height)?,
+        Orientation::Wall => SchematicBuilder::try_new(width, height, 1)?,
+    };
+
+    // Colors still to be matched, with the dithering error added as it spreads
+    let mut pixels: Vec<[f32; 3]> = image.pixels().map(|p| [0, 1, 2].map(|c| p[c] as f32 / 255.0)).collect();
+    let (w, h) = (width as usize, height as usize);
+    for y in 0..h {
+        for x in 0..w {
+            if image.get_pixel(x as u32, y as u32)[3] < MIN_ALPHA {
+                continue;
+            }
+            let [r, g, b] = pixels[y * w + x];
+            let Some((block, (mr, mg, mb))) = matcher.nearest((r, g, b)) else { continue };
+            let (bx, by, bz) = match options.orientation {
+                Orientation::Flat => (x as u32, 0, y as u32),
+                Orientation::Wall => (x as u32, (h - 1 - y) as u32, 0),
+            };
+            builder.set_block(bx, by, bz, block.clone())?;
+
+            if options.dither == Dither::FloydSteinberg {
+                let error = [r - mr, g - mg, b - mb];
+                let mut spread = |dx: isize, dy: usize, weight: f32| {
+                    let nx = x as isize + dx;
+                    if nx >= 0 && (nx as usize) < w && y + dy < h {
+                        let pixel = &mut pixels[(y + dy) * w + nx as usize];
+                        for c in 0..3 {
+                            pixel[c] += error[c] * weight;
+                        }
+                    }
+                };
+                spread(1, 0, 7.0 / 16.0);
+                spread(-1, 1, 3.0 / 16.0);
+                spread(0, 1, 5.0 / 16.0);
+                spread(1, 1, 1.0 / 16.0);
+            }
+        }
+    }
+    Ok(builder.build())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_image() {
+        // Red, transparent / blue, white
+        let mut image = image::RgbaImage::new(2, 2);
+        image.put_pixel(0, 0, image::Rgba([160, 40, 40, 255]));
+        image.put_pixel(0, 1, image::Rgba([60, 75, 170, 255]));
+        image.put_pixel(1, 1, image::Rgba([255, 255, 255, 255]));
+
+        let options = PixelArtOptions { palette: BlockPalette::Concrete, ..Default::default() };
+        let flat = from_image(&image, &options).unwrap();
+        assert_eq!((flat.width, flat.height, flat.length), (2, 1, 2));
+        assert_eq!(flat.get_block(0, 0, 0).unwrap().name, "minecraft:red_concrete");
+        assert!(flat.get_block(1, 0, 0).unwrap().is_air());
+        assert_eq!(flat.get_block(0, 0, 1).unwrap().name, "minecraft:blue_concrete");
+        assert_eq!(flat.get_block(1, 0, 1).unwrap().name, "minecraft:white_concrete");
+
+        let wall = from_image(&image, &PixelArtOptions { orientation: Orientation::Wall, ..options }).unwrap();
+        assert_eq!((wall.width, wall.height, wall.length), (2, 2, 1));
+        assert_eq!(wall.get_block(0, 1, 0).unwrap().name, "minecraft:red_concrete");
+
+        let small = from_image(&image, &PixelArtOptions { max_size: Some(1), ..options }).unwrap();
+        assert_eq!((small.width, small.length), (1, 1));
+    }
+
+    #[test]
+    fn test_dithering() {
+        // A gray between two palette shades: dithering mixes them
+        let image = image::RgbaImage::from_pixel(8, 8, image::Rgba([118, 118, 118, 255]));
+        let palette = BlockPalette::Grayscale;
+        let count = |dither| {
+            let schem = from_image(&image, &PixelArtOptions { palette, dither, ..Default::default() }).unwrap();
+            schem.block_counts().len()
+        };
+        assert_eq!(count(Dither::None), 1);
+        assert!(count(Dither::FloydSteinberg) > 1);
+        assert_eq!("floyd-steinberg".parse(), Ok(Dither::FloydSteinberg));
+    }
+}
@@ -0,0 +1,235 @@
+//! Preview images, as shown in Litematica's schematic browser
+//!
+//! Litematica keeps the preview in the metadata's `PreviewImageData`: a square
+//! image stored row by row as one ARGB int per pixel. [`PreviewImage::render`]
+//! draws a schematic from above at the usual isometric angle, one flat color per
+//! block, with the top, east and south faces shaded differently so the shape
+//! reads at thumbnail size.
+
+use crate::{Block, UnifiedSchematic};
+
+/// Side of the previews Litematica itself saves, in pixels
+pub const PREVIEW_SIZE: u32 = 140;
+
+/// Brightness of the east (+X), top (+Y) and south (+Z) faces, by axis
+const SHADES: [f32; 3] = [0.8, 1.0, 0.64];
+
+/// Square image with one ARGB pixel per `u32`, row by row from the top left
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreviewImage {
+    size: u32,
+    pixels: Vec<u32>,
+}
+
+impl PreviewImage {
+    /// Fully transparent image
+    pub fn new(size: u32) -> Self {
+        Self { size, pixels: vec![0; (size * size) as usize] }
+    }
+
+    /// Width and height in pixels
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    /// ARGB pixels, row by row
+    pub fn pixels(&self) -> &[u32] {
+        &self.pixels
+    }
+
+    /// ARGB color at (x, y)
+    pub fn pixel(&self, x: u32, y: u32) -> u32 {
+        self.pixels[(y * self.size + x) as usize]
+    }
+
+    /// Image from RGBA bytes, or `None` unless there are exactly `size * size` pixels
+    pub fn from_rgba(size: u32, rgba: &[u8]) -> Option<Self> {
+        if rgba.len() != (size * size * 4) as usize {
+            return None;
+        }
+        let pixels = rgba.chunks_exact(4)
+            .map(|p| u32::from_be_bytes([p[3], p[0], p[1], p[2]]))
+            .collect();
+        Some(Self { size, pixels })
+    }
+
+    /// RGBA bytes, row by row
+    pub fn to_rgba(&self) -> Vec<u8> {
+        self.pixels.iter()
+            .flat_map(|&argb| {
+                let [a, r, g, b] = argb.to_be_bytes();
+                [r, g, b, a]
+            })
+            .collect()
+    }
+
+    /// Image from a `PreviewImageData` int array, or `None` when its length
+    /// isn't a square
+    pub fn from_int_array(data: &[i32]) -> Option<Self> {
+        let size = (data.len() as f64).sqrt() as u32;
+        (size as usize * size as usize == data.len()).then(|| Self {
+            size,
+            pixels: data.iter().map(|&v| v as u32).collect(),
+        })
+    }
+
+    /// The image as a `PreviewImageData` int array
+    pub fn to_int_array(&self) -> fastnbt::IntArray {
+        fastnbt::IntArray::new(self.pixels.iter().map(|&v| v as i32).collect())
+    }
+
+    /// `image` scaled to fit a `size` square, centered on a transparent background
+    #[cfg(feature = "fs")]
+    pub fn from_image(image: &image::DynamicImage, size: u32) -> Self {
+        use image::GenericImageView;
+
+        let (width, height) = image.dimensions();
+        let scale = size as f32 / width.max(height).max(1) as f32;
+        let (fit_w, fit_h) = (((width as f32 * scale).round() as u32).clamp(1, size), ((height as f32 * scale).round() as u32).clamp(1, size));
+        let scaled = image.resize_exact(fit_w, fit_h, image::imageops::FilterType::Triangle).to_rgba8();
+
+        let mut preview = Self::new(size);
+        let (left, top) = ((size - fit_w) / 2, (size - fit_h) / 2);
+        for (x, y, p) in scaled.enumerate_pixels() {
+            preview.pixels[((top + y) * size + left + x) as usize] = u32::from_be_bytes([p[3], p[0], p[1], p[2]]);
+        }
+        preview
+    }
+
+    /// Isometric view of `schematic` from above its south-east corner, with
+    /// each block drawn in `color` (RGB, 0-1). Air and technical blocks are
+    /// left out; everything else is opaque.
+    pub fn render(schematic: &UnifiedSchematic, size: u32, color: impl Fn(&Block) -> (f32, f32, f32)) -> Self {
+        let mut preview = Self::new(size);
+        let extent = [schematic.width, schematic.height, schematic.length].map(|e| e as f32);
+        if size == 0 || schematic.volume() == 0 {
+            return preview;
+        }
+
+        // Screen axes: right points north-east, up is up and away from the
+        // viewer, who looks along -(1, 1, 1)
+        let right = [1.0 / 2f32.sqrt(), 0.0, -1.0 / 2f32.sqrt()];
+        let up = [-1.0 / 6f32.sqrt(), 2.0 / 6f32.sqrt(), -1.0 / 6f32.sqrt()];
+        let dot = |a: [f32; 3], b: [f32; 3]| a[0] * b[0] + a[1] * b[1] + a[2] * b[2];
+
+        // Fit the schematic's bounding box into the image with a small margin
+        let corners = (0..8).map(|i| [(i & 1) as f32 * extent[0], ((i >> 1) & 1) as f32 * extent[1], ((i >> 2) & 1) as f32 * extent[2]]);
+        let (mut min, mut max) = ([f32::INFINITY; 2], [f32::NEG_INFINITY; 2]);
+        for corner in corners {
+            let screen = [dot(corner, right), dot(corner, up)];
+            for axis in 0..2 {
+                min[axis] = min[axis].min(screen[axis]);
+                max[axis] = max[axis].max(screen[axis]);
+            }
+        }
+        let scale = size as f32 * 0.94 / (max[0] - min[0]).max(max[1] - min[1]);
+        let center = [(min[0] + max[0]) / 2.0, (min[1] + max[1]) / 2.0];
+
+        for py in 0..size {
+            for px in 0..size {
+                let sx = center[0] + (px as f32 + 0.5 - size as f32 / 2.0) / scale;
+                let sy = center[1] - (py as f32 + 0.5 - size as f32 / 2.0) / scale;
+                let point = [0, 1, 2].map(|a| sx * right[a] + sy * up[a]);
+                if let Some((block, face)) = first_hit(schematic, point, extent) {
+                    let (r, g, b) = color(block);
+                    let shade = |c: f32| ((c * SHADES[face]).clamp(0.0, 1.0) * 255.0).round() as u32;
+                    preview.pixels[(py * size + px) as usize] = 0xFF00_0000 | shade(r) << 16 | shade(g) << 8 | shade(b);
+                }
+            }
+        }
+        preview
+    }
+}
+
+/// Whether a block shows up in previews
+fn drawn(block: &Block) -> bool {
+    !block.is_air() && !block.is_technical()
+}
+
+/// The first drawn block on the line through `point` along -(1, 1, 1), coming
+/// from the viewer's side, and the axis of the face it was entered through
+fn first_hit(schematic: &UnifiedSchematic, point: [f32; 3], extent: [f32; 3]) -> Option<(&Block, usize)> {
+    // The line is point - t * (1, 1, 1); it's inside the box's slab on axis a
+    // for point[a] - extent[a] <= t <= point[a]
+    let mut axis = 0;
+    for a in 1..3 {
+        if point[a] - extent[a] > point[axis] - extent[axis] {
+            axis = a;
+        }
+    }
+    let enter = point[axis] - extent[axis];
+    if enter >= (0..3).map(|a| point[a]).fold(f32::INFINITY, f32::min) {
+        return None;
+    }
+
+    // Walk the cells along the line (Amanatides & Woo), stepping down one axis at a time
+    let entry = [0, 1, 2].map(|a| point[a] - enter);
+    let mut cell = [0, 1, 2].map(|a| (entry[a].floor() as i64).clamp(0, extent[a] as i64 - 1));
+    let mut next = [0, 1, 2].map(|a| entry[a] - cell[a] as f32);
+    loop {
+        let block = schematic.get_block(cell[0] as u32, cell[1] as u32, cell[2] as u32)?;
+        if drawn(block) {
+            return Some((block, axis));
+        }
+        axis = (0..3).fold(0, |best, a| if next[a] < next[best] { a } else { best });
+        cell[axis] -= 1;
+        if cell[axis] < 0 {
+            return None;
+        }
+        next[axis] += 1.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::SchematicBuilder;
+
+    #[test]
+    fn test_int_array_round_trip() {
+        // Opaque red, half-transparent green, transparent black, opaque white
+        let rgba = [255, 0, 0, 255, 0, 255, 0, 128, 0, 0, 0, 0, 255, 255, 255, 255];
+        let image = PreviewImage::from_rgba(2, &rgba).unwrap();
+        let data = image.to_int_array();
+        assert_eq!(&*data, &[0xFFFF_0000u32 as i32, 0x8000_FF00u32 as i32, 0, -1]);
+
+        let decoded = PreviewImage::from_int_array(&data).unwrap();
+        assert_eq!(decoded, image);
+        assert_eq!(decoded.to_rgba(), rgba);
+        assert_eq!(decoded.pixel(1, 0), 0x8000_FF00);
+        assert!(PreviewImage::from_int_array(&[0; 3]).is_none());
+        assert!(PreviewImage::from_rgba(2, &rgba[..12]).is_none());
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn test_from_image_fits_square() {
+        let wide = image::RgbaImage::from_pixel(4, 2, image::Rgba([10, 20, 30, 255]));
+        let preview = PreviewImage::from_image(&image::DynamicImage::ImageRgba8(wide), 8);
+        assert_eq!(preview.size(), 8);
+        // Scaled to 8x4 and centered, with transparent bars above and below
+        assert_eq!([0, 1, 2, 5, 6, 7].map(|y| preview.pixel(3, y)), [0, 0, 0xFF0A_141E, 0xFF0A_141E, 0, 0]);
+        assert_eq!(preview.pixel(0, 3), 0xFF0A_141E);
+    }
+
+    #[test]
+    fn test_render() {
+        let mut builder = SchematicBuilder::new(3, 3, 3);
+        builder.set_block(1, 1, 1, Block::new("minecraft:stone")).unwrap();
+        builder.set_block(0, 0, 0, Block::new("minecraft:barrier")).unwrap();
+        let schem = builder.build();
+
+        let image = PreviewImage::render(&schem, 32, |_| (1.0, 0.5, 0.0));
+        assert_eq!(image.size(), 32);
+        // The block sits in the middle of the box, surrounded by background
+        assert_eq!(image.pixel(0, 0), 0);
+        assert_eq!(image.pixel(16, 16) >> 24, 0xFF);
+        // Top, east and south faces come out in three shades
+        let mut colors: Vec<u32> = image.pixels().iter().copied().filter(|&p| p != 0).collect();
+        colors.sort();
+        colors.dedup();
+        assert_eq!(colors, [0xFFA3_5200, 0xFFCC_6600, 0xFFFF_8000]);
+
+        assert_eq!(PreviewImage::render(&SchematicBuilder::new(2, 2, 2).build(), 8, |_| (1.0, 1.0, 1.0)), PreviewImage::new(8));
+    }
+}
@@ -0,0 +1,181 @@
+//! Format detection from the start of a decompressed NBT stream
+//!
+//! Loading streams the file through the decompressor into the parser, so the
+//! format has to be known before parsing starts. The root compound's field
+//! names and tag types within the first kilobyte are usually enough: Sponge v3
+//! opens with its `Schematic` wrapper, and the other formats have fields only
+//! they use. When the prefix doesn't settle it, loading buffers the file and
+//! probes all of its root fields, and only tries each format in turn if those
+//! don't settle it either.
+
+/// Bytes of decompressed data read for detection
+pub(crate) const PROBE_LEN: usize = 1024;
+
+const TAG_BYTE_ARRAY: u8 = 7;
+const TAG_COMPOUND: u8 = 10;
+
+/// A format detected from root field names
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Detected {
+    Litematica,
+    /// Sponge v3 with the `Schematic` wrapper
+    SpongeWrapped,
+    /// Sponge v2, or v3 without the wrapper
+    Sponge,
+    Legacy,
+}
+
+/// Guess the format from the fields starting within `prefix`
+pub(crate) fn detect(prefix: &[u8]) -> Option<Detected> {
+    root_fields(prefix).into_iter().find_map(|(tag, name)| match name.as_str() {
+        "Schematic" if tag == TAG_COMPOUND => Some(Detected::SpongeWrapped),
+        "Regions" | "MinecraftDataVersion" | "SubVersion" => Some(Detected::Litematica),
+        "BlockData" | "Palette" | "PaletteMax" | "DataVersion" | "Offset" => Some(Detected::Sponge),
+        "Blocks" if tag == TAG_COMPOUND => Some(Detected::Sponge),
+        "Blocks" if tag == TAG_BYTE_ARRAY => Some(Detected::Legacy),
+        "Materials" | "AddBlocks" => Some(Detected::Legacy),
+        _ => None,
+    })
+}
+
+/// Tag types and names of the root compound's fields whose headers fit in `data`
+fn root_fields(data: &[u8]) -> Vec<(u8, String)> {
+    let mut fields = Vec::new();
+    let mut reader = Prefix { data, pos: 0 };
+    if reader.byte() != Some(TAG_COMPOUND) || reader.string().is_none() {
+        return fields;
+    }
+    while let Some(tag) = reader.byte().filter(|&tag| tag != 0) {
+        let Some(name) = reader.string() else { break };
+        fields.push((tag, name));
+        if reader.skip_payload(tag, 0).is_none() {
+            break;
+        }
+    }
+    fields
+}
+
+/// Cursor over a possibly truncated NBT buffer; every read fails past the end
+struct Prefix<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl Prefix<'_> {
+    fn take(&mut self, n: usize) -> Option<&[u8]> {
+        let bytes = self.data.get(self.pos..self.pos.checked_add(n)?)?;
+        self.pos += n;
+        Some(bytes)
+    }
+
+    fn byte(&mut self) -> Option<u8> {
+        self.take(1).map(|b| b[0])
+    }
+
+    fn int(&mut self) -> Option<usize> {
+        let b = self.take(4)?;
+        usize::try_from(i32::from_be_bytes([b[0], b[1], b[2], b[3]])).ok()
+    }
+
+    fn string(&mut self) -> Option<String> {
+        let b = self.take(2)?;
+        let len = u16::from_be_bytes([b[0], b[1]]) as usize;
+        self.take(len).map(|name| String::from_utf8_lossy(name).into_owned())
+    }
+
+    /// Skip a value of type `tag`; nesting is capped like the game's 512 levels
+    fn skip_payload(&mut self, tag: u8, depth: usize) -> Option<()> {
+        if depth > 512 {
+            return None;
+        }
+        match tag {
+            1 => self.take(1).map(drop),
+            2 => self.take(2).map(drop),
+            3 | 5 => self.take(4).map(drop),
+            4 | 6 => self.take(8).map(drop),
+            7 => {
+                let len = self.int()?;
+                self.take(len).map(drop)
+            }
+            8 => self.string().map(drop),
+            9 => {
+                let element = self.byte()?;
+                for _ in 0..self.int()? {
+                    self.skip_payload(element, depth + 1)?;
+                }
+                Some(())
+            }
+            10 => loop {
+                match self.byte()? {
+                    0 => return Some(()),
+                    tag => {
+                        self.string()?;
+                        self.skip_payload(tag, depth + 1)?;
+                    }
+                }
+            },
+            11 => {
+                let len = self.int()?;
+                self.take(len.checked_mul(4)?).map(drop)
+            }
+            12 => {
+                let len = self.int()?;
+                self.take(len.checked_mul(8)?).map(drop)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(tag: u8, name: &str, payload: &[u8]) -> Vec<u8> {
+        let mut bytes = vec![tag];
+        bytes.extend((name.len() as u16).to_be_bytes());
+        bytes.extend(name.as_bytes());
+        bytes.extend(payload);
+        bytes
+    }
+
+    fn root(fields: &[Vec<u8>]) -> Vec<u8> {
+        let mut bytes = field(TAG_COMPOUND, "", &[]);
+        for f in fields {
+            bytes.extend(f);
+        }
+        bytes.push(0);
+        bytes
+    }
+
+    #[test]
+    fn test_detect_from_root_fields() {
+        let int = |v: i32| v.to_be_bytes().to_vec();
+        let metadata = {
+            let mut payload = field(8, "Name", &[0, 3, b'a', b'b', b'c']);
+            payload.push(0);
+            payload
+        };
+
+        // Litematica: Metadata (shared with Sponge) comes before the deciding field
+        let lit = root(&[field(3, "Version", &int(6)), field(TAG_COMPOUND, "Metadata", &metadata), field(TAG_COMPOUND, "Regions", &[0])]);
+        assert_eq!(detect(&lit), Some(Detected::Litematica));
+
+        let wrapped = root(&[field(TAG_COMPOUND, "Schematic", &[0])]);
+        assert_eq!(detect(&wrapped), Some(Detected::SpongeWrapped));
+
+        let mut blocks = int(3);
+        blocks.extend([1, 2, 3]);
+        let legacy = root(&[field(2, "Width", &[0, 1]), field(TAG_BYTE_ARRAY, "Blocks", &blocks)]);
+        assert_eq!(detect(&legacy), Some(Detected::Legacy));
+
+        // A large array before the deciding field runs past the prefix
+        let mut data = int(5000);
+        data.extend(vec![0; 5000]);
+        let sponge = root(&[field(TAG_BYTE_ARRAY, "BlockData", &data), field(3, "Version", &int(2))]);
+        assert_eq!(detect(&sponge[..PROBE_LEN]), Some(Detected::Sponge));
+        let unknown = root(&[field(TAG_BYTE_ARRAY, "Unknown", &data), field(3, "DataVersion", &int(3953))]);
+        assert_eq!(detect(&unknown[..PROBE_LEN]), None);
+        assert_eq!(detect(&unknown), Some(Detected::Sponge));
+    }
+}
@@ -0,0 +1,111 @@
+//! Progress reporting and cancellation for long-running operations
+//!
+//! Loaders and exporters report their phases through the [`Progress`] trait so
+//! the crate can be embedded in GUIs or services. The CLI uses
+//! `ConsoleProgress` (behind the `cli` feature), which renders indicatif
+//! progress bars on stderr.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "cli")]
+use std::sync::{Arc, Mutex};
+#[cfg(feature = "cli")]
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+use crate::SchemError;
+
+/// Receiver for progress updates and cancellation requests
+///
+/// All methods have no-op defaults, so implementors only override what they need.
+pub trait Progress {
+    /// A new phase started with `total` steps
+    fn start(&self, _total: u64, _message: &str) {}
+
+    /// Current position within the running phase
+    fn set_position(&self, _position: u64) {}
+
+    /// The running phase finished
+    fn finish(&self, _message: &str) {}
+
+    /// Return true to abort the operation with [`SchemError::Cancelled`]
+    fn should_cancel(&self) -> bool {
+        false
+    }
+}
+
+/// Progress receiver that ignores all updates (the library default)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoProgress;
+
+impl Progress for NoProgress {}
+
+/// A bare flag works as a silent, cancellable progress receiver
+impl Progress for AtomicBool {
+    fn should_cancel(&self) -> bool {
+        self.load(Ordering::Relaxed)
+    }
+}
+
+/// Progress bars on stderr, one per phase (hidden when stderr isn't a terminal)
+#[cfg(feature = "cli")]
+#[derive(Default)]
+pub struct ConsoleProgress {
+    bar: Mutex<Option<ProgressBar>>,
+    cancel: Option<Arc<AtomicBool>>,
+}
+
+#[cfg(feature = "cli")]
+impl ConsoleProgress {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Abort the running operation once `flag` is set
+    pub fn with_cancel_flag(mut self, flag: Arc<AtomicBool>) -> Self {
+        self.cancel = Some(flag);
+        self
+    }
+}
+
+#[cfg(feature = "cli")]
+impl Progress for ConsoleProgress {
+    fn start(&self, total: u64, message: &str) {
+        use std::io::IsTerminal;
+
+        let pb = ProgressBar::new(total);
+        if !std::io::stderr().is_terminal() {
+            pb.set_draw_target(ProgressDrawTarget::hidden());
+        }
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("{msg} [{bar:40.cyan/blue}] {pos}/{len} ({percent}%) {elapsed_precise}")
+                .unwrap()
+                .progress_chars("=>-")
+        );
+        pb.set_message(message.to_string());
+        *self.bar.lock().unwrap() = Some(pb);
+    }
+
+    fn set_position(&self, position: u64) {
+        if let Some(pb) = self.bar.lock().unwrap().as_ref() {
+            pb.set_position(position);
+        }
+    }
+
+    fn finish(&self, message: &str) {
+        if let Some(pb) = self.bar.lock().unwrap().take() {
+            pb.finish_with_message(message.to_string());
+        }
+    }
+
+    fn should_cancel(&self) -> bool {
+        self.cancel.as_ref().map(|c| c.load(Ordering::Relaxed)).unwrap_or(false)
+    }
+}
+
+/// Return [`SchemError::Cancelled`] if the receiver asked to stop
+pub(crate) fn check_cancel(progress: &dyn Progress) -> Result<(), SchemError> {
+    if progress.should_cancel() {
+        Err(SchemError::Cancelled)
+    } else {
+        Ok(())
+    }
+}
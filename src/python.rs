@@ -0,0 +1,185 @@
+//! Python bindings (enabled by the `python` feature)
+//!
+//! Build a wheel with maturin (see `pyproject.toml`):
+//!
+//! ```text
+//! pip install maturin
+//! maturin develop --release
+//! ```
+//!
+//! ```python
+//! import numpy as np
+//! import schem_tool
+//!
+//! s = schem_tool.load("castle.litematic")
+//! s.block_counts()
+//! palette, data, shape = s.block_index_grid()
+//! grid = np.frombuffer(data, dtype="<u4").reshape(shape)  # (y, z, x)
+//! ```
+
+use std::collections::HashMap;
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+use crate::{recipes, UnifiedSchematic};
+
+create_exception!(schem_tool, SchemError, PyException, "Error raised when a schematic cannot be loaded");
+
+/// ((x, y, z), front_lines, back_lines)
+type PySign = ((i32, i32, i32), Vec<String>, Vec<String>);
+
+fn to_py_err(e: crate::SchemError) -> PyErr {
+    SchemError::new_err(e.to_string())
+}
+
+/// A loaded schematic
+#[pyclass(name = "Schematic", module = "schem_tool")]
+pub struct PySchematic {
+    schem: UnifiedSchematic,
+}
+
+#[pymethods]
+impl PySchematic {
+    /// Detected format name, e.g. "SpongeV3"
+    #[getter]
+    fn format(&self) -> String {
+        format!("{:?}", self.schem.format)
+    }
+
+    /// (width, height, length)
+    fn dimensions(&self) -> (u32, u32, u32) {
+        (self.schem.width, self.schem.height, self.schem.length)
+    }
+
+    fn volume(&self) -> usize {
+        self.schem.volume()
+    }
+
+    /// {block name: count}
+    fn block_counts(&self) -> HashMap<String, usize> {
+        self.schem.block_counts()
+    }
+
+    /// Full block state string at a position, or None outside the schematic
+    fn get_block(&self, x: u32, y: u32, z: u32) -> Option<String> {
+        self.schem.get_block(x, y, z).map(|b| b.full_name())
+    }
+
+    /// Unique block states as full state strings
+    fn unique_blocks(&self) -> Vec<String> {
+        self.schem.unique_blocks().iter().map(|b| b.full_name()).collect()
+    }
+
+    /// [((x, y, z), front_lines, back_lines)] for every sign with text
+    fn signs(&self) -> Vec<PySign> {
+        self.schem
+            .get_signs()
+            .into_iter()
+            .map(|(be, text)| (be.pos, text.front, text.back))
+            .collect()
+    }
+
+    /// {raw material: amount} needed to build the schematic
+    #[pyo3(signature = (stonecutter = false))]
+    fn materials(&self, stonecutter: bool) -> HashMap<String, f64> {
+        recipes::calculate_materials_with_options(&self.schem.block_counts(), stonecutter)
+    }
+
+    /// (palette, data, shape) for building a numpy array of palette indices
+    ///
+    /// `data` holds little-endian u32 indices into `palette` in YZX order and
+    /// `shape` is (height, length, width).
+    fn block_index_grid<'py>(&self, py: Python<'py>) -> (Vec<String>, Bound<'py, PyBytes>, (usize, usize, usize)) {
+        let mut lookup: HashMap<String, u32> = HashMap::new();
+        let mut palette = Vec::new();
+        let mut data = Vec::with_capacity(self.schem.blocks.len() * 4);
+
+        for block in &self.schem.blocks {
+            let name = block.full_name();
+            let id = *lookup.entry(name).or_insert_with_key(|name| {
+                palette.push(name.clone());
+                (palette.len() - 1) as u32
+            });
+            data.extend_from_slice(&id.to_le_bytes());
+        }
+
+        let shape = (self.schem.height as usize, self.schem.length as usize, self.schem.width as usize);
+        (palette, PyBytes::new(py, &data), shape)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<Schematic {:?} {}>", self.schem.format, self.schem.dimensions_str())
+    }
+}
+
+/// Load a schematic file, auto-detecting its format
+#[pyfunction]
+fn load(path: std::path::PathBuf) -> PyResult<PySchematic> {
+    let schem = UnifiedSchematic::load(path).map_err(to_py_err)?;
+    Ok(PySchematic { schem })
+}
+
+/// Parse schematic file contents (gzipped or raw NBT)
+#[pyfunction]
+fn loads(data: &[u8]) -> PyResult<PySchematic> {
+    let schem = UnifiedSchematic::from_bytes(data).map_err(to_py_err)?;
+    Ok(PySchematic { schem })
+}
+
+#[pymodule]
+fn schem_tool(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PySchematic>()?;
+    m.add_function(wrap_pyfunction!(load, m)?)?;
+    m.add_function(wrap_pyfunction!(loads, m)?)?;
+    m.add("SchemError", m.py().get_type::<SchemError>())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schem::SchemWrapper;
+    use crate::{Block, SchematicBuilder};
+
+    #[test]
+    fn test_module_from_python() {
+        let mut builder = SchematicBuilder::new(3, 2, 1);
+        builder.fill((0, 0, 0), (2, 0, 0), Block::new("minecraft:stone")).unwrap();
+        builder.set_block(1, 1, 0, Block::new("minecraft:oak_planks")).unwrap();
+        let bytes = SchemWrapper::from_unified(&builder.build()).unwrap().to_bytes().unwrap();
+
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| -> PyResult<()> {
+            let module = pyo3::wrap_pymodule!(schem_tool)(py).into_bound(py);
+            let schem = module.getattr("loads")?.call1((PyBytes::new(py, &bytes),))?;
+            assert_eq!(schem.call_method0("dimensions")?.extract::<(u32, u32, u32)>()?, (3, 2, 1));
+            let counts: HashMap<String, usize> = schem.call_method0("block_counts")?.extract()?;
+            assert_eq!(counts, HashMap::from([
+                ("minecraft:air".to_string(), 2),
+                ("minecraft:stone".to_string(), 3),
+                ("minecraft:oak_planks".to_string(), 1),
+            ]));
+
+            // Indices in YZX order with the shape as (height, length, width)
+            let (palette, data, shape): (Vec<String>, Bound<PyBytes>, (usize, usize, usize)) =
+                schem.call_method0("block_index_grid")?.extract()?;
+            assert_eq!(shape, (2, 1, 3));
+            let indices: Vec<u32> = data.as_bytes().chunks(4).map(|b| u32::from_le_bytes(b.try_into().unwrap())).collect();
+            assert_eq!(indices.len(), 6);
+            let at = |x: usize, y: usize, z: usize| palette[indices[(y * shape.1 + z) * shape.2 + x] as usize].as_str();
+            assert_eq!((at(1, 1, 0), at(2, 1, 0), at(2, 0, 0)), ("minecraft:oak_planks", "minecraft:air", "minecraft:stone"));
+
+            // Load failures raise the module's SchemError with the library's message
+            let err = module.getattr("loads")?.call1((PyBytes::new(py, b"junk"),)).unwrap_err();
+            assert!(err.is_instance_of::<SchemError>(py));
+            assert!(module.getattr("SchemError")?.is(py.get_type::<SchemError>()));
+            assert!(!err.value(py).to_string().is_empty());
+            let missing = module.getattr("load")?.call1(("/nonexistent/castle.litematic",)).unwrap_err();
+            assert!(missing.is_instance_of::<SchemError>(py));
+            assert!(missing.value(py).to_string().starts_with("IO error"));
+            Ok(())
+        })
+        .unwrap();
+    }
+}
@@ -0,0 +1,121 @@
+//! Seeded random choices, so exports come out the same on every run
+//!
+//! Every random choice an exporter makes (currently which of a block's weighted
+//! model variants to use) comes from a [`RandomContext`]. Choices are seeded
+//! per block position rather than drawn from one running generator, so they
+//! don't depend on the order blocks are visited in, which differs between the
+//! exporters and between parallel runs.
+
+use std::fmt;
+use std::str::FromStr;
+
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+
+/// Seeds for random choices during export
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RandomContext {
+    seed: u64,
+}
+
+impl RandomContext {
+    /// Seed used unless another one is given, so default runs are reproducible
+    pub const DEFAULT_SEED: u64 = 0x5eed;
+
+    pub fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+
+    /// A seed that differs between runs, from the standard library's hash keys
+    /// and the clock
+    pub fn from_entropy() -> Self {
+        use std::hash::{BuildHasher, Hasher};
+
+        let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_nanos());
+        hasher.write_u128(nanos);
+        Self::new(hasher.finish())
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Generator for the choices made at one block position
+    pub fn rng_at(&self, (x, y, z): (i32, i32, i32)) -> SmallRng {
+        let mixed = self.seed
+            ^ (x as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15)
+            ^ (y as u64).wrapping_mul(0xc2b2_ae3d_27d4_eb4f)
+            ^ (z as u64).wrapping_mul(0x1656_67b1_9e37_79f9);
+        SmallRng::seed_from_u64(mixed)
+    }
+}
+
+impl Default for RandomContext {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_SEED)
+    }
+}
+
+impl fmt::Display for RandomContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.seed)
+    }
+}
+
+/// A number, or `random` for a different seed every run
+impl FromStr for RandomContext {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        if s.eq_ignore_ascii_case("random") {
+            return Ok(Self::from_entropy());
+        }
+        s.parse()
+            .map(Self::new)
+            .map_err(|_| format!("invalid seed '{}' (expected a number or 'random')", s))
+    }
+}
+
+/// One of `items`, each as likely as its `weight` (weights below 1 are never
+/// picked unless all of them are, in which case the first is)
+pub fn pick_weighted<'a, T>(items: &'a [T], weight: impl Fn(&T) -> i32, rng: &mut impl Rng) -> Option<&'a T> {
+    let total: u64 = items.iter().map(|item| weight(item).max(0) as u64).sum();
+    if total == 0 {
+        return items.first();
+    }
+    let mut roll = rng.random_range(0..total);
+    items.iter().find(|item| {
+        let weight = weight(item).max(0) as u64;
+        if roll < weight {
+            return true;
+        }
+        roll -= weight;
+        false
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pick_weighted() {
+        let random = RandomContext::default();
+        let items = [("never", 0), ("rare", 1), ("common", 9)];
+        let mut counts = [0; 3];
+        for x in 0..1000 {
+            let picked = pick_weighted(&items, |item| item.1, &mut random.rng_at((x, 0, 0))).unwrap();
+            counts[items.iter().position(|item| item == picked).unwrap()] += 1;
+        }
+        assert_eq!(counts[0], 0);
+        assert!((50..=150).contains(&counts[1]), "{:?}", counts);
+
+        let mut rng = random.rng_at((0, 0, 0));
+        assert_eq!(pick_weighted(&[("a", 0), ("b", 0)], |item| item.1, &mut rng), Some(&("a", 0)));
+        assert_eq!("42".parse::<RandomContext>(), Ok(RandomContext::new(42)));
+        assert!("forty".parse::<RandomContext>().unwrap_err().contains("expected a number"));
+    }
+}
@@ -2,7 +2,7 @@
 //!
 //! This module contains recipes to break down crafted items into raw materials.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 /// Recipe definition: what raw materials are needed for one item
 #[derive(Debug, Clone)]
@@ -13,289 +13,358 @@ pub struct Recipe {
     pub output_count: u32,
     /// Required ingredients: (item_name, count)
     pub ingredients: &'static [(&'static str, u32)],
+    /// Where the recipe is made
+    pub method: CraftingMethod,
+}
+
+/// Where a recipe is made
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CraftingMethod {
+    /// Crafting table (or the inventory grid)
+    Crafting,
+    /// Furnace, one fuel-burning operation per output item
+    Smelting,
+    /// Stonecutter
+    Stonecutting,
+    /// Anything else (smithing table, loom, ...)
+    Other,
 }
 
 /// Get all known recipes
 pub fn get_recipes() -> HashMap<&'static str, Recipe> {
+    use CraftingMethod::*;
     let recipes: Vec<Recipe> = vec![
         // === Wood products ===
         Recipe {
             output: "minecraft:oak_planks",
             output_count: 4,
             ingredients: &[("minecraft:oak_log", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:spruce_planks",
             output_count: 4,
             ingredients: &[("minecraft:spruce_log", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:birch_planks",
             output_count: 4,
             ingredients: &[("minecraft:birch_log", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:jungle_planks",
             output_count: 4,
             ingredients: &[("minecraft:jungle_log", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:acacia_planks",
             output_count: 4,
             ingredients: &[("minecraft:acacia_log", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:dark_oak_planks",
             output_count: 4,
             ingredients: &[("minecraft:dark_oak_log", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:mangrove_planks",
             output_count: 4,
             ingredients: &[("minecraft:mangrove_log", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:cherry_planks",
             output_count: 4,
             ingredients: &[("minecraft:cherry_log", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:bamboo_planks",
             output_count: 2,
             ingredients: &[("minecraft:bamboo_block", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:crimson_planks",
             output_count: 4,
             ingredients: &[("minecraft:crimson_stem", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:warped_planks",
             output_count: 4,
             ingredients: &[("minecraft:warped_stem", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:stick",
             output_count: 4,
             ingredients: &[("minecraft:any_planks", 2)],
+            method: Crafting,
         },
+        // Recipes that take any wood end up as logs of any kind
+        Recipe { output: "minecraft:any_planks", output_count: 4, ingredients: &[("minecraft:any_log", 1)], method: Crafting },
+        Recipe { output: "minecraft:any_slab", output_count: 6, ingredients: &[("minecraft:any_planks", 3)], method: Crafting },
 
         // === Wood stairs (all types) ===
-        Recipe { output: "minecraft:oak_stairs", output_count: 4, ingredients: &[("minecraft:oak_planks", 6)] },
-        Recipe { output: "minecraft:spruce_stairs", output_count: 4, ingredients: &[("minecraft:spruce_planks", 6)] },
-        Recipe { output: "minecraft:birch_stairs", output_count: 4, ingredients: &[("minecraft:birch_planks", 6)] },
-        Recipe { output: "minecraft:jungle_stairs", output_count: 4, ingredients: &[("minecraft:jungle_planks", 6)] },
-        Recipe { output: "minecraft:acacia_stairs", output_count: 4, ingredients: &[("minecraft:acacia_planks", 6)] },
-        Recipe { output: "minecraft:dark_oak_stairs", output_count: 4, ingredients: &[("minecraft:dark_oak_planks", 6)] },
-        Recipe { output: "minecraft:mangrove_stairs", output_count: 4, ingredients: &[("minecraft:mangrove_planks", 6)] },
-        Recipe { output: "minecraft:cherry_stairs", output_count: 4, ingredients: &[("minecraft:cherry_planks", 6)] },
-        Recipe { output: "minecraft:bamboo_stairs", output_count: 4, ingredients: &[("minecraft:bamboo_planks", 6)] },
-        Recipe { output: "minecraft:crimson_stairs", output_count: 4, ingredients: &[("minecraft:crimson_planks", 6)] },
-        Recipe { output: "minecraft:warped_stairs", output_count: 4, ingredients: &[("minecraft:warped_planks", 6)] },
+        Recipe { output: "minecraft:oak_stairs", output_count: 4, ingredients: &[("minecraft:oak_planks", 6)], method: Crafting },
+        Recipe { output: "minecraft:spruce_stairs", output_count: 4, ingredients: &[("minecraft:spruce_planks", 6)], method: Crafting },
+        Recipe { output: "minecraft:birch_stairs", output_count: 4, ingredients: &[("minecraft:birch_planks", 6)], method: Crafting },
+        Recipe { output: "minecraft:jungle_stairs", output_count: 4, ingredients: &[("minecraft:jungle_planks", 6)], method: Crafting },
+        Recipe { output: "minecraft:acacia_stairs", output_count: 4, ingredients: &[("minecraft:acacia_planks", 6)], method: Crafting },
+        Recipe { output: "minecraft:dark_oak_stairs", output_count: 4, ingredients: &[("minecraft:dark_oak_planks", 6)], method: Crafting },
+        Recipe { output: "minecraft:mangrove_stairs", output_count: 4, ingredients: &[("minecraft:mangrove_planks", 6)], method: Crafting },
+        Recipe { output: "minecraft:cherry_stairs", output_count: 4, ingredients: &[("minecraft:cherry_planks", 6)], method: Crafting },
+        Recipe { output: "minecraft:bamboo_stairs", output_count: 4, ingredients: &[("minecraft:bamboo_planks", 6)], method: Crafting },
+        Recipe { output: "minecraft:crimson_stairs", output_count: 4, ingredients: &[("minecraft:crimson_planks", 6)], method: Crafting },
+        Recipe { output: "minecraft:warped_stairs", output_count: 4, ingredients: &[("minecraft:warped_planks", 6)], method: Crafting },
 
         // === Wood slabs ===
-        Recipe { output: "minecraft:oak_slab", output_count: 6, ingredients: &[("minecraft:oak_planks", 3)] },
-        Recipe { output: "minecraft:spruce_slab", output_count: 6, ingredients: &[("minecraft:spruce_planks", 3)] },
-        Recipe { output: "minecraft:birch_slab", output_count: 6, ingredients: &[("minecraft:birch_planks", 3)] },
-        Recipe { output: "minecraft:jungle_slab", output_count: 6, ingredients: &[("minecraft:jungle_planks", 3)] },
-        Recipe { output: "minecraft:acacia_slab", output_count: 6, ingredients: &[("minecraft:acacia_planks", 3)] },
-        Recipe { output: "minecraft:dark_oak_slab", output_count: 6, ingredients: &[("minecraft:dark_oak_planks", 3)] },
-        Recipe { output: "minecraft:mangrove_slab", output_count: 6, ingredients: &[("minecraft:mangrove_planks", 3)] },
-        Recipe { output: "minecraft:cherry_slab", output_count: 6, ingredients: &[("minecraft:cherry_planks", 3)] },
-        Recipe { output: "minecraft:bamboo_slab", output_count: 6, ingredients: &[("minecraft:bamboo_planks", 3)] },
-        Recipe { output: "minecraft:crimson_slab", output_count: 6, ingredients: &[("minecraft:crimson_planks", 3)] },
-        Recipe { output: "minecraft:warped_slab", output_count: 6, ingredients: &[("minecraft:warped_planks", 3)] },
+        Recipe { output: "minecraft:oak_slab", output_count: 6, ingredients: &[("minecraft:oak_planks", 3)], method: Crafting },
+        Recipe { output: "minecraft:spruce_slab", output_count: 6, ingredients: &[("minecraft:spruce_planks", 3)], method: Crafting },
+        Recipe { output: "minecraft:birch_slab", output_count: 6, ingredients: &[("minecraft:birch_planks", 3)], method: Crafting },
+        Recipe { output: "minecraft:jungle_slab", output_count: 6, ingredients: &[("minecraft:jungle_planks", 3)], method: Crafting },
+        Recipe { output: "minecraft:acacia_slab", output_count: 6, ingredients: &[("minecraft:acacia_planks", 3)], method: Crafting },
+        Recipe { output: "minecraft:dark_oak_slab", output_count: 6, ingredients: &[("minecraft:dark_oak_planks", 3)], method: Crafting },
+        Recipe { output: "minecraft:mangrove_slab", output_count: 6, ingredients: &[("minecraft:mangrove_planks", 3)], method: Crafting },
+        Recipe { output: "minecraft:cherry_slab", output_count: 6, ingredients: &[("minecraft:cherry_planks", 3)], method: Crafting },
+        Recipe { output: "minecraft:bamboo_slab", output_count: 6, ingredients: &[("minecraft:bamboo_planks", 3)], method: Crafting },
+        Recipe { output: "minecraft:crimson_slab", output_count: 6, ingredients: &[("minecraft:crimson_planks", 3)], method: Crafting },
+        Recipe { output: "minecraft:warped_slab", output_count: 6, ingredients: &[("minecraft:warped_planks", 3)], method: Crafting },
 
         // === Wood fences ===
-        Recipe { output: "minecraft:oak_fence", output_count: 3, ingredients: &[("minecraft:oak_planks", 4), ("minecraft:stick", 2)] },
-        Recipe { output: "minecraft:spruce_fence", output_count: 3, ingredients: &[("minecraft:spruce_planks", 4), ("minecraft:stick", 2)] },
-        Recipe { output: "minecraft:birch_fence", output_count: 3, ingredients: &[("minecraft:birch_planks", 4), ("minecraft:stick", 2)] },
-        Recipe { output: "minecraft:jungle_fence", output_count: 3, ingredients: &[("minecraft:jungle_planks", 4), ("minecraft:stick", 2)] },
-        Recipe { output: "minecraft:acacia_fence", output_count: 3, ingredients: &[("minecraft:acacia_planks", 4), ("minecraft:stick", 2)] },
-        Recipe { output: "minecraft:dark_oak_fence", output_count: 3, ingredients: &[("minecraft:dark_oak_planks", 4), ("minecraft:stick", 2)] },
-        Recipe { output: "minecraft:mangrove_fence", output_count: 3, ingredients: &[("minecraft:mangrove_planks", 4), ("minecraft:stick", 2)] },
-        Recipe { output: "minecraft:cherry_fence", output_count: 3, ingredients: &[("minecraft:cherry_planks", 4), ("minecraft:stick", 2)] },
-        Recipe { output: "minecraft:bamboo_fence", output_count: 3, ingredients: &[("minecraft:bamboo_planks", 4), ("minecraft:stick", 2)] },
-        Recipe { output: "minecraft:crimson_fence", output_count: 3, ingredients: &[("minecraft:crimson_planks", 4), ("minecraft:stick", 2)] },
-        Recipe { output: "minecraft:warped_fence", output_count: 3, ingredients: &[("minecraft:warped_planks", 4), ("minecraft:stick", 2)] },
+        Recipe { output: "minecraft:oak_fence", output_count: 3, ingredients: &[("minecraft:oak_planks", 4), ("minecraft:stick", 2)], method: Crafting },
+        Recipe { output: "minecraft:spruce_fence", output_count: 3, ingredients: &[("minecraft:spruce_planks", 4), ("minecraft:stick", 2)], method: Crafting },
+        Recipe { output: "minecraft:birch_fence", output_count: 3, ingredients: &[("minecraft:birch_planks", 4), ("minecraft:stick", 2)], method: Crafting },
+        Recipe { output: "minecraft:jungle_fence", output_count: 3, ingredients: &[("minecraft:jungle_planks", 4), ("minecraft:stick", 2)], method: Crafting },
+        Recipe { output: "minecraft:acacia_fence", output_count: 3, ingredients: &[("minecraft:acacia_planks", 4), ("minecraft:stick", 2)], method: Crafting },
+        Recipe { output: "minecraft:dark_oak_fence", output_count: 3, ingredients: &[("minecraft:dark_oak_planks", 4), ("minecraft:stick", 2)], method: Crafting },
+        Recipe { output: "minecraft:mangrove_fence", output_count: 3, ingredients: &[("minecraft:mangrove_planks", 4), ("minecraft:stick", 2)], method: Crafting },
+        Recipe { output: "minecraft:cherry_fence", output_count: 3, ingredients: &[("minecraft:cherry_planks", 4), ("minecraft:stick", 2)], method: Crafting },
+        Recipe { output: "minecraft:bamboo_fence", output_count: 3, ingredients: &[("minecraft:bamboo_planks", 4), ("minecraft:stick", 2)], method: Crafting },
+        Recipe { output: "minecraft:crimson_fence", output_count: 3, ingredients: &[("minecraft:crimson_planks", 4), ("minecraft:stick", 2)], method: Crafting },
+        Recipe { output: "minecraft:warped_fence", output_count: 3, ingredients: &[("minecraft:warped_planks", 4), ("minecraft:stick", 2)], method: Crafting },
 
         // === Fence gates ===
-        Recipe { output: "minecraft:oak_fence_gate", output_count: 1, ingredients: &[("minecraft:oak_planks", 2), ("minecraft:stick", 4)] },
-        Recipe { output: "minecraft:spruce_fence_gate", output_count: 1, ingredients: &[("minecraft:spruce_planks", 2), ("minecraft:stick", 4)] },
-        Recipe { output: "minecraft:birch_fence_gate", output_count: 1, ingredients: &[("minecraft:birch_planks", 2), ("minecraft:stick", 4)] },
-        Recipe { output: "minecraft:jungle_fence_gate", output_count: 1, ingredients: &[("minecraft:jungle_planks", 2), ("minecraft:stick", 4)] },
-        Recipe { output: "minecraft:acacia_fence_gate", output_count: 1, ingredients: &[("minecraft:acacia_planks", 2), ("minecraft:stick", 4)] },
-        Recipe { output: "minecraft:dark_oak_fence_gate", output_count: 1, ingredients: &[("minecraft:dark_oak_planks", 2), ("minecraft:stick", 4)] },
-        Recipe { output: "minecraft:mangrove_fence_gate", output_count: 1, ingredients: &[("minecraft:mangrove_planks", 2), ("minecraft:stick", 4)] },
-        Recipe { output: "minecraft:cherry_fence_gate", output_count: 1, ingredients: &[("minecraft:cherry_planks", 2), ("minecraft:stick", 4)] },
-        Recipe { output: "minecraft:bamboo_fence_gate", output_count: 1, ingredients: &[("minecraft:bamboo_planks", 2), ("minecraft:stick", 4)] },
-        Recipe { output: "minecraft:crimson_fence_gate", output_count: 1, ingredients: &[("minecraft:crimson_planks", 2), ("minecraft:stick", 4)] },
-        Recipe { output: "minecraft:warped_fence_gate", output_count: 1, ingredients: &[("minecraft:warped_planks", 2), ("minecraft:stick", 4)] },
+        Recipe { output: "minecraft:oak_fence_gate", output_count: 1, ingredients: &[("minecraft:oak_planks", 2), ("minecraft:stick", 4)], method: Crafting },
+        Recipe { output: "minecraft:spruce_fence_gate", output_count: 1, ingredients: &[("minecraft:spruce_planks", 2), ("minecraft:stick", 4)], method: Crafting },
+        Recipe { output: "minecraft:birch_fence_gate", output_count: 1, ingredients: &[("minecraft:birch_planks", 2), ("minecraft:stick", 4)], method: Crafting },
+        Recipe { output: "minecraft:jungle_fence_gate", output_count: 1, ingredients: &[("minecraft:jungle_planks", 2), ("minecraft:stick", 4)], method: Crafting },
+        Recipe { output: "minecraft:acacia_fence_gate", output_count: 1, ingredients: &[("minecraft:acacia_planks", 2), ("minecraft:stick", 4)], method: Crafting },
+        Recipe { output: "minecraft:dark_oak_fence_gate", output_count: 1, ingredients: &[("minecraft:dark_oak_planks", 2), ("minecraft:stick", 4)], method: Crafting },
+        Recipe { output: "minecraft:mangrove_fence_gate", output_count: 1, ingredients: &[("minecraft:mangrove_planks", 2), ("minecraft:stick", 4)], method: Crafting },
+        Recipe { output: "minecraft:cherry_fence_gate", output_count: 1, ingredients: &[("minecraft:cherry_planks", 2), ("minecraft:stick", 4)], method: Crafting },
+        Recipe { output: "minecraft:bamboo_fence_gate", output_count: 1, ingredients: &[("minecraft:bamboo_planks", 2), ("minecraft:stick", 4)], method: Crafting },
+        Recipe { output: "minecraft:crimson_fence_gate", output_count: 1, ingredients: &[("minecraft:crimson_planks", 2), ("minecraft:stick", 4)], method: Crafting },
+        Recipe { output: "minecraft:warped_fence_gate", output_count: 1, ingredients: &[("minecraft:warped_planks", 2), ("minecraft:stick", 4)], method: Crafting },
 
         // === Doors ===
-        Recipe { output: "minecraft:oak_door", output_count: 3, ingredients: &[("minecraft:oak_planks", 6)] },
-        Recipe { output: "minecraft:spruce_door", output_count: 3, ingredients: &[("minecraft:spruce_planks", 6)] },
-        Recipe { output: "minecraft:birch_door", output_count: 3, ingredients: &[("minecraft:birch_planks", 6)] },
-        Recipe { output: "minecraft:jungle_door", output_count: 3, ingredients: &[("minecraft:jungle_planks", 6)] },
-        Recipe { output: "minecraft:acacia_door", output_count: 3, ingredients: &[("minecraft:acacia_planks", 6)] },
-        Recipe { output: "minecraft:dark_oak_door", output_count: 3, ingredients: &[("minecraft:dark_oak_planks", 6)] },
-        Recipe { output: "minecraft:mangrove_door", output_count: 3, ingredients: &[("minecraft:mangrove_planks", 6)] },
-        Recipe { output: "minecraft:cherry_door", output_count: 3, ingredients: &[("minecraft:cherry_planks", 6)] },
-        Recipe { output: "minecraft:bamboo_door", output_count: 3, ingredients: &[("minecraft:bamboo_planks", 6)] },
-        Recipe { output: "minecraft:crimson_door", output_count: 3, ingredients: &[("minecraft:crimson_planks", 6)] },
-        Recipe { output: "minecraft:warped_door", output_count: 3, ingredients: &[("minecraft:warped_planks", 6)] },
-        Recipe { output: "minecraft:iron_door", output_count: 3, ingredients: &[("minecraft:iron_ingot", 6)] },
+        Recipe { output: "minecraft:oak_door", output_count: 3, ingredients: &[("minecraft:oak_planks", 6)], method: Crafting },
+        Recipe { output: "minecraft:spruce_door", output_count: 3, ingredients: &[("minecraft:spruce_planks", 6)], method: Crafting },
+        Recipe { output: "minecraft:birch_door", output_count: 3, ingredients: &[("minecraft:birch_planks", 6)], method: Crafting },
+        Recipe { output: "minecraft:jungle_door", output_count: 3, ingredients: &[("minecraft:jungle_planks", 6)], method: Crafting },
+        Recipe { output: "minecraft:acacia_door", output_count: 3, ingredients: &[("minecraft:acacia_planks", 6)], method: Crafting },
+        Recipe { output: "minecraft:dark_oak_door", output_count: 3, ingredients: &[("minecraft:dark_oak_planks", 6)], method: Crafting },
+        Recipe { output: "minecraft:mangrove_door", output_count: 3, ingredients: &[("minecraft:mangrove_planks", 6)], method: Crafting },
+        Recipe { output: "minecraft:cherry_door", output_count: 3, ingredients: &[("minecraft:cherry_planks", 6)], method: Crafting },
+        Recipe { output: "minecraft:bamboo_door", output_count: 3, ingredients: &[("minecraft:bamboo_planks", 6)], method: Crafting },
+        Recipe { output: "minecraft:crimson_door", output_count: 3, ingredients: &[("minecraft:crimson_planks", 6)], method: Crafting },
+        Recipe { output: "minecraft:warped_door", output_count: 3, ingredients: &[("minecraft:warped_planks", 6)], method: Crafting },
+        Recipe { output: "minecraft:iron_door", output_count: 3, ingredients: &[("minecraft:iron_ingot", 6)], method: Crafting },
 
         // === Trapdoors ===
-        Recipe { output: "minecraft:oak_trapdoor", output_count: 2, ingredients: &[("minecraft:oak_planks", 6)] },
-        Recipe { output: "minecraft:spruce_trapdoor", output_count: 2, ingredients: &[("minecraft:spruce_planks", 6)] },
-        Recipe { output: "minecraft:birch_trapdoor", output_count: 2, ingredients: &[("minecraft:birch_planks", 6)] },
-        Recipe { output: "minecraft:jungle_trapdoor", output_count: 2, ingredients: &[("minecraft:jungle_planks", 6)] },
-        Recipe { output: "minecraft:acacia_trapdoor", output_count: 2, ingredients: &[("minecraft:acacia_planks", 6)] },
-        Recipe { output: "minecraft:dark_oak_trapdoor", output_count: 2, ingredients: &[("minecraft:dark_oak_planks", 6)] },
-        Recipe { output: "minecraft:mangrove_trapdoor", output_count: 2, ingredients: &[("minecraft:mangrove_planks", 6)] },
-        Recipe { output: "minecraft:cherry_trapdoor", output_count: 2, ingredients: &[("minecraft:cherry_planks", 6)] },
-        Recipe { output: "minecraft:bamboo_trapdoor", output_count: 2, ingredients: &[("minecraft:bamboo_planks", 6)] },
-        Recipe { output: "minecraft:crimson_trapdoor", output_count: 2, ingredients: &[("minecraft:crimson_planks", 6)] },
-        Recipe { output: "minecraft:warped_trapdoor", output_count: 2, ingredients: &[("minecraft:warped_planks", 6)] },
-        Recipe { output: "minecraft:iron_trapdoor", output_count: 1, ingredients: &[("minecraft:iron_ingot", 4)] },
+        Recipe { output: "minecraft:oak_trapdoor", output_count: 2, ingredients: &[("minecraft:oak_planks", 6)], method: Crafting },
+        Recipe { output: "minecraft:spruce_trapdoor", output_count: 2, ingredients: &[("minecraft:spruce_planks", 6)], method: Crafting },
+        Recipe { output: "minecraft:birch_trapdoor", output_count: 2, ingredients: &[("minecraft:birch_planks", 6)], method: Crafting },
+        Recipe { output: "minecraft:jungle_trapdoor", output_count: 2, ingredients: &[("minecraft:jungle_planks", 6)], method: Crafting },
+        Recipe { output: "minecraft:acacia_trapdoor", output_count: 2, ingredients: &[("minecraft:acacia_planks", 6)], method: Crafting },
+        Recipe { output: "minecraft:dark_oak_trapdoor", output_count: 2, ingredients: &[("minecraft:dark_oak_planks", 6)], method: Crafting },
+        Recipe { output: "minecraft:mangrove_trapdoor", output_count: 2, ingredients: &[("minecraft:mangrove_planks", 6)], method: Crafting },
+        Recipe { output: "minecraft:cherry_trapdoor", output_count: 2, ingredients: &[("minecraft:cherry_planks", 6)], method: Crafting },
+        Recipe { output: "minecraft:bamboo_trapdoor", output_count: 2, ingredients: &[("minecraft:bamboo_planks", 6)], method: Crafting },
+        Recipe { output: "minecraft:crimson_trapdoor", output_count: 2, ingredients: &[("minecraft:crimson_planks", 6)], method: Crafting },
+        Recipe { output: "minecraft:warped_trapdoor", output_count: 2, ingredients: &[("minecraft:warped_planks", 6)], method: Crafting },
+        Recipe { output: "minecraft:iron_trapdoor", output_count: 1, ingredients: &[("minecraft:iron_ingot", 4)], method: Crafting },
 
         // === Pressure plates ===
-        Recipe { output: "minecraft:oak_pressure_plate", output_count: 1, ingredients: &[("minecraft:oak_planks", 2)] },
-        Recipe { output: "minecraft:spruce_pressure_plate", output_count: 1, ingredients: &[("minecraft:spruce_planks", 2)] },
-        Recipe { output: "minecraft:birch_pressure_plate", output_count: 1, ingredients: &[("minecraft:birch_planks", 2)] },
-        Recipe { output: "minecraft:jungle_pressure_plate", output_count: 1, ingredients: &[("minecraft:jungle_planks", 2)] },
-        Recipe { output: "minecraft:acacia_pressure_plate", output_count: 1, ingredients: &[("minecraft:acacia_planks", 2)] },
-        Recipe { output: "minecraft:dark_oak_pressure_plate", output_count: 1, ingredients: &[("minecraft:dark_oak_planks", 2)] },
-        Recipe { output: "minecraft:mangrove_pressure_plate", output_count: 1, ingredients: &[("minecraft:mangrove_planks", 2)] },
-        Recipe { output: "minecraft:cherry_pressure_plate", output_count: 1, ingredients: &[("minecraft:cherry_planks", 2)] },
-        Recipe { output: "minecraft:bamboo_pressure_plate", output_count: 1, ingredients: &[("minecraft:bamboo_planks", 2)] },
-        Recipe { output: "minecraft:crimson_pressure_plate", output_count: 1, ingredients: &[("minecraft:crimson_planks", 2)] },
-        Recipe { output: "minecraft:warped_pressure_plate", output_count: 1, ingredients: &[("minecraft:warped_planks", 2)] },
-        Recipe { output: "minecraft:stone_pressure_plate", output_count: 1, ingredients: &[("minecraft:stone", 2)] },
-        Recipe { output: "minecraft:polished_blackstone_pressure_plate", output_count: 1, ingredients: &[("minecraft:polished_blackstone", 2)] },
-        Recipe { output: "minecraft:heavy_weighted_pressure_plate", output_count: 1, ingredients: &[("minecraft:iron_ingot", 2)] },
-        Recipe { output: "minecraft:light_weighted_pressure_plate", output_count: 1, ingredients: &[("minecraft:gold_ingot", 2)] },
+        Recipe { output: "minecraft:oak_pressure_plate", output_count: 1, ingredients: &[("minecraft:oak_planks", 2)], method: Crafting },
+        Recipe { output: "minecraft:spruce_pressure_plate", output_count: 1, ingredients: &[("minecraft:spruce_planks", 2)], method: Crafting },
+        Recipe { output: "minecraft:birch_pressure_plate", output_count: 1, ingredients: &[("minecraft:birch_planks", 2)], method: Crafting },
+        Recipe { output: "minecraft:jungle_pressure_plate", output_count: 1, ingredients: &[("minecraft:jungle_planks", 2)], method: Crafting },
+        Recipe { output: "minecraft:acacia_pressure_plate", output_count: 1, ingredients: &[("minecraft:acacia_planks", 2)], method: Crafting },
+        Recipe { output: "minecraft:dark_oak_pressure_plate", output_count: 1, ingredients: &[("minecraft:dark_oak_planks", 2)], method: Crafting },
+        Recipe { output: "minecraft:mangrove_pressure_plate", output_count: 1, ingredients: &[("minecraft:mangrove_planks", 2)], method: Crafting },
+        Recipe { output: "minecraft:cherry_pressure_plate", output_count: 1, ingredients: &[("minecraft:cherry_planks", 2)], method: Crafting },
+        Recipe { output: "minecraft:bamboo_pressure_plate", output_count: 1, ingredients: &[("minecraft:bamboo_planks", 2)], method: Crafting },
+        Recipe { output: "minecraft:crimson_pressure_plate", output_count: 1, ingredients: &[("minecraft:crimson_planks", 2)], method: Crafting },
+        Recipe { output: "minecraft:warped_pressure_plate", output_count: 1, ingredients: &[("minecraft:warped_planks", 2)], method: Crafting },
+        Recipe { output: "minecraft:stone_pressure_plate", output_count: 1, ingredients: &[("minecraft:stone", 2)], method: Crafting },
+        Recipe { output: "minecraft:polished_blackstone_pressure_plate", output_count: 1, ingredients: &[("minecraft:polished_blackstone", 2)], method: Crafting },
+        Recipe { output: "minecraft:heavy_weighted_pressure_plate", output_count: 1, ingredients: &[("minecraft:iron_ingot", 2)], method: Crafting },
+        Recipe { output: "minecraft:light_weighted_pressure_plate", output_count: 1, ingredients: &[("minecraft:gold_ingot", 2)], method: Crafting },
 
         // === Buttons ===
-        Recipe { output: "minecraft:oak_button", output_count: 1, ingredients: &[("minecraft:oak_planks", 1)] },
-        Recipe { output: "minecraft:spruce_button", output_count: 1, ingredients: &[("minecraft:spruce_planks", 1)] },
-        Recipe { output: "minecraft:birch_button", output_count: 1, ingredients: &[("minecraft:birch_planks", 1)] },
-        Recipe { output: "minecraft:jungle_button", output_count: 1, ingredients: &[("minecraft:jungle_planks", 1)] },
-        Recipe { output: "minecraft:acacia_button", output_count: 1, ingredients: &[("minecraft:acacia_planks", 1)] },
-        Recipe { output: "minecraft:dark_oak_button", output_count: 1, ingredients: &[("minecraft:dark_oak_planks", 1)] },
-        Recipe { output: "minecraft:mangrove_button", output_count: 1, ingredients: &[("minecraft:mangrove_planks", 1)] },
-        Recipe { output: "minecraft:cherry_button", output_count: 1, ingredients: &[("minecraft:cherry_planks", 1)] },
-        Recipe { output: "minecraft:bamboo_button", output_count: 1, ingredients: &[("minecraft:bamboo_planks", 1)] },
-        Recipe { output: "minecraft:crimson_button", output_count: 1, ingredients: &[("minecraft:crimson_planks", 1)] },
-        Recipe { output: "minecraft:warped_button", output_count: 1, ingredients: &[("minecraft:warped_planks", 1)] },
-        Recipe { output: "minecraft:stone_button", output_count: 1, ingredients: &[("minecraft:stone", 1)] },
-        Recipe { output: "minecraft:polished_blackstone_button", output_count: 1, ingredients: &[("minecraft:polished_blackstone", 1)] },
+        Recipe { output: "minecraft:oak_button", output_count: 1, ingredients: &[("minecraft:oak_planks", 1)], method: Crafting },
+        Recipe { output: "minecraft:spruce_button", output_count: 1, ingredients: &[("minecraft:spruce_planks", 1)], method: Crafting },
+        Recipe { output: "minecraft:birch_button", output_count: 1, ingredients: &[("minecraft:birch_planks", 1)], method: Crafting },
+        Recipe { output: "minecraft:jungle_button", output_count: 1, ingredients: &[("minecraft:jungle_planks", 1)], method: Crafting },
+        Recipe { output: "minecraft:acacia_button", output_count: 1, ingredients: &[("minecraft:acacia_planks", 1)], method: Crafting },
+        Recipe { output: "minecraft:dark_oak_button", output_count: 1, ingredients: &[("minecraft:dark_oak_planks", 1)], method: Crafting },
+        Recipe { output: "minecraft:mangrove_button", output_count: 1, ingredients: &[("minecraft:mangrove_planks", 1)], method: Crafting },
+        Recipe { output: "minecraft:cherry_button", output_count: 1, ingredients: &[("minecraft:cherry_planks", 1)], method: Crafting },
+        Recipe { output: "minecraft:bamboo_button", output_count: 1, ingredients: &[("minecraft:bamboo_planks", 1)], method: Crafting },
+        Recipe { output: "minecraft:crimson_button", output_count: 1, ingredients: &[("minecraft:crimson_planks", 1)], method: Crafting },
+        Recipe { output: "minecraft:warped_button", output_count: 1, ingredients: &[("minecraft:warped_planks", 1)], method: Crafting },
+        Recipe { output: "minecraft:stone_button", output_count: 1, ingredients: &[("minecraft:stone", 1)], method: Crafting },
+        Recipe { output: "minecraft:polished_blackstone_button", output_count: 1, ingredients: &[("minecraft:polished_blackstone", 1)], method: Crafting },
 
         // === Signs ===
-        Recipe { output: "minecraft:oak_sign", output_count: 3, ingredients: &[("minecraft:oak_planks", 6), ("minecraft:stick", 1)] },
-        Recipe { output: "minecraft:spruce_sign", output_count: 3, ingredients: &[("minecraft:spruce_planks", 6), ("minecraft:stick", 1)] },
-        Recipe { output: "minecraft:birch_sign", output_count: 3, ingredients: &[("minecraft:birch_planks", 6), ("minecraft:stick", 1)] },
-        Recipe { output: "minecraft:jungle_sign", output_count: 3, ingredients: &[("minecraft:jungle_planks", 6), ("minecraft:stick", 1)] },
-        Recipe { output: "minecraft:acacia_sign", output_count: 3, ingredients: &[("minecraft:acacia_planks", 6), ("minecraft:stick", 1)] },
-        Recipe { output: "minecraft:dark_oak_sign", output_count: 3, ingredients: &[("minecraft:dark_oak_planks", 6), ("minecraft:stick", 1)] },
-        Recipe { output: "minecraft:mangrove_sign", output_count: 3, ingredients: &[("minecraft:mangrove_planks", 6), ("minecraft:stick", 1)] },
-        Recipe { output: "minecraft:cherry_sign", output_count: 3, ingredients: &[("minecraft:cherry_planks", 6), ("minecraft:stick", 1)] },
-        Recipe { output: "minecraft:bamboo_sign", output_count: 3, ingredients: &[("minecraft:bamboo_planks", 6), ("minecraft:stick", 1)] },
-        Recipe { output: "minecraft:crimson_sign", output_count: 3, ingredients: &[("minecraft:crimson_planks", 6), ("minecraft:stick", 1)] },
-        Recipe { output: "minecraft:warped_sign", output_count: 3, ingredients: &[("minecraft:warped_planks", 6), ("minecraft:stick", 1)] },
+        Recipe { output: "minecraft:oak_sign", output_count: 3, ingredients: &[("minecraft:oak_planks", 6), ("minecraft:stick", 1)], method: Crafting },
+        Recipe { output: "minecraft:spruce_sign", output_count: 3, ingredients: &[("minecraft:spruce_planks", 6), ("minecraft:stick", 1)], method: Crafting },
+        Recipe { output: "minecraft:birch_sign", output_count: 3, ingredients: &[("minecraft:birch_planks", 6), ("minecraft:stick", 1)], method: Crafting },
+        Recipe { output: "minecraft:jungle_sign", output_count: 3, ingredients: &[("minecraft:jungle_planks", 6), ("minecraft:stick", 1)], method: Crafting },
+        Recipe { output: "minecraft:acacia_sign", output_count: 3, ingredients: &[("minecraft:acacia_planks", 6), ("minecraft:stick", 1)], method: Crafting },
+        Recipe { output: "minecraft:dark_oak_sign", output_count: 3, ingredients: &[("minecraft:dark_oak_planks", 6), ("minecraft:stick", 1)], method: Crafting },
+        Recipe { output: "minecraft:mangrove_sign", output_count: 3, ingredients: &[("minecraft:mangrove_planks", 6), ("minecraft:stick", 1)], method: Crafting },
+        Recipe { output: "minecraft:cherry_sign", output_count: 3, ingredients: &[("minecraft:cherry_planks", 6), ("minecraft:stick", 1)], method: Crafting },
+        Recipe { output: "minecraft:bamboo_sign", output_count: 3, ingredients: &[("minecraft:bamboo_planks", 6), ("minecraft:stick", 1)], method: Crafting },
+        Recipe { output: "minecraft:crimson_sign", output_count: 3, ingredients: &[("minecraft:crimson_planks", 6), ("minecraft:stick", 1)], method: Crafting },
+        Recipe { output: "minecraft:warped_sign", output_count: 3, ingredients: &[("minecraft:warped_planks", 6), ("minecraft:stick", 1)], method: Crafting },
 
         // === Stone products ===
         Recipe {
             output: "minecraft:stone_bricks",
             output_count: 4,
             ingredients: &[("minecraft:stone", 4)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:stone_brick_stairs",
             output_count: 4,
             ingredients: &[("minecraft:stone_bricks", 6)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:stone_brick_slab",
             output_count: 6,
             ingredients: &[("minecraft:stone_bricks", 3)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:cobblestone_stairs",
             output_count: 4,
             ingredients: &[("minecraft:cobblestone", 6)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:cobblestone_slab",
             output_count: 6,
             ingredients: &[("minecraft:cobblestone", 3)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:cobblestone_wall",
             output_count: 6,
             ingredients: &[("minecraft:cobblestone", 6)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:smooth_stone",
             output_count: 1,
-            ingredients: &[("minecraft:stone", 1)], // smelting
+            ingredients: &[("minecraft:stone", 1)],
+            method: Smelting,
         },
         Recipe {
             output: "minecraft:smooth_stone_slab",
             output_count: 6,
             ingredients: &[("minecraft:smooth_stone", 3)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:stone",
             output_count: 1,
-            ingredients: &[("minecraft:cobblestone", 1)], // smelting
+            ingredients: &[("minecraft:cobblestone", 1)],
+            method: Smelting,
         },
         Recipe {
             output: "minecraft:bricks",
             output_count: 1,
             ingredients: &[("minecraft:brick", 4)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:brick",
             output_count: 1,
-            ingredients: &[("minecraft:clay_ball", 1)], // smelting
+            ingredients: &[("minecraft:clay_ball", 1)],
+            method: Smelting,
         },
         Recipe {
             output: "minecraft:brick_stairs",
             output_count: 4,
             ingredients: &[("minecraft:bricks", 6)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:brick_slab",
             output_count: 6,
             ingredients: &[("minecraft:bricks", 3)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:brick_wall",
             output_count: 6,
             ingredients: &[("minecraft:bricks", 6)],
+            method: Crafting,
         },
         // Cracked stone bricks (smelting)
         Recipe {
             output: "minecraft:cracked_stone_bricks",
             output_count: 1,
             ingredients: &[("minecraft:stone_bricks", 1)],
+            method: Smelting,
         },
         Recipe {
             output: "minecraft:mossy_stone_bricks",
             output_count: 1,
             ingredients: &[("minecraft:stone_bricks", 1), ("minecraft:vine", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:mossy_cobblestone",
             output_count: 1,
             ingredients: &[("minecraft:cobblestone", 1), ("minecraft:vine", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:stone_brick_wall",
             output_count: 6,
             ingredients: &[("minecraft:stone_bricks", 6)],
+            method: Crafting,
+        },
+
+        // === Granite, diorite, andesite ===
+        Recipe {
+            output: "minecraft:polished_granite",
+            output_count: 4,
+            ingredients: &[("minecraft:granite", 4)],
+            method: Crafting,
+        },
+        Recipe {
+            output: "minecraft:polished_diorite",
+            output_count: 4,
+            ingredients: &[("minecraft:diorite", 4)],
+            method: Crafting,
+        },
+        Recipe {
+            output: "minecraft:polished_andesite",
+            output_count: 4,
+            ingredients: &[("minecraft:andesite", 4)],
+            method: Crafting,
         },
 
         // === Deepslate ===
@@ -303,93 +372,111 @@ pub fn get_recipes() -> HashMap<&'static str, Recipe> {
             output: "minecraft:polished_deepslate",
             output_count: 4,
             ingredients: &[("minecraft:cobbled_deepslate", 4)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:deepslate_bricks",
             output_count: 4,
             ingredients: &[("minecraft:polished_deepslate", 4)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:deepslate_tiles",
             output_count: 4,
             ingredients: &[("minecraft:deepslate_bricks", 4)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:chiseled_deepslate",
             output_count: 1,
             ingredients: &[("minecraft:cobbled_deepslate", 2)], // via slabs
+            method: Crafting,
         },
         // Cracked variants (smelting)
         Recipe {
             output: "minecraft:cracked_deepslate_bricks",
             output_count: 1,
             ingredients: &[("minecraft:deepslate_bricks", 1)],
+            method: Smelting,
         },
         Recipe {
             output: "minecraft:cracked_deepslate_tiles",
             output_count: 1,
             ingredients: &[("minecraft:deepslate_tiles", 1)],
+            method: Smelting,
         },
         // Deepslate stairs and slabs
         Recipe {
             output: "minecraft:cobbled_deepslate_stairs",
             output_count: 4,
             ingredients: &[("minecraft:cobbled_deepslate", 6)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:cobbled_deepslate_slab",
             output_count: 6,
             ingredients: &[("minecraft:cobbled_deepslate", 3)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:cobbled_deepslate_wall",
             output_count: 6,
             ingredients: &[("minecraft:cobbled_deepslate", 6)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:polished_deepslate_stairs",
             output_count: 4,
             ingredients: &[("minecraft:polished_deepslate", 6)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:polished_deepslate_slab",
             output_count: 6,
             ingredients: &[("minecraft:polished_deepslate", 3)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:polished_deepslate_wall",
             output_count: 6,
             ingredients: &[("minecraft:polished_deepslate", 6)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:deepslate_brick_stairs",
             output_count: 4,
             ingredients: &[("minecraft:deepslate_bricks", 6)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:deepslate_brick_slab",
             output_count: 6,
             ingredients: &[("minecraft:deepslate_bricks", 3)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:deepslate_brick_wall",
             output_count: 6,
             ingredients: &[("minecraft:deepslate_bricks", 6)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:deepslate_tile_stairs",
             output_count: 4,
             ingredients: &[("minecraft:deepslate_tiles", 6)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:deepslate_tile_slab",
             output_count: 6,
             ingredients: &[("minecraft:deepslate_tiles", 3)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:deepslate_tile_wall",
             output_count: 6,
             ingredients: &[("minecraft:deepslate_tiles", 6)],
+            method: Crafting,
         },
 
         // === Blackstone ===
@@ -397,68 +484,81 @@ pub fn get_recipes() -> HashMap<&'static str, Recipe> {
             output: "minecraft:polished_blackstone",
             output_count: 4,
             ingredients: &[("minecraft:blackstone", 4)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:polished_blackstone_bricks",
             output_count: 4,
             ingredients: &[("minecraft:polished_blackstone", 4)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:chiseled_polished_blackstone",
             output_count: 1,
             ingredients: &[("minecraft:blackstone", 2)], // via slabs
+            method: Crafting,
         },
         // Cracked blackstone (smelting)
         Recipe {
             output: "minecraft:cracked_polished_blackstone_bricks",
             output_count: 1,
             ingredients: &[("minecraft:polished_blackstone_bricks", 1)],
+            method: Smelting,
         },
         // Blackstone stairs and slabs
         Recipe {
             output: "minecraft:blackstone_stairs",
             output_count: 4,
             ingredients: &[("minecraft:blackstone", 6)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:blackstone_slab",
             output_count: 6,
             ingredients: &[("minecraft:blackstone", 3)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:blackstone_wall",
             output_count: 6,
             ingredients: &[("minecraft:blackstone", 6)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:polished_blackstone_stairs",
             output_count: 4,
             ingredients: &[("minecraft:polished_blackstone", 6)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:polished_blackstone_slab",
             output_count: 6,
             ingredients: &[("minecraft:polished_blackstone", 3)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:polished_blackstone_wall",
             output_count: 6,
             ingredients: &[("minecraft:polished_blackstone", 6)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:polished_blackstone_brick_stairs",
             output_count: 4,
             ingredients: &[("minecraft:polished_blackstone_bricks", 6)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:polished_blackstone_brick_slab",
             output_count: 6,
             ingredients: &[("minecraft:polished_blackstone_bricks", 3)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:polished_blackstone_brick_wall",
             output_count: 6,
             ingredients: &[("minecraft:polished_blackstone_bricks", 6)],
+            method: Crafting,
         },
 
         // === Nether ===
@@ -466,76 +566,91 @@ pub fn get_recipes() -> HashMap<&'static str, Recipe> {
             output: "minecraft:nether_bricks",
             output_count: 1,
             ingredients: &[("minecraft:nether_brick", 4)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:nether_brick",
             output_count: 1,
-            ingredients: &[("minecraft:netherrack", 1)], // smelting
+            ingredients: &[("minecraft:netherrack", 1)],
+            method: Smelting,
         },
         Recipe {
             output: "minecraft:red_nether_bricks",
             output_count: 1,
             ingredients: &[("minecraft:nether_brick", 2), ("minecraft:nether_wart", 2)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:cracked_nether_bricks",
             output_count: 1,
-            ingredients: &[("minecraft:nether_bricks", 1)], // smelting
+            ingredients: &[("minecraft:nether_bricks", 1)],
+            method: Smelting,
         },
         Recipe {
             output: "minecraft:chiseled_nether_bricks",
             output_count: 1,
             ingredients: &[("minecraft:nether_bricks", 2)], // via slabs
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:nether_brick_stairs",
             output_count: 4,
             ingredients: &[("minecraft:nether_bricks", 6)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:nether_brick_slab",
             output_count: 6,
             ingredients: &[("minecraft:nether_bricks", 3)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:nether_brick_wall",
             output_count: 6,
             ingredients: &[("minecraft:nether_bricks", 6)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:nether_brick_fence",
             output_count: 6,
             ingredients: &[("minecraft:nether_bricks", 4), ("minecraft:nether_brick", 2)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:red_nether_brick_stairs",
             output_count: 4,
             ingredients: &[("minecraft:red_nether_bricks", 6)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:red_nether_brick_slab",
             output_count: 6,
             ingredients: &[("minecraft:red_nether_bricks", 3)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:red_nether_brick_wall",
             output_count: 6,
             ingredients: &[("minecraft:red_nether_bricks", 6)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:quartz_block",
             output_count: 1,
             ingredients: &[("minecraft:quartz", 4)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:quartz_bricks",
             output_count: 1,
             ingredients: &[("minecraft:quartz_block", 4)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:smooth_quartz",
             output_count: 1,
-            ingredients: &[("minecraft:quartz_block", 1)], // smelting
+            ingredients: &[("minecraft:quartz_block", 1)],
+            method: Smelting,
         },
 
         // === Metal blocks ===
@@ -543,159 +658,190 @@ pub fn get_recipes() -> HashMap<&'static str, Recipe> {
             output: "minecraft:iron_block",
             output_count: 1,
             ingredients: &[("minecraft:iron_ingot", 9)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:gold_block",
             output_count: 1,
             ingredients: &[("minecraft:gold_ingot", 9)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:diamond_block",
             output_count: 1,
             ingredients: &[("minecraft:diamond", 9)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:emerald_block",
             output_count: 1,
             ingredients: &[("minecraft:emerald", 9)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:lapis_block",
             output_count: 1,
             ingredients: &[("minecraft:lapis_lazuli", 9)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:redstone_block",
             output_count: 1,
             ingredients: &[("minecraft:redstone", 9)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:coal_block",
             output_count: 1,
             ingredients: &[("minecraft:coal", 9)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:copper_block",
             output_count: 1,
             ingredients: &[("minecraft:copper_ingot", 9)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:netherite_block",
             output_count: 1,
             ingredients: &[("minecraft:netherite_ingot", 9)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:netherite_ingot",
             output_count: 1,
             ingredients: &[("minecraft:netherite_scrap", 4), ("minecraft:gold_ingot", 4)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:raw_iron_block",
             output_count: 1,
             ingredients: &[("minecraft:raw_iron", 9)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:raw_gold_block",
             output_count: 1,
             ingredients: &[("minecraft:raw_gold", 9)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:raw_copper_block",
             output_count: 1,
             ingredients: &[("minecraft:raw_copper", 9)],
+            method: Crafting,
         },
 
         // === Glass ===
         Recipe {
             output: "minecraft:glass",
             output_count: 1,
-            ingredients: &[("minecraft:sand", 1)], // smelting
+            ingredients: &[("minecraft:sand", 1)],
+            method: Smelting,
         },
         Recipe {
             output: "minecraft:glass_pane",
             output_count: 16,
             ingredients: &[("minecraft:glass", 6)],
+            method: Crafting,
         },
         // Stained glass
         Recipe {
             output: "minecraft:white_stained_glass",
             output_count: 8,
             ingredients: &[("minecraft:glass", 8), ("minecraft:white_dye", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:red_stained_glass",
             output_count: 8,
             ingredients: &[("minecraft:glass", 8), ("minecraft:red_dye", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:black_stained_glass",
             output_count: 8,
             ingredients: &[("minecraft:glass", 8), ("minecraft:black_dye", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:blue_stained_glass",
             output_count: 8,
             ingredients: &[("minecraft:glass", 8), ("minecraft:blue_dye", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:green_stained_glass",
             output_count: 8,
             ingredients: &[("minecraft:glass", 8), ("minecraft:green_dye", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:yellow_stained_glass",
             output_count: 8,
             ingredients: &[("minecraft:glass", 8), ("minecraft:yellow_dye", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:orange_stained_glass",
             output_count: 8,
             ingredients: &[("minecraft:glass", 8), ("minecraft:orange_dye", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:purple_stained_glass",
             output_count: 8,
             ingredients: &[("minecraft:glass", 8), ("minecraft:purple_dye", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:cyan_stained_glass",
             output_count: 8,
             ingredients: &[("minecraft:glass", 8), ("minecraft:cyan_dye", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:pink_stained_glass",
             output_count: 8,
             ingredients: &[("minecraft:glass", 8), ("minecraft:pink_dye", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:gray_stained_glass",
             output_count: 8,
             ingredients: &[("minecraft:glass", 8), ("minecraft:gray_dye", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:light_gray_stained_glass",
             output_count: 8,
             ingredients: &[("minecraft:glass", 8), ("minecraft:light_gray_dye", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:light_blue_stained_glass",
             output_count: 8,
             ingredients: &[("minecraft:glass", 8), ("minecraft:light_blue_dye", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:lime_stained_glass",
             output_count: 8,
             ingredients: &[("minecraft:glass", 8), ("minecraft:lime_dye", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:magenta_stained_glass",
             output_count: 8,
             ingredients: &[("minecraft:glass", 8), ("minecraft:magenta_dye", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:brown_stained_glass",
             output_count: 8,
             ingredients: &[("minecraft:glass", 8), ("minecraft:brown_dye", 1)],
+            method: Crafting,
         },
 
         // === Concrete ===
@@ -703,42 +849,50 @@ pub fn get_recipes() -> HashMap<&'static str, Recipe> {
             output: "minecraft:white_concrete_powder",
             output_count: 8,
             ingredients: &[("minecraft:sand", 4), ("minecraft:gravel", 4), ("minecraft:white_dye", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:red_concrete_powder",
             output_count: 8,
             ingredients: &[("minecraft:sand", 4), ("minecraft:gravel", 4), ("minecraft:red_dye", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:black_concrete_powder",
             output_count: 8,
             ingredients: &[("minecraft:sand", 4), ("minecraft:gravel", 4), ("minecraft:black_dye", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:gray_concrete_powder",
             output_count: 8,
             ingredients: &[("minecraft:sand", 4), ("minecraft:gravel", 4), ("minecraft:gray_dye", 1)],
+            method: Crafting,
         },
         // Concrete (from powder + water, 1:1)
         Recipe {
             output: "minecraft:white_concrete",
             output_count: 1,
             ingredients: &[("minecraft:white_concrete_powder", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:red_concrete",
             output_count: 1,
             ingredients: &[("minecraft:red_concrete_powder", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:black_concrete",
             output_count: 1,
             ingredients: &[("minecraft:black_concrete_powder", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:gray_concrete",
             output_count: 1,
             ingredients: &[("minecraft:gray_concrete_powder", 1)],
+            method: Crafting,
         },
 
         // === Wool ===
@@ -746,48 +900,57 @@ pub fn get_recipes() -> HashMap<&'static str, Recipe> {
             output: "minecraft:white_wool",
             output_count: 1,
             ingredients: &[("minecraft:string", 4)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:red_wool",
             output_count: 1,
             ingredients: &[("minecraft:white_wool", 1), ("minecraft:red_dye", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:black_wool",
             output_count: 1,
             ingredients: &[("minecraft:white_wool", 1), ("minecraft:black_dye", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:gray_wool",
             output_count: 1,
             ingredients: &[("minecraft:white_wool", 1), ("minecraft:gray_dye", 1)],
+            method: Crafting,
         },
 
         // === Terracotta ===
         Recipe {
             output: "minecraft:terracotta",
             output_count: 1,
-            ingredients: &[("minecraft:clay", 1)], // smelting clay block
+            ingredients: &[("minecraft:clay", 1)],
+            method: Smelting,
         },
         Recipe {
             output: "minecraft:white_terracotta",
             output_count: 8,
             ingredients: &[("minecraft:terracotta", 8), ("minecraft:white_dye", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:red_terracotta",
             output_count: 8,
             ingredients: &[("minecraft:terracotta", 8), ("minecraft:red_dye", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:orange_terracotta",
             output_count: 8,
             ingredients: &[("minecraft:terracotta", 8), ("minecraft:orange_dye", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:black_terracotta",
             output_count: 8,
             ingredients: &[("minecraft:terracotta", 8), ("minecraft:black_dye", 1)],
+            method: Crafting,
         },
 
         // === Sandstone ===
@@ -795,26 +958,31 @@ pub fn get_recipes() -> HashMap<&'static str, Recipe> {
             output: "minecraft:sandstone",
             output_count: 1,
             ingredients: &[("minecraft:sand", 4)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:smooth_sandstone",
             output_count: 1,
-            ingredients: &[("minecraft:sandstone", 1)], // smelting
+            ingredients: &[("minecraft:sandstone", 1)],
+            method: Smelting,
         },
         Recipe {
             output: "minecraft:cut_sandstone",
             output_count: 4,
             ingredients: &[("minecraft:sandstone", 4)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:red_sandstone",
             output_count: 1,
             ingredients: &[("minecraft:red_sand", 4)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:smooth_red_sandstone",
             output_count: 1,
-            ingredients: &[("minecraft:red_sandstone", 1)], // smelting
+            ingredients: &[("minecraft:red_sandstone", 1)],
+            method: Smelting,
         },
 
         // === Prismarine ===
@@ -822,21 +990,25 @@ pub fn get_recipes() -> HashMap<&'static str, Recipe> {
             output: "minecraft:prismarine",
             output_count: 1,
             ingredients: &[("minecraft:prismarine_shard", 4)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:prismarine_bricks",
             output_count: 1,
             ingredients: &[("minecraft:prismarine_shard", 9)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:dark_prismarine",
             output_count: 1,
             ingredients: &[("minecraft:prismarine_shard", 8), ("minecraft:black_dye", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:sea_lantern",
             output_count: 1,
             ingredients: &[("minecraft:prismarine_shard", 4), ("minecraft:prismarine_crystals", 5)],
+            method: Crafting,
         },
 
         // === End stone ===
@@ -844,16 +1016,19 @@ pub fn get_recipes() -> HashMap<&'static str, Recipe> {
             output: "minecraft:end_stone_bricks",
             output_count: 4,
             ingredients: &[("minecraft:end_stone", 4)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:purpur_block",
             output_count: 4,
             ingredients: &[("minecraft:popped_chorus_fruit", 4)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:purpur_pillar",
             output_count: 1,
             ingredients: &[("minecraft:purpur_block", 2)], // via slabs
+            method: Crafting,
         },
 
         // === Misc ===
@@ -861,71 +1036,91 @@ pub fn get_recipes() -> HashMap<&'static str, Recipe> {
             output: "minecraft:bookshelf",
             output_count: 1,
             ingredients: &[("minecraft:any_planks", 6), ("minecraft:book", 3)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:book",
             output_count: 1,
             ingredients: &[("minecraft:paper", 3), ("minecraft:leather", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:paper",
             output_count: 3,
             ingredients: &[("minecraft:sugar_cane", 3)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:hay_block",
             output_count: 1,
             ingredients: &[("minecraft:wheat", 9)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:bone_block",
             output_count: 1,
             ingredients: &[("minecraft:bone_meal", 9)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:slime_block",
             output_count: 1,
             ingredients: &[("minecraft:slime_ball", 9)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:honey_block",
             output_count: 1,
             ingredients: &[("minecraft:honey_bottle", 4)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:packed_ice",
             output_count: 1,
             ingredients: &[("minecraft:ice", 9)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:blue_ice",
             output_count: 1,
             ingredients: &[("minecraft:packed_ice", 9)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:snow_block",
             output_count: 1,
             ingredients: &[("minecraft:snowball", 4)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:glowstone",
             output_count: 1,
             ingredients: &[("minecraft:glowstone_dust", 4)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:tnt",
             output_count: 1,
             ingredients: &[("minecraft:gunpowder", 5), ("minecraft:sand", 4)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:melon",
             output_count: 1,
             ingredients: &[("minecraft:melon_slice", 9)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:dried_kelp_block",
             output_count: 1,
             ingredients: &[("minecraft:dried_kelp", 9)],
+            method: Crafting,
+        },
+        Recipe {
+            output: "minecraft:dried_kelp",
+            output_count: 1,
+            ingredients: &[("minecraft:kelp", 1)],
+            method: Smelting,
         },
 
         // === Mud and clay ===
@@ -933,16 +1128,19 @@ pub fn get_recipes() -> HashMap<&'static str, Recipe> {
             output: "minecraft:packed_mud",
             output_count: 1,
             ingredients: &[("minecraft:mud", 1), ("minecraft:wheat", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:mud_bricks",
             output_count: 4,
             ingredients: &[("minecraft:packed_mud", 4)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:clay",
             output_count: 1,
             ingredients: &[("minecraft:clay_ball", 4)],
+            method: Crafting,
         },
 
         // === Tuff ===
@@ -950,11 +1148,13 @@ pub fn get_recipes() -> HashMap<&'static str, Recipe> {
             output: "minecraft:polished_tuff",
             output_count: 4,
             ingredients: &[("minecraft:tuff", 4)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:tuff_bricks",
             output_count: 4,
             ingredients: &[("minecraft:polished_tuff", 4)],
+            method: Crafting,
         },
 
         // === Copper variants ===
@@ -962,23 +1162,34 @@ pub fn get_recipes() -> HashMap<&'static str, Recipe> {
             output: "minecraft:cut_copper",
             output_count: 4,
             ingredients: &[("minecraft:copper_block", 4)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:cut_copper_stairs",
             output_count: 4,
             ingredients: &[("minecraft:cut_copper", 6)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:cut_copper_slab",
             output_count: 6,
             ingredients: &[("minecraft:cut_copper", 3)],
+            method: Crafting,
         },
+        Recipe { output: "minecraft:exposed_cut_copper", output_count: 4, ingredients: &[("minecraft:exposed_copper", 4)], method: Crafting },
+        Recipe { output: "minecraft:weathered_cut_copper", output_count: 4, ingredients: &[("minecraft:weathered_copper", 4)], method: Crafting },
+        Recipe { output: "minecraft:oxidized_cut_copper", output_count: 4, ingredients: &[("minecraft:oxidized_copper", 4)], method: Crafting },
+        Recipe { output: "minecraft:waxed_cut_copper", output_count: 1, ingredients: &[("minecraft:cut_copper", 1), ("minecraft:honeycomb", 1)], method: Crafting },
+        Recipe { output: "minecraft:waxed_exposed_cut_copper", output_count: 1, ingredients: &[("minecraft:exposed_cut_copper", 1), ("minecraft:honeycomb", 1)], method: Crafting },
+        Recipe { output: "minecraft:waxed_weathered_cut_copper", output_count: 1, ingredients: &[("minecraft:weathered_cut_copper", 1), ("minecraft:honeycomb", 1)], method: Crafting },
+        Recipe { output: "minecraft:waxed_oxidized_cut_copper", output_count: 1, ingredients: &[("minecraft:oxidized_cut_copper", 1), ("minecraft:honeycomb", 1)], method: Crafting },
 
         // === Amethyst ===
         Recipe {
             output: "minecraft:amethyst_block",
             output_count: 1,
             ingredients: &[("minecraft:amethyst_shard", 4)],
+            method: Crafting,
         },
 
         // === Calcite - natural only, no crafting ===
@@ -989,12 +1200,14 @@ pub fn get_recipes() -> HashMap<&'static str, Recipe> {
         Recipe {
             output: "minecraft:smooth_basalt",
             output_count: 1,
-            ingredients: &[("minecraft:basalt", 1)], // smelting
+            ingredients: &[("minecraft:basalt", 1)],
+            method: Smelting,
         },
         Recipe {
             output: "minecraft:polished_basalt",
             output_count: 4,
             ingredients: &[("minecraft:basalt", 4)],
+            method: Crafting,
         },
 
         // === Redstone components ===
@@ -1002,56 +1215,67 @@ pub fn get_recipes() -> HashMap<&'static str, Recipe> {
             output: "minecraft:redstone_lamp",
             output_count: 1,
             ingredients: &[("minecraft:redstone", 4), ("minecraft:glowstone", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:observer",
             output_count: 1,
             ingredients: &[("minecraft:cobblestone", 6), ("minecraft:redstone", 2), ("minecraft:quartz", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:piston",
             output_count: 1,
             ingredients: &[("minecraft:any_planks", 3), ("minecraft:cobblestone", 4), ("minecraft:iron_ingot", 1), ("minecraft:redstone", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:sticky_piston",
             output_count: 1,
             ingredients: &[("minecraft:piston", 1), ("minecraft:slime_ball", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:dispenser",
             output_count: 1,
             ingredients: &[("minecraft:cobblestone", 7), ("minecraft:bow", 1), ("minecraft:redstone", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:dropper",
             output_count: 1,
             ingredients: &[("minecraft:cobblestone", 7), ("minecraft:redstone", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:hopper",
             output_count: 1,
             ingredients: &[("minecraft:iron_ingot", 5), ("minecraft:chest", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:comparator",
             output_count: 1,
             ingredients: &[("minecraft:redstone_torch", 3), ("minecraft:quartz", 1), ("minecraft:stone", 3)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:repeater",
             output_count: 1,
             ingredients: &[("minecraft:redstone_torch", 2), ("minecraft:redstone", 1), ("minecraft:stone", 3)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:redstone_torch",
             output_count: 1,
             ingredients: &[("minecraft:stick", 1), ("minecraft:redstone", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:lever",
             output_count: 1,
             ingredients: &[("minecraft:stick", 1), ("minecraft:cobblestone", 1)],
+            method: Crafting,
         },
 
         // === Containers ===
@@ -1059,31 +1283,37 @@ pub fn get_recipes() -> HashMap<&'static str, Recipe> {
             output: "minecraft:chest",
             output_count: 1,
             ingredients: &[("minecraft:any_planks", 8)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:barrel",
             output_count: 1,
             ingredients: &[("minecraft:any_planks", 6), ("minecraft:any_slab", 2)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:furnace",
             output_count: 1,
             ingredients: &[("minecraft:cobblestone", 8)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:blast_furnace",
             output_count: 1,
             ingredients: &[("minecraft:iron_ingot", 5), ("minecraft:furnace", 1), ("minecraft:smooth_stone", 3)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:smoker",
             output_count: 1,
             ingredients: &[("minecraft:any_log", 4), ("minecraft:furnace", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:crafting_table",
             output_count: 1,
             ingredients: &[("minecraft:any_planks", 4)],
+            method: Crafting,
         },
 
         // === Rails ===
@@ -1091,21 +1321,25 @@ pub fn get_recipes() -> HashMap<&'static str, Recipe> {
             output: "minecraft:rail",
             output_count: 16,
             ingredients: &[("minecraft:iron_ingot", 6), ("minecraft:stick", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:powered_rail",
             output_count: 6,
             ingredients: &[("minecraft:gold_ingot", 6), ("minecraft:stick", 1), ("minecraft:redstone", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:detector_rail",
             output_count: 6,
             ingredients: &[("minecraft:iron_ingot", 6), ("minecraft:stone_pressure_plate", 1), ("minecraft:redstone", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:activator_rail",
             output_count: 6,
             ingredients: &[("minecraft:iron_ingot", 6), ("minecraft:stick", 2), ("minecraft:redstone_torch", 1)],
+            method: Crafting,
         },
 
         // === Lanterns ===
@@ -1113,249 +1347,253 @@ pub fn get_recipes() -> HashMap<&'static str, Recipe> {
             output: "minecraft:lantern",
             output_count: 1,
             ingredients: &[("minecraft:iron_nugget", 8), ("minecraft:torch", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:soul_lantern",
             output_count: 1,
             ingredients: &[("minecraft:iron_nugget", 8), ("minecraft:soul_torch", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:torch",
             output_count: 4,
             ingredients: &[("minecraft:stick", 1), ("minecraft:coal", 1)],
+            method: Crafting,
         },
         Recipe {
             output: "minecraft:soul_torch",
             output_count: 4,
             ingredients: &[("minecraft:stick", 1), ("minecraft:coal", 1), ("minecraft:soul_sand", 1)],
+            method: Crafting,
         },
 
         // === Colored Concrete (16 colors) ===
         // Concrete is made by dropping concrete powder into water
-        Recipe { output: "minecraft:white_concrete", output_count: 1, ingredients: &[("minecraft:white_concrete_powder", 1)] },
-        Recipe { output: "minecraft:orange_concrete", output_count: 1, ingredients: &[("minecraft:orange_concrete_powder", 1)] },
-        Recipe { output: "minecraft:magenta_concrete", output_count: 1, ingredients: &[("minecraft:magenta_concrete_powder", 1)] },
-        Recipe { output: "minecraft:light_blue_concrete", output_count: 1, ingredients: &[("minecraft:light_blue_concrete_powder", 1)] },
-        Recipe { output: "minecraft:yellow_concrete", output_count: 1, ingredients: &[("minecraft:yellow_concrete_powder", 1)] },
-        Recipe { output: "minecraft:lime_concrete", output_count: 1, ingredients: &[("minecraft:lime_concrete_powder", 1)] },
-        Recipe { output: "minecraft:pink_concrete", output_count: 1, ingredients: &[("minecraft:pink_concrete_powder", 1)] },
-        Recipe { output: "minecraft:gray_concrete", output_count: 1, ingredients: &[("minecraft:gray_concrete_powder", 1)] },
-        Recipe { output: "minecraft:light_gray_concrete", output_count: 1, ingredients: &[("minecraft:light_gray_concrete_powder", 1)] },
-        Recipe { output: "minecraft:cyan_concrete", output_count: 1, ingredients: &[("minecraft:cyan_concrete_powder", 1)] },
-        Recipe { output: "minecraft:purple_concrete", output_count: 1, ingredients: &[("minecraft:purple_concrete_powder", 1)] },
-        Recipe { output: "minecraft:blue_concrete", output_count: 1, ingredients: &[("minecraft:blue_concrete_powder", 1)] },
-        Recipe { output: "minecraft:brown_concrete", output_count: 1, ingredients: &[("minecraft:brown_concrete_powder", 1)] },
-        Recipe { output: "minecraft:green_concrete", output_count: 1, ingredients: &[("minecraft:green_concrete_powder", 1)] },
-        Recipe { output: "minecraft:red_concrete", output_count: 1, ingredients: &[("minecraft:red_concrete_powder", 1)] },
-        Recipe { output: "minecraft:black_concrete", output_count: 1, ingredients: &[("minecraft:black_concrete_powder", 1)] },
+        Recipe { output: "minecraft:white_concrete", output_count: 1, ingredients: &[("minecraft:white_concrete_powder", 1)], method: Crafting },
+        Recipe { output: "minecraft:orange_concrete", output_count: 1, ingredients: &[("minecraft:orange_concrete_powder", 1)], method: Crafting },
+        Recipe { output: "minecraft:magenta_concrete", output_count: 1, ingredients: &[("minecraft:magenta_concrete_powder", 1)], method: Crafting },
+        Recipe { output: "minecraft:light_blue_concrete", output_count: 1, ingredients: &[("minecraft:light_blue_concrete_powder", 1)], method: Crafting },
+        Recipe { output: "minecraft:yellow_concrete", output_count: 1, ingredients: &[("minecraft:yellow_concrete_powder", 1)], method: Crafting },
+        Recipe { output: "minecraft:lime_concrete", output_count: 1, ingredients: &[("minecraft:lime_concrete_powder", 1)], method: Crafting },
+        Recipe { output: "minecraft:pink_concrete", output_count: 1, ingredients: &[("minecraft:pink_concrete_powder", 1)], method: Crafting },
+        Recipe { output: "minecraft:gray_concrete", output_count: 1, ingredients: &[("minecraft:gray_concrete_powder", 1)], method: Crafting },
+        Recipe { output: "minecraft:light_gray_concrete", output_count: 1, ingredients: &[("minecraft:light_gray_concrete_powder", 1)], method: Crafting },
+        Recipe { output: "minecraft:cyan_concrete", output_count: 1, ingredients: &[("minecraft:cyan_concrete_powder", 1)], method: Crafting },
+        Recipe { output: "minecraft:purple_concrete", output_count: 1, ingredients: &[("minecraft:purple_concrete_powder", 1)], method: Crafting },
+        Recipe { output: "minecraft:blue_concrete", output_count: 1, ingredients: &[("minecraft:blue_concrete_powder", 1)], method: Crafting },
+        Recipe { output: "minecraft:brown_concrete", output_count: 1, ingredients: &[("minecraft:brown_concrete_powder", 1)], method: Crafting },
+        Recipe { output: "minecraft:green_concrete", output_count: 1, ingredients: &[("minecraft:green_concrete_powder", 1)], method: Crafting },
+        Recipe { output: "minecraft:red_concrete", output_count: 1, ingredients: &[("minecraft:red_concrete_powder", 1)], method: Crafting },
+        Recipe { output: "minecraft:black_concrete", output_count: 1, ingredients: &[("minecraft:black_concrete_powder", 1)], method: Crafting },
 
         // === Concrete Powder (4 sand + 4 gravel + 1 dye = 8 powder) ===
-        Recipe { output: "minecraft:white_concrete_powder", output_count: 8, ingredients: &[("minecraft:sand", 4), ("minecraft:gravel", 4), ("minecraft:white_dye", 1)] },
-        Recipe { output: "minecraft:orange_concrete_powder", output_count: 8, ingredients: &[("minecraft:sand", 4), ("minecraft:gravel", 4), ("minecraft:orange_dye", 1)] },
-        Recipe { output: "minecraft:magenta_concrete_powder", output_count: 8, ingredients: &[("minecraft:sand", 4), ("minecraft:gravel", 4), ("minecraft:magenta_dye", 1)] },
-        Recipe { output: "minecraft:light_blue_concrete_powder", output_count: 8, ingredients: &[("minecraft:sand", 4), ("minecraft:gravel", 4), ("minecraft:light_blue_dye", 1)] },
-        Recipe { output: "minecraft:yellow_concrete_powder", output_count: 8, ingredients: &[("minecraft:sand", 4), ("minecraft:gravel", 4), ("minecraft:yellow_dye", 1)] },
-        Recipe { output: "minecraft:lime_concrete_powder", output_count: 8, ingredients: &[("minecraft:sand", 4), ("minecraft:gravel", 4), ("minecraft:lime_dye", 1)] },
-        Recipe { output: "minecraft:pink_concrete_powder", output_count: 8, ingredients: &[("minecraft:sand", 4), ("minecraft:gravel", 4), ("minecraft:pink_dye", 1)] },
-        Recipe { output: "minecraft:gray_concrete_powder", output_count: 8, ingredients: &[("minecraft:sand", 4), ("minecraft:gravel", 4), ("minecraft:gray_dye", 1)] },
-        Recipe { output: "minecraft:light_gray_concrete_powder", output_count: 8, ingredients: &[("minecraft:sand", 4), ("minecraft:gravel", 4), ("minecraft:light_gray_dye", 1)] },
-        Recipe { output: "minecraft:cyan_concrete_powder", output_count: 8, ingredients: &[("minecraft:sand", 4), ("minecraft:gravel", 4), ("minecraft:cyan_dye", 1)] },
-        Recipe { output: "minecraft:purple_concrete_powder", output_count: 8, ingredients: &[("minecraft:sand", 4), ("minecraft:gravel", 4), ("minecraft:purple_dye", 1)] },
-        Recipe { output: "minecraft:blue_concrete_powder", output_count: 8, ingredients: &[("minecraft:sand", 4), ("minecraft:gravel", 4), ("minecraft:blue_dye", 1)] },
-        Recipe { output: "minecraft:brown_concrete_powder", output_count: 8, ingredients: &[("minecraft:sand", 4), ("minecraft:gravel", 4), ("minecraft:brown_dye", 1)] },
-        Recipe { output: "minecraft:green_concrete_powder", output_count: 8, ingredients: &[("minecraft:sand", 4), ("minecraft:gravel", 4), ("minecraft:green_dye", 1)] },
-        Recipe { output: "minecraft:red_concrete_powder", output_count: 8, ingredients: &[("minecraft:sand", 4), ("minecraft:gravel", 4), ("minecraft:red_dye", 1)] },
-        Recipe { output: "minecraft:black_concrete_powder", output_count: 8, ingredients: &[("minecraft:sand", 4), ("minecraft:gravel", 4), ("minecraft:black_dye", 1)] },
+        Recipe { output: "minecraft:white_concrete_powder", output_count: 8, ingredients: &[("minecraft:sand", 4), ("minecraft:gravel", 4), ("minecraft:white_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:orange_concrete_powder", output_count: 8, ingredients: &[("minecraft:sand", 4), ("minecraft:gravel", 4), ("minecraft:orange_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:magenta_concrete_powder", output_count: 8, ingredients: &[("minecraft:sand", 4), ("minecraft:gravel", 4), ("minecraft:magenta_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:light_blue_concrete_powder", output_count: 8, ingredients: &[("minecraft:sand", 4), ("minecraft:gravel", 4), ("minecraft:light_blue_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:yellow_concrete_powder", output_count: 8, ingredients: &[("minecraft:sand", 4), ("minecraft:gravel", 4), ("minecraft:yellow_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:lime_concrete_powder", output_count: 8, ingredients: &[("minecraft:sand", 4), ("minecraft:gravel", 4), ("minecraft:lime_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:pink_concrete_powder", output_count: 8, ingredients: &[("minecraft:sand", 4), ("minecraft:gravel", 4), ("minecraft:pink_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:gray_concrete_powder", output_count: 8, ingredients: &[("minecraft:sand", 4), ("minecraft:gravel", 4), ("minecraft:gray_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:light_gray_concrete_powder", output_count: 8, ingredients: &[("minecraft:sand", 4), ("minecraft:gravel", 4), ("minecraft:light_gray_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:cyan_concrete_powder", output_count: 8, ingredients: &[("minecraft:sand", 4), ("minecraft:gravel", 4), ("minecraft:cyan_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:purple_concrete_powder", output_count: 8, ingredients: &[("minecraft:sand", 4), ("minecraft:gravel", 4), ("minecraft:purple_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:blue_concrete_powder", output_count: 8, ingredients: &[("minecraft:sand", 4), ("minecraft:gravel", 4), ("minecraft:blue_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:brown_concrete_powder", output_count: 8, ingredients: &[("minecraft:sand", 4), ("minecraft:gravel", 4), ("minecraft:brown_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:green_concrete_powder", output_count: 8, ingredients: &[("minecraft:sand", 4), ("minecraft:gravel", 4), ("minecraft:green_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:red_concrete_powder", output_count: 8, ingredients: &[("minecraft:sand", 4), ("minecraft:gravel", 4), ("minecraft:red_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:black_concrete_powder", output_count: 8, ingredients: &[("minecraft:sand", 4), ("minecraft:gravel", 4), ("minecraft:black_dye", 1)], method: Crafting },
 
         // === Colored Terracotta (8 terracotta + 1 dye = 8 colored) ===
-        Recipe { output: "minecraft:white_terracotta", output_count: 8, ingredients: &[("minecraft:terracotta", 8), ("minecraft:white_dye", 1)] },
-        Recipe { output: "minecraft:orange_terracotta", output_count: 8, ingredients: &[("minecraft:terracotta", 8), ("minecraft:orange_dye", 1)] },
-        Recipe { output: "minecraft:magenta_terracotta", output_count: 8, ingredients: &[("minecraft:terracotta", 8), ("minecraft:magenta_dye", 1)] },
-        Recipe { output: "minecraft:light_blue_terracotta", output_count: 8, ingredients: &[("minecraft:terracotta", 8), ("minecraft:light_blue_dye", 1)] },
-        Recipe { output: "minecraft:yellow_terracotta", output_count: 8, ingredients: &[("minecraft:terracotta", 8), ("minecraft:yellow_dye", 1)] },
-        Recipe { output: "minecraft:lime_terracotta", output_count: 8, ingredients: &[("minecraft:terracotta", 8), ("minecraft:lime_dye", 1)] },
-        Recipe { output: "minecraft:pink_terracotta", output_count: 8, ingredients: &[("minecraft:terracotta", 8), ("minecraft:pink_dye", 1)] },
-        Recipe { output: "minecraft:gray_terracotta", output_count: 8, ingredients: &[("minecraft:terracotta", 8), ("minecraft:gray_dye", 1)] },
-        Recipe { output: "minecraft:light_gray_terracotta", output_count: 8, ingredients: &[("minecraft:terracotta", 8), ("minecraft:light_gray_dye", 1)] },
-        Recipe { output: "minecraft:cyan_terracotta", output_count: 8, ingredients: &[("minecraft:terracotta", 8), ("minecraft:cyan_dye", 1)] },
-        Recipe { output: "minecraft:purple_terracotta", output_count: 8, ingredients: &[("minecraft:terracotta", 8), ("minecraft:purple_dye", 1)] },
-        Recipe { output: "minecraft:blue_terracotta", output_count: 8, ingredients: &[("minecraft:terracotta", 8), ("minecraft:blue_dye", 1)] },
-        Recipe { output: "minecraft:brown_terracotta", output_count: 8, ingredients: &[("minecraft:terracotta", 8), ("minecraft:brown_dye", 1)] },
-        Recipe { output: "minecraft:green_terracotta", output_count: 8, ingredients: &[("minecraft:terracotta", 8), ("minecraft:green_dye", 1)] },
-        Recipe { output: "minecraft:red_terracotta", output_count: 8, ingredients: &[("minecraft:terracotta", 8), ("minecraft:red_dye", 1)] },
-        Recipe { output: "minecraft:black_terracotta", output_count: 8, ingredients: &[("minecraft:terracotta", 8), ("minecraft:black_dye", 1)] },
+        Recipe { output: "minecraft:white_terracotta", output_count: 8, ingredients: &[("minecraft:terracotta", 8), ("minecraft:white_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:orange_terracotta", output_count: 8, ingredients: &[("minecraft:terracotta", 8), ("minecraft:orange_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:magenta_terracotta", output_count: 8, ingredients: &[("minecraft:terracotta", 8), ("minecraft:magenta_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:light_blue_terracotta", output_count: 8, ingredients: &[("minecraft:terracotta", 8), ("minecraft:light_blue_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:yellow_terracotta", output_count: 8, ingredients: &[("minecraft:terracotta", 8), ("minecraft:yellow_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:lime_terracotta", output_count: 8, ingredients: &[("minecraft:terracotta", 8), ("minecraft:lime_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:pink_terracotta", output_count: 8, ingredients: &[("minecraft:terracotta", 8), ("minecraft:pink_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:gray_terracotta", output_count: 8, ingredients: &[("minecraft:terracotta", 8), ("minecraft:gray_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:light_gray_terracotta", output_count: 8, ingredients: &[("minecraft:terracotta", 8), ("minecraft:light_gray_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:cyan_terracotta", output_count: 8, ingredients: &[("minecraft:terracotta", 8), ("minecraft:cyan_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:purple_terracotta", output_count: 8, ingredients: &[("minecraft:terracotta", 8), ("minecraft:purple_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:blue_terracotta", output_count: 8, ingredients: &[("minecraft:terracotta", 8), ("minecraft:blue_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:brown_terracotta", output_count: 8, ingredients: &[("minecraft:terracotta", 8), ("minecraft:brown_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:green_terracotta", output_count: 8, ingredients: &[("minecraft:terracotta", 8), ("minecraft:green_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:red_terracotta", output_count: 8, ingredients: &[("minecraft:terracotta", 8), ("minecraft:red_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:black_terracotta", output_count: 8, ingredients: &[("minecraft:terracotta", 8), ("minecraft:black_dye", 1)], method: Crafting },
 
         // Base terracotta from clay
-        Recipe { output: "minecraft:terracotta", output_count: 1, ingredients: &[("minecraft:clay", 1)] }, // smelting
+        Recipe { output: "minecraft:terracotta", output_count: 1, ingredients: &[("minecraft:clay", 1)], method: Smelting },
 
         // === Glazed Terracotta (smelting colored terracotta) ===
-        Recipe { output: "minecraft:white_glazed_terracotta", output_count: 1, ingredients: &[("minecraft:white_terracotta", 1)] },
-        Recipe { output: "minecraft:orange_glazed_terracotta", output_count: 1, ingredients: &[("minecraft:orange_terracotta", 1)] },
-        Recipe { output: "minecraft:magenta_glazed_terracotta", output_count: 1, ingredients: &[("minecraft:magenta_terracotta", 1)] },
-        Recipe { output: "minecraft:light_blue_glazed_terracotta", output_count: 1, ingredients: &[("minecraft:light_blue_terracotta", 1)] },
-        Recipe { output: "minecraft:yellow_glazed_terracotta", output_count: 1, ingredients: &[("minecraft:yellow_terracotta", 1)] },
-        Recipe { output: "minecraft:lime_glazed_terracotta", output_count: 1, ingredients: &[("minecraft:lime_terracotta", 1)] },
-        Recipe { output: "minecraft:pink_glazed_terracotta", output_count: 1, ingredients: &[("minecraft:pink_terracotta", 1)] },
-        Recipe { output: "minecraft:gray_glazed_terracotta", output_count: 1, ingredients: &[("minecraft:gray_terracotta", 1)] },
-        Recipe { output: "minecraft:light_gray_glazed_terracotta", output_count: 1, ingredients: &[("minecraft:light_gray_terracotta", 1)] },
-        Recipe { output: "minecraft:cyan_glazed_terracotta", output_count: 1, ingredients: &[("minecraft:cyan_terracotta", 1)] },
-        Recipe { output: "minecraft:purple_glazed_terracotta", output_count: 1, ingredients: &[("minecraft:purple_terracotta", 1)] },
-        Recipe { output: "minecraft:blue_glazed_terracotta", output_count: 1, ingredients: &[("minecraft:blue_terracotta", 1)] },
-        Recipe { output: "minecraft:brown_glazed_terracotta", output_count: 1, ingredients: &[("minecraft:brown_terracotta", 1)] },
-        Recipe { output: "minecraft:green_glazed_terracotta", output_count: 1, ingredients: &[("minecraft:green_terracotta", 1)] },
-        Recipe { output: "minecraft:red_glazed_terracotta", output_count: 1, ingredients: &[("minecraft:red_terracotta", 1)] },
-        Recipe { output: "minecraft:black_glazed_terracotta", output_count: 1, ingredients: &[("minecraft:black_terracotta", 1)] },
+        Recipe { output: "minecraft:white_glazed_terracotta", output_count: 1, ingredients: &[("minecraft:white_terracotta", 1)], method: Smelting },
+        Recipe { output: "minecraft:orange_glazed_terracotta", output_count: 1, ingredients: &[("minecraft:orange_terracotta", 1)], method: Smelting },
+        Recipe { output: "minecraft:magenta_glazed_terracotta", output_count: 1, ingredients: &[("minecraft:magenta_terracotta", 1)], method: Smelting },
+        Recipe { output: "minecraft:light_blue_glazed_terracotta", output_count: 1, ingredients: &[("minecraft:light_blue_terracotta", 1)], method: Smelting },
+        Recipe { output: "minecraft:yellow_glazed_terracotta", output_count: 1, ingredients: &[("minecraft:yellow_terracotta", 1)], method: Smelting },
+        Recipe { output: "minecraft:lime_glazed_terracotta", output_count: 1, ingredients: &[("minecraft:lime_terracotta", 1)], method: Smelting },
+        Recipe { output: "minecraft:pink_glazed_terracotta", output_count: 1, ingredients: &[("minecraft:pink_terracotta", 1)], method: Smelting },
+        Recipe { output: "minecraft:gray_glazed_terracotta", output_count: 1, ingredients: &[("minecraft:gray_terracotta", 1)], method: Smelting },
+        Recipe { output: "minecraft:light_gray_glazed_terracotta", output_count: 1, ingredients: &[("minecraft:light_gray_terracotta", 1)], method: Smelting },
+        Recipe { output: "minecraft:cyan_glazed_terracotta", output_count: 1, ingredients: &[("minecraft:cyan_terracotta", 1)], method: Smelting },
+        Recipe { output: "minecraft:purple_glazed_terracotta", output_count: 1, ingredients: &[("minecraft:purple_terracotta", 1)], method: Smelting },
+        Recipe { output: "minecraft:blue_glazed_terracotta", output_count: 1, ingredients: &[("minecraft:blue_terracotta", 1)], method: Smelting },
+        Recipe { output: "minecraft:brown_glazed_terracotta", output_count: 1, ingredients: &[("minecraft:brown_terracotta", 1)], method: Smelting },
+        Recipe { output: "minecraft:green_glazed_terracotta", output_count: 1, ingredients: &[("minecraft:green_terracotta", 1)], method: Smelting },
+        Recipe { output: "minecraft:red_glazed_terracotta", output_count: 1, ingredients: &[("minecraft:red_terracotta", 1)], method: Smelting },
+        Recipe { output: "minecraft:black_glazed_terracotta", output_count: 1, ingredients: &[("minecraft:black_terracotta", 1)], method: Smelting },
 
         // === Colored Wool (1 wool + 1 dye = 1 colored wool) ===
-        Recipe { output: "minecraft:orange_wool", output_count: 1, ingredients: &[("minecraft:white_wool", 1), ("minecraft:orange_dye", 1)] },
-        Recipe { output: "minecraft:magenta_wool", output_count: 1, ingredients: &[("minecraft:white_wool", 1), ("minecraft:magenta_dye", 1)] },
-        Recipe { output: "minecraft:light_blue_wool", output_count: 1, ingredients: &[("minecraft:white_wool", 1), ("minecraft:light_blue_dye", 1)] },
-        Recipe { output: "minecraft:yellow_wool", output_count: 1, ingredients: &[("minecraft:white_wool", 1), ("minecraft:yellow_dye", 1)] },
-        Recipe { output: "minecraft:lime_wool", output_count: 1, ingredients: &[("minecraft:white_wool", 1), ("minecraft:lime_dye", 1)] },
-        Recipe { output: "minecraft:pink_wool", output_count: 1, ingredients: &[("minecraft:white_wool", 1), ("minecraft:pink_dye", 1)] },
-        Recipe { output: "minecraft:gray_wool", output_count: 1, ingredients: &[("minecraft:white_wool", 1), ("minecraft:gray_dye", 1)] },
-        Recipe { output: "minecraft:light_gray_wool", output_count: 1, ingredients: &[("minecraft:white_wool", 1), ("minecraft:light_gray_dye", 1)] },
-        Recipe { output: "minecraft:cyan_wool", output_count: 1, ingredients: &[("minecraft:white_wool", 1), ("minecraft:cyan_dye", 1)] },
-        Recipe { output: "minecraft:purple_wool", output_count: 1, ingredients: &[("minecraft:white_wool", 1), ("minecraft:purple_dye", 1)] },
-        Recipe { output: "minecraft:blue_wool", output_count: 1, ingredients: &[("minecraft:white_wool", 1), ("minecraft:blue_dye", 1)] },
-        Recipe { output: "minecraft:brown_wool", output_count: 1, ingredients: &[("minecraft:white_wool", 1), ("minecraft:brown_dye", 1)] },
-        Recipe { output: "minecraft:green_wool", output_count: 1, ingredients: &[("minecraft:white_wool", 1), ("minecraft:green_dye", 1)] },
-        Recipe { output: "minecraft:red_wool", output_count: 1, ingredients: &[("minecraft:white_wool", 1), ("minecraft:red_dye", 1)] },
-        Recipe { output: "minecraft:black_wool", output_count: 1, ingredients: &[("minecraft:white_wool", 1), ("minecraft:black_dye", 1)] },
+        Recipe { output: "minecraft:orange_wool", output_count: 1, ingredients: &[("minecraft:white_wool", 1), ("minecraft:orange_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:magenta_wool", output_count: 1, ingredients: &[("minecraft:white_wool", 1), ("minecraft:magenta_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:light_blue_wool", output_count: 1, ingredients: &[("minecraft:white_wool", 1), ("minecraft:light_blue_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:yellow_wool", output_count: 1, ingredients: &[("minecraft:white_wool", 1), ("minecraft:yellow_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:lime_wool", output_count: 1, ingredients: &[("minecraft:white_wool", 1), ("minecraft:lime_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:pink_wool", output_count: 1, ingredients: &[("minecraft:white_wool", 1), ("minecraft:pink_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:gray_wool", output_count: 1, ingredients: &[("minecraft:white_wool", 1), ("minecraft:gray_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:light_gray_wool", output_count: 1, ingredients: &[("minecraft:white_wool", 1), ("minecraft:light_gray_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:cyan_wool", output_count: 1, ingredients: &[("minecraft:white_wool", 1), ("minecraft:cyan_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:purple_wool", output_count: 1, ingredients: &[("minecraft:white_wool", 1), ("minecraft:purple_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:blue_wool", output_count: 1, ingredients: &[("minecraft:white_wool", 1), ("minecraft:blue_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:brown_wool", output_count: 1, ingredients: &[("minecraft:white_wool", 1), ("minecraft:brown_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:green_wool", output_count: 1, ingredients: &[("minecraft:white_wool", 1), ("minecraft:green_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:red_wool", output_count: 1, ingredients: &[("minecraft:white_wool", 1), ("minecraft:red_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:black_wool", output_count: 1, ingredients: &[("minecraft:white_wool", 1), ("minecraft:black_dye", 1)], method: Crafting },
 
         // === Colored Stained Glass (8 glass + 1 dye = 8 stained) ===
-        Recipe { output: "minecraft:white_stained_glass", output_count: 8, ingredients: &[("minecraft:glass", 8), ("minecraft:white_dye", 1)] },
-        Recipe { output: "minecraft:orange_stained_glass", output_count: 8, ingredients: &[("minecraft:glass", 8), ("minecraft:orange_dye", 1)] },
-        Recipe { output: "minecraft:magenta_stained_glass", output_count: 8, ingredients: &[("minecraft:glass", 8), ("minecraft:magenta_dye", 1)] },
-        Recipe { output: "minecraft:light_blue_stained_glass", output_count: 8, ingredients: &[("minecraft:glass", 8), ("minecraft:light_blue_dye", 1)] },
-        Recipe { output: "minecraft:yellow_stained_glass", output_count: 8, ingredients: &[("minecraft:glass", 8), ("minecraft:yellow_dye", 1)] },
-        Recipe { output: "minecraft:lime_stained_glass", output_count: 8, ingredients: &[("minecraft:glass", 8), ("minecraft:lime_dye", 1)] },
-        Recipe { output: "minecraft:pink_stained_glass", output_count: 8, ingredients: &[("minecraft:glass", 8), ("minecraft:pink_dye", 1)] },
-        Recipe { output: "minecraft:gray_stained_glass", output_count: 8, ingredients: &[("minecraft:glass", 8), ("minecraft:gray_dye", 1)] },
-        Recipe { output: "minecraft:light_gray_stained_glass", output_count: 8, ingredients: &[("minecraft:glass", 8), ("minecraft:light_gray_dye", 1)] },
-        Recipe { output: "minecraft:cyan_stained_glass", output_count: 8, ingredients: &[("minecraft:glass", 8), ("minecraft:cyan_dye", 1)] },
-        Recipe { output: "minecraft:purple_stained_glass", output_count: 8, ingredients: &[("minecraft:glass", 8), ("minecraft:purple_dye", 1)] },
-        Recipe { output: "minecraft:blue_stained_glass", output_count: 8, ingredients: &[("minecraft:glass", 8), ("minecraft:blue_dye", 1)] },
-        Recipe { output: "minecraft:brown_stained_glass", output_count: 8, ingredients: &[("minecraft:glass", 8), ("minecraft:brown_dye", 1)] },
-        Recipe { output: "minecraft:green_stained_glass", output_count: 8, ingredients: &[("minecraft:glass", 8), ("minecraft:green_dye", 1)] },
-        Recipe { output: "minecraft:red_stained_glass", output_count: 8, ingredients: &[("minecraft:glass", 8), ("minecraft:red_dye", 1)] },
-        Recipe { output: "minecraft:black_stained_glass", output_count: 8, ingredients: &[("minecraft:glass", 8), ("minecraft:black_dye", 1)] },
+        Recipe { output: "minecraft:white_stained_glass", output_count: 8, ingredients: &[("minecraft:glass", 8), ("minecraft:white_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:orange_stained_glass", output_count: 8, ingredients: &[("minecraft:glass", 8), ("minecraft:orange_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:magenta_stained_glass", output_count: 8, ingredients: &[("minecraft:glass", 8), ("minecraft:magenta_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:light_blue_stained_glass", output_count: 8, ingredients: &[("minecraft:glass", 8), ("minecraft:light_blue_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:yellow_stained_glass", output_count: 8, ingredients: &[("minecraft:glass", 8), ("minecraft:yellow_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:lime_stained_glass", output_count: 8, ingredients: &[("minecraft:glass", 8), ("minecraft:lime_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:pink_stained_glass", output_count: 8, ingredients: &[("minecraft:glass", 8), ("minecraft:pink_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:gray_stained_glass", output_count: 8, ingredients: &[("minecraft:glass", 8), ("minecraft:gray_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:light_gray_stained_glass", output_count: 8, ingredients: &[("minecraft:glass", 8), ("minecraft:light_gray_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:cyan_stained_glass", output_count: 8, ingredients: &[("minecraft:glass", 8), ("minecraft:cyan_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:purple_stained_glass", output_count: 8, ingredients: &[("minecraft:glass", 8), ("minecraft:purple_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:blue_stained_glass", output_count: 8, ingredients: &[("minecraft:glass", 8), ("minecraft:blue_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:brown_stained_glass", output_count: 8, ingredients: &[("minecraft:glass", 8), ("minecraft:brown_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:green_stained_glass", output_count: 8, ingredients: &[("minecraft:glass", 8), ("minecraft:green_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:red_stained_glass", output_count: 8, ingredients: &[("minecraft:glass", 8), ("minecraft:red_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:black_stained_glass", output_count: 8, ingredients: &[("minecraft:glass", 8), ("minecraft:black_dye", 1)], method: Crafting },
 
         // Base glass from sand
-        Recipe { output: "minecraft:glass", output_count: 1, ingredients: &[("minecraft:sand", 1)] }, // smelting
+        Recipe { output: "minecraft:glass", output_count: 1, ingredients: &[("minecraft:sand", 1)], method: Smelting },
 
         // === Stained Glass Panes (6 stained glass = 16 panes) ===
-        Recipe { output: "minecraft:white_stained_glass_pane", output_count: 16, ingredients: &[("minecraft:white_stained_glass", 6)] },
-        Recipe { output: "minecraft:orange_stained_glass_pane", output_count: 16, ingredients: &[("minecraft:orange_stained_glass", 6)] },
-        Recipe { output: "minecraft:magenta_stained_glass_pane", output_count: 16, ingredients: &[("minecraft:magenta_stained_glass", 6)] },
-        Recipe { output: "minecraft:light_blue_stained_glass_pane", output_count: 16, ingredients: &[("minecraft:light_blue_stained_glass", 6)] },
-        Recipe { output: "minecraft:yellow_stained_glass_pane", output_count: 16, ingredients: &[("minecraft:yellow_stained_glass", 6)] },
-        Recipe { output: "minecraft:lime_stained_glass_pane", output_count: 16, ingredients: &[("minecraft:lime_stained_glass", 6)] },
-        Recipe { output: "minecraft:pink_stained_glass_pane", output_count: 16, ingredients: &[("minecraft:pink_stained_glass", 6)] },
-        Recipe { output: "minecraft:gray_stained_glass_pane", output_count: 16, ingredients: &[("minecraft:gray_stained_glass", 6)] },
-        Recipe { output: "minecraft:light_gray_stained_glass_pane", output_count: 16, ingredients: &[("minecraft:light_gray_stained_glass", 6)] },
-        Recipe { output: "minecraft:cyan_stained_glass_pane", output_count: 16, ingredients: &[("minecraft:cyan_stained_glass", 6)] },
-        Recipe { output: "minecraft:purple_stained_glass_pane", output_count: 16, ingredients: &[("minecraft:purple_stained_glass", 6)] },
-        Recipe { output: "minecraft:blue_stained_glass_pane", output_count: 16, ingredients: &[("minecraft:blue_stained_glass", 6)] },
-        Recipe { output: "minecraft:brown_stained_glass_pane", output_count: 16, ingredients: &[("minecraft:brown_stained_glass", 6)] },
-        Recipe { output: "minecraft:green_stained_glass_pane", output_count: 16, ingredients: &[("minecraft:green_stained_glass", 6)] },
-        Recipe { output: "minecraft:red_stained_glass_pane", output_count: 16, ingredients: &[("minecraft:red_stained_glass", 6)] },
-        Recipe { output: "minecraft:black_stained_glass_pane", output_count: 16, ingredients: &[("minecraft:black_stained_glass", 6)] },
+        Recipe { output: "minecraft:white_stained_glass_pane", output_count: 16, ingredients: &[("minecraft:white_stained_glass", 6)], method: Crafting },
+        Recipe { output: "minecraft:orange_stained_glass_pane", output_count: 16, ingredients: &[("minecraft:orange_stained_glass", 6)], method: Crafting },
+        Recipe { output: "minecraft:magenta_stained_glass_pane", output_count: 16, ingredients: &[("minecraft:magenta_stained_glass", 6)], method: Crafting },
+        Recipe { output: "minecraft:light_blue_stained_glass_pane", output_count: 16, ingredients: &[("minecraft:light_blue_stained_glass", 6)], method: Crafting },
+        Recipe { output: "minecraft:yellow_stained_glass_pane", output_count: 16, ingredients: &[("minecraft:yellow_stained_glass", 6)], method: Crafting },
+        Recipe { output: "minecraft:lime_stained_glass_pane", output_count: 16, ingredients: &[("minecraft:lime_stained_glass", 6)], method: Crafting },
+        Recipe { output: "minecraft:pink_stained_glass_pane", output_count: 16, ingredients: &[("minecraft:pink_stained_glass", 6)], method: Crafting },
+        Recipe { output: "minecraft:gray_stained_glass_pane", output_count: 16, ingredients: &[("minecraft:gray_stained_glass", 6)], method: Crafting },
+        Recipe { output: "minecraft:light_gray_stained_glass_pane", output_count: 16, ingredients: &[("minecraft:light_gray_stained_glass", 6)], method: Crafting },
+        Recipe { output: "minecraft:cyan_stained_glass_pane", output_count: 16, ingredients: &[("minecraft:cyan_stained_glass", 6)], method: Crafting },
+        Recipe { output: "minecraft:purple_stained_glass_pane", output_count: 16, ingredients: &[("minecraft:purple_stained_glass", 6)], method: Crafting },
+        Recipe { output: "minecraft:blue_stained_glass_pane", output_count: 16, ingredients: &[("minecraft:blue_stained_glass", 6)], method: Crafting },
+        Recipe { output: "minecraft:brown_stained_glass_pane", output_count: 16, ingredients: &[("minecraft:brown_stained_glass", 6)], method: Crafting },
+        Recipe { output: "minecraft:green_stained_glass_pane", output_count: 16, ingredients: &[("minecraft:green_stained_glass", 6)], method: Crafting },
+        Recipe { output: "minecraft:red_stained_glass_pane", output_count: 16, ingredients: &[("minecraft:red_stained_glass", 6)], method: Crafting },
+        Recipe { output: "minecraft:black_stained_glass_pane", output_count: 16, ingredients: &[("minecraft:black_stained_glass", 6)], method: Crafting },
 
         // Regular glass pane
-        Recipe { output: "minecraft:glass_pane", output_count: 16, ingredients: &[("minecraft:glass", 6)] },
+        Recipe { output: "minecraft:glass_pane", output_count: 16, ingredients: &[("minecraft:glass", 6)], method: Crafting },
 
         // === Beds (3 wool + 3 planks = 1 bed) ===
-        Recipe { output: "minecraft:white_bed", output_count: 1, ingredients: &[("minecraft:white_wool", 3), ("minecraft:any_planks", 3)] },
-        Recipe { output: "minecraft:orange_bed", output_count: 1, ingredients: &[("minecraft:orange_wool", 3), ("minecraft:any_planks", 3)] },
-        Recipe { output: "minecraft:magenta_bed", output_count: 1, ingredients: &[("minecraft:magenta_wool", 3), ("minecraft:any_planks", 3)] },
-        Recipe { output: "minecraft:light_blue_bed", output_count: 1, ingredients: &[("minecraft:light_blue_wool", 3), ("minecraft:any_planks", 3)] },
-        Recipe { output: "minecraft:yellow_bed", output_count: 1, ingredients: &[("minecraft:yellow_wool", 3), ("minecraft:any_planks", 3)] },
-        Recipe { output: "minecraft:lime_bed", output_count: 1, ingredients: &[("minecraft:lime_wool", 3), ("minecraft:any_planks", 3)] },
-        Recipe { output: "minecraft:pink_bed", output_count: 1, ingredients: &[("minecraft:pink_wool", 3), ("minecraft:any_planks", 3)] },
-        Recipe { output: "minecraft:gray_bed", output_count: 1, ingredients: &[("minecraft:gray_wool", 3), ("minecraft:any_planks", 3)] },
-        Recipe { output: "minecraft:light_gray_bed", output_count: 1, ingredients: &[("minecraft:light_gray_wool", 3), ("minecraft:any_planks", 3)] },
-        Recipe { output: "minecraft:cyan_bed", output_count: 1, ingredients: &[("minecraft:cyan_wool", 3), ("minecraft:any_planks", 3)] },
-        Recipe { output: "minecraft:purple_bed", output_count: 1, ingredients: &[("minecraft:purple_wool", 3), ("minecraft:any_planks", 3)] },
-        Recipe { output: "minecraft:blue_bed", output_count: 1, ingredients: &[("minecraft:blue_wool", 3), ("minecraft:any_planks", 3)] },
-        Recipe { output: "minecraft:brown_bed", output_count: 1, ingredients: &[("minecraft:brown_wool", 3), ("minecraft:any_planks", 3)] },
-        Recipe { output: "minecraft:green_bed", output_count: 1, ingredients: &[("minecraft:green_wool", 3), ("minecraft:any_planks", 3)] },
-        Recipe { output: "minecraft:red_bed", output_count: 1, ingredients: &[("minecraft:red_wool", 3), ("minecraft:any_planks", 3)] },
-        Recipe { output: "minecraft:black_bed", output_count: 1, ingredients: &[("minecraft:black_wool", 3), ("minecraft:any_planks", 3)] },
+        Recipe { output: "minecraft:white_bed", output_count: 1, ingredients: &[("minecraft:white_wool", 3), ("minecraft:any_planks", 3)], method: Crafting },
+        Recipe { output: "minecraft:orange_bed", output_count: 1, ingredients: &[("minecraft:orange_wool", 3), ("minecraft:any_planks", 3)], method: Crafting },
+        Recipe { output: "minecraft:magenta_bed", output_count: 1, ingredients: &[("minecraft:magenta_wool", 3), ("minecraft:any_planks", 3)], method: Crafting },
+        Recipe { output: "minecraft:light_blue_bed", output_count: 1, ingredients: &[("minecraft:light_blue_wool", 3), ("minecraft:any_planks", 3)], method: Crafting },
+        Recipe { output: "minecraft:yellow_bed", output_count: 1, ingredients: &[("minecraft:yellow_wool", 3), ("minecraft:any_planks", 3)], method: Crafting },
+        Recipe { output: "minecraft:lime_bed", output_count: 1, ingredients: &[("minecraft:lime_wool", 3), ("minecraft:any_planks", 3)], method: Crafting },
+        Recipe { output: "minecraft:pink_bed", output_count: 1, ingredients: &[("minecraft:pink_wool", 3), ("minecraft:any_planks", 3)], method: Crafting },
+        Recipe { output: "minecraft:gray_bed", output_count: 1, ingredients: &[("minecraft:gray_wool", 3), ("minecraft:any_planks", 3)], method: Crafting },
+        Recipe { output: "minecraft:light_gray_bed", output_count: 1, ingredients: &[("minecraft:light_gray_wool", 3), ("minecraft:any_planks", 3)], method: Crafting },
+        Recipe { output: "minecraft:cyan_bed", output_count: 1, ingredients: &[("minecraft:cyan_wool", 3), ("minecraft:any_planks", 3)], method: Crafting },
+        Recipe { output: "minecraft:purple_bed", output_count: 1, ingredients: &[("minecraft:purple_wool", 3), ("minecraft:any_planks", 3)], method: Crafting },
+        Recipe { output: "minecraft:blue_bed", output_count: 1, ingredients: &[("minecraft:blue_wool", 3), ("minecraft:any_planks", 3)], method: Crafting },
+        Recipe { output: "minecraft:brown_bed", output_count: 1, ingredients: &[("minecraft:brown_wool", 3), ("minecraft:any_planks", 3)], method: Crafting },
+        Recipe { output: "minecraft:green_bed", output_count: 1, ingredients: &[("minecraft:green_wool", 3), ("minecraft:any_planks", 3)], method: Crafting },
+        Recipe { output: "minecraft:red_bed", output_count: 1, ingredients: &[("minecraft:red_wool", 3), ("minecraft:any_planks", 3)], method: Crafting },
+        Recipe { output: "minecraft:black_bed", output_count: 1, ingredients: &[("minecraft:black_wool", 3), ("minecraft:any_planks", 3)], method: Crafting },
 
         // === Banners (6 wool + 1 stick = 1 banner) ===
-        Recipe { output: "minecraft:white_banner", output_count: 1, ingredients: &[("minecraft:white_wool", 6), ("minecraft:stick", 1)] },
-        Recipe { output: "minecraft:orange_banner", output_count: 1, ingredients: &[("minecraft:orange_wool", 6), ("minecraft:stick", 1)] },
-        Recipe { output: "minecraft:magenta_banner", output_count: 1, ingredients: &[("minecraft:magenta_wool", 6), ("minecraft:stick", 1)] },
-        Recipe { output: "minecraft:light_blue_banner", output_count: 1, ingredients: &[("minecraft:light_blue_wool", 6), ("minecraft:stick", 1)] },
-        Recipe { output: "minecraft:yellow_banner", output_count: 1, ingredients: &[("minecraft:yellow_wool", 6), ("minecraft:stick", 1)] },
-        Recipe { output: "minecraft:lime_banner", output_count: 1, ingredients: &[("minecraft:lime_wool", 6), ("minecraft:stick", 1)] },
-        Recipe { output: "minecraft:pink_banner", output_count: 1, ingredients: &[("minecraft:pink_wool", 6), ("minecraft:stick", 1)] },
-        Recipe { output: "minecraft:gray_banner", output_count: 1, ingredients: &[("minecraft:gray_wool", 6), ("minecraft:stick", 1)] },
-        Recipe { output: "minecraft:light_gray_banner", output_count: 1, ingredients: &[("minecraft:light_gray_wool", 6), ("minecraft:stick", 1)] },
-        Recipe { output: "minecraft:cyan_banner", output_count: 1, ingredients: &[("minecraft:cyan_wool", 6), ("minecraft:stick", 1)] },
-        Recipe { output: "minecraft:purple_banner", output_count: 1, ingredients: &[("minecraft:purple_wool", 6), ("minecraft:stick", 1)] },
-        Recipe { output: "minecraft:blue_banner", output_count: 1, ingredients: &[("minecraft:blue_wool", 6), ("minecraft:stick", 1)] },
-        Recipe { output: "minecraft:brown_banner", output_count: 1, ingredients: &[("minecraft:brown_wool", 6), ("minecraft:stick", 1)] },
-        Recipe { output: "minecraft:green_banner", output_count: 1, ingredients: &[("minecraft:green_wool", 6), ("minecraft:stick", 1)] },
-        Recipe { output: "minecraft:red_banner", output_count: 1, ingredients: &[("minecraft:red_wool", 6), ("minecraft:stick", 1)] },
-        Recipe { output: "minecraft:black_banner", output_count: 1, ingredients: &[("minecraft:black_wool", 6), ("minecraft:stick", 1)] },
+        Recipe { output: "minecraft:white_banner", output_count: 1, ingredients: &[("minecraft:white_wool", 6), ("minecraft:stick", 1)], method: Crafting },
+        Recipe { output: "minecraft:orange_banner", output_count: 1, ingredients: &[("minecraft:orange_wool", 6), ("minecraft:stick", 1)], method: Crafting },
+        Recipe { output: "minecraft:magenta_banner", output_count: 1, ingredients: &[("minecraft:magenta_wool", 6), ("minecraft:stick", 1)], method: Crafting },
+        Recipe { output: "minecraft:light_blue_banner", output_count: 1, ingredients: &[("minecraft:light_blue_wool", 6), ("minecraft:stick", 1)], method: Crafting },
+        Recipe { output: "minecraft:yellow_banner", output_count: 1, ingredients: &[("minecraft:yellow_wool", 6), ("minecraft:stick", 1)], method: Crafting },
+        Recipe { output: "minecraft:lime_banner", output_count: 1, ingredients: &[("minecraft:lime_wool", 6), ("minecraft:stick", 1)], method: Crafting },
+        Recipe { output: "minecraft:pink_banner", output_count: 1, ingredients: &[("minecraft:pink_wool", 6), ("minecraft:stick", 1)], method: Crafting },
+        Recipe { output: "minecraft:gray_banner", output_count: 1, ingredients: &[("minecraft:gray_wool", 6), ("minecraft:stick", 1)], method: Crafting },
+        Recipe { output: "minecraft:light_gray_banner", output_count: 1, ingredients: &[("minecraft:light_gray_wool", 6), ("minecraft:stick", 1)], method: Crafting },
+        Recipe { output: "minecraft:cyan_banner", output_count: 1, ingredients: &[("minecraft:cyan_wool", 6), ("minecraft:stick", 1)], method: Crafting },
+        Recipe { output: "minecraft:purple_banner", output_count: 1, ingredients: &[("minecraft:purple_wool", 6), ("minecraft:stick", 1)], method: Crafting },
+        Recipe { output: "minecraft:blue_banner", output_count: 1, ingredients: &[("minecraft:blue_wool", 6), ("minecraft:stick", 1)], method: Crafting },
+        Recipe { output: "minecraft:brown_banner", output_count: 1, ingredients: &[("minecraft:brown_wool", 6), ("minecraft:stick", 1)], method: Crafting },
+        Recipe { output: "minecraft:green_banner", output_count: 1, ingredients: &[("minecraft:green_wool", 6), ("minecraft:stick", 1)], method: Crafting },
+        Recipe { output: "minecraft:red_banner", output_count: 1, ingredients: &[("minecraft:red_wool", 6), ("minecraft:stick", 1)], method: Crafting },
+        Recipe { output: "minecraft:black_banner", output_count: 1, ingredients: &[("minecraft:black_wool", 6), ("minecraft:stick", 1)], method: Crafting },
 
         // === Carpets (2 wool = 3 carpet) ===
-        Recipe { output: "minecraft:white_carpet", output_count: 3, ingredients: &[("minecraft:white_wool", 2)] },
-        Recipe { output: "minecraft:orange_carpet", output_count: 3, ingredients: &[("minecraft:orange_wool", 2)] },
-        Recipe { output: "minecraft:magenta_carpet", output_count: 3, ingredients: &[("minecraft:magenta_wool", 2)] },
-        Recipe { output: "minecraft:light_blue_carpet", output_count: 3, ingredients: &[("minecraft:light_blue_wool", 2)] },
-        Recipe { output: "minecraft:yellow_carpet", output_count: 3, ingredients: &[("minecraft:yellow_wool", 2)] },
-        Recipe { output: "minecraft:lime_carpet", output_count: 3, ingredients: &[("minecraft:lime_wool", 2)] },
-        Recipe { output: "minecraft:pink_carpet", output_count: 3, ingredients: &[("minecraft:pink_wool", 2)] },
-        Recipe { output: "minecraft:gray_carpet", output_count: 3, ingredients: &[("minecraft:gray_wool", 2)] },
-        Recipe { output: "minecraft:light_gray_carpet", output_count: 3, ingredients: &[("minecraft:light_gray_wool", 2)] },
-        Recipe { output: "minecraft:cyan_carpet", output_count: 3, ingredients: &[("minecraft:cyan_wool", 2)] },
-        Recipe { output: "minecraft:purple_carpet", output_count: 3, ingredients: &[("minecraft:purple_wool", 2)] },
-        Recipe { output: "minecraft:blue_carpet", output_count: 3, ingredients: &[("minecraft:blue_wool", 2)] },
-        Recipe { output: "minecraft:brown_carpet", output_count: 3, ingredients: &[("minecraft:brown_wool", 2)] },
-        Recipe { output: "minecraft:green_carpet", output_count: 3, ingredients: &[("minecraft:green_wool", 2)] },
-        Recipe { output: "minecraft:red_carpet", output_count: 3, ingredients: &[("minecraft:red_wool", 2)] },
-        Recipe { output: "minecraft:black_carpet", output_count: 3, ingredients: &[("minecraft:black_wool", 2)] },
+        Recipe { output: "minecraft:white_carpet", output_count: 3, ingredients: &[("minecraft:white_wool", 2)], method: Crafting },
+        Recipe { output: "minecraft:orange_carpet", output_count: 3, ingredients: &[("minecraft:orange_wool", 2)], method: Crafting },
+        Recipe { output: "minecraft:magenta_carpet", output_count: 3, ingredients: &[("minecraft:magenta_wool", 2)], method: Crafting },
+        Recipe { output: "minecraft:light_blue_carpet", output_count: 3, ingredients: &[("minecraft:light_blue_wool", 2)], method: Crafting },
+        Recipe { output: "minecraft:yellow_carpet", output_count: 3, ingredients: &[("minecraft:yellow_wool", 2)], method: Crafting },
+        Recipe { output: "minecraft:lime_carpet", output_count: 3, ingredients: &[("minecraft:lime_wool", 2)], method: Crafting },
+        Recipe { output: "minecraft:pink_carpet", output_count: 3, ingredients: &[("minecraft:pink_wool", 2)], method: Crafting },
+        Recipe { output: "minecraft:gray_carpet", output_count: 3, ingredients: &[("minecraft:gray_wool", 2)], method: Crafting },
+        Recipe { output: "minecraft:light_gray_carpet", output_count: 3, ingredients: &[("minecraft:light_gray_wool", 2)], method: Crafting },
+        Recipe { output: "minecraft:cyan_carpet", output_count: 3, ingredients: &[("minecraft:cyan_wool", 2)], method: Crafting },
+        Recipe { output: "minecraft:purple_carpet", output_count: 3, ingredients: &[("minecraft:purple_wool", 2)], method: Crafting },
+        Recipe { output: "minecraft:blue_carpet", output_count: 3, ingredients: &[("minecraft:blue_wool", 2)], method: Crafting },
+        Recipe { output: "minecraft:brown_carpet", output_count: 3, ingredients: &[("minecraft:brown_wool", 2)], method: Crafting },
+        Recipe { output: "minecraft:green_carpet", output_count: 3, ingredients: &[("minecraft:green_wool", 2)], method: Crafting },
+        Recipe { output: "minecraft:red_carpet", output_count: 3, ingredients: &[("minecraft:red_wool", 2)], method: Crafting },
+        Recipe { output: "minecraft:black_carpet", output_count: 3, ingredients: &[("minecraft:black_wool", 2)], method: Crafting },
 
         // === Candles (1 string + 1 honeycomb = 1 candle) ===
-        Recipe { output: "minecraft:candle", output_count: 1, ingredients: &[("minecraft:string", 1), ("minecraft:honeycomb", 1)] },
-        Recipe { output: "minecraft:white_candle", output_count: 1, ingredients: &[("minecraft:candle", 1), ("minecraft:white_dye", 1)] },
-        Recipe { output: "minecraft:orange_candle", output_count: 1, ingredients: &[("minecraft:candle", 1), ("minecraft:orange_dye", 1)] },
-        Recipe { output: "minecraft:magenta_candle", output_count: 1, ingredients: &[("minecraft:candle", 1), ("minecraft:magenta_dye", 1)] },
-        Recipe { output: "minecraft:light_blue_candle", output_count: 1, ingredients: &[("minecraft:candle", 1), ("minecraft:light_blue_dye", 1)] },
-        Recipe { output: "minecraft:yellow_candle", output_count: 1, ingredients: &[("minecraft:candle", 1), ("minecraft:yellow_dye", 1)] },
-        Recipe { output: "minecraft:lime_candle", output_count: 1, ingredients: &[("minecraft:candle", 1), ("minecraft:lime_dye", 1)] },
-        Recipe { output: "minecraft:pink_candle", output_count: 1, ingredients: &[("minecraft:candle", 1), ("minecraft:pink_dye", 1)] },
-        Recipe { output: "minecraft:gray_candle", output_count: 1, ingredients: &[("minecraft:candle", 1), ("minecraft:gray_dye", 1)] },
-        Recipe { output: "minecraft:light_gray_candle", output_count: 1, ingredients: &[("minecraft:candle", 1), ("minecraft:light_gray_dye", 1)] },
-        Recipe { output: "minecraft:cyan_candle", output_count: 1, ingredients: &[("minecraft:candle", 1), ("minecraft:cyan_dye", 1)] },
-        Recipe { output: "minecraft:purple_candle", output_count: 1, ingredients: &[("minecraft:candle", 1), ("minecraft:purple_dye", 1)] },
-        Recipe { output: "minecraft:blue_candle", output_count: 1, ingredients: &[("minecraft:candle", 1), ("minecraft:blue_dye", 1)] },
-        Recipe { output: "minecraft:brown_candle", output_count: 1, ingredients: &[("minecraft:candle", 1), ("minecraft:brown_dye", 1)] },
-        Recipe { output: "minecraft:green_candle", output_count: 1, ingredients: &[("minecraft:candle", 1), ("minecraft:green_dye", 1)] },
-        Recipe { output: "minecraft:red_candle", output_count: 1, ingredients: &[("minecraft:candle", 1), ("minecraft:red_dye", 1)] },
-        Recipe { output: "minecraft:black_candle", output_count: 1, ingredients: &[("minecraft:candle", 1), ("minecraft:black_dye", 1)] },
+        Recipe { output: "minecraft:candle", output_count: 1, ingredients: &[("minecraft:string", 1), ("minecraft:honeycomb", 1)], method: Crafting },
+        Recipe { output: "minecraft:white_candle", output_count: 1, ingredients: &[("minecraft:candle", 1), ("minecraft:white_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:orange_candle", output_count: 1, ingredients: &[("minecraft:candle", 1), ("minecraft:orange_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:magenta_candle", output_count: 1, ingredients: &[("minecraft:candle", 1), ("minecraft:magenta_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:light_blue_candle", output_count: 1, ingredients: &[("minecraft:candle", 1), ("minecraft:light_blue_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:yellow_candle", output_count: 1, ingredients: &[("minecraft:candle", 1), ("minecraft:yellow_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:lime_candle", output_count: 1, ingredients: &[("minecraft:candle", 1), ("minecraft:lime_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:pink_candle", output_count: 1, ingredients: &[("minecraft:candle", 1), ("minecraft:pink_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:gray_candle", output_count: 1, ingredients: &[("minecraft:candle", 1), ("minecraft:gray_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:light_gray_candle", output_count: 1, ingredients: &[("minecraft:candle", 1), ("minecraft:light_gray_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:cyan_candle", output_count: 1, ingredients: &[("minecraft:candle", 1), ("minecraft:cyan_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:purple_candle", output_count: 1, ingredients: &[("minecraft:candle", 1), ("minecraft:purple_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:blue_candle", output_count: 1, ingredients: &[("minecraft:candle", 1), ("minecraft:blue_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:brown_candle", output_count: 1, ingredients: &[("minecraft:candle", 1), ("minecraft:brown_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:green_candle", output_count: 1, ingredients: &[("minecraft:candle", 1), ("minecraft:green_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:red_candle", output_count: 1, ingredients: &[("minecraft:candle", 1), ("minecraft:red_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:black_candle", output_count: 1, ingredients: &[("minecraft:candle", 1), ("minecraft:black_dye", 1)], method: Crafting },
 
         // === Shulker Boxes (1 chest + 2 shulker shells = 1 shulker box) ===
-        Recipe { output: "minecraft:shulker_box", output_count: 1, ingredients: &[("minecraft:chest", 1), ("minecraft:shulker_shell", 2)] },
-        Recipe { output: "minecraft:white_shulker_box", output_count: 1, ingredients: &[("minecraft:shulker_box", 1), ("minecraft:white_dye", 1)] },
-        Recipe { output: "minecraft:orange_shulker_box", output_count: 1, ingredients: &[("minecraft:shulker_box", 1), ("minecraft:orange_dye", 1)] },
-        Recipe { output: "minecraft:magenta_shulker_box", output_count: 1, ingredients: &[("minecraft:shulker_box", 1), ("minecraft:magenta_dye", 1)] },
-        Recipe { output: "minecraft:light_blue_shulker_box", output_count: 1, ingredients: &[("minecraft:shulker_box", 1), ("minecraft:light_blue_dye", 1)] },
-        Recipe { output: "minecraft:yellow_shulker_box", output_count: 1, ingredients: &[("minecraft:shulker_box", 1), ("minecraft:yellow_dye", 1)] },
-        Recipe { output: "minecraft:lime_shulker_box", output_count: 1, ingredients: &[("minecraft:shulker_box", 1), ("minecraft:lime_dye", 1)] },
-        Recipe { output: "minecraft:pink_shulker_box", output_count: 1, ingredients: &[("minecraft:shulker_box", 1), ("minecraft:pink_dye", 1)] },
-        Recipe { output: "minecraft:gray_shulker_box", output_count: 1, ingredients: &[("minecraft:shulker_box", 1), ("minecraft:gray_dye", 1)] },
-        Recipe { output: "minecraft:light_gray_shulker_box", output_count: 1, ingredients: &[("minecraft:shulker_box", 1), ("minecraft:light_gray_dye", 1)] },
-        Recipe { output: "minecraft:cyan_shulker_box", output_count: 1, ingredients: &[("minecraft:shulker_box", 1), ("minecraft:cyan_dye", 1)] },
-        Recipe { output: "minecraft:purple_shulker_box", output_count: 1, ingredients: &[("minecraft:shulker_box", 1), ("minecraft:purple_dye", 1)] },
-        Recipe { output: "minecraft:blue_shulker_box", output_count: 1, ingredients: &[("minecraft:shulker_box", 1), ("minecraft:blue_dye", 1)] },
-        Recipe { output: "minecraft:brown_shulker_box", output_count: 1, ingredients: &[("minecraft:shulker_box", 1), ("minecraft:brown_dye", 1)] },
-        Recipe { output: "minecraft:green_shulker_box", output_count: 1, ingredients: &[("minecraft:shulker_box", 1), ("minecraft:green_dye", 1)] },
-        Recipe { output: "minecraft:red_shulker_box", output_count: 1, ingredients: &[("minecraft:shulker_box", 1), ("minecraft:red_dye", 1)] },
-        Recipe { output: "minecraft:black_shulker_box", output_count: 1, ingredients: &[("minecraft:black_dye", 1), ("minecraft:shulker_box", 1)] },
+        Recipe { output: "minecraft:shulker_box", output_count: 1, ingredients: &[("minecraft:chest", 1), ("minecraft:shulker_shell", 2)], method: Crafting },
+        Recipe { output: "minecraft:white_shulker_box", output_count: 1, ingredients: &[("minecraft:shulker_box", 1), ("minecraft:white_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:orange_shulker_box", output_count: 1, ingredients: &[("minecraft:shulker_box", 1), ("minecraft:orange_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:magenta_shulker_box", output_count: 1, ingredients: &[("minecraft:shulker_box", 1), ("minecraft:magenta_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:light_blue_shulker_box", output_count: 1, ingredients: &[("minecraft:shulker_box", 1), ("minecraft:light_blue_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:yellow_shulker_box", output_count: 1, ingredients: &[("minecraft:shulker_box", 1), ("minecraft:yellow_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:lime_shulker_box", output_count: 1, ingredients: &[("minecraft:shulker_box", 1), ("minecraft:lime_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:pink_shulker_box", output_count: 1, ingredients: &[("minecraft:shulker_box", 1), ("minecraft:pink_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:gray_shulker_box", output_count: 1, ingredients: &[("minecraft:shulker_box", 1), ("minecraft:gray_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:light_gray_shulker_box", output_count: 1, ingredients: &[("minecraft:shulker_box", 1), ("minecraft:light_gray_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:cyan_shulker_box", output_count: 1, ingredients: &[("minecraft:shulker_box", 1), ("minecraft:cyan_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:purple_shulker_box", output_count: 1, ingredients: &[("minecraft:shulker_box", 1), ("minecraft:purple_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:blue_shulker_box", output_count: 1, ingredients: &[("minecraft:shulker_box", 1), ("minecraft:blue_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:brown_shulker_box", output_count: 1, ingredients: &[("minecraft:shulker_box", 1), ("minecraft:brown_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:green_shulker_box", output_count: 1, ingredients: &[("minecraft:shulker_box", 1), ("minecraft:green_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:red_shulker_box", output_count: 1, ingredients: &[("minecraft:shulker_box", 1), ("minecraft:red_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:black_shulker_box", output_count: 1, ingredients: &[("minecraft:black_dye", 1), ("minecraft:shulker_box", 1)], method: Crafting },
     ];
 
     recipes.into_iter().map(|r| (r.output, r)).collect()
@@ -1409,6 +1647,14 @@ pub fn is_raw_material(name: &str) -> bool {
         "minecraft:moss_block" |
         "minecraft:sculk" |
         "minecraft:mud" |
+        "minecraft:granite" |
+        "minecraft:diorite" |
+        "minecraft:andesite" |
+
+        // Copper blocks oxidize in the world
+        "minecraft:exposed_copper" |
+        "minecraft:weathered_copper" |
+        "minecraft:oxidized_copper" |
 
         // Dirt/grass
         "minecraft:dirt" |
@@ -1437,8 +1683,6 @@ pub fn is_raw_material(name: &str) -> bool {
         "minecraft:crimson_stem" |
         "minecraft:warped_stem" |
         "minecraft:any_log" |
-        "minecraft:any_planks" |
-        "minecraft:any_slab" |
 
         // Ice/snow
         "minecraft:ice" |
@@ -1454,8 +1698,14 @@ pub fn is_raw_material(name: &str) -> bool {
         "minecraft:honey_bottle" |
         "minecraft:melon_slice" |
         "minecraft:gunpowder" |
-        "minecraft:dried_kelp" |
+        "minecraft:kelp" |
         "minecraft:popped_chorus_fruit" |
+        "minecraft:vine" |
+        "minecraft:ink_sac" |
+        "minecraft:cactus" |
+        "minecraft:cocoa_beans" |
+        "minecraft:poppy" |
+        "minecraft:dandelion" |
 
         // Dyes
         "minecraft:white_dye" |
@@ -1494,153 +1744,198 @@ pub fn is_raw_material(name: &str) -> bool {
     )
 }
 
+/// Dyes broken down to the few sources they're usually farmed from: bone
+/// meal, ink sacs, cactus, lapis, cocoa beans, poppies and dandelions. Mixed
+/// dyes are crafted from the primary ones.
+pub fn get_dye_source_recipes() -> HashMap<&'static str, Recipe> {
+    use CraftingMethod::*;
+    let recipes = vec![
+        Recipe { output: "minecraft:white_dye", output_count: 1, ingredients: &[("minecraft:bone_meal", 1)], method: Crafting },
+        Recipe { output: "minecraft:black_dye", output_count: 1, ingredients: &[("minecraft:ink_sac", 1)], method: Crafting },
+        Recipe { output: "minecraft:green_dye", output_count: 1, ingredients: &[("minecraft:cactus", 1)], method: Smelting },
+        Recipe { output: "minecraft:blue_dye", output_count: 1, ingredients: &[("minecraft:lapis_lazuli", 1)], method: Crafting },
+        Recipe { output: "minecraft:brown_dye", output_count: 1, ingredients: &[("minecraft:cocoa_beans", 1)], method: Crafting },
+        Recipe { output: "minecraft:red_dye", output_count: 1, ingredients: &[("minecraft:poppy", 1)], method: Crafting },
+        Recipe { output: "minecraft:yellow_dye", output_count: 1, ingredients: &[("minecraft:dandelion", 1)], method: Crafting },
+        Recipe { output: "minecraft:orange_dye", output_count: 2, ingredients: &[("minecraft:red_dye", 1), ("minecraft:yellow_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:pink_dye", output_count: 2, ingredients: &[("minecraft:red_dye", 1), ("minecraft:white_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:lime_dye", output_count: 2, ingredients: &[("minecraft:green_dye", 1), ("minecraft:white_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:cyan_dye", output_count: 2, ingredients: &[("minecraft:blue_dye", 1), ("minecraft:green_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:light_blue_dye", output_count: 2, ingredients: &[("minecraft:blue_dye", 1), ("minecraft:white_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:purple_dye", output_count: 2, ingredients: &[("minecraft:blue_dye", 1), ("minecraft:red_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:magenta_dye", output_count: 2, ingredients: &[("minecraft:purple_dye", 1), ("minecraft:pink_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:gray_dye", output_count: 2, ingredients: &[("minecraft:black_dye", 1), ("minecraft:white_dye", 1)], method: Crafting },
+        Recipe { output: "minecraft:light_gray_dye", output_count: 2, ingredients: &[("minecraft:gray_dye", 1), ("minecraft:white_dye", 1)], method: Crafting },
+    ];
+
+    recipes.into_iter().map(|r| (r.output, r)).collect()
+}
+
+/// Flowers, for the "flowers needed" summary
+pub fn is_flower(name: &str) -> bool {
+    matches!(name.strip_prefix("minecraft:").unwrap_or(name),
+        "dandelion" | "poppy" | "blue_orchid" | "allium" | "azure_bluet" | "red_tulip" | "orange_tulip"
+        | "white_tulip" | "pink_tulip" | "oxeye_daisy" | "cornflower" | "lily_of_the_valley" | "wither_rose"
+        | "torchflower" | "sunflower" | "lilac" | "rose_bush" | "peony" | "pitcher_plant" | "pink_petals")
+}
+
+/// Name to show for a material: without the `minecraft:` namespace, and the
+/// `any_log` placeholder for recipes that take any kind of wood spelled out
+pub fn material_label(name: &str) -> String {
+    match name {
+        "minecraft:any_log" => "any wood (logs)".to_string(),
+        _ => name.strip_prefix("minecraft:").unwrap_or(name).to_string(),
+    }
+}
+
 /// Get stonecutter recipes (1:1 ratios for stairs/slabs)
 /// Stonecutter is more efficient than crafting table
 pub fn get_stonecutter_recipes() -> HashMap<&'static str, Recipe> {
+    use CraftingMethod::*;
     let recipes: Vec<Recipe> = vec![
         // Stone stairs and slabs (1:1 with stonecutter)
-        Recipe { output: "minecraft:stone_stairs", output_count: 1, ingredients: &[("minecraft:stone", 1)] },
-        Recipe { output: "minecraft:stone_slab", output_count: 2, ingredients: &[("minecraft:stone", 1)] },
-        Recipe { output: "minecraft:cobblestone_stairs", output_count: 1, ingredients: &[("minecraft:cobblestone", 1)] },
-        Recipe { output: "minecraft:cobblestone_slab", output_count: 2, ingredients: &[("minecraft:cobblestone", 1)] },
-        Recipe { output: "minecraft:cobblestone_wall", output_count: 1, ingredients: &[("minecraft:cobblestone", 1)] },
-        Recipe { output: "minecraft:mossy_cobblestone_stairs", output_count: 1, ingredients: &[("minecraft:mossy_cobblestone", 1)] },
-        Recipe { output: "minecraft:mossy_cobblestone_slab", output_count: 2, ingredients: &[("minecraft:mossy_cobblestone", 1)] },
-        Recipe { output: "minecraft:mossy_cobblestone_wall", output_count: 1, ingredients: &[("minecraft:mossy_cobblestone", 1)] },
-        Recipe { output: "minecraft:stone_brick_stairs", output_count: 1, ingredients: &[("minecraft:stone_bricks", 1)] },
-        Recipe { output: "minecraft:stone_brick_slab", output_count: 2, ingredients: &[("minecraft:stone_bricks", 1)] },
-        Recipe { output: "minecraft:stone_brick_wall", output_count: 1, ingredients: &[("minecraft:stone_bricks", 1)] },
-        Recipe { output: "minecraft:mossy_stone_brick_stairs", output_count: 1, ingredients: &[("minecraft:mossy_stone_bricks", 1)] },
-        Recipe { output: "minecraft:mossy_stone_brick_slab", output_count: 2, ingredients: &[("minecraft:mossy_stone_bricks", 1)] },
-        Recipe { output: "minecraft:mossy_stone_brick_wall", output_count: 1, ingredients: &[("minecraft:mossy_stone_bricks", 1)] },
-        Recipe { output: "minecraft:smooth_stone_slab", output_count: 2, ingredients: &[("minecraft:smooth_stone", 1)] },
+        Recipe { output: "minecraft:stone_stairs", output_count: 1, ingredients: &[("minecraft:stone", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:stone_slab", output_count: 2, ingredients: &[("minecraft:stone", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:cobblestone_stairs", output_count: 1, ingredients: &[("minecraft:cobblestone", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:cobblestone_slab", output_count: 2, ingredients: &[("minecraft:cobblestone", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:cobblestone_wall", output_count: 1, ingredients: &[("minecraft:cobblestone", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:mossy_cobblestone_stairs", output_count: 1, ingredients: &[("minecraft:mossy_cobblestone", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:mossy_cobblestone_slab", output_count: 2, ingredients: &[("minecraft:mossy_cobblestone", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:mossy_cobblestone_wall", output_count: 1, ingredients: &[("minecraft:mossy_cobblestone", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:stone_brick_stairs", output_count: 1, ingredients: &[("minecraft:stone_bricks", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:stone_brick_slab", output_count: 2, ingredients: &[("minecraft:stone_bricks", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:stone_brick_wall", output_count: 1, ingredients: &[("minecraft:stone_bricks", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:mossy_stone_brick_stairs", output_count: 1, ingredients: &[("minecraft:mossy_stone_bricks", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:mossy_stone_brick_slab", output_count: 2, ingredients: &[("minecraft:mossy_stone_bricks", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:mossy_stone_brick_wall", output_count: 1, ingredients: &[("minecraft:mossy_stone_bricks", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:smooth_stone_slab", output_count: 2, ingredients: &[("minecraft:smooth_stone", 1)], method: Stonecutting },
 
         // Granite
-        Recipe { output: "minecraft:granite_stairs", output_count: 1, ingredients: &[("minecraft:granite", 1)] },
-        Recipe { output: "minecraft:granite_slab", output_count: 2, ingredients: &[("minecraft:granite", 1)] },
-        Recipe { output: "minecraft:granite_wall", output_count: 1, ingredients: &[("minecraft:granite", 1)] },
-        Recipe { output: "minecraft:polished_granite_stairs", output_count: 1, ingredients: &[("minecraft:polished_granite", 1)] },
-        Recipe { output: "minecraft:polished_granite_slab", output_count: 2, ingredients: &[("minecraft:polished_granite", 1)] },
+        Recipe { output: "minecraft:granite_stairs", output_count: 1, ingredients: &[("minecraft:granite", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:granite_slab", output_count: 2, ingredients: &[("minecraft:granite", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:granite_wall", output_count: 1, ingredients: &[("minecraft:granite", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:polished_granite_stairs", output_count: 1, ingredients: &[("minecraft:polished_granite", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:polished_granite_slab", output_count: 2, ingredients: &[("minecraft:polished_granite", 1)], method: Stonecutting },
 
         // Diorite
-        Recipe { output: "minecraft:diorite_stairs", output_count: 1, ingredients: &[("minecraft:diorite", 1)] },
-        Recipe { output: "minecraft:diorite_slab", output_count: 2, ingredients: &[("minecraft:diorite", 1)] },
-        Recipe { output: "minecraft:diorite_wall", output_count: 1, ingredients: &[("minecraft:diorite", 1)] },
-        Recipe { output: "minecraft:polished_diorite_stairs", output_count: 1, ingredients: &[("minecraft:polished_diorite", 1)] },
-        Recipe { output: "minecraft:polished_diorite_slab", output_count: 2, ingredients: &[("minecraft:polished_diorite", 1)] },
+        Recipe { output: "minecraft:diorite_stairs", output_count: 1, ingredients: &[("minecraft:diorite", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:diorite_slab", output_count: 2, ingredients: &[("minecraft:diorite", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:diorite_wall", output_count: 1, ingredients: &[("minecraft:diorite", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:polished_diorite_stairs", output_count: 1, ingredients: &[("minecraft:polished_diorite", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:polished_diorite_slab", output_count: 2, ingredients: &[("minecraft:polished_diorite", 1)], method: Stonecutting },
 
         // Andesite
-        Recipe { output: "minecraft:andesite_stairs", output_count: 1, ingredients: &[("minecraft:andesite", 1)] },
-        Recipe { output: "minecraft:andesite_slab", output_count: 2, ingredients: &[("minecraft:andesite", 1)] },
-        Recipe { output: "minecraft:andesite_wall", output_count: 1, ingredients: &[("minecraft:andesite", 1)] },
-        Recipe { output: "minecraft:polished_andesite_stairs", output_count: 1, ingredients: &[("minecraft:polished_andesite", 1)] },
-        Recipe { output: "minecraft:polished_andesite_slab", output_count: 2, ingredients: &[("minecraft:polished_andesite", 1)] },
+        Recipe { output: "minecraft:andesite_stairs", output_count: 1, ingredients: &[("minecraft:andesite", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:andesite_slab", output_count: 2, ingredients: &[("minecraft:andesite", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:andesite_wall", output_count: 1, ingredients: &[("minecraft:andesite", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:polished_andesite_stairs", output_count: 1, ingredients: &[("minecraft:polished_andesite", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:polished_andesite_slab", output_count: 2, ingredients: &[("minecraft:polished_andesite", 1)], method: Stonecutting },
 
         // Deepslate
-        Recipe { output: "minecraft:cobbled_deepslate_stairs", output_count: 1, ingredients: &[("minecraft:cobbled_deepslate", 1)] },
-        Recipe { output: "minecraft:cobbled_deepslate_slab", output_count: 2, ingredients: &[("minecraft:cobbled_deepslate", 1)] },
-        Recipe { output: "minecraft:cobbled_deepslate_wall", output_count: 1, ingredients: &[("minecraft:cobbled_deepslate", 1)] },
-        Recipe { output: "minecraft:polished_deepslate_stairs", output_count: 1, ingredients: &[("minecraft:polished_deepslate", 1)] },
-        Recipe { output: "minecraft:polished_deepslate_slab", output_count: 2, ingredients: &[("minecraft:polished_deepslate", 1)] },
-        Recipe { output: "minecraft:polished_deepslate_wall", output_count: 1, ingredients: &[("minecraft:polished_deepslate", 1)] },
-        Recipe { output: "minecraft:deepslate_brick_stairs", output_count: 1, ingredients: &[("minecraft:deepslate_bricks", 1)] },
-        Recipe { output: "minecraft:deepslate_brick_slab", output_count: 2, ingredients: &[("minecraft:deepslate_bricks", 1)] },
-        Recipe { output: "minecraft:deepslate_brick_wall", output_count: 1, ingredients: &[("minecraft:deepslate_bricks", 1)] },
-        Recipe { output: "minecraft:deepslate_tile_stairs", output_count: 1, ingredients: &[("minecraft:deepslate_tiles", 1)] },
-        Recipe { output: "minecraft:deepslate_tile_slab", output_count: 2, ingredients: &[("minecraft:deepslate_tiles", 1)] },
-        Recipe { output: "minecraft:deepslate_tile_wall", output_count: 1, ingredients: &[("minecraft:deepslate_tiles", 1)] },
+        Recipe { output: "minecraft:cobbled_deepslate_stairs", output_count: 1, ingredients: &[("minecraft:cobbled_deepslate", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:cobbled_deepslate_slab", output_count: 2, ingredients: &[("minecraft:cobbled_deepslate", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:cobbled_deepslate_wall", output_count: 1, ingredients: &[("minecraft:cobbled_deepslate", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:polished_deepslate_stairs", output_count: 1, ingredients: &[("minecraft:polished_deepslate", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:polished_deepslate_slab", output_count: 2, ingredients: &[("minecraft:polished_deepslate", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:polished_deepslate_wall", output_count: 1, ingredients: &[("minecraft:polished_deepslate", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:deepslate_brick_stairs", output_count: 1, ingredients: &[("minecraft:deepslate_bricks", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:deepslate_brick_slab", output_count: 2, ingredients: &[("minecraft:deepslate_bricks", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:deepslate_brick_wall", output_count: 1, ingredients: &[("minecraft:deepslate_bricks", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:deepslate_tile_stairs", output_count: 1, ingredients: &[("minecraft:deepslate_tiles", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:deepslate_tile_slab", output_count: 2, ingredients: &[("minecraft:deepslate_tiles", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:deepslate_tile_wall", output_count: 1, ingredients: &[("minecraft:deepslate_tiles", 1)], method: Stonecutting },
 
         // Blackstone
-        Recipe { output: "minecraft:blackstone_stairs", output_count: 1, ingredients: &[("minecraft:blackstone", 1)] },
-        Recipe { output: "minecraft:blackstone_slab", output_count: 2, ingredients: &[("minecraft:blackstone", 1)] },
-        Recipe { output: "minecraft:blackstone_wall", output_count: 1, ingredients: &[("minecraft:blackstone", 1)] },
-        Recipe { output: "minecraft:polished_blackstone_stairs", output_count: 1, ingredients: &[("minecraft:polished_blackstone", 1)] },
-        Recipe { output: "minecraft:polished_blackstone_slab", output_count: 2, ingredients: &[("minecraft:polished_blackstone", 1)] },
-        Recipe { output: "minecraft:polished_blackstone_wall", output_count: 1, ingredients: &[("minecraft:polished_blackstone", 1)] },
-        Recipe { output: "minecraft:polished_blackstone_brick_stairs", output_count: 1, ingredients: &[("minecraft:polished_blackstone_bricks", 1)] },
-        Recipe { output: "minecraft:polished_blackstone_brick_slab", output_count: 2, ingredients: &[("minecraft:polished_blackstone_bricks", 1)] },
-        Recipe { output: "minecraft:polished_blackstone_brick_wall", output_count: 1, ingredients: &[("minecraft:polished_blackstone_bricks", 1)] },
+        Recipe { output: "minecraft:blackstone_stairs", output_count: 1, ingredients: &[("minecraft:blackstone", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:blackstone_slab", output_count: 2, ingredients: &[("minecraft:blackstone", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:blackstone_wall", output_count: 1, ingredients: &[("minecraft:blackstone", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:polished_blackstone_stairs", output_count: 1, ingredients: &[("minecraft:polished_blackstone", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:polished_blackstone_slab", output_count: 2, ingredients: &[("minecraft:polished_blackstone", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:polished_blackstone_wall", output_count: 1, ingredients: &[("minecraft:polished_blackstone", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:polished_blackstone_brick_stairs", output_count: 1, ingredients: &[("minecraft:polished_blackstone_bricks", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:polished_blackstone_brick_slab", output_count: 2, ingredients: &[("minecraft:polished_blackstone_bricks", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:polished_blackstone_brick_wall", output_count: 1, ingredients: &[("minecraft:polished_blackstone_bricks", 1)], method: Stonecutting },
 
         // Nether bricks
-        Recipe { output: "minecraft:nether_brick_stairs", output_count: 1, ingredients: &[("minecraft:nether_bricks", 1)] },
-        Recipe { output: "minecraft:nether_brick_slab", output_count: 2, ingredients: &[("minecraft:nether_bricks", 1)] },
-        Recipe { output: "minecraft:nether_brick_wall", output_count: 1, ingredients: &[("minecraft:nether_bricks", 1)] },
-        Recipe { output: "minecraft:red_nether_brick_stairs", output_count: 1, ingredients: &[("minecraft:red_nether_bricks", 1)] },
-        Recipe { output: "minecraft:red_nether_brick_slab", output_count: 2, ingredients: &[("minecraft:red_nether_bricks", 1)] },
-        Recipe { output: "minecraft:red_nether_brick_wall", output_count: 1, ingredients: &[("minecraft:red_nether_bricks", 1)] },
+        Recipe { output: "minecraft:nether_brick_stairs", output_count: 1, ingredients: &[("minecraft:nether_bricks", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:nether_brick_slab", output_count: 2, ingredients: &[("minecraft:nether_bricks", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:nether_brick_wall", output_count: 1, ingredients: &[("minecraft:nether_bricks", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:red_nether_brick_stairs", output_count: 1, ingredients: &[("minecraft:red_nether_bricks", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:red_nether_brick_slab", output_count: 2, ingredients: &[("minecraft:red_nether_bricks", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:red_nether_brick_wall", output_count: 1, ingredients: &[("minecraft:red_nether_bricks", 1)], method: Stonecutting },
 
         // Quartz
-        Recipe { output: "minecraft:quartz_stairs", output_count: 1, ingredients: &[("minecraft:quartz_block", 1)] },
-        Recipe { output: "minecraft:quartz_slab", output_count: 2, ingredients: &[("minecraft:quartz_block", 1)] },
-        Recipe { output: "minecraft:smooth_quartz_stairs", output_count: 1, ingredients: &[("minecraft:smooth_quartz", 1)] },
-        Recipe { output: "minecraft:smooth_quartz_slab", output_count: 2, ingredients: &[("minecraft:smooth_quartz", 1)] },
+        Recipe { output: "minecraft:quartz_stairs", output_count: 1, ingredients: &[("minecraft:quartz_block", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:quartz_slab", output_count: 2, ingredients: &[("minecraft:quartz_block", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:smooth_quartz_stairs", output_count: 1, ingredients: &[("minecraft:smooth_quartz", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:smooth_quartz_slab", output_count: 2, ingredients: &[("minecraft:smooth_quartz", 1)], method: Stonecutting },
 
         // Bricks
-        Recipe { output: "minecraft:brick_stairs", output_count: 1, ingredients: &[("minecraft:bricks", 1)] },
-        Recipe { output: "minecraft:brick_slab", output_count: 2, ingredients: &[("minecraft:bricks", 1)] },
-        Recipe { output: "minecraft:brick_wall", output_count: 1, ingredients: &[("minecraft:bricks", 1)] },
-        Recipe { output: "minecraft:mud_brick_stairs", output_count: 1, ingredients: &[("minecraft:mud_bricks", 1)] },
-        Recipe { output: "minecraft:mud_brick_slab", output_count: 2, ingredients: &[("minecraft:mud_bricks", 1)] },
-        Recipe { output: "minecraft:mud_brick_wall", output_count: 1, ingredients: &[("minecraft:mud_bricks", 1)] },
+        Recipe { output: "minecraft:brick_stairs", output_count: 1, ingredients: &[("minecraft:bricks", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:brick_slab", output_count: 2, ingredients: &[("minecraft:bricks", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:brick_wall", output_count: 1, ingredients: &[("minecraft:bricks", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:mud_brick_stairs", output_count: 1, ingredients: &[("minecraft:mud_bricks", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:mud_brick_slab", output_count: 2, ingredients: &[("minecraft:mud_bricks", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:mud_brick_wall", output_count: 1, ingredients: &[("minecraft:mud_bricks", 1)], method: Stonecutting },
 
         // Sandstone
-        Recipe { output: "minecraft:sandstone_stairs", output_count: 1, ingredients: &[("minecraft:sandstone", 1)] },
-        Recipe { output: "minecraft:sandstone_slab", output_count: 2, ingredients: &[("minecraft:sandstone", 1)] },
-        Recipe { output: "minecraft:sandstone_wall", output_count: 1, ingredients: &[("minecraft:sandstone", 1)] },
-        Recipe { output: "minecraft:smooth_sandstone_stairs", output_count: 1, ingredients: &[("minecraft:smooth_sandstone", 1)] },
-        Recipe { output: "minecraft:smooth_sandstone_slab", output_count: 2, ingredients: &[("minecraft:smooth_sandstone", 1)] },
-        Recipe { output: "minecraft:red_sandstone_stairs", output_count: 1, ingredients: &[("minecraft:red_sandstone", 1)] },
-        Recipe { output: "minecraft:red_sandstone_slab", output_count: 2, ingredients: &[("minecraft:red_sandstone", 1)] },
-        Recipe { output: "minecraft:red_sandstone_wall", output_count: 1, ingredients: &[("minecraft:red_sandstone", 1)] },
-        Recipe { output: "minecraft:smooth_red_sandstone_stairs", output_count: 1, ingredients: &[("minecraft:smooth_red_sandstone", 1)] },
-        Recipe { output: "minecraft:smooth_red_sandstone_slab", output_count: 2, ingredients: &[("minecraft:smooth_red_sandstone", 1)] },
+        Recipe { output: "minecraft:sandstone_stairs", output_count: 1, ingredients: &[("minecraft:sandstone", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:sandstone_slab", output_count: 2, ingredients: &[("minecraft:sandstone", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:sandstone_wall", output_count: 1, ingredients: &[("minecraft:sandstone", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:smooth_sandstone_stairs", output_count: 1, ingredients: &[("minecraft:smooth_sandstone", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:smooth_sandstone_slab", output_count: 2, ingredients: &[("minecraft:smooth_sandstone", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:red_sandstone_stairs", output_count: 1, ingredients: &[("minecraft:red_sandstone", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:red_sandstone_slab", output_count: 2, ingredients: &[("minecraft:red_sandstone", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:red_sandstone_wall", output_count: 1, ingredients: &[("minecraft:red_sandstone", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:smooth_red_sandstone_stairs", output_count: 1, ingredients: &[("minecraft:smooth_red_sandstone", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:smooth_red_sandstone_slab", output_count: 2, ingredients: &[("minecraft:smooth_red_sandstone", 1)], method: Stonecutting },
 
         // Prismarine
-        Recipe { output: "minecraft:prismarine_stairs", output_count: 1, ingredients: &[("minecraft:prismarine", 1)] },
-        Recipe { output: "minecraft:prismarine_slab", output_count: 2, ingredients: &[("minecraft:prismarine", 1)] },
-        Recipe { output: "minecraft:prismarine_wall", output_count: 1, ingredients: &[("minecraft:prismarine", 1)] },
-        Recipe { output: "minecraft:prismarine_brick_stairs", output_count: 1, ingredients: &[("minecraft:prismarine_bricks", 1)] },
-        Recipe { output: "minecraft:prismarine_brick_slab", output_count: 2, ingredients: &[("minecraft:prismarine_bricks", 1)] },
-        Recipe { output: "minecraft:dark_prismarine_stairs", output_count: 1, ingredients: &[("minecraft:dark_prismarine", 1)] },
-        Recipe { output: "minecraft:dark_prismarine_slab", output_count: 2, ingredients: &[("minecraft:dark_prismarine", 1)] },
+        Recipe { output: "minecraft:prismarine_stairs", output_count: 1, ingredients: &[("minecraft:prismarine", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:prismarine_slab", output_count: 2, ingredients: &[("minecraft:prismarine", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:prismarine_wall", output_count: 1, ingredients: &[("minecraft:prismarine", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:prismarine_brick_stairs", output_count: 1, ingredients: &[("minecraft:prismarine_bricks", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:prismarine_brick_slab", output_count: 2, ingredients: &[("minecraft:prismarine_bricks", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:dark_prismarine_stairs", output_count: 1, ingredients: &[("minecraft:dark_prismarine", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:dark_prismarine_slab", output_count: 2, ingredients: &[("minecraft:dark_prismarine", 1)], method: Stonecutting },
 
         // End stone
-        Recipe { output: "minecraft:end_stone_brick_stairs", output_count: 1, ingredients: &[("minecraft:end_stone_bricks", 1)] },
-        Recipe { output: "minecraft:end_stone_brick_slab", output_count: 2, ingredients: &[("minecraft:end_stone_bricks", 1)] },
-        Recipe { output: "minecraft:end_stone_brick_wall", output_count: 1, ingredients: &[("minecraft:end_stone_bricks", 1)] },
+        Recipe { output: "minecraft:end_stone_brick_stairs", output_count: 1, ingredients: &[("minecraft:end_stone_bricks", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:end_stone_brick_slab", output_count: 2, ingredients: &[("minecraft:end_stone_bricks", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:end_stone_brick_wall", output_count: 1, ingredients: &[("minecraft:end_stone_bricks", 1)], method: Stonecutting },
 
         // Purpur
-        Recipe { output: "minecraft:purpur_stairs", output_count: 1, ingredients: &[("minecraft:purpur_block", 1)] },
-        Recipe { output: "minecraft:purpur_slab", output_count: 2, ingredients: &[("minecraft:purpur_block", 1)] },
+        Recipe { output: "minecraft:purpur_stairs", output_count: 1, ingredients: &[("minecraft:purpur_block", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:purpur_slab", output_count: 2, ingredients: &[("minecraft:purpur_block", 1)], method: Stonecutting },
 
         // Copper (cut copper)
-        Recipe { output: "minecraft:cut_copper_stairs", output_count: 1, ingredients: &[("minecraft:cut_copper", 1)] },
-        Recipe { output: "minecraft:cut_copper_slab", output_count: 2, ingredients: &[("minecraft:cut_copper", 1)] },
-        Recipe { output: "minecraft:exposed_cut_copper_stairs", output_count: 1, ingredients: &[("minecraft:exposed_cut_copper", 1)] },
-        Recipe { output: "minecraft:exposed_cut_copper_slab", output_count: 2, ingredients: &[("minecraft:exposed_cut_copper", 1)] },
-        Recipe { output: "minecraft:weathered_cut_copper_stairs", output_count: 1, ingredients: &[("minecraft:weathered_cut_copper", 1)] },
-        Recipe { output: "minecraft:weathered_cut_copper_slab", output_count: 2, ingredients: &[("minecraft:weathered_cut_copper", 1)] },
-        Recipe { output: "minecraft:oxidized_cut_copper_stairs", output_count: 1, ingredients: &[("minecraft:oxidized_cut_copper", 1)] },
-        Recipe { output: "minecraft:oxidized_cut_copper_slab", output_count: 2, ingredients: &[("minecraft:oxidized_cut_copper", 1)] },
-        Recipe { output: "minecraft:waxed_cut_copper_stairs", output_count: 1, ingredients: &[("minecraft:waxed_cut_copper", 1)] },
-        Recipe { output: "minecraft:waxed_cut_copper_slab", output_count: 2, ingredients: &[("minecraft:waxed_cut_copper", 1)] },
-        Recipe { output: "minecraft:waxed_exposed_cut_copper_stairs", output_count: 1, ingredients: &[("minecraft:waxed_exposed_cut_copper", 1)] },
-        Recipe { output: "minecraft:waxed_exposed_cut_copper_slab", output_count: 2, ingredients: &[("minecraft:waxed_exposed_cut_copper", 1)] },
-        Recipe { output: "minecraft:waxed_weathered_cut_copper_stairs", output_count: 1, ingredients: &[("minecraft:waxed_weathered_cut_copper", 1)] },
-        Recipe { output: "minecraft:waxed_weathered_cut_copper_slab", output_count: 2, ingredients: &[("minecraft:waxed_weathered_cut_copper", 1)] },
-        Recipe { output: "minecraft:waxed_oxidized_cut_copper_stairs", output_count: 1, ingredients: &[("minecraft:waxed_oxidized_cut_copper", 1)] },
-        Recipe { output: "minecraft:waxed_oxidized_cut_copper_slab", output_count: 2, ingredients: &[("minecraft:waxed_oxidized_cut_copper", 1)] },
+        Recipe { output: "minecraft:cut_copper_stairs", output_count: 1, ingredients: &[("minecraft:cut_copper", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:cut_copper_slab", output_count: 2, ingredients: &[("minecraft:cut_copper", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:exposed_cut_copper_stairs", output_count: 1, ingredients: &[("minecraft:exposed_cut_copper", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:exposed_cut_copper_slab", output_count: 2, ingredients: &[("minecraft:exposed_cut_copper", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:weathered_cut_copper_stairs", output_count: 1, ingredients: &[("minecraft:weathered_cut_copper", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:weathered_cut_copper_slab", output_count: 2, ingredients: &[("minecraft:weathered_cut_copper", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:oxidized_cut_copper_stairs", output_count: 1, ingredients: &[("minecraft:oxidized_cut_copper", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:oxidized_cut_copper_slab", output_count: 2, ingredients: &[("minecraft:oxidized_cut_copper", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:waxed_cut_copper_stairs", output_count: 1, ingredients: &[("minecraft:waxed_cut_copper", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:waxed_cut_copper_slab", output_count: 2, ingredients: &[("minecraft:waxed_cut_copper", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:waxed_exposed_cut_copper_stairs", output_count: 1, ingredients: &[("minecraft:waxed_exposed_cut_copper", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:waxed_exposed_cut_copper_slab", output_count: 2, ingredients: &[("minecraft:waxed_exposed_cut_copper", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:waxed_weathered_cut_copper_stairs", output_count: 1, ingredients: &[("minecraft:waxed_weathered_cut_copper", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:waxed_weathered_cut_copper_slab", output_count: 2, ingredients: &[("minecraft:waxed_weathered_cut_copper", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:waxed_oxidized_cut_copper_stairs", output_count: 1, ingredients: &[("minecraft:waxed_oxidized_cut_copper", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:waxed_oxidized_cut_copper_slab", output_count: 2, ingredients: &[("minecraft:waxed_oxidized_cut_copper", 1)], method: Stonecutting },
 
         // Tuff
-        Recipe { output: "minecraft:tuff_stairs", output_count: 1, ingredients: &[("minecraft:tuff", 1)] },
-        Recipe { output: "minecraft:tuff_slab", output_count: 2, ingredients: &[("minecraft:tuff", 1)] },
-        Recipe { output: "minecraft:tuff_wall", output_count: 1, ingredients: &[("minecraft:tuff", 1)] },
-        Recipe { output: "minecraft:polished_tuff_stairs", output_count: 1, ingredients: &[("minecraft:polished_tuff", 1)] },
-        Recipe { output: "minecraft:polished_tuff_slab", output_count: 2, ingredients: &[("minecraft:polished_tuff", 1)] },
-        Recipe { output: "minecraft:polished_tuff_wall", output_count: 1, ingredients: &[("minecraft:polished_tuff", 1)] },
-        Recipe { output: "minecraft:tuff_brick_stairs", output_count: 1, ingredients: &[("minecraft:tuff_bricks", 1)] },
-        Recipe { output: "minecraft:tuff_brick_slab", output_count: 2, ingredients: &[("minecraft:tuff_bricks", 1)] },
-        Recipe { output: "minecraft:tuff_brick_wall", output_count: 1, ingredients: &[("minecraft:tuff_bricks", 1)] },
+        Recipe { output: "minecraft:tuff_stairs", output_count: 1, ingredients: &[("minecraft:tuff", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:tuff_slab", output_count: 2, ingredients: &[("minecraft:tuff", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:tuff_wall", output_count: 1, ingredients: &[("minecraft:tuff", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:polished_tuff_stairs", output_count: 1, ingredients: &[("minecraft:polished_tuff", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:polished_tuff_slab", output_count: 2, ingredients: &[("minecraft:polished_tuff", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:polished_tuff_wall", output_count: 1, ingredients: &[("minecraft:polished_tuff", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:tuff_brick_stairs", output_count: 1, ingredients: &[("minecraft:tuff_bricks", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:tuff_brick_slab", output_count: 2, ingredients: &[("minecraft:tuff_bricks", 1)], method: Stonecutting },
+        Recipe { output: "minecraft:tuff_brick_wall", output_count: 1, ingredients: &[("minecraft:tuff_bricks", 1)], method: Stonecutting },
     ];
 
     recipes.into_iter().map(|r| (r.output, r)).collect()
@@ -1654,18 +1949,134 @@ pub fn calculate_materials(blocks: &HashMap<String, usize>) -> HashMap<String, f
 /// Calculate raw materials with options
 /// - `use_stonecutter`: If true, uses stonecutter recipes (1:1 ratios) for stairs/slabs/walls
 pub fn calculate_materials_with_options(blocks: &HashMap<String, usize>, use_stonecutter: bool) -> HashMap<String, f64> {
-    let mut recipes = get_recipes();
+    calculate_materials_with(blocks, MaterialOptions::new().with_stonecutter(use_stonecutter))
+}
 
-    // Override with stonecutter recipes if enabled
-    if use_stonecutter {
-        for (name, recipe) in get_stonecutter_recipes() {
-            recipes.insert(name, recipe);
+/// How far materials get broken down
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MaterialOptions {
+    /// Use stonecutter recipes (1:1 ratios) for stairs/slabs/walls
+    pub stonecutter: bool,
+    /// Break dyes down to their farmable sources (see [`get_dye_source_recipes`])
+    pub consolidate_dyes: bool,
+}
+
+impl MaterialOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_stonecutter(mut self, stonecutter: bool) -> Self {
+        self.stonecutter = stonecutter;
+        self
+    }
+
+    pub fn with_consolidated_dyes(mut self, consolidate: bool) -> Self {
+        self.consolidate_dyes = consolidate;
+        self
+    }
+
+    /// Whether recipes made with `method` are used
+    pub fn allows(self, method: CraftingMethod) -> bool {
+        method != CraftingMethod::Stonecutting || self.stonecutter
+    }
+}
+
+/// Calculate raw materials, broken down as `options` say
+pub fn calculate_materials_with(blocks: &HashMap<String, usize>, options: MaterialOptions) -> HashMap<String, f64> {
+    material_report(blocks, options).materials
+}
+
+/// Raw materials, and the items that went into them unbroken for want of a recipe
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MaterialReport {
+    /// Raw material name -> amount (fractional until rounded)
+    pub materials: HashMap<String, f64>,
+    /// Items that aren't raw materials but have no known recipe; they're
+    /// counted in `materials` as they are, so the totals are incomplete
+    pub unknown: BTreeSet<String>,
+    /// Furnace operations on the way (see [`Fuel::needed`])
+    pub smelts: f64,
+}
+
+/// What keeps the furnaces going
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Fuel {
+    /// Coal or charcoal, 8 items each (a block of coal does 80)
+    #[default]
+    Coal,
+    /// Lava buckets, 100 items each
+    Lava,
+    /// Dried kelp blocks, 20 items each
+    Kelp,
+}
+
+impl Fuel {
+    pub const ALL: [Fuel; 3] = [Fuel::Coal, Fuel::Lava, Fuel::Kelp];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Fuel::Coal => "coal",
+            Fuel::Lava => "lava",
+            Fuel::Kelp => "kelp",
+        }
+    }
+
+    /// The fuel item
+    pub fn item(self) -> &'static str {
+        match self {
+            Fuel::Coal => "minecraft:coal",
+            Fuel::Lava => "minecraft:lava_bucket",
+            Fuel::Kelp => "minecraft:dried_kelp_block",
         }
     }
 
+    /// Items one unit smelts
+    pub fn smelts_per_unit(self) -> u32 {
+        match self {
+            Fuel::Coal => 8,
+            Fuel::Lava => 100,
+            Fuel::Kelp => 20,
+        }
+    }
+
+    /// Whole units needed for `smelts` furnace operations
+    pub fn needed(self, smelts: f64) -> u64 {
+        (smelts / self.smelts_per_unit() as f64).ceil() as u64
+    }
+}
+
+impl std::fmt::Display for Fuel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for Fuel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Fuel::ALL.into_iter().find(|f| f.name() == s).ok_or_else(|| {
+            let names: Vec<_> = Fuel::ALL.iter().map(|f| f.name()).collect();
+            format!("unknown fuel '{}' (expected one of: {})", s, names.join(", "))
+        })
+    }
+}
+
+/// Calculate raw materials as [`calculate_materials_with`] does, also
+/// tracking which items had no recipe
+pub fn material_report(blocks: &HashMap<String, usize>, options: MaterialOptions) -> MaterialReport {
+    // Stonecutter recipes, when allowed, replace the crafting ones for the same item
+    let mut recipes = get_recipes();
+    recipes.extend(get_stonecutter_recipes().into_iter().filter(|(_, recipe)| options.allows(recipe.method)));
+    // Dyes are raw materials unless they get consolidated
+    let dye_sources = if options.consolidate_dyes { get_dye_source_recipes() } else { HashMap::new() };
+
     let mut materials: HashMap<String, f64> = HashMap::new();
+    let mut unknown = BTreeSet::new();
+    let mut smelts = 0.0;
     let mut to_process: Vec<(String, f64)> = blocks.iter()
-        .filter(|(name, _)| !name.contains("air"))
+        .filter(|(name, _)| crate::BlockClass::of(name) != crate::BlockClass::Air)
         .map(|(name, count)| (name.clone(), *count as f64))
         .collect();
 
@@ -1677,15 +2088,24 @@ pub fn calculate_materials_with_options(blocks: &HashMap<String, usize>, use_sto
         let mut next_round: Vec<(String, f64)> = Vec::new();
 
         for (item, count) in to_process {
-            if is_raw_material(&item) {
-                *materials.entry(item).or_insert(0.0) += count;
-            } else if let Some(recipe) = recipes.get(item.as_str()) {
+            let recipe = match dye_sources.get(item.as_str()) {
+                Some(recipe) => Some(recipe),
+                None if is_raw_material(&item) => None,
+                None => recipes.get(item.as_str()),
+            };
+            if let Some(recipe) = recipe {
                 let batches = count / recipe.output_count as f64;
+                if recipe.method == CraftingMethod::Smelting {
+                    smelts += batches;
+                }
                 for (ingredient, ing_count) in recipe.ingredients.iter() {
                     next_round.push((ingredient.to_string(), batches * *ing_count as f64));
                 }
             } else {
-                // Unknown recipe - treat as raw material
+                // Raw material, or unknown recipe - treat as raw material
+                if !is_raw_material(&item) {
+                    unknown.insert(item.clone());
+                }
                 *materials.entry(item).or_insert(0.0) += count;
             }
         }
@@ -1693,5 +2113,490 @@ pub fn calculate_materials_with_options(blocks: &HashMap<String, usize>, use_sto
         to_process = next_round;
     }
 
-    materials
+    MaterialReport { materials, unknown, smelts }
+}
+
+/// Round material amounts up to whole items
+pub fn round_materials(materials: &HashMap<String, f64>) -> BTreeMap<String, u64> {
+    materials.iter().map(|(name, count)| (name.clone(), count.ceil() as u64)).collect()
+}
+
+/// Per-material change between two material lists
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MaterialsDiff {
+    /// Needed on top of the old list: (name, amount)
+    pub additional: Vec<(String, u64)>,
+    /// No longer needed: (name, amount)
+    pub surplus: Vec<(String, u64)>,
+    /// Same amount in both: (name, amount)
+    pub unchanged: Vec<(String, u64)>,
+}
+
+/// Compare whole-item material lists, sorted by name
+pub fn diff_materials(old: &BTreeMap<String, u64>, new: &BTreeMap<String, u64>) -> MaterialsDiff {
+    let mut diff = MaterialsDiff::default();
+    let names: std::collections::BTreeSet<_> = old.keys().chain(new.keys()).collect();
+    for name in names {
+        let (before, after) = (old.get(name).copied().unwrap_or(0), new.get(name).copied().unwrap_or(0));
+        match after.cmp(&before) {
+            std::cmp::Ordering::Greater => diff.additional.push((name.clone(), after - before)),
+            std::cmp::Ordering::Less => diff.surplus.push((name.clone(), before - after)),
+            std::cmp::Ordering::Equal => diff.unchanged.push((name.clone(), after)),
+        }
+    }
+    diff
+}
+
+/// Broad groups for planning around a material list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Category {
+    /// Logs, planks and everything made of wood
+    Wood,
+    /// Stone, bricks, sand, dirt and other masonry
+    Stone,
+    /// Ores, ingots and metal blocks
+    Metal,
+    /// Glass blocks and panes, stained or not
+    Glass,
+    /// Wool, carpet, concrete, terracotta and other dyed blocks of one color
+    Colored(&'static str),
+    /// Redstone components
+    Redstone,
+    /// Leaves, flowers, crops and other plants
+    Plants,
+    /// Known blocks that fit nowhere else (lights, decoration, liquids)
+    Misc,
+    /// Everything the table doesn't know
+    Other,
+}
+
+impl Category {
+    /// Snake-case name, as used in JSON output
+    pub fn key(self) -> String {
+        match self {
+            Category::Wood => "wood".to_string(),
+            Category::Stone => "stone".to_string(),
+            Category::Metal => "ores_metals".to_string(),
+            Category::Glass => "glass".to_string(),
+            Category::Colored(color) => format!("colored_{}", color),
+            Category::Redstone => "redstone".to_string(),
+            Category::Plants => "plants".to_string(),
+            Category::Misc => "misc".to_string(),
+            Category::Other => "other".to_string(),
+        }
+    }
+
+    /// Heading for tables
+    pub fn label(self) -> String {
+        match self {
+            Category::Wood => "Wood".to_string(),
+            Category::Stone => "Stone & earth".to_string(),
+            Category::Metal => "Ores & metals".to_string(),
+            Category::Glass => "Glass".to_string(),
+            Category::Colored(color) => format!("Colored ({})", color.replace('_', " ")),
+            Category::Redstone => "Redstone".to_string(),
+            Category::Plants => "Plants".to_string(),
+            Category::Misc => "Misc".to_string(),
+            Category::Other => "Other".to_string(),
+        }
+    }
+}
+
+/// Dye colors, longest first so `light_blue` wins over `blue`
+const DYE_COLORS: [&str; 16] = [
+    "light_blue", "light_gray", "magenta", "orange", "yellow", "purple", "brown", "white",
+    "black", "green", "lime", "pink", "gray", "cyan", "blue", "red",
+];
+
+/// Blocks and items that come in the 16 dye colors, after the color
+const DYED: &[&str] = &[
+    "wool", "carpet", "concrete", "concrete_powder", "terracotta", "glazed_terracotta",
+    "bed", "banner", "wall_banner", "candle", "shulker_box", "dye",
+];
+
+/// Name patterns (`*` matches anything) and their category; the first match
+/// wins, so narrow rules come before the broad ones they'd otherwise fall into
+const CATEGORY_RULES: &[(&str, Category)] = &[
+    // Plants that look like wood or stone by name
+    ("*_leaves", Category::Plants),
+    ("*_sapling", Category::Plants),
+    ("*_propagule", Category::Plants),
+    ("mushroom_stem", Category::Plants),
+    ("melon_stem", Category::Plants),
+    ("pumpkin_stem", Category::Plants),
+    ("*_fungus", Category::Plants),
+    ("*_roots", Category::Plants),
+    ("*azalea*", Category::Plants),
+    ("*moss_block", Category::Plants),
+    ("*moss_carpet", Category::Plants),
+    ("*wart*", Category::Plants),
+    ("*_nylium", Category::Stone),
+    ("*_ore", Category::Metal),
+    // Glass before colors, so stained glass stays glass
+    ("*glass*", Category::Glass),
+    // Redstone, including the wooden and stone buttons and plates
+    ("redstone*", Category::Redstone),
+    ("*_button", Category::Redstone),
+    ("*_pressure_plate", Category::Redstone),
+    ("*rail", Category::Redstone),
+    ("*piston*", Category::Redstone),
+    ("repeater", Category::Redstone),
+    ("comparator", Category::Redstone),
+    ("observer", Category::Redstone),
+    ("dispenser", Category::Redstone),
+    ("dropper", Category::Redstone),
+    ("hopper", Category::Redstone),
+    ("lever", Category::Redstone),
+    ("tripwire*", Category::Redstone),
+    ("daylight_detector", Category::Redstone),
+    ("target", Category::Redstone),
+    ("note_block", Category::Redstone),
+    ("trapped_chest", Category::Redstone),
+    ("tnt", Category::Redstone),
+    ("*sculk_sensor", Category::Redstone),
+    ("crafter", Category::Redstone),
+    ("lightning_rod", Category::Redstone),
+    ("slime_*", Category::Redstone),
+    ("honey_block", Category::Redstone),
+    // Lights whose names would put them with wood or stone
+    ("*torch", Category::Misc),
+    ("*lantern", Category::Misc),
+    ("*campfire", Category::Misc),
+    ("glowstone*", Category::Misc),
+    // Wood
+    ("*_log", Category::Wood),
+    ("*_wood", Category::Wood),
+    ("*_planks", Category::Wood),
+    ("*_stem", Category::Wood),
+    ("*_hyphae", Category::Wood),
+    ("stripped_*", Category::Wood),
+    ("bamboo_block", Category::Wood),
+    ("bamboo_mosaic*", Category::Wood),
+    ("any_*", Category::Wood),
+    ("oak_*", Category::Wood),
+    ("spruce_*", Category::Wood),
+    ("birch_*", Category::Wood),
+    ("jungle_*", Category::Wood),
+    ("acacia_*", Category::Wood),
+    ("dark_oak_*", Category::Wood),
+    ("pale_oak_*", Category::Wood),
+    ("mangrove_*", Category::Wood),
+    ("cherry_*", Category::Wood),
+    ("bamboo_*", Category::Wood),
+    ("crimson_*", Category::Wood),
+    ("warped_*", Category::Wood),
+    ("stick", Category::Wood),
+    ("chest", Category::Wood),
+    ("barrel", Category::Wood),
+    ("crafting_table", Category::Wood),
+    ("*bookshelf", Category::Wood),
+    ("ladder", Category::Wood),
+    ("composter", Category::Wood),
+    ("lectern", Category::Wood),
+    // Ores and metals
+    ("raw_*", Category::Metal),
+    ("*_ingot", Category::Metal),
+    ("*_nugget", Category::Metal),
+    ("iron_*", Category::Metal),
+    ("gold_*", Category::Metal),
+    ("*copper*", Category::Metal),
+    ("diamond*", Category::Metal),
+    ("emerald*", Category::Metal),
+    ("netherite*", Category::Metal),
+    ("lapis*", Category::Metal),
+    ("coal*", Category::Metal),
+    ("*amethyst*", Category::Metal),
+    ("quartz", Category::Metal),
+    ("ancient_debris", Category::Metal),
+    ("chain", Category::Metal),
+    ("*anvil", Category::Metal),
+    ("cauldron", Category::Metal),
+    // Stone and earth
+    ("*stone*", Category::Stone),
+    ("*brick*", Category::Stone),
+    ("*deepslate*", Category::Stone),
+    ("granite*", Category::Stone),
+    ("diorite*", Category::Stone),
+    ("andesite*", Category::Stone),
+    ("polished_*", Category::Stone),
+    ("chiseled_*", Category::Stone),
+    ("cut_*", Category::Stone),
+    ("smooth_*", Category::Stone),
+    ("tuff*", Category::Stone),
+    ("calcite", Category::Stone),
+    ("*basalt", Category::Stone),
+    ("*prismarine*", Category::Stone),
+    ("purpur_*", Category::Stone),
+    ("quartz_*", Category::Stone),
+    ("terracotta", Category::Stone),
+    ("*obsidian", Category::Stone),
+    ("netherrack", Category::Stone),
+    ("*dripstone*", Category::Stone),
+    ("magma_block", Category::Stone),
+    ("soul_*", Category::Stone),
+    ("*sand", Category::Stone),
+    ("gravel", Category::Stone),
+    ("flint", Category::Stone),
+    ("*dirt", Category::Stone),
+    ("grass_block", Category::Stone),
+    ("dirt_path", Category::Stone),
+    ("podzol", Category::Stone),
+    ("mycelium", Category::Stone),
+    ("*mud", Category::Stone),
+    ("clay*", Category::Stone),
+    // Plants
+    ("*flower*", Category::Plants),
+    ("*_tulip", Category::Plants),
+    ("*mushroom*", Category::Plants),
+    ("*grass", Category::Plants),
+    ("*fern", Category::Plants),
+    ("*vine*", Category::Plants),
+    ("*_bush", Category::Plants),
+    ("*coral*", Category::Plants),
+    ("*kelp*", Category::Plants),
+    ("*dripleaf", Category::Plants),
+    ("dandelion", Category::Plants),
+    ("poppy", Category::Plants),
+    ("blue_orchid", Category::Plants),
+    ("allium", Category::Plants),
+    ("azure_bluet", Category::Plants),
+    ("oxeye_daisy", Category::Plants),
+    ("cornflower", Category::Plants),
+    ("lily_of_the_valley", Category::Plants),
+    ("wither_rose", Category::Plants),
+    ("sunflower", Category::Plants),
+    ("lilac", Category::Plants),
+    ("peony", Category::Plants),
+    ("torchflower*", Category::Plants),
+    ("pitcher_*", Category::Plants),
+    ("seagrass", Category::Plants),
+    ("tall_seagrass", Category::Plants),
+    ("lily_pad", Category::Plants),
+    ("cactus", Category::Plants),
+    ("sugar_cane", Category::Plants),
+    ("bamboo", Category::Plants),
+    ("melon*", Category::Plants),
+    ("*pumpkin", Category::Plants),
+    ("hay_block", Category::Plants),
+    ("wheat*", Category::Plants),
+    ("carrots", Category::Plants),
+    ("potatoes", Category::Plants),
+    ("beetroots", Category::Plants),
+    ("cocoa", Category::Plants),
+    ("glow_lichen", Category::Plants),
+    ("spore_blossom", Category::Plants),
+    ("chorus_*", Category::Plants),
+    ("potted_*", Category::Plants),
+    // Lights, decoration and the rest of what a build is made of
+    ("candle", Category::Misc),
+    ("shroomlight", Category::Misc),
+    ("end_rod", Category::Misc),
+    ("flower_pot", Category::Misc),
+    ("scaffolding", Category::Misc),
+    ("*_head", Category::Misc),
+    ("*_skull", Category::Misc),
+    ("painting", Category::Misc),
+    ("*item_frame", Category::Misc),
+    ("bell", Category::Misc),
+    ("beacon", Category::Misc),
+    ("conduit", Category::Misc),
+    ("enchanting_table", Category::Misc),
+    ("brewing_stand", Category::Misc),
+    ("*water*", Category::Misc),
+    ("*lava*", Category::Misc),
+    ("*ice", Category::Misc),
+    ("snow*", Category::Misc),
+    ("cobweb", Category::Misc),
+    ("*sponge", Category::Misc),
+    ("book", Category::Misc),
+    ("string", Category::Misc),
+    ("leather", Category::Misc),
+    ("bone_meal", Category::Misc),
+];
+
+/// Whether `name` matches `pattern`, where `*` stands for any run of characters
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or("");
+    let Some(mut rest) = name.strip_prefix(first) else { return false };
+    let mut parts: Vec<&str> = parts.collect();
+    let Some(last) = parts.pop() else { return rest.is_empty() };
+    for part in parts {
+        match rest.find(part) {
+            Some(at) => rest = &rest[at + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+/// Category of a block or item name; modded names go by the same patterns,
+/// so `mod:fir_log` is wood
+pub fn category(name: &str) -> Category {
+    let name = name.strip_prefix("minecraft:").unwrap_or(name);
+    for color in DYE_COLORS {
+        if let Some(base) = name.strip_prefix(color).and_then(|rest| rest.strip_prefix('_')) {
+            if DYED.contains(&base) {
+                return Category::Colored(color);
+            }
+        }
+    }
+    CATEGORY_RULES.iter()
+        .find(|(pattern, _)| wildcard_match(pattern, name))
+        .map_or(Category::Other, |&(_, category)| category)
+}
+
+/// Split named amounts into categories, keeping their order within each
+pub fn group_by_category<T>(items: impl IntoIterator<Item = (String, T)>) -> BTreeMap<Category, Vec<(String, T)>> {
+    let mut groups: BTreeMap<Category, Vec<(String, T)>> = BTreeMap::new();
+    for (name, amount) in items {
+        groups.entry(category(&name)).or_default().push((name, amount));
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_materials_uses_whole_items() {
+        // Fractions round up before comparing, so 3.33 and 3.9 logs are both 4
+        let old = round_materials(&HashMap::from([
+            ("minecraft:oak_log".to_string(), 10.0 / 3.0),
+            ("minecraft:cobblestone".to_string(), 64.0),
+        ]));
+        let new = round_materials(&HashMap::from([
+            ("minecraft:oak_log".to_string(), 3.9),
+            ("minecraft:cobblestone".to_string(), 32.0),
+            ("minecraft:glass".to_string(), 5.0),
+        ]));
+
+        let diff = diff_materials(&old, &new);
+        assert_eq!(diff.additional, [("minecraft:glass".to_string(), 5)]);
+        assert_eq!(diff.surplus, [("minecraft:cobblestone".to_string(), 32)]);
+        assert_eq!(diff.unchanged, [("minecraft:oak_log".to_string(), 4)]);
+    }
+
+    #[test]
+    fn test_categories() {
+        let cases = [
+            ("minecraft:oak_planks", Category::Wood),
+            ("minecraft:dark_oak_stairs", Category::Wood),
+            ("minecraft:stripped_crimson_stem", Category::Wood),
+            ("biomesoplenty:fir_log", Category::Wood),
+            ("minecraft:oak_leaves", Category::Plants),
+            ("minecraft:oak_button", Category::Redstone),
+            ("minecraft:sticky_piston", Category::Redstone),
+            ("minecraft:redstone_ore", Category::Metal),
+            ("minecraft:waxed_cut_copper_stairs", Category::Metal),
+            ("minecraft:deepslate_tile_slab", Category::Stone),
+            ("minecraft:smooth_sandstone", Category::Stone),
+            ("minecraft:terracotta", Category::Stone),
+            ("minecraft:light_blue_stained_glass_pane", Category::Glass),
+            ("minecraft:light_blue_concrete", Category::Colored("light_blue")),
+            ("minecraft:blue_glazed_terracotta", Category::Colored("blue")),
+            ("minecraft:red_wool", Category::Colored("red")),
+            ("minecraft:water_bucket", Category::Misc),
+            ("minecraft:lantern", Category::Misc),
+            ("minecraft:soul_lantern", Category::Misc),
+            ("minecraft:glowstone", Category::Misc),
+            ("minecraft:redstone_torch", Category::Redstone),
+            ("minecraft:crimson_nylium", Category::Stone),
+            ("create:cogwheel", Category::Other),
+        ];
+        for (name, expected) in cases {
+            assert_eq!(category(name), expected, "{}", name);
+        }
+        assert_eq!(Category::Colored("light_gray").key(), "colored_light_gray");
+        assert_eq!(Category::Colored("light_gray").label(), "Colored (light gray)");
+
+        assert!(wildcard_match("*stone*", "cobblestone_wall"));
+        assert!(wildcard_match("a*b*c", "abc"));
+        assert!(!wildcard_match("a*bc", "abc_bc_"));
+        assert!(!wildcard_match("*_log", "_lo"));
+
+        let groups = group_by_category([("minecraft:glass".to_string(), 3), ("minecraft:oak_log".to_string(), 5), ("minecraft:oak_slab".to_string(), 1)]);
+        let keys: Vec<_> = groups.keys().copied().collect();
+        assert_eq!(keys, [Category::Wood, Category::Glass]);
+        assert_eq!(groups[&Category::Wood], [("minecraft:oak_log".to_string(), 5), ("minecraft:oak_slab".to_string(), 1)]);
+    }
+
+    #[test]
+    fn test_any_wood_becomes_logs() {
+        // A barrel is 6 planks and 2 slabs (one plank), 4 barrels are 28 planks
+        let materials = calculate_materials(&HashMap::from([("minecraft:barrel".to_string(), 4)]));
+        assert_eq!(materials, HashMap::from([("minecraft:any_log".to_string(), 7.0)]));
+        assert_eq!(material_label("minecraft:any_log"), "any wood (logs)");
+        assert_eq!(material_label("minecraft:oak_log"), "oak_log");
+    }
+
+    #[test]
+    fn test_consolidate_dyes() {
+        let blocks = HashMap::from([("minecraft:magenta_terracotta".to_string(), 8), ("minecraft:lime_wool".to_string(), 2)]);
+        let plain = calculate_materials(&blocks);
+        assert_eq!(plain["minecraft:magenta_dye"], 1.0);
+
+        // Magenta is purple and pink, which are blue, red and white; lime is green and white
+        let consolidated = calculate_materials_with(&blocks, MaterialOptions::new().with_consolidated_dyes(true));
+        let expected = [
+            ("minecraft:clay", 8.0),
+            ("minecraft:white_wool", 2.0),
+            ("minecraft:lapis_lazuli", 0.25),
+            ("minecraft:poppy", 0.5),
+            ("minecraft:bone_meal", 1.25),
+            ("minecraft:cactus", 1.0),
+        ];
+        assert_eq!(consolidated, expected.into_iter().map(|(name, n)| (name.to_string(), n)).collect::<HashMap<_, _>>());
+        assert!(is_flower("minecraft:poppy") && !is_flower("minecraft:cactus"));
+    }
+
+    #[test]
+    fn test_unknown_recipes_are_reported() {
+        let blocks = HashMap::from([
+            ("minecraft:stone_bricks".to_string(), 4),
+            ("minecraft:copper_bulb".to_string(), 2),
+            ("minecraft:stonebrick".to_string(), 1),
+        ]);
+        let report = material_report(&blocks, MaterialOptions::new());
+        assert_eq!(report.unknown, BTreeSet::from(["minecraft:copper_bulb".to_string(), "minecraft:stonebrick".to_string()]));
+        // Still counted as they are
+        assert_eq!(report.materials["minecraft:copper_bulb"], 2.0);
+        assert_eq!(report.materials["minecraft:stone"], 4.0);
+
+        // Air needs nothing, and stairs aren't mistaken for it
+        let stairs = calculate_materials(&HashMap::from([("minecraft:oak_stairs".to_string(), 4), ("minecraft:cave_air".to_string(), 9)]));
+        assert_eq!(stairs, HashMap::from([("minecraft:oak_log".to_string(), 1.5)]));
+    }
+
+    #[test]
+    fn test_every_ingredient_is_known() {
+        let recipes: Vec<Recipe> = get_recipes().into_values()
+            .chain(get_stonecutter_recipes().into_values())
+            .chain(get_dye_source_recipes().into_values())
+            .collect();
+        let outputs: BTreeSet<&str> = recipes.iter().map(|r| r.output).collect();
+        let unknown: BTreeSet<&str> = recipes.iter()
+            .flat_map(|r| r.ingredients.iter().map(|(name, _)| *name))
+            .filter(|name| !is_raw_material(name) && !outputs.contains(name))
+            .collect();
+        assert!(unknown.is_empty(), "ingredients without a recipe: {:?}", unknown);
+    }
+
+    #[test]
+    fn test_smelting_fuel() {
+        // 8 glass and 4 smooth stone slabs: 8 sand and 2 stone smelted (stone
+        // is mined, so it isn't smelted from cobblestone)
+        let blocks = HashMap::from([("minecraft:glass".to_string(), 8), ("minecraft:smooth_stone_slab".to_string(), 4)]);
+        let report = material_report(&blocks, MaterialOptions::new());
+        assert_eq!(report.smelts, 10.0);
+        assert_eq!(report.materials["minecraft:stone"], 2.0);
+        assert_eq!([Fuel::Coal, Fuel::Lava, Fuel::Kelp].map(|f| f.needed(report.smelts)), [2, 1, 1]);
+        assert_eq!(Fuel::Coal.needed(16.0), 2);
+        assert_eq!("lava".parse::<Fuel>(), Ok(Fuel::Lava));
+
+        let stonecutter = MaterialOptions::new().with_stonecutter(true);
+        assert!(stonecutter.allows(CraftingMethod::Stonecutting) && !MaterialOptions::new().allows(CraftingMethod::Stonecutting));
+        assert_eq!(get_recipes()["minecraft:glass"].method, CraftingMethod::Smelting);
+    }
 }
@@ -0,0 +1,193 @@
+//! Removing personal data from block entities before sharing a schematic
+//!
+//! [`sanitize`] edits block entity NBT key by key, so the blocks and the rest
+//! of their data stay: emptied chests keep their `Lock` and `CustomName`
+//! unless names are stripped too, and cleared signs keep their color and wax.
+
+use std::collections::BTreeMap;
+
+use fastnbt::Value;
+
+use crate::{BlockSelector, UnifiedSchematic};
+
+/// Single-item slots outside `Items`: a lectern's book, a jukebox's record, a
+/// decorated pot's item
+const ITEM_KEYS: &[&str] = &["Book", "RecordItem", "item"];
+
+/// Keys naming or locking a block entity (`lock` since 1.21.2)
+const NAME_KEYS: &[&str] = &["CustomName", "Lock", "lock"];
+
+/// What [`sanitize`] removes
+#[derive(Debug, Clone, Default)]
+pub struct SanitizeOptions {
+    /// Empty container inventories and single-item slots
+    pub clear_inventories: bool,
+    /// Blank the text on both sides of signs
+    pub strip_signs: bool,
+    /// Remove custom names and container locks
+    pub strip_names: bool,
+    /// Remove block entities whose id matches, keeping their blocks
+    pub remove: BlockSelector,
+}
+
+impl SanitizeOptions {
+    /// Whether these options change anything
+    pub fn is_empty(&self) -> bool {
+        !self.clear_inventories && !self.strip_signs && !self.strip_names && self.remove.is_empty()
+    }
+}
+
+/// What [`sanitize`] removed
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct SanitizeReport {
+    /// Block entities with items that were emptied
+    pub inventories: usize,
+    /// Item stacks taken out of them
+    pub stacks: usize,
+    /// Signs with text that were blanked
+    pub signs: usize,
+    /// Block entities that lost a custom name or lock
+    pub names: usize,
+    /// Block entities removed, by id
+    pub removed: BTreeMap<String, usize>,
+}
+
+impl SanitizeReport {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Remove what `options` asks for from the block entities in `schematic`
+pub fn sanitize(schematic: &mut UnifiedSchematic, options: &SanitizeOptions) -> SanitizeReport {
+    let mut report = SanitizeReport::default();
+    if !options.remove.is_empty() {
+        schematic.block_entities.retain(|entity| {
+            let remove = options.remove.matches_name(&entity.id);
+            if remove {
+                *report.removed.entry(entity.id.clone()).or_default() += 1;
+            }
+            !remove
+        });
+    }
+
+    for entity in &mut schematic.block_entities {
+        let mut nbt = entity.nbt.clone();
+        let mut changed = false;
+        if options.clear_inventories {
+            let stacks = clear_inventory(&mut nbt);
+            if stacks > 0 {
+                report.inventories += 1;
+                report.stacks += stacks;
+                changed = true;
+            }
+        }
+        if options.strip_signs && entity.is_sign() && clear_sign(&mut nbt) {
+            report.signs += 1;
+            changed = true;
+        }
+        if options.strip_names && NAME_KEYS.iter().filter(|key| nbt.remove(**key).is_some()).count() > 0 {
+            report.names += 1;
+            changed = true;
+        }
+        if changed {
+            entity.set_nbt(nbt);
+        }
+    }
+    report
+}
+
+/// Empty `Items` and the single-item slots, returning the stacks removed
+fn clear_inventory(nbt: &mut std::collections::HashMap<String, Value>) -> usize {
+    let mut stacks = 0;
+    if let Some(Value::List(items)) = nbt.get_mut("Items") {
+        stacks += items.len();
+        items.clear();
+    }
+    for key in ITEM_KEYS {
+        if nbt.remove(*key).is_some() {
+            stacks += 1;
+        }
+    }
+    stacks
+}
+
+/// Blank every line of a sign, in the 1.20+ and the older layout; false if
+/// it had no text
+fn clear_sign(nbt: &mut std::collections::HashMap<String, Value>) -> bool {
+    let blank = serde_json::json!({ "text": "" }).to_string();
+    let is_blank = |line: &Value| matches!(line, Value::String(text) if text.is_empty() || *text == blank || text == "\"\"");
+    let mut had_text = false;
+    for side in ["front_text", "back_text"] {
+        if let Some(Value::Compound(side)) = nbt.get_mut(side) {
+            if let Some(Value::List(lines)) = side.get_mut("messages") {
+                had_text |= !lines.iter().all(is_blank);
+                lines.iter_mut().for_each(|line| *line = Value::String(blank.clone()));
+            }
+            side.remove("filtered_messages");
+        }
+    }
+    for line in (1..=4).map(|line| format!("Text{}", line)) {
+        if let Some(text) = nbt.get_mut(&line) {
+            had_text |= !is_blank(text);
+            *text = Value::String(blank.clone());
+        }
+        nbt.remove(&format!("Filtered{}", line));
+    }
+    had_text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use crate::{BlockEntity, SchematicBuilder, SignText};
+
+    fn entity(id: &str, x: i32, nbt: HashMap<String, Value>) -> BlockEntity {
+        let mut entity = BlockEntity { id: id.to_string(), pos: (x, 0, 0), ..Default::default() };
+        entity.set_nbt(nbt);
+        entity
+    }
+
+    #[test]
+    fn test_sanitize() {
+        let stack = Value::Compound([("id".to_string(), Value::String("minecraft:diamond".to_string()))].into());
+        let text = SignText { front: vec!["Alex's base".to_string()], back: vec!["keep out".to_string()] };
+        let chest = HashMap::from([
+            ("Items".to_string(), Value::List(vec![stack.clone(), stack])),
+            ("CustomName".to_string(), Value::String(r#"{"text":"Loot"}"#.to_string())),
+            ("Lock".to_string(), Value::String("key".to_string())),
+        ]);
+        let command = HashMap::from([("Command".to_string(), Value::String("/op Alex".to_string()))]);
+        let mut builder = SchematicBuilder::new(4, 1, 1);
+        builder.add_block_entity(entity("minecraft:chest", 0, chest)).unwrap();
+        builder.add_block_entity(entity("minecraft:sign", 1, text.to_nbt(false))).unwrap();
+        builder.add_block_entity(entity("minecraft:sign", 2, text.to_nbt(true))).unwrap();
+        builder.add_block_entity(entity("minecraft:command_block", 3, command)).unwrap();
+        let mut schem = builder.build();
+
+        let options = SanitizeOptions {
+            clear_inventories: true,
+            strip_signs: true,
+            remove: BlockSelector::new(["command_block"]),
+            ..Default::default()
+        };
+        let report = sanitize(&mut schem, &options);
+        assert_eq!((report.inventories, report.stacks, report.signs, report.names), (1, 2, 2, 0));
+        assert_eq!(report.removed, BTreeMap::from([("minecraft:command_block".to_string(), 1)]));
+        assert_eq!(schem.block_entities.len(), 3);
+        let chest = &schem.block_entities[0];
+        assert_eq!(chest.nbt["Items"], Value::List(Vec::new()));
+        assert!(chest.nbt.contains_key("CustomName") && chest.data.contains_key("Lock"));
+        for sign in &schem.block_entities[1..] {
+            assert!(!clear_sign(&mut sign.nbt.clone()), "{:?}", sign.nbt);
+        }
+        assert!(schem.block_entities[1].nbt.contains_key("is_waxed"));
+
+        let names = SanitizeOptions { strip_names: true, ..Default::default() };
+        let report = sanitize(&mut schem, &names);
+        assert_eq!(report.names, 1);
+        assert!(!schem.block_entities[0].data.contains_key("Lock"));
+        assert!(sanitize(&mut schem, &options).is_empty());
+    }
+}
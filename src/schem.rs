@@ -4,7 +4,7 @@
 //! - Version: int (2)
 //! - DataVersion: int (Minecraft data version)
 //! - Width, Height, Length: short
-//! - Offset: int array [x, y, z]
+//! - Offset: int array [x, y, z] (world position of the minimum corner)
 //! - Palette: compound {block_state_string -> varint_id}
 //! - BlockData: byte array (varint encoded palette indices)
 //! - BlockEntities: list
@@ -18,10 +18,20 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use crate::{
-    Block, BlockState, BlockEntity, Entity, Metadata,
-    SchematicFormat, UnifiedSchematic,
+    checked_volume, Block, BlockState, BlockEntity, Entity, LoadReport, Metadata,
+    SchemError, SchematicFormat, UnifiedSchematic,
 };
 
+/// Root keys read by [`Schem`], including aliases
+impl crate::extra_nbt::KnownFields for Schem {
+    const KNOWN: &'static [&'static str] = &[
+        "Version", "version", "DataVersion", "dataVersion",
+        "Width", "width", "Height", "height", "Length", "length",
+        "Offset", "Palette", "PaletteMax", "BlockData", "BlockEntities", "TileEntities",
+        "Entities", "Metadata", "Schematic", "Blocks", "Biomes",
+    ];
+}
+
 /// Wrapper for v3 format where root NBT has "Schematic" compound
 #[derive(Debug, Deserialize, Serialize)]
 pub struct SchemWrapper {
@@ -59,13 +69,13 @@ pub struct Schem {
     #[serde(rename = "BlockData", default)]
     pub block_data: Option<fastnbt::ByteArray>,
 
-    #[serde(rename = "BlockEntities", default)]
+    #[serde(rename = "BlockEntities", default, skip_serializing_if = "Vec::is_empty")]
     pub block_entities: Vec<SchemBlockEntity>,
 
-    #[serde(rename = "TileEntities", default)]
+    #[serde(rename = "TileEntities", default, skip_serializing_if = "Vec::is_empty")]
     pub tile_entities: Vec<SchemBlockEntity>,
 
-    #[serde(rename = "Entities", default)]
+    #[serde(rename = "Entities", default, skip_serializing_if = "Vec::is_empty")]
     pub entities: Vec<SchemEntity>,
 
     #[serde(rename = "Metadata", default)]
@@ -81,6 +91,11 @@ pub struct Schem {
     // v3 biomes
     #[serde(rename = "Biomes", default)]
     pub biomes: Option<SchemBiomes>,
+
+    /// Fields written back from `Metadata::raw_extra`; loading collects them
+    /// separately, so this stays empty on a loaded schematic
+    #[serde(flatten, skip_deserializing)]
+    pub extra: HashMap<String, fastnbt::Value>,
 }
 
 /// v3 Blocks compound
@@ -92,7 +107,7 @@ pub struct SchemBlocks {
     #[serde(rename = "Data", default)]
     pub data: Option<fastnbt::ByteArray>,
 
-    #[serde(rename = "BlockEntities", default)]
+    #[serde(rename = "BlockEntities", alias = "TileEntities", default)]
     pub block_entities: Vec<SchemBlockEntity>,
 }
 
@@ -108,15 +123,18 @@ pub struct SchemBiomes {
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct SchemBlockEntity {
-    #[serde(rename = "Id", alias = "id", default)]
+    #[serde(rename = "Id", alias = "id", default, skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
 
-    #[serde(rename = "Pos", default)]
+    #[serde(rename = "Pos", alias = "pos", default, skip_serializing_if = "Option::is_none")]
     pub pos: Option<fastnbt::IntArray>,
 
     // Alternative position format
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub x: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub y: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub z: Option<i32>,
 
     #[serde(flatten)]
@@ -128,7 +146,7 @@ pub struct SchemEntity {
     #[serde(rename = "Id", alias = "id", default)]
     pub id: Option<String>,
 
-    #[serde(rename = "Pos", default)]
+    #[serde(rename = "Pos", alias = "pos", default)]
     pub pos: Option<Vec<f64>>,
 
     #[serde(flatten)]
@@ -149,44 +167,27 @@ pub struct SchemMetadata {
     #[serde(rename = "RequiredMods", default)]
     pub required_mods: Option<Vec<String>>,
 
+    // WorldEdit: minimum corner relative to the copy origin
+    #[serde(rename = "WEOffsetX", default)]
+    pub we_offset_x: Option<i32>,
+
+    #[serde(rename = "WEOffsetY", default)]
+    pub we_offset_y: Option<i32>,
+
+    #[serde(rename = "WEOffsetZ", default)]
+    pub we_offset_z: Option<i32>,
+
     #[serde(flatten)]
     pub extra: HashMap<String, fastnbt::Value>,
 }
 
 impl Schem {
-    /// Read variable-length integer from byte array
-    fn read_varint(data: &[i8], offset: &mut usize) -> Option<i32> {
-        let mut result: i32 = 0;
-        let mut shift = 0;
-
-        loop {
-            if *offset >= data.len() {
-                return None;
-            }
-
-            let byte = (data[*offset] as u8) as i32;
-            *offset += 1;
-
-            result |= (byte & 0x7F) << shift;
-
-            if byte & 0x80 == 0 {
-                break;
-            }
-
-            shift += 7;
-            if shift >= 32 {
-                return None;
-            }
-        }
-
-        Some(result)
-    }
-
     /// Parse block state string like "minecraft:chest[facing=north,waterlogged=false]"
-    fn parse_block_state(state_str: &str) -> Block {
+    pub(crate) fn parse_block_state(state_str: &str) -> Block {
         if let Some(bracket_pos) = state_str.find('[') {
             let name = &state_str[..bracket_pos];
-            let props_str = &state_str[bracket_pos + 1..state_str.len() - 1];
+            let props_str = &state_str[bracket_pos + 1..];
+            let props_str = props_str.strip_suffix(']').unwrap_or(props_str);
 
             let mut properties = HashMap::new();
             for prop in props_str.split(',') {
@@ -197,7 +198,7 @@ impl Schem {
                 }
             }
 
-            Block::with_state(name, BlockState { properties })
+            Block::with_state(name, BlockState { properties: properties.into() })
         } else {
             Block::new(state_str)
         }
@@ -208,8 +209,17 @@ impl Schem {
         self.schematic.as_deref().unwrap_or(self)
     }
 
+    /// Convert to unified format, rejecting corrupt block data
+    pub fn to_unified(&self) -> Result<UnifiedSchematic, SchemError> {
+        self.to_unified_with(false)
+    }
+
     /// Convert to unified format
-    pub fn to_unified(&self) -> UnifiedSchematic {
+    ///
+    /// Fails with [`SchemError::CorruptData`] if BlockData references a palette
+    /// entry that doesn't exist or ends before every block is read, unless
+    /// `lenient` is set; then those blocks become air and a warning is logged.
+    pub fn to_unified_with(&self, lenient: bool) -> Result<UnifiedSchematic, SchemError> {
         let eff = self.get_effective();
 
         let version = eff.version;
@@ -219,77 +229,40 @@ impl Schem {
             SchematicFormat::SpongeV2
         };
 
-        let width = eff.width.unwrap_or(0) as u16;
-        let height = eff.height.unwrap_or(0) as u16;
-        let length = eff.length.unwrap_or(0) as u16;
+        // Dimensions are unsigned shorts stored in signed NBT tags
+        let width = eff.width.unwrap_or(0) as u16 as u32;
+        let height = eff.height.unwrap_or(0) as u16 as u32;
+        let length = eff.length.unwrap_or(0) as u16 as u32;
 
         // Get palette and data based on version
         let empty_palette = HashMap::new();
-        let empty_block_entities = Vec::new();
 
-        let (palette, block_data, block_entities_raw) = if version >= 3 {
+        let (palette, block_data) = match eff.blocks {
             // v3: blocks are in Blocks compound
-            if let Some(ref blocks) = eff.blocks {
-                (
-                    &blocks.palette,
-                    blocks.data.as_ref(),
-                    &blocks.block_entities,
-                )
-            } else {
-                // Fallback to v2 style
-                (
-                    eff.palette.as_ref().unwrap_or(&empty_palette),
-                    eff.block_data.as_ref(),
-                    if !eff.block_entities.is_empty() {
-                        &eff.block_entities
-                    } else {
-                        &empty_block_entities
-                    },
-                )
-            }
-        } else {
-            // v2
-            (
-                eff.palette.as_ref().unwrap_or(&empty_palette),
-                eff.block_data.as_ref(),
-                if !eff.block_entities.is_empty() {
-                    &eff.block_entities
-                } else {
-                    &eff.tile_entities
-                },
-            )
+            Some(ref blocks) if version >= 3 => (&blocks.palette, blocks.data.as_ref()),
+            // v2, or v3 written v2 style
+            _ => (eff.palette.as_ref().unwrap_or(&empty_palette), eff.block_data.as_ref()),
         };
 
-        // Build reverse palette (id -> block state string)
-        let mut reverse_palette: Vec<Block> = vec![Block::air(); palette.len().max(1)];
-        for (state_str, &id) in palette {
-            if id >= 0 && (id as usize) < reverse_palette.len() {
-                reverse_palette[id as usize] = Self::parse_block_state(state_str);
-            }
-        }
-
-        // Parse block data (varint encoded)
-        let volume = width as usize * height as usize * length as usize;
-        let mut blocks = Vec::with_capacity(volume);
-
-        if let Some(data) = block_data {
-            let mut offset = 0;
-            while blocks.len() < volume {
-                if let Some(palette_id) = Self::read_varint(data.as_ref(), &mut offset) {
-                    let block = reverse_palette
-                        .get(palette_id as usize)
-                        .cloned()
-                        .unwrap_or_else(Block::air);
-                    blocks.push(block);
-                } else {
-                    // Padding with air if data is incomplete
-                    blocks.push(Block::air());
-                }
-            }
-        } else {
+        // Block entities belong in Blocks (v3) or at the root as BlockEntities (v2), but
+        // older WorldEdit builds use the v1 name TileEntities and some v3 exporters keep
+        // them at the root, so take the first list that has any
+        let block_entities_raw = [
+            eff.blocks.as_ref().map(|blocks| &blocks.block_entities),
+            Some(&eff.block_entities),
+            Some(&eff.tile_entities),
+        ]
+        .into_iter()
+        .flatten()
+        .find(|list| !list.is_empty())
+        .unwrap_or(&eff.block_entities);
+
+        let volume = checked_volume(width, height, length)?;
+        let (blocks, recovered_blocks) = match block_data {
+            Some(data) => decode_block_data(data, &PaletteLookup::new(palette), volume, lenient)?,
             // No block data, fill with air
-            blocks.resize(volume, Block::air());
-        }
+            None => (vec![Block::air(); volume], 0),
+        };
 
         // Parse block entities
         let block_entities: Vec<BlockEntity> = block_entities_raw.iter().map(|be| {
@@ -309,12 +282,8 @@ impl Schem {
                 )
             };
 
-            let mut data = HashMap::new();
-            for (key, value) in &be.extra {
-                data.insert(key.clone(), format_nbt_value(value));
-            }
-
-            BlockEntity { id, pos, data }
+            let nbt = nbt_payload(&be.extra);
+            BlockEntity { id, pos, data: payload(&nbt), nbt }
         }).collect();
 
         // Parse entities
@@ -327,51 +296,309 @@ impl Schem {
 
             let pos = (pos_vec[0], pos_vec[1], pos_vec[2]);
 
-            let mut data = HashMap::new();
-            for (key, value) in &e.extra {
-                data.insert(key.clone(), format_nbt_value(value));
-            }
-
-            Some(Entity { id, pos, data })
+            let nbt = nbt_payload(&e.extra);
+            Some(Entity { id, pos, data: payload(&nbt), nbt })
         }).collect();
 
         // Parse metadata
-        let metadata = eff.metadata.as_ref().map(|m| {
+        let mut metadata = eff.metadata.as_ref().map(|m| {
             let mut extra = HashMap::new();
             for (key, value) in &m.extra {
                 extra.insert(key.clone(), format_nbt_value(value));
             }
 
+            let we_offset = match (m.we_offset_x, m.we_offset_y, m.we_offset_z) {
+                (Some(x), Some(y), Some(z)) => Some((x, y, z)),
+                _ => None,
+            };
+
             Metadata {
                 name: m.name.clone(),
                 author: m.author.clone(),
                 date: m.date,
                 required_mods: m.required_mods.clone().unwrap_or_default(),
                 extra,
+                we_offset,
+                ..Default::default()
             }
         }).unwrap_or_default();
+        metadata.data_version = eff.data_version;
+        metadata.offset = match eff.offset.as_deref() {
+            Some(&[x, y, z, ..]) => Some((x, y, z)),
+            _ => None,
+        };
 
-        UnifiedSchematic {
+        Ok(UnifiedSchematic {
             format,
             width,
             height,
             length,
+            // World position of the minimum corner
+            offset: metadata.offset.unwrap_or_default(),
             blocks,
             block_entities,
             entities,
             metadata,
+            load_report: LoadReport { recovered_blocks, ..Default::default() },
+        })
+    }
+}
+
+impl SchemWrapper {
+    /// Sponge v3 schematic holding a unified schematic's blocks
+    ///
+    /// Block entities and entities keep their `nbt` in the v3 `Data` compound.
+    /// Unrecognised fields in `metadata.raw_extra` are written back if the
+    /// schematic was loaded from a Sponge schematic of either version, and dropped
+    /// with a warning otherwise. Fails with `SchemError::TooLarge` past 65,535
+    /// blocks along an axis.
+    pub fn from_unified(schem: &UnifiedSchematic) -> Result<Self, SchemError> {
+        let (width, height, length) = (schem.width, schem.height, schem.length);
+        let short = |v: u32| u16::try_from(v).map(|v| v as i16).map_err(|_| SchemError::TooLarge { width, height, length });
+
+        let mut palette = HashMap::from([(Block::air().full_name(), 0)]);
+        let mut data = Vec::with_capacity(schem.blocks.len());
+        for block in &schem.blocks {
+            let next = palette.len() as i32;
+            let id = *palette.entry(block.full_name()).or_insert(next);
+            write_varint(&mut data, id as u32);
+        }
+
+        let (x, y, z) = schem.offset;
+        let meta = &schem.metadata;
+        let schematic = Schem {
+            version: 3,
+            data_version: meta.data_version,
+            width: Some(short(width)?),
+            height: Some(short(height)?),
+            length: Some(short(length)?),
+            offset: Some(fastnbt::IntArray::new(vec![x, y, z])),
+            palette: None,
+            palette_max: None,
+            block_data: None,
+            block_entities: Vec::new(),
+            tile_entities: Vec::new(),
+            entities: schem.entities.iter()
+                .map(|entity| SchemEntity {
+                    id: Some(entity.id.clone()),
+                    pos: Some(vec![entity.pos.0, entity.pos.1, entity.pos.2]),
+                    extra: HashMap::from([("Data".to_string(), fastnbt::Value::Compound(entity.nbt.clone()))]),
+                })
+                .collect(),
+            metadata: Some(SchemMetadata {
+                name: meta.name.clone(),
+                author: meta.author.clone(),
+                date: meta.date,
+                we_offset_x: meta.we_offset.map(|offset| offset.0),
+                we_offset_y: meta.we_offset.map(|offset| offset.1),
+                we_offset_z: meta.we_offset.map(|offset| offset.2),
+                extra: meta.own_extra().into_iter()
+                    .map(|(key, value)| (key.to_string(), fastnbt::Value::String(value.to_string())))
+                    .collect(),
+                ..Default::default()
+            }),
+            schematic: None,
+            blocks: Some(SchemBlocks {
+                palette,
+                data: Some(fastnbt::ByteArray::new(data)),
+                block_entities: schem.block_entities.iter()
+                    .map(|be| SchemBlockEntity {
+                        id: Some(be.id.clone()),
+                        pos: Some(fastnbt::IntArray::new(vec![be.pos.0, be.pos.1, be.pos.2])),
+                        x: None,
+                        y: None,
+                        z: None,
+                        extra: HashMap::from([("Data".to_string(), fastnbt::Value::Compound(be.nbt.clone()))]),
+                    })
+                    .collect(),
+            }),
+            biomes: None,
+            // Sponge v2 root fields belong under v3's "Schematic" compound just the same
+            extra: meta.raw_extra_root(matches!(schem.format, SchematicFormat::SpongeV2 | SchematicFormat::SpongeV3), "Sponge"),
+        };
+        Ok(SchemWrapper { schematic })
+    }
+
+    /// Gzipped NBT, as stored in .schem files
+    pub fn to_bytes(&self) -> Result<Vec<u8>, SchemError> {
+        crate::gzip_nbt(self)
+    }
+}
+
+/// Palette id -> block, for ids as WorldEdit writes them (dense, from 0) and any stragglers
+struct PaletteLookup {
+    dense: Vec<Option<Block>>,
+    sparse: HashMap<u32, Block>,
+}
+
+impl PaletteLookup {
+    fn new(palette: &HashMap<String, i32>) -> Self {
+        let mut dense = vec![None; palette.len()];
+        let mut sparse = HashMap::new();
+        for (state_str, &id) in palette {
+            let block = Schem::parse_block_state(state_str);
+            match usize::try_from(id) {
+                Ok(i) if i < dense.len() => dense[i] = Some(block),
+                Ok(_) => { sparse.insert(id as u32, block); }
+                // Negative ids can't be referenced by varints
+                Err(_) => {}
+            }
+        }
+        Self { dense, sparse }
+    }
+
+    fn get(&self, id: u32) -> Option<&Block> {
+        match self.dense.get(id as usize) {
+            Some(entry) => entry.as_ref(),
+            None => self.sparse.get(&id),
+        }
+    }
+
+    /// Number of palette entries
+    fn len(&self) -> usize {
+        self.dense.iter().flatten().count() + self.sparse.len()
+    }
+}
+
+/// Why a varint could not be read
+enum VarintError {
+    /// The data ended partway through (or before) the varint
+    Eof,
+    /// More than 5 bytes, which can't fit in 32 bits
+    TooLong,
+}
+
+/// Read a variable-length integer, advancing `offset` past it
+fn read_varint(data: &[i8], offset: &mut usize) -> Result<u32, VarintError> {
+    let mut result: u32 = 0;
+
+    for shift in (0..35).step_by(7) {
+        let byte = *data.get(*offset).ok_or(VarintError::Eof)? as u8;
+        *offset += 1;
+
+        result |= ((byte & 0x7F) as u32) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(result);
+        }
+    }
+
+    Err(VarintError::TooLong)
+}
+
+/// Append a variable-length integer
+fn write_varint(data: &mut Vec<i8>, mut value: u32) {
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+        if value == 0 {
+            data.push(byte as i8);
+            return;
         }
+        data.push((byte | 0x80) as i8);
     }
 }
 
-impl From<Schem> for UnifiedSchematic {
-    fn from(schem: Schem) -> Self {
+/// Decode varint-encoded BlockData into exactly `volume` blocks, and the
+/// number of them that had to be recovered
+///
+/// In lenient mode unknown palette ids and missing tail blocks become air and
+/// the number of substituted blocks is logged instead of returning an error.
+fn decode_block_data(data: &[i8], palette: &PaletteLookup, volume: usize, lenient: bool) -> Result<(Vec<Block>, usize), SchemError> {
+    let mut blocks = Vec::with_capacity(volume);
+    let mut offset = 0;
+    let mut recovered = 0;
+
+    while blocks.len() < volume {
+        let start = offset;
+        match read_varint(data, &mut offset) {
+            Ok(id) => match palette.get(id) {
+                Some(block) => blocks.push(block.clone()),
+                None if lenient => {
+                    blocks.push(Block::air());
+                    recovered += 1;
+                }
+                None => {
+                    return Err(SchemError::CorruptData {
+                        offset: start,
+                        reason: format!("palette id {} is not among the {} palette entries", id, palette.len()),
+                        expected: palette.len(),
+                        actual: id as usize,
+                    });
+                }
+            },
+            Err(_) if lenient => {
+                recovered += volume - blocks.len();
+                blocks.resize(volume, Block::air());
+            }
+            Err(VarintError::Eof) => {
+                return Err(SchemError::CorruptData {
+                    offset: start,
+                    reason: format!("block data ended after {} of {} blocks", blocks.len(), volume),
+                    expected: volume,
+                    actual: blocks.len(),
+                });
+            }
+            Err(VarintError::TooLong) => {
+                return Err(SchemError::CorruptData {
+                    offset: start,
+                    reason: format!("varint longer than 5 bytes after {} of {} blocks", blocks.len(), volume),
+                    expected: volume,
+                    actual: blocks.len(),
+                });
+            }
+        }
+    }
+
+    if recovered > 0 {
+        log::warn!("Corrupt block data: filled {} of {} blocks with air", recovered, volume);
+    }
+
+    Ok((blocks, recovered))
+}
+
+impl TryFrom<Schem> for UnifiedSchematic {
+    type Error = SchemError;
+
+    fn try_from(schem: Schem) -> Result<Self, SchemError> {
         schem.to_unified()
     }
 }
 
+/// Block entity or entity fields with the v3 `Data` compound (in any casing)
+/// unwrapped into the top level
+///
+/// Fields already at the top level win, and the id and position copies some
+/// exporters repeat inside `Data` are left out.
+fn nbt_payload(fields: &HashMap<String, fastnbt::Value>) -> HashMap<String, fastnbt::Value> {
+    let is_data = |key: &str, value: &fastnbt::Value| {
+        key.eq_ignore_ascii_case("data") && matches!(value, fastnbt::Value::Compound(_))
+    };
+    let mut nbt: HashMap<String, fastnbt::Value> = fields.iter()
+        .filter(|(key, value)| !is_data(key, value))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+    for (key, value) in fields {
+        if let fastnbt::Value::Compound(inner) = value {
+            if !is_data(key, value) {
+                continue;
+            }
+            for (key, value) in inner {
+                if !matches!(key.to_ascii_lowercase().as_str(), "id" | "pos" | "x" | "y" | "z") {
+                    nbt.entry(key.clone()).or_insert_with(|| value.clone());
+                }
+            }
+        }
+    }
+    nbt
+}
+
+/// [`nbt_payload`] fields as text
+fn payload(fields: &HashMap<String, fastnbt::Value>) -> HashMap<String, String> {
+    fields.iter().map(|(key, value)| (key.clone(), format_nbt_value(value))).collect()
+}
+
 /// Format NBT value for display
-fn format_nbt_value(value: &fastnbt::Value) -> String {
+pub(crate) fn format_nbt_value(value: &fastnbt::Value) -> String {
     match value {
         fastnbt::Value::Byte(b) => b.to_string(),
         fastnbt::Value::Short(s) => s.to_string(),
@@ -384,6 +611,217 @@ fn format_nbt_value(value: &fastnbt::Value) -> String {
         fastnbt::Value::IntArray(arr) => format!("[{} ints]", arr.len()),
         fastnbt::Value::LongArray(arr) => format!("[{} longs]", arr.len()),
         fastnbt::Value::List(list) => format!("[{} items]", list.len()),
-        fastnbt::Value::Compound(map) => format!("{{{} entries}}", map.len()),
+        fastnbt::Value::Compound(map) => {
+            let mut entries: Vec<_> = map.iter()
+                .map(|(k, v)| format!("{}={}", k, format_nbt_value(v)))
+                .collect();
+            entries.sort();
+            format!("{{{}}}", entries.join(", "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2x2x2 v2 schematic over a two-entry palette
+    fn schem(block_data: Vec<i8>) -> Schem {
+        Schem {
+            version: 2,
+            data_version: None,
+            width: Some(2),
+            height: Some(2),
+            length: Some(2),
+            offset: None,
+            palette: Some(HashMap::from([
+                ("minecraft:air".to_string(), 0),
+                ("minecraft:stone".to_string(), 1),
+            ])),
+            // WorldEdit sometimes writes a PaletteMax above the real entry count
+            palette_max: Some(5),
+            block_data: Some(fastnbt::ByteArray::new(block_data)),
+            block_entities: Vec::new(),
+            tile_entities: Vec::new(),
+            entities: Vec::new(),
+            metadata: None,
+            schematic: None,
+            blocks: None,
+            biomes: None,
+            extra: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_truncated_block_data_never_panics() {
+        // Multi-byte varints (128 = [0x80, 0x01]) so truncation can split one
+        let palette = HashMap::from([("minecraft:stone".to_string(), 128)]);
+        let lookup = PaletteLookup::new(&palette);
+        let full: Vec<i8> = [0x80u8, 0x01].repeat(8).into_iter().map(|b| b as i8).collect();
+
+        for len in 0..full.len() {
+            let data = &full[..len];
+            match decode_block_data(data, &lookup, 8, false) {
+                Err(SchemError::CorruptData { expected, actual, .. }) => {
+                    assert_eq!(expected, 8);
+                    assert_eq!(actual, len / 2);
+                }
+                other => panic!("expected CorruptData for {} bytes, got {:?}", len, other),
+            }
+
+            let (blocks, recovered) = decode_block_data(data, &lookup, 8, true).unwrap();
+            assert_eq!(blocks.len(), 8);
+            assert_eq!(recovered, 8 - len / 2);
+            assert_eq!(blocks.iter().filter(|b| b.name == "minecraft:stone").count(), len / 2);
+        }
+
+        // Overlong varint
+        let data = vec![-1i8; 16];
+        assert!(matches!(decode_block_data(&data, &lookup, 8, false), Err(SchemError::CorruptData { offset: 0, .. })));
+        assert_eq!(decode_block_data(&data, &lookup, 8, true).unwrap().0.len(), 8);
+    }
+
+    #[test]
+    fn test_palette_id_out_of_range() {
+        let data = vec![1, 0, 1, 4, 0, 0, 0, 0];
+
+        match schem(data.clone()).to_unified() {
+            Err(SchemError::CorruptData { offset, expected, actual, .. }) => {
+                assert_eq!((offset, expected, actual), (3, 2, 4));
+            }
+            other => panic!("expected CorruptData, got {:?}", other.map(|s| s.dimensions_str())),
+        }
+
+        let unified = schem(data).to_unified_with(true).unwrap();
+        assert_eq!(unified.blocks.len(), 8);
+        assert_eq!(unified.solid_blocks(), 2);
+        assert!(unified.blocks[3].is_air());
+    }
+
+    #[test]
+    fn test_malformed_state_strings() {
+        for state in ["minecraft:chest[", "minecraft:chest[facing", "[", "minecraft:chest[facing=north"] {
+            Schem::parse_block_state(state);
+        }
+        let block = Schem::parse_block_state("minecraft:chest[facing=north");
+        assert_eq!(block.state.properties.get("facing").map(String::as_str), Some("north"));
+    }
+
+    #[test]
+    fn test_from_unified_round_trip() {
+        use crate::builder::SchematicBuilder;
+
+        // Over 128 palette entries, so ids take two varint bytes
+        let mut builder = SchematicBuilder::new(20, 2, 10);
+        for i in 0..300u32 {
+            builder.set_block(i % 20, i / 200, (i / 20) % 10, Block::new(format!("minecraft:block_{}", i % 150))).unwrap();
+        }
+        let nbt = HashMap::from([("Lock".to_string(), fastnbt::Value::String("key".to_string()))]);
+        builder.add_block_entity(crate::BlockEntity { id: "minecraft:chest".to_string(), pos: (3, 1, 4), nbt, ..Default::default() }).unwrap();
+        let nbt = HashMap::from([("Health".to_string(), fastnbt::Value::Float(4.0))]);
+        builder.add_entity(crate::Entity { id: "minecraft:chicken".to_string(), pos: (0.5, 2.0, 9.5), nbt, ..Default::default() });
+        let mut schem = builder.build();
+        schem.offset = (-100, -64, 7);
+        schem.metadata.set_name("Round trip");
+
+        let wrapper = SchemWrapper::from_unified(&schem).unwrap();
+        assert_eq!(wrapper.schematic.blocks.as_ref().unwrap().palette.len(), 151);
+        let back = wrapper.schematic.to_unified().unwrap();
+        assert!(matches!(back.format, SchematicFormat::SpongeV3));
+        assert_eq!(back.offset, (-100, -64, 7));
+        assert_eq!(back.metadata.name.as_deref(), Some("Round trip"));
+        assert_eq!(back.blocks, schem.blocks);
+        let be = &back.block_entities[0];
+        assert_eq!((be.id.as_str(), be.pos), ("minecraft:chest", (3, 1, 4)));
+        assert_eq!(be.nbt, schem.block_entities[0].nbt);
+        assert_eq!(be.data["Lock"], "key");
+        let entity = &back.entities[0];
+        assert_eq!((entity.id.as_str(), entity.pos), ("minecraft:chicken", (0.5, 2.0, 9.5)));
+        assert_eq!(entity.mob_info().health, Some(4.0));
+
+        schem.width = 70_000;
+        assert!(matches!(SchemWrapper::from_unified(&schem), Err(SchemError::TooLarge { .. })));
+    }
+
+    fn chest(pos: Option<[i32; 3]>, xyz: (Option<i32>, Option<i32>, Option<i32>)) -> SchemBlockEntity {
+        SchemBlockEntity {
+            id: Some("minecraft:chest".to_string()),
+            pos: pos.map(|p| fastnbt::IntArray::new(p.to_vec())),
+            x: xyz.0,
+            y: xyz.1,
+            z: xyz.2,
+            extra: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_block_entity_containers_and_positions() {
+        let positions = |s: &Schem| -> Vec<(i32, i32, i32)> {
+            s.to_unified().unwrap().block_entities.iter().map(|be| be.pos).collect()
+        };
+
+        // v2 from older WorldEdit builds: TileEntities with x/y/z
+        let mut v2 = schem(vec![0; 8]);
+        v2.tile_entities = vec![chest(None, (Some(1), Some(0), Some(1)))];
+        assert_eq!(positions(&v2), vec![(1, 0, 1)]);
+
+        // v3 nested under Schematic, block entities in Blocks with Pos
+        let mut inner = schem(Vec::new());
+        inner.version = 3;
+        inner.palette = None;
+        inner.blocks = Some(SchemBlocks {
+            palette: HashMap::from([("minecraft:air".to_string(), 0)]),
+            data: Some(fastnbt::ByteArray::new(vec![0; 8])),
+            block_entities: vec![chest(Some([0, 1, 1]), (None, None, None))],
+        });
+        let mut v3 = schem(Vec::new());
+        v3.version = 3;
+        v3.schematic = Some(Box::new(inner));
+        assert_eq!(positions(&v3), vec![(0, 1, 1)]);
+
+        // v3 with the block entities left at the root under either name
+        let inner = v3.schematic.as_mut().unwrap();
+        let moved = std::mem::take(&mut inner.blocks.as_mut().unwrap().block_entities);
+        inner.tile_entities = moved;
+        assert_eq!(positions(&v3), vec![(0, 1, 1)]);
+        let inner = v3.schematic.as_mut().unwrap();
+        inner.block_entities = std::mem::take(&mut inner.tile_entities);
+        assert_eq!(positions(&v3), vec![(0, 1, 1)]);
+    }
+
+    #[test]
+    fn test_v3_data_compound_is_unwrapped() {
+        use fastnbt::Value;
+        let text = |s: &str| Value::String(s.to_string());
+
+        let mut chest = chest(Some([0, 0, 0]), (None, None, None));
+        chest.extra = HashMap::from([(
+            "Data".to_string(),
+            Value::Compound(HashMap::from([
+                ("id".to_string(), text("minecraft:chest")),
+                ("Lock".to_string(), text("key")),
+                ("CustomName".to_string(), text("Loot")),
+            ])),
+        )]);
+        // Lowercase payload key, with a top-level field that takes precedence
+        let mut barrel = chest.clone();
+        barrel.id = Some("minecraft:barrel".to_string());
+        barrel.extra = HashMap::from([
+            ("data".to_string(), Value::Compound(HashMap::from([("CustomName".to_string(), text("Inner"))]))),
+            ("CustomName".to_string(), text("Outer")),
+        ]);
+
+        let mut v2 = schem(vec![0; 8]);
+        v2.block_entities = vec![chest, barrel];
+        let unified = v2.to_unified().unwrap();
+
+        let chest = &unified.block_entities[0].data;
+        assert_eq!(chest.get("Lock").map(String::as_str), Some("key"));
+        assert_eq!(chest.get("CustomName").map(String::as_str), Some("Loot"));
+        assert!(!chest.contains_key("Data") && !chest.contains_key("id"));
+
+        let barrel = &unified.block_entities[1].data;
+        assert_eq!(barrel.get("CustomName").map(String::as_str), Some("Outer"));
+        assert_eq!(barrel.len(), 1);
     }
 }
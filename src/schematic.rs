@@ -6,11 +6,12 @@
 //! - Data: byte array (block data/damage values)
 //! - Entities: list of entity compounds
 //! - TileEntities: list of tile entity compounds
+//! - WEOriginX/Y/Z, WEOffsetX/Y/Z: int (optional, WorldEdit copy origin and offset)
 
 use serde::{Deserialize, Serialize};
 use crate::{
-    Block, BlockState, BlockEntity, Entity, Metadata,
-    SchematicFormat, UnifiedSchematic,
+    checked_volume, Block, BlockState, BlockEntity, Entity, Metadata,
+    SchemError, SchematicFormat, UnifiedSchematic,
     block::{legacy_id_to_name, legacy_data_to_state},
 };
 use std::collections::HashMap;
@@ -45,7 +46,16 @@ pub struct Schematic {
     #[serde(rename = "TileEntities", default)]
     pub tile_entities: Vec<LegacyTileEntity>,
 
-    // Optional offset
+    // WorldEdit copy origin and the minimum corner's offset from it
+    #[serde(rename = "WEOriginX", default)]
+    pub we_origin_x: Option<i32>,
+
+    #[serde(rename = "WEOriginY", default)]
+    pub we_origin_y: Option<i32>,
+
+    #[serde(rename = "WEOriginZ", default)]
+    pub we_origin_z: Option<i32>,
+
     #[serde(rename = "WEOffsetX", default)]
     pub we_offset_x: Option<i32>,
 
@@ -60,6 +70,15 @@ pub struct Schematic {
     pub schematica_mapping: Option<HashMap<String, i16>>,
 }
 
+/// Root keys read by [`Schematic`]
+impl crate::extra_nbt::KnownFields for Schematic {
+    const KNOWN: &'static [&'static str] = &[
+        "Width", "Height", "Length", "Materials", "Blocks", "Data", "AddBlocks",
+        "Entities", "TileEntities", "WEOriginX", "WEOriginY", "WEOriginZ",
+        "WEOffsetX", "WEOffsetY", "WEOffsetZ", "SchematicaMapping",
+    ];
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct LegacyEntity {
     pub id: Option<String>,
@@ -103,18 +122,22 @@ impl Schematic {
     }
 
     /// Convert to unified format
-    pub fn to_unified(&self) -> UnifiedSchematic {
-        let width = self.width as u16;
-        let height = self.height as u16;
-        let length = self.length as u16;
+    pub fn to_unified(&self) -> Result<UnifiedSchematic, SchemError> {
+        // Dimensions are unsigned shorts stored in signed NBT tags
+        let width = self.width as u16 as u32;
+        let height = self.height as u16 as u32;
+        let length = self.length as u16 as u32;
+        let volume = checked_volume(width, height, length)?;
 
         // Build reverse mapping if Schematica mapping exists
         let id_to_name: Option<HashMap<i16, String>> = self.schematica_mapping.as_ref().map(|m| {
             m.iter().map(|(name, id)| (*id, name.clone())).collect()
         });
 
-        // Parse blocks
-        let mut blocks = Vec::with_capacity(self.blocks.len());
+        // Parse blocks, converting each ID and data value once so equal blocks
+        // share their state
+        let mut converted: HashMap<(u16, u8), Block> = HashMap::new();
+        let mut blocks = Vec::with_capacity(volume);
         for y in 0..height {
             for z in 0..length {
                 for x in 0..width {
@@ -122,19 +145,21 @@ impl Schematic {
                     let block_id = self.get_block_id(index);
                     let data_value = self.data.get(index).copied().unwrap_or(0) as u8;
 
-                    let (name, state) = if let Some(ref mapping) = id_to_name {
-                        // Use Schematica mapping
-                        if let Some(mapped_name) = mapping.get(&(block_id as i16)) {
-                            (mapped_name.clone(), BlockState::default())
+                    let block = converted.entry((block_id, data_value)).or_insert_with(|| {
+                        let (name, state) = if let Some(ref mapping) = id_to_name {
+                            // Use Schematica mapping
+                            if let Some(mapped_name) = mapping.get(&(block_id as i16)) {
+                                (mapped_name.clone(), BlockState::default())
+                            } else {
+                                (legacy_id_to_name(block_id as u8, data_value), legacy_data_to_state(block_id as u8, data_value))
+                            }
                         } else {
+                            // Use legacy ID mapping
                             (legacy_id_to_name(block_id as u8, data_value), legacy_data_to_state(block_id as u8, data_value))
-                        }
-                    } else {
-                        // Use legacy ID mapping
-                        (legacy_id_to_name(block_id as u8, data_value), legacy_data_to_state(block_id as u8, data_value))
-                    };
-
-                    blocks.push(Block::with_state(name, state));
+                        };
+                        Block::with_state(name, state)
+                    });
+                    blocks.push(block.clone());
                 }
             }
         }
@@ -156,7 +181,7 @@ impl Schematic {
                 data.insert(key.clone(), format!("{:?}", value));
             }
 
-            BlockEntity { id, pos, data }
+            BlockEntity { id, pos, data, nbt: te.extra.clone() }
         }).collect();
 
         // Parse entities
@@ -174,24 +199,43 @@ impl Schematic {
                 data.insert(key.clone(), format!("{:?}", value));
             }
 
-            Some(Entity { id, pos, data })
+            Some(Entity { id, pos, data, nbt: e.extra.clone() })
         }).collect();
 
-        UnifiedSchematic {
+        let triple = |x: Option<i32>, y: Option<i32>, z: Option<i32>| Some((x?, y?, z?));
+        let metadata = Metadata {
+            we_origin: triple(self.we_origin_x, self.we_origin_y, self.we_origin_z),
+            we_offset: triple(self.we_offset_x, self.we_offset_y, self.we_offset_z),
+            ..Default::default()
+        };
+
+        // The minimum corner sits at origin + offset
+        let axis = |origin: Option<i32>, offset: Option<i32>| origin.unwrap_or(0).wrapping_add(offset.unwrap_or(0));
+        let offset = (
+            axis(self.we_origin_x, self.we_offset_x),
+            axis(self.we_origin_y, self.we_offset_y),
+            axis(self.we_origin_z, self.we_offset_z),
+        );
+
+        Ok(UnifiedSchematic {
             format: SchematicFormat::Legacy,
             width,
             height,
             length,
+            offset,
             blocks,
             block_entities,
             entities,
-            metadata: Metadata::default(),
-        }
+            metadata,
+            load_report: Default::default(),
+        })
     }
 }
 
-impl From<Schematic> for UnifiedSchematic {
-    fn from(schematic: Schematic) -> Self {
+impl TryFrom<Schematic> for UnifiedSchematic {
+    type Error = SchemError;
+
+    fn try_from(schematic: Schematic) -> Result<Self, SchemError> {
         schematic.to_unified()
     }
 }
@@ -0,0 +1,183 @@
+//! Serde support for `UnifiedSchematic` (enabled by the `serde` feature)
+//!
+//! Blocks are stored as a palette of unique states plus one palette index per
+//! position (YZX order), so large schematics don't repeat block names millions
+//! of times. The other types derive Serialize/Deserialize directly.
+//!
+//! Schema stability: the top-level `version` field is bumped whenever a change
+//! would break reading older data. Within a version, fields may be added but
+//! not removed or renamed; data from a newer version is rejected.
+
+use std::collections::HashMap;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use crate::{Block, BlockEntity, Entity, Metadata, SchematicFormat, UnifiedSchematic};
+
+/// Current version of the serialized schema
+const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Serialize)]
+struct CompactRef<'a> {
+    version: u32,
+    format: &'a SchematicFormat,
+    width: u32,
+    height: u32,
+    length: u32,
+    offset: (i32, i32, i32),
+    palette: Vec<&'a Block>,
+    blocks: Vec<u32>,
+    block_entities: &'a [BlockEntity],
+    entities: &'a [Entity],
+    metadata: &'a Metadata,
+}
+
+#[derive(Deserialize)]
+struct Compact {
+    version: u32,
+    format: SchematicFormat,
+    width: u32,
+    height: u32,
+    length: u32,
+    #[serde(default)]
+    offset: (i32, i32, i32),
+    palette: Vec<Block>,
+    blocks: Vec<u32>,
+    #[serde(default)]
+    block_entities: Vec<BlockEntity>,
+    #[serde(default)]
+    entities: Vec<Entity>,
+    #[serde(default)]
+    metadata: Metadata,
+}
+
+impl Serialize for UnifiedSchematic {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut lookup: HashMap<String, u32> = HashMap::new();
+        let mut palette = Vec::new();
+        let mut blocks = Vec::with_capacity(self.blocks.len());
+
+        for block in &self.blocks {
+            let id = *lookup.entry(block.to_selector()).or_insert_with(|| {
+                palette.push(block);
+                (palette.len() - 1) as u32
+            });
+            blocks.push(id);
+        }
+
+        CompactRef {
+            version: SCHEMA_VERSION,
+            format: &self.format,
+            width: self.width,
+            height: self.height,
+            length: self.length,
+            offset: self.offset,
+            palette,
+            blocks,
+            block_entities: &self.block_entities,
+            entities: &self.entities,
+            metadata: &self.metadata,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for UnifiedSchematic {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let compact = Compact::deserialize(deserializer)?;
+
+        if compact.version > SCHEMA_VERSION {
+            return Err(D::Error::custom(format!(
+                "unsupported schematic schema version {} (max {})",
+                compact.version, SCHEMA_VERSION
+            )));
+        }
+
+        let volume = crate::checked_volume(compact.width, compact.height, compact.length)
+            .map_err(D::Error::custom)?;
+        if compact.blocks.len() != volume {
+            return Err(D::Error::custom(format!(
+                "expected {} block indices, found {}",
+                volume,
+                compact.blocks.len()
+            )));
+        }
+
+        let blocks = compact.blocks.iter()
+            .map(|&id| {
+                compact.palette.get(id as usize).cloned().ok_or_else(|| {
+                    D::Error::custom(format!("palette index {} out of range", id))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(UnifiedSchematic {
+            format: compact.format,
+            width: compact.width,
+            height: compact.height,
+            length: compact.length,
+            offset: compact.offset,
+            blocks,
+            block_entities: compact.block_entities,
+            entities: compact.entities,
+            metadata: compact.metadata,
+            load_report: Default::default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BlockState, SchematicBuilder};
+
+    fn sample() -> UnifiedSchematic {
+        let mut builder = SchematicBuilder::new(3, 2, 2);
+        let mut state = BlockState::default();
+        state.properties.insert("facing".to_string(), "north".to_string());
+        state.properties.insert("half".to_string(), "top".to_string());
+        builder.fill((0, 0, 0), (2, 0, 1), Block::new("minecraft:stone")).unwrap();
+        builder.set_block(1, 1, 1, Block::with_state("minecraft:oak_stairs", state)).unwrap();
+
+        let mut be = BlockEntity { id: "minecraft:chest".to_string(), pos: (1, 1, 0), ..Default::default() };
+        be.data.insert("CustomName".to_string(), "Loot".to_string());
+        builder.add_block_entity(be).unwrap();
+        builder.add_entity(Entity { id: "minecraft:pig".to_string(), pos: (0.5, 1.0, 0.5), ..Default::default() });
+
+        let mut metadata = Metadata { name: Some("Test".to_string()), date: Some(1_700_000_000_000), ..Default::default() };
+        metadata.required_mods.push("create".to_string());
+        builder.set_metadata(metadata);
+        builder.set_offset(-10, 64, 200);
+        builder.build()
+    }
+
+    fn assert_same(a: &UnifiedSchematic, b: &UnifiedSchematic) {
+        assert_eq!(a.dimensions_str(), b.dimensions_str());
+        assert_eq!(a.offset, b.offset);
+        assert_eq!(format!("{:?}", a.format), format!("{:?}", b.format));
+        assert_eq!(a.blocks, b.blocks);
+        assert_eq!(a.block_entities.len(), b.block_entities.len());
+        assert_eq!(a.block_entities[0].pos, b.block_entities[0].pos);
+        assert_eq!(a.block_entities[0].data, b.block_entities[0].data);
+        assert_eq!(a.entities[0].pos, b.entities[0].pos);
+        assert_eq!(a.metadata.name, b.metadata.name);
+        assert_eq!(a.metadata.date, b.metadata.date);
+        assert_eq!(a.metadata.required_mods, b.metadata.required_mods);
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let schem = sample();
+        let json = serde_json::to_string(&schem).unwrap();
+        let back: UnifiedSchematic = serde_json::from_str(&json).unwrap();
+        assert_same(&schem, &back);
+    }
+
+    #[test]
+    fn test_cbor_round_trip() {
+        let schem = sample();
+        let mut bytes = Vec::new();
+        ciborium::into_writer(&schem, &mut bytes).unwrap();
+        let back: UnifiedSchematic = ciborium::from_reader(&bytes[..]).unwrap();
+        assert_same(&schem, &back);
+    }
+}
@@ -0,0 +1,419 @@
+//! Browsing a folder of schematics from a web browser
+//!
+//! [`serve`] answers:
+//!
+//! - `/`: the schematic files under the folder, with links to the pages below
+//! - `/info/<file>`: the [summary](UnifiedSchematic::summary) and raw materials
+//!   as JSON
+//! - `/view/<file>`: the interactive [HTML viewer](crate::export3d::html_viewer)
+//! - `/glb/<file>`: the model as a GLB download (cubes, no textures)
+//!
+//! Loaded schematics are kept in a [`SchematicCache`], so clicking through a
+//! file's pages loads it once.
+//!
+//! This is meant for a trusted LAN only: there's no authentication and no TLS,
+//! and anyone who can reach the port can read every schematic in the folder.
+
+use std::collections::VecDeque;
+use std::net::ToSocketAddrs;
+use std::path::{Component, Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::export3d::{html_viewer, ExportOptions};
+use crate::recipes::{material_report, round_materials, MaterialOptions};
+use crate::{BlockClass, SchemError, UnifiedSchematic};
+
+/// Extensions listed and served
+pub const SCHEMATIC_EXTENSIONS: &[&str] = &["schem", "schematic", "litematic", "nbt"];
+
+/// Settings for [`serve`]
+#[derive(Debug, Clone, Copy)]
+pub struct ServeOptions {
+    /// Most blocks the HTML viewer shows
+    pub max_blocks: usize,
+    /// Most schematics kept loaded
+    pub cache_size: usize,
+}
+
+impl Default for ServeOptions {
+    fn default() -> Self {
+        Self { max_blocks: 100_000, cache_size: 16 }
+    }
+}
+
+struct CacheEntry {
+    path: PathBuf,
+    modified: SystemTime,
+    schematic: UnifiedSchematic,
+    /// The viewer page, once someone asked for it
+    html: Option<String>,
+}
+
+/// Loaded schematics by path, least recently used first out; a file changed
+/// on disk since it was loaded is loaded again
+pub struct SchematicCache {
+    capacity: usize,
+    /// Most recently used last
+    entries: VecDeque<CacheEntry>,
+}
+
+impl SchematicCache {
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity: capacity.max(1), entries: VecDeque::new() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn entry(&mut self, path: &Path) -> Result<&mut CacheEntry, SchemError> {
+        let modified = std::fs::metadata(path)?.modified()?;
+        let index = match self.entries.iter().position(|entry| entry.path == path) {
+            Some(index) if self.entries[index].modified == modified => index,
+            stale => {
+                if let Some(index) = stale {
+                    self.entries.remove(index);
+                }
+                let schematic = UnifiedSchematic::load(path)?;
+                if self.entries.len() >= self.capacity {
+                    self.entries.pop_front();
+                }
+                self.entries.push_back(CacheEntry { path: path.to_path_buf(), modified, schematic, html: None });
+                self.entries.len() - 1
+            }
+        };
+        let entry = self.entries.remove(index).expect("index in range");
+        self.entries.push_back(entry);
+        Ok(self.entries.back_mut().expect("just pushed"))
+    }
+
+    /// The schematic at `path`, loading it if it isn't cached or has changed
+    pub fn get(&mut self, path: &Path) -> Result<&UnifiedSchematic, SchemError> {
+        Ok(&self.entry(path)?.schematic)
+    }
+
+    /// The viewer page for the schematic at `path`, built on first use
+    pub fn html(&mut self, path: &Path, max_blocks: usize) -> Result<&str, SchemError> {
+        let entry = self.entry(path)?;
+        if entry.html.is_none() {
+            entry.html = Some(html_viewer(&entry.schematic, max_blocks, &ExportOptions::new())?);
+        }
+        Ok(entry.html.as_deref().unwrap_or_default())
+    }
+}
+
+/// An answer to a request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reply {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: Vec<u8>,
+    /// Offer the body as a download with this file name
+    pub download: Option<String>,
+}
+
+impl Reply {
+    fn new(content_type: &'static str, body: impl Into<Vec<u8>>) -> Self {
+        Self { status: 200, content_type, body: body.into(), download: None }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Self { status, ..Self::new("text/plain; charset=utf-8", message) }
+    }
+}
+
+/// The routing behind [`serve`], without the HTTP
+pub struct Browser {
+    dir: PathBuf,
+    options: ServeOptions,
+    cache: SchematicCache,
+}
+
+impl Browser {
+    pub fn new(dir: impl Into<PathBuf>, options: ServeOptions) -> Self {
+        Self { dir: dir.into(), options, cache: SchematicCache::new(options.cache_size) }
+    }
+
+    /// Answer a GET for `url` (path and optional query)
+    pub fn handle(&mut self, url: &str) -> Reply {
+        let path = url.split(['?', '#']).next().unwrap_or_default();
+        let (route, file) = match path.trim_start_matches('/').split_once('/') {
+            Some((route, file)) => (route, Some(file)),
+            None => (path.trim_start_matches('/'), None),
+        };
+        let result = match (route, file) {
+            ("", None) => self.index(),
+            ("info" | "view" | "glb", Some(file)) => {
+                let Some((name, path)) = self.resolve(file) else {
+                    return Reply::error(404, "No such schematic");
+                };
+                match route {
+                    "info" => self.info(&name, &path),
+                    "view" => self.view(&path),
+                    _ => self.glb(&path),
+                }
+            }
+            _ => return Reply::error(404, "Not found"),
+        };
+        result.unwrap_or_else(|e| Reply::error(500, &e.to_string()))
+    }
+
+    /// The file a URL path names, as its path relative to the folder and on
+    /// disk; `None` for anything that isn't a schematic inside the folder,
+    /// including symlinks that lead out of it
+    fn resolve(&self, file: &str) -> Option<(String, PathBuf)> {
+        let name = percent_decode(file)?;
+        let relative = Path::new(&name);
+        if name.contains('\\') || !relative.components().all(|c| matches!(c, Component::Normal(_))) {
+            return None;
+        }
+        let path = self.dir.join(relative);
+        let inside = path.canonicalize().ok()?.starts_with(self.dir.canonicalize().ok()?);
+        (inside && is_schematic(&path) && path.is_file()).then_some((name, path))
+    }
+
+    fn index(&self) -> Result<Reply, SchemError> {
+        let mut items = String::new();
+        for path in find_schematics(&self.dir)? {
+            let Ok(relative) = path.strip_prefix(&self.dir) else { continue };
+            let name = relative.components()
+                .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                .collect::<Vec<_>>()
+                .join("/");
+            let url = percent_encode(&name);
+            items.push_str(&format!(
+                "<li>{} <a href=\"/view/{url}\">view</a> <a href=\"/info/{url}\">info</a> <a href=\"/glb/{url}\">glb</a></li>\n",
+                html_escape(&name),
+            ));
+        }
+        if items.is_empty() {
+            items.push_str("<li>No schematics here</li>\n");
+        }
+        let html = format!(r#"<!DOCTYPE html>
+<html>
+<head>
+    <meta charset="utf-8">
+    <title>Schematics in {dir}</title>
+    <style>
+        body {{ font-family: sans-serif; margin: 2em; }}
+        li {{ margin: 0.3em 0; }}
+        a {{ margin-left: 0.5em; }}
+    </style>
+</head>
+<body>
+    <h1>Schematics in {dir}</h1>
+    <ul>
+{items}    </ul>
+</body>
+</html>"#,
+            dir = html_escape(&self.dir.display().to_string()),
+            items = items,
+        );
+        Ok(Reply::new("text/html; charset=utf-8", html))
+    }
+
+    fn info(&mut self, name: &str, path: &Path) -> Result<Reply, SchemError> {
+        let schematic = self.cache.get(path)?;
+        let mut counts = schematic.block_counts();
+        counts.retain(|name, _| BlockClass::of(name) != BlockClass::Technical);
+        let report = material_report(&counts, MaterialOptions::new());
+        let json = serde_json::json!({
+            "file": name,
+            "summary": schematic.summary(10),
+            "materials": round_materials(&report.materials),
+            "no_recipe": report.unknown,
+        });
+        Ok(Reply::new("application/json", serde_json::to_vec_pretty(&json)?))
+    }
+
+    fn view(&mut self, path: &Path) -> Result<Reply, SchemError> {
+        let html = self.cache.html(path, self.options.max_blocks)?;
+        Ok(Reply::new("text/html; charset=utf-8", html))
+    }
+
+    fn glb(&mut self, path: &Path) -> Result<Reply, SchemError> {
+        let schematic = self.cache.get(path)?;
+        let mut glb = Vec::new();
+        crate::export_gltf::write_glb(schematic, &mut glb, None, None, true, None, &ExportOptions::new())?;
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        Ok(Reply { download: Some(format!("{}.glb", stem)), ..Reply::new("model/gltf-binary", glb) })
+    }
+}
+
+/// Serve the schematics under `dir` at `addr` until the process is stopped
+///
+/// Anyone who can reach `addr` can read every schematic in `dir`; see the
+/// [module docs](self).
+pub fn serve(dir: &Path, addr: impl ToSocketAddrs, options: ServeOptions) -> Result<(), SchemError> {
+    let server = tiny_http::Server::http(addr).map_err(|e| SchemError::Invalid(format!("can't start the server: {}", e)))?;
+    let mut browser = Browser::new(dir, options);
+    for request in server.incoming_requests() {
+        let reply = match request.method() {
+            tiny_http::Method::Get | tiny_http::Method::Head => browser.handle(request.url()),
+            _ => Reply::error(405, "Only GET is supported"),
+        };
+        log::info!("{} {} -> {}", request.method(), request.url(), reply.status);
+
+        let mut response = tiny_http::Response::from_data(reply.body).with_status_code(reply.status);
+        let mut headers = vec![format!("Content-Type: {}", reply.content_type)];
+        if let Some(name) = reply.download {
+            headers.push(format!("Content-Disposition: attachment; filename=\"{}\"", name.replace('"', "")));
+        }
+        for header in headers {
+            if let Ok(header) = header.parse::<tiny_http::Header>() {
+                response.add_header(header);
+            }
+        }
+        if let Err(e) = request.respond(response) {
+            log::warn!("Failed to send the response: {}", e);
+        }
+    }
+    Ok(())
+}
+
+fn is_schematic(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| SCHEMATIC_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+}
+
+/// Schematic files under `dir` and its subfolders, sorted by path; symlinks
+/// aren't followed, so a link back up the tree can't make the walk loop
+fn find_schematics(dir: &Path) -> Result<Vec<PathBuf>, SchemError> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        for entry in std::fs::read_dir(&dir)? {
+            let entry = entry?;
+            let file_type = entry.file_type()?;
+            if file_type.is_dir() {
+                pending.push(entry.path());
+            } else if file_type.is_file() && is_schematic(&entry.path()) {
+                files.push(entry.path());
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// Escape everything in a path but unreserved characters and `/`
+fn percent_encode(path: &str) -> String {
+    let mut encoded = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~/".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+/// `None` for broken escapes or text that isn't UTF-8
+fn percent_decode(path: &str) -> Option<String> {
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = std::str::from_utf8(bytes.get(i + 1..i + 3)?).ok()?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_browser_routes() {
+        let dir = std::env::temp_dir().join(format!("schem-tool-serve-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("my builds")).unwrap();
+        std::fs::write(dir.join("my builds/hut & shed.schem"), b"").unwrap();
+        std::fs::write(dir.join("notes.txt"), "not a schematic").unwrap();
+
+        let mut browser = Browser::new(&dir, ServeOptions::default());
+        let index = String::from_utf8(browser.handle("/").body).unwrap();
+        assert!(index.contains("<li>my builds/hut &amp; shed.schem <a href=\"/view/my%20builds/hut%20%26%20shed.schem\">"));
+        assert!(!index.contains("notes.txt"));
+
+        let (name, path) = browser.resolve("my%20builds/hut%20%26%20shed.schem").unwrap();
+        assert_eq!(name, "my builds/hut & shed.schem");
+        assert!(path.is_file());
+        for url in ["/info/../notes.txt", "/info/notes.txt", "/view/my%20builds/missing.schem", "/info/%2e%2e/x.schem", "/info/%zz", "/nope"] {
+            assert_eq!(browser.handle(url).status, 404, "{}", url);
+        }
+        assert!(browser.cache.is_empty());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_browser_serves_schematic() {
+        let dir = std::env::temp_dir().join(format!("schem-tool-serve-pages-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut builder = crate::SchematicBuilder::new(2, 2, 1);
+        builder.fill((0, 0, 0), (1, 0, 0), crate::Block::new("minecraft:oak_planks")).unwrap();
+        builder.build().save_schem(dir.join("floor.schem")).unwrap();
+
+        let mut browser = Browser::new(&dir, ServeOptions::default());
+        let info = browser.handle("/info/floor.schem");
+        assert_eq!((info.status, info.content_type), (200, "application/json"));
+        let json: serde_json::Value = serde_json::from_slice(&info.body).unwrap();
+        assert_eq!(json["file"], "floor.schem");
+        assert_eq!((json["summary"]["width"].as_u64(), json["summary"]["height"].as_u64()), (Some(2), Some(2)));
+        assert_eq!(json["summary"]["solid_blocks"], 2);
+        // Two planks take half a log, rounded up
+        assert_eq!(json["materials"]["minecraft:oak_log"], 1);
+
+        let view = browser.handle("/view/floor.schem");
+        assert_eq!(view.status, 200);
+        assert!(String::from_utf8(view.body).unwrap().contains("<html"));
+
+        let glb = browser.handle("/glb/floor.schem");
+        assert_eq!((glb.status, glb.download.as_deref()), (200, Some("floor.glb")));
+        assert_eq!(&glb.body[..4], b"glTF");
+        // All three pages came from one load
+        assert_eq!(browser.cache.len(), 1);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_browser_ignores_symlinks_out_of_the_folder() {
+        let root = std::env::temp_dir().join(format!("schem-tool-serve-links-{}", std::process::id()));
+        let (served, outside) = (root.join("served"), root.join("outside"));
+        std::fs::create_dir_all(&served).unwrap();
+        std::fs::create_dir_all(&outside).unwrap();
+        std::fs::write(outside.join("secret.schem"), b"").unwrap();
+        std::fs::write(served.join("own.schem"), b"").unwrap();
+        // A link back to the folder itself, and links to a file and a folder outside it
+        std::os::unix::fs::symlink(&served, served.join("loop")).unwrap();
+        std::os::unix::fs::symlink(outside.join("secret.schem"), served.join("secret.schem")).unwrap();
+        std::os::unix::fs::symlink(&outside, served.join("elsewhere")).unwrap();
+
+        let mut browser = Browser::new(&served, ServeOptions::default());
+        assert_eq!(find_schematics(&served).unwrap(), [served.join("own.schem")]);
+        let index = String::from_utf8(browser.handle("/").body).unwrap();
+        assert!(!index.contains("secret"));
+        assert!(browser.resolve("own.schem").is_some());
+        for url in ["/info/secret.schem", "/info/elsewhere/secret.schem"] {
+            assert_eq!(browser.handle(url).status, 404, "{}", url);
+        }
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
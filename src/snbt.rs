@@ -0,0 +1,407 @@
+//! SNBT, the text form of NBT used by `/data get` and commands
+//!
+//! [`to_string`] writes one entry per line with sorted compound keys, so the
+//! output diffs well; [`from_str`] reads it back, along with the compact form
+//! the game prints. Numbers carry their type as a suffix (`1b`, `2s`, `3L`,
+//! `0.5f`, `1.0d`, plain ints), arrays are typed (`[B; ...]`, `[I; ...]`,
+//! `[L; ...]`) and `true`/`false` are bytes.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use fastnbt::Value;
+
+use crate::error::SchemError;
+
+/// Array values per line
+const ARRAY_WRAP: usize = 16;
+
+/// `value` as indented SNBT with sorted keys and a trailing newline
+pub fn to_string(value: &Value) -> String {
+    let mut out = String::new();
+    write_value(&mut out, value, 0);
+    out.push('\n');
+    out
+}
+
+fn write_value(out: &mut String, value: &Value, indent: usize) {
+    let pad = "    ".repeat(indent + 1);
+    let close = "    ".repeat(indent);
+    match value {
+        Value::Byte(b) => { let _ = write!(out, "{}b", b); }
+        Value::Short(s) => { let _ = write!(out, "{}s", s); }
+        Value::Int(i) => { let _ = write!(out, "{}", i); }
+        Value::Long(l) => { let _ = write!(out, "{}L", l); }
+        Value::Float(f) => { let _ = write!(out, "{:?}f", f); }
+        Value::Double(d) => { let _ = write!(out, "{:?}d", d); }
+        Value::String(s) => out.push_str(&quote(s)),
+        Value::ByteArray(arr) => write_array(out, 'B', arr.iter().map(|v| format!("{}b", v)), &pad, &close),
+        Value::IntArray(arr) => write_array(out, 'I', arr.iter().map(|v| v.to_string()), &pad, &close),
+        Value::LongArray(arr) => write_array(out, 'L', arr.iter().map(|v| format!("{}L", v)), &pad, &close),
+        Value::List(list) if list.is_empty() => out.push_str("[]"),
+        Value::List(list) => {
+            out.push_str("[\n");
+            for (i, item) in list.iter().enumerate() {
+                out.push_str(&pad);
+                write_value(out, item, indent + 1);
+                out.push_str(if i + 1 < list.len() { ",\n" } else { "\n" });
+            }
+            let _ = write!(out, "{}]", close);
+        }
+        Value::Compound(map) if map.is_empty() => out.push_str("{}"),
+        Value::Compound(map) => {
+            out.push_str("{\n");
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_unstable_by_key(|&(key, _)| key);
+            for (i, (key, item)) in entries.iter().enumerate() {
+                let _ = write!(out, "{}{}: ", pad, key_text(key));
+                write_value(out, item, indent + 1);
+                out.push_str(if i + 1 < entries.len() { ",\n" } else { "\n" });
+            }
+            let _ = write!(out, "{}}}", close);
+        }
+    }
+}
+
+/// A typed array, `ARRAY_WRAP` values per line
+fn write_array(out: &mut String, kind: char, values: impl Iterator<Item = String>, pad: &str, close: &str) {
+    let values: Vec<String> = values.collect();
+    if values.is_empty() {
+        let _ = write!(out, "[{};]", kind);
+        return;
+    }
+    let _ = writeln!(out, "[{};", kind);
+    let lines: Vec<String> = values.chunks(ARRAY_WRAP).map(|chunk| chunk.join(", ")).collect();
+    for (i, line) in lines.iter().enumerate() {
+        let _ = writeln!(out, "{}{}{}", pad, line, if i + 1 < lines.len() { "," } else { "" });
+    }
+    let _ = write!(out, "{}]", close);
+}
+
+fn is_bare(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '+')
+}
+
+/// Keys stay unquoted when SNBT allows it
+fn key_text(key: &str) -> String {
+    if !key.is_empty() && key.chars().all(is_bare) { key.to_string() } else { quote(key) }
+}
+
+fn quote(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Parse one SNBT value; anything but whitespace after it is an error
+pub fn from_str(text: &str) -> Result<Value, SchemError> {
+    let mut parser = Parser { text, pos: 0 };
+    let value = parser.value(0)?;
+    parser.skip_whitespace();
+    if parser.pos < text.len() {
+        return Err(parser.error("unexpected text after the value"));
+    }
+    Ok(value)
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, message: impl Into<String>) -> SchemError {
+        let before = &self.text[..self.pos];
+        let line = before.matches('\n').count() + 1;
+        let column = before.rfind('\n').map_or(before, |i| &before[i + 1..]).chars().count() + 1;
+        SchemError::Snbt { line, column, message: message.into() }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.text[self.pos..].chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.text[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    /// Skip whitespace and consume `c` if it comes next
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), SchemError> {
+        if self.eat(c) { Ok(()) } else { Err(self.error(format!("expected '{}'", c))) }
+    }
+
+    fn value(&mut self, depth: usize) -> Result<Value, SchemError> {
+        // The game's own nesting limit
+        if depth > 512 {
+            return Err(self.error("nested too deeply"));
+        }
+        self.skip_whitespace();
+        match self.peek() {
+            Some('{') => self.compound(depth),
+            Some('[') => self.list_or_array(depth),
+            Some('"' | '\'') => Ok(Value::String(self.quoted()?)),
+            Some(_) => {
+                let start = self.pos;
+                let token = self.bare();
+                if token.is_empty() {
+                    return Err(self.error("expected a value"));
+                }
+                scalar(token).ok_or_else(|| {
+                    self.pos = start;
+                    self.error(format!("'{}' is out of range", token))
+                })
+            }
+            None => Err(self.error("expected a value")),
+        }
+    }
+
+    fn compound(&mut self, depth: usize) -> Result<Value, SchemError> {
+        self.expect('{')?;
+        let mut map = HashMap::new();
+        while !self.eat('}') {
+            self.skip_whitespace();
+            let key = match self.peek() {
+                Some('"' | '\'') => self.quoted()?,
+                _ => self.bare().to_string(),
+            };
+            if key.is_empty() {
+                return Err(self.error("expected a key or '}'"));
+            }
+            self.expect(':')?;
+            let value = self.value(depth + 1)?;
+            map.insert(key, value);
+            if !self.eat(',') {
+                self.expect('}')?;
+                break;
+            }
+        }
+        Ok(Value::Compound(map))
+    }
+
+    fn list_or_array(&mut self, depth: usize) -> Result<Value, SchemError> {
+        self.expect('[')?;
+        let rest = &self.text[self.pos..];
+        let kind = rest.chars().next().filter(|c| matches!(c, 'B' | 'I' | 'L'));
+        if let Some(kind) = kind.filter(|_| rest[1..].trim_start().starts_with(';')) {
+            self.pos += 1;
+            self.expect(';')?;
+            return self.array(kind);
+        }
+
+        let mut items: Vec<Value> = Vec::new();
+        while !self.eat(']') {
+            let start = self.pos;
+            let item = self.value(depth + 1)?;
+            if items.first().is_some_and(|first| std::mem::discriminant(first) != std::mem::discriminant(&item)) {
+                self.pos = start;
+                self.skip_whitespace();
+                return Err(self.error("list elements must all have the same type"));
+            }
+            items.push(item);
+            if !self.eat(',') {
+                self.expect(']')?;
+                break;
+            }
+        }
+        Ok(Value::List(items))
+    }
+
+    /// Elements of a typed array after the `;`; bytes and longs may leave out their suffix
+    fn array(&mut self, kind: char) -> Result<Value, SchemError> {
+        let mut values: Vec<i64> = Vec::new();
+        while !self.eat(']') {
+            self.skip_whitespace();
+            let start = self.pos;
+            let value = match (kind, scalar(self.bare())) {
+                ('B', Some(Value::Byte(v))) => Some(v as i64),
+                ('B', Some(Value::Int(v))) => i8::try_from(v).ok().map(i64::from),
+                ('I', Some(Value::Int(v))) => Some(v as i64),
+                ('L', Some(Value::Long(v))) => Some(v),
+                ('L', Some(Value::Int(v))) => Some(v as i64),
+                _ => None,
+            };
+            let Some(value) = value else {
+                self.pos = start;
+                return Err(self.error(format!("expected a {} in the [{};] array", element_name(kind), kind)));
+            };
+            values.push(value);
+            if !self.eat(',') {
+                self.expect(']')?;
+                break;
+            }
+        }
+        // Values were range-checked above
+        Ok(match kind {
+            'B' => Value::ByteArray(fastnbt::ByteArray::new(values.into_iter().map(|v| v as i8).collect())),
+            'I' => Value::IntArray(fastnbt::IntArray::new(values.into_iter().map(|v| v as i32).collect())),
+            _ => Value::LongArray(fastnbt::LongArray::new(values)),
+        })
+    }
+
+    /// A run of characters allowed unquoted
+    fn bare(&mut self) -> &'a str {
+        let rest = &self.text[self.pos..];
+        let len = rest.find(|c: char| !is_bare(c)).unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+
+    fn quoted(&mut self) -> Result<String, SchemError> {
+        let quote = self.peek().expect("called at a quote");
+        self.pos += 1;
+        let mut out = String::new();
+        let mut chars = self.text[self.pos..].char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                c if c == quote => {
+                    self.pos += i + 1;
+                    return Ok(out);
+                }
+                '\\' => {
+                    let escaped = match chars.next().map(|(_, e)| e) {
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some(e @ ('\\' | '"' | '\'')) => e,
+                        _ => {
+                            self.pos += i;
+                            return Err(self.error("unknown escape sequence"));
+                        }
+                    };
+                    out.push(escaped);
+                }
+                c => out.push(c),
+            }
+        }
+        Err(self.error("unterminated string"))
+    }
+}
+
+/// Number with an optional type suffix, a boolean or an unquoted string;
+/// `None` for numbers out of their type's range
+fn scalar(token: &str) -> Option<Value> {
+    match token {
+        "true" => return Some(Value::Byte(1)),
+        "false" => return Some(Value::Byte(0)),
+        _ => {}
+    }
+    let is_number = |s: &str| {
+        let digits = s.strip_prefix(['-', '+']).unwrap_or(s);
+        digits.starts_with(|c: char| c.is_ascii_digit() || c == '.') && s.parse::<f64>().is_ok()
+    };
+    let (body, suffix) = token.split_at(token.len().saturating_sub(1));
+    let suffix = suffix.chars().next().map(|c| c.to_ascii_lowercase());
+    let typed = |c| suffix == Some(c) && is_number(body);
+    let is_integer = |s: &str| s.strip_prefix(['-', '+']).unwrap_or(s).chars().all(|c| c.is_ascii_digit());
+
+    if typed('b') && is_integer(body) {
+        body.parse().ok().map(Value::Byte)
+    } else if typed('s') && is_integer(body) {
+        body.parse().ok().map(Value::Short)
+    } else if typed('l') && is_integer(body) {
+        body.parse().ok().map(Value::Long)
+    } else if typed('f') {
+        body.parse().ok().map(Value::Float)
+    } else if typed('d') {
+        body.parse().ok().map(Value::Double)
+    } else if is_number(token) && is_integer(token) {
+        token.parse().ok().map(Value::Int)
+    } else if is_number(token) {
+        token.parse().ok().map(Value::Double)
+    } else {
+        Some(Value::String(token.to_string()))
+    }
+}
+
+fn element_name(kind: char) -> &'static str {
+    match kind {
+        'B' => "byte",
+        'I' => "int",
+        _ => "long",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compound(entries: Vec<(&str, Value)>) -> Value {
+        Value::Compound(entries.into_iter().map(|(k, v)| (k.to_string(), v)).collect())
+    }
+
+    #[test]
+    fn test_to_string() {
+        let value = compound(vec![
+            ("BlockStates", Value::LongArray(fastnbt::LongArray::new((0..20).collect()))),
+            ("a b", Value::String("say \"hi\"\n".into())),
+            ("f", Value::Float(0.5)),
+            ("l", Value::List(vec![])),
+        ]);
+        let expected = "{\n    BlockStates: [L;\n        0L, 1L, 2L, 3L, 4L, 5L, 6L, 7L, 8L, 9L, 10L, 11L, 12L, 13L, 14L, 15L,\n        16L, 17L, 18L, 19L\n    ],\n    \"a b\": \"say \\\"hi\\\"\\n\",\n    f: 0.5f,\n    l: []\n}\n";
+        assert_eq!(to_string(&value), expected);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let item = |slot: i8, id: &str, count: i32| compound(vec![
+            ("Slot", Value::Byte(slot)),
+            ("id", Value::String(id.into())),
+            ("count", Value::Int(count)),
+        ]);
+        let value = compound(vec![
+            ("Items", Value::List(vec![item(0, "minecraft:diamond", 64), item(13, "minecraft:stick", 1)])),
+            ("CustomName", Value::String("{\"text\":\"Loot\"}".into())),
+            ("Lock", Value::String(String::new())),
+            ("Ticks", Value::Short(-3)),
+            ("Seed", Value::Long(i64::MIN)),
+            ("Ratio", Value::Double(1.0)),
+            ("Bytes", Value::ByteArray(fastnbt::ByteArray::new(vec![-128, 0, 127]))),
+            ("Ints", Value::IntArray(fastnbt::IntArray::new(vec![]))),
+            ("Empty", compound(vec![])),
+        ]);
+        assert_eq!(from_str(&to_string(&value)).unwrap(), value);
+    }
+
+    #[test]
+    fn test_from_str() {
+        let value = from_str(r#"{id:"minecraft:chest",Items:[{Slot:0b,id:'minecraft:torch',count:3}],lit:true,x:-1.5,name:abc,h:[I;1,-2]}"#).unwrap();
+        let Value::Compound(map) = value else { panic!("not a compound") };
+        assert_eq!(map["lit"], Value::Byte(1));
+        assert_eq!(map["x"], Value::Double(-1.5));
+        assert_eq!(map["name"], Value::String("abc".into()));
+        assert_eq!(map["h"], Value::IntArray(fastnbt::IntArray::new(vec![1, -2])));
+        assert_eq!(from_str("[B; 1b, 2]").unwrap(), Value::ByteArray(fastnbt::ByteArray::new(vec![1, 2])));
+        assert_eq!(from_str("1e3").unwrap(), Value::Double(1000.0));
+        assert_eq!(from_str("'it\\'s'").unwrap(), Value::String("it's".into()));
+
+        let error = |text: &str| from_str(text).unwrap_err().to_string();
+        assert_eq!(error("{a: 1,\n  b: [1, 2b]}"), "SNBT error at line 2, column 10: list elements must all have the same type");
+        assert!(error("300b").contains("out of range"));
+        assert!(error("[B; 300]").contains("expected a byte"));
+        assert!(error("{a: \"open").contains("unterminated string"));
+        assert!(error("{a: 1} x").contains("unexpected text"));
+        assert!(error("{a 1}").contains("expected ':'"));
+    }
+}
@@ -0,0 +1,167 @@
+//! Vanilla structure format (.nbt), as saved by structure blocks and used by
+//! datapack structures and jigsaw pools
+//!
+//! Structure:
+//! - DataVersion: int
+//! - size: list of 3 ints
+//! - palette: list of block state compounds (Name, Properties)
+//! - blocks: list of compounds
+//!   - pos: list of 3 ints
+//!   - state: int (palette index)
+//!   - nbt: compound (block entity data with its id, optional)
+//! - entities: list of compounds
+//!   - pos: list of 3 doubles
+//!   - blockPos: list of 3 ints (the block the entity is in)
+//!   - nbt: compound (entity data with its id)
+//! - author: string (written by structure blocks before 1.13, optional)
+//!
+//! Positions missing from `blocks` are left as they are when the structure is
+//! placed; that's how structure blocks save structure voids. Writing one
+//! therefore leaves structure voids out, and air in.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::litematica::DEFAULT_DATA_VERSION;
+use crate::{Entity, SchemError, UnifiedSchematic};
+
+#[derive(Debug, Serialize)]
+pub struct StructureNbt {
+    #[serde(rename = "DataVersion")]
+    pub data_version: i32,
+
+    pub size: Vec<i32>,
+
+    pub palette: Vec<StructureBlockState>,
+
+    pub blocks: Vec<StructureBlock>,
+
+    pub entities: Vec<fastnbt::Value>,
+
+    /// The only metadata structure files have room for; the name is the file
+    /// name, and there's no date
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StructureBlockState {
+    #[serde(rename = "Name")]
+    pub name: String,
+
+    #[serde(rename = "Properties", skip_serializing_if = "Option::is_none")]
+    pub properties: Option<HashMap<String, String>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StructureBlock {
+    pub pos: Vec<i32>,
+
+    pub state: i32,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nbt: Option<HashMap<String, fastnbt::Value>>,
+}
+
+impl StructureNbt {
+    /// Structure holding a unified schematic's blocks, block entities and entities
+    ///
+    /// Structure voids are left out (see the module docs), block entity and
+    /// entity data is written as NBT with its id, and jigsaw settings survive
+    /// as they are. Unrecognised root fields in `metadata.raw_extra` are
+    /// dropped with a warning.
+    pub fn from_unified(schem: &UnifiedSchematic) -> Self {
+        schem.metadata.warn_dropped_raw_extra("structure");
+        let block_entities: HashMap<_, _> = schem.block_entities.iter()
+            .map(|be| ((be.pos.0, be.pos.1, be.pos.2), be))
+            .collect();
+
+        let mut palette = Vec::new();
+        let mut ids = HashMap::new();
+        let mut blocks = Vec::new();
+        for ((x, y, z), block) in schem.iter_blocks() {
+            if block.is_structure_void() {
+                continue;
+            }
+            let state = *ids.entry(block.full_name()).or_insert_with(|| {
+                palette.push(StructureBlockState {
+                    name: block.name.clone(),
+                    properties: (!block.state.properties.is_empty()).then(|| (*block.state.properties).clone()),
+                });
+                palette.len() as i32 - 1
+            });
+            let pos = (x as i32, y as i32, z as i32);
+            let nbt = block_entities.get(&pos).map(|be| {
+                let mut nbt = be.nbt.clone();
+                nbt.insert("id".to_string(), fastnbt::Value::String(be.id.clone()));
+                nbt
+            });
+            blocks.push(StructureBlock { pos: vec![pos.0, pos.1, pos.2], state, nbt });
+        }
+
+        StructureNbt {
+            data_version: schem.metadata.data_version.unwrap_or(DEFAULT_DATA_VERSION),
+            size: vec![schem.width as i32, schem.height as i32, schem.length as i32],
+            palette,
+            blocks,
+            entities: schem.entities.iter().map(structure_entity).collect(),
+            author: schem.metadata.author.clone(),
+        }
+    }
+
+    /// Gzipped NBT, as stored in .nbt structure files
+    pub fn to_bytes(&self) -> Result<Vec<u8>, SchemError> {
+        crate::gzip_nbt(self)
+    }
+}
+
+/// An entity's `pos`, `blockPos` and `nbt` compound
+fn structure_entity(entity: &Entity) -> fastnbt::Value {
+    use fastnbt::Value;
+    let (x, y, z) = entity.pos;
+    let mut nbt = entity.nbt.clone();
+    nbt.insert("id".to_string(), Value::String(entity.id.clone()));
+    Value::Compound(HashMap::from([
+        ("pos".to_string(), Value::List(vec![Value::Double(x), Value::Double(y), Value::Double(z)])),
+        ("blockPos".to_string(), Value::List([x, y, z].map(|v| Value::Int(v.floor() as i32)).to_vec())),
+        ("nbt".to_string(), Value::Compound(nbt)),
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::SchematicBuilder;
+    use crate::{Block, BlockEntity};
+
+    #[test]
+    fn test_structure_voids_and_jigsaws() {
+        let mut builder = SchematicBuilder::new(2, 1, 2);
+        builder.set_block(0, 0, 0, Block::new("minecraft:structure_void")).unwrap();
+        builder.set_block(1, 0, 0, Block::new("minecraft:jigsaw")).unwrap();
+        builder.set_block(0, 0, 1, Block::new("minecraft:structure_void")).unwrap();
+        let nbt = HashMap::from([
+            ("pool".to_string(), fastnbt::Value::String("minecraft:village/plains/streets".to_string())),
+            ("final_state".to_string(), fastnbt::Value::String("minecraft:dirt_path".to_string())),
+        ]);
+        builder.add_block_entity(BlockEntity { id: "minecraft:jigsaw".to_string(), pos: (1, 0, 0), nbt, ..Default::default() }).unwrap();
+        builder.add_entity(Entity { id: "minecraft:pig".to_string(), pos: (1.5, 0.0, 0.25), ..Default::default() });
+        let structure = StructureNbt::from_unified(&builder.build());
+
+        assert_eq!(structure.size, [2, 1, 2]);
+        // The air at (1, 0, 1) is kept, the structure voids aren't
+        assert_eq!(structure.blocks.len(), 2);
+        assert!(structure.palette.iter().all(|state| state.name != "minecraft:structure_void"));
+        let jigsaw = structure.blocks.iter().find(|block| block.pos == [1, 0, 0]).unwrap();
+        assert_eq!(structure.palette[jigsaw.state as usize].name, "minecraft:jigsaw");
+        let nbt = jigsaw.nbt.as_ref().unwrap();
+        assert_eq!(nbt["id"], fastnbt::Value::String("minecraft:jigsaw".to_string()));
+        assert_eq!(nbt["pool"], fastnbt::Value::String("minecraft:village/plains/streets".to_string()));
+
+        let fastnbt::Value::Compound(pig) = &structure.entities[0] else { panic!("entity isn't a compound") };
+        assert_eq!(pig["blockPos"], fastnbt::Value::List(vec![fastnbt::Value::Int(1), fastnbt::Value::Int(0), fastnbt::Value::Int(0)]));
+        let fastnbt::Value::Compound(nbt) = &pig["nbt"] else { panic!("entity nbt isn't a compound") };
+        assert_eq!(nbt["id"], fastnbt::Value::String("minecraft:pig".to_string()));
+    }
+}
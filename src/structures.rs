@@ -0,0 +1,216 @@
+//! Checks for blocks whose effect depends on a surrounding structure
+//!
+//! Beacons need pyramid layers of mineral blocks beneath them and conduits
+//! need a prismarine frame around a water-filled cage. Positions are local
+//! schematic coordinates; parts of a structure that fall outside the
+//! schematic count as missing.
+
+use crate::{Block, UnifiedSchematic};
+
+/// Signed local position (structures may reach outside the schematic)
+pub type Pos = (i64, i64, i64);
+
+const BEACON_BASE: &[&str] = &[
+    "minecraft:iron_block",
+    "minecraft:gold_block",
+    "minecraft:diamond_block",
+    "minecraft:emerald_block",
+    "minecraft:netherite_block",
+];
+
+const CONDUIT_FRAME: &[&str] = &[
+    "minecraft:prismarine",
+    "minecraft:prismarine_bricks",
+    "minecraft:dark_prismarine",
+    "minecraft:sea_lantern",
+];
+
+/// Frame blocks a conduit needs to activate
+pub const CONDUIT_MIN_FRAME: usize = 16;
+/// Frame blocks for the full 96-block range
+pub const CONDUIT_FULL_FRAME: usize = 42;
+
+/// One pyramid layer beneath a beacon
+#[derive(Debug, Clone, PartialEq)]
+pub struct PyramidLayer {
+    /// 1 for the 3x3 layer directly below, up to 4 for the 9x9 layer
+    pub level: u8,
+    pub valid: usize,
+    pub required: usize,
+    /// Positions without a valid base block
+    pub missing: Vec<Pos>,
+}
+
+impl PyramidLayer {
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BeaconReport {
+    pub pos: (u32, u32, u32),
+    /// Achieved tier (0 = inactive, 4 = all powers)
+    pub tier: u8,
+    pub layers: Vec<PyramidLayer>,
+}
+
+impl BeaconReport {
+    /// The first incomplete layer, i.e. what to build next
+    pub fn next_layer(&self) -> Option<&PyramidLayer> {
+        self.layers.iter().find(|layer| !layer.is_complete())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConduitReport {
+    pub pos: (u32, u32, u32),
+    /// Valid frame blocks found
+    pub frame: usize,
+    /// Frame positions without a valid frame block
+    pub missing_frame: Vec<Pos>,
+    /// Positions in the 3x3x3 cage that aren't water
+    pub dry: Vec<Pos>,
+}
+
+impl ConduitReport {
+    pub fn is_active(&self) -> bool {
+        self.frame >= CONDUIT_MIN_FRAME && self.dry.is_empty()
+    }
+
+    pub fn is_full_power(&self) -> bool {
+        self.frame >= CONDUIT_FULL_FRAME && self.dry.is_empty()
+    }
+}
+
+fn is_one_of(block: Option<&Block>, names: &[&str]) -> bool {
+    block.is_some_and(|b| names.contains(&b.name.as_str()))
+}
+
+fn is_water(block: Option<&Block>) -> bool {
+    block.is_some_and(|b| b.name == "minecraft:water" || b.state.is_waterlogged())
+}
+
+fn signed((x, y, z): (u32, u32, u32)) -> Pos {
+    (x as i64, y as i64, z as i64)
+}
+
+/// Positions of every block named `name`
+fn find_blocks<'a>(schem: &'a UnifiedSchematic, name: &'a str) -> impl Iterator<Item = (u32, u32, u32)> + 'a {
+    let (width, length) = (schem.width as usize, schem.length as usize);
+    schem.blocks.iter().enumerate()
+        .filter(move |(_, block)| block.name == name)
+        .map(move |(i, _)| ((i % width) as u32, (i / (width * length)) as u32, ((i / width) % length) as u32))
+}
+
+/// Check the pyramid beneath a beacon
+pub fn check_beacon(schem: &UnifiedSchematic, pos: (u32, u32, u32)) -> BeaconReport {
+    let (x, y, z) = signed(pos);
+    let mut layers = Vec::new();
+    let mut tier = 0;
+
+    for level in 1..=4u8 {
+        let r = level as i64;
+        let mut layer = PyramidLayer { level, valid: 0, required: 0, missing: Vec::new() };
+        for (p, block) in schem.blocks_in_box((x - r, y - r, z - r), (x + r, y - r, z + r)) {
+            layer.required += 1;
+            if is_one_of(block, BEACON_BASE) {
+                layer.valid += 1;
+            } else {
+                layer.missing.push(p);
+            }
+        }
+        if layer.is_complete() && tier == level - 1 {
+            tier = level;
+        }
+        layers.push(layer);
+    }
+
+    BeaconReport { pos, tier, layers }
+}
+
+/// Check the frame and water cage around a conduit
+pub fn check_conduit(schem: &UnifiedSchematic, pos: (u32, u32, u32)) -> ConduitReport {
+    let (x, y, z) = signed(pos);
+    let mut report = ConduitReport { pos, frame: 0, missing_frame: Vec::new(), dry: Vec::new() };
+
+    for ((px, py, pz), block) in schem.blocks_in_box((x - 2, y - 2, z - 2), (x + 2, y + 2, z + 2)) {
+        let d = [(px - x).abs(), (py - y).abs(), (pz - z).abs()];
+        let p = (px, py, pz);
+
+        if d.iter().all(|&d| d <= 1) {
+            if p != (x, y, z) && !is_water(block) {
+                report.dry.push(p);
+            }
+        } else if d.contains(&0) && d.iter().max() == Some(&2) {
+            // Outer ring of the three 5x5 squares through the conduit
+            if is_one_of(block, CONDUIT_FRAME) {
+                report.frame += 1;
+            } else {
+                report.missing_frame.push(p);
+            }
+        }
+    }
+
+    report
+}
+
+/// Check every beacon in the schematic
+pub fn beacons(schem: &UnifiedSchematic) -> Vec<BeaconReport> {
+    find_blocks(schem, "minecraft:beacon").map(|pos| check_beacon(schem, pos)).collect()
+}
+
+/// Check every conduit in the schematic
+pub fn conduits(schem: &UnifiedSchematic) -> Vec<ConduitReport> {
+    find_blocks(schem, "minecraft:conduit").map(|pos| check_conduit(schem, pos)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::SchematicBuilder;
+
+    #[test]
+    fn test_beacon_tiers() {
+        // Two full layers and a third with one gap
+        let mut builder = SchematicBuilder::new(7, 4, 7);
+        builder.fill((1, 1, 1), (5, 1, 5), Block::new("minecraft:iron_block")).unwrap();
+        builder.fill((2, 2, 2), (4, 2, 4), Block::new("minecraft:diamond_block")).unwrap();
+        builder.fill((0, 0, 0), (6, 0, 6), Block::new("minecraft:gold_block")).unwrap();
+        builder.set_block(6, 0, 6, Block::new("minecraft:stone")).unwrap();
+        builder.set_block(3, 3, 3, Block::new("minecraft:beacon")).unwrap();
+        let schem = builder.build();
+
+        let reports = beacons(&schem);
+        assert_eq!(reports.len(), 1);
+        let beacon = &reports[0];
+        assert_eq!(beacon.tier, 2);
+
+        let next = beacon.next_layer().unwrap();
+        assert_eq!((next.level, next.valid, next.required), (3, 48, 49));
+        assert_eq!(next.missing, vec![(6, 0, 6)]);
+        // The 9x9 layer would sit below the schematic
+        assert_eq!(beacon.layers[3].valid, 0);
+    }
+
+    #[test]
+    fn test_conduit_frame() {
+        let mut builder = SchematicBuilder::new(5, 5, 5);
+        builder.fill((1, 1, 1), (3, 3, 3), Block::new("minecraft:water")).unwrap();
+        builder.set_block(2, 2, 2, Block::new("minecraft:conduit")).unwrap();
+        // Ring in the horizontal plane through the conduit
+        for i in 0..5 {
+            for (x, z) in [(i, 0), (i, 4), (0, i), (4, i)] {
+                builder.set_block(x, 2, z, Block::new("minecraft:prismarine")).unwrap();
+            }
+        }
+        let schem = builder.build();
+
+        let conduit = &conduits(&schem)[0];
+        assert_eq!(conduit.frame, 16);
+        assert_eq!(conduit.missing_frame.len(), CONDUIT_FULL_FRAME - 16);
+        assert!(conduit.dry.is_empty());
+        assert!(conduit.is_active());
+        assert!(!conduit.is_full_power());
+    }
+}
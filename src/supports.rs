@@ -0,0 +1,212 @@
+//! Scaffolding estimates for building a schematic in survival
+//!
+//! A block is grounded when it rests on the ground or on another grounded
+//! block, and floating otherwise, so it can't be stacked up from the ground. Floating blocks next to the
+//! grounded structure can be placed by bridging out from it, as long as the
+//! player can reach them; everything else needs temporary blocks. The bottom
+//! layer of the schematic counts as resting on the ground.
+
+use std::collections::{HashMap, VecDeque};
+use crate::block_geometry;
+use crate::{Block, UnifiedSchematic};
+
+/// Default reach in blocks for `analyze`
+pub const DEFAULT_REACH: u32 = 4;
+
+/// A group of connected floating blocks
+#[derive(Debug, Clone, PartialEq)]
+pub struct FloatingCluster {
+    pub blocks: usize,
+    /// Blocks that can't be placed from the grounded structure
+    pub unreachable: usize,
+    /// Bounding box corners
+    pub min: (u32, u32, u32),
+    pub max: (u32, u32, u32),
+    /// Whether the cluster touches the grounded structure
+    pub attached: bool,
+    /// Estimated temporary blocks (one pillar) needed to build the cluster
+    pub scaffold: u32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SupportReport {
+    /// Floating blocks in total
+    pub floating: usize,
+    /// Clusters needing scaffolding, most scaffolding first
+    pub clusters: Vec<FloatingCluster>,
+}
+
+impl SupportReport {
+    pub fn unreachable(&self) -> usize {
+        self.clusters.iter().map(|c| c.unreachable).sum()
+    }
+
+    pub fn scaffold(&self) -> u64 {
+        self.clusters.iter().map(|c| c.scaffold as u64).sum()
+    }
+}
+
+/// Blocks that hold up what's above them
+fn is_solid(block: &Block) -> bool {
+    let name = block.display_name();
+    name != "water" && name != "lava"
+        && block_geometry::get_block_geometry(&block.name, &block.state.properties).is_solid()
+}
+
+/// Find floating blocks and estimate scaffolding, counting blocks within
+/// `max_reach` steps of the grounded structure as placeable
+pub fn analyze(schem: &UnifiedSchematic, max_reach: u32) -> SupportReport {
+    let (w, l) = (schem.width as usize, schem.length as usize);
+    let solid: Vec<bool> = schem.blocks.iter().map(is_solid).collect();
+
+    // Grounded blocks stand on the ground or on another grounded block, so
+    // fill upwards from the bottom layer; a solid block over air or over a
+    // floating block is floating, however thick the structure above it is
+    let layer = w * l;
+    let mut grounded = vec![false; solid.len()];
+    for i in 0..solid.len() {
+        grounded[i] = solid[i] && (i < layer || grounded[i - layer]);
+    }
+    let floating: Vec<bool> = solid.iter().zip(&grounded).map(|(&solid, &grounded)| solid && !grounded).collect();
+    let grounded = |(x, y, z): (u32, u32, u32)| grounded[(y as usize * l + z as usize) * w + x as usize];
+
+    let components = schem.connected_components(|(x, y, z), _| {
+        floating[(y as usize * l + z as usize) * w + x as usize]
+    });
+
+    let mut report = SupportReport::default();
+    for component in components {
+        report.floating += component.len();
+
+        // Steps from the grounded structure through the cluster; blocks touching
+        // it are one step away
+        let mut steps: HashMap<(u32, u32, u32), u32> = HashMap::new();
+        let mut queue = VecDeque::new();
+        for &pos in &component {
+            if neighbors(schem, pos).any(&grounded) {
+                steps.insert(pos, 1);
+                queue.push_back(pos);
+            }
+        }
+        let attached = !queue.is_empty();
+        let members: std::collections::HashSet<_> = component.iter().copied().collect();
+        while let Some(pos) = queue.pop_front() {
+            let next = steps[&pos] + 1;
+            if next > max_reach {
+                continue;
+            }
+            for n in neighbors(schem, pos) {
+                if members.contains(&n) && !steps.contains_key(&n) {
+                    steps.insert(n, next);
+                    queue.push_back(n);
+                }
+            }
+        }
+
+        let unreachable: Vec<_> = component.iter().filter(|pos| !steps.contains_key(pos)).collect();
+        if unreachable.is_empty() {
+            continue;
+        }
+
+        let lowest = unreachable.iter().map(|p| p.1).min().unwrap_or(0);
+        // Detached clusters need a pillar right up to them; otherwise the pillar
+        // only has to bring the player within reach
+        let scaffold = if attached { lowest.saturating_sub(max_reach) } else { lowest };
+
+        let mut min = component[0];
+        let mut max = component[0];
+        for &(x, y, z) in &component {
+            min = (min.0.min(x), min.1.min(y), min.2.min(z));
+            max = (max.0.max(x), max.1.max(y), max.2.max(z));
+        }
+
+        report.clusters.push(FloatingCluster {
+            blocks: component.len(),
+            unreachable: unreachable.len(),
+            min,
+            max,
+            attached,
+            scaffold,
+        });
+    }
+
+    report.clusters.sort_by(|a, b| b.scaffold.cmp(&a.scaffold).then(b.unreachable.cmp(&a.unreachable)));
+    report
+}
+
+/// Face neighbors inside the schematic
+fn neighbors(schem: &UnifiedSchematic, (x, y, z): (u32, u32, u32)) -> impl Iterator<Item = (u32, u32, u32)> {
+    let (w, h, l) = (schem.width, schem.height, schem.length);
+    [
+        x.checked_sub(1).map(|x| (x, y, z)),
+        (x + 1 < w).then(|| (x + 1, y, z)),
+        y.checked_sub(1).map(|y| (x, y, z)),
+        (y + 1 < h).then(|| (x, y + 1, z)),
+        z.checked_sub(1).map(|z| (x, y, z)),
+        (z + 1 < l).then(|| (x, y, z + 1)),
+    ].into_iter().flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::SchematicBuilder;
+
+    #[test]
+    fn test_overhang_and_floating_platform() {
+        let stone = Block::new("minecraft:stone");
+        let mut builder = SchematicBuilder::new(12, 8, 1);
+        // Tower at x=0 with a 6-block ledge at the top
+        builder.fill((0, 0, 0), (0, 5, 0), stone.clone()).unwrap();
+        builder.fill((1, 5, 0), (6, 5, 0), stone.clone()).unwrap();
+        // Detached 2-block platform at y=7
+        builder.fill((9, 7, 0), (10, 7, 0), stone.clone()).unwrap();
+        // Grounded pillar at x=11 is never floating
+        builder.fill((11, 0, 0), (11, 2, 0), stone).unwrap();
+        let schem = builder.build();
+
+        let report = analyze(&schem, 4);
+        assert_eq!(report.floating, 8);
+        assert_eq!(report.clusters.len(), 2);
+
+        let platform = &report.clusters[0];
+        assert!(!platform.attached);
+        assert_eq!((platform.unreachable, platform.scaffold), (2, 7));
+
+        // The ledge's last two blocks are beyond reach
+        let ledge = &report.clusters[1];
+        assert!(ledge.attached);
+        assert_eq!((ledge.blocks, ledge.unreachable, ledge.scaffold), (6, 2, 1));
+        assert_eq!((ledge.min, ledge.max), ((1, 5, 0), (6, 5, 0)));
+
+        // With enough reach the ledge needs nothing
+        assert_eq!(analyze(&schem, 6).clusters.len(), 1);
+        assert_eq!(report.scaffold(), 8);
+    }
+
+    #[test]
+    fn test_thick_floating_cluster() {
+        let stone = Block::new("minecraft:stone");
+        let build = |pillar: bool| {
+            let mut builder = SchematicBuilder::new(3, 12, 3);
+            builder.fill((0, 10, 0), (2, 11, 2), stone.clone()).unwrap();
+            if pillar {
+                builder.fill((1, 0, 1), (1, 9, 1), stone.clone()).unwrap();
+            }
+            builder.build()
+        };
+        // Two-layer slab with nothing below: its top layer isn't holding it up
+        let report = analyze(&build(false), 4);
+        assert_eq!(report.floating, 18);
+        assert_eq!(report.clusters.len(), 1);
+        let slab = &report.clusters[0];
+        assert!(!slab.attached);
+        assert_eq!((slab.blocks, slab.unreachable, slab.scaffold), (18, 18, 10));
+        assert_eq!((slab.min, slab.max), ((0, 10, 0), (2, 11, 2)));
+
+        // Propped up in the middle, the rest of both layers is bridged out from the pillar
+        let report = analyze(&build(true), 4);
+        assert_eq!(report.floating, 16);
+        assert!(report.clusters.is_empty());
+    }
+}
@@ -1,13 +1,18 @@
 //! Texture extraction and management for Minecraft block textures
 //!
-//! Extracts textures from installed Minecraft client.jar
+//! Extracts textures from installed Minecraft client.jar into a cache
+//! directory per jar, so later runs don't open the jar at all
 
 use std::collections::HashMap;
 use std::fs::{self, File};
-use std::io::{Read, Write};
+use std::io::{BufReader, Read, Write};
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
+use rayon::prelude::*;
 use zip::ZipArchive;
-use image::{GenericImageView, ImageBuffer, Rgba};
+use image::{GenericImageView, ImageBuffer, Rgba, RgbaImage};
+use crate::SchemError;
 
 /// Get the default Minecraft directory based on OS
 pub fn get_minecraft_dir() -> Option<PathBuf> {
@@ -68,52 +73,215 @@ pub fn find_client_jar(minecraft_dir: &Path) -> Option<PathBuf> {
     jars.first().map(|(p, _)| p.clone())
 }
 
-/// Extract block textures from client.jar to cache directory
-pub fn extract_textures(jar_path: &Path, cache_dir: &Path) -> std::io::Result<usize> {
-    let file = File::open(jar_path)?;
-    let mut archive = ZipArchive::new(file).map_err(|e| std::io::Error::other(e.to_string()))?;
-
-    fs::create_dir_all(cache_dir)?;
+/// Bumped when the layout of extracted textures changes, so older caches are ignored
+/// (2: `.png.mcmeta` animation files are extracted too)
+const CACHE_VERSION: u32 = 2;
+
+/// Jar hashes by path, size and modification time, in the cache root
+const JAR_INDEX: &str = "jars.tsv";
+
+/// Cache directory for the textures of one client.jar, named after a hash of its contents
+///
+/// The hash is remembered by the jar's path, size and modification time, so an
+/// unchanged jar is only read once.
+pub fn jar_cache_dir(cache_root: &Path, jar_path: &Path) -> Result<PathBuf, SchemError> {
+    let meta = fs::metadata(jar_path).map_err(|e| SchemError::texture(jar_path, e))?;
+    let modified = meta.modified().ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |age| age.as_nanos());
+    let key = format!("{}\t{}\t{}", jar_path.display(), meta.len(), modified);
+
+    let index_path = cache_root.join(JAR_INDEX);
+    let index = fs::read_to_string(&index_path).unwrap_or_default();
+    let known = index.lines().find_map(|line| {
+        let (line_key, hash) = line.rsplit_once('\t')?;
+        (line_key == key).then(|| hash.to_string())
+    });
+    let hash = match known {
+        Some(hash) => hash,
+        None => {
+            let hash = format!("{:016x}", hash_file(jar_path)?);
+            // Replace any entry for an older version of the same jar
+            let path_prefix = format!("{}\t", jar_path.display());
+            let mut lines: Vec<&str> = index.lines().filter(|line| !line.starts_with(&path_prefix)).collect();
+            let entry = format!("{}\t{}", key, hash);
+            lines.push(&entry);
+            fs::create_dir_all(cache_root).map_err(|e| SchemError::export_io(cache_root, e))?;
+            fs::write(&index_path, lines.join("\n") + "\n").map_err(|e| SchemError::export_io(&index_path, e))?;
+            hash
+        }
+    };
+    Ok(cache_root.join(format!("v{}-{}", CACHE_VERSION, hash)))
+}
 
-    let mut count = 0;
-    let prefix = "assets/minecraft/textures/block/";
+/// FNV-1a of a file's contents
+fn hash_file(path: &Path) -> Result<u64, SchemError> {
+    let mut reader = BufReader::with_capacity(1 << 16, File::open(path).map_err(|e| SchemError::texture(path, e))?);
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    let mut buf = [0u8; 1 << 16];
+    loop {
+        let n = reader.read(&mut buf).map_err(|e| SchemError::texture(path, e))?;
+        if n == 0 {
+            return Ok(hash);
+        }
+        for &b in &buf[..n] {
+            hash = (hash ^ b as u64).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
 
-    for i in 0..archive.len() {
-        let mut file = archive.by_index(i).map_err(|e| std::io::Error::other(e.to_string()))?;
-        let name = file.name().to_string();
+fn open_jar(jar_path: &Path) -> Result<ZipArchive<File>, SchemError> {
+    let file = File::open(jar_path).map_err(|e| SchemError::texture(jar_path, e))?;
+    ZipArchive::new(file).map_err(|e| SchemError::texture(jar_path, e))
+}
 
-        if name.starts_with(prefix) && name.ends_with(".png") {
-            let texture_name = &name[prefix.len()..];
-            let dest_path = cache_dir.join(texture_name);
+/// Extract block textures (and the `.png.mcmeta` files of animated ones) from
+/// client.jar to cache directory, returning the number of textures
+///
+/// Entries are decompressed and written in parallel, each worker thread with
+/// its own handle on the jar.
+pub fn extract_textures(jar_path: &Path, cache_dir: &Path) -> Result<usize, SchemError> {
+    let archive = open_jar(jar_path)?;
+    fs::create_dir_all(cache_dir).map_err(|e| SchemError::export_io(cache_dir, e))?;
 
-            // Create parent dirs if needed
-            if let Some(parent) = dest_path.parent() {
-                fs::create_dir_all(parent)?;
-            }
+    let prefix = "assets/minecraft/textures/block/";
+    let entries: Vec<(usize, String)> = (0..archive.len())
+        .filter_map(|i| {
+            let name = archive.name_for_index(i)?;
+            let wanted = name.ends_with(".png") || name.ends_with(".png.mcmeta");
+            (name.starts_with(prefix) && wanted).then(|| (i, name.to_string()))
+        })
+        .collect();
+
+    entries.par_iter().try_for_each_init(|| open_jar(jar_path), |archive, (index, name)| {
+        let archive = archive.as_mut().map_err(|e| SchemError::texture(jar_path, e.to_string()))?;
+        let mut file = archive.by_index(*index).map_err(|e| SchemError::texture(jar_path, e))?;
+        let dest_path = cache_dir.join(&name[prefix.len()..]);
+
+        // Create parent dirs if needed
+        if let Some(parent) = dest_path.parent() {
+            fs::create_dir_all(parent).map_err(|e| SchemError::export_io(parent, e))?;
+        }
 
-            let mut contents = Vec::new();
-            file.read_to_end(&mut contents)?;
+        let mut contents = Vec::new();
+        file.read_to_end(&mut contents)
+            .map_err(|e| SchemError::texture(jar_path, format!("{}: {}", name, e)))?;
 
-            let mut dest_file = File::create(&dest_path)?;
-            dest_file.write_all(&contents)?;
-            count += 1;
-        }
-    }
+        File::create(&dest_path)
+            .and_then(|mut dest_file| dest_file.write_all(&contents))
+            .map_err(|e| SchemError::export_io(&dest_path, e))
+    })?;
 
-    Ok(count)
+    Ok(entries.iter().filter(|(_, name)| name.ends_with(".png")).count())
 }
 
+/// Average texture colors saved in the texture cache by `save_color_cache`
+const COLOR_CACHE: &str = "average_colors.tsv";
+
 /// Check if textures are cached
 pub fn textures_cached(cache_dir: &Path) -> bool {
     cache_dir.exists() && cache_dir.join("stone.png").exists()
 }
 
+type AverageColors = HashMap<PathBuf, Option<(f32, f32, f32)>>;
+
+/// Frame layout of an animated texture (a strip of frames), from its `.png.mcmeta`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Animation {
+    /// Frame size in pixels; unset, frames are as wide as the image and square
+    pub frame_width: Option<u32>,
+    pub frame_height: Option<u32>,
+    /// Strip indices in playback order; empty plays every frame in strip order
+    pub frames: Vec<u32>,
+}
+
+impl Animation {
+    /// The animation of the texture at `png_path`, or `None` without a readable
+    /// `<name>.png.mcmeta` next to it
+    pub fn for_texture(png_path: &Path) -> Option<Self> {
+        let mut mcmeta = png_path.as_os_str().to_owned();
+        mcmeta.push(".mcmeta");
+        Self::parse(&fs::read_to_string(mcmeta).ok()?)
+    }
+
+    /// The `animation` section of an mcmeta file; frame times and interpolation
+    /// don't matter for a still frame and are ignored
+    pub fn parse(mcmeta: &str) -> Option<Self> {
+        let json: serde_json::Value = serde_json::from_str(mcmeta).ok()?;
+        let animation = json.get("animation")?;
+        let size = |key: &str| {
+            animation.get(key).and_then(|v| v.as_u64()).and_then(|v| u32::try_from(v).ok()).filter(|&v| v > 0)
+        };
+        // Entries are indices, or {"index": i, "time": t}
+        let frames = animation.get("frames").and_then(|f| f.as_array()).map(|frames| {
+            frames.iter()
+                .filter_map(|f| f.as_u64().or_else(|| f.get("index")?.as_u64()))
+                .filter_map(|i| u32::try_from(i).ok())
+                .collect()
+        });
+        Some(Animation { frame_width: size("width"), frame_height: size("height"), frames: frames.unwrap_or_default() })
+    }
+
+    /// Pixel rectangle `(x, y, width, height)` of playback frame `frame` in an
+    /// image of `size`; frames past the end wrap around
+    pub fn frame_rect(&self, (width, height): (u32, u32), frame: usize) -> (u32, u32, u32, u32) {
+        let (frame_width, frame_height) = match (self.frame_width, self.frame_height) {
+            (Some(w), Some(h)) => (w, h),
+            (Some(w), None) => (w, height),
+            (None, Some(h)) => (width, h),
+            (None, None) => (width.min(height), width.min(height)),
+        };
+        let (frame_width, frame_height) = (frame_width.clamp(1, width.max(1)), frame_height.clamp(1, height.max(1)));
+        let columns = (width / frame_width).max(1) as usize;
+        let count = columns * (height / frame_height).max(1) as usize;
+        let index = if self.frames.is_empty() {
+            frame % count
+        } else {
+            self.frames[frame % self.frames.len()] as usize
+        };
+        // Indices past the strip (a broken mcmeta) show the first frame
+        let index = if index < count { index } else { 0 };
+        ((index % columns) as u32 * frame_width, (index / columns) as u32 * frame_height, frame_width, frame_height)
+    }
+
+    /// Playback frame `frame` of `img`
+    pub fn crop(&self, img: &image::DynamicImage, frame: usize) -> RgbaImage {
+        let (x, y, width, height) = self.frame_rect(img.dimensions(), frame);
+        img.view(x, y, width, height).to_image()
+    }
+}
+
+/// Textures a [`TextureManager`] found, by where they came from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TextureCount {
+    /// Already on disk: in the jar's cache directory, or the directory given to `new`
+    pub cached: usize,
+    /// Extracted from client.jar while creating the manager
+    pub extracted: usize,
+}
+
+impl TextureCount {
+    pub fn total(&self) -> usize {
+        self.cached + self.extracted
+    }
+}
+
 /// Texture manager for block textures
 pub struct TextureManager {
     texture_dir: PathBuf,
     texture_map: HashMap<String, PathBuf>,
     /// Resource pack texture overrides (texture name -> path)
     resource_pack_textures: HashMap<String, PathBuf>,
+    /// Biome used for grass/foliage tints
+    biome: Biome,
+    /// Untinted average colors by texture file (`None` if fully transparent or unreadable)
+    average_colors: Mutex<AverageColors>,
+    /// Textures extracted from the jar when this manager was created
+    extracted: usize,
+    /// Frame of animated textures to use in exports
+    anim_frame: usize,
+    /// Whether `texture_dir` is the texture cache, where tinted copies can be kept
+    tint_cache: bool,
 }
 
 impl TextureManager {
@@ -123,8 +291,14 @@ impl TextureManager {
             texture_dir,
             texture_map: HashMap::new(),
             resource_pack_textures: HashMap::new(),
+            biome: Biome::default(),
+            average_colors: Mutex::new(HashMap::new()),
+            extracted: 0,
+            anim_frame: 0,
+            tint_cache: false,
         };
         manager.scan_textures();
+        manager.load_color_cache();
         manager
     }
 
@@ -135,7 +309,13 @@ impl TextureManager {
 
     /// Try to initialize with optional custom Minecraft path or jar path
     pub fn from_minecraft_with_path(custom_path: Option<&Path>, resource_pack: Option<&Path>) -> Option<Self> {
-        let cache_dir = get_cache_dir()?;
+        Self::from_minecraft_with_options(custom_path, resource_pack, false)
+    }
+
+    /// Like [`from_minecraft_with_path`](Self::from_minecraft_with_path); with `refresh`,
+    /// the jar's cached textures are thrown away and extracted again
+    pub fn from_minecraft_with_options(custom_path: Option<&Path>, resource_pack: Option<&Path>, refresh: bool) -> Option<Self> {
+        let cache_root = get_cache_dir()?;
 
         // Determine jar path
         let jar_path = if let Some(path) = custom_path {
@@ -152,46 +332,54 @@ impl TextureManager {
             find_client_jar(&mc_dir)?
         };
 
-        // Check if we need to re-extract (different jar)
-        let jar_marker = cache_dir.join(".source_jar");
-        let jar_path_str = jar_path.to_string_lossy().to_string();
-        let need_extract = if textures_cached(&cache_dir) {
-            // Check if source jar changed
-            match std::fs::read_to_string(&jar_marker) {
-                Ok(cached_jar) => cached_jar.trim() != jar_path_str,
-                Err(_) => true,
+        let cache_dir = match jar_cache_dir(&cache_root, &jar_path) {
+            Ok(dir) => dir,
+            Err(e) => {
+                log::warn!("Failed to read {}: {}", jar_path.display(), e);
+                return None;
             }
-        } else {
-            true
         };
+        if refresh {
+            let _ = fs::remove_dir_all(&cache_dir);
+        }
 
-        if need_extract {
-            eprintln!("Extracting textures from {:?}...", jar_path);
-            match extract_textures(&jar_path, &cache_dir) {
+        let mut extracted = 0;
+        if !textures_cached(&cache_dir) {
+            log::info!("Extracting textures from {:?}...", jar_path);
+            // Extract next to the cache and move it in place, so an interrupted run leaves no half cache
+            let staging = cache_dir.with_extension(format!("partial-{}", std::process::id()));
+            let result = extract_textures(&jar_path, &staging).and_then(|count| {
+                let _ = fs::remove_dir_all(&cache_dir);
+                fs::rename(&staging, &cache_dir).map_err(|e| SchemError::export_io(&cache_dir, e))?;
+                Ok(count)
+            });
+            match result {
                 Ok(count) => {
-                    eprintln!("Extracted {} textures", count);
-                    // Save source jar path
-                    let _ = std::fs::write(&jar_marker, &jar_path_str);
+                    log::info!("Extracted {} textures", count);
+                    extracted = count;
                 }
                 Err(e) => {
-                    eprintln!("Failed to extract textures: {}", e);
+                    let _ = fs::remove_dir_all(&staging);
+                    log::warn!("Failed to extract textures: {}", e);
                     return None;
                 }
             }
         }
 
         let mut manager = Self::new(cache_dir);
+        manager.extracted = extracted.min(manager.texture_map.len());
+        manager.tint_cache = true;
 
         // Load resource pack textures if provided
         if let Some(pack_path) = resource_pack {
             match manager.load_resource_pack_textures(pack_path) {
                 Ok(count) => {
                     if count > 0 {
-                        eprintln!("Loaded {} textures from resource pack", count);
+                        log::info!("Loaded {} textures from resource pack", count);
                     }
                 }
                 Err(e) => {
-                    eprintln!("Warning: Failed to load resource pack textures: {}", e);
+                    log::warn!("Failed to load resource pack textures: {}", e);
                 }
             }
         }
@@ -200,42 +388,49 @@ impl TextureManager {
     }
 
     /// Load textures from a resource pack (ZIP file)
-    pub fn load_resource_pack_textures(&mut self, pack_path: &Path) -> std::io::Result<usize> {
-        let file = File::open(pack_path)?;
+    pub fn load_resource_pack_textures(&mut self, pack_path: &Path) -> Result<usize, SchemError> {
+        let file = File::open(pack_path).map_err(|e| SchemError::texture(pack_path, e))?;
         let mut archive = ZipArchive::new(file)
-            .map_err(|e| std::io::Error::other(format!("Failed to open resource pack: {}", e)))?;
+            .map_err(|e| SchemError::texture(pack_path, format!("Failed to open resource pack: {}", e)))?;
 
         // Create cache directory for resource pack textures
         let pack_cache = get_cache_dir()
-            .ok_or_else(|| std::io::Error::other("Could not get cache directory"))?
+            .ok_or_else(|| SchemError::texture(pack_path, "Could not get cache directory"))?
             .join("resource_pack");
-        fs::create_dir_all(&pack_cache)?;
+        fs::create_dir_all(&pack_cache).map_err(|e| SchemError::export_io(&pack_cache, e))?;
 
         let prefix = "assets/minecraft/textures/block/";
         let mut count = 0;
 
         for i in 0..archive.len() {
             let mut file = archive.by_index(i)
-                .map_err(|e| std::io::Error::other(e.to_string()))?;
+                .map_err(|e| SchemError::texture(pack_path, e))?;
             let name = file.name().to_string();
 
-            if name.starts_with(prefix) && name.ends_with(".png") {
-                let texture_name = name[prefix.len()..].strip_suffix(".png").unwrap();
-                let dest_path = pack_cache.join(format!("{}.png", texture_name));
+            let animation = name.ends_with(".png.mcmeta");
+            if name.starts_with(prefix) && (name.ends_with(".png") || animation) {
+                let file_name = &name[prefix.len()..];
+                let dest_path = pack_cache.join(file_name);
 
                 // Create parent dirs if needed
                 if let Some(parent) = dest_path.parent() {
-                    fs::create_dir_all(parent)?;
+                    fs::create_dir_all(parent).map_err(|e| SchemError::export_io(parent, e))?;
                 }
 
                 let mut contents = Vec::new();
-                file.read_to_end(&mut contents)?;
-
-                let mut dest_file = File::create(&dest_path)?;
-                dest_file.write_all(&contents)?;
-
-                self.resource_pack_textures.insert(texture_name.to_string(), dest_path);
-                count += 1;
+                file.read_to_end(&mut contents)
+                    .map_err(|e| SchemError::texture(pack_path, format!("{}: {}", name, e)))?;
+
+                File::create(&dest_path)
+                    .and_then(|mut dest_file| dest_file.write_all(&contents))
+                    .map_err(|e| SchemError::export_io(&dest_path, e))?;
+
+                // Animation files sit next to their texture, where `Animation::for_texture` finds them
+                if !animation {
+                    let texture_name = file_name.strip_suffix(".png").unwrap();
+                    self.resource_pack_textures.insert(texture_name.to_string(), dest_path);
+                    count += 1;
+                }
             }
         }
 
@@ -310,36 +505,315 @@ impl TextureManager {
         !self.texture_map.is_empty()
     }
 
-    /// Get count of loaded textures
-    pub fn texture_count(&self) -> usize {
-        self.texture_map.len()
+    /// Vanilla textures found, split into cache hits and fresh extractions
+    pub fn texture_count(&self) -> TextureCount {
+        let total = self.texture_map.len();
+        TextureCount { cached: total - self.extracted, extracted: self.extracted }
+    }
+
+    /// Copy a texture to `dest_path`, tinted for `block_name` in this manager's biome
+    /// and cropped to the chosen frame if it's animated
+    ///
+    /// Tinted copies of cached vanilla textures are kept under `tinted/` in the
+    /// cache, so later exports copy them instead of processing the image again.
+    pub fn copy_texture(&self, src_path: &Path, dest_path: &Path, block_name: &str) -> Result<(), SchemError> {
+        let tint = get_block_tint_in(block_name, self.biome);
+        if let Some(animation) = Animation::for_texture(src_path) {
+            // Few textures are animated, so their frames aren't cached
+            let img = image::open(src_path)
+                .map_err(|e| SchemError::texture(src_path, format!("Failed to open image: {}", e)))?;
+            let frame = animation.crop(&img, self.anim_frame);
+            let frame = match tint {
+                Some(tint) => tinted(&frame.into(), tint),
+                None => frame,
+            };
+            return frame.save(dest_path)
+                .map_err(|e| SchemError::texture(dest_path, format!("Failed to save image: {}", e)));
+        }
+        let Some(tint) = tint else {
+            return copy_file(src_path, dest_path);
+        };
+        let relative = match src_path.strip_prefix(&self.texture_dir) {
+            Ok(relative) if self.tint_cache => relative,
+            _ => return apply_tint_and_save(src_path, dest_path, tint),
+        };
+
+        let byte = |c: f32| (c * 255.0).round() as u8;
+        let tint_dir = format!("{:02x}{:02x}{:02x}", byte(tint.0), byte(tint.1), byte(tint.2));
+        let cached = self.texture_dir.join("tinted").join(tint_dir).join(relative);
+        if !cached.exists() {
+            if let Some(parent) = cached.parent() {
+                fs::create_dir_all(parent).map_err(|e| SchemError::export_io(parent, e))?;
+            }
+            // Another export may be tinting the same texture
+            let partial = cached.with_extension(format!("partial-{}.png", std::process::id()));
+            apply_tint_and_save(src_path, &partial, tint)?;
+            fs::rename(&partial, &cached).map_err(|e| SchemError::export_io(&cached, e))?;
+        }
+        copy_file(&cached, dest_path)
+    }
+
+    /// Tint grass and foliage textures with the colors of `biome`
+    pub fn with_biome(mut self, biome: Biome) -> Self {
+        self.biome = biome;
+        self
+    }
+
+    /// Biome used for grass/foliage tints
+    pub fn biome(&self) -> Biome {
+        self.biome
+    }
+
+    /// Use frame `frame` of animated textures (water, lava, magma, ...) instead of the first
+    pub fn with_anim_frame(mut self, frame: usize) -> Self {
+        self.anim_frame = frame;
+        self
+    }
+
+    /// Frame of animated textures used in exports
+    pub fn anim_frame(&self) -> usize {
+        self.anim_frame
+    }
+
+    /// A texture file's PNG bytes, cropped to the chosen frame if it's animated
+    pub fn read_texture(&self, path: &Path) -> Option<Vec<u8>> {
+        let Some(animation) = Animation::for_texture(path) else {
+            return fs::read(path).ok();
+        };
+        let frame = animation.crop(&image::open(path).ok()?, self.anim_frame);
+        let mut png = std::io::Cursor::new(Vec::new());
+        frame.write_to(&mut png, image::ImageFormat::Png).ok()?;
+        Some(png.into_inner())
+    }
+
+    /// Average color of a block's texture over its non-transparent pixels, with
+    /// the biome tint applied for grass, foliage and water
+    ///
+    /// Averages are cached per texture file for the lifetime of the manager.
+    pub fn average_color(&self, block_name: &str) -> Option<(f32, f32, f32)> {
+        let path = self.get_texture(block_name)?;
+        let (r, g, b) = {
+            let mut cache = self.average_colors.lock().unwrap_or_else(|e| e.into_inner());
+            (*cache.entry(path.clone()).or_insert_with(|| average_pixels(path)))?
+        };
+        // The tint multiplies every pixel, so tinting the average is the same as
+        // averaging the tinted pixels
+        Some(match get_block_tint_in(block_name, self.biome) {
+            Some((tr, tg, tb)) => (r * tr, g * tg, b * tb),
+            None => (r, g, b),
+        })
+    }
+
+    /// Save the averages of vanilla textures computed so far to the texture
+    /// directory, where later managers pick them up
+    pub fn save_color_cache(&self) -> Result<(), SchemError> {
+        let cache = self.average_colors.lock().unwrap_or_else(|e| e.into_inner());
+        let mut lines: Vec<String> = cache.iter()
+            // Resource pack textures live in a subdirectory and change with the pack
+            .filter(|(path, _)| path.parent() == Some(self.texture_dir.as_path()))
+            .filter_map(|(path, color)| {
+                let name = path.file_name()?.to_string_lossy();
+                Some(match color {
+                    Some((r, g, b)) => format!("{}\t{}\t{}\t{}", name, r, g, b),
+                    None => format!("{}\t-", name),
+                })
+            })
+            .collect();
+        lines.sort();
+        let path = self.texture_dir.join(COLOR_CACHE);
+        fs::write(&path, lines.join("\n") + "\n").map_err(|e| SchemError::export_io(&path, e))
+    }
+
+    /// Read averages saved by `save_color_cache`, ignoring a missing or damaged file
+    fn load_color_cache(&mut self) {
+        let Ok(text) = fs::read_to_string(self.texture_dir.join(COLOR_CACHE)) else { return };
+        let cache = self.average_colors.get_mut().unwrap_or_else(|e| e.into_inner());
+        for line in text.lines() {
+            let mut fields = line.split('\t');
+            let Some(name) = fields.next() else { continue };
+            let values: Vec<f32> = fields.filter_map(|f| f.parse().ok()).collect();
+            let color = match values[..] {
+                [r, g, b] => Some((r, g, b)),
+                _ if line.ends_with("\t-") => None,
+                _ => continue,
+            };
+            cache.insert(self.texture_dir.join(name), color);
+        }
+    }
+}
+
+/// Mean color of the pixels with any opacity, as 0-1 components
+fn average_pixels(path: &Path) -> Option<(f32, f32, f32)> {
+    let img = image::open(path).ok()?.to_rgba8();
+    let mut sum = [0u64; 3];
+    let mut count = 0u64;
+    for pixel in img.pixels() {
+        let [r, g, b, a] = pixel.0;
+        if a > 0 {
+            sum[0] += r as u64;
+            sum[1] += g as u64;
+            sum[2] += b as u64;
+            count += 1;
+        }
+    }
+    if count == 0 {
+        return None;
+    }
+    let channel = |s: u64| s as f32 / count as f32 / 255.0;
+    Some((channel(sum[0]), channel(sum[1]), channel(sum[2])))
+}
+
+/// Biomes with distinct grass and foliage colors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Biome {
+    #[default]
+    Plains,
+    Forest,
+    BirchForest,
+    DarkForest,
+    Jungle,
+    Taiga,
+    SnowyPlains,
+    Swamp,
+    Savanna,
+    Desert,
+    Badlands,
+    Meadow,
+    CherryGrove,
+}
+
+/// Convert a 0xRRGGBB color to (r, g, b) multipliers
+fn rgb(hex: u32) -> (f32, f32, f32) {
+    (
+        ((hex >> 16) & 0xFF) as f32 / 255.0,
+        ((hex >> 8) & 0xFF) as f32 / 255.0,
+        (hex & 0xFF) as f32 / 255.0,
+    )
+}
+
+impl Biome {
+    pub const ALL: [Biome; 13] = [
+        Biome::Plains, Biome::Forest, Biome::BirchForest, Biome::DarkForest, Biome::Jungle,
+        Biome::Taiga, Biome::SnowyPlains, Biome::Swamp, Biome::Savanna, Biome::Desert,
+        Biome::Badlands, Biome::Meadow, Biome::CherryGrove,
+    ];
+
+    /// Minecraft biome id without namespace, e.g. "birch_forest"
+    pub fn name(self) -> &'static str {
+        match self {
+            Biome::Plains => "plains",
+            Biome::Forest => "forest",
+            Biome::BirchForest => "birch_forest",
+            Biome::DarkForest => "dark_forest",
+            Biome::Jungle => "jungle",
+            Biome::Taiga => "taiga",
+            Biome::SnowyPlains => "snowy_plains",
+            Biome::Swamp => "swamp",
+            Biome::Savanna => "savanna",
+            Biome::Desert => "desert",
+            Biome::Badlands => "badlands",
+            Biome::Meadow => "meadow",
+            Biome::CherryGrove => "cherry_grove",
+        }
+    }
+
+    /// Grass block / tall grass tint
+    pub fn grass_color(self) -> (f32, f32, f32) {
+        match self {
+            Biome::Plains => (0.57, 0.74, 0.35), // #91BD59
+            Biome::Forest => rgb(0x79C05A),
+            Biome::BirchForest => rgb(0x88BB67),
+            Biome::DarkForest => rgb(0x507A32),
+            Biome::Jungle => rgb(0x59C93C),
+            Biome::Taiga => rgb(0x86B783),
+            Biome::SnowyPlains => rgb(0x80B497),
+            Biome::Swamp => rgb(0x6A7039),
+            Biome::Savanna | Biome::Desert => rgb(0xBFB755),
+            Biome::Badlands => rgb(0x90814D),
+            Biome::Meadow => rgb(0x83BB6D),
+            Biome::CherryGrove => rgb(0xB6DB61),
+        }
+    }
+
+    /// Leaves / vines tint
+    pub fn foliage_color(self) -> (f32, f32, f32) {
+        match self {
+            Biome::Plains => (0.47, 0.74, 0.34),
+            Biome::Forest | Biome::DarkForest => rgb(0x59AE30),
+            Biome::BirchForest => rgb(0x6BA941),
+            Biome::Jungle => rgb(0x30BB0B),
+            Biome::Taiga => rgb(0x68A464),
+            Biome::SnowyPlains => rgb(0x60A17B),
+            Biome::Swamp => rgb(0x6A7039),
+            Biome::Savanna | Biome::Desert => rgb(0xAEA42A),
+            Biome::Badlands => rgb(0x9E814D),
+            Biome::Meadow => rgb(0x63A948),
+            Biome::CherryGrove => rgb(0xB6DB61),
+        }
+    }
+
+    /// Water tint
+    pub fn water_color(self) -> (f32, f32, f32) {
+        match self {
+            Biome::Swamp => rgb(0x617B64),
+            Biome::Meadow => rgb(0x0E4ECF),
+            Biome::CherryGrove => rgb(0x5DB7EF),
+            _ => rgb(0x3F76E4),
+        }
+    }
+}
+
+impl std::fmt::Display for Biome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
     }
 }
 
-/// Get tint color for a block (if it needs tinting)
+impl std::str::FromStr for Biome {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.strip_prefix("minecraft:").unwrap_or(s);
+        Biome::ALL.into_iter().find(|b| b.name() == name).ok_or_else(|| {
+            let names: Vec<_> = Biome::ALL.iter().map(|b| b.name()).collect();
+            format!("unknown biome '{}' (expected one of: {})", s, names.join(", "))
+        })
+    }
+}
+
+/// Get tint color for a block (if it needs tinting), using plains colors
 /// Returns (r, g, b) multiplier where 1.0 = no change
 pub fn get_block_tint(block_name: &str) -> Option<(f32, f32, f32)> {
+    get_block_tint_in(block_name, Biome::Plains)
+}
+
+/// Get tint color for a block in the given biome
+pub fn get_block_tint_in(block_name: &str, biome: Biome) -> Option<(f32, f32, f32)> {
     let name = block_name.strip_prefix("minecraft:").unwrap_or(block_name);
 
     // Leaves use foliage color (green tint)
-    // Default foliage color from plains biome
     if name.contains("leaves") {
-        return Some((0.47, 0.74, 0.34)); // #77BD2F - plains foliage
+        return Some(biome.foliage_color());
     }
 
     // Grass blocks and grass use grass color
     if name == "grass_block" || name == "grass" || name == "tall_grass" {
-        return Some((0.57, 0.74, 0.35)); // #91BD59 - plains grass
+        return Some(biome.grass_color());
     }
 
     // Vines
     if name.contains("vine") {
-        return Some((0.47, 0.74, 0.34));
+        return Some(biome.foliage_color());
+    }
+
+    // Water textures are grayscale
+    if name == "water" || name == "bubble_column" {
+        return Some(biome.water_color());
     }
 
-    // Lily pad
+    // Lily pad (fixed color, but historically tinted like foliage here)
     if name == "lily_pad" {
-        return Some((0.47, 0.74, 0.34));
+        return Some(Biome::Plains.foliage_color());
     }
 
     None
@@ -347,12 +821,19 @@ pub fn get_block_tint(block_name: &str) -> Option<(f32, f32, f32)> {
 
 /// Apply tint to an image and save to destination
 /// The tint multiplies each pixel's RGB values
-pub fn apply_tint_and_save(src_path: &Path, dest_path: &Path, tint: (f32, f32, f32)) -> std::io::Result<()> {
+pub fn apply_tint_and_save(src_path: &Path, dest_path: &Path, tint: (f32, f32, f32)) -> Result<(), SchemError> {
     let img = image::open(src_path)
-        .map_err(|e| std::io::Error::other(format!("Failed to open image: {}", e)))?;
+        .map_err(|e| SchemError::texture(src_path, format!("Failed to open image: {}", e)))?;
 
+    tinted(&img, tint).save(dest_path)
+        .map_err(|e| SchemError::texture(dest_path, format!("Failed to save image: {}", e)))?;
+
+    Ok(())
+}
+
+fn tinted(img: &image::DynamicImage, tint: (f32, f32, f32)) -> RgbaImage {
     let (width, height) = img.dimensions();
-    let mut output: ImageBuffer<Rgba<u8>, Vec<u8>> = ImageBuffer::new(width, height);
+    let mut output: RgbaImage = ImageBuffer::new(width, height);
 
     for (x, y, pixel) in img.pixels() {
         let [r, g, b, a] = pixel.0;
@@ -361,23 +842,29 @@ pub fn apply_tint_and_save(src_path: &Path, dest_path: &Path, tint: (f32, f32, f
         let new_b = ((b as f32 * tint.2).min(255.0)) as u8;
         output.put_pixel(x, y, Rgba([new_r, new_g, new_b, a]));
     }
+    output
+}
 
-    output.save(dest_path)
-        .map_err(|e| std::io::Error::other(format!("Failed to save image: {}", e)))?;
-
-    Ok(())
+/// Copy texture with optional tinting (plains colors)
+pub fn copy_texture_with_tint(src_path: &Path, dest_path: &Path, block_name: &str) -> Result<(), SchemError> {
+    copy_texture_with_biome_tint(src_path, dest_path, block_name, Biome::Plains)
 }
 
-/// Copy texture with optional tinting
-pub fn copy_texture_with_tint(src_path: &Path, dest_path: &Path, block_name: &str) -> std::io::Result<()> {
-    if let Some(tint) = get_block_tint(block_name) {
+/// Copy texture, tinting grass and foliage with the colors of `biome`
+pub fn copy_texture_with_biome_tint(src_path: &Path, dest_path: &Path, block_name: &str, biome: Biome) -> Result<(), SchemError> {
+    if let Some(tint) = get_block_tint_in(block_name, biome) {
         apply_tint_and_save(src_path, dest_path, tint)
     } else {
-        std::fs::copy(src_path, dest_path)?;
-        Ok(())
+        copy_file(src_path, dest_path)
     }
 }
 
+fn copy_file(src_path: &Path, dest_path: &Path) -> Result<(), SchemError> {
+    std::fs::copy(src_path, dest_path)
+        .map_err(|e| SchemError::texture(src_path, format!("Failed to copy to {}: {}", dest_path.display(), e)))?;
+    Ok(())
+}
+
 /// Get texture name variations for a block
 fn get_texture_variations(name: &str) -> Vec<String> {
     let mut variations = Vec::new();
@@ -387,6 +874,17 @@ fn get_texture_variations(name: &str) -> Vec<String> {
     variations.push(format!("{}_side", name));
     variations.push(format!("{}_front", name));
 
+    // Fluids only have animated still/flow textures
+    if name == "water" || name == "lava" || name == "bubble_column" {
+        let fluid = if name == "lava" { "lava" } else { "water" };
+        variations.push(format!("{}_still", fluid));
+    }
+
+    // Blocks named after their material (magma_block -> magma)
+    if let Some(base) = name.strip_suffix("_block") {
+        variations.push(base.to_string());
+    }
+
     // Handle planks
     if name.ends_with("_planks") {
         variations.push(name.replace("_planks", "_planks"));
@@ -455,3 +953,86 @@ fn get_texture_variations(name: &str) -> Vec<String> {
 
     variations
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_average_color_skips_transparent_pixels_and_tints() {
+        let dir = std::env::temp_dir().join(format!("schem-tool-textures-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let mut img: ImageBuffer<Rgba<u8>, Vec<u8>> = ImageBuffer::new(2, 1);
+        img.put_pixel(0, 0, Rgba([204, 204, 204, 255]));
+        img.put_pixel(1, 0, Rgba([0, 0, 0, 0]));
+        img.save(dir.join("water_still.png")).unwrap();
+
+        let manager = TextureManager::new(dir.clone()).with_biome(Biome::Swamp);
+        let (r, g, b) = Biome::Swamp.water_color();
+        assert_eq!(manager.average_color("minecraft:water"), Some((0.8 * r, 0.8 * g, 0.8 * b)));
+        assert_eq!(manager.average_color("minecraft:stone"), None);
+
+        // A new manager reads the saved average instead of the image
+        manager.save_color_cache().unwrap();
+        fs::remove_file(dir.join("water_still.png")).unwrap();
+        let reloaded = TextureManager::new(dir.clone());
+        let cached = reloaded.average_colors.lock().unwrap()[&dir.join("water_still.png")];
+        assert_eq!(cached, Some((0.8, 0.8, 0.8)));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_animation_frames() {
+        let strip = Animation::parse(r#"{"animation": {"frametime": 2}}"#).unwrap();
+        assert_eq!(strip.frame_rect((16, 512), 0), (0, 0, 16, 16));
+        assert_eq!(strip.frame_rect((16, 512), 33), (0, 16, 16, 16));
+
+        let ordered = Animation::parse(r#"{"animation": {"frames": [2, {"index": 5, "time": 3}], "width": 8, "height": 8}}"#).unwrap();
+        assert_eq!(ordered.frames, [2, 5]);
+        assert_eq!(ordered.frame_rect((16, 32), 1), (8, 16, 8, 8));
+        // Past the strip: the first frame
+        assert_eq!(ordered.frame_rect((16, 16), 1), (0, 0, 8, 8));
+
+        assert!(Animation::parse(r#"{"villager": {"hat": "full"}}"#).is_none());
+    }
+
+    #[test]
+    fn test_jar_cache_and_tinted_copies() {
+        let dir = std::env::temp_dir().join(format!("schem-tool-jar-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let mut png = Vec::new();
+        let img: ImageBuffer<Rgba<u8>, Vec<u8>> = ImageBuffer::from_pixel(1, 1, Rgba([200, 200, 200, 255]));
+        img.write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png).unwrap();
+
+        let jar = dir.join("client.jar");
+        let mut zip = zip::ZipWriter::new(File::create(&jar).unwrap());
+        for name in ["block/stone.png", "block/oak_leaves.png", "item/stick.png", "block/stone.png.mcmeta"] {
+            zip.start_file(format!("assets/minecraft/textures/{}", name), zip::write::SimpleFileOptions::default()).unwrap();
+            zip.write_all(&png).unwrap();
+        }
+        zip.finish().unwrap();
+
+        let root = dir.join("cache");
+        let cache = jar_cache_dir(&root, &jar).unwrap();
+        assert!(cache.file_name().unwrap().to_string_lossy().starts_with(&format!("v{}-", CACHE_VERSION)));
+        assert_eq!(jar_cache_dir(&root, &jar).unwrap(), cache);
+        assert_eq!(fs::read_to_string(root.join(JAR_INDEX)).unwrap().lines().count(), 1);
+
+        assert_eq!(extract_textures(&jar, &cache).unwrap(), 2);
+        assert!(cache.join("stone.png.mcmeta").exists());
+        assert!(textures_cached(&cache));
+
+        // Tinted textures are made once, then copied from the cache
+        let mut manager = TextureManager::new(cache.clone());
+        manager.tint_cache = true;
+        assert_eq!(manager.texture_count(), TextureCount { cached: 2, extracted: 0 });
+        let leaves = manager.get_texture("minecraft:oak_leaves").unwrap().clone();
+        manager.copy_texture(&leaves, &dir.join("out.png"), "minecraft:oak_leaves").unwrap();
+        let tinted: Vec<PathBuf> = fs::read_dir(cache.join("tinted")).unwrap().flatten().map(|e| e.path().join("oak_leaves.png")).collect();
+        assert!(tinted.len() == 1 && tinted[0].exists());
+        assert_eq!(fs::read(&tinted[0]).unwrap(), fs::read(dir.join("out.png")).unwrap());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
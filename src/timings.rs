@@ -0,0 +1,145 @@
+//! Wall-clock time spent in each phase of loading and exporting
+//!
+//! Exporters already announce their phases through [`Progress`], so wrapping
+//! the receiver in a [`TimedProgress`] times them without any other changes;
+//! [`Timings::time`] covers work that doesn't report progress.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::Progress;
+
+/// Total time spent in one named phase
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Phase {
+    pub name: String,
+    pub duration: Duration,
+    /// Times the phase ran
+    pub count: usize,
+}
+
+/// Phase times, in the order phases first ran; phases with the same name add up
+#[derive(Debug, Default)]
+pub struct Timings {
+    phases: Mutex<Vec<Phase>>,
+}
+
+impl Timings {
+    pub const fn new() -> Self {
+        Self { phases: Mutex::new(Vec::new()) }
+    }
+
+    /// Add `duration` to the phase called `name`
+    pub fn record(&self, name: &str, duration: Duration) {
+        let mut phases = self.phases.lock().unwrap();
+        match phases.iter_mut().find(|phase| phase.name == name) {
+            Some(phase) => {
+                phase.duration += duration;
+                phase.count += 1;
+            }
+            None => phases.push(Phase { name: name.to_string(), duration, count: 1 }),
+        }
+    }
+
+    /// Run `f` as the phase called `name`
+    pub fn time<T>(&self, name: &str, f: impl FnOnce() -> T) -> T {
+        let started = Instant::now();
+        let result = f();
+        self.record(name, started.elapsed());
+        result
+    }
+
+    pub fn phases(&self) -> Vec<Phase> {
+        self.phases.lock().unwrap().clone()
+    }
+
+    /// Time spent in all phases
+    pub fn total(&self) -> Duration {
+        self.phases.lock().unwrap().iter().map(|phase| phase.duration).sum()
+    }
+}
+
+/// Progress receiver that times each phase from `start` to `finish` (or the
+/// next `start`) and passes every update on to another receiver
+pub struct TimedProgress<'a> {
+    inner: &'a dyn Progress,
+    timings: &'a Timings,
+    running: Mutex<Option<(String, Instant)>>,
+}
+
+impl<'a> TimedProgress<'a> {
+    pub fn new(inner: &'a dyn Progress, timings: &'a Timings) -> Self {
+        Self { inner, timings, running: Mutex::new(None) }
+    }
+
+    fn end_phase(&self) {
+        if let Some((name, started)) = self.running.lock().unwrap().take() {
+            self.timings.record(&name, started.elapsed());
+        }
+    }
+}
+
+impl Progress for TimedProgress<'_> {
+    fn start(&self, total: u64, message: &str) {
+        self.end_phase();
+        *self.running.lock().unwrap() = Some((message.to_string(), Instant::now()));
+        self.inner.start(total, message);
+    }
+
+    fn set_position(&self, position: u64) {
+        self.inner.set_position(position);
+    }
+
+    fn finish(&self, message: &str) {
+        self.end_phase();
+        self.inner.finish(message);
+    }
+
+    fn should_cancel(&self) -> bool {
+        self.inner.should_cancel()
+    }
+}
+
+impl Drop for TimedProgress<'_> {
+    fn drop(&mut self) {
+        self.end_phase();
+    }
+}
+
+/// Peak resident memory of this process in bytes (`VmHWM` in
+/// `/proc/self/status`), or None where that isn't available
+pub fn peak_rss() -> Option<u64> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find_map(|line| line.strip_prefix("VmHWM:"))?;
+    let kib: u64 = line.trim().strip_suffix("kB")?.trim().parse().ok()?;
+    Some(kib * 1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NoProgress;
+
+    #[test]
+    fn test_timed_progress() {
+        let timings = Timings::new();
+        {
+            let progress = TimedProgress::new(&NoProgress, &timings);
+            progress.start(10, "Collecting materials");
+            progress.start(10, "Writing OBJ");
+            progress.finish("Written");
+            progress.start(10, "Collecting materials");
+        }
+        assert_eq!(timings.time("Saving", || 42), 42);
+
+        let phases: Vec<(String, usize)> = timings.phases().into_iter().map(|phase| (phase.name, phase.count)).collect();
+        let expected = [("Collecting materials", 2), ("Writing OBJ", 1), ("Saving", 1)];
+        assert_eq!(phases, expected.map(|(name, count)| (name.to_string(), count)));
+        if cfg!(target_os = "linux") {
+            assert!(peak_rss().is_some_and(|bytes| bytes > 0));
+        }
+    }
+}
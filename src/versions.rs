@@ -0,0 +1,243 @@
+//! Minecraft releases and the blocks they introduced
+//!
+//! [`introduced_in`] knows the blocks added since 1.13, the first release with
+//! flattened block names; anything else (vanilla blocks from 1.13 and before,
+//! and modded blocks) has no requirement. [`minimum_version`] finds the newest
+//! release a schematic's blocks need and checks it against the `DataVersion`
+//! the schematic declares: a palette with blocks newer than its `DataVersion`
+//! came from a broken exporter.
+
+use std::collections::BTreeMap;
+
+use crate::UnifiedSchematic;
+
+/// A release and its data version
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Release {
+    pub name: &'static str,
+    pub data_version: i32,
+}
+
+impl std::fmt::Display for Release {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name)
+    }
+}
+
+const fn release(name: &'static str, data_version: i32) -> Release {
+    Release { name, data_version }
+}
+
+/// Releases since the flattening, oldest first
+pub const RELEASES: &[Release] = &[
+    release("1.13", 1519), release("1.13.1", 1628), release("1.13.2", 1631),
+    release("1.14", 1952), release("1.14.1", 1957), release("1.14.2", 1963), release("1.14.3", 1968), release("1.14.4", 1976),
+    release("1.15", 2225), release("1.15.1", 2227), release("1.15.2", 2230),
+    release("1.16", 2566), release("1.16.1", 2567), release("1.16.2", 2578), release("1.16.3", 2580),
+    release("1.16.4", 2584), release("1.16.5", 2586),
+    release("1.17", 2724), release("1.17.1", 2730),
+    release("1.18", 2860), release("1.18.1", 2865), release("1.18.2", 2975),
+    release("1.19", 3105), release("1.19.1", 3117), release("1.19.2", 3120), release("1.19.3", 3218), release("1.19.4", 3337),
+    release("1.20", 3463), release("1.20.1", 3465), release("1.20.2", 3578), release("1.20.3", 3698), release("1.20.4", 3700),
+    release("1.20.5", 3837), release("1.20.6", 3839),
+    release("1.21", 3953), release("1.21.1", 3955), release("1.21.2", 4080), release("1.21.3", 4082), release("1.21.4", 4189),
+    release("1.21.5", 4325), release("1.21.6", 4435), release("1.21.7", 4438), release("1.21.8", 4440),
+];
+
+/// The release a data version belongs to: the newest one at or below it
+pub fn release_for_data_version(data_version: i32) -> Option<&'static Release> {
+    RELEASES.iter().rev().find(|release| release.data_version <= data_version)
+}
+
+/// A release by name, e.g. "1.16.5"
+pub fn find_release(name: &str) -> Option<&'static Release> {
+    RELEASES.iter().find(|release| release.name == name)
+}
+
+fn named(name: &str) -> &'static Release {
+    find_release(name).expect("release in RELEASES")
+}
+
+/// Block names (without namespace) each release added; `*` matches any run of
+/// characters. Exact names are checked before patterns, and patterns from newer
+/// releases before older ones, so `bamboo_sapling` stays 1.14 while `bamboo_*`
+/// is 1.20.
+const INTRODUCED: &[(&str, &[&str])] = &[
+    // Renamed in 1.14, so their 1.13 names must not match `*_sign` below
+    ("1.13", &["sign", "wall_sign"]),
+    ("1.14", &[
+        "bamboo", "bamboo_sapling", "barrel", "bell", "blast_furnace", "smoker", "cartography_table",
+        "fletching_table", "grindstone", "lectern", "smithing_table", "stonecutter", "composter",
+        "campfire", "lantern", "scaffolding", "sweet_berry_bush", "cornflower", "lily_of_the_valley",
+        "wither_rose", "potted_cornflower", "potted_lily_of_the_valley", "potted_wither_rose", "potted_bamboo",
+        "jigsaw", "smooth_stone", "smooth_stone_slab", "stone_stairs", "cut_sandstone_slab", "cut_red_sandstone_slab",
+        "granite_stairs", "granite_slab", "granite_wall", "polished_granite_stairs", "polished_granite_slab",
+        "diorite_stairs", "diorite_slab", "diorite_wall", "polished_diorite_stairs", "polished_diorite_slab",
+        "andesite_stairs", "andesite_slab", "andesite_wall", "polished_andesite_stairs", "polished_andesite_slab",
+        "mossy_cobblestone_stairs", "mossy_cobblestone_slab", "mossy_stone_brick_stairs", "mossy_stone_brick_slab",
+        "mossy_stone_brick_wall", "smooth_sandstone_stairs", "smooth_sandstone_slab", "smooth_red_sandstone_stairs",
+        "smooth_red_sandstone_slab", "smooth_quartz_stairs", "smooth_quartz_slab", "red_nether_brick_stairs",
+        "red_nether_brick_slab", "red_nether_brick_wall", "end_stone_brick_stairs", "end_stone_brick_slab",
+        "end_stone_brick_wall", "brick_wall", "prismarine_wall", "red_sandstone_wall", "sandstone_wall",
+        "stone_brick_wall", "nether_brick_wall", "*_sign",
+    ]),
+    ("1.15", &["bee_nest", "beehive", "honey_block", "honeycomb_block"]),
+    ("1.16", &[
+        "*crimson*", "*warped*", "soul_soil", "soul_fire", "soul_torch", "soul_wall_torch", "soul_lantern",
+        "soul_campfire", "netherite_block", "ancient_debris", "nether_gold_ore", "basalt", "polished_basalt",
+        "*blackstone*", "chiseled_nether_bricks", "cracked_nether_bricks", "quartz_bricks", "crying_obsidian",
+        "respawn_anchor", "lodestone", "target", "shroomlight", "chain", "weeping_vines", "weeping_vines_plant",
+        "twisting_vines", "twisting_vines_plant", "nether_sprouts",
+    ]),
+    ("1.17", &[
+        "*amethyst*", "calcite", "tuff", "*deepslate*", "*copper*", "raw_iron_block", "raw_gold_block",
+        "dripstone_block", "pointed_dripstone", "moss_block", "moss_carpet", "*azalea*", "spore_blossom",
+        "cave_vines", "cave_vines_plant", "big_dripleaf", "big_dripleaf_stem", "small_dripleaf", "glow_lichen",
+        "hanging_roots", "rooted_dirt", "powder_snow", "*candle*", "lightning_rod", "sculk_sensor",
+        "tinted_glass", "smooth_basalt", "light", "*_amethyst_bud", "amethyst_cluster",
+    ]),
+    ("1.19", &[
+        "*mangrove*", "mud", "packed_mud", "mud_bricks", "mud_brick_*", "sculk", "sculk_vein", "sculk_catalyst",
+        "sculk_shrieker", "reinforced_deepslate", "frogspawn", "ochre_froglight", "verdant_froglight",
+        "pearlescent_froglight",
+    ]),
+    ("1.20", &[
+        "*cherry*", "bamboo_*", "stripped_bamboo_block", "*_hanging_sign", "chiseled_bookshelf", "decorated_pot",
+        "suspicious_sand", "suspicious_gravel", "torchflower", "torchflower_crop", "potted_torchflower",
+        "pitcher_plant", "pitcher_crop", "pink_petals", "sniffer_egg", "calibrated_sculk_sensor", "piglin_head",
+        "piglin_wall_head",
+    ]),
+    ("1.20.3", &["short_grass"]),
+    ("1.21", &[
+        "crafter", "trial_spawner", "vault", "heavy_core", "tuff_*", "polished_tuff*", "chiseled_tuff*",
+        "*chiseled_copper", "*copper_grate", "*copper_bulb", "*copper_door", "*copper_trapdoor",
+    ]),
+    ("1.21.4", &[
+        "*pale_oak*", "pale_moss_block", "pale_moss_carpet", "pale_hanging_moss", "creaking_heart", "*resin*",
+        "open_eyeblossom", "closed_eyeblossom", "potted_open_eyeblossom", "potted_closed_eyeblossom",
+    ]),
+    ("1.21.5", &[
+        "leaf_litter", "wildflowers", "bush", "firefly_bush", "short_dry_grass", "tall_dry_grass", "cactus_flower",
+        "test_block", "test_instance_block",
+    ]),
+    ("1.21.6", &["dried_ghast"]),
+];
+
+/// Match a name against a pattern where `*` stands for any run of characters
+fn matches(pattern: &str, name: &str) -> bool {
+    match (pattern.strip_prefix('*'), pattern.strip_suffix('*')) {
+        (Some(rest), _) if rest.ends_with('*') => name.contains(&rest[..rest.len() - 1]),
+        (Some(suffix), _) => name.ends_with(suffix),
+        (None, Some(prefix)) => name.starts_with(prefix),
+        (None, None) => name == pattern,
+    }
+}
+
+/// The release that added a block, or None for blocks from 1.13 or before and
+/// blocks outside the `minecraft` namespace
+pub fn introduced_in(name: &str) -> Option<&'static Release> {
+    let name = match name.split_once(':') {
+        Some(("minecraft", name)) => name,
+        Some(_) => return None,
+        None => name,
+    };
+    let exact = INTRODUCED.iter().find(|(_, names)| names.contains(&name));
+    let pattern = || INTRODUCED.iter().rev()
+        .find(|(_, patterns)| patterns.iter().any(|p| p.contains('*') && matches(p, name)));
+    exact.or_else(pattern)
+        .map(|(release, _)| named(release))
+        .filter(|release| release.name != "1.13")
+}
+
+/// What [`minimum_version`] found
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VersionReport {
+    /// Newest release the blocks need; None if 1.13 has them all
+    pub minimum: Option<&'static Release>,
+    /// Blocks from that release, with counts
+    pub culprits: Vec<(String, usize)>,
+    /// Blocks from each release after 1.13, newest first
+    pub by_release: Vec<(&'static Release, Vec<String>)>,
+    /// DataVersion the schematic declares
+    pub declared: Option<i32>,
+    /// Blocks newer than the declared DataVersion
+    pub newer_than_declared: Vec<String>,
+}
+
+/// Minimum release for a schematic's blocks, checked against its DataVersion
+pub fn minimum_version(schem: &UnifiedSchematic) -> VersionReport {
+    let mut by_release: BTreeMap<i32, (&'static Release, BTreeMap<String, usize>)> = BTreeMap::new();
+    for (name, count) in schem.block_counts() {
+        if let Some(release) = introduced_in(&name) {
+            by_release.entry(release.data_version).or_insert((release, BTreeMap::new())).1.insert(name, count);
+        }
+    }
+
+    let declared = schem.metadata.data_version;
+    let newer_than_declared = declared.map_or_else(Vec::new, |declared| {
+        by_release.values()
+            .filter(|(release, _)| release.data_version > declared)
+            .flat_map(|(_, blocks)| blocks.keys().cloned())
+            .collect()
+    });
+    let (minimum, culprits) = match by_release.values().next_back() {
+        Some((release, blocks)) => {
+            let mut culprits: Vec<(String, usize)> = blocks.iter().map(|(name, count)| (name.clone(), *count)).collect();
+            culprits.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+            (Some(*release), culprits)
+        }
+        None => (None, Vec::new()),
+    };
+    VersionReport {
+        minimum,
+        culprits,
+        by_release: by_release.into_values().rev().map(|(release, blocks)| (release, blocks.into_keys().collect())).collect(),
+        declared,
+        newer_than_declared,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::SchematicBuilder;
+    use crate::{Block, Metadata};
+
+    #[test]
+    fn test_introduced_in() {
+        let version = |name| introduced_in(name).map(|release| release.name);
+        assert_eq!(version("minecraft:stone"), None);
+        assert_eq!(version("minecraft:sign"), None);
+        assert_eq!(version("minecraft:oak_sign"), Some("1.14"));
+        assert_eq!(version("minecraft:bamboo_sapling"), Some("1.14"));
+        assert_eq!(version("minecraft:bamboo_planks"), Some("1.20"));
+        assert_eq!(version("minecraft:bee_nest"), Some("1.15"));
+        assert_eq!(version("minecraft:crimson_sign"), Some("1.16"));
+        assert_eq!(version("minecraft:copper_block"), Some("1.17"));
+        assert_eq!(version("minecraft:waxed_oxidized_copper_bulb"), Some("1.21"));
+        assert_eq!(version("minecraft:reinforced_deepslate"), Some("1.19"));
+        assert_eq!(version("minecraft:cherry_hanging_sign"), Some("1.20"));
+        assert_eq!(version("minecraft:tuff"), Some("1.17"));
+        assert_eq!(version("minecraft:tuff_brick_wall"), Some("1.21"));
+        assert_eq!(version("minecraft:pale_oak_log"), Some("1.21.4"));
+        assert_eq!(version("create:cogwheel"), None);
+        assert_eq!(release_for_data_version(3465).map(|release| release.name), Some("1.20.1"));
+        assert_eq!(release_for_data_version(3500).map(|release| release.name), Some("1.20.1"));
+        assert_eq!(release_for_data_version(100), None);
+    }
+
+    #[test]
+    fn test_minimum_version() {
+        let mut builder = SchematicBuilder::new(3, 1, 1);
+        builder.set_block(0, 0, 0, Block::new("minecraft:cherry_planks")).unwrap();
+        builder.set_block(1, 0, 0, Block::new("minecraft:copper_block")).unwrap();
+        builder.set_block(2, 0, 0, Block::new("minecraft:stone")).unwrap();
+        builder.set_metadata(Metadata { data_version: Some(3120), ..Default::default() });
+        let report = minimum_version(&builder.build());
+
+        assert_eq!(report.minimum.map(|release| release.name), Some("1.20"));
+        assert_eq!(report.culprits, [("minecraft:cherry_planks".to_string(), 1)]);
+        assert_eq!(report.by_release.len(), 2);
+        assert_eq!(report.newer_than_declared, ["minecraft:cherry_planks"]);
+    }
+}
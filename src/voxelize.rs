@@ -0,0 +1,379 @@
+//! Voxelizing triangle meshes into schematics, the reverse of the OBJ export
+//!
+//! [`Mesh::load_obj`] reads an OBJ file with its MTL materials. [`VoxelGrid::from_mesh`]
+//! scales the mesh so its longest side spans the requested number of blocks and
+//! marks every voxel a triangle passes through (surface voxelization), optionally
+//! filling the closed inside as well. Each voxel gets the average color of the
+//! mesh where it was hit, taken from vertex colors (`v x y z r g b`), else the
+//! material's `map_Kd` texture, else its `Kd` diffuse color, and
+//! [`ColorMatcher`](crate::export3d::ColorMatcher) picks the closest block from a
+//! palette.
+
+use std::collections::{HashMap, VecDeque};
+use std::path::Path;
+
+use crate::{Block, SchemError, SchematicBuilder, UnifiedSchematic};
+
+/// RGB, 0-1
+pub type Color = (f32, f32, f32);
+
+/// OBJ material (`newmtl`)
+#[derive(Debug, Clone, Default)]
+pub struct Material {
+    pub name: String,
+    /// `Kd`
+    pub diffuse: Option<Color>,
+    /// `map_Kd`
+    pub texture: Option<image::RgbaImage>,
+}
+
+/// One triangle of a [`Mesh`], by index into its vertex lists
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Triangle {
+    pub positions: [usize; 3],
+    pub uvs: Option<[usize; 3]>,
+    pub material: Option<usize>,
+}
+
+/// Triangle mesh with what's needed to color it
+#[derive(Debug, Clone, Default)]
+pub struct Mesh {
+    pub positions: Vec<[f32; 3]>,
+    /// Per position, from `v x y z r g b`
+    pub colors: Vec<Option<Color>>,
+    pub uvs: Vec<[f32; 2]>,
+    pub materials: Vec<Material>,
+    pub triangles: Vec<Triangle>,
+    /// Files named by `mtllib`
+    pub material_libraries: Vec<String>,
+}
+
+impl Mesh {
+    /// Parse OBJ text; polygons are split into triangle fans, and materials
+    /// named by `usemtl` are added without colors (see [`Mesh::parse_mtl`])
+    pub fn parse_obj(text: &str) -> Result<Self, SchemError> {
+        let mut mesh = Mesh::default();
+        let mut material = None;
+        for (number, line) in text.lines().enumerate() {
+            let invalid = |what: &str| SchemError::Invalid(format!("OBJ line {}: {}", number + 1, what));
+            let mut fields = line.split_whitespace();
+            let floats = |fields: std::str::SplitWhitespace| -> Result<Vec<f32>, SchemError> {
+                fields.map(|f| f.parse::<f32>().map_err(|_| invalid("expected numbers"))).collect()
+            };
+            match fields.next() {
+                Some("v") => {
+                    let values = floats(fields)?;
+                    let [x, y, z] = values.get(..3).and_then(|v| v.try_into().ok()).ok_or_else(|| invalid("expected x y z"))?;
+                    mesh.positions.push([x, y, z]);
+                    mesh.colors.push(values.get(3..6).map(|c| (c[0], c[1], c[2])));
+                }
+                Some("vt") => {
+                    let values = floats(fields)?;
+                    let [u, v] = values.get(..2).and_then(|v| v.try_into().ok()).ok_or_else(|| invalid("expected u v"))?;
+                    mesh.uvs.push([u, v]);
+                }
+                Some("f") => {
+                    let mut corners = Vec::new();
+                    for corner in fields {
+                        let mut parts = corner.split('/');
+                        let resolve = |part: Option<&str>, len: usize| -> Result<Option<usize>, SchemError> {
+                            let Some(part) = part.filter(|p| !p.is_empty()) else { return Ok(None) };
+                            let index: i64 = part.parse().map_err(|_| invalid("bad face index"))?;
+                            // 1-based, or negative counting back from the latest vertex
+                            let resolved = if index < 0 { len as i64 + index } else { index - 1 };
+                            usize::try_from(resolved).ok().filter(|&i| i < len).map(Some).ok_or_else(|| invalid("face index out of range"))
+                        };
+                        let position = resolve(parts.next(), mesh.positions.len())?.ok_or_else(|| invalid("face without a vertex"))?;
+                        corners.push((position, resolve(parts.next(), mesh.uvs.len())?));
+                    }
+                    if corners.len() < 3 {
+                        return Err(invalid("face with fewer than 3 vertices"));
+                    }
+                    for i in 1..corners.len() - 1 {
+                        let [a, b, c] = [corners[0], corners[i], corners[i + 1]];
+                        mesh.triangles.push(Triangle {
+                            positions: [a.0, b.0, c.0],
+                            uvs: a.1.zip(b.1).zip(c.1).map(|((a, b), c)| [a, b, c]),
+                            material,
+                        });
+                    }
+                }
+                Some("usemtl") => {
+                    let name = fields.collect::<Vec<_>>().join(" ");
+                    material = Some(mesh.material_index(&name));
+                }
+                Some("mtllib") => mesh.material_libraries.extend(fields.map(str::to_string)),
+                _ => {}
+            }
+        }
+        Ok(mesh)
+    }
+
+    fn material_index(&mut self, name: &str) -> usize {
+        self.materials.iter().position(|m| m.name == name).unwrap_or_else(|| {
+            self.materials.push(Material { name: name.to_string(), ..Default::default() });
+            self.materials.len() - 1
+        })
+    }
+
+    /// Read diffuse colors and texture names from MTL text; `texture` loads a
+    /// `map_Kd` file name, or returns None to leave the material untextured
+    pub fn parse_mtl(&mut self, text: &str, mut texture: impl FnMut(&str) -> Option<image::RgbaImage>) {
+        let mut current = None;
+        for line in text.lines() {
+            let line = line.trim();
+            let (keyword, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+            let rest = rest.trim();
+            match (keyword, current) {
+                ("newmtl", _) => current = Some(self.material_index(rest)),
+                ("Kd", Some(i)) => {
+                    let values: Vec<f32> = rest.split_whitespace().filter_map(|v| v.parse().ok()).collect();
+                    if let [r, g, b] = values[..] {
+                        self.materials[i].diffuse = Some((r, g, b));
+                    }
+                }
+                // Options like -s come before the file name, which is last
+                ("map_Kd", Some(i)) => {
+                    if let Some(file) = rest.split_whitespace().last() {
+                        self.materials[i].texture = texture(file);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Load an OBJ file with the MTL files it names and their textures, all
+    /// relative to the OBJ's directory
+    pub fn load_obj(path: &Path) -> Result<Self, SchemError> {
+        let text = std::fs::read_to_string(path).map_err(|e| SchemError::model(path, e))?;
+        let mut mesh = Self::parse_obj(&text).map_err(|e| SchemError::model(path, e))?;
+        let dir = path.parent().unwrap_or(Path::new("."));
+        for library in mesh.material_libraries.clone() {
+            let mtl_path = dir.join(&library);
+            match std::fs::read_to_string(&mtl_path) {
+                Ok(mtl) => mesh.parse_mtl(&mtl, |file| match image::open(dir.join(file)) {
+                    Ok(image) => Some(image.to_rgba8()),
+                    Err(e) => {
+                        log::warn!("Can't read texture {}: {}", dir.join(file).display(), e);
+                        None
+                    }
+                }),
+                Err(e) => log::warn!("Can't read material library {}: {}", mtl_path.display(), e),
+            }
+        }
+        Ok(mesh)
+    }
+
+    /// Color of a triangle at barycentric weights `w`, if the mesh has any
+    fn color_at(&self, triangle: &Triangle, w: [f32; 3]) -> Option<Color> {
+        let mix = |values: [[f32; 3]; 3]| -> [f32; 3] {
+            [0, 1, 2].map(|c| values[0][c] * w[0] + values[1][c] * w[1] + values[2][c] * w[2])
+        };
+        let vertex_colors = triangle.positions.map(|i| self.colors[i]);
+        if let [Some(a), Some(b), Some(c)] = vertex_colors {
+            let [r, g, b] = mix([a, b, c].map(|(r, g, b)| [r, g, b]));
+            return Some((r, g, b));
+        }
+
+        let material = triangle.material.and_then(|i| self.materials.get(i))?;
+        if let (Some(texture), Some(uvs)) = (&material.texture, triangle.uvs) {
+            let [u, v, _] = mix(uvs.map(|i| [self.uvs[i][0], self.uvs[i][1], 0.0]));
+            // Textures repeat, and v counts up from the bottom row
+            let x = (u.rem_euclid(1.0) * texture.width() as f32) as u32;
+            let y = ((1.0 - v.rem_euclid(1.0)) * texture.height() as f32) as u32;
+            let pixel = texture.get_pixel(x.min(texture.width() - 1), y.min(texture.height() - 1));
+            return Some((pixel[0] as f32 / 255.0, pixel[1] as f32 / 255.0, pixel[2] as f32 / 255.0));
+        }
+        material.diffuse
+    }
+}
+
+/// Voxels hit by a mesh, with their average color where the mesh has one
+#[derive(Debug, Clone, PartialEq)]
+pub struct VoxelGrid {
+    pub width: u32,
+    pub height: u32,
+    pub length: u32,
+    /// YZX order like `UnifiedSchematic::blocks`: None for empty voxels,
+    /// Some(None) for filled voxels without a color
+    pub voxels: Vec<Option<Option<Color>>>,
+}
+
+impl VoxelGrid {
+    /// Voxelize `mesh` so its longest side spans `size` voxels; `solid` also
+    /// fills voxels the surface encloses, colored like the nearest surface voxel
+    pub fn from_mesh(mesh: &Mesh, size: u32, solid: bool) -> Result<Self, SchemError> {
+        let used = mesh.triangles.iter().flat_map(|t| t.positions).map(|i| mesh.positions[i]);
+        let (mut min, mut max) = ([f32::INFINITY; 3], [f32::NEG_INFINITY; 3]);
+        for p in used {
+            for axis in 0..3 {
+                min[axis] = min[axis].min(p[axis]);
+                max[axis] = max[axis].max(p[axis]);
+            }
+        }
+        if size == 0 || !min[0].is_finite() {
+            return Err(SchemError::Invalid("nothing to voxelize: the mesh has no faces".to_string()));
+        }
+
+        let extent = [0, 1, 2].map(|a| max[a] - min[a]);
+        let longest = extent.iter().copied().fold(0.0, f32::max);
+        let scale = if longest > 0.0 { size as f32 / longest } else { 1.0 };
+        let dims = extent.map(|e| ((e * scale).ceil() as u32).clamp(1, size));
+        let [width, height, length] = dims;
+        let volume = crate::checked_volume(width, height, length)?;
+        let index = |v: [u32; 3]| (v[1] as usize * length as usize + v[2] as usize) * width as usize + v[0] as usize;
+
+        // Sample each triangle at under half a voxel's spacing
+        let mut sums: HashMap<usize, ([f32; 3], u32)> = HashMap::new();
+        let mut filled = vec![false; volume];
+        for triangle in &mesh.triangles {
+            let corners = triangle.positions.map(|i| [0, 1, 2].map(|a| (mesh.positions[i][a] - min[a]) * scale));
+            let edge = |a: [f32; 3], b: [f32; 3]| ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)).sqrt();
+            let longest_edge = edge(corners[0], corners[1]).max(edge(corners[1], corners[2])).max(edge(corners[2], corners[0]));
+            let steps = ((longest_edge * 2.0).ceil() as u32).max(1);
+            for i in 0..=steps {
+                for j in 0..=steps - i {
+                    let (u, v) = (i as f32 / steps as f32, j as f32 / steps as f32);
+                    let w = [1.0 - u - v, u, v];
+                    let point = [0, 1, 2].map(|a| corners[0][a] * w[0] + corners[1][a] * w[1] + corners[2][a] * w[2]);
+                    let voxel = [0, 1, 2].map(|a| (point[a].max(0.0) as u32).min(dims[a] - 1));
+                    let i = index(voxel);
+                    filled[i] = true;
+                    if let Some((r, g, b)) = mesh.color_at(triangle, w) {
+                        let entry = sums.entry(i).or_insert(([0.0; 3], 0));
+                        entry.0 = [entry.0[0] + r, entry.0[1] + g, entry.0[2] + b];
+                        entry.1 += 1;
+                    }
+                }
+            }
+        }
+
+        let mut voxels: Vec<Option<Option<Color>>> = filled.iter().enumerate()
+            .map(|(i, &hit)| hit.then(|| sums.get(&i).map(|(sum, n)| (sum[0] / *n as f32, sum[1] / *n as f32, sum[2] / *n as f32))))
+            .collect();
+        let mut grid = VoxelGrid { width, height, length, voxels: Vec::new() };
+        if solid {
+            grid.fill_inside(&mut voxels);
+        }
+        grid.voxels = voxels;
+        Ok(grid)
+    }
+
+    /// Neighbors of a voxel index sharing a face
+    fn neighbors(&self, i: usize) -> impl Iterator<Item = usize> {
+        let (w, l) = (self.width as usize, self.length as usize);
+        let (x, y, z) = (i % w, i / (w * l), (i / w) % l);
+        [
+            (x > 0).then(|| i - 1),
+            (x + 1 < w).then(|| i + 1),
+            (z > 0).then(|| i - w),
+            (z + 1 < l).then(|| i + w),
+            (y > 0).then(|| i - w * l),
+            (y + 1 < self.height as usize).then(|| i + w * l),
+        ].into_iter().flatten()
+    }
+
+    /// Fill empty voxels that can't be reached from the grid's boundary, with
+    /// the color of the nearest filled voxel
+    fn fill_inside(&self, voxels: &mut [Option<Option<Color>>]) {
+        let (w, h, l) = (self.width as usize, self.height as usize, self.length as usize);
+        let on_boundary = |i: usize| {
+            let (x, y, z) = (i % w, i / (w * l), (i / w) % l);
+            x == 0 || y == 0 || z == 0 || x + 1 == w || y + 1 == h || z + 1 == l
+        };
+
+        let mut outside = vec![false; voxels.len()];
+        let mut queue: VecDeque<usize> = (0..voxels.len()).filter(|&i| voxels[i].is_none() && on_boundary(i)).collect();
+        queue.iter().for_each(|&i| outside[i] = true);
+        while let Some(i) = queue.pop_front() {
+            for n in self.neighbors(i) {
+                if !outside[n] && voxels[n].is_none() {
+                    outside[n] = true;
+                    queue.push_back(n);
+                }
+            }
+        }
+
+        // Spread surface colors inward, nearest first
+        let mut queue: VecDeque<usize> = (0..voxels.len()).filter(|&i| voxels[i].is_some()).collect();
+        while let Some(i) = queue.pop_front() {
+            for n in self.neighbors(i) {
+                if !outside[n] && voxels[n].is_none() {
+                    voxels[n] = voxels[i];
+                    queue.push_back(n);
+                }
+            }
+        }
+    }
+
+    /// Number of filled voxels
+    pub fn filled(&self) -> usize {
+        self.voxels.iter().filter(|v| v.is_some()).count()
+    }
+
+    /// Schematic with a block for every filled voxel, chosen by `block` from
+    /// the voxel's color
+    pub fn to_schematic(&self, mut block: impl FnMut(Option<Color>) -> Block) -> UnifiedSchematic {
+        let mut builder = SchematicBuilder::new(self.width, self.height, self.length);
+        let (w, l) = (self.width as usize, self.length as usize);
+        for (i, voxel) in self.voxels.iter().enumerate() {
+            if let Some(color) = voxel {
+                let (x, y, z) = ((i % w) as u32, (i / (w * l)) as u32, ((i / w) % l) as u32);
+                builder.set_block(x, y, z, block(*color)).expect("voxels are inside the schematic");
+            }
+        }
+        builder.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export3d::{BlockPalette, ColorMatcher};
+
+    /// Unit cube, red on the bottom face (vertex colors) and blue everywhere else (material)
+    const CUBE: &str = "\
+mtllib cube.mtl
+v 0 0 0 0.7 0.2 0.2
+v 1 0 0 0.7 0.2 0.2
+v 1 0 1 0.7 0.2 0.2
+v 0 0 1 0.7 0.2 0.2
+v 0 1 0
+v 1 1 0
+v 1 1 1
+v 0 1 1
+usemtl blue
+f 1 2 3 4
+f 5 8 7 6
+f 1 5 6 2
+f 2 6 7 3
+f 3 7 8 4
+f 4 8 5 1
+";
+
+    #[test]
+    fn test_voxelize_cube() {
+        let mut mesh = Mesh::parse_obj(CUBE).unwrap();
+        assert_eq!(mesh.triangles.len(), 12);
+        assert_eq!(mesh.material_libraries, ["cube.mtl"]);
+        mesh.parse_mtl("newmtl blue\nKd 0.2 0.3 0.7\n", |_| None);
+
+        let hollow = VoxelGrid::from_mesh(&mesh, 6, false).unwrap();
+        assert_eq!((hollow.width, hollow.height, hollow.length), (6, 6, 6));
+        assert_eq!(hollow.filled(), 6 * 6 * 6 - 4 * 4 * 4);
+        let solid = VoxelGrid::from_mesh(&mesh, 6, true).unwrap();
+        assert_eq!(solid.filled(), 6 * 6 * 6);
+
+        let matcher = ColorMatcher::new(BlockPalette::Wool.blocks());
+        let schem = solid.to_schematic(|color| {
+            color.and_then(|c| matcher.nearest(c)).map_or_else(|| Block::new("minecraft:stone"), |(block, _)| block.clone())
+        });
+        assert_eq!(schem.get_block(3, 0, 3).unwrap().name, "minecraft:red_wool");
+        assert_eq!(schem.get_block(3, 5, 3).unwrap().name, "minecraft:blue_wool");
+        assert_eq!(schem.solid_blocks(), 216);
+
+        assert!(Mesh::parse_obj("v 0 0 0\nf 1 2 3\n").is_err());
+        assert!(VoxelGrid::from_mesh(&Mesh::default(), 8, false).is_err());
+    }
+}
@@ -0,0 +1,75 @@
+//! wasm-bindgen entry points (enabled by the `wasm` feature)
+//!
+//! Build for the browser without the filesystem-dependent parts:
+//!
+//! ```text
+//! cargo build --target wasm32-unknown-unknown --no-default-features --features wasm
+//! wasm-bindgen --target web --out-dir pkg target/wasm32-unknown-unknown/debug/schem_tool.wasm
+//! ```
+//!
+//! ```js
+//! import init, { analyze } from "./pkg/schem_tool.js";
+//! await init();
+//! const bytes = new Uint8Array(await file.arrayBuffer());
+//! const info = analyze(bytes);
+//! console.log(info.width, info.block_counts["minecraft:stone"]);
+//! ```
+
+use std::collections::BTreeMap;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+use crate::{recipes, UnifiedSchematic};
+
+#[derive(Serialize)]
+struct Analysis {
+    format: String,
+    width: u32,
+    height: u32,
+    length: u32,
+    volume: usize,
+    solid_blocks: usize,
+    unique_blocks: usize,
+    block_entities: usize,
+    entities: usize,
+    block_counts: BTreeMap<String, usize>,
+}
+
+fn to_js<T: Serialize>(value: &T) -> Result<JsValue, JsValue> {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(JsValue::from)
+}
+
+fn parse(bytes: &[u8]) -> Result<UnifiedSchematic, JsValue> {
+    UnifiedSchematic::from_bytes(bytes).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Parse a schematic file and return its info and block counts as a plain object
+#[wasm_bindgen]
+pub fn analyze(bytes: &[u8]) -> Result<JsValue, JsValue> {
+    let schem = parse(bytes)?;
+    let analysis = Analysis {
+        format: format!("{:?}", schem.format),
+        width: schem.width,
+        height: schem.height,
+        length: schem.length,
+        volume: schem.volume(),
+        solid_blocks: schem.solid_blocks(),
+        unique_blocks: schem.unique_blocks().len(),
+        block_entities: schem.block_entities.len(),
+        entities: schem.entities.len(),
+        block_counts: schem.block_counts().into_iter().collect(),
+    };
+    to_js(&analysis)
+}
+
+/// Raw materials needed for a schematic file, as `{ material: count }`
+#[wasm_bindgen]
+pub fn materials(bytes: &[u8], stonecutter: bool) -> Result<JsValue, JsValue> {
+    let schem = parse(bytes)?;
+    let materials: BTreeMap<String, f64> =
+        recipes::calculate_materials_with_options(&schem.block_counts(), stonecutter)
+            .into_iter()
+            .collect();
+    to_js(&materials)
+}
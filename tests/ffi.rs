@@ -0,0 +1,113 @@
+//! Smoke test for the C ABI: load the cdylib the way a foreign caller would
+
+#![cfg(feature = "ffi")]
+
+use std::ffi::{c_char, CStr};
+use std::path::PathBuf;
+use libloading::{Library, Symbol};
+use schem_tool::schem::SchemWrapper;
+use schem_tool::{Block, SchematicBuilder};
+
+#[repr(C)]
+struct SchemHandle {
+    _private: [u8; 0],
+}
+
+fn cdylib_path() -> PathBuf {
+    // Integration tests run from target/<profile>/deps. `cargo test` leaves the
+    // cdylib next to them; `cargo build` also copies it one level up.
+    let exe = std::env::current_exe().unwrap();
+    let deps = exe.parent().unwrap();
+    let name = libloading::library_filename("schem_tool");
+    for dir in [deps, deps.parent().unwrap()] {
+        if dir.join(&name).exists() {
+            return dir.join(&name);
+        }
+    }
+    deps.join(&name)
+}
+
+#[test]
+fn test_dlopen_and_error_reporting() {
+    unsafe {
+        let lib = Library::new(cdylib_path()).expect("cdylib not built");
+
+        let load: Symbol<unsafe extern "C" fn(*const u8, usize) -> *mut SchemHandle> =
+            lib.get(b"schem_load").unwrap();
+        let last_code: Symbol<extern "C" fn() -> i32> = lib.get(b"schem_last_error_code").unwrap();
+        let last_message: Symbol<extern "C" fn() -> *mut c_char> = lib.get(b"schem_last_error_message").unwrap();
+        let string_free: Symbol<unsafe extern "C" fn(*mut c_char)> = lib.get(b"schem_string_free").unwrap();
+        let counts_json: Symbol<unsafe extern "C" fn(*const SchemHandle) -> *mut c_char> =
+            lib.get(b"schem_block_counts_json").unwrap();
+        let free: Symbol<unsafe extern "C" fn(*mut SchemHandle)> = lib.get(b"schem_free").unwrap();
+
+        // NULL input is rejected, not dereferenced
+        assert!(load(std::ptr::null(), 0).is_null());
+        assert_eq!(last_code(), 1);
+
+        // Garbage bytes fail cleanly with a message
+        let junk = [0u8, 1, 2, 3];
+        assert!(load(junk.as_ptr(), junk.len()).is_null());
+        assert_ne!(last_code(), 0);
+        let msg = last_message();
+        assert!(!msg.is_null());
+        assert!(!CStr::from_ptr(msg).to_bytes().is_empty());
+        string_free(msg);
+
+        assert!(counts_json(std::ptr::null()).is_null());
+        assert_eq!(last_code(), 1);
+
+        // Freeing NULL is a no-op
+        free(std::ptr::null_mut());
+        string_free(std::ptr::null_mut());
+    }
+}
+
+#[test]
+fn test_load_and_query() {
+    let mut builder = SchematicBuilder::new(3, 2, 1);
+    builder.fill((0, 0, 0), (2, 0, 0), Block::new("minecraft:stone")).unwrap();
+    builder.set_block(1, 1, 0, Block::new("minecraft:oak_planks")).unwrap();
+    let bytes = SchemWrapper::from_unified(&builder.build()).unwrap().to_bytes().unwrap();
+
+    unsafe {
+        let lib = Library::new(cdylib_path()).expect("cdylib not built");
+        let load: Symbol<unsafe extern "C" fn(*const u8, usize) -> *mut SchemHandle> =
+            lib.get(b"schem_load").unwrap();
+        let dimensions: Symbol<unsafe extern "C" fn(*const SchemHandle, *mut u32, *mut u32, *mut u32) -> i32> =
+            lib.get(b"schem_dimensions").unwrap();
+        let counts_json: Symbol<unsafe extern "C" fn(*const SchemHandle) -> *mut c_char> =
+            lib.get(b"schem_block_counts_json").unwrap();
+        let materials_json: Symbol<unsafe extern "C" fn(*const SchemHandle, bool) -> *mut c_char> =
+            lib.get(b"schem_materials_json").unwrap();
+        let last_code: Symbol<extern "C" fn() -> i32> = lib.get(b"schem_last_error_code").unwrap();
+        let string_free: Symbol<unsafe extern "C" fn(*mut c_char)> = lib.get(b"schem_string_free").unwrap();
+        let free: Symbol<unsafe extern "C" fn(*mut SchemHandle)> = lib.get(b"schem_free").unwrap();
+        let take_json = |s: *mut c_char| {
+            assert!(!s.is_null());
+            let json: serde_json::Value = serde_json::from_slice(CStr::from_ptr(s).to_bytes()).unwrap();
+            string_free(s);
+            json
+        };
+
+        let handle = load(bytes.as_ptr(), bytes.len());
+        assert!(!handle.is_null());
+        assert_eq!(last_code(), 0);
+
+        let (mut width, mut height, mut length) = (0, 0, 0);
+        assert_eq!(dimensions(handle, &mut width, &mut height, &mut length), 0);
+        assert_eq!((width, height, length), (3, 2, 1));
+        assert_eq!(dimensions(handle, std::ptr::null_mut(), &mut height, &mut length), 1);
+
+        assert_eq!(
+            take_json(counts_json(handle)),
+            serde_json::json!({ "minecraft:air": 2, "minecraft:oak_planks": 1, "minecraft:stone": 3 })
+        );
+        let materials = take_json(materials_json(handle, false));
+        assert_eq!(materials["minecraft:oak_log"], 0.25);
+        assert_eq!(materials["minecraft:stone"], 3.0);
+        assert_eq!(materials.as_object().unwrap().len(), 2);
+
+        free(handle);
+    }
+}
@@ -0,0 +1,291 @@
+//! Golden-file tests for the OBJ/MTL and GLB exporters
+//!
+//! Each exporter configuration writes a small fixture and compares the
+//! normalized output with `tests/golden/`. After an intended output change,
+//! regenerate the files with `BLESS=1 cargo test --test golden` and review the
+//! diff.
+
+#![cfg(feature = "fs")]
+
+use std::path::{Path, PathBuf};
+
+use schem_tool::block::BlockState;
+use schem_tool::export3d::{self, Explode, ExportOptions};
+use schem_tool::export_gltf;
+use schem_tool::textures::TextureManager;
+use schem_tool::{Block, BlockEntity, SchematicBuilder, UnifiedSchematic};
+
+/// Stone floor with stairs, a slab, glass, water, a sign and a fence
+fn fixture() -> UnifiedSchematic {
+    let with_props = |name: &str, props: &[(&str, &str)]| {
+        let properties = props.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        Block::with_state(name, BlockState { properties })
+    };
+
+    let mut builder = SchematicBuilder::new(4, 2, 3);
+    builder.fill((0, 0, 0), (3, 0, 2), Block::new("minecraft:stone")).unwrap();
+    builder.set_block(0, 1, 0, with_props("minecraft:oak_stairs", &[("facing", "east"), ("half", "bottom"), ("shape", "straight")])).unwrap();
+    builder.set_block(1, 1, 0, with_props("minecraft:stone_slab", &[("type", "bottom")])).unwrap();
+    builder.set_block(2, 1, 0, Block::new("minecraft:glass")).unwrap();
+    builder.set_block(3, 1, 0, with_props("minecraft:water", &[("level", "0")])).unwrap();
+    builder.set_block(0, 1, 2, with_props("minecraft:oak_sign", &[("rotation", "8")])).unwrap();
+    builder.set_block(3, 1, 2, with_props("minecraft:oak_fence", &[("east", "false"), ("north", "false"), ("south", "false"), ("west", "false")])).unwrap();
+    builder.add_block_entity(BlockEntity {
+        id: "minecraft:sign".to_string(),
+        pos: (0, 1, 2),
+        ..Default::default()
+    }).unwrap();
+    builder.build()
+}
+
+/// Water, lava and magma, whose textures are animated strips
+fn animated_fixture() -> UnifiedSchematic {
+    let mut builder = SchematicBuilder::new(3, 1, 1);
+    for (x, name) in ["minecraft:water", "minecraft:lava", "minecraft:magma_block"].into_iter().enumerate() {
+        builder.set_block(x as u32, 0, 0, Block::new(name)).unwrap();
+    }
+    builder.build()
+}
+
+/// 2x2 frames stacked into strips, each frame one gray level, with their mcmeta:
+/// water plays in strip order, lava in reverse and magma interpolates
+fn write_animated_textures(dir: &Path) {
+    let strips: [(&str, u8, &str); 3] = [
+        ("water_still", 4, r#"{"animation": {"frametime": 2}}"#),
+        ("lava_still", 4, r#"{"animation": {"frametime": 2, "frames": [3, 2, {"index": 1, "time": 4}, 0]}}"#),
+        ("magma", 3, r#"{"animation": {"frametime": 8, "interpolate": true}}"#),
+    ];
+    for (name, frames, mcmeta) in strips {
+        let strip = image::RgbaImage::from_fn(2, 2 * frames as u32, |_, y| {
+            let level = 40 + 60 * (y / 2) as u8;
+            image::Rgba([level, level, level, 255])
+        });
+        strip.save(dir.join(format!("{}.png", name))).unwrap();
+        std::fs::write(dir.join(format!("{}.png.mcmeta", name)), mcmeta).unwrap();
+    }
+}
+
+fn golden_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("golden")
+}
+
+/// Scratch directory for one export, removed when dropped
+struct Scratch(PathBuf);
+
+impl Scratch {
+    fn new(name: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!("schem-tool-golden-{}-{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        Scratch(dir)
+    }
+}
+
+impl Drop for Scratch {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Compare with the golden file, or overwrite it when `BLESS` is set
+fn check_golden(name: &str, actual: &str) {
+    let path = golden_dir().join(name);
+    if std::env::var_os("BLESS").is_some() {
+        std::fs::create_dir_all(golden_dir()).unwrap();
+        std::fs::write(&path, actual).unwrap();
+        return;
+    }
+    let expected = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("{}: {} (run with BLESS=1 to create it)", path.display(), e));
+    if expected != actual {
+        let line = expected.lines().zip(actual.lines()).position(|(a, b)| a != b)
+            .unwrap_or_else(|| expected.lines().count().min(actual.lines().count()));
+        panic!(
+            "{} differs from the export at line {} (run with BLESS=1 to accept):\nexpected: {:?}\n  actual: {:?}",
+            path.display(), line + 1, expected.lines().nth(line), actual.lines().nth(line),
+        );
+    }
+}
+
+/// OBJ without comments and the `mtllib` line, which only name the generator and file
+fn normalize_obj(obj: &str) -> String {
+    obj.lines()
+        .filter(|line| !line.starts_with('#') && !line.starts_with("mtllib "))
+        .map(|line| format!("{}\n", line))
+        .collect()
+}
+
+/// MTL materials without comments, one blank line between them
+fn normalize_mtl(mtl: &str) -> String {
+    let mut materials: Vec<String> = Vec::new();
+    for line in mtl.lines().filter(|line| !line.starts_with('#') && !line.trim().is_empty()) {
+        if line.starts_with("newmtl ") || materials.is_empty() {
+            materials.push(String::new());
+        }
+        let material = materials.last_mut().unwrap();
+        material.push_str(line);
+        material.push('\n');
+    }
+    materials.join("\n")
+}
+
+fn check_obj(name: &str, export: impl FnOnce(&Path)) {
+    let scratch = Scratch::new(name);
+    let obj_path = scratch.0.join("model.obj");
+    export(&obj_path);
+
+    let obj = std::fs::read_to_string(&obj_path).unwrap();
+    let mtl = std::fs::read_to_string(obj_path.with_extension("mtl")).unwrap();
+    check_golden(&format!("{}.obj", name), &normalize_obj(&obj));
+    check_golden(&format!("{}.mtl", name), &normalize_mtl(&mtl));
+}
+
+/// FNV-1a, enough to notice any change in the binary buffer
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &b| (hash ^ b as u64).wrapping_mul(0x0100_0000_01b3))
+}
+
+/// Structure of a GLB: meshes, materials and accessors from the JSON chunk,
+/// and the binary chunk's length and hash
+fn summarize_glb(glb: &[u8]) -> String {
+    let u32_at = |offset: usize| u32::from_le_bytes(glb[offset..offset + 4].try_into().unwrap()) as usize;
+    assert_eq!(&glb[0..4], b"glTF", "not a GLB file");
+    assert_eq!(u32_at(8), glb.len(), "GLB length header");
+
+    let json_len = u32_at(12);
+    assert_eq!(&glb[16..20], b"JSON");
+    let json: serde_json::Value = serde_json::from_slice(&glb[20..20 + json_len]).unwrap();
+    let bin_start = 20 + json_len;
+    let bin = if bin_start < glb.len() {
+        assert_eq!(&glb[bin_start + 4..bin_start + 8], b"BIN\0");
+        &glb[bin_start + 8..bin_start + 8 + u32_at(bin_start)]
+    } else {
+        &[]
+    };
+
+    let list = |key: &str| json[key].as_array().cloned().unwrap_or_default();
+    let mut out = String::new();
+    for material in list("materials") {
+        out.push_str(&format!(
+            "material {} alpha={} color={}\n",
+            material["name"].as_str().unwrap_or("?"),
+            material["alphaMode"].as_str().unwrap_or("OPAQUE"),
+            material["pbrMetallicRoughness"]["baseColorFactor"],
+        ));
+    }
+    for mesh in list("meshes") {
+        let primitives: Vec<String> = mesh["primitives"].as_array().unwrap().iter()
+            .map(|p| format!("material={} indices={}", p["material"], p["indices"]))
+            .collect();
+        out.push_str(&format!("mesh {} [{}]\n", mesh["name"].as_str().unwrap_or("?"), primitives.join("; ")));
+    }
+    for accessor in list("accessors") {
+        out.push_str(&format!(
+            "accessor {} x{} component={} min={} max={}\n",
+            accessor["type"].as_str().unwrap_or("?"), accessor["count"], accessor["componentType"],
+            accessor["min"], accessor["max"],
+        ));
+    }
+    for image in list("images") {
+        let view = &json["bufferViews"][image["bufferView"].as_u64().unwrap() as usize];
+        let start = view["byteOffset"].as_u64().unwrap_or(0) as usize;
+        let png = &bin[start..start + view["byteLength"].as_u64().unwrap() as usize];
+        out.push_str(&format!("image {}\n", summarize_png(png)));
+    }
+    out.push_str(&format!("nodes {}\n", list("nodes").len()));
+    out.push_str(&format!("buffer {} bytes fnv1a={:016x}\n", bin.len(), fnv1a(bin)));
+    out
+}
+
+/// Size and pixels of a small PNG
+fn summarize_png(png: &[u8]) -> String {
+    let img = image::load_from_memory(png).unwrap().to_rgba8();
+    let pixels: Vec<String> = img.pixels().map(|p| format!("{:02x}{:02x}{:02x}{:02x}", p[0], p[1], p[2], p[3])).collect();
+    format!("{}x{} {}", img.width(), img.height(), pixels.join(" "))
+}
+
+fn check_glb(name: &str, hollow: bool, options: &ExportOptions) {
+    let scratch = Scratch::new(name);
+    let path = scratch.0.join("model.glb");
+    export_gltf::export_glb(&fixture(), &path, None, None, hollow, None, options).unwrap();
+    check_golden(&format!("{}.glb.txt", name), &summarize_glb(&std::fs::read(&path).unwrap()));
+}
+
+#[test]
+fn test_obj_naive() {
+    check_obj("obj_naive", |path| export3d::export_obj(&fixture(), path, false, true, &ExportOptions::new()).unwrap());
+}
+
+#[test]
+fn test_obj_hollow() {
+    check_obj("obj_hollow", |path| export3d::export_obj(&fixture(), path, true, true, &ExportOptions::new()).unwrap());
+}
+
+#[test]
+fn test_obj_greedy() {
+    check_obj("obj_greedy", |path| export3d::export_obj_greedy(&fixture(), path, None, &ExportOptions::new()).unwrap());
+}
+
+#[test]
+fn test_glb() {
+    check_glb("glb", false, &ExportOptions::new());
+}
+
+#[test]
+fn test_glb_hollow() {
+    check_glb("glb_hollow", true, &ExportOptions::new());
+}
+
+#[test]
+fn test_glb_explode() {
+    // Same materials and accessor counts as glb_hollow, only the positions move
+    check_glb("glb_explode", true, &ExportOptions::new().with_explode(Explode::new(2.0)));
+}
+
+#[test]
+fn test_animated_textures() {
+    let scratch = Scratch::new("animated");
+    let texture_dir = scratch.0.join("textures-in");
+    std::fs::create_dir_all(&texture_dir).unwrap();
+    write_animated_textures(&texture_dir);
+
+    // OBJ textures hold the first frame, the GLB the one asked for
+    let textures = TextureManager::new(texture_dir.clone());
+    let obj_path = scratch.0.join("model.obj");
+    export3d::export_obj_with_textures(&animated_fixture(), &obj_path, false, true, Some(&textures), &ExportOptions::new()).unwrap();
+    let mut written: Vec<PathBuf> = std::fs::read_dir(scratch.0.join("textures")).unwrap().flatten().map(|e| e.path()).collect();
+    written.sort();
+    let summary: String = written.iter()
+        .map(|path| format!("{} {}\n", path.file_name().unwrap().to_string_lossy(), summarize_png(&std::fs::read(path).unwrap())))
+        .collect();
+    check_golden("animated_obj.txt", &summary);
+
+    let textures = TextureManager::new(texture_dir).with_anim_frame(1);
+    let glb_path = scratch.0.join("model.glb");
+    export_gltf::export_glb(&animated_fixture(), &glb_path, None, Some(&textures), false, None, &ExportOptions::new()).unwrap();
+    check_golden("animated.glb.txt", &summarize_glb(&std::fs::read(&glb_path).unwrap()));
+}
+
+#[test]
+fn test_exports_are_reproducible() {
+    let scratch = Scratch::new("reproducible");
+    let texture_dir = scratch.0.join("textures-in");
+    std::fs::create_dir_all(&texture_dir).unwrap();
+    write_animated_textures(&texture_dir);
+    let textures = TextureManager::new(texture_dir);
+
+    const FILES: [&str; 5] = ["naive.obj", "naive.mtl", "greedy.obj", "greedy.mtl", "model.glb"];
+    // Each export gets fresh hash maps, so two runs would disagree on any hash order
+    let export = |run: &str| {
+        let dir = scratch.0.join(run);
+        std::fs::create_dir_all(&dir).unwrap();
+        export3d::export_obj_with_textures(&fixture(), dir.join("naive.obj"), false, true, Some(&textures), &ExportOptions::new()).unwrap();
+        export3d::export_obj_greedy(&fixture(), dir.join("greedy.obj"), Some(&textures), &ExportOptions::new()).unwrap();
+        export_gltf::export_glb(&fixture(), dir.join("model.glb"), None, Some(&textures), false, None, &ExportOptions::new()).unwrap();
+        FILES.map(|name| std::fs::read(dir.join(name)).unwrap())
+    };
+    let first = export("first");
+    let second = export("second");
+    for (name, (a, b)) in FILES.iter().zip(first.iter().zip(&second)) {
+        assert!(a == b, "{} differs between two exports", name);
+    }
+}